@@ -45,11 +45,15 @@ use bifrost_salp_rpc::{SalpRpc, SalpRpcApiServer};
 use bifrost_salp_rpc_runtime_api::SalpRuntimeApi;
 use bifrost_stable_pool_rpc::{StablePoolRpc, StablePoolRpcApiServer};
 use bifrost_stable_pool_rpc_runtime_api::StablePoolRuntimeApi;
+use bifrost_swap_router_rpc::{SwapRouterRpc, SwapRouterRpcApiServer};
+use bifrost_swap_router_rpc_runtime_api::SwapRouterRuntimeApi;
 use bifrost_vtoken_minting_rpc::{VtokenMintingRpc, VtokenMintingRpcApiServer};
 use bifrost_vtoken_minting_rpc_runtime_api::VtokenMintingRuntimeApi;
 use futures::channel::mpsc;
 use lend_market_rpc::{LendMarket, LendMarketApiServer};
 use lend_market_rpc_runtime_api::LendMarketApi;
+use leverage_staking_rpc::{LeverageStaking, LeverageStakingApiServer};
+use leverage_staking_rpc_runtime_api::LeverageStakingApi;
 use pallet_transaction_payment_rpc::{TransactionPayment, TransactionPaymentApiServer};
 use sc_consensus_manual_seal::rpc::{EngineCommand, ManualSeal, ManualSealApiServer};
 use sc_rpc_api::DenyUnsafe;
@@ -109,7 +113,9 @@ where
 	C::Api: FeeRuntimeApi<Block, AccountId>,
 	C::Api: SalpRuntimeApi<Block, ParaId, AccountId>,
 	C::Api: StablePoolRuntimeApi<Block>,
+	C::Api: SwapRouterRuntimeApi<Block>,
 	C::Api: LendMarketApi<Block, AccountId, Balance>,
+	C::Api: LeverageStakingApi<Block, AccountId, Balance>,
 	C::Api: VtokenMintingRuntimeApi<Block, CurrencyId, Balance>,
 	C::Api: ZenlinkProtocolRuntimeApi<Block, AccountId, AssetId>,
 	C::Api:
@@ -133,7 +139,9 @@ where
 	module.merge(ZenlinkProtocol::new(client.clone()).into_rpc())?;
 	module.merge(StableAmm::new(client.clone()).into_rpc())?;
 	module.merge(StablePoolRpc::new(client.clone()).into_rpc())?;
+	module.merge(SwapRouterRpc::new(client.clone()).into_rpc())?;
 	module.merge(LendMarket::new(client.clone()).into_rpc())?;
+	module.merge(LeverageStaking::new(client.clone()).into_rpc())?;
 	module.merge(VtokenMintingRpc::new(client).into_rpc())?;
 
 	Ok(module)
@@ -158,9 +166,11 @@ where
 	C::Api: SalpRuntimeApi<Block, ParaId, AccountId>,
 	C::Api: BbBNCRuntimeApi<Block, AccountId>,
 	C::Api: LendMarketApi<Block, AccountId, Balance>,
+	C::Api: LeverageStakingApi<Block, AccountId, Balance>,
 	C::Api: VtokenMintingRuntimeApi<Block, CurrencyId, Balance>,
 	C::Api: ZenlinkProtocolRuntimeApi<Block, AccountId, AssetId>,
 	C::Api: StablePoolRuntimeApi<Block>,
+	C::Api: SwapRouterRuntimeApi<Block>,
 	C::Api: BlockBuilder<Block>,
 	P: TransactionPool + Sync + Send + 'static,
 {
@@ -181,7 +191,9 @@ where
 	module.merge(BbBNCRpc::new(client.clone()).into_rpc())?;
 	module.merge(ZenlinkProtocol::new(client.clone()).into_rpc())?;
 	module.merge(StablePoolRpc::new(client.clone()).into_rpc())?;
+	module.merge(SwapRouterRpc::new(client.clone()).into_rpc())?;
 	module.merge(LendMarket::new(client.clone()).into_rpc())?;
+	module.merge(LeverageStaking::new(client.clone()).into_rpc())?;
 	module.merge(VtokenMintingRpc::new(client).into_rpc())?;
 
 	if let Some(command_sink) = command_sink {
@@ -41,7 +41,7 @@ use frame_support::{
 };
 use frame_system::{EnsureRoot, EnsureSignedBy};
 use orml_traits::{location::RelativeReserveProvider, parameter_type_with_key};
-use sp_core::ConstU32;
+use sp_core::{ConstU32, ConstU64};
 use sp_runtime::{
 	traits::{ConvertInto, IdentityLookup},
 	AccountId32, BuildStorage,
@@ -328,6 +328,7 @@ impl bifrost_slp::Config for Runtime {
 	type AssetIdMaps = AssetIdMaps<Runtime>;
 	type TreasuryAccount = TreasuryAccount;
 	type BlockNumberProvider = System;
+	type XcmQueryTimeout = ConstU64<1000>;
 }
 
 parameter_types! {
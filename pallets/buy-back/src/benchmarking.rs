@@ -99,6 +99,21 @@ mod benchmarks {
 		Ok(())
 	}
 
+	#[benchmark]
+	fn set_swap_chunk_size() -> Result<(), BenchmarkError> {
+		let origin =
+			T::ControlOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+
+		#[extrinsic_call]
+		_(
+			origin as <T as frame_system::Config>::RuntimeOrigin,
+			VDOT,
+			Some(BalanceOf::<T>::unique_saturated_from(1_000_000u128)),
+		);
+
+		Ok(())
+	}
+
 	#[benchmark]
 	fn on_initialize() -> Result<(), BenchmarkError> {
 		let origin =
@@ -28,16 +28,19 @@ mod tests;
 #[cfg(feature = "runtime-benchmarks")]
 mod benchmarking;
 
+pub mod migration;
 pub mod weights;
 
 use bb_bnc::{BbBNCInterface, BB_BNC_SYSTEM_POOL_ID};
-use bifrost_primitives::{currency::BNC, CurrencyId, CurrencyIdRegister, TryConvertFrom};
+use bifrost_primitives::{
+	currency::BNC, CurrencyId, CurrencyIdRegister, OraclePriceProvider, Price, TryConvertFrom,
+};
 use cumulus_primitives_core::ParaId;
 use frame_support::{
 	pallet_prelude::*,
 	sp_runtime::{
 		traits::{AccountIdConversion, BlockNumberProvider, One, Zero},
-		Permill, SaturatedConversion, Saturating,
+		FixedPointNumber, FixedU128, Permill, SaturatedConversion, Saturating,
 	},
 	transactional, PalletId,
 };
@@ -60,8 +63,11 @@ type BalanceOf<T> = <<T as Config>::MultiCurrency as MultiCurrency<AccountIdOf<T
 pub mod pallet {
 	use super::*;
 
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
 	#[pallet::pallet]
 	#[pallet::without_storage_info]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
 
 	#[pallet::config]
@@ -98,6 +104,9 @@ pub mod pallet {
 
 		/// The current block number provider.
 		type BlockNumberProvider: BlockNumberProvider<BlockNumber = BlockNumberFor<Self>>;
+
+		/// The oracle price feeder, used to compute the minimum output of swaps.
+		type OraclePriceProvider: OraclePriceProvider;
 	}
 
 	#[pallet::event]
@@ -136,15 +145,18 @@ pub mod pallet {
 			currency_id: CurrencyIdOf<T>,
 			block_number: BlockNumberFor<T>,
 		},
-		/// A failed call of the `SetSwapOutMin` extrinsic will create this event.
-		SetSwapOutMinFailed {
+		/// A swap to BNC was executed. The prices are in BNC per currency, in their smallest units.
+		Swapped {
 			currency_id: CurrencyIdOf<T>,
-			block_number: BlockNumberFor<T>,
+			amount_in: BalanceOf<T>,
+			amount_out: BalanceOf<T>,
+			executed_price: Price,
+			oracle_price: Price,
 		},
-		/// A successful call of the `SetSwapOutMin` extrinsic will create this event.
-		SetSwapOutMinSuccess {
+		/// A successful call of the `SetSwapChunkSize` extrinsic will create this event.
+		SwapChunkSizeSet {
 			currency_id: CurrencyIdOf<T>,
-			block_number: BlockNumberFor<T>,
+			chunk_size: Option<BalanceOf<T>>,
 		},
 	}
 
@@ -160,41 +172,49 @@ pub mod pallet {
 		ZeroDuration,
 		/// Field min_swap_value can't be zero.
 		ZeroMinSwapValue,
+		/// The chunk size of swaps can't be zero.
+		ZeroChunkSize,
+		/// The oracle has no price for the swap.
+		OraclePriceNotFound,
 	}
 
 	#[pallet::storage]
 	pub type Infos<T: Config> =
 		StorageMap<_, Twox64Concat, CurrencyIdOf<T>, Info<BalanceOf<T>, BlockNumberFor<T>>>;
 
+	/// The most of a currency swapped in one block, the rest of a buyback is swapped in the
+	/// following blocks.
 	#[pallet::storage]
-	pub type SwapOutMin<T: Config> = StorageMap<_, Twox64Concat, CurrencyIdOf<T>, u128>;
+	pub type SwapChunkSize<T: Config> = StorageMap<_, Twox64Concat, CurrencyIdOf<T>, BalanceOf<T>>;
 
+	/// The amount of a currency left to swap in the current buyback.
 	#[pallet::storage]
-	pub type AddLiquiditySwapOutMin<T: Config> = StorageMap<_, Twox64Concat, CurrencyIdOf<T>, u128>;
+	pub type PendingBuyBack<T: Config> =
+		StorageMap<_, Twox64Concat, CurrencyIdOf<T>, BalanceOf<T>, ValueQuery>;
 
 	/// Information on buybacks and add liquidity
 	#[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo)]
 	pub struct Info<BalanceOf, BlockNumberFor> {
 		/// The minimum value of the token to be swapped.
-		min_swap_value: BalanceOf,
+		pub min_swap_value: BalanceOf,
 		/// Whether to automatically add liquidity and buy back.
-		if_auto: bool,
+		pub if_auto: bool,
 		/// The proportion of the token to be added to the liquidity pool.
-		proportion: Permill,
+		pub proportion: Permill,
 		/// The duration of the buyback.
-		buyback_duration: BlockNumberFor,
+		pub buyback_duration: BlockNumberFor,
 		/// The last time the buyback was executed.
-		last_buyback: BlockNumberFor,
+		pub last_buyback: BlockNumberFor,
 		/// The end block of the last buyback cycle.
-		last_buyback_cycle: BlockNumberFor,
+		pub last_buyback_cycle: BlockNumberFor,
 		/// The duration of adding liquidity.
-		add_liquidity_duration: BlockNumberFor,
+		pub add_liquidity_duration: BlockNumberFor,
 		/// The last time liquidity was added.
-		last_add_liquidity: BlockNumberFor,
+		pub last_add_liquidity: BlockNumberFor,
 		/// The destruction ratio of BNC.
-		destruction_ratio: Option<Permill>,
-		/// The bias of the token value to be swapped.
-		bias: Permill,
+		pub destruction_ratio: Option<Permill>,
+		/// The maximum slippage of the swap output from the oracle amount.
+		pub max_slippage: Permill,
 	}
 
 	#[pallet::hooks]
@@ -208,127 +228,76 @@ pub mod pallet {
 					continue;
 				}
 
-				match info.last_add_liquidity + info.add_liquidity_duration {
-					target_block if target_block.saturating_sub(One::one()) == n => {
-						if let Some(e) = Self::set_add_liquidity_swap_out_min(
-							&liquidity_address,
+				if info.last_add_liquidity + info.add_liquidity_duration == n {
+					if let Some(e) =
+						Self::add_liquidity(&liquidity_address, currency_id, &info).err()
+					{
+						log::error!(
+							target: "buy-back::add_liquidity",
+							"Received invalid justification for {:?}",
+							e,
+						);
+						Self::deposit_event(Event::AddLiquidityFailed {
 							currency_id,
-							&info,
-						)
-						.err()
-						{
-							log::error!(
-								target: "buy-back::set_add_liquidity_swap_out_min",
-								"Received invalid justification for {:?}",
-								e,
-							);
-							Self::deposit_event(Event::SetSwapOutMinFailed {
-								currency_id,
-								block_number: n,
-							});
-						} else {
-							Self::deposit_event(Event::SetSwapOutMinSuccess {
-								currency_id,
-								block_number: n,
-							});
-						}
-					}
-					target_block if target_block == n => {
-						if let Some(swap_out_min) = AddLiquiditySwapOutMin::<T>::get(currency_id) {
-							if let Some(e) = Self::add_liquidity(
-								&liquidity_address,
-								currency_id,
-								&info,
-								swap_out_min,
-							)
-							.err()
-							{
-								log::error!(
-									target: "buy-back::add_liquidity",
-									"Received invalid justification for {:?}",
-									e,
-								);
-								Self::deposit_event(Event::AddLiquidityFailed {
-									currency_id,
-									block_number: n,
-								});
-							} else {
-								Self::deposit_event(Event::AddLiquiditySuccess {
-									currency_id,
-									block_number: n,
-								});
-							}
-							info.last_add_liquidity =
-								info.last_add_liquidity + info.add_liquidity_duration;
-							Infos::<T>::insert(currency_id, info.clone());
-							AddLiquiditySwapOutMin::<T>::remove(currency_id);
-						}
+							block_number: n,
+						});
+					} else {
+						Self::deposit_event(Event::AddLiquiditySuccess {
+							currency_id,
+							block_number: n,
+						});
 					}
-					_ => (),
+					info.last_add_liquidity = info.last_add_liquidity + info.add_liquidity_duration;
+					Infos::<T>::insert(currency_id, info.clone());
+				}
+
+				// Start a new buyback once the previous period has ended.
+				if info.last_buyback_cycle < n
+					&& Self::get_target_block(info.last_buyback, info.buyback_duration)
+						== n.saturating_sub(info.last_buyback_cycle)
+							.saturated_into::<u32>()
+							.saturating_sub(One::one())
+				{
+					PendingBuyBack::<T>::mutate(currency_id, |pending| {
+						*pending = pending.saturating_add(info.min_swap_value)
+					});
+					info.last_buyback_cycle = info
+						.last_buyback_cycle
+						.saturating_add(info.buyback_duration);
+					info.last_buyback = n;
+					Infos::<T>::insert(currency_id, info.clone());
 				}
 
-				// If the previous period has not ended, continue with the next currency.
-				if info.last_buyback_cycle >= n {
+				// Swap the next chunk of the pending buyback.
+				let pending = PendingBuyBack::<T>::get(currency_id);
+				if pending.is_zero() {
 					continue;
 				}
-				match Self::get_target_block(info.last_buyback, info.buyback_duration) {
-					target_block
-						if target_block
-							== n.saturating_sub(info.last_buyback_cycle)
-								.saturated_into::<u32>() =>
-					{
-						if let Some(e) = Self::set_swap_out_min(currency_id, &info).err() {
-							log::error!(
-								target: "buy-back::set_swap_out_min",
-								"Received invalid justification for {:?}",
-								e,
-							);
-							Self::deposit_event(Event::SetSwapOutMinFailed {
-								currency_id,
-								block_number: n,
-							});
-						} else {
-							Self::deposit_event(Event::SetSwapOutMinSuccess {
-								currency_id,
-								block_number: n,
-							});
-						}
-					}
-					target_block
-						if target_block
-							== n.saturating_sub(info.last_buyback_cycle)
-								.saturated_into::<u32>()
-								.saturating_sub(One::one()) =>
-					{
-						if let Some(swap_out_min) = SwapOutMin::<T>::get(currency_id) {
-							if let Some(e) =
-								Self::buy_back(&buyback_address, currency_id, &info, swap_out_min)
-									.err()
-							{
-								log::error!(
-									target: "buy-back::buy_back",
-									"Received invalid justification for {:?}",
-									e,
-								);
-								Self::deposit_event(Event::BuyBackFailed {
-									currency_id,
-									block_number: n,
-								});
-							} else {
-								Self::deposit_event(Event::BuyBackSuccess {
-									currency_id,
-									block_number: n,
-								});
-							}
-							info.last_buyback_cycle = info
-								.last_buyback_cycle
-								.saturating_add(info.buyback_duration);
-							info.last_buyback = n;
-							Infos::<T>::insert(currency_id, info);
-							SwapOutMin::<T>::remove(currency_id);
-						}
-					}
-					_ => (),
+				let amount = SwapChunkSize::<T>::get(currency_id)
+					.map_or(pending, |chunk_size| chunk_size.min(pending));
+				if let Some(e) = Self::buy_back(&buyback_address, currency_id, &info, amount).err()
+				{
+					log::error!(
+						target: "buy-back::buy_back",
+						"Received invalid justification for {:?}",
+						e,
+					);
+					Self::deposit_event(Event::BuyBackFailed {
+						currency_id,
+						block_number: n,
+					});
+				} else {
+					Self::deposit_event(Event::BuyBackSuccess {
+						currency_id,
+						block_number: n,
+					});
+				}
+				// A failed chunk is dropped, the rest of the buyback goes on in the next blocks.
+				let remaining = pending.saturating_sub(amount);
+				if remaining.is_zero() {
+					PendingBuyBack::<T>::remove(currency_id);
+				} else {
+					PendingBuyBack::<T>::insert(currency_id, remaining);
 				}
 			}
 			T::WeightInfo::on_initialize()
@@ -349,7 +318,7 @@ pub mod pallet {
 			add_liquidity_duration: BlockNumberFor<T>,
 			if_auto: bool,
 			destruction_ratio: Option<Permill>,
-			bias: Permill,
+			max_slippage: Permill,
 		) -> DispatchResult {
 			T::ControlOrigin::ensure_origin(origin)?;
 
@@ -373,7 +342,7 @@ pub mod pallet {
 				add_liquidity_duration,
 				last_add_liquidity: now,
 				destruction_ratio,
-				bias,
+				max_slippage,
 			};
 			Infos::<T>::insert(currency_id, info.clone());
 
@@ -420,11 +389,36 @@ pub mod pallet {
 				Error::<T>::CurrencyIdNotExists
 			);
 			Infos::<T>::remove(currency_id);
+			PendingBuyBack::<T>::remove(currency_id);
 
 			Self::deposit_event(Event::Removed { currency_id });
 
 			Ok(())
 		}
+
+		/// Set the most of a currency swapped in one block, `None` to swap a buyback at once.
+		#[pallet::call_index(3)]
+		#[pallet::weight(T::WeightInfo::set_swap_chunk_size())]
+		pub fn set_swap_chunk_size(
+			origin: OriginFor<T>,
+			currency_id: CurrencyIdOf<T>,
+			chunk_size: Option<BalanceOf<T>>,
+		) -> DispatchResult {
+			T::ControlOrigin::ensure_origin(origin)?;
+
+			Self::check_currency_id(currency_id)?;
+			if let Some(chunk_size) = chunk_size {
+				ensure!(chunk_size > Zero::zero(), Error::<T>::ZeroChunkSize);
+			}
+			SwapChunkSize::<T>::set(currency_id, chunk_size);
+
+			Self::deposit_event(Event::SwapChunkSizeSet {
+				currency_id,
+				chunk_size,
+			});
+
+			Ok(())
+		}
 	}
 
 	impl<T: Config> Pallet<T> {
@@ -433,19 +427,17 @@ pub mod pallet {
 			buyback_address: &AccountIdOf<T>,
 			currency_id: CurrencyId,
 			info: &Info<BalanceOf<T>, BlockNumberFor<T>>,
-			swap_out_min: u128,
+			amount: BalanceOf<T>,
 		) -> DispatchResult {
 			let balance = T::MultiCurrency::free_balance(currency_id, &buyback_address);
-			ensure!(balance >= info.min_swap_value, Error::<T>::NotEnoughBalance);
+			ensure!(balance >= amount, Error::<T>::NotEnoughBalance);
 			let path = Self::get_path(currency_id)?;
-			let amount_out_min = swap_out_min.saturating_sub(info.bias * swap_out_min);
-
-			T::DexOperator::inner_swap_exact_assets_for_assets(
+			Self::swap_to_bnc(
 				buyback_address,
-				info.min_swap_value.saturated_into(),
-				amount_out_min,
+				currency_id,
+				amount,
 				&path,
-				&buyback_address,
+				info.max_slippage,
 			)?;
 
 			if let Some(ratio) = info.destruction_ratio {
@@ -465,20 +457,17 @@ pub mod pallet {
 			liquidity_address: &AccountIdOf<T>,
 			currency_id: CurrencyId,
 			info: &Info<BalanceOf<T>, BlockNumberFor<T>>,
-			swap_out_min: u128,
 		) -> DispatchResult {
 			let path = Self::get_path(currency_id)?;
 			let balance = T::MultiCurrency::free_balance(currency_id, &liquidity_address);
 			let token_balance = info.proportion * balance;
 			ensure!(token_balance > Zero::zero(), Error::<T>::NotEnoughBalance);
-			let amount_out_min = swap_out_min.saturating_sub(info.bias * swap_out_min);
-
-			T::DexOperator::inner_swap_exact_assets_for_assets(
+			Self::swap_to_bnc(
 				liquidity_address,
-				token_balance.saturated_into(),
-				amount_out_min,
+				currency_id,
+				token_balance,
 				&path,
-				&liquidity_address,
+				info.max_slippage,
 			)?;
 			let remaining_balance = T::MultiCurrency::free_balance(currency_id, &liquidity_address);
 			let bnc_balance = T::MultiCurrency::free_balance(BNC, &liquidity_address);
@@ -534,31 +523,48 @@ pub mod pallet {
 			Ok(vec![asset_id, bnc_asset_id])
 		}
 
-		pub fn set_swap_out_min(
+		/// Swap `amount_in` of the currency to BNC, with the minimum output `max_slippage` below
+		/// the amount given by the oracle prices.
+		fn swap_to_bnc(
+			account: &AccountIdOf<T>,
 			currency_id: CurrencyId,
-			info: &Info<BalanceOf<T>, BlockNumberFor<T>>,
+			amount_in: BalanceOf<T>,
+			path: &[AssetId],
+			max_slippage: Permill,
 		) -> DispatchResult {
-			let path = Self::get_path(currency_id)?;
-			let amounts = T::DexOperator::get_amount_out_by_path(
-				info.min_swap_value.saturated_into(),
-				&path,
+			let (oracle_amount_out, _, _) =
+				T::OraclePriceProvider::get_oracle_amount_by_currency_and_amount_in(
+					&currency_id,
+					amount_in.saturated_into(),
+					&BNC,
+				)
+				.ok_or(Error::<T>::OraclePriceNotFound)?;
+			let amount_out_min = oracle_amount_out.saturating_sub(max_slippage * oracle_amount_out);
+
+			let bnc_balance_before = T::MultiCurrency::free_balance(BNC, account);
+			T::DexOperator::inner_swap_exact_assets_for_assets(
+				account,
+				amount_in.saturated_into(),
+				amount_out_min,
+				path,
+				account,
 			)?;
-			SwapOutMin::<T>::insert(currency_id, amounts[amounts.len() - 1]);
-			Ok(())
-		}
+			let amount_out =
+				T::MultiCurrency::free_balance(BNC, account).saturating_sub(bnc_balance_before);
 
-		pub fn set_add_liquidity_swap_out_min(
-			liquidity_address: &AccountIdOf<T>,
-			currency_id: CurrencyId,
-			info: &Info<BalanceOf<T>, BlockNumberFor<T>>,
-		) -> DispatchResult {
-			let path = Self::get_path(currency_id)?;
-			let balance = T::MultiCurrency::free_balance(currency_id, &liquidity_address);
-			let token_balance = info.proportion * balance;
-			ensure!(token_balance > Zero::zero(), Error::<T>::NotEnoughBalance);
-			let amounts =
-				T::DexOperator::get_amount_out_by_path(token_balance.saturated_into(), &path)?;
-			AddLiquiditySwapOutMin::<T>::insert(currency_id, amounts[amounts.len() - 1]);
+			let amount_in_u128: u128 = amount_in.saturated_into();
+			Self::deposit_event(Event::Swapped {
+				currency_id,
+				amount_in,
+				amount_out,
+				executed_price: FixedU128::checked_from_rational(
+					amount_out.saturated_into::<u128>(),
+					amount_in_u128,
+				)
+				.unwrap_or_default(),
+				oracle_price: FixedU128::checked_from_rational(oracle_amount_out, amount_in_u128)
+					.unwrap_or_default(),
+			});
 			Ok(())
 		}
 	}
@@ -0,0 +1,156 @@
+// This file is part of Bifrost.
+
+// Copyright (C) Liebi Technologies PTE. LTD.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::*;
+use frame_support::{
+	pallet_prelude::StorageVersion,
+	storage_alias,
+	traits::{GetStorageVersion, OnRuntimeUpgrade},
+};
+#[cfg(feature = "try-runtime")]
+use sp_runtime::TryRuntimeError;
+
+const LOG_TARGET: &str = "buy-back::migration";
+
+/// The storage before the minimum output of swaps was derived from the oracle prices.
+pub mod v0 {
+	use super::*;
+
+	/// The minimum output of the next buyback swap, set by governance.
+	#[storage_alias]
+	pub type SwapOutMin<T: Config> =
+		StorageMap<Pallet<T>, Twox64Concat, CurrencyIdOf<T>, u128, OptionQuery>;
+
+	/// The minimum output of the next add liquidity swap, set by governance.
+	#[storage_alias]
+	pub type AddLiquiditySwapOutMin<T: Config> =
+		StorageMap<Pallet<T>, Twox64Concat, CurrencyIdOf<T>, u128, OptionQuery>;
+
+	#[derive(Encode, Decode, Clone, RuntimeDebug, TypeInfo)]
+	pub struct Info<BalanceOf, BlockNumberFor> {
+		pub min_swap_value: BalanceOf,
+		pub if_auto: bool,
+		pub proportion: Permill,
+		pub buyback_duration: BlockNumberFor,
+		pub last_buyback: BlockNumberFor,
+		pub last_buyback_cycle: BlockNumberFor,
+		pub add_liquidity_duration: BlockNumberFor,
+		pub last_add_liquidity: BlockNumberFor,
+		pub destruction_ratio: Option<Permill>,
+		pub bias: Permill,
+	}
+
+	impl<BalanceOf, BlockNumberFor> From<Info<BalanceOf, BlockNumberFor>>
+		for crate::Info<BalanceOf, BlockNumberFor>
+	{
+		fn from(info: Info<BalanceOf, BlockNumberFor>) -> Self {
+			Self {
+				min_swap_value: info.min_swap_value,
+				if_auto: info.if_auto,
+				proportion: info.proportion,
+				buyback_duration: info.buyback_duration,
+				last_buyback: info.last_buyback,
+				last_buyback_cycle: info.last_buyback_cycle,
+				add_liquidity_duration: info.add_liquidity_duration,
+				last_add_liquidity: info.last_add_liquidity,
+				destruction_ratio: info.destruction_ratio,
+				// The bias used to be applied to the minimum output as the slippage.
+				max_slippage: info.bias,
+			}
+		}
+	}
+}
+
+pub mod v1 {
+	use super::*;
+
+	pub struct MigrateToV1<T>(sp_std::marker::PhantomData<T>);
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV1<T> {
+		fn on_runtime_upgrade() -> Weight {
+			// Check the storage version
+			let onchain_version = Pallet::<T>::on_chain_storage_version();
+			if onchain_version < 1 {
+				log::info!(target: LOG_TARGET, "Start to migrate Infos storage...");
+				let mut info_count = 0u64;
+				Infos::<T>::translate::<v0::Info<BalanceOf<T>, BlockNumberFor<T>>, _>(
+					|k: CurrencyIdOf<T>, info: v0::Info<BalanceOf<T>, BlockNumberFor<T>>| {
+						log::info!(target: LOG_TARGET, "Migrated info for {:?}...", k);
+						info_count += 1;
+						Some(info.into())
+					},
+				);
+
+				// The minimum outputs set by governance are replaced by the oracle prices.
+				let swap_out_min = v0::SwapOutMin::<T>::clear(u32::MAX, None);
+				let add_liquidity_swap_out_min =
+					v0::AddLiquiditySwapOutMin::<T>::clear(u32::MAX, None);
+				let removed = swap_out_min.unique as u64 + add_liquidity_swap_out_min.unique as u64;
+
+				// Update the storage version
+				StorageVersion::new(1).put::<Pallet<T>>();
+
+				// Return the consumed weight
+				Weight::from(
+					T::DbWeight::get()
+						.reads_writes(info_count + removed + 1, info_count + removed + 1),
+				)
+			} else {
+				log::warn!(target: LOG_TARGET, "buy-back migration should be removed.");
+				T::DbWeight::get().reads(1)
+			}
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<Vec<u8>, TryRuntimeError> {
+			let info_count = Infos::<T>::iter_keys().count() as u64;
+
+			// print out the pre-migrate storage count
+			log::info!(target: LOG_TARGET, "Infos pre-migrate storage count: {:?}", info_count);
+			Ok(info_count.encode())
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(cnt: Vec<u8>) -> Result<(), TryRuntimeError> {
+			let old_info_count: u64 = Decode::decode(&mut cnt.as_slice()).expect(
+				"the state parameter should be something that was generated by pre_upgrade",
+			);
+			let new_info_count = Infos::<T>::iter().count() as u64;
+
+			// print out the post-migrate storage count
+			log::info!(target: LOG_TARGET, "Infos post-migrate storage count: {:?}", new_info_count);
+
+			ensure!(
+				Pallet::<T>::on_chain_storage_version() == 1,
+				"on_chain_storage_version should be 1"
+			);
+			ensure!(
+				new_info_count == old_info_count,
+				"Infos post-migrate storage count not match"
+			);
+			ensure!(
+				v0::SwapOutMin::<T>::iter_keys().next().is_none()
+					&& v0::AddLiquiditySwapOutMin::<T>::iter_keys()
+						.next()
+						.is_none(),
+				"SwapOutMin and AddLiquiditySwapOutMin should be removed"
+			);
+
+			Ok(())
+		}
+	}
+}
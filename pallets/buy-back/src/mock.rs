@@ -25,7 +25,8 @@ use bifrost_asset_registry::AssetIdMaps;
 pub use bifrost_primitives::{currency::*, CurrencyId, SlpxOperator};
 use bifrost_primitives::{
 	BifrostEntranceAccount, BifrostExitAccount, BifrostFeeAccount, BuyBackAccount,
-	IncentivePalletId, IncentivePoolAccount, LiquidityAccount, MoonbeamChainId, ZenlinkPalletId,
+	IncentivePalletId, IncentivePoolAccount, LiquidityAccount, MoonbeamChainId,
+	OraclePriceProvider, Price, PriceDetail, ZenlinkPalletId,
 };
 use bifrost_slp::{QueryId, QueryResponseManager};
 pub use cumulus_primitives_core::ParaId;
@@ -38,12 +39,13 @@ use frame_support::{
 };
 use frame_system::{EnsureRoot, EnsureSignedBy};
 use orml_traits::{location::RelativeReserveProvider, parameter_type_with_key, MultiCurrency};
-use sp_core::ConstU32;
+use sp_core::{ConstU32, ConstU64};
 use sp_runtime::{
 	traits::{AccountIdConversion, IdentityLookup, UniqueSaturatedInto},
-	AccountId32, BuildStorage, SaturatedConversion,
+	AccountId32, BuildStorage, FixedPointNumber, FixedU128, SaturatedConversion,
 };
 use sp_std::marker::PhantomData;
+use std::collections::BTreeMap;
 use xcm::{v3::Weight, v4::prelude::*};
 use xcm_builder::{FixedWeightBounds, FrameTransactionalProcessor};
 use xcm_executor::XcmExecutor;
@@ -179,6 +181,60 @@ impl bifrost_buy_back::Config for Runtime {
 	type CurrencyIdRegister = AssetIdMaps<Runtime>;
 	type BbBNC = BbBNC;
 	type BlockNumberProvider = System;
+	type OraclePriceProvider = MockOraclePriceProvider;
+}
+
+parameter_types! {
+	pub static OraclePrices: BTreeMap<CurrencyId, Price> = BTreeMap::from([
+		(BNC, FixedU128::from(1)),
+		(VKSM, FixedU128::from_rational(64, 100)),
+	]);
+}
+
+/// Oracle prices of currencies with the same decimals.
+pub struct MockOraclePriceProvider;
+impl MockOraclePriceProvider {
+	pub fn set_price(currency_id: CurrencyId, price: Option<Price>) {
+		let mut prices = OraclePrices::get();
+		match price {
+			Some(price) => prices.insert(currency_id, price),
+			None => prices.remove(&currency_id),
+		};
+		OraclePrices::set(prices);
+	}
+}
+
+impl OraclePriceProvider for MockOraclePriceProvider {
+	fn get_price(currency_id: &CurrencyId) -> Option<PriceDetail> {
+		OraclePrices::get()
+			.get(currency_id)
+			.map(|price| (*price, 0))
+	}
+
+	fn get_amount_by_prices(
+		_currency_in: &CurrencyId,
+		amount_in: Balance,
+		currency_in_price: Price,
+		_currency_out: &CurrencyId,
+		currency_out_price: Price,
+	) -> Option<Balance> {
+		Some(
+			(FixedU128::from_inner(amount_in) * currency_in_price / currency_out_price)
+				.into_inner(),
+		)
+	}
+
+	fn get_oracle_amount_by_currency_and_amount_in(
+		currency_in: &CurrencyId,
+		amount_in: Balance,
+		currency_out: &CurrencyId,
+	) -> Option<(Balance, Price, Price)> {
+		let price_in = *OraclePrices::get().get(currency_in)?;
+		let price_out = *OraclePrices::get().get(currency_out)?;
+		let amount_out =
+			Self::get_amount_by_prices(currency_in, amount_in, price_in, currency_out, price_out)?;
+		Some((amount_out, price_in, price_out))
+	}
 }
 
 pub struct ParaInfo;
@@ -247,6 +303,7 @@ impl bifrost_slp::Config for Runtime {
 	type AssetIdMaps = AssetIdMaps<Runtime>;
 	type TreasuryAccount = TreasuryAccount;
 	type BlockNumberProvider = System;
+	type XcmQueryTimeout = ConstU64<1000>;
 }
 
 parameter_type_with_key! {
@@ -22,7 +22,10 @@
 
 use crate::{mock::*, *};
 use bifrost_primitives::IncentivePalletId;
-use frame_support::{assert_noop, assert_ok};
+use frame_support::{
+	assert_noop, assert_ok,
+	traits::{GetStorageVersion, OnRuntimeUpgrade},
+};
 use sp_arithmetic::per_things::Permill;
 
 const PARAID: u32 = 2001;
@@ -38,7 +41,7 @@ fn set_vtoken_should_not_work() {
 		.build()
 		.execute_with(|| {
 			let destruction_ratio = Some(Permill::from_percent(2));
-			let bias: Permill = Permill::from_percent(10);
+			let max_slippage: Permill = Permill::from_percent(10);
 			assert_noop!(
 				BuyBack::set_vtoken(
 					RuntimeOrigin::signed(ALICE),
@@ -49,7 +52,7 @@ fn set_vtoken_should_not_work() {
 					LIQUID_DURATION,
 					true,
 					destruction_ratio,
-					bias
+					max_slippage
 				),
 				Error::<Runtime>::CurrencyIdError
 			);
@@ -64,7 +67,7 @@ fn set_vtoken_should_not_work() {
 					LIQUID_DURATION,
 					true,
 					destruction_ratio,
-					bias
+					max_slippage
 				),
 				Error::<Runtime>::ZeroDuration
 			);
@@ -79,7 +82,7 @@ fn set_vtoken_should_not_work() {
 					LIQUID_DURATION,
 					true,
 					destruction_ratio,
-					bias
+					max_slippage
 				),
 				Error::<Runtime>::ZeroMinSwapValue
 			);
@@ -94,7 +97,7 @@ fn buy_back_with_burn_should_work() {
 		.execute_with(|| {
 			let zenlink_pair_account_id = init_zenlink(PARAID);
 			let destruction_ratio = Some(Permill::from_percent(2));
-			let bias: Permill = Permill::from_percent(10);
+			let max_slippage: Permill = Permill::from_percent(10);
 
 			assert_ok!(BuyBack::set_vtoken(
 				RuntimeOrigin::signed(ALICE),
@@ -105,7 +108,7 @@ fn buy_back_with_burn_should_work() {
 				LIQUID_DURATION,
 				true,
 				destruction_ratio,
-				bias
+				max_slippage
 			));
 			let buyback_account =
 				<Runtime as Config>::BuyBackAccount::get().into_account_truncating();
@@ -128,7 +131,7 @@ fn buy_back_with_burn_should_work() {
 			);
 			assert_ok!(BuyBack::charge(RuntimeOrigin::signed(ALICE), VKSM, 1000));
 			let infos = Infos::<Runtime>::get(VKSM).unwrap();
-			assert_ok!(BuyBack::buy_back(&buyback_account, VKSM, &infos, 0));
+			assert_ok!(BuyBack::buy_back(&buyback_account, VKSM, &infos, VALUE));
 			System::set_block_number(System::block_number() + 1);
 			assert_eq!(Currencies::free_balance(VKSM, &buyback_account), 9000);
 			assert_eq!(
@@ -152,7 +155,7 @@ fn buy_back_no_burn_should_work() {
 		.execute_with(|| {
 			let zenlink_pair_account_id = init_zenlink(PARAID);
 			let destruction_ratio = Some(Permill::from_percent(0));
-			let bias: Permill = Permill::from_percent(10);
+			let max_slippage: Permill = Permill::from_percent(10);
 
 			assert_ok!(BuyBack::set_vtoken(
 				RuntimeOrigin::signed(ALICE),
@@ -163,7 +166,7 @@ fn buy_back_no_burn_should_work() {
 				LIQUID_DURATION,
 				true,
 				destruction_ratio,
-				bias
+				max_slippage
 			));
 			let buyback_account =
 				<Runtime as Config>::BuyBackAccount::get().into_account_truncating();
@@ -186,7 +189,7 @@ fn buy_back_no_burn_should_work() {
 			);
 			assert_ok!(BuyBack::charge(RuntimeOrigin::signed(ALICE), VKSM, 1000));
 			let infos = Infos::<Runtime>::get(VKSM).unwrap();
-			assert_ok!(BuyBack::buy_back(&buyback_account, VKSM, &infos, 0));
+			assert_ok!(BuyBack::buy_back(&buyback_account, VKSM, &infos, VALUE));
 			System::set_block_number(System::block_number() + 1);
 			assert_eq!(Currencies::free_balance(VKSM, &buyback_account), 9000);
 			assert_eq!(
@@ -210,7 +213,7 @@ fn on_initialize_no_burn_should_work() {
 		.execute_with(|| {
 			let zenlink_pair_account_id = init_zenlink(PARAID);
 			let destruction_ratio = None;
-			let bias: Permill = Permill::from_percent(10);
+			let max_slippage: Permill = Permill::from_percent(10);
 
 			assert_ok!(BuyBack::set_vtoken(
 				RuntimeOrigin::signed(ALICE),
@@ -221,7 +224,7 @@ fn on_initialize_no_burn_should_work() {
 				LIQUID_DURATION,
 				true,
 				destruction_ratio,
-				bias
+				max_slippage
 			));
 			let buyback_account =
 				<Runtime as Config>::BuyBackAccount::get().into_account_truncating();
@@ -271,7 +274,7 @@ fn on_initialize_with_burn_should_work() {
 		.execute_with(|| {
 			let zenlink_pair_account_id = init_zenlink(PARAID);
 			let destruction_ratio = Some(Permill::from_percent(10));
-			let bias: Permill = Permill::from_percent(10);
+			let max_slippage: Permill = Permill::from_percent(10);
 
 			assert_ok!(BuyBack::set_vtoken(
 				RuntimeOrigin::signed(ALICE),
@@ -282,7 +285,7 @@ fn on_initialize_with_burn_should_work() {
 				LIQUID_DURATION,
 				true,
 				destruction_ratio,
-				bias
+				max_slippage
 			));
 			let buyback_account =
 				<Runtime as Config>::BuyBackAccount::get().into_account_truncating();
@@ -323,14 +326,14 @@ fn on_initialize_with_burn_should_work() {
 }
 
 #[test]
-fn on_initialize_with_bias_should_work() {
+fn on_initialize_within_max_slippage_should_work() {
 	ExtBuilder::default()
 		.one_hundred_for_alice_n_bob()
 		.build()
 		.execute_with(|| {
 			let zenlink_pair_account_id = init_zenlink(PARAID);
 			let destruction_ratio = None;
-			let bias: Permill = Permill::from_percent(10);
+			let max_slippage: Permill = Permill::from_percent(10);
 
 			assert_ok!(BuyBack::set_vtoken(
 				RuntimeOrigin::signed(ALICE),
@@ -341,7 +344,7 @@ fn on_initialize_with_bias_should_work() {
 				LIQUID_DURATION,
 				true,
 				destruction_ratio,
-				bias
+				max_slippage
 			));
 			let buyback_account =
 				<Runtime as Config>::BuyBackAccount::get().into_account_truncating();
@@ -397,14 +400,15 @@ fn on_initialize_with_bias_should_work() {
 }
 
 #[test]
-fn on_initialize_with_bias_should_not_work() {
+fn on_initialize_beyond_max_slippage_should_not_work() {
 	ExtBuilder::default()
 		.one_hundred_for_alice_n_bob()
 		.build()
 		.execute_with(|| {
 			let zenlink_pair_account_id = init_zenlink(PARAID);
 			let destruction_ratio = None;
-			let bias: Permill = Permill::from_percent(5);
+			// The max slippage bounds the swap output.
+			let max_slippage: Permill = Permill::from_percent(5);
 
 			assert_ok!(BuyBack::set_vtoken(
 				RuntimeOrigin::signed(ALICE),
@@ -415,7 +419,7 @@ fn on_initialize_with_bias_should_not_work() {
 				LIQUID_DURATION,
 				true,
 				destruction_ratio,
-				bias
+				max_slippage
 			));
 			let buyback_account =
 				<Runtime as Config>::BuyBackAccount::get().into_account_truncating();
@@ -437,6 +441,7 @@ fn on_initialize_with_bias_should_not_work() {
 				Some(buyback_account.clone()),
 			);
 			assert_ok!(BuyBack::charge(RuntimeOrigin::signed(ALICE), VKSM, 1000));
+			System::set_block_number(1);
 			BuyBack::on_initialize(1);
 			let path = vec![
 				AssetId::try_convert_from(VKSM, PARAID).unwrap(),
@@ -451,7 +456,14 @@ fn on_initialize_with_bias_should_not_work() {
 				<frame_system::Pallet<Runtime>>::block_number()
 					+ BlockNumberFor::<Runtime>::from(1u32)
 			));
+			// 578 BNC out is below the oracle amount of 640 minus 5%.
+			System::set_block_number(2);
 			BuyBack::on_initialize(2);
+			System::assert_last_event(RuntimeEvent::BuyBack(Event::BuyBackFailed {
+				currency_id: VKSM,
+				block_number: 2,
+			}));
+			System::set_block_number(3);
 			BuyBack::on_initialize(3);
 			assert_eq!(Currencies::free_balance(VKSM, &buyback_account), 10000);
 			assert_eq!(
@@ -467,6 +479,243 @@ fn on_initialize_with_bias_should_not_work() {
 		});
 }
 
+#[test]
+fn set_swap_chunk_size_should_work() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob()
+		.build()
+		.execute_with(|| {
+			assert_noop!(
+				BuyBack::set_swap_chunk_size(RuntimeOrigin::signed(BOB), VKSM, Some(500)),
+				DispatchError::BadOrigin
+			);
+			assert_noop!(
+				BuyBack::set_swap_chunk_size(RuntimeOrigin::signed(ALICE), KSM, Some(500)),
+				Error::<Runtime>::CurrencyIdError
+			);
+			assert_noop!(
+				BuyBack::set_swap_chunk_size(RuntimeOrigin::signed(ALICE), VKSM, Some(0)),
+				Error::<Runtime>::ZeroChunkSize
+			);
+
+			assert_ok!(BuyBack::set_swap_chunk_size(
+				RuntimeOrigin::signed(ALICE),
+				VKSM,
+				Some(500)
+			));
+			assert_eq!(SwapChunkSize::<Runtime>::get(VKSM), Some(500));
+			System::assert_last_event(RuntimeEvent::BuyBack(Event::SwapChunkSizeSet {
+				currency_id: VKSM,
+				chunk_size: Some(500),
+			}));
+
+			assert_ok!(BuyBack::set_swap_chunk_size(
+				RuntimeOrigin::signed(ALICE),
+				VKSM,
+				None
+			));
+			assert_eq!(SwapChunkSize::<Runtime>::get(VKSM), None);
+		});
+}
+
+#[test]
+fn buy_back_without_oracle_price_should_not_work() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob()
+		.build()
+		.execute_with(|| {
+			init_zenlink(PARAID);
+			assert_ok!(BuyBack::set_vtoken(
+				RuntimeOrigin::signed(ALICE),
+				VKSM,
+				VALUE,
+				LIQUID_PROPORTION,
+				BUYBACK_DURATION,
+				LIQUID_DURATION,
+				true,
+				None,
+				Permill::from_percent(10),
+				Permill::from_percent(10)
+			));
+			let buyback_account =
+				<Runtime as Config>::BuyBackAccount::get().into_account_truncating();
+			MockOraclePriceProvider::set_price(VKSM, None);
+			let infos = Infos::<Runtime>::get(VKSM).unwrap();
+			assert_noop!(
+				BuyBack::buy_back(&buyback_account, VKSM, &infos, VALUE),
+				Error::<Runtime>::OraclePriceNotFound
+			);
+		});
+}
+
+#[test]
+fn on_initialize_with_swap_chunks_should_work() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob()
+		.build()
+		.execute_with(|| {
+			let zenlink_pair_account_id = init_zenlink(PARAID);
+			assert_ok!(BuyBack::set_vtoken(
+				RuntimeOrigin::signed(ALICE),
+				VKSM,
+				VALUE,
+				LIQUID_PROPORTION,
+				BUYBACK_DURATION,
+				LIQUID_DURATION,
+				true,
+				None,
+				Permill::from_percent(10),
+				Permill::from_percent(10)
+			));
+			assert_ok!(BuyBack::set_swap_chunk_size(
+				RuntimeOrigin::signed(ALICE),
+				VKSM,
+				Some(500)
+			));
+			MockOraclePriceProvider::set_price(VKSM, Some(FixedU128::from_rational(1, 2)));
+			let buyback_account =
+				<Runtime as Config>::BuyBackAccount::get().into_account_truncating();
+			BbBNC::set_incentive(
+				BB_BNC_SYSTEM_POOL_ID,
+				Some(7 * 86400 / 12),
+				Some(buyback_account.clone()),
+			);
+
+			System::set_block_number(1);
+			BuyBack::on_initialize(1);
+			assert_eq!(PendingBuyBack::<Runtime>::get(VKSM), 0);
+
+			System::set_block_number(2);
+			BuyBack::on_initialize(2);
+			assert_eq!(PendingBuyBack::<Runtime>::get(VKSM), 500);
+			System::assert_has_event(RuntimeEvent::BuyBack(Event::Swapped {
+				currency_id: VKSM,
+				amount_in: 500,
+				amount_out: 369,
+				executed_price: FixedU128::from_rational(369, 500),
+				oracle_price: FixedU128::from_rational(1, 2),
+			}));
+			assert_eq!(Currencies::free_balance(VKSM, &buyback_account), 8500);
+
+			System::set_block_number(3);
+			BuyBack::on_initialize(3);
+			assert_eq!(PendingBuyBack::<Runtime>::get(VKSM), 0);
+			System::assert_has_event(RuntimeEvent::BuyBack(Event::Swapped {
+				currency_id: VKSM,
+				amount_in: 500,
+				amount_out: 254,
+				executed_price: FixedU128::from_rational(254, 500),
+				oracle_price: FixedU128::from_rational(1, 2),
+			}));
+			assert_eq!(Currencies::free_balance(VKSM, &buyback_account), 8000);
+			assert_eq!(
+				Currencies::free_balance(VKSM, &zenlink_pair_account_id),
+				3200
+			);
+			assert_eq!(
+				Currencies::free_balance(BNC, &zenlink_pair_account_id),
+				1377
+			);
+		});
+}
+
+#[test]
+fn failed_swap_chunk_should_only_drop_the_chunk() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob()
+		.build()
+		.execute_with(|| {
+			init_zenlink(PARAID);
+			assert_ok!(BuyBack::set_vtoken(
+				RuntimeOrigin::signed(ALICE),
+				VKSM,
+				VALUE,
+				LIQUID_PROPORTION,
+				BUYBACK_DURATION,
+				LIQUID_DURATION,
+				true,
+				None,
+				Permill::from_percent(10),
+				Permill::from_percent(10)
+			));
+			assert_ok!(BuyBack::set_swap_chunk_size(
+				RuntimeOrigin::signed(ALICE),
+				VKSM,
+				Some(500)
+			));
+			let buyback_account =
+				<Runtime as Config>::BuyBackAccount::get().into_account_truncating();
+			BbBNC::set_incentive(
+				BB_BNC_SYSTEM_POOL_ID,
+				Some(7 * 86400 / 12),
+				Some(buyback_account.clone()),
+			);
+
+			System::set_block_number(1);
+			BuyBack::on_initialize(1);
+
+			MockOraclePriceProvider::set_price(VKSM, None);
+			System::set_block_number(2);
+			BuyBack::on_initialize(2);
+			System::assert_last_event(RuntimeEvent::BuyBack(Event::BuyBackFailed {
+				currency_id: VKSM,
+				block_number: 2,
+			}));
+			assert_eq!(PendingBuyBack::<Runtime>::get(VKSM), 500);
+			assert_eq!(Currencies::free_balance(VKSM, &buyback_account), 9000);
+
+			MockOraclePriceProvider::set_price(VKSM, Some(FixedU128::from_rational(1, 2)));
+			System::set_block_number(3);
+			BuyBack::on_initialize(3);
+			System::assert_last_event(RuntimeEvent::BuyBack(Event::BuyBackSuccess {
+				currency_id: VKSM,
+				block_number: 3,
+			}));
+			assert_eq!(PendingBuyBack::<Runtime>::get(VKSM), 0);
+			assert_eq!(Currencies::free_balance(VKSM, &buyback_account), 8500);
+		});
+}
+
+#[test]
+fn migrate_to_v1_should_work() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob()
+		.build()
+		.execute_with(|| {
+			StorageVersion::new(0).put::<BuyBack>();
+			let old_info = migration::v0::Info {
+				min_swap_value: VALUE,
+				if_auto: true,
+				proportion: LIQUID_PROPORTION,
+				buyback_duration: BUYBACK_DURATION,
+				last_buyback: 0,
+				last_buyback_cycle: 0,
+				add_liquidity_duration: LIQUID_DURATION,
+				last_add_liquidity: 0,
+				destruction_ratio: None,
+				bias: Permill::from_percent(10),
+			};
+			frame_support::storage::unhashed::put(
+				&Infos::<Runtime>::hashed_key_for(VKSM),
+				&old_info,
+			);
+			migration::v0::SwapOutMin::<Runtime>::insert(VKSM, 100);
+			migration::v0::AddLiquiditySwapOutMin::<Runtime>::insert(VKSM, 100);
+
+			migration::v1::MigrateToV1::<Runtime>::on_runtime_upgrade();
+
+			let info = Infos::<Runtime>::get(VKSM).unwrap();
+			assert_eq!(info.min_swap_value, VALUE);
+			assert_eq!(info.max_slippage, Permill::from_percent(10));
+			assert_eq!(migration::v0::SwapOutMin::<Runtime>::get(VKSM), None);
+			assert_eq!(
+				migration::v0::AddLiquiditySwapOutMin::<Runtime>::get(VKSM),
+				None
+			);
+			assert_eq!(BuyBack::on_chain_storage_version(), 1);
+		});
+}
+
 fn init_zenlink(_para_id: u32) -> AccountIdOf<Runtime> {
 	let asset_0_currency_id: AssetId = AssetId::try_convert_from(BNC, PARAID).unwrap();
 	let asset_1_currency_id: AssetId = AssetId::try_convert_from(VKSM, PARAID).unwrap();
@@ -57,6 +57,7 @@ pub trait WeightInfo {
 	fn charge() -> Weight;
 	fn remove_vtoken() -> Weight;
 	fn on_initialize() -> Weight;
+	fn set_swap_chunk_size() -> Weight;
 }
 
 // For backwards compatibility and tests
@@ -134,4 +135,17 @@ impl WeightInfo for () {
 		Weight::from_parts(5_932_000, 6243)
 			.saturating_add(RocksDbWeight::get().reads(2_u64))
 	}
+	/// Storage: `AssetRegistry::CurrencyMetadatas` (r:1 w:0)
+	/// Proof: `AssetRegistry::CurrencyMetadatas` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `BuyBack::SwapChunkSize` (r:0 w:1)
+	/// Proof: `BuyBack::SwapChunkSize` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn set_swap_chunk_size() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `667`
+		//  Estimated: `4132`
+		// Minimum execution time: 10_215_000 picoseconds.
+		Weight::from_parts(10_804_000, 4132)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
 }
@@ -38,7 +38,7 @@ use frame_support::{
 };
 use frame_system::{EnsureRoot, EnsureSignedBy};
 use orml_traits::{location::RelativeReserveProvider, parameter_type_with_key, MultiCurrency};
-use sp_core::ConstU32;
+use sp_core::{ConstU32, ConstU64};
 use sp_runtime::{
 	traits::{AccountIdConversion, IdentityLookup, UniqueSaturatedInto},
 	AccountId32, BuildStorage, SaturatedConversion,
@@ -244,6 +244,7 @@ impl bifrost_slp::Config for Runtime {
 	type StablePoolHandler = ();
 	type AssetIdMaps = AssetIdMaps<Runtime>;
 	type TreasuryAccount = TreasuryAccount;
+	type XcmQueryTimeout = ConstU64<1000>;
 }
 
 parameter_type_with_key! {
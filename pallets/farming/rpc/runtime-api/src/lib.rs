@@ -38,5 +38,16 @@ decl_runtime_apis! {
 			who: AccountId,
 			pid: PoolId,
 		) -> Vec<(CurrencyId, Balance)>;
+
+		/// The positions of `who` as `(position_id, pid, deposited value, pending rewards)`.
+		fn get_user_positions(
+			who: AccountId,
+		) -> Vec<(u64, PoolId, Balance, Vec<(CurrencyId, Balance)>)>;
+
+		/// The bbBNC boost factor of `who` in the pool `pid`, in parts per billion.
+		fn get_farming_boost(
+			who: AccountId,
+			pid: PoolId,
+		) -> u32;
 	}
 }
@@ -50,6 +50,23 @@ pub trait FarmingRpcApi<BlockHash, AccountId, PoolId, CurrencyId> {
 		pid: PoolId,
 		at: Option<BlockHash>,
 	) -> RpcResult<Vec<(CurrencyId, NumberOrHex)>>;
+
+	/// rpc method for getting user positions with their pending rewards
+	#[method(name = "farming_getUserPositions")]
+	fn get_user_positions(
+		&self,
+		who: AccountId,
+		at: Option<BlockHash>,
+	) -> RpcResult<Vec<(u64, PoolId, NumberOrHex, Vec<(CurrencyId, NumberOrHex)>)>>;
+
+	/// rpc method for getting the bbBNC boost factor of a user, in parts per billion
+	#[method(name = "farming_getFarmingBoost")]
+	fn get_farming_boost(
+		&self,
+		who: AccountId,
+		pid: PoolId,
+		at: Option<BlockHash>,
+	) -> RpcResult<u32>;
 }
 
 #[derive(Clone, Debug)]
@@ -127,4 +144,59 @@ where
 			)),
 		}
 	}
+
+	fn get_user_positions(
+		&self,
+		who: AccountId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Vec<(u64, PoolId, NumberOrHex, Vec<(CurrencyId, NumberOrHex)>)>> {
+		let lm_rpc_api = self.client.runtime_api();
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		let rs: Result<Vec<(u64, PoolId, Balance, Vec<(CurrencyId, Balance)>)>, _> =
+			lm_rpc_api.get_user_positions(at, who);
+
+		match rs {
+			Ok(positions) => Ok(positions
+				.into_iter()
+				.map(|(position_id, pid, amount, rewards)| {
+					(
+						position_id,
+						pid,
+						NumberOrHex::Hex(amount.into()),
+						rewards
+							.into_iter()
+							.map(|(token, amount)| (token, NumberOrHex::Hex(amount.into())))
+							.collect(),
+					)
+				})
+				.collect()),
+			Err(e) => Err(ErrorObject::owned(
+				ErrorCode::InternalError.code(),
+				"Failed to get user positions.",
+				Some(format!("{:?}", e)),
+			)),
+		}
+	}
+
+	fn get_farming_boost(
+		&self,
+		who: AccountId,
+		pid: PoolId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<u32> {
+		let lm_rpc_api = self.client.runtime_api();
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		let rs: Result<u32, _> = lm_rpc_api.get_farming_boost(at, who, pid);
+
+		match rs {
+			Ok(boost) => Ok(boost),
+			Err(e) => Err(ErrorObject::owned(
+				ErrorCode::InternalError.code(),
+				"Failed to get farming boost.",
+				Some(format!("{:?}", e)),
+			)),
+		}
+	}
 }
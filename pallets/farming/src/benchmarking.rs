@@ -662,4 +662,266 @@ mod benchmarks {
 
 		Ok(())
 	}
+
+	#[benchmark]
+	fn deposit_position() -> Result<(), BenchmarkError> {
+		let caller: T::AccountId = whitelisted_caller();
+		let token_amount = BalanceOf::<T>::unique_saturated_from(1000u128);
+		let default_currency_id = DOT.into();
+		let tokens_proportion = vec![(default_currency_id, Perbill::from_percent(100))];
+		let basic_rewards = vec![(default_currency_id, token_amount)];
+		assert_ok!(Farming::<T>::create_farming_pool(
+			RawOrigin::Root.into(),
+			tokens_proportion.clone(),
+			basic_rewards.clone(),
+			None,
+			BalanceOf::<T>::unique_saturated_from(0u128),
+			BlockNumberFor::<T>::from(0u32),
+			BlockNumberFor::<T>::from(7u32),
+			BlockNumberFor::<T>::from(0u32),
+			5,
+		));
+		<T as Config>::MultiCurrency::deposit(
+			default_currency_id,
+			&caller,
+			BalanceOf::<T>::unique_saturated_from(1_000_000_000_000_000u128),
+		)?;
+		let charge_rewards = vec![(
+			default_currency_id,
+			BalanceOf::<T>::unique_saturated_from(300000u128),
+		)];
+		assert_ok!(Farming::<T>::charge(
+			RawOrigin::Signed(caller.clone()).into(),
+			0,
+			charge_rewards,
+		));
+		#[extrinsic_call]
+		_(
+			RawOrigin::Signed(caller.clone()),
+			0,
+			token_amount,
+			BlockNumberFor::<T>::from(7u32),
+		);
+
+		Ok(())
+	}
+
+	#[benchmark]
+	fn withdraw_position() -> Result<(), BenchmarkError> {
+		let caller: T::AccountId = whitelisted_caller();
+		let token_amount = BalanceOf::<T>::unique_saturated_from(1000u128);
+		let default_currency_id = DOT.into();
+		let tokens_proportion = vec![(default_currency_id, Perbill::from_percent(100))];
+		let basic_rewards = vec![(default_currency_id, token_amount)];
+		assert_ok!(Farming::<T>::create_farming_pool(
+			RawOrigin::Root.into(),
+			tokens_proportion.clone(),
+			basic_rewards.clone(),
+			None,
+			BalanceOf::<T>::unique_saturated_from(0u128),
+			BlockNumberFor::<T>::from(0u32),
+			BlockNumberFor::<T>::from(7u32),
+			BlockNumberFor::<T>::from(0u32),
+			5,
+		));
+		<T as Config>::MultiCurrency::deposit(
+			default_currency_id,
+			&caller,
+			BalanceOf::<T>::unique_saturated_from(1_000_000_000_000_000u128),
+		)?;
+		let charge_rewards = vec![(
+			default_currency_id,
+			BalanceOf::<T>::unique_saturated_from(300000u128),
+		)];
+		assert_ok!(Farming::<T>::charge(
+			RawOrigin::Signed(caller.clone()).into(),
+			0,
+			charge_rewards,
+		));
+		assert_ok!(Farming::<T>::deposit_position(
+			RawOrigin::Signed(caller.clone()).into(),
+			0,
+			token_amount,
+			BlockNumberFor::<T>::from(7u32),
+		));
+		Farming::<T>::on_initialize(BlockNumberFor::<T>::from(0u32));
+		System::<T>::set_block_number(
+			System::<T>::block_number() + BlockNumberFor::<T>::from(10u32),
+		);
+		Farming::<T>::on_initialize(BlockNumberFor::<T>::from(0u32));
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller.clone()), 0, None);
+
+		Ok(())
+	}
+
+	#[benchmark]
+	fn claim_position() -> Result<(), BenchmarkError> {
+		let caller: T::AccountId = whitelisted_caller();
+		let token_amount = BalanceOf::<T>::unique_saturated_from(1000u128);
+		let default_currency_id = DOT.into();
+		let tokens_proportion = vec![(default_currency_id, Perbill::from_percent(100))];
+		let basic_rewards = vec![(default_currency_id, token_amount)];
+		assert_ok!(Farming::<T>::create_farming_pool(
+			RawOrigin::Root.into(),
+			tokens_proportion.clone(),
+			basic_rewards.clone(),
+			None,
+			BalanceOf::<T>::unique_saturated_from(0u128),
+			BlockNumberFor::<T>::from(0u32),
+			BlockNumberFor::<T>::from(7u32),
+			BlockNumberFor::<T>::from(0u32),
+			5,
+		));
+		<T as Config>::MultiCurrency::deposit(
+			default_currency_id,
+			&caller,
+			BalanceOf::<T>::unique_saturated_from(1_000_000_000_000_000u128),
+		)?;
+		let charge_rewards = vec![(
+			default_currency_id,
+			BalanceOf::<T>::unique_saturated_from(300000u128),
+		)];
+		assert_ok!(Farming::<T>::charge(
+			RawOrigin::Signed(caller.clone()).into(),
+			0,
+			charge_rewards,
+		));
+		assert_ok!(Farming::<T>::deposit_position(
+			RawOrigin::Signed(caller.clone()).into(),
+			0,
+			token_amount,
+			BlockNumberFor::<T>::from(7u32),
+		));
+		Farming::<T>::on_initialize(BlockNumberFor::<T>::from(0u32));
+		System::<T>::set_block_number(
+			System::<T>::block_number() + BlockNumberFor::<T>::from(10u32),
+		);
+		Farming::<T>::on_initialize(BlockNumberFor::<T>::from(0u32));
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller.clone()), 0);
+
+		Ok(())
+	}
+
+	#[benchmark]
+	fn transfer_position() -> Result<(), BenchmarkError> {
+		let caller: T::AccountId = whitelisted_caller();
+		let token_amount = BalanceOf::<T>::unique_saturated_from(1000u128);
+		let default_currency_id = DOT.into();
+		let tokens_proportion = vec![(default_currency_id, Perbill::from_percent(100))];
+		let basic_rewards = vec![(default_currency_id, token_amount)];
+		assert_ok!(Farming::<T>::create_farming_pool(
+			RawOrigin::Root.into(),
+			tokens_proportion.clone(),
+			basic_rewards.clone(),
+			None,
+			BalanceOf::<T>::unique_saturated_from(0u128),
+			BlockNumberFor::<T>::from(0u32),
+			BlockNumberFor::<T>::from(7u32),
+			BlockNumberFor::<T>::from(0u32),
+			5,
+		));
+		<T as Config>::MultiCurrency::deposit(
+			default_currency_id,
+			&caller,
+			BalanceOf::<T>::unique_saturated_from(1_000_000_000_000_000u128),
+		)?;
+		let charge_rewards = vec![(
+			default_currency_id,
+			BalanceOf::<T>::unique_saturated_from(300000u128),
+		)];
+		assert_ok!(Farming::<T>::charge(
+			RawOrigin::Signed(caller.clone()).into(),
+			0,
+			charge_rewards,
+		));
+		assert_ok!(Farming::<T>::deposit_position(
+			RawOrigin::Signed(caller.clone()).into(),
+			0,
+			token_amount,
+			BlockNumberFor::<T>::from(7u32),
+		));
+		let dest: T::AccountId = account("dest", 0, 0);
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller.clone()), 0, dest);
+
+		Ok(())
+	}
+
+	#[benchmark]
+	fn set_farming_boost() -> Result<(), BenchmarkError> {
+		let token_amount = BalanceOf::<T>::unique_saturated_from(1000u128);
+		let default_currency_id = DOT.into();
+		let tokens_proportion = vec![(default_currency_id, Perbill::from_percent(100))];
+		let basic_rewards = vec![(default_currency_id, token_amount)];
+		assert_ok!(Farming::<T>::create_farming_pool(
+			RawOrigin::Root.into(),
+			tokens_proportion.clone(),
+			basic_rewards.clone(),
+			None,
+			BalanceOf::<T>::unique_saturated_from(0u128),
+			BlockNumberFor::<T>::from(0u32),
+			BlockNumberFor::<T>::from(7u32),
+			BlockNumberFor::<T>::from(0u32),
+			5,
+		));
+		let config = FarmingBoostConfig {
+			base: Perbill::from_percent(40),
+		};
+		#[extrinsic_call]
+		_(RawOrigin::Root, 0, Some(config));
+
+		Ok(())
+	}
+
+	#[benchmark]
+	fn accept_position() -> Result<(), BenchmarkError> {
+		let caller: T::AccountId = whitelisted_caller();
+		let token_amount = BalanceOf::<T>::unique_saturated_from(1000u128);
+		let default_currency_id = DOT.into();
+		let tokens_proportion = vec![(default_currency_id, Perbill::from_percent(100))];
+		let basic_rewards = vec![(default_currency_id, token_amount)];
+		assert_ok!(Farming::<T>::create_farming_pool(
+			RawOrigin::Root.into(),
+			tokens_proportion.clone(),
+			basic_rewards.clone(),
+			None,
+			BalanceOf::<T>::unique_saturated_from(0u128),
+			BlockNumberFor::<T>::from(0u32),
+			BlockNumberFor::<T>::from(7u32),
+			BlockNumberFor::<T>::from(0u32),
+			5,
+		));
+		<T as Config>::MultiCurrency::deposit(
+			default_currency_id,
+			&caller,
+			BalanceOf::<T>::unique_saturated_from(1_000_000_000_000_000u128),
+		)?;
+		let charge_rewards = vec![(
+			default_currency_id,
+			BalanceOf::<T>::unique_saturated_from(300000u128),
+		)];
+		assert_ok!(Farming::<T>::charge(
+			RawOrigin::Signed(caller.clone()).into(),
+			0,
+			charge_rewards,
+		));
+		assert_ok!(Farming::<T>::deposit_position(
+			RawOrigin::Signed(caller.clone()).into(),
+			0,
+			token_amount,
+			BlockNumberFor::<T>::from(7u32),
+		));
+		let dest: T::AccountId = account("dest", 0, 0);
+		assert_ok!(Farming::<T>::transfer_position(
+			RawOrigin::Signed(caller.clone()).into(),
+			0,
+			dest.clone(),
+		));
+		#[extrinsic_call]
+		_(RawOrigin::Signed(dest), 0);
+
+		Ok(())
+	}
 }
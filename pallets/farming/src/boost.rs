@@ -18,6 +18,8 @@
 
 use crate::*;
 use bb_bnc::BbBNCInterface;
+use sp_core::U256;
+use sp_runtime::traits::UniqueSaturatedInto;
 
 #[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, Default)]
 pub struct BoostPoolInfo<Balance, BlockNumber> {
@@ -35,6 +37,16 @@ pub struct UserBoostInfo<T: Config> {
 	pub last_vote: BlockNumberFor<T>, // Change only when voting
 }
 
+/// Boost the rewards of the farmers of a pool by their bbBNC, the way veCRV does.
+///
+/// The rewards of a farmer are paid on its working share `min(base * share + (1 - base) *
+/// total_shares * bbBNC / total_bbBNC, share)` instead of its share, so the pool is shared by
+/// the working shares only.
+#[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct FarmingBoostConfig {
+	pub base: Perbill, // The part of the rewards paid without any bbBNC
+}
+
 pub trait BoostInterface<AccountId, CurrencyId, Balance, BlockNumber> {
 	fn refresh_vebnc_farming(who: &AccountId) -> DispatchResult;
 }
@@ -283,4 +295,105 @@ impl<T: Config> Pallet<T> {
 		UserBoostInfos::<T>::insert(who, new_user_boost_info);
 		Ok(())
 	}
+
+	/// The working share of `who` in the pool `pid`, with the current bbBNC balance and supply.
+	///
+	/// The share itself if the pool has no `FarmingBoostConfig`.
+	pub fn calculate_working_share(
+		who: &AccountIdOf<T>,
+		pid: PoolId,
+		share: BalanceOf<T>,
+		total_shares: BalanceOf<T>,
+	) -> Result<BalanceOf<T>, DispatchError> {
+		let Some(config) = FarmingBoostConfigs::<T>::get(pid) else {
+			return Ok(share);
+		};
+		if share.is_zero() {
+			return Ok(Zero::zero());
+		}
+
+		let current_block_number: BlockNumberFor<T> =
+			T::BlockNumberProvider::current_block_number();
+		let vote_amount = T::BbBNC::balance_of(who, None)?;
+		let total_votes = T::BbBNC::total_supply(current_block_number)?;
+
+		// (1 - base) * total_shares * bbBNC / total_bbBNC
+		let voting_share: BalanceOf<T> = u128::try_from(
+			U256::from(total_shares.saturated_into::<u128>())
+				.saturating_mul(U256::from(vote_amount.saturated_into::<u128>()))
+				.checked_div(U256::from(total_votes.saturated_into::<u128>()))
+				.unwrap_or_default()
+				.min(U256::from(u128::MAX)),
+		)
+		.map_err(|_| ArithmeticError::Overflow)?
+		.unique_saturated_into();
+
+		Ok(config
+			.base
+			.mul_floor(share)
+			.saturating_add(config.base.left_from_one().mul_floor(voting_share))
+			.min(share))
+	}
+
+	/// The share the rewards of `who` in the pool `pid` are paid on.
+	pub fn working_share(who: &AccountIdOf<T>, pid: PoolId, share: BalanceOf<T>) -> BalanceOf<T> {
+		WorkingShares::<T>::get(pid, who).unwrap_or(share)
+	}
+
+	/// Recalculate the working share of `who` in the pool `pid`, and move the difference in or
+	/// out of the pool. The rewards accumulated so far stay pending, they were earned on the
+	/// previous working share.
+	pub fn update_farming_boost(who: &AccountIdOf<T>, pid: PoolId) -> DispatchResult {
+		let Some(mut share_info) = SharesAndWithdrawnRewards::<T>::get(pid, who) else {
+			WorkingShares::<T>::remove(pid, who);
+			return Ok(());
+		};
+		let mut pool_info = PoolInfos::<T>::get(pid).ok_or(Error::<T>::PoolDoesNotExist)?;
+
+		let working_share = Self::working_share(who, pid, share_info.share);
+		let new_working_share =
+			Self::calculate_working_share(who, pid, share_info.share, pool_info.total_shares)?;
+		if new_working_share > working_share {
+			let withdrawn_inflation = Self::inflate_rewards(
+				&mut pool_info,
+				new_working_share.saturating_sub(working_share),
+			);
+			Self::add_withdrawn_inflation(&mut share_info, withdrawn_inflation);
+		} else if new_working_share < working_share {
+			Self::deflate_rewards(
+				&mut pool_info,
+				&mut share_info,
+				working_share.saturating_sub(new_working_share),
+			);
+		}
+		if new_working_share != working_share {
+			SharesAndWithdrawnRewards::<T>::insert(pid, who, &share_info);
+			PoolInfos::<T>::insert(pid, pool_info);
+		}
+
+		if !FarmingBoostConfigs::<T>::contains_key(pid) {
+			WorkingShares::<T>::remove(pid, who);
+		} else if WorkingShares::<T>::get(pid, who) != Some(new_working_share) {
+			WorkingShares::<T>::insert(pid, who, new_working_share);
+			Self::deposit_event(Event::FarmingBoostUpdated {
+				who: who.clone(),
+				pid,
+				boost: Perbill::from_rational(new_working_share, share_info.share),
+			});
+		}
+		Ok(())
+	}
+
+	/// The part of its share `who` is paid on in the pool `pid`, `base` for a new farmer.
+	pub fn get_farming_boost(who: &AccountIdOf<T>, pid: PoolId) -> Perbill {
+		match SharesAndWithdrawnRewards::<T>::get(pid, who) {
+			Some(share_info) if !share_info.share.is_zero() => Perbill::from_rational(
+				Self::working_share(who, pid, share_info.share),
+				share_info.share,
+			),
+			_ => FarmingBoostConfigs::<T>::get(pid)
+				.map(|config| config.base)
+				.unwrap_or(Perbill::one()),
+		}
+	}
 }
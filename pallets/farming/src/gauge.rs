@@ -142,6 +142,7 @@ where
 			SharesAndWithdrawnRewards::<T>::get(pid, who).ok_or(Error::<T>::ShareInfoNotExists)?;
 		let pool_info = PoolInfos::<T>::get(pid).ok_or(Error::<T>::PoolDoesNotExist)?;
 		let total_shares = U256::from(pool_info.total_shares.to_owned().saturated_into::<u128>());
+		let working_share = Self::working_share(who, pid, share_info.share);
 		let mut result_vec = Vec::<(T::CurrencyId, BalanceOf<T>)>::new();
 
 		pool_info.rewards.iter().try_for_each(
@@ -152,20 +153,13 @@ where
 					.copied()
 					.unwrap_or_default();
 
-				let total_reward_proportion: BalanceOf<T> = u128::try_from(
-					U256::from(share_info.share.to_owned().saturated_into::<u128>())
-						.saturating_mul(U256::from(
-							total_reward.to_owned().saturated_into::<u128>(),
-						))
-						.checked_div(total_shares)
-						.unwrap_or_default(),
-				)
-				.map_err(|_| ArithmeticError::Overflow)?
-				.unique_saturated_into();
-
-				let reward_to_withdraw = total_reward_proportion
-					.saturating_sub(withdrawn_reward)
-					.min(total_reward.saturating_sub(*total_withdrawn_reward));
+				let reward_to_withdraw = Self::pending_reward(
+					working_share,
+					withdrawn_reward,
+					*total_reward,
+					*total_withdrawn_reward,
+					total_shares,
+				)?;
 
 				if reward_to_withdraw.is_zero() {
 					return Ok(());
@@ -29,6 +29,7 @@ mod benchmarking;
 
 pub mod boost;
 pub mod gauge;
+pub mod position;
 pub mod rewards;
 pub mod weights;
 pub use weights::WeightInfo;
@@ -50,6 +51,7 @@ use frame_system::pallet_prelude::*;
 pub use gauge::*;
 use orml_traits::MultiCurrency;
 pub use pallet::*;
+pub use position::*;
 pub use rewards::*;
 use sp_runtime::SaturatedConversion;
 use sp_std::{cmp::Ordering, collections::btree_map::BTreeMap, vec::Vec};
@@ -129,6 +131,14 @@ pub mod pallet {
 
 		/// The current block number provider.
 		type BlockNumberProvider: BlockNumberProvider<BlockNumber = BlockNumberFor<Self>>;
+
+		/// The longest lock a farming position can be created with.
+		#[pallet::constant]
+		type MaxPositionLock: Get<BlockNumberFor<Self>>;
+
+		/// The extra shares a position locked for `MaxPositionLock` gets.
+		#[pallet::constant]
+		type MaxPositionBoost: Get<Perbill>;
 	}
 
 	#[pallet::event]
@@ -265,6 +275,71 @@ pub mod pallet {
 			/// Charged boost pool id.
 			rewards: Vec<(CurrencyIdOf<T>, BalanceOf<T>)>,
 		},
+		/// A position is deposited.
+		PositionDeposited {
+			/// The owner of the position.
+			who: AccountIdOf<T>,
+			/// Deposited pool id.
+			pid: PoolId,
+			/// The id of the new position.
+			position_id: PositionId,
+			/// Deposited value.
+			add_value: BalanceOf<T>,
+			/// The block the position is locked until.
+			lock_end: BlockNumberFor<T>,
+			/// The boost of the position.
+			boost: Perbill,
+		},
+		/// A position is withdrawn.
+		PositionWithdrawn {
+			/// The owner of the position.
+			who: AccountIdOf<T>,
+			/// Withdrawn position id.
+			position_id: PositionId,
+			/// Withdrawn value.
+			remove_value: BalanceOf<T>,
+		},
+		/// The rewards of a position are claimed.
+		PositionClaimed {
+			/// The owner of the position.
+			who: AccountIdOf<T>,
+			/// Claimed position id.
+			position_id: PositionId,
+		},
+		/// A position transfer is proposed, waiting for the new owner to accept it.
+		PositionTransferProposed {
+			/// The owner of the position.
+			from: AccountIdOf<T>,
+			/// The proposed new owner of the position.
+			to: AccountIdOf<T>,
+			/// Proposed position id.
+			position_id: PositionId,
+		},
+		/// A position is transferred.
+		PositionTransferred {
+			/// The previous owner of the position.
+			from: AccountIdOf<T>,
+			/// The new owner of the position.
+			to: AccountIdOf<T>,
+			/// Transferred position id.
+			position_id: PositionId,
+		},
+		/// The bbBNC boost of a pool is set.
+		FarmingBoostConfigSet {
+			/// Pool id.
+			pid: PoolId,
+			/// The boost config, `None` if removed.
+			config: Option<FarmingBoostConfig>,
+		},
+		/// The bbBNC boost factor of a user is updated.
+		FarmingBoostUpdated {
+			/// The farmer.
+			who: AccountIdOf<T>,
+			/// Pool id.
+			pid: PoolId,
+			/// The part of the share the rewards are paid on.
+			boost: Perbill,
+		},
 	}
 
 	#[pallet::error]
@@ -311,6 +386,23 @@ pub mod pallet {
 		InvalidRemoveAmount,
 		/// User farming pool overflow
 		UserFarmingPoolOverflow,
+		/// The position does not exist.
+		PositionNotExists,
+		/// The caller does not own the position.
+		NotPositionOwner,
+		/// The position is still locked.
+		PositionLocked,
+		/// The lock duration is shorter than the pool's `withdraw_limit_time` or exceeds
+		/// `MaxPositionLock`.
+		InvalidLockDuration,
+		/// The deposit amount of a position can not be zero.
+		InvalidDepositAmount,
+		/// User positions overflow
+		UserPositionsOverflow,
+		/// The boost can only be enabled on a pool without farmers.
+		PoolHasFarmers,
+		/// No transfer of the position is proposed to the caller.
+		NoPendingPositionTransfer,
 	}
 
 	/// Record the id of the new pool.
@@ -409,6 +501,61 @@ pub mod pallet {
 		ValueQuery,
 	>;
 
+	/// Record the id of the new position.
+	#[pallet::storage]
+	pub type NextPositionId<T: Config> = StorageValue<_, PositionId, ValueQuery>;
+
+	/// Record the farming positions.
+	///
+	/// map PositionId => PositionInfo
+	#[pallet::storage]
+	pub type Positions<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		PositionId,
+		PositionInfo<BalanceOf<T>, CurrencyIdOf<T>, BlockNumberFor<T>, AccountIdOf<T>>,
+	>;
+
+	/// The positions owned by the user.
+	#[pallet::storage]
+	pub type UserPositions<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		AccountIdOf<T>,
+		BoundedVec<PositionId, ConstU32<256>>,
+		ValueQuery,
+	>;
+
+	/// The proposed new owners of the positions, who have to accept the transfer.
+	///
+	/// map PositionId => AccountId
+	#[pallet::storage]
+	pub type PendingPositionTransfers<T: Config> =
+		StorageMap<_, Twox64Concat, PositionId, AccountIdOf<T>>;
+
+	/// The positions in the pool.
+	///
+	/// double_map (PoolId, PositionId) => ()
+	#[pallet::storage]
+	pub type PoolPositions<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, PoolId, Twox64Concat, PositionId, ()>;
+
+	/// The bbBNC boost config of the pools.
+	///
+	/// map PoolId => FarmingBoostConfig
+	#[pallet::storage]
+	pub type FarmingBoostConfigs<T: Config> =
+		StorageMap<_, Twox64Concat, PoolId, FarmingBoostConfig>;
+
+	/// The shares the rewards of the farmers are paid on, scaled by their bbBNC boost and
+	/// recalculated at deposit, withdraw, claim and refresh. Farmers without an entry are paid on
+	/// their whole share.
+	///
+	/// double_map (PoolId, AccountId) => BalanceOf
+	#[pallet::storage]
+	pub type WorkingShares<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, PoolId, Blake2_128Concat, AccountIdOf<T>, BalanceOf<T>>;
+
 	#[pallet::hooks]
 	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
 		fn on_initialize(_n: BlockNumberFor<T>) -> Weight {
@@ -650,6 +797,7 @@ pub mod pallet {
 				}
 				Ok(())
 			})?;
+			Self::update_farming_boost(&exchanger, pid)?;
 
 			Self::deposit_event(Event::Deposited {
 				who: exchanger,
@@ -693,6 +841,7 @@ pub mod pallet {
 			);
 
 			Self::remove_share(&exchanger, pid, remove_value, pool_info.withdraw_limit_time)?;
+			Self::update_farming_boost(&exchanger, pid)?;
 			let gauge_pid = pid + GAUGE_BASE_ID;
 			if let Some(mut gauge_pool_info) = PoolInfos::<T>::get(gauge_pid) {
 				let native_remove_value = remove_value.unwrap_or(share_info.share);
@@ -751,6 +900,8 @@ pub mod pallet {
 				Error::<T>::CanNotClaim
 			);
 
+			// Refresh the bbBNC boost before paying the rewards.
+			Self::update_farming_boost(&exchanger, pid)?;
 			Self::claim_rewards(&exchanger, pid)?;
 			Self::claim_rewards(&exchanger, pid + GAUGE_BASE_ID)?;
 			Self::process_withdraw_list(&exchanger, pid, &pool_info, true)?;
@@ -804,18 +955,33 @@ pub mod pallet {
 			let withdraw_limit_time = BlockNumberFor::<T>::default();
 			let retire_limit = RetireLimit::<T>::get();
 			let mut all_retired = true;
+			let mut retire_count: u32 = 0;
 			let share_infos = SharesAndWithdrawnRewards::<T>::iter_prefix_values(pid);
-			for (retire_count, share_info) in share_infos.enumerate() {
-				if retire_count.saturated_into::<u32>() >= retire_limit {
+			for share_info in share_infos {
+				if retire_count >= retire_limit {
 					all_retired = false;
 					break;
 				}
 				let who = share_info.who;
 				Self::remove_share(&who, pid, None, withdraw_limit_time)?;
 				Self::process_withdraw_list(&who, pid, &pool_info, true)?;
+				retire_count.saturating_inc();
+			}
+			// Positions are retired within the same limit, after the shares.
+			let position_ids: Vec<PositionId> = PoolPositions::<T>::iter_key_prefix(pid)
+				.take(retire_limit.saturating_sub(retire_count).saturating_add(1) as usize)
+				.collect();
+			for position_id in position_ids {
+				if retire_count >= retire_limit {
+					all_retired = false;
+					break;
+				}
+				Self::withdraw_position_inner(position_id, None)?;
+				retire_count.saturating_inc();
 			}
-
 			if all_retired {
+				// Withdrawn shares and positions changed the pool info.
+				pool_info = PoolInfos::<T>::get(&pid).ok_or(Error::<T>::PoolDoesNotExist)?;
 				pool_info.state = PoolState::Retired;
 				pool_info.gauge = None;
 				PoolInfos::<T>::insert(&pid, pool_info);
@@ -945,6 +1111,8 @@ pub mod pallet {
 			);
 			let res = SharesAndWithdrawnRewards::<T>::clear_prefix(pid, u32::max_value(), None);
 			ensure!(res.maybe_cursor.is_none(), Error::<T>::PoolNotCleared);
+			let _ = WorkingShares::<T>::clear_prefix(pid, u32::max_value(), None);
+			FarmingBoostConfigs::<T>::remove(pid);
 			PoolInfos::<T>::remove(pid);
 
 			Self::deposit_event(Event::FarmingPoolKilled { pid });
@@ -1153,6 +1321,245 @@ pub mod pallet {
 			let exchanger = ensure_signed(origin)?;
 			Self::refresh_gauge_pool(&exchanger)
 		}
+
+		/// Deposit the pool as a new position.
+		///
+		/// Each position has its own id, lock and boost, and can be transferred or partially
+		/// withdrawn. The boost grows with `lock_duration`, up to `MaxPositionBoost` for
+		/// `MaxPositionLock`. Positions earn the basic rewards of the pool, not the gauge rewards.
+		///
+		/// - `pid`: The pool id.
+		/// - `add_value`: The value to deposit.
+		/// - `lock_duration`: The number of blocks the position can not be withdrawn for, at least
+		///   the `withdraw_limit_time` of the pool since positions are paid out without a delay.
+		#[pallet::call_index(21)]
+		#[pallet::weight(T::WeightInfo::deposit_position())]
+		pub fn deposit_position(
+			origin: OriginFor<T>,
+			pid: PoolId,
+			add_value: BalanceOf<T>,
+			lock_duration: BlockNumberFor<T>,
+		) -> DispatchResult {
+			let exchanger = ensure_signed(origin)?;
+
+			let mut pool_info = PoolInfos::<T>::get(&pid).ok_or(Error::<T>::PoolDoesNotExist)?;
+			ensure!(
+				pool_info.state == PoolState::Ongoing || pool_info.state == PoolState::Charged,
+				Error::<T>::InvalidPoolState
+			);
+			ensure!(!add_value.is_zero(), Error::<T>::InvalidDepositAmount);
+			ensure!(
+				lock_duration >= pool_info.withdraw_limit_time
+					&& lock_duration <= T::MaxPositionLock::get(),
+				Error::<T>::InvalidLockDuration
+			);
+
+			if let PoolState::Charged = pool_info.state {
+				let current_block_number: BlockNumberFor<T> =
+					T::BlockNumberProvider::current_block_number();
+				ensure!(
+					current_block_number >= pool_info.after_block_to_start,
+					Error::<T>::CanNotDeposit
+				);
+			}
+
+			let native_amount = pool_info.basic_token.1.saturating_reciprocal_mul(add_value);
+			pool_info.tokens_proportion.iter().try_for_each(
+				|(token, proportion)| -> DispatchResult {
+					T::MultiCurrency::transfer(
+						*token,
+						&exchanger,
+						&pool_info.keeper,
+						*proportion * native_amount,
+					)
+				},
+			)?;
+			let position_id =
+				Self::create_position(&exchanger, pid, &mut pool_info, add_value, lock_duration)?;
+			let position = Positions::<T>::get(position_id).ok_or(Error::<T>::PositionNotExists)?;
+
+			Self::deposit_event(Event::PositionDeposited {
+				who: exchanger,
+				pid,
+				position_id,
+				add_value,
+				lock_end: position.lock_end,
+				boost: position.boost,
+			});
+			Ok(())
+		}
+
+		/// Withdraw from a position.
+		///
+		/// The rewards of the position are claimed and the withdrawn tokens are transferred to the
+		/// owner immediately. The position should be unlocked, unless the pool is `Dead`.
+		///
+		/// - `position_id`: The position id.
+		/// - `remove_value`: The value to withdraw, all of the position if `None`.
+		#[pallet::call_index(22)]
+		#[pallet::weight(T::WeightInfo::withdraw_position())]
+		pub fn withdraw_position(
+			origin: OriginFor<T>,
+			position_id: PositionId,
+			remove_value: Option<BalanceOf<T>>,
+		) -> DispatchResult {
+			let exchanger = ensure_signed(origin)?;
+
+			let position = Positions::<T>::get(position_id).ok_or(Error::<T>::PositionNotExists)?;
+			ensure!(position.owner == exchanger, Error::<T>::NotPositionOwner);
+			let pool_info =
+				PoolInfos::<T>::get(&position.pid).ok_or(Error::<T>::PoolDoesNotExist)?;
+			ensure!(
+				pool_info.state == PoolState::Ongoing
+					|| pool_info.state == PoolState::Charged
+					|| pool_info.state == PoolState::Dead,
+				Error::<T>::InvalidPoolState
+			);
+			let current_block_number: BlockNumberFor<T> =
+				T::BlockNumberProvider::current_block_number();
+			ensure!(
+				position.lock_end <= current_block_number || pool_info.state == PoolState::Dead,
+				Error::<T>::PositionLocked
+			);
+
+			let remove_value = Self::withdraw_position_inner(position_id, remove_value)?;
+
+			Self::deposit_event(Event::PositionWithdrawn {
+				who: exchanger,
+				position_id,
+				remove_value,
+			});
+			Ok(())
+		}
+
+		/// Claim the rewards of a position.
+		///
+		/// The state of the pool should be `Ongoing` or `Dead`.
+		/// The owner should not claim the rewards within the field `claim_limit_time`.
+		///
+		/// - `position_id`: The position id.
+		#[pallet::call_index(23)]
+		#[pallet::weight(T::WeightInfo::claim_position())]
+		pub fn claim_position(origin: OriginFor<T>, position_id: PositionId) -> DispatchResult {
+			let exchanger = ensure_signed(origin)?;
+
+			let mut position =
+				Positions::<T>::get(position_id).ok_or(Error::<T>::PositionNotExists)?;
+			ensure!(position.owner == exchanger, Error::<T>::NotPositionOwner);
+			let mut pool_info =
+				PoolInfos::<T>::get(&position.pid).ok_or(Error::<T>::PoolDoesNotExist)?;
+			ensure!(
+				pool_info.state == PoolState::Ongoing || pool_info.state == PoolState::Dead,
+				Error::<T>::InvalidPoolState
+			);
+			let current_block_number: BlockNumberFor<T> =
+				T::BlockNumberProvider::current_block_number();
+			ensure!(
+				position
+					.claim_last_block
+					.saturating_add(pool_info.claim_limit_time)
+					<= current_block_number,
+				Error::<T>::CanNotClaim
+			);
+
+			Self::claim_position_rewards(&mut position, &mut pool_info)?;
+			PoolInfos::<T>::insert(&position.pid, &pool_info);
+			Positions::<T>::insert(position_id, position);
+
+			Self::deposit_event(Event::PositionClaimed {
+				who: exchanger,
+				position_id,
+			});
+			Ok(())
+		}
+
+		/// Propose to transfer a position to another account, with its pending rewards.
+		///
+		/// The position moves once `dest` accepts it with `accept_position`, so no account is
+		/// given positions it did not ask for. A new proposal replaces the previous one.
+		///
+		/// - `position_id`: The position id.
+		/// - `dest`: The new owner.
+		#[pallet::call_index(24)]
+		#[pallet::weight(T::WeightInfo::transfer_position())]
+		pub fn transfer_position(
+			origin: OriginFor<T>,
+			position_id: PositionId,
+			dest: AccountIdOf<T>,
+		) -> DispatchResult {
+			let exchanger = ensure_signed(origin)?;
+
+			let position = Positions::<T>::get(position_id).ok_or(Error::<T>::PositionNotExists)?;
+			ensure!(position.owner == exchanger, Error::<T>::NotPositionOwner);
+			PendingPositionTransfers::<T>::insert(position_id, &dest);
+
+			Self::deposit_event(Event::PositionTransferProposed {
+				from: exchanger,
+				to: dest,
+				position_id,
+			});
+			Ok(())
+		}
+
+		/// Set the bbBNC boost of the pool.
+		///
+		/// The rewards of each farmer are paid on a working share scaled by its bbBNC, which is
+		/// recalculated at deposit, withdraw, claim and `refresh`. The boost can only be enabled on
+		/// a pool without farmers, who then join with the `base` part of their deposits. Once the
+		/// boost is removed, the farmers get their whole shares back as they are refreshed.
+		///
+		/// - `pid`: The pool id.
+		/// - `config`: The boost config, `None` to remove the boost.
+		#[pallet::call_index(25)]
+		#[pallet::weight(T::WeightInfo::set_farming_boost())]
+		pub fn set_farming_boost(
+			origin: OriginFor<T>,
+			pid: PoolId,
+			config: Option<FarmingBoostConfig>,
+		) -> DispatchResult {
+			T::ControlOrigin::ensure_origin(origin)?;
+
+			ensure!(
+				PoolInfos::<T>::contains_key(pid),
+				Error::<T>::PoolDoesNotExist
+			);
+			if config.is_some() && !FarmingBoostConfigs::<T>::contains_key(pid) {
+				ensure!(
+					SharesAndWithdrawnRewards::<T>::iter_key_prefix(pid)
+						.next()
+						.is_none(),
+					Error::<T>::PoolHasFarmers
+				);
+			}
+			FarmingBoostConfigs::<T>::set(pid, config.clone());
+
+			Self::deposit_event(Event::FarmingBoostConfigSet { pid, config });
+			Ok(())
+		}
+
+		/// Accept a position transferred to the caller.
+		///
+		/// - `position_id`: The position id.
+		#[pallet::call_index(26)]
+		#[pallet::weight(T::WeightInfo::accept_position())]
+		pub fn accept_position(origin: OriginFor<T>, position_id: PositionId) -> DispatchResult {
+			let exchanger = ensure_signed(origin)?;
+
+			ensure!(
+				PendingPositionTransfers::<T>::get(position_id).as_ref() == Some(&exchanger),
+				Error::<T>::NoPendingPositionTransfer
+			);
+			let position = Positions::<T>::get(position_id).ok_or(Error::<T>::PositionNotExists)?;
+			Self::transfer_position_inner(&position.owner, &exchanger, position_id)?;
+			PendingPositionTransfers::<T>::remove(position_id);
+
+			Self::deposit_event(Event::PositionTransferred {
+				from: position.owner,
+				to: exchanger,
+				position_id,
+			});
+			Ok(())
+		}
 	}
 }
 
@@ -1198,10 +1605,12 @@ impl<T: Config> FarmingInfo<BalanceOf<T>, CurrencyIdOf<T>, T::AccountId> for Pal
 						);
 					}
 				}
+				Self::update_farming_boost(&exchanger, pid)?;
 			} else {
 				// If `SharesAndWithdrawnRewards` returns `None`, remove the `pid` from `UserFarmingPool`.
 				pids.retain(|&x| x != pid);
 				SharesAndWithdrawnRewards::<T>::remove(gauge_pid, &exchanger);
+				WorkingShares::<T>::remove(pid, &exchanger);
 			}
 		}
 		UserFarmingPool::<T>::insert(&exchanger, pids);
@@ -32,7 +32,7 @@ use frame_system::EnsureSignedBy;
 use sp_core::ConstU32;
 use sp_runtime::{
 	traits::{ConvertInto, IdentityLookup},
-	AccountId32, BuildStorage,
+	AccountId32, BuildStorage, Perbill,
 };
 
 use crate as bifrost_farming;
@@ -130,6 +130,8 @@ impl orml_tokens::Config for Runtime {
 parameter_types! {
 	pub const TreasuryAccount: AccountId32 = TREASURY_ACCOUNT;
 	pub const WhitelistMaximumLimit: u32 = 10;
+	pub const FarmingMaxPositionLock: BlockNumber = 1000;
+	pub const FarmingMaxPositionBoost: Perbill = Perbill::from_percent(100);
 }
 
 ord_parameter_types! {
@@ -151,6 +153,8 @@ impl bifrost_farming::Config for Runtime {
 	type WhitelistMaximumLimit = WhitelistMaximumLimit;
 	type GaugeRewardIssuer = FarmingGaugeRewardIssuerPalletId;
 	type BlockNumberProvider = System;
+	type MaxPositionLock = FarmingMaxPositionLock;
+	type MaxPositionBoost = FarmingMaxPositionBoost;
 }
 
 parameter_types! {
@@ -0,0 +1,344 @@
+// This file is part of Bifrost.
+
+// Copyright (C) Liebi Technologies PTE. LTD.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::*;
+use frame_support::pallet_prelude::*;
+use parity_scale_codec::HasCompact;
+use scale_info::TypeInfo;
+use sp_core::U256;
+use sp_runtime::{
+	traits::{CheckedAdd, Saturating, UniqueSaturatedInto, Zero},
+	RuntimeDebug, SaturatedConversion,
+};
+use sp_std::{borrow::ToOwned, collections::btree_map::BTreeMap, prelude::*};
+
+pub type PositionId = u64;
+
+/// A farming position created by a single deposit.
+///
+/// Unlike `ShareInfo`, a position has its own lock and boost, and can be transferred to another
+/// account.
+#[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct PositionInfo<BalanceOf: HasCompact, CurrencyIdOf: Ord, BlockNumberFor, AccountIdOf> {
+	pub owner: AccountIdOf,
+	pub pid: PoolId,
+	/// Deposited value not withdrawn yet
+	pub amount: BalanceOf,
+	/// Shares in the pool, the amount scaled by the boost
+	pub share: BalanceOf,
+	/// Extra shares per deposited value, decided by the lock duration
+	pub boost: Perbill,
+	/// The position can not be withdrawn before this block
+	pub lock_end: BlockNumberFor,
+	pub withdrawn_rewards: BTreeMap<CurrencyIdOf, BalanceOf>,
+	pub claim_last_block: BlockNumberFor,
+}
+
+impl<T: Config> Pallet<T>
+where
+	BlockNumberFor<T>: AtLeast32BitUnsigned + Copy,
+	BalanceOf<T>: AtLeast32BitUnsigned + Copy,
+{
+	/// The boost of a position locked for `lock_duration`, growing linearly up to
+	/// `MaxPositionBoost` for `MaxPositionLock`.
+	pub fn position_boost(lock_duration: BlockNumberFor<T>) -> Perbill {
+		let max_lock = T::MaxPositionLock::get();
+		if max_lock.is_zero() {
+			return Perbill::zero();
+		}
+		Perbill::from_rational(
+			lock_duration.saturated_into::<u128>(),
+			max_lock.saturated_into::<u128>(),
+		) * T::MaxPositionBoost::get()
+	}
+
+	pub fn create_position(
+		who: &T::AccountId,
+		pid: PoolId,
+		pool_info: &mut PoolInfo<BalanceOf<T>, CurrencyIdOf<T>, AccountIdOf<T>, BlockNumberFor<T>>,
+		add_value: BalanceOf<T>,
+		lock_duration: BlockNumberFor<T>,
+	) -> Result<PositionId, DispatchError> {
+		let current_block_number: BlockNumberFor<T> =
+			T::BlockNumberProvider::current_block_number();
+		let boost = Self::position_boost(lock_duration);
+		let share = add_value.saturating_add(boost * add_value);
+		let withdrawn_rewards: BTreeMap<CurrencyIdOf<T>, BalanceOf<T>> =
+			Self::inflate_rewards(pool_info, share)
+				.into_iter()
+				.collect();
+
+		let position_id = NextPositionId::<T>::get();
+		NextPositionId::<T>::set(
+			position_id
+				.checked_add(1)
+				.ok_or(ArithmeticError::Overflow)?,
+		);
+		UserPositions::<T>::try_mutate(who, |position_ids| {
+			position_ids
+				.try_push(position_id)
+				.map_err(|_| Error::<T>::UserPositionsOverflow)
+		})?;
+		PoolPositions::<T>::insert(pid, position_id, ());
+		Positions::<T>::insert(
+			position_id,
+			PositionInfo {
+				owner: who.clone(),
+				pid,
+				amount: add_value,
+				share,
+				boost,
+				lock_end: current_block_number.saturating_add(lock_duration),
+				withdrawn_rewards,
+				claim_last_block: current_block_number,
+			},
+		);
+		PoolInfos::<T>::insert(pid, pool_info);
+		Ok(position_id)
+	}
+
+	/// Pay the pending rewards of the position to its owner.
+	pub fn claim_position_rewards(
+		position: &mut PositionInfo<
+			BalanceOf<T>,
+			CurrencyIdOf<T>,
+			BlockNumberFor<T>,
+			AccountIdOf<T>,
+		>,
+		pool_info: &mut PoolInfo<BalanceOf<T>, CurrencyIdOf<T>, AccountIdOf<T>, BlockNumberFor<T>>,
+	) -> DispatchResult {
+		if position.share.is_zero() {
+			return Ok(());
+		}
+
+		let total_shares = U256::from(pool_info.total_shares.saturated_into::<u128>());
+		pool_info.rewards.iter_mut().try_for_each(
+			|(reward_currency, (total_reward, total_withdrawn_reward))| -> DispatchResult {
+				let withdrawn_reward = position
+					.withdrawn_rewards
+					.get(reward_currency)
+					.copied()
+					.unwrap_or_default();
+				let reward_to_withdraw = Self::pending_reward(
+					position.share,
+					withdrawn_reward,
+					*total_reward,
+					*total_withdrawn_reward,
+					total_shares,
+				)?;
+				if reward_to_withdraw.is_zero() {
+					return Ok(());
+				}
+
+				*total_withdrawn_reward = total_withdrawn_reward.saturating_add(reward_to_withdraw);
+				position.withdrawn_rewards.insert(
+					*reward_currency,
+					withdrawn_reward.saturating_add(reward_to_withdraw),
+				);
+
+				let account_to_send =
+					Self::reward_receiver(*reward_currency, &position.owner, reward_to_withdraw)?;
+				T::MultiCurrency::transfer(
+					*reward_currency,
+					&pool_info.reward_issuer,
+					&account_to_send,
+					reward_to_withdraw,
+				)
+			},
+		)?;
+		position.claim_last_block = T::BlockNumberProvider::current_block_number();
+		Ok(())
+	}
+
+	/// Claim the rewards of the position and return `remove_value` of its deposit to the owner,
+	/// or all of it if `None`. The position is removed once nothing is left in it.
+	pub fn withdraw_position_inner(
+		position_id: PositionId,
+		remove_value: Option<BalanceOf<T>>,
+	) -> Result<BalanceOf<T>, DispatchError> {
+		let mut position = Positions::<T>::get(position_id).ok_or(Error::<T>::PositionNotExists)?;
+		let pid = position.pid;
+		let mut pool_info = PoolInfos::<T>::get(pid).ok_or(Error::<T>::PoolDoesNotExist)?;
+
+		Self::claim_position_rewards(&mut position, &mut pool_info)?;
+
+		let remove_amount = remove_value.unwrap_or(position.amount);
+		ensure!(
+			remove_amount <= position.amount,
+			Error::<T>::InvalidRemoveAmount
+		);
+		let remove_share = if remove_amount == position.amount {
+			position.share
+		} else {
+			u128::try_from(
+				U256::from(position.share.saturated_into::<u128>())
+					.saturating_mul(U256::from(remove_amount.saturated_into::<u128>()))
+					.checked_div(U256::from(position.amount.saturated_into::<u128>()))
+					.unwrap_or_default(),
+			)
+			.map_err(|_| ArithmeticError::Overflow)?
+			.unique_saturated_into()
+		};
+
+		pool_info.total_shares = pool_info.total_shares.saturating_sub(remove_share);
+		// update withdrawn rewards for each reward currency
+		position.withdrawn_rewards.iter_mut().try_for_each(
+			|(reward_currency, withdrawn_reward)| -> DispatchResult {
+				let withdrawn_reward_to_remove: BalanceOf<T> = u128::try_from(
+					U256::from(remove_share.saturated_into::<u128>())
+						.saturating_mul(U256::from(
+							withdrawn_reward.to_owned().saturated_into::<u128>(),
+						))
+						.checked_div(U256::from(position.share.saturated_into::<u128>()))
+						.unwrap_or_default(),
+				)
+				.map_err(|_| ArithmeticError::Overflow)?
+				.unique_saturated_into();
+				if let Some((total_reward, total_withdrawn_reward)) =
+					pool_info.rewards.get_mut(reward_currency)
+				{
+					*total_reward = total_reward.saturating_sub(withdrawn_reward_to_remove);
+					*total_withdrawn_reward =
+						total_withdrawn_reward.saturating_sub(withdrawn_reward_to_remove);
+
+					// remove if all reward is withdrawn
+					if total_reward.is_zero() {
+						pool_info.rewards.remove(reward_currency);
+					}
+				}
+				*withdrawn_reward = withdrawn_reward.saturating_sub(withdrawn_reward_to_remove);
+				Ok(())
+			},
+		)?;
+		position.share = position.share.saturating_sub(remove_share);
+		position.amount = position.amount.saturating_sub(remove_amount);
+
+		let native_amount = pool_info
+			.basic_token
+			.1
+			.saturating_reciprocal_mul(remove_amount);
+		pool_info.tokens_proportion.iter().try_for_each(
+			|(token, &proportion)| -> DispatchResult {
+				let withdraw_amount = proportion * native_amount;
+				let account_to_send =
+					Self::reward_receiver(*token, &position.owner, withdraw_amount)?;
+				T::MultiCurrency::transfer(
+					*token,
+					&pool_info.keeper,
+					&account_to_send,
+					withdraw_amount,
+				)
+			},
+		)?;
+
+		if position.amount.is_zero() {
+			UserPositions::<T>::mutate(&position.owner, |position_ids| {
+				position_ids.retain(|&x| x != position_id);
+			});
+			PoolPositions::<T>::remove(pid, position_id);
+			Positions::<T>::remove(position_id);
+			PendingPositionTransfers::<T>::remove(position_id);
+		} else {
+			Positions::<T>::insert(position_id, position);
+		}
+		PoolInfos::<T>::insert(pid, pool_info);
+		Ok(remove_amount)
+	}
+
+	pub fn transfer_position_inner(
+		from: &T::AccountId,
+		to: &T::AccountId,
+		position_id: PositionId,
+	) -> DispatchResult {
+		Positions::<T>::try_mutate(position_id, |maybe_position| -> DispatchResult {
+			let position = maybe_position
+				.as_mut()
+				.ok_or(Error::<T>::PositionNotExists)?;
+			ensure!(position.owner == *from, Error::<T>::NotPositionOwner);
+			UserPositions::<T>::mutate(from, |position_ids| {
+				position_ids.retain(|&x| x != position_id);
+			});
+			UserPositions::<T>::try_mutate(to, |position_ids| {
+				position_ids
+					.try_push(position_id)
+					.map_err(|_| Error::<T>::UserPositionsOverflow)
+			})?;
+			position.owner = to.clone();
+			Ok(())
+		})
+	}
+
+	/// The account to send `amount` of `currency_id` to, the treasury if `who` would stay below
+	/// the existential deposit.
+	fn reward_receiver(
+		currency_id: CurrencyIdOf<T>,
+		who: &T::AccountId,
+		amount: BalanceOf<T>,
+	) -> Result<T::AccountId, DispatchError> {
+		if amount < T::MultiCurrency::minimum_balance(currency_id) {
+			let receiver_balance_after = T::MultiCurrency::total_balance(currency_id, who)
+				.checked_add(&amount)
+				.ok_or(ArithmeticError::Overflow)?;
+			if receiver_balance_after < T::MultiCurrency::minimum_balance(currency_id) {
+				return Ok(T::TreasuryAccount::get());
+			}
+		}
+		Ok(who.clone())
+	}
+
+	/// The positions of `who`, with the deposited value and the pending rewards of each.
+	pub fn get_user_positions(
+		who: &T::AccountId,
+	) -> Vec<(
+		PositionId,
+		PoolId,
+		BalanceOf<T>,
+		Vec<(CurrencyIdOf<T>, BalanceOf<T>)>,
+	)> {
+		UserPositions::<T>::get(who)
+			.into_iter()
+			.filter_map(|position_id| {
+				let position = Positions::<T>::get(position_id)?;
+				let pool_info = PoolInfos::<T>::get(position.pid)?;
+				let total_shares = U256::from(pool_info.total_shares.saturated_into::<u128>());
+				let rewards = pool_info
+					.rewards
+					.iter()
+					.filter_map(
+						|(reward_currency, (total_reward, total_withdrawn_reward))| {
+							let reward = Self::pending_reward(
+								position.share,
+								position
+									.withdrawn_rewards
+									.get(reward_currency)
+									.copied()
+									.unwrap_or_default(),
+								*total_reward,
+								*total_withdrawn_reward,
+								total_shares,
+							)
+							.ok()?;
+							(!reward.is_zero()).then_some((*reward_currency, reward))
+						},
+					)
+					.collect();
+				Some((position_id, position.pid, position.amount, rewards))
+			})
+			.collect()
+	}
+}
@@ -181,16 +181,13 @@ impl<T: Config> Pallet<T> {
 		})
 	}
 
-	pub fn add_share(
-		who: &T::AccountId,
-		pid: PoolId,
+	/// Add `add_amount` to the total shares of the pool and inflate its rewards, so that the new
+	/// shares do not take the rewards accumulated before. Returns the inflation of each reward
+	/// currency, to be recorded as withdrawn by the new shares.
+	pub fn inflate_rewards(
 		pool_info: &mut PoolInfo<BalanceOf<T>, CurrencyIdOf<T>, AccountIdOf<T>, BlockNumberFor<T>>,
 		add_amount: BalanceOf<T>,
-	) {
-		if add_amount.is_zero() {
-			return;
-		}
-
+	) -> Vec<(CurrencyIdOf<T>, BalanceOf<T>)> {
 		let initial_total_shares = pool_info.total_shares;
 		pool_info.total_shares = pool_info.total_shares.saturating_add(add_amount);
 
@@ -225,13 +222,58 @@ impl<T: Config> Pallet<T> {
 			},
 		);
 
-		let current_block_number: BlockNumberFor<T> =
-			T::BlockNumberProvider::current_block_number();
+		withdrawn_inflation
+	}
 
-		let mut share_info = SharesAndWithdrawnRewards::<T>::get(pid, who)
-			.unwrap_or_else(|| ShareInfo::new(who.clone(), current_block_number));
-		share_info.share = share_info.share.saturating_add(add_amount);
-		// update withdrawn inflation for each reward currency
+	/// Remove `remove_amount` of the shares of `share_info` from the total shares of the pool,
+	/// with the rewards they are entitled to, the inverse of `inflate_rewards`. The rewards
+	/// pending on the removed shares stay with the holder.
+	pub fn deflate_rewards(
+		pool_info: &mut PoolInfo<BalanceOf<T>, CurrencyIdOf<T>, AccountIdOf<T>, BlockNumberFor<T>>,
+		share_info: &mut ShareInfo<
+			BalanceOf<T>,
+			CurrencyIdOf<T>,
+			BlockNumberFor<T>,
+			AccountIdOf<T>,
+		>,
+		remove_amount: BalanceOf<T>,
+	) {
+		let initial_total_shares = pool_info.total_shares;
+		pool_info.total_shares = pool_info.total_shares.saturating_sub(remove_amount);
+
+		pool_info.rewards.iter_mut().for_each(
+			|(reward_currency, (total_reward, total_withdrawn_reward))| {
+				let Some(withdrawn_reward) = share_info.withdrawn_rewards.get_mut(reward_currency)
+				else {
+					return;
+				};
+				let reward_deflation: BalanceOf<T> = u128::try_from(
+					U256::from(remove_amount.saturated_into::<u128>())
+						.saturating_mul(total_reward.to_owned().saturated_into::<u128>().into())
+						.checked_div(initial_total_shares.saturated_into::<u128>().into())
+						.unwrap_or_default(),
+				)
+				.ok()
+				.unwrap_or_default()
+				.saturated_into::<BalanceOf<T>>()
+				.min(*withdrawn_reward);
+				*total_reward = total_reward.saturating_sub(reward_deflation);
+				*total_withdrawn_reward = total_withdrawn_reward.saturating_sub(reward_deflation);
+				*withdrawn_reward = withdrawn_reward.saturating_sub(reward_deflation);
+			},
+		);
+	}
+
+	/// Record the inflation of `inflate_rewards` as withdrawn by the new shares.
+	pub fn add_withdrawn_inflation(
+		share_info: &mut ShareInfo<
+			BalanceOf<T>,
+			CurrencyIdOf<T>,
+			BlockNumberFor<T>,
+			AccountIdOf<T>,
+		>,
+		withdrawn_inflation: Vec<(CurrencyIdOf<T>, BalanceOf<T>)>,
+	) {
 		withdrawn_inflation
 			.into_iter()
 			.for_each(|(reward_currency, reward_inflation)| {
@@ -243,6 +285,62 @@ impl<T: Config> Pallet<T> {
 					})
 					.or_insert(reward_inflation);
 			});
+	}
+
+	/// The reward of `share` not withdrawn yet, bounded by the reward left in the pool.
+	pub fn pending_reward(
+		share: BalanceOf<T>,
+		withdrawn_reward: BalanceOf<T>,
+		total_reward: BalanceOf<T>,
+		total_withdrawn_reward: BalanceOf<T>,
+		total_shares: U256,
+	) -> Result<BalanceOf<T>, DispatchError> {
+		let total_reward_proportion: BalanceOf<T> = u128::try_from(
+			U256::from(share.saturated_into::<u128>())
+				.saturating_mul(U256::from(total_reward.saturated_into::<u128>()))
+				.checked_div(total_shares)
+				.unwrap_or_default(),
+		)
+		.map_err(|_| ArithmeticError::Overflow)?
+		.unique_saturated_into();
+
+		Ok(total_reward_proportion
+			.saturating_sub(withdrawn_reward)
+			.min(total_reward.saturating_sub(total_withdrawn_reward)))
+	}
+
+	pub fn add_share(
+		who: &T::AccountId,
+		pid: PoolId,
+		pool_info: &mut PoolInfo<BalanceOf<T>, CurrencyIdOf<T>, AccountIdOf<T>, BlockNumberFor<T>>,
+		add_amount: BalanceOf<T>,
+	) {
+		if add_amount.is_zero() {
+			return;
+		}
+
+		let current_block_number: BlockNumberFor<T> =
+			T::BlockNumberProvider::current_block_number();
+
+		let mut share_info = SharesAndWithdrawnRewards::<T>::get(pid, who)
+			.unwrap_or_else(|| ShareInfo::new(who.clone(), current_block_number));
+		// A boosted pool takes the new shares in with the `base` part, until the boost is updated.
+		let working_amount = match FarmingBoostConfigs::<T>::get(pid) {
+			Some(config) => {
+				let working_amount = config.base.mul_floor(add_amount);
+				WorkingShares::<T>::insert(
+					pid,
+					who,
+					Self::working_share(who, pid, share_info.share).saturating_add(working_amount),
+				);
+				working_amount
+			}
+			None => add_amount,
+		};
+		let withdrawn_inflation = Self::inflate_rewards(pool_info, working_amount);
+
+		share_info.share = share_info.share.saturating_add(add_amount);
+		Self::add_withdrawn_inflation(&mut share_info, withdrawn_inflation);
 		SharesAndWithdrawnRewards::<T>::insert(pid, who, share_info);
 		PoolInfos::<T>::insert(&pid, pool_info);
 	}
@@ -292,7 +390,27 @@ impl<T: Config> Pallet<T> {
 
 					let removing_share = U256::from(remove_amount.saturated_into::<u128>());
 
-					pool_info.total_shares = pool_info.total_shares.saturating_sub(remove_amount);
+					// The working share is removed in proportion to the share.
+					let working_share = Self::working_share(who, pool, share_info.share);
+					let removing_working_share: BalanceOf<T> = u128::try_from(
+						removing_share
+							.saturating_mul(working_share.saturated_into::<u128>().into())
+							.checked_div(share_info.share.saturated_into::<u128>().into())
+							.unwrap_or_default(),
+					)
+					.map_err(|_| ArithmeticError::Overflow)?
+					.unique_saturated_into();
+					if WorkingShares::<T>::contains_key(pool, who) {
+						WorkingShares::<T>::insert(
+							pool,
+							who,
+							working_share.saturating_sub(removing_working_share),
+						);
+					}
+
+					pool_info.total_shares = pool_info
+						.total_shares
+						.saturating_sub(removing_working_share);
 
 					// update withdrawn rewards for each reward currency
 					share_info.withdrawn_rewards.iter_mut().try_for_each(
@@ -355,6 +473,7 @@ impl<T: Config> Pallet<T> {
 
 						let total_shares =
 							U256::from(pool_info.total_shares.to_owned().saturated_into::<u128>());
+						let working_share = Self::working_share(who, pool, share_info.share);
 						pool_info.rewards.iter_mut().try_for_each(
 							|(reward_currency, (total_reward, total_withdrawn_reward))|  -> DispatchResult {
 								let withdrawn_reward = share_info
@@ -363,20 +482,13 @@ impl<T: Config> Pallet<T> {
 									.copied()
 									.unwrap_or_default();
 
-								let total_reward_proportion: BalanceOf<T> = u128::try_from(
-									U256::from(share_info.share.to_owned().saturated_into::<u128>())
-										.saturating_mul(U256::from(
-											total_reward.to_owned().saturated_into::<u128>(),
-										))
-										.checked_div(total_shares)
-										.unwrap_or_default(),
-								)
-								.map_err(|_| ArithmeticError::Overflow)?
-								.unique_saturated_into();
-
-								let reward_to_withdraw = total_reward_proportion
-									.saturating_sub(withdrawn_reward)
-									.min(total_reward.saturating_sub(*total_withdrawn_reward));
+								let reward_to_withdraw = Self::pending_reward(
+									working_share,
+									withdrawn_reward,
+									*total_reward,
+									*total_withdrawn_reward,
+									total_shares,
+								)?;
 
 								if reward_to_withdraw.is_zero() {
 									return Ok(());
@@ -516,6 +628,7 @@ impl<T: Config> Pallet<T> {
 			});
 			SharesAndWithdrawnRewards::<T>::remove(gauge_pid, &exchanger);
 		}
+		Self::update_farming_boost(exchanger, pid)?;
 
 		Ok(())
 	}
@@ -821,3 +821,264 @@ fn refresh_should_work() {
 			);
 		})
 }
+
+#[test]
+fn position() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+			let tokens_proportion = vec![(KSM, Perbill::from_percent(100))];
+			let basic_rewards = vec![(KSM, 1000)];
+			assert_ok!(Farming::create_farming_pool(
+				RuntimeOrigin::signed(ALICE),
+				tokens_proportion,
+				basic_rewards,
+				None,
+				0,
+				0,
+				10,
+				0,
+				1
+			));
+			let pid = 0;
+			assert_ok!(Farming::charge(
+				RuntimeOrigin::signed(BOB),
+				pid,
+				vec![(KSM, 100000)],
+			));
+			assert_err!(
+				Farming::deposit_position(RuntimeOrigin::signed(ALICE), pid, 1000, 1001),
+				Error::<Runtime>::InvalidLockDuration
+			);
+			assert_ok!(Farming::deposit_position(
+				RuntimeOrigin::signed(ALICE),
+				pid,
+				1000,
+				500
+			));
+			let position = Positions::<Runtime>::get(0).unwrap();
+			assert_eq!(position.boost, Perbill::from_percent(50));
+			assert_eq!(position.share, 1500);
+			assert_eq!(position.lock_end, 501);
+			assert_eq!(Tokens::free_balance(KSM, &ALICE), 2000);
+
+			Farming::on_initialize(0);
+			Farming::on_initialize(0);
+			assert_eq!(
+				Farming::get_user_positions(&ALICE),
+				vec![(0, pid, 1000, vec![(KSM, 1000)])]
+			);
+			assert_err!(
+				Farming::withdraw_position(RuntimeOrigin::signed(ALICE), 0, None),
+				Error::<Runtime>::PositionLocked
+			);
+			assert_err!(
+				Farming::transfer_position(RuntimeOrigin::signed(BOB), 0, BOB),
+				Error::<Runtime>::NotPositionOwner
+			);
+			assert_ok!(Farming::transfer_position(
+				RuntimeOrigin::signed(ALICE),
+				0,
+				BOB
+			));
+			System::assert_last_event(RuntimeEvent::Farming(Event::PositionTransferProposed {
+				from: ALICE,
+				to: BOB,
+				position_id: 0,
+			}));
+			// The position only moves once the new owner accepts it.
+			assert_eq!(UserPositions::<Runtime>::get(ALICE).into_inner(), vec![0]);
+			assert_eq!(UserPositions::<Runtime>::get(BOB).into_inner(), vec![]);
+			assert_err!(
+				Farming::accept_position(RuntimeOrigin::signed(CHARLIE), 0),
+				Error::<Runtime>::NoPendingPositionTransfer
+			);
+			assert_ok!(Farming::accept_position(RuntimeOrigin::signed(BOB), 0));
+			System::assert_last_event(RuntimeEvent::Farming(Event::PositionTransferred {
+				from: ALICE,
+				to: BOB,
+				position_id: 0,
+			}));
+			assert_eq!(PendingPositionTransfers::<Runtime>::get(0), None);
+			assert_err!(
+				Farming::accept_position(RuntimeOrigin::signed(BOB), 0),
+				Error::<Runtime>::NoPendingPositionTransfer
+			);
+			assert_eq!(UserPositions::<Runtime>::get(ALICE).into_inner(), vec![]);
+			assert_eq!(UserPositions::<Runtime>::get(BOB).into_inner(), vec![0]);
+
+			assert_ok!(Farming::claim_position(RuntimeOrigin::signed(BOB), 0));
+			assert_eq!(Tokens::free_balance(KSM, &BOB), 9_901_000);
+
+			// A later position does not take the rewards accumulated before.
+			assert_err!(
+				Farming::deposit_position(RuntimeOrigin::signed(ALICE), pid, 1000, 9),
+				Error::<Runtime>::InvalidLockDuration
+			);
+			assert_ok!(Farming::deposit_position(
+				RuntimeOrigin::signed(ALICE),
+				pid,
+				1000,
+				10
+			));
+			assert_eq!(
+				Farming::get_user_positions(&ALICE),
+				vec![(1, pid, 1000, vec![])]
+			);
+
+			System::set_block_number(501);
+			assert_err!(
+				Farming::withdraw_position(RuntimeOrigin::signed(BOB), 0, Some(1001)),
+				Error::<Runtime>::InvalidRemoveAmount
+			);
+			assert_ok!(Farming::withdraw_position(
+				RuntimeOrigin::signed(BOB),
+				0,
+				Some(400)
+			));
+			let position = Positions::<Runtime>::get(0).unwrap();
+			assert_eq!(position.amount, 600);
+			assert_eq!(position.share, 900);
+			assert_eq!(Tokens::free_balance(KSM, &BOB), 9_901_400);
+
+			assert_ok!(Farming::set_retire_limit(RuntimeOrigin::signed(ALICE), 10));
+			assert_ok!(Farming::close_pool(RuntimeOrigin::signed(ALICE), pid));
+			assert_ok!(Farming::force_retire_pool(
+				RuntimeOrigin::signed(ALICE),
+				pid
+			));
+			assert_eq!(Positions::<Runtime>::get(0), None);
+			assert_eq!(Positions::<Runtime>::get(1), None);
+			assert_eq!(UserPositions::<Runtime>::get(BOB).into_inner(), vec![]);
+			assert_eq!(Tokens::free_balance(KSM, &BOB), 9_902_000);
+			assert_eq!(Tokens::free_balance(KSM, &ALICE), 2000);
+			assert_eq!(
+				PoolInfos::<Runtime>::get(pid).unwrap().state,
+				PoolState::Retired
+			);
+		});
+}
+
+#[test]
+fn farming_boost() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob()
+		.build()
+		.execute_with(|| {
+			let (pid, tokens) = init_no_gauge();
+			assert_ok!(BbBNC::set_config(
+				RuntimeOrigin::signed(ALICE),
+				Some(0),
+				Some(7 * 86400 / 12)
+			));
+			assert_ok!(BbBNC::create_lock_inner(
+				&ALICE,
+				100_000_000_000,
+				System::block_number() + (4 * 365 * 86400 - 7 * 86400) / 12
+			));
+
+			let config = FarmingBoostConfig {
+				base: Perbill::from_percent(40),
+			};
+			assert_err!(
+				Farming::set_farming_boost(RuntimeOrigin::signed(ALICE), 1, Some(config.clone())),
+				Error::<Runtime>::PoolDoesNotExist
+			);
+			// ALICE already farms in the pool.
+			assert_err!(
+				Farming::set_farming_boost(RuntimeOrigin::signed(ALICE), pid, Some(config.clone())),
+				Error::<Runtime>::PoolHasFarmers
+			);
+
+			assert_ok!(Farming::create_farming_pool(
+				RuntimeOrigin::signed(ALICE),
+				vec![(KSM, Perbill::from_percent(100))],
+				vec![(KSM, 1000)],
+				None,
+				0,
+				0,
+				10,
+				0,
+				1
+			));
+			let pid = 1;
+			assert_ok!(Farming::charge(
+				RuntimeOrigin::signed(BOB),
+				pid,
+				vec![(KSM, 100000)],
+			));
+			assert_ok!(Farming::set_farming_boost(
+				RuntimeOrigin::signed(ALICE),
+				pid,
+				Some(config.clone())
+			));
+			System::assert_last_event(RuntimeEvent::Farming(Event::FarmingBoostConfigSet {
+				pid,
+				config: Some(config),
+			}));
+			// A new farmer starts from the base.
+			assert_eq!(
+				Farming::get_farming_boost(&BOB, pid),
+				Perbill::from_percent(40)
+			);
+
+			// BOB has no bbBNC, so only the base of the deposit works.
+			assert_ok!(Farming::deposit(RuntimeOrigin::signed(BOB), pid, tokens));
+			assert_eq!(WorkingShares::<Runtime>::get(pid, BOB), Some(400));
+			assert_eq!(PoolInfos::<Runtime>::get(pid).unwrap().total_shares, 400);
+
+			assert_ok!(Farming::deposit(RuntimeOrigin::signed(ALICE), pid, tokens));
+			let alice_working_share = WorkingShares::<Runtime>::get(pid, ALICE).unwrap();
+			assert!(alice_working_share > 400);
+			System::assert_has_event(RuntimeEvent::Farming(Event::FarmingBoostUpdated {
+				who: ALICE,
+				pid,
+				boost: Perbill::from_rational(alice_working_share, 1000),
+			}));
+			assert_eq!(
+				PoolInfos::<Runtime>::get(pid).unwrap().total_shares,
+				400 + alice_working_share
+			);
+
+			Farming::on_initialize(0);
+			Farming::on_initialize(0);
+			let (total_reward, _) = *PoolInfos::<Runtime>::get(pid)
+				.unwrap()
+				.rewards
+				.get(&KSM)
+				.unwrap();
+			let bob_balance = Tokens::free_balance(KSM, &BOB);
+			assert_ok!(Farming::claim(RuntimeOrigin::signed(BOB), pid));
+			// BOB is paid on his working share, and nothing goes back to the pool.
+			assert_eq!(
+				Tokens::free_balance(KSM, &BOB) - bob_balance,
+				400 * total_reward / (400 + alice_working_share)
+			);
+			assert_eq!(
+				PoolInfos::<Runtime>::get(pid)
+					.unwrap()
+					.rewards
+					.get(&KSM)
+					.unwrap()
+					.0,
+				total_reward
+			);
+
+			assert_ok!(Farming::set_farming_boost(
+				RuntimeOrigin::signed(ALICE),
+				pid,
+				None
+			));
+			// The shares come back as the farmers are refreshed.
+			assert_eq!(WorkingShares::<Runtime>::get(pid, BOB), Some(400));
+			assert_ok!(Farming::refresh(RuntimeOrigin::signed(BOB)));
+			assert_eq!(WorkingShares::<Runtime>::get(pid, BOB), None);
+			assert_eq!(Farming::get_farming_boost(&BOB, pid), Perbill::one());
+			assert_eq!(
+				PoolInfos::<Runtime>::get(pid).unwrap().total_shares,
+				1000 + alice_working_share
+			);
+		})
+}
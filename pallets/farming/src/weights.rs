@@ -74,6 +74,12 @@ pub trait WeightInfo {
 	fn end_boost_round() -> Weight;
 	fn charge_boost() -> Weight;
 	fn refresh() -> Weight;
+	fn deposit_position() -> Weight;
+	fn withdraw_position() -> Weight;
+	fn claim_position() -> Weight;
+	fn transfer_position() -> Weight;
+	fn set_farming_boost() -> Weight;
+	fn accept_position() -> Weight;
 }
 
 // For backwards compatibility and tests
@@ -379,4 +385,100 @@ impl WeightInfo for () {
 		Weight::from_parts(17_023_000, 6544)
 			.saturating_add(RocksDbWeight::get().reads(5))
 	}
+	/// Storage: `Farming::PoolInfos` (r:1 w:1)
+	/// Proof: `Farming::PoolInfos` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Tokens::Accounts` (r:2 w:2)
+	/// Proof: `Tokens::Accounts` (`max_values`: None, `max_size`: Some(118), added: 2593, mode: `MaxEncodedLen`)
+	/// Storage: `Farming::NextPositionId` (r:1 w:1)
+	/// Proof: `Farming::NextPositionId` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `Farming::UserPositions` (r:1 w:1)
+	/// Proof: `Farming::UserPositions` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Farming::Positions` (r:1 w:1)
+	/// Proof: `Farming::Positions` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Farming::PoolPositions` (r:0 w:1)
+	/// Proof: `Farming::PoolPositions` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn deposit_position() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1752`
+		//  Estimated: `6176`
+		// Minimum execution time: 152_000_000 picoseconds.
+		Weight::from_parts(152_000_000, 6176)
+			.saturating_add(RocksDbWeight::get().reads(6_u64))
+			.saturating_add(RocksDbWeight::get().writes(7_u64))
+	}
+	/// Storage: `Farming::Positions` (r:2 w:1)
+	/// Proof: `Farming::Positions` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Farming::PoolInfos` (r:2 w:1)
+	/// Proof: `Farming::PoolInfos` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Tokens::Accounts` (r:4 w:4)
+	/// Proof: `Tokens::Accounts` (`max_values`: None, `max_size`: Some(118), added: 2593, mode: `MaxEncodedLen`)
+	/// Storage: `Farming::UserPositions` (r:1 w:1)
+	/// Proof: `Farming::UserPositions` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Farming::PoolPositions` (r:0 w:1)
+	/// Proof: `Farming::PoolPositions` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn withdraw_position() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1987`
+		//  Estimated: `11176`
+		// Minimum execution time: 171_000_000 picoseconds.
+		Weight::from_parts(171_000_000, 11176)
+			.saturating_add(RocksDbWeight::get().reads(9_u64))
+			.saturating_add(RocksDbWeight::get().writes(8_u64))
+	}
+	/// Storage: `Farming::Positions` (r:1 w:1)
+	/// Proof: `Farming::Positions` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Farming::PoolInfos` (r:1 w:1)
+	/// Proof: `Farming::PoolInfos` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Tokens::Accounts` (r:2 w:2)
+	/// Proof: `Tokens::Accounts` (`max_values`: None, `max_size`: Some(118), added: 2593, mode: `MaxEncodedLen`)
+	fn claim_position() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1861`
+		//  Estimated: `6176`
+		// Minimum execution time: 98_000_000 picoseconds.
+		Weight::from_parts(98_000_000, 6176)
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+	}
+	/// Storage: `Farming::Positions` (r:1 w:0)
+	/// Proof: `Farming::Positions` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Farming::PendingPositionTransfers` (r:0 w:1)
+	/// Proof: `Farming::PendingPositionTransfers` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn transfer_position() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `412`
+		//  Estimated: `3877`
+		// Minimum execution time: 16_000_000 picoseconds.
+		Weight::from_parts(16_000_000, 3877)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `Farming::PoolInfos` (r:1 w:0)
+	/// Proof: `Farming::PoolInfos` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Farming::FarmingBoostConfigs` (r:0 w:1)
+	/// Proof: `Farming::FarmingBoostConfigs` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn set_farming_boost() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1220`
+		//  Estimated: `4685`
+		// Minimum execution time: 22_000_000 picoseconds.
+		Weight::from_parts(22_000_000, 4685)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `Farming::PendingPositionTransfers` (r:1 w:1)
+	/// Proof: `Farming::PendingPositionTransfers` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Farming::Positions` (r:1 w:1)
+	/// Proof: `Farming::Positions` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Farming::UserPositions` (r:2 w:2)
+	/// Proof: `Farming::UserPositions` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn accept_position() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `480`
+		//  Estimated: `6420`
+		// Minimum execution time: 36_000_000 picoseconds.
+		Weight::from_parts(36_000_000, 6420)
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+	}
 }
@@ -42,7 +42,7 @@ use orml_traits::{
 	location::RelativeReserveProvider, parameter_type_with_key, DataFeeder, DataProvider,
 	DataProviderExtended, MultiCurrency,
 };
-use sp_core::ConstU32;
+use sp_core::{ConstU32, ConstU64};
 use sp_runtime::{
 	traits::{AccountIdConversion, IdentityLookup, UniqueSaturatedInto},
 	AccountId32, BuildStorage, SaturatedConversion,
@@ -193,9 +193,21 @@ impl pallet_prices::Config for Runtime {
 	type RelayCurrency = RelayCurrencyId;
 	type Assets = Currencies;
 	type CurrencyIdConvert = AssetIdMaps<Runtime>;
+	type VtokenMinting = ();
+	type LendMarket = ();
+	type StableLpToken = ();
+	type UnixTime = MockUnixTime;
+	type StableTwap = ();
 	type WeightInfo = ();
 }
 
+pub struct MockUnixTime;
+impl frame_support::traits::UnixTime for MockUnixTime {
+	fn now() -> core::time::Duration {
+		core::time::Duration::ZERO
+	}
+}
+
 // pallet-price is using for benchmark compilation
 pub type TimeStampedPrice = orml_oracle::TimestampedValue<Price, Moment>;
 pub struct MockDataProvider;
@@ -364,6 +376,7 @@ impl bifrost_slp::Config for Runtime {
 	type AssetIdMaps = AssetIdMaps<Runtime>;
 	type TreasuryAccount = TreasuryAccount;
 	type BlockNumberProvider = System;
+	type XcmQueryTimeout = ConstU64<1000>;
 }
 
 parameter_type_with_key! {
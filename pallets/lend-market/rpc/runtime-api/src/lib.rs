@@ -21,6 +21,7 @@
 use bifrost_primitives::{CurrencyId, Liquidity, Rate, Ratio, Shortfall};
 use parity_scale_codec::Codec;
 use sp_runtime::{DispatchError, FixedU128};
+use sp_std::vec::Vec;
 
 sp_api::decl_runtime_apis! {
 	pub trait LendMarketApi<AccountId, Balance> where
@@ -29,5 +30,6 @@ sp_api::decl_runtime_apis! {
 		fn get_account_liquidity(account: AccountId) -> Result<(Liquidity, Shortfall, Liquidity, Shortfall), DispatchError>;
 		fn get_market_status(asset_id: CurrencyId) -> Result<(Rate, Rate, Rate, Ratio, Balance, Balance, FixedU128), DispatchError>;
 		fn get_liquidation_threshold_liquidity(account: AccountId) -> Result<(Liquidity, Shortfall, Liquidity, Shortfall), DispatchError>;
+		fn get_liquidatable_accounts(start_key: Option<Vec<u8>>, limit: u32) -> (Vec<AccountId>, Option<Vec<u8>>);
 	}
 }
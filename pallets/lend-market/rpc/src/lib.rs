@@ -29,6 +29,7 @@ use jsonrpsee::{
 use parity_scale_codec::Codec;
 use sp_api::ProvideRuntimeApi;
 use sp_blockchain::HeaderBackend;
+use sp_core::Bytes;
 use sp_rpc::number::NumberOrHex;
 use sp_runtime::{traits::Block as BlockT, FixedU128};
 
@@ -55,6 +56,13 @@ where
 		account: AccountId,
 		at: Option<BlockHash>,
 	) -> RpcResult<(Liquidity, Shortfall, Liquidity, Shortfall)>;
+	#[method(name = "lend_market_getLiquidatableAccounts")]
+	fn get_liquidatable_accounts(
+		&self,
+		start_key: Option<Bytes>,
+		limit: u32,
+		at: Option<BlockHash>,
+	) -> RpcResult<(Vec<AccountId>, Option<Bytes>)>;
 }
 
 /// A struct that implements the [`LendMarketApi`].
@@ -162,6 +170,23 @@ where
 			.map_err(runtime_error_into_rpc_error)?
 			.map_err(account_liquidity_error_into_rpc_error)
 	}
+
+	fn get_liquidatable_accounts(
+		&self,
+		start_key: Option<Bytes>,
+		limit: u32,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<(Vec<AccountId>, Option<Bytes>)> {
+		let api = self.client.runtime_api();
+		let at = at.unwrap_or(
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash,
+		);
+		let (accounts, next_key) = api
+			.get_liquidatable_accounts(at, start_key.map(|key| key.to_vec()), limit)
+			.map_err(runtime_error_into_rpc_error)?;
+		Ok((accounts, next_key.map(Into::into)))
+	}
 }
 
 /// Converts a runtime trap into an RPC error.
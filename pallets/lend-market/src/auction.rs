@@ -0,0 +1,313 @@
+// This file is part of Bifrost.
+
+// Copyright (C) Liebi Technologies PTE. LTD.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use bifrost_primitives::Timestamp;
+use sp_runtime::{traits::Zero, DispatchResult};
+use sp_std::collections::btree_set::BTreeSet;
+
+use crate::*;
+
+impl<T: Config> Pallet<T> {
+	/// Whether `borrower` can be liquidated right now.
+	pub fn is_liquidatable(borrower: &T::AccountId) -> Result<bool, DispatchError> {
+		let (liquidity, shortfall, lf_liquidity, _) =
+			Self::get_account_liquidation_threshold_liquidity(borrower)?;
+
+		// See `liquidate_borrow_allowed`.
+		Ok(!shortfall.is_zero() || liquidity < lf_liquidity)
+	}
+
+	/// Returns the accounts that can be liquidated right now among the next `limit` borrow
+	/// records after the raw storage key `start_key`, together with the raw key to continue
+	/// from, or `None` once all the borrow records have been scanned.
+	pub fn get_liquidatable_accounts(
+		start_key: Option<Vec<u8>>,
+		limit: u32,
+	) -> (Vec<T::AccountId>, Option<Vec<u8>>) {
+		let mut iter = match start_key {
+			Some(start_key) => AccountBorrows::<T>::iter_from(start_key),
+			None => AccountBorrows::<T>::iter(),
+		};
+		let mut borrowers = BTreeSet::new();
+		let mut scanned = 0;
+		let next_key = loop {
+			if scanned >= limit.min(MAX_LIQUIDATABLE_ACCOUNTS_SCAN) {
+				break Some(iter.last_raw_key().to_vec());
+			}
+			match iter.next() {
+				Some((_, borrower, snapshot)) => {
+					if !snapshot.principal.is_zero() {
+						borrowers.insert(borrower);
+					}
+				}
+				None => break None,
+			}
+			scanned += 1;
+		};
+
+		let accounts = borrowers
+			.into_iter()
+			.filter(|borrower| Self::is_liquidatable(borrower).unwrap_or(false))
+			.collect();
+		(accounts, next_key)
+	}
+
+	/// Closes the auction of `borrower` once it can no longer be liquidated, so that a later
+	/// shortfall starts a new auction at the initial discount.
+	pub(crate) fn close_recovered_liquidation_auction(borrower: &T::AccountId) {
+		if LiquidationAuctions::<T>::contains_key(borrower)
+			&& matches!(Self::is_liquidatable(borrower), Ok(false))
+		{
+			LiquidationAuctions::<T>::remove(borrower);
+			Self::deposit_event(Event::<T>::LiquidationAuctionClosed {
+				borrower: borrower.clone(),
+			});
+		}
+	}
+
+	/// The current discount of an auction started at `start`.
+	pub fn auction_discount(config: &AuctionConfig, start: Timestamp) -> Ratio {
+		let elapsed = T::UnixTime::now().as_secs().saturating_sub(start);
+		if elapsed >= config.duration {
+			return config.max_discount;
+		}
+		let discount_range = config.max_discount.saturating_sub(config.initial_discount);
+		config
+			.initial_discount
+			.saturating_add(Ratio::from_rational(elapsed, config.duration) * discount_range)
+	}
+
+	/// Starts the auction of `borrower`. An auction left over from an earlier shortfall the
+	/// borrower has since recovered from is closed instead.
+	#[require_transactional]
+	pub(crate) fn do_start_liquidation_auction(borrower: T::AccountId) -> DispatchResult {
+		ensure!(
+			LiquidationAuctionConfig::<T>::exists(),
+			Error::<T>::AuctionNotConfigured
+		);
+		if LiquidationAuctions::<T>::contains_key(&borrower) {
+			ensure!(
+				!Self::is_liquidatable(&borrower)?,
+				Error::<T>::AuctionAlreadyStarted
+			);
+			LiquidationAuctions::<T>::remove(&borrower);
+			Self::deposit_event(Event::<T>::LiquidationAuctionClosed { borrower });
+			return Ok(());
+		}
+		ensure!(
+			Self::is_liquidatable(&borrower)?,
+			Error::<T>::InsufficientShortfall
+		);
+
+		let start = T::UnixTime::now().as_secs();
+		LiquidationAuctions::<T>::insert(&borrower, start);
+
+		Self::deposit_event(Event::<T>::LiquidationAuctionStarted { borrower, start });
+		Ok(())
+	}
+
+	/// The bidder repays `repay_amount` of the borrower's debt and receives the collateral at
+	/// the current auction discount. Bids may be partial, the auction closes once the borrower
+	/// can no longer be liquidated.
+	#[require_transactional]
+	pub(crate) fn do_bid_liquidation_auction(
+		bidder: T::AccountId,
+		borrower: T::AccountId,
+		liquidation_asset_id: AssetIdOf<T>,
+		repay_amount: BalanceOf<T>,
+		collateral_asset_id: AssetIdOf<T>,
+	) -> DispatchResult {
+		ensure!(!repay_amount.is_zero(), Error::<T>::InvalidAmount);
+		ensure!(bidder != borrower, Error::<T>::LiquidatorIsBorrower);
+		Self::ensure_active_market(liquidation_asset_id)?;
+		Self::ensure_active_market(collateral_asset_id)?;
+		let config =
+			LiquidationAuctionConfig::<T>::get().ok_or(Error::<T>::AuctionNotConfigured)?;
+		let start =
+			LiquidationAuctions::<T>::get(&borrower).ok_or(Error::<T>::AuctionNotStarted)?;
+		ensure!(
+			Self::is_liquidatable(&borrower)?,
+			Error::<T>::InsufficientShortfall
+		);
+
+		let account_borrows = Self::current_borrow_balance(&borrower, liquidation_asset_id)?;
+		ensure!(repay_amount <= account_borrows, Error::<T>::TooMuchRepay);
+		let deposits = AccountDeposits::<T>::get(collateral_asset_id, &borrower);
+		ensure!(deposits.is_collateral, Error::<T>::DepositsAreNotCollateral);
+
+		// Paying `1 - discount` for the collateral is the same as a liquidate incentive of
+		// `1 / (1 - discount)`.
+		let discount = Self::auction_discount(&config, start);
+		let liquidate_incentive = Rate::one()
+			.checked_div(&Rate::from(Ratio::one().saturating_sub(discount)))
+			.ok_or(ArithmeticError::Underflow)?;
+
+		let exchange_rate = Self::exchange_rate_stored(collateral_asset_id)?;
+		let borrower_deposit_amount = exchange_rate
+			.checked_mul_int(deposits.voucher_balance)
+			.ok_or(ArithmeticError::Overflow)?;
+		let collateral_value = Self::get_asset_value(collateral_asset_id, borrower_deposit_amount)?;
+		let liquidate_value = Self::get_asset_value(liquidation_asset_id, repay_amount)?
+			.checked_mul(&liquidate_incentive)
+			.ok_or(ArithmeticError::Overflow)?;
+		ensure!(
+			collateral_value >= liquidate_value,
+			Error::<T>::InsufficientCollateral
+		);
+		let collateral_underlying_amount = liquidate_value
+			.checked_div(&Self::get_price(collateral_asset_id)?)
+			.ok_or(ArithmeticError::Underflow)?
+			.into_inner();
+
+		let market = Market {
+			liquidate_incentive,
+			..Self::market(liquidation_asset_id)?
+		};
+		Self::liquidated_transfer(
+			&bidder,
+			&borrower,
+			liquidation_asset_id,
+			collateral_asset_id,
+			repay_amount,
+			collateral_underlying_amount,
+			&market,
+		)?;
+		Self::deposit_event(Event::<T>::LiquidationAuctionBid {
+			bidder,
+			borrower: borrower.clone(),
+			discount,
+		});
+
+		if !Self::is_liquidatable(&borrower)? {
+			LiquidationAuctions::<T>::remove(&borrower);
+			Self::deposit_event(Event::<T>::LiquidationAuctionClosed { borrower });
+		}
+
+		Ok(())
+	}
+
+	/// Writes off the remaining debt of an insolvent borrower once its auction has reached the
+	/// maximum discount. The remaining collateral is moved to `TotalReserves`, which then absorbs
+	/// the bad debt.
+	#[require_transactional]
+	pub(crate) fn do_absorb_bad_debt(borrower: T::AccountId) -> DispatchResult {
+		let config =
+			LiquidationAuctionConfig::<T>::get().ok_or(Error::<T>::AuctionNotConfigured)?;
+		let start =
+			LiquidationAuctions::<T>::get(&borrower).ok_or(Error::<T>::AuctionNotStarted)?;
+		ensure!(
+			T::UnixTime::now().as_secs() >= start.saturating_add(config.duration),
+			Error::<T>::AuctionNotFinished
+		);
+
+		let asset_ids: Vec<AssetIdOf<T>> = Self::active_markets()
+			.map(|(asset_id, _)| asset_id)
+			.collect();
+		let mut total_collateral_value = FixedU128::zero();
+		for asset_id in asset_ids.iter() {
+			Self::accrue_interest(*asset_id)?;
+			let deposits = AccountDeposits::<T>::get(asset_id, &borrower);
+			if !deposits.is_collateral {
+				continue;
+			}
+			let exchange_rate = Self::exchange_rate_stored(*asset_id)?;
+			let underlying_amount =
+				Self::calc_underlying_amount(deposits.voucher_balance, exchange_rate)?;
+			total_collateral_value = total_collateral_value
+				.checked_add(&Self::get_asset_value(*asset_id, underlying_amount)?)
+				.ok_or(ArithmeticError::Overflow)?;
+		}
+		ensure!(
+			total_collateral_value < Self::total_borrowed_value(&borrower)?,
+			Error::<T>::NoBadDebt
+		);
+
+		// The remaining collateral is added to the reserves of its market before the bad debt is
+		// written down, so the reserves keep the recovered value and collateral supplied to a
+		// borrowed market reduces the loss left to its suppliers.
+		for asset_id in asset_ids.iter() {
+			let deposits = AccountDeposits::<T>::get(asset_id, &borrower);
+			if !deposits.is_collateral || deposits.voucher_balance.is_zero() {
+				continue;
+			}
+			Self::update_reward_supply_index(*asset_id)?;
+			Self::distribute_supplier_reward(*asset_id, &borrower)?;
+
+			// Burning the vouchers for their underlying amount of reserves keeps the exchange
+			// rate of the other suppliers unchanged.
+			let exchange_rate = Self::exchange_rate_stored(*asset_id)?;
+			let underlying_amount =
+				Self::calc_underlying_amount(deposits.voucher_balance, exchange_rate)?;
+			AccountDeposits::<T>::remove(asset_id, &borrower);
+			TotalSupply::<T>::try_mutate(asset_id, |total_supply| -> DispatchResult {
+				*total_supply = total_supply
+					.checked_sub(deposits.voucher_balance)
+					.ok_or(ArithmeticError::Underflow)?;
+				Ok(())
+			})?;
+			let total_reserves_new = TotalReserves::<T>::try_mutate(
+				asset_id,
+				|total_reserves| -> Result<BalanceOf<T>, DispatchError> {
+					*total_reserves = total_reserves
+						.checked_add(underlying_amount)
+						.ok_or(ArithmeticError::Overflow)?;
+					Ok(*total_reserves)
+				},
+			)?;
+
+			Self::deposit_event(Event::<T>::ReservesAdded(
+				borrower.clone(),
+				*asset_id,
+				underlying_amount,
+				total_reserves_new,
+			));
+		}
+
+		for asset_id in asset_ids.iter() {
+			let bad_debt = Self::current_borrow_balance(&borrower, *asset_id)?;
+			if bad_debt.is_zero() {
+				continue;
+			}
+			Self::update_reward_borrow_index(*asset_id)?;
+			Self::distribute_borrower_reward(*asset_id, &borrower)?;
+
+			AccountBorrows::<T>::remove(asset_id, &borrower);
+			Self::decrease_isolation_debt(&borrower, *asset_id, bad_debt)?;
+			TotalBorrows::<T>::mutate(asset_id, |total_borrows| {
+				*total_borrows = total_borrows.saturating_sub(bad_debt)
+			});
+			// Suppliers only take the loss that exceeds the reserves.
+			let absorbed_by_reserves = TotalReserves::<T>::mutate(asset_id, |total_reserves| {
+				let absorbed = bad_debt.min(*total_reserves);
+				*total_reserves = total_reserves.saturating_sub(absorbed);
+				absorbed
+			});
+
+			Self::deposit_event(Event::<T>::BadDebtAbsorbed {
+				borrower: borrower.clone(),
+				asset_id: *asset_id,
+				bad_debt,
+				absorbed_by_reserves,
+			});
+		}
+
+		LiquidationAuctions::<T>::remove(&borrower);
+		Self::deposit_event(Event::<T>::LiquidationAuctionClosed { borrower });
+		Ok(())
+	}
+}
@@ -383,6 +383,137 @@ benchmarks! {
 	verify {
 		assert_last_event::<T>(Event::<T>::LiquidationFreeCollateralsUpdated(vec![PHA]).into());
 	}
+
+	set_emode_category {
+		let category = EModeCategory {
+			collateral_factor: Ratio::from_percent(90),
+			liquidation_threshold: Ratio::from_percent(93),
+		};
+	}: _(SystemOrigin::Root, 1, Some(category))
+	verify {
+		assert_last_event::<T>(Event::<T>::EModeCategorySet { category_id: 1, category: Some(category) }.into());
+	}
+
+	set_asset_emode_category {
+		assert_ok!(LendMarket::<T>::add_market(SystemOrigin::Root.into(), KSM, pending_market_mock::<T>(LKSM)));
+		assert_ok!(LendMarket::<T>::set_emode_category(SystemOrigin::Root.into(), 1, Some(EModeCategory {
+			collateral_factor: Ratio::from_percent(90),
+			liquidation_threshold: Ratio::from_percent(93),
+		})));
+	}: _(SystemOrigin::Root, KSM, Some(1))
+	verify {
+		assert_last_event::<T>(Event::<T>::AssetEModeCategorySet { asset_id: KSM, category_id: Some(1) }.into());
+	}
+
+	set_isolation_debt_ceiling {
+		assert_ok!(LendMarket::<T>::add_market(SystemOrigin::Root.into(), KSM, pending_market_mock::<T>(LKSM)));
+		let debt_ceiling = FixedU128::from_inner(1_000_000_000);
+	}: _(SystemOrigin::Root, KSM, Some(debt_ceiling))
+	verify {
+		assert_last_event::<T>(Event::<T>::IsolationDebtCeilingSet { asset_id: KSM, debt_ceiling: Some(debt_ceiling) }.into());
+	}
+
+	set_account_emode_category {
+		let caller: T::AccountId = whitelisted_caller();
+		transfer_initial_balance::<T>(caller.clone());
+		let deposit_amount: u32 = 200_000_000;
+		let borrowed_amount: u32 = 100_000_000;
+		assert_ok!(LendMarket::<T>::add_market(SystemOrigin::Root.into(), KSM, pending_market_mock::<T>(LKSM)));
+		assert_ok!(LendMarket::<T>::activate_market(SystemOrigin::Root.into(), KSM));
+		assert_ok!(LendMarket::<T>::mint(SystemOrigin::Signed(caller.clone()).into(), KSM, deposit_amount.into()));
+		assert_ok!(LendMarket::<T>::collateral_asset(SystemOrigin::Signed(caller.clone()).into(), KSM, true));
+		set_account_borrows::<T>(caller.clone(), KSM, borrowed_amount.into());
+		assert_ok!(LendMarket::<T>::set_emode_category(SystemOrigin::Root.into(), 1, Some(EModeCategory {
+			collateral_factor: Ratio::from_percent(90),
+			liquidation_threshold: Ratio::from_percent(93),
+		})));
+		assert_ok!(LendMarket::<T>::set_asset_emode_category(SystemOrigin::Root.into(), KSM, Some(1)));
+	}: _(SystemOrigin::Signed(caller.clone()), Some(1))
+	verify {
+		assert_last_event::<T>(Event::<T>::AccountEModeCategorySet { account: caller, category_id: Some(1) }.into());
+	}
+
+	set_liquidation_auction_config {
+		let config = AuctionConfig {
+			initial_discount: Ratio::from_percent(5),
+			max_discount: Ratio::from_percent(20),
+			duration: 3600,
+		};
+	}: _(SystemOrigin::Root, Some(config))
+	verify {
+		assert_last_event::<T>(Event::<T>::LiquidationAuctionConfigSet { config: Some(config) }.into());
+	}
+
+	start_liquidation_auction {
+		let alice: T::AccountId = account("Sample", 100, SEED);
+		let bob: T::AccountId = account("Sample", 101, SEED);
+		transfer_initial_balance::<T>(alice.clone());
+		let deposit_amount: u32 = 200_000_000;
+		let borrowed_amount: u32 = 200_000_000;
+		assert_ok!(LendMarket::<T>::add_market(SystemOrigin::Root.into(), KSM, pending_market_mock::<T>(LKSM)));
+		assert_ok!(LendMarket::<T>::activate_market(SystemOrigin::Root.into(), KSM));
+		assert_ok!(LendMarket::<T>::mint(SystemOrigin::Signed(alice.clone()).into(), KSM, deposit_amount.into()));
+		assert_ok!(LendMarket::<T>::collateral_asset(SystemOrigin::Signed(alice.clone()).into(), KSM, true));
+		set_account_borrows::<T>(alice.clone(), KSM, borrowed_amount.into());
+		assert_ok!(LendMarket::<T>::set_liquidation_auction_config(SystemOrigin::Root.into(), Some(AuctionConfig {
+			initial_discount: Ratio::from_percent(5),
+			max_discount: Ratio::from_percent(20),
+			duration: 3600,
+		})));
+	}: _(SystemOrigin::Signed(bob), alice.clone())
+	verify {
+		assert!(LiquidationAuctions::<T>::contains_key(&alice));
+	}
+
+	bid_liquidation_auction {
+		let alice: T::AccountId = account("Sample", 100, SEED);
+		let bob: T::AccountId = account("Sample", 101, SEED);
+		transfer_initial_balance::<T>(alice.clone());
+		transfer_initial_balance::<T>(bob.clone());
+		let deposit_amount: u32 = 200_000_000;
+		let borrowed_amount: u32 = 200_000_000;
+		let liquidate_amount: u32 = 100_000_000;
+		assert_ok!(LendMarket::<T>::add_market(SystemOrigin::Root.into(), VKSM, pending_market_mock::<T>(VSKSM)));
+		assert_ok!(LendMarket::<T>::activate_market(SystemOrigin::Root.into(), VKSM));
+		assert_ok!(LendMarket::<T>::add_market(SystemOrigin::Root.into(), KSM, pending_market_mock::<T>(LKSM)));
+		assert_ok!(LendMarket::<T>::activate_market(SystemOrigin::Root.into(), KSM));
+		assert_ok!(LendMarket::<T>::mint(SystemOrigin::Signed(bob.clone()).into(), KSM, deposit_amount.into()));
+		assert_ok!(LendMarket::<T>::mint(SystemOrigin::Signed(alice.clone()).into(), VKSM, deposit_amount.into()));
+		assert_ok!(LendMarket::<T>::collateral_asset(SystemOrigin::Signed(alice.clone()).into(), VKSM, true));
+		set_account_borrows::<T>(alice.clone(), KSM, borrowed_amount.into());
+		assert_ok!(LendMarket::<T>::set_liquidation_auction_config(SystemOrigin::Root.into(), Some(AuctionConfig {
+			initial_discount: Ratio::from_percent(5),
+			max_discount: Ratio::from_percent(20),
+			duration: 3600,
+		})));
+		assert_ok!(LendMarket::<T>::start_liquidation_auction(SystemOrigin::Signed(bob.clone()).into(), alice.clone()));
+	}: _(SystemOrigin::Signed(bob.clone()), alice.clone(), KSM, liquidate_amount.into(), VKSM)
+	verify {
+		assert_last_event::<T>(Event::<T>::LiquidationAuctionBid { bidder: bob, borrower: alice, discount: Ratio::from_percent(5) }.into());
+	}
+
+	absorb_bad_debt {
+		let alice: T::AccountId = account("Sample", 100, SEED);
+		let bob: T::AccountId = account("Sample", 101, SEED);
+		transfer_initial_balance::<T>(alice.clone());
+		let deposit_amount: u32 = 200_000_000;
+		let borrowed_amount: u32 = 250_000_000;
+		assert_ok!(LendMarket::<T>::add_market(SystemOrigin::Root.into(), KSM, pending_market_mock::<T>(LKSM)));
+		assert_ok!(LendMarket::<T>::activate_market(SystemOrigin::Root.into(), KSM));
+		assert_ok!(LendMarket::<T>::mint(SystemOrigin::Signed(alice.clone()).into(), KSM, deposit_amount.into()));
+		assert_ok!(LendMarket::<T>::collateral_asset(SystemOrigin::Signed(alice.clone()).into(), KSM, true));
+		set_account_borrows::<T>(alice.clone(), KSM, borrowed_amount.into());
+		// A zero duration lets the auction reach its maximum discount right away.
+		LiquidationAuctionConfig::<T>::put(AuctionConfig {
+			initial_discount: Ratio::from_percent(5),
+			max_discount: Ratio::from_percent(20),
+			duration: 0,
+		});
+		assert_ok!(LendMarket::<T>::start_liquidation_auction(SystemOrigin::Signed(bob.clone()).into(), alice.clone()));
+	}: _(SystemOrigin::Signed(bob), alice.clone())
+	verify {
+		assert_last_event::<T>(Event::<T>::LiquidationAuctionClosed { borrower: alice }.into());
+	}
 }
 
 impl_benchmark_test_suite!(LendMarket, crate::mock::new_test_ext(), crate::mock::Test);
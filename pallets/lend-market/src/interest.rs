@@ -45,6 +45,15 @@ impl<T: Config> Pallet<T> {
 			borrow_index_new,
 		) = Self::get_market_status(asset_id)?;
 
+		let market = Self::market(asset_id)?;
+		Self::update_rate_model_state(
+			asset_id,
+			&market.rate_model,
+			util,
+			now - last_accrued_interest_time,
+			now,
+		)?;
+
 		Self::update_last_accrued_interest_time(asset_id, now)?;
 		TotalBorrows::<T>::insert(asset_id, total_borrows_new);
 		TotalReserves::<T>::insert(asset_id, total_reserves_new);
@@ -81,9 +90,10 @@ impl<T: Config> Pallet<T> {
 		let mut borrow_index = BorrowIndex::<T>::get(asset_id);
 
 		let util = Self::calc_utilization_ratio(total_cash, total_borrows, total_reserves)?;
+		let rate_model_state = Self::rate_model_state(asset_id, &market.rate_model);
 		let borrow_rate = market
 			.rate_model
-			.get_borrow_rate(util)
+			.get_borrow_rate(util, &rate_model_state)
 			.ok_or(ArithmeticError::Overflow)?;
 		let supply_rate =
 			InterestRateModel::get_supply_rate(borrow_rate, util, market.reserve_factor);
@@ -176,6 +186,92 @@ impl<T: Config> Pallet<T> {
 		})
 	}
 
+	/// The inputs of the rate model of `asset_id` that change over time
+	pub(crate) fn rate_model_state(
+		asset_id: AssetIdOf<T>,
+		rate_model: &InterestRateModel,
+	) -> RateModelState {
+		match rate_model {
+			InterestRateModel::Adaptive(_) => RateModelState {
+				rate_at_target: AdaptiveRateAtTarget::<T>::get(asset_id),
+				..Default::default()
+			},
+			InterestRateModel::VTokenApy(vtoken_apy) => RateModelState {
+				staking_apy: VTokenStakingApy::<T>::get(vtoken_apy.vtoken_id),
+				..Default::default()
+			},
+			_ => Default::default(),
+		}
+	}
+
+	/// Moves the rate at target of adaptive models and samples the vToken exchange rate of
+	/// vToken APY models, `delta_time` seconds after the last accrual at `util`.
+	pub(crate) fn update_rate_model_state(
+		asset_id: AssetIdOf<T>,
+		rate_model: &InterestRateModel,
+		util: Ratio,
+		delta_time: Timestamp,
+		now: Timestamp,
+	) -> DispatchResult {
+		match rate_model {
+			InterestRateModel::Adaptive(adaptive) => {
+				let rate_at_target = AdaptiveRateAtTarget::<T>::get(asset_id)
+					.unwrap_or(adaptive.initial_rate_at_target);
+				AdaptiveRateAtTarget::<T>::insert(
+					asset_id,
+					adaptive.next_rate_at_target(rate_at_target, util, delta_time),
+				);
+			}
+			InterestRateModel::VTokenApy(vtoken_apy) => {
+				Self::update_vtoken_staking_apy(vtoken_apy.vtoken_id, now)?
+			}
+			_ => (),
+		}
+
+		Ok(())
+	}
+
+	/// Samples the exchange rate of `vtoken_id`, and updates its staking APY from the growth
+	/// of the exchange rate once `APY_SAMPLE_PERIOD` has elapsed since the last sample.
+	pub(crate) fn update_vtoken_staking_apy(
+		vtoken_id: CurrencyId,
+		now: Timestamp,
+	) -> DispatchResult {
+		let token_id = vtoken_id
+			.to_token()
+			.map_err(|_| Error::<T>::InvalidRateModelParam)?;
+		let (Some(token_supply), Some(vtoken_supply)) = (
+			T::VTokenSupplyProvider::get_token_supply(token_id),
+			T::VTokenSupplyProvider::get_vtoken_supply(vtoken_id),
+		) else {
+			return Ok(());
+		};
+		let exchange_rate = match Rate::checked_from_rational(token_supply, vtoken_supply) {
+			Some(exchange_rate) if !exchange_rate.is_zero() => exchange_rate,
+			_ => return Ok(()),
+		};
+
+		if let Some((last_exchange_rate, last_time)) =
+			VTokenExchangeRateSamples::<T>::get(vtoken_id)
+		{
+			let delta_time = now.saturating_sub(last_time);
+			if delta_time < VTokenApyModel::APY_SAMPLE_PERIOD {
+				return Ok(());
+			}
+			// (exchange_rate / last_exchange_rate - 1) * SECONDS_PER_YEAR / delta_time
+			let apy = exchange_rate
+				.checked_div(&last_exchange_rate)
+				.ok_or(ArithmeticError::Underflow)?
+				.saturating_sub(Rate::one())
+				.saturating_mul(Rate::saturating_from_rational(SECONDS_PER_YEAR, delta_time));
+			VTokenStakingApy::<T>::insert(vtoken_id, apy);
+			Self::deposit_event(Event::<T>::VTokenStakingApyUpdated { vtoken_id, apy });
+		}
+		VTokenExchangeRateSamples::<T>::insert(vtoken_id, (exchange_rate, now));
+
+		Ok(())
+	}
+
 	fn accrued_interest(
 		borrow_rate: Rate,
 		amount: BalanceOf<T>,
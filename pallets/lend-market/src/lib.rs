@@ -31,6 +31,7 @@ use core::cmp::max;
 pub use crate::rate_model::*;
 use bifrost_primitives::{
 	Balance, CurrencyId, Liquidity, OraclePriceProvider, Price, Rate, Ratio, Shortfall, Timestamp,
+	VTokenSupplyProvider,
 };
 use frame_support::{
 	pallet_prelude::*,
@@ -61,7 +62,10 @@ use sp_std::{result::Result, vec::Vec};
 
 use log;
 use sp_io::hashing::blake2_256;
-pub use types::{BorrowSnapshot, Deposits, EarnedSnapshot, Market, MarketState, RewardMarketState};
+pub use types::{
+	AuctionConfig, BorrowSnapshot, Deposits, EModeCategory, EarnedSnapshot, Market, MarketState,
+	RewardMarketState,
+};
 pub use weights::WeightInfo;
 
 #[cfg(feature = "runtime-benchmarks")]
@@ -73,6 +77,7 @@ pub mod mock;
 #[cfg(test)]
 mod tests;
 
+mod auction;
 mod farming;
 mod interest;
 mod lend_token;
@@ -83,6 +88,8 @@ pub mod weights;
 
 pub const MAX_EXCHANGE_RATE: u128 = 1_000_000_000_000_000_000; // 1
 pub const MIN_EXCHANGE_RATE: u128 = 20_000_000_000_000_000; // 0.02
+/// Maximum number of borrow records `get_liquidatable_accounts` scans in one call.
+pub const MAX_LIQUIDATABLE_ACCOUNTS_SCAN: u32 = 1_000;
 
 pub type AccountIdOf<T> = <T as frame_system::Config>::AccountId;
 pub type AssetIdOf<T> =
@@ -135,6 +142,9 @@ pub mod pallet {
 
 		/// The current block number provider.
 		type BlockNumberProvider: BlockNumberProvider<BlockNumber = BlockNumberFor<Self>>;
+
+		/// The vToken supplies, used to track the staking APY of vTokens
+		type VTokenSupplyProvider: VTokenSupplyProvider<CurrencyId, BalanceOf<Self>>;
 	}
 
 	#[pallet::error]
@@ -201,6 +211,26 @@ pub mod pallet {
 		MarketBondDoesNotExist,
 		/// Error converting Vec to BoundedVec.
 		ConversionError,
+		/// Efficiency mode category does not exist
+		EModeCategoryDoesNotExist,
+		/// The asset does not belong to the efficiency mode category of the account
+		EModeCategoryMismatch,
+		/// Borrowing against isolated collateral would exceed its debt ceiling
+		IsolationDebtCeilingExceeded,
+		/// Isolated collateral cannot be combined with other collaterals
+		IsolatedCollateralConflict,
+		/// The market is already used as collateral and cannot be isolated
+		MarketUsedAsCollateral,
+		/// Liquidation auction parameters are not set
+		AuctionNotConfigured,
+		/// The liquidation auction of the borrower has already started
+		AuctionAlreadyStarted,
+		/// The borrower has no liquidation auction
+		AuctionNotStarted,
+		/// The liquidation auction has not reached the maximum discount yet
+		AuctionNotFinished,
+		/// The borrower's collateral still covers its debt
+		NoBadDebt,
 	}
 
 	#[pallet::event]
@@ -271,6 +301,51 @@ pub mod pallet {
 			asset_id: AssetIdOf<T>,
 			market_bond: Vec<AssetIdOf<T>>,
 		},
+		/// Efficiency mode category has been set or removed
+		EModeCategorySet {
+			category_id: u8,
+			category: Option<EModeCategory>,
+		},
+		/// Asset has been added to or removed from an efficiency mode category
+		AssetEModeCategorySet {
+			asset_id: AssetIdOf<T>,
+			category_id: Option<u8>,
+		},
+		/// Account has entered or left an efficiency mode category
+		AccountEModeCategorySet {
+			account: T::AccountId,
+			category_id: Option<u8>,
+		},
+		/// Debt ceiling of an isolated collateral has been set or removed
+		IsolationDebtCeilingSet {
+			asset_id: AssetIdOf<T>,
+			debt_ceiling: Option<FixedU128>,
+		},
+		/// Liquidation auction parameters have been set or removed
+		LiquidationAuctionConfigSet { config: Option<AuctionConfig> },
+		/// An underwater account has entered a liquidation auction
+		LiquidationAuctionStarted {
+			borrower: T::AccountId,
+			start: Timestamp,
+		},
+		/// A bidder has repaid part of the debt of an account in auction
+		LiquidationAuctionBid {
+			bidder: T::AccountId,
+			borrower: T::AccountId,
+			discount: Ratio,
+		},
+		/// The liquidation auction of an account has ended
+		LiquidationAuctionClosed { borrower: T::AccountId },
+		/// The remaining debt of an insolvent account has been written off
+		/// `absorbed_by_reserves` is taken from `TotalReserves`, the rest is taken by suppliers
+		BadDebtAbsorbed {
+			borrower: T::AccountId,
+			asset_id: AssetIdOf<T>,
+			bad_debt: BalanceOf<T>,
+			absorbed_by_reserves: BalanceOf<T>,
+		},
+		/// The staking APY of a vToken tracked by vToken APY rate models has been updated
+		VTokenStakingApyUpdated { vtoken_id: CurrencyId, apy: Rate },
 	}
 
 	/// The timestamp of the last calculation of accrued interest
@@ -442,7 +517,84 @@ pub mod pallet {
 	pub type MarketBond<T: Config> =
 		StorageMap<_, Blake2_128Concat, AssetIdOf<T>, BoundedVec<AssetIdOf<T>, T::MaxLengthLimit>>;
 
-	const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+	/// Efficiency mode categories
+	/// CategoryId -> EModeCategory
+	#[pallet::storage]
+	pub type EModeCategories<T: Config> = StorageMap<_, Twox64Concat, u8, EModeCategory>;
+
+	/// Efficiency mode category of each asset
+	/// CurrencyId -> CategoryId
+	#[pallet::storage]
+	pub type AssetEModeCategory<T: Config> = StorageMap<_, Blake2_128Concat, AssetIdOf<T>, u8>;
+
+	/// Efficiency mode category each account has opted into
+	/// Owner -> CategoryId
+	#[pallet::storage]
+	pub type AccountEModeCategory<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, u8>;
+
+	/// Debt ceiling of isolated collaterals, in the same unit as `get_asset_value`.
+	/// An account using an isolated collateral cannot use any other collateral.
+	/// CurrencyId -> Value
+	#[pallet::storage]
+	pub type IsolationDebtCeilings<T: Config> =
+		StorageMap<_, Blake2_128Concat, AssetIdOf<T>, FixedU128>;
+
+	/// Principal borrowed against each isolated collateral, in units of the borrowed asset.
+	/// It is valued at the current price when checking the debt ceiling.
+	/// Isolated CurrencyId -> Borrowed CurrencyId -> Balance
+	#[pallet::storage]
+	pub type IsolationTotalDebt<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		AssetIdOf<T>,
+		Blake2_128Concat,
+		AssetIdOf<T>,
+		BalanceOf<T>,
+		ValueQuery,
+	>;
+
+	/// Principal each account borrowed against an isolated collateral, and that collateral.
+	/// Repayments are released from the recorded collateral, even if the account no longer
+	/// uses it.
+	/// Owner -> Borrowed CurrencyId -> (Isolated CurrencyId, Balance)
+	#[pallet::storage]
+	pub type AccountIsolationDebt<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		Blake2_128Concat,
+		AssetIdOf<T>,
+		(AssetIdOf<T>, BalanceOf<T>),
+	>;
+
+	/// Liquidation auction parameters, auctions are disabled when unset
+	#[pallet::storage]
+	pub type LiquidationAuctionConfig<T: Config> = StorageValue<_, AuctionConfig>;
+
+	/// Start time of the liquidation auction of each account
+	/// Owner -> Timestamp
+	#[pallet::storage]
+	pub type LiquidationAuctions<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, Timestamp>;
+
+	/// Current interest rate at target utilization of the markets using an adaptive rate model
+	/// CurrencyId -> Rate
+	#[pallet::storage]
+	pub type AdaptiveRateAtTarget<T: Config> = StorageMap<_, Blake2_128Concat, AssetIdOf<T>, Rate>;
+
+	/// Last sample of the exchange rate of the vTokens tracked by vToken APY rate models
+	/// vToken CurrencyId -> (Exchange rate, Timestamp)
+	#[pallet::storage]
+	pub type VTokenExchangeRateSamples<T: Config> =
+		StorageMap<_, Blake2_128Concat, CurrencyId, (Rate, Timestamp)>;
+
+	/// Staking APY of the vTokens tracked by vToken APY rate models
+	/// vToken CurrencyId -> Rate
+	#[pallet::storage]
+	pub type VTokenStakingApy<T: Config> =
+		StorageMap<_, Blake2_128Concat, CurrencyId, Rate, ValueQuery>;
+
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(2);
 
 	#[pallet::pallet]
 	#[pallet::without_storage_info]
@@ -567,6 +719,8 @@ pub mod pallet {
 				stored_market.rate_model = rate_model;
 				stored_market.clone()
 			})?;
+			// A new adaptive model starts from its initial rate at target
+			AdaptiveRateAtTarget::<T>::remove(asset_id);
 			Self::deposit_event(Event::<T>::UpdatedMarket(asset_id, market));
 
 			Ok(().into())
@@ -1166,6 +1320,237 @@ pub mod pallet {
 			});
 			Ok(().into())
 		}
+
+		/// Sets or removes an efficiency mode category.
+		///
+		/// - `category_id`: the id of the category.
+		/// - `category`: the collateral factor and liquidation threshold of the category, `None`
+		///   removes it.
+		#[pallet::call_index(23)]
+		#[pallet::weight(T::WeightInfo::set_emode_category())]
+		#[transactional]
+		pub fn set_emode_category(
+			origin: OriginFor<T>,
+			category_id: u8,
+			category: Option<EModeCategory>,
+		) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			if let Some(category) = category {
+				ensure!(
+					category.collateral_factor < Ratio::one(),
+					Error::<T>::InvalidFactor
+				);
+				ensure!(
+					category.liquidation_threshold < Ratio::one()
+						&& category.liquidation_threshold >= category.collateral_factor,
+					Error::<T>::InvalidFactor
+				);
+			}
+			EModeCategories::<T>::set(category_id, category);
+
+			Self::deposit_event(Event::<T>::EModeCategorySet {
+				category_id,
+				category,
+			});
+			Ok(().into())
+		}
+
+		/// Adds an asset to an efficiency mode category, or removes it.
+		///
+		/// - `asset_id`: market related currency.
+		/// - `category_id`: the id of the category, `None` removes the asset from its category.
+		#[pallet::call_index(24)]
+		#[pallet::weight(T::WeightInfo::set_asset_emode_category())]
+		#[transactional]
+		pub fn set_asset_emode_category(
+			origin: OriginFor<T>,
+			asset_id: AssetIdOf<T>,
+			category_id: Option<u8>,
+		) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			Self::market(asset_id)?;
+			if let Some(category_id) = category_id {
+				ensure!(
+					EModeCategories::<T>::contains_key(category_id),
+					Error::<T>::EModeCategoryDoesNotExist
+				);
+			}
+			AssetEModeCategory::<T>::set(asset_id, category_id);
+
+			Self::deposit_event(Event::<T>::AssetEModeCategorySet {
+				asset_id,
+				category_id,
+			});
+			Ok(().into())
+		}
+
+		/// Sets or removes the debt ceiling of an isolated collateral.
+		///
+		/// - `asset_id`: market related currency.
+		/// - `debt_ceiling`: the maximum value that can be borrowed against the asset, `None`
+		///   removes it from isolation mode.
+		///
+		/// A market can only enter isolation mode while no supplier uses it as collateral, so
+		/// that no existing account loses its other collaterals.
+		#[pallet::call_index(25)]
+		#[pallet::weight(T::WeightInfo::set_isolation_debt_ceiling())]
+		#[transactional]
+		pub fn set_isolation_debt_ceiling(
+			origin: OriginFor<T>,
+			asset_id: AssetIdOf<T>,
+			debt_ceiling: Option<FixedU128>,
+		) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			Self::market(asset_id)?;
+			if debt_ceiling.is_some() && !IsolationDebtCeilings::<T>::contains_key(asset_id) {
+				ensure!(
+					!AccountDeposits::<T>::iter_prefix_values(asset_id).any(|deposits| {
+						deposits.is_collateral && !deposits.voucher_balance.is_zero()
+					}),
+					Error::<T>::MarketUsedAsCollateral
+				);
+			}
+			// The debt is kept when the ceiling is removed, so that it is still released on
+			// repayment and counted if the ceiling is set again.
+			IsolationDebtCeilings::<T>::set(asset_id, debt_ceiling);
+
+			Self::deposit_event(Event::<T>::IsolationDebtCeilingSet {
+				asset_id,
+				debt_ceiling,
+			});
+			Ok(().into())
+		}
+
+		/// Sender enters or leaves an efficiency mode category.
+		///
+		/// All outstanding borrows must belong to the new category and the account must not
+		/// have any shortfall afterwards.
+		///
+		/// - `category_id`: the id of the category, `None` leaves efficiency mode.
+		#[pallet::call_index(26)]
+		#[pallet::weight(T::WeightInfo::set_account_emode_category())]
+		#[transactional]
+		pub fn set_account_emode_category(
+			origin: OriginFor<T>,
+			category_id: Option<u8>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			if let Some(category_id) = category_id {
+				ensure!(
+					EModeCategories::<T>::contains_key(category_id),
+					Error::<T>::EModeCategoryDoesNotExist
+				);
+				for (asset_id, _) in Self::active_markets() {
+					if !Self::current_borrow_balance(&who, asset_id)?.is_zero() {
+						ensure!(
+							AssetEModeCategory::<T>::get(asset_id) == Some(category_id),
+							Error::<T>::EModeCategoryMismatch
+						);
+					}
+				}
+			}
+			AccountEModeCategory::<T>::set(&who, category_id);
+
+			let (_, shortfall, _, _) = Self::get_account_liquidity(&who)?;
+			ensure!(shortfall.is_zero(), Error::<T>::InsufficientLiquidity);
+
+			Self::deposit_event(Event::<T>::AccountEModeCategorySet {
+				account: who,
+				category_id,
+			});
+			Ok(().into())
+		}
+
+		/// Sets or removes the liquidation auction parameters.
+		///
+		/// - `config`: the discount curve of the auctions, `None` disables new auctions.
+		#[pallet::call_index(27)]
+		#[pallet::weight(T::WeightInfo::set_liquidation_auction_config())]
+		#[transactional]
+		pub fn set_liquidation_auction_config(
+			origin: OriginFor<T>,
+			config: Option<AuctionConfig>,
+		) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			if let Some(config) = config {
+				ensure!(
+					config.max_discount < Ratio::one()
+						&& config.max_discount >= config.initial_discount,
+					Error::<T>::InvalidFactor
+				);
+				ensure!(!config.duration.is_zero(), Error::<T>::InvalidAmount);
+			}
+			LiquidationAuctionConfig::<T>::set(config);
+
+			Self::deposit_event(Event::<T>::LiquidationAuctionConfigSet { config });
+			Ok(().into())
+		}
+
+		/// Starts the liquidation auction of an underwater account, or closes the auction of an
+		/// account that is no longer underwater.
+		///
+		/// - `borrower`: the account to be liquidated.
+		#[pallet::call_index(28)]
+		#[pallet::weight(T::WeightInfo::start_liquidation_auction())]
+		#[transactional]
+		pub fn start_liquidation_auction(
+			origin: OriginFor<T>,
+			borrower: T::AccountId,
+		) -> DispatchResultWithPostInfo {
+			ensure_signed(origin)?;
+			Self::do_start_liquidation_auction(borrower)?;
+			Ok(().into())
+		}
+
+		/// The sender repays part of the debt of an account in auction and receives its
+		/// collateral at the current auction discount.
+		///
+		/// - `borrower`: the account in auction.
+		/// - `liquidation_asset_id`: the asset to be repaid.
+		/// - `repay_amount`: the amount to be repaid, up to the whole borrow balance.
+		/// - `collateral_asset_id`: the collateral to seize from the borrower.
+		#[pallet::call_index(29)]
+		#[pallet::weight(T::WeightInfo::bid_liquidation_auction())]
+		#[transactional]
+		pub fn bid_liquidation_auction(
+			origin: OriginFor<T>,
+			borrower: T::AccountId,
+			liquidation_asset_id: AssetIdOf<T>,
+			#[pallet::compact] repay_amount: BalanceOf<T>,
+			collateral_asset_id: AssetIdOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			ensure!(
+				!LiquidationFreeCollaterals::<T>::get().contains(&collateral_asset_id),
+				Error::<T>::CollateralReserved
+			);
+			Self::accrue_interest(liquidation_asset_id)?;
+			Self::accrue_interest(collateral_asset_id)?;
+			Self::do_bid_liquidation_auction(
+				who,
+				borrower,
+				liquidation_asset_id,
+				repay_amount,
+				collateral_asset_id,
+			)?;
+			Ok(().into())
+		}
+
+		/// Writes off the debt left by an insolvent account whose auction has reached the
+		/// maximum discount. The bad debt is absorbed by `TotalReserves`.
+		///
+		/// - `borrower`: the account in auction.
+		#[pallet::call_index(30)]
+		#[pallet::weight(T::WeightInfo::absorb_bad_debt())]
+		#[transactional]
+		pub fn absorb_bad_debt(
+			origin: OriginFor<T>,
+			borrower: T::AccountId,
+		) -> DispatchResultWithPostInfo {
+			ensure_signed(origin)?;
+			Self::do_absorb_bad_debt(borrower)?;
+			Ok(().into())
+		}
 	}
 }
 
@@ -1304,7 +1689,7 @@ impl<T: Config> Pallet<T> {
 		}
 	}
 
-	fn total_borrowed_value(borrower: &T::AccountId) -> Result<FixedU128, DispatchError> {
+	pub fn total_borrowed_value(borrower: &T::AccountId) -> Result<FixedU128, DispatchError> {
 		let mut total_borrow_value: FixedU128 = FixedU128::zero();
 		for (asset_id, _) in Self::active_markets() {
 			let currency_borrow_amount = Self::current_borrow_balance(borrower, asset_id)?;
@@ -1337,7 +1722,8 @@ impl<T: Config> Pallet<T> {
 		let underlying_amount =
 			Self::calc_underlying_amount(deposits.voucher_balance, exchange_rate)?;
 		let market = Self::market(asset_id)?;
-		let effects_amount = market.collateral_factor.mul_ceil(underlying_amount);
+		let (collateral_factor, _) = Self::collateral_factors(supplier, asset_id, &market);
+		let effects_amount = collateral_factor.mul_ceil(underlying_amount);
 
 		Ok(BalanceOf::<T>::saturated_from(effects_amount))
 	}
@@ -1369,14 +1755,18 @@ impl<T: Config> Pallet<T> {
 		let underlying_amount =
 			Self::calc_underlying_amount(deposits.voucher_balance, exchange_rate)?;
 		let market = Self::market(asset_id)?;
-		let effects_amount = market.liquidation_threshold.mul_ceil(underlying_amount);
+		let (_, liquidation_threshold) = Self::collateral_factors(borrower, asset_id, &market);
+		let effects_amount = liquidation_threshold.mul_ceil(underlying_amount);
 
 		Self::get_asset_value(asset_id, effects_amount)
 	}
 
 	fn total_collateral_value(supplier: &T::AccountId) -> Result<FixedU128, DispatchError> {
+		let isolated_collateral = Self::isolated_collateral(supplier);
 		let mut total_asset_value: FixedU128 = FixedU128::zero();
-		for (asset_id, _market) in Self::active_markets() {
+		for (asset_id, _market) in Self::active_markets().filter(|(asset_id, _)| {
+			isolated_collateral.map_or(true, |isolated| isolated == *asset_id)
+		}) {
 			total_asset_value = total_asset_value
 				.checked_add(&Self::collateral_asset_value(supplier, asset_id)?)
 				.ok_or(ArithmeticError::Overflow)?;
@@ -1385,11 +1775,14 @@ impl<T: Config> Pallet<T> {
 		Ok(total_asset_value)
 	}
 
-	fn total_liquidation_threshold_value(
+	pub fn total_liquidation_threshold_value(
 		borrower: &T::AccountId,
 	) -> Result<FixedU128, DispatchError> {
+		let isolated_collateral = Self::isolated_collateral(borrower);
 		let mut total_asset_value: FixedU128 = FixedU128::zero();
-		for (asset_id, _market) in Self::active_markets() {
+		for (asset_id, _market) in Self::active_markets().filter(|(asset_id, _)| {
+			isolated_collateral.map_or(true, |isolated| isolated == *asset_id)
+		}) {
 			total_asset_value = total_asset_value
 				.checked_add(&Self::liquidation_threshold_asset_value(
 					borrower, asset_id,
@@ -1400,6 +1793,39 @@ impl<T: Config> Pallet<T> {
 		Ok(total_asset_value)
 	}
 
+	// Returns the collateral factor and liquidation threshold of `asset_id` for `account`.
+	// The efficiency mode category values are used when the account has opted into the
+	// category of the asset, otherwise the market values are used.
+	fn collateral_factors(
+		account: &T::AccountId,
+		asset_id: AssetIdOf<T>,
+		market: &Market<BalanceOf<T>>,
+	) -> (Ratio, Ratio) {
+		match (
+			AccountEModeCategory::<T>::get(account),
+			AssetEModeCategory::<T>::get(asset_id),
+		) {
+			(Some(account_category), Some(asset_category))
+				if account_category == asset_category =>
+			{
+				EModeCategories::<T>::get(account_category).map_or(
+					(market.collateral_factor, market.liquidation_threshold),
+					|category| (category.collateral_factor, category.liquidation_threshold),
+				)
+			}
+			_ => (market.collateral_factor, market.liquidation_threshold),
+		}
+	}
+
+	// Returns the isolated collateral enabled by `account`, if any.
+	// Other collaterals are not counted while an isolated collateral is enabled.
+	pub fn isolated_collateral(account: &T::AccountId) -> Option<AssetIdOf<T>> {
+		IsolationDebtCeilings::<T>::iter_keys().find(|asset_id| {
+			let deposits = AccountDeposits::<T>::get(asset_id, account);
+			deposits.is_collateral && !deposits.voucher_balance.is_zero()
+		})
+	}
+
 	/// Checks if the redeemer should be allowed to redeem tokens in given market
 	fn redeem_allowed(
 		asset_id: AssetIdOf<T>,
@@ -1427,7 +1853,8 @@ impl<T: Config> Pallet<T> {
 		}
 
 		let market = Self::market(asset_id)?;
-		let effects_amount = market.collateral_factor.mul_ceil(redeem_amount);
+		let (collateral_factor, _) = Self::collateral_factors(redeemer, asset_id, &market);
+		let effects_amount = collateral_factor.mul_ceil(redeem_amount);
 		let redeem_effects_value = Self::get_asset_value(asset_id, effects_amount)?;
 		log::trace!(
 			target: "lend-market::redeem_allowed",
@@ -1499,6 +1926,13 @@ impl<T: Config> Pallet<T> {
 	) -> DispatchResult {
 		Self::ensure_under_borrow_cap(asset_id, borrow_amount)?;
 		Self::ensure_enough_cash(asset_id, borrow_amount)?;
+		if let Some(category_id) = AccountEModeCategory::<T>::get(borrower) {
+			ensure!(
+				AssetEModeCategory::<T>::get(asset_id) == Some(category_id),
+				Error::<T>::EModeCategoryMismatch
+			);
+		}
+		Self::ensure_under_isolation_debt_ceiling(borrower, asset_id, borrow_amount)?;
 		let borrow_value = Self::get_asset_value(asset_id, borrow_amount)?;
 		Self::ensure_liquidity(
 			borrower,
@@ -1562,6 +1996,7 @@ impl<T: Config> Pallet<T> {
 			},
 		);
 		TotalBorrows::<T>::insert(asset_id, total_borrows_new);
+		Self::decrease_isolation_debt(borrower, asset_id, repay_amount)?;
 
 		Ok(())
 	}
@@ -1627,16 +2062,13 @@ impl<T: Config> Pallet<T> {
 			repay_amount,
 			market
 		);
-		let (liquidity, shortfall, lf_liquidity, _) =
-			Self::get_account_liquidation_threshold_liquidity(borrower)?;
-
 		// C_other >= B_other + B_dot_over
 		// C_other >= B_other + max(B_dot - C_lf, 0)
 		// C_other + C_lf >= B_other + B_dot - B_dot + C_lf + max(B_dot - C_lf, 0)
 		// C_all - B_all >= max(0, C_lf - B_dot)
 		// C_all - B_all >= 0 && C_all - B_all >= max(0, C_lf - B_dot)
 		// shortfall == 0 && liquidity > lf_liquidity
-		if shortfall.is_zero() && liquidity >= lf_liquidity {
+		if !Self::is_liquidatable(borrower)? {
 			return Err(Error::<T>::InsufficientShortfall.into());
 		}
 
@@ -1797,6 +2229,7 @@ impl<T: Config> Pallet<T> {
 			},
 		);
 		TotalBorrows::<T>::insert(liquidation_asset_id, total_borrows_new);
+		Self::decrease_isolation_debt(borrower, liquidation_asset_id, repay_amount)?;
 
 		// update supply index before modify supply balance.
 		Self::update_reward_supply_index(collateral_asset_id)?;
@@ -1957,6 +2390,110 @@ impl<T: Config> Pallet<T> {
 		Err(Error::<T>::InsufficientLiquidity.into())
 	}
 
+	// Ensures that borrowing `borrow_amount` of `asset_id` against the isolated collateral of
+	// `account`, if any, stays within its debt ceiling. The debt is valued at current prices.
+	fn ensure_under_isolation_debt_ceiling(
+		account: &T::AccountId,
+		asset_id: AssetIdOf<T>,
+		borrow_amount: BalanceOf<T>,
+	) -> DispatchResult {
+		let Some(isolated_collateral) = Self::isolated_collateral(account) else {
+			return Ok(());
+		};
+		let debt_ceiling = IsolationDebtCeilings::<T>::get(isolated_collateral).unwrap_or_default();
+		let mut total_debt = Self::get_asset_value(asset_id, borrow_amount)?;
+		for (borrowed_asset_id, principal) in
+			IsolationTotalDebt::<T>::iter_prefix(isolated_collateral)
+		{
+			total_debt = total_debt
+				.checked_add(&Self::get_asset_value(borrowed_asset_id, principal)?)
+				.ok_or(ArithmeticError::Overflow)?;
+		}
+		ensure!(
+			total_debt <= debt_ceiling,
+			Error::<T>::IsolationDebtCeilingExceeded
+		);
+
+		Ok(())
+	}
+
+	// Records `amount` of `asset_id` borrowed by `borrower` against its isolated collateral,
+	// if any.
+	fn increase_isolation_debt(
+		borrower: &T::AccountId,
+		asset_id: AssetIdOf<T>,
+		amount: BalanceOf<T>,
+	) -> DispatchResult {
+		let Some(isolated_collateral) = Self::isolated_collateral(borrower) else {
+			return Ok(());
+		};
+		// Debt recorded against a former isolated collateral moves to the current one.
+		let recorded = match AccountIsolationDebt::<T>::get(borrower, asset_id) {
+			Some((recorded_collateral, recorded)) if recorded_collateral != isolated_collateral => {
+				IsolationTotalDebt::<T>::mutate(recorded_collateral, asset_id, |total_debt| {
+					*total_debt = total_debt.saturating_sub(recorded)
+				});
+				IsolationTotalDebt::<T>::try_mutate(isolated_collateral, asset_id, |total_debt| {
+					*total_debt = total_debt
+						.checked_add(recorded)
+						.ok_or(ArithmeticError::Overflow)?;
+					Ok::<(), DispatchError>(())
+				})?;
+				recorded
+			}
+			Some((_, recorded)) => recorded,
+			None => Zero::zero(),
+		};
+		AccountIsolationDebt::<T>::insert(
+			borrower,
+			asset_id,
+			(
+				isolated_collateral,
+				recorded
+					.checked_add(amount)
+					.ok_or(ArithmeticError::Overflow)?,
+			),
+		);
+		IsolationTotalDebt::<T>::try_mutate(isolated_collateral, asset_id, |total_debt| {
+			*total_debt = total_debt
+				.checked_add(amount)
+				.ok_or(ArithmeticError::Overflow)?;
+			Ok::<(), DispatchError>(())
+		})
+	}
+
+	// Releases `repay_amount` of `asset_id` from the isolated debt recorded for `borrower`.
+	// Must be called after the borrow balance is updated, the whole record is released once
+	// the borrow is fully repaid.
+	fn decrease_isolation_debt(
+		borrower: &T::AccountId,
+		asset_id: AssetIdOf<T>,
+		repay_amount: BalanceOf<T>,
+	) -> DispatchResult {
+		let Some((isolated_collateral, recorded)) =
+			AccountIsolationDebt::<T>::get(borrower, asset_id)
+		else {
+			return Ok(());
+		};
+		// Repayments include interest, so they may exceed the recorded principal.
+		let released = if Self::current_borrow_balance(borrower, asset_id)?.is_zero() {
+			recorded
+		} else {
+			recorded.min(repay_amount)
+		};
+		let remaining = recorded.saturating_sub(released);
+		if remaining.is_zero() {
+			AccountIsolationDebt::<T>::remove(borrower, asset_id);
+		} else {
+			AccountIsolationDebt::<T>::insert(borrower, asset_id, (isolated_collateral, remaining));
+		}
+		IsolationTotalDebt::<T>::mutate_exists(isolated_collateral, asset_id, |total_debt| {
+			let total_debt_new = total_debt.unwrap_or_default().saturating_sub(released);
+			*total_debt = (!total_debt_new.is_zero()).then_some(total_debt_new);
+		});
+		Ok(())
+	}
+
 	fn ensure_liquidity_for_market_bond(
 		borrow_asset_id: AssetIdOf<T>,
 		account: &T::AccountId,
@@ -2167,6 +2704,7 @@ impl<T: Config> LendMarketTrait<AssetIdOf<T>, AccountIdOf<T>, BalanceOf<T>> for
 			*total_balance = new_balance;
 			Ok(())
 		})?;
+		Self::close_recovered_liquidation_auction(supplier);
 		Self::deposit_event(Event::<T>::Deposited(supplier.clone(), asset_id, amount));
 		Ok(())
 	}
@@ -2203,6 +2741,7 @@ impl<T: Config> LendMarketTrait<AssetIdOf<T>, AccountIdOf<T>, BalanceOf<T>> for
 			},
 		);
 		TotalBorrows::<T>::insert(asset_id, total_borrows_new);
+		Self::increase_isolation_debt(borrower, asset_id, amount)?;
 		T::Assets::transfer(
 			asset_id,
 			&Self::account_id(),
@@ -2227,8 +2766,23 @@ impl<T: Config> LendMarketTrait<AssetIdOf<T>, AccountIdOf<T>, BalanceOf<T>> for
 		let mut deposits = AccountDeposits::<T>::get(asset_id, supplier);
 		// turn on the collateral button
 		if enable {
+			// isolated collateral cannot be combined with any other collateral
+			let is_isolated = IsolationDebtCeilings::<T>::contains_key(asset_id);
+			for (other_asset_id, _) in Self::active_markets() {
+				if other_asset_id == asset_id
+					|| !AccountDeposits::<T>::get(other_asset_id, supplier).is_collateral
+				{
+					continue;
+				}
+				ensure!(
+					!is_isolated && !IsolationDebtCeilings::<T>::contains_key(other_asset_id),
+					Error::<T>::IsolatedCollateralConflict
+				);
+			}
+
 			deposits.is_collateral = true;
 			AccountDeposits::<T>::insert(asset_id, supplier, deposits);
+			Self::close_recovered_liquidation_auction(supplier);
 			Self::deposit_event(Event::<T>::CollateralAssetAdded(supplier.clone(), asset_id));
 			return Ok(());
 		}
@@ -2270,6 +2824,7 @@ impl<T: Config> LendMarketTrait<AssetIdOf<T>, AccountIdOf<T>, BalanceOf<T>> for
 		Self::accrue_interest(asset_id)?;
 		let account_borrows = Self::current_borrow_balance(borrower, asset_id)?;
 		Self::do_repay_borrow_with_amount(borrower, asset_id, account_borrows, amount)?;
+		Self::close_recovered_liquidation_auction(borrower);
 		Self::deposit_event(Event::<T>::RepaidBorrow(borrower.clone(), asset_id, amount));
 		Ok(())
 	}
@@ -2331,14 +2886,14 @@ impl<T: Config> LendMarketMarketDataProvider<AssetIdOf<T>, BalanceOf<T>> for Pal
 
 	fn get_full_interest_rate(asset_id: AssetIdOf<T>) -> Option<Rate> {
 		if let Ok(market) = Self::market(asset_id) {
-			let rate = match market.rate_model {
-				InterestRateModel::Jump(jump) => Some(jump.full_rate),
-				_ => None,
-			};
-			return rate;
+			return market.rate_model.full_rate();
 		}
 		None
 	}
+
+	fn get_underlying_asset_id(lend_token_id: AssetIdOf<T>) -> Option<AssetIdOf<T>> {
+		UnderlyingAssetId::<T>::get(lend_token_id)
+	}
 }
 
 impl<T: Config> LendMarketPositionDataProvider<AssetIdOf<T>, AccountIdOf<T>, BalanceOf<T>>
@@ -17,3 +17,4 @@
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
 pub mod v1;
+pub mod v2;
@@ -0,0 +1,141 @@
+// This file is part of Bifrost.
+
+// Copyright (C) Liebi Technologies PTE. LTD.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::*;
+use frame_support::{
+	pallet_prelude::StorageVersion,
+	traits::{GetStorageVersion, OnRuntimeUpgrade},
+};
+#[cfg(feature = "try-runtime")]
+use sp_runtime::TryRuntimeError;
+
+const LOG_TARGET: &str = "lend-market::migration";
+
+/// The storage types before the multi-kink, adaptive and vToken APY rate models.
+pub mod v1 {
+	use super::*;
+
+	#[derive(Encode, Decode, Clone, Copy, RuntimeDebug, TypeInfo)]
+	pub enum InterestRateModel {
+		Jump(JumpModel),
+		Curve(CurveModel),
+	}
+
+	impl From<InterestRateModel> for crate::InterestRateModel {
+		fn from(rate_model: InterestRateModel) -> Self {
+			match rate_model {
+				InterestRateModel::Jump(jump) => Self::Jump(jump),
+				InterestRateModel::Curve(curve) => Self::Curve(curve),
+			}
+		}
+	}
+
+	#[derive(Encode, Decode, Clone, RuntimeDebug, TypeInfo)]
+	pub struct Market<Balance> {
+		pub collateral_factor: Ratio,
+		pub liquidation_threshold: Ratio,
+		pub reserve_factor: Ratio,
+		pub close_factor: Ratio,
+		pub liquidate_incentive: Rate,
+		pub liquidate_incentive_reserved_factor: Ratio,
+		pub rate_model: InterestRateModel,
+		pub state: MarketState,
+		pub supply_cap: Balance,
+		pub borrow_cap: Balance,
+		pub lend_token_id: CurrencyId,
+	}
+
+	impl<Balance> From<Market<Balance>> for crate::Market<Balance> {
+		fn from(market: Market<Balance>) -> Self {
+			Self {
+				collateral_factor: market.collateral_factor,
+				liquidation_threshold: market.liquidation_threshold,
+				reserve_factor: market.reserve_factor,
+				close_factor: market.close_factor,
+				liquidate_incentive: market.liquidate_incentive,
+				liquidate_incentive_reserved_factor: market.liquidate_incentive_reserved_factor,
+				rate_model: market.rate_model.into(),
+				state: market.state,
+				supply_cap: market.supply_cap,
+				borrow_cap: market.borrow_cap,
+				lend_token_id: market.lend_token_id,
+			}
+		}
+	}
+}
+
+pub struct MigrateToV2<T>(sp_std::marker::PhantomData<T>);
+impl<T: Config> OnRuntimeUpgrade for MigrateToV2<T> {
+	fn on_runtime_upgrade() -> frame_support::weights::Weight {
+		// Check the storage version
+		let onchain_version = Pallet::<T>::on_chain_storage_version();
+		if onchain_version < 2 {
+			// The rate models of the existing markets are translated into the new
+			// `InterestRateModel`, which adds the multi-kink, adaptive and vToken APY models.
+			log::info!(target: LOG_TARGET, "Start to migrate Markets storage...");
+			let mut market_count = 0u64;
+			Markets::<T>::translate::<v1::Market<BalanceOf<T>>, _>(
+				|k: AssetIdOf<T>, market: v1::Market<BalanceOf<T>>| {
+					log::info!(target: LOG_TARGET, "Migrated rate model for {:?}...", k);
+					market_count += 1;
+					Some(market.into())
+				},
+			);
+
+			// Update the storage version
+			StorageVersion::new(2).put::<Pallet<T>>();
+
+			// Return the consumed weight
+			Weight::from(T::DbWeight::get().reads_writes(market_count + 1, market_count + 1))
+		} else {
+			// We don't do anything here.
+			Weight::zero()
+		}
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn pre_upgrade() -> Result<Vec<u8>, TryRuntimeError> {
+		let market_count = Markets::<T>::iter().count();
+
+		// print out the pre-migrate storage count
+		log::info!(target: LOG_TARGET, "Markets pre-migrate storage count: {:?}", market_count);
+		Ok((market_count as u64).encode())
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade(cnt: Vec<u8>) -> Result<(), TryRuntimeError> {
+		let new_market_count = Markets::<T>::iter().count();
+
+		let old_market_count: u64 = Decode::decode(&mut cnt.as_slice())
+			.expect("the state parameter should be something that was generated by pre_upgrade");
+
+		// print out the post-migrate storage count
+		log::info!(target: LOG_TARGET, "Markets post-migrate storage count: {:?}", new_market_count);
+
+		ensure!(
+			new_market_count as u64 == old_market_count,
+			"Markets Post-migration storage count does not match pre-migration count"
+		);
+		ensure!(
+			Markets::<T>::iter().all(|(_, market)| market.rate_model.check_model()),
+			"Markets Post-migration rate model is invalid"
+		);
+
+		Ok(())
+	}
+}
@@ -312,6 +312,11 @@ impl pallet_prices::Config for Test {
 	type RelayCurrency = RelayCurrency;
 	type Assets = Currencies;
 	type CurrencyIdConvert = AssetIdMaps<Test>;
+	type VtokenMinting = ();
+	type LendMarket = ();
+	type StableLpToken = ();
+	type UnixTime = TimestampPallet;
+	type StableTwap = ();
 	type WeightInfo = ();
 }
 
@@ -321,6 +326,30 @@ parameter_types! {
 	pub const MaxLengthLimit: u32 = 500;
 }
 
+parameter_types! {
+	pub static TokenSupply: Balance = 0;
+	pub static VTokenSupply: Balance = 0;
+}
+
+pub struct MockVTokenSupplyProvider;
+
+impl MockVTokenSupplyProvider {
+	pub fn set_supplies(token_supply: Balance, vtoken_supply: Balance) {
+		TokenSupply::set(token_supply);
+		VTokenSupply::set(vtoken_supply);
+	}
+}
+
+impl VTokenSupplyProvider<CurrencyId, Balance> for MockVTokenSupplyProvider {
+	fn get_vtoken_supply(_: CurrencyId) -> Option<Balance> {
+		Some(VTokenSupply::get())
+	}
+
+	fn get_token_supply(_: CurrencyId) -> Option<Balance> {
+		Some(TokenSupply::get())
+	}
+}
+
 impl Config for Test {
 	type RuntimeEvent = RuntimeEvent;
 	type OraclePriceProvider = MockOraclePriceProvider;
@@ -334,6 +363,7 @@ impl Config for Test {
 	type LiquidationFreeAssetId = LiquidationFreeAssetId;
 	type MaxLengthLimit = MaxLengthLimit;
 	type BlockNumberProvider = System;
+	type VTokenSupplyProvider = MockVTokenSupplyProvider;
 }
 
 pub(crate) fn new_test_ext() -> sp_io::TestExternalities {
@@ -16,9 +16,9 @@
 // You should have received a copy of the GNU General Public License
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
-use bifrost_primitives::{Rate, Ratio};
+use bifrost_primitives::{CurrencyId, Rate, Ratio, Timestamp, SECONDS_PER_YEAR};
 use scale_info::TypeInfo;
-use sp_runtime::traits::{CheckedAdd, CheckedDiv, CheckedSub, Saturating};
+use sp_runtime::traits::{CheckedAdd, CheckedDiv, CheckedMul, CheckedSub, Saturating};
 
 use crate::*;
 
@@ -28,6 +28,19 @@ use crate::*;
 pub enum InterestRateModel {
 	Jump(JumpModel),
 	Curve(CurveModel),
+	MultiKink(MultiKinkModel),
+	Adaptive(AdaptiveModel),
+	VTokenApy(VTokenApyModel),
+}
+
+/// The inputs of the interest rate models that change over time
+#[derive(Eq, PartialEq, Copy, Clone, RuntimeDebug, Default)]
+pub struct RateModelState {
+	/// The current rate at target utilization of the adaptive model, `None` until it has
+	/// been adjusted once
+	pub rate_at_target: Option<Rate>,
+	/// The staking APY of the vToken tracked by the vToken APY model
+	pub staking_apy: Rate,
 }
 
 impl Default for InterestRateModel {
@@ -60,18 +73,49 @@ impl InterestRateModel {
 		Self::Curve(CurveModel::new_model(base_rate))
 	}
 
+	pub fn new_multi_kink_model(
+		base_rate: Rate,
+		kinks: [Kink; MAX_KINKS],
+		full_rate: Rate,
+	) -> Self {
+		Self::MultiKink(MultiKinkModel::new_model(base_rate, kinks, full_rate))
+	}
+
 	pub fn check_model(&self) -> bool {
 		match self {
 			Self::Jump(jump) => jump.check_model(),
 			Self::Curve(curve) => curve.check_model(),
+			Self::MultiKink(multi_kink) => multi_kink.check_model(),
+			Self::Adaptive(adaptive) => adaptive.check_model(),
+			Self::VTokenApy(vtoken_apy) => vtoken_apy.check_model(),
 		}
 	}
 
 	/// Calculates the current borrow interest rate
-	pub fn get_borrow_rate(&self, utilization: Ratio) -> Option<Rate> {
+	pub fn get_borrow_rate(&self, utilization: Ratio, state: &RateModelState) -> Option<Rate> {
 		match self {
 			Self::Jump(jump) => jump.get_borrow_rate(utilization),
 			Self::Curve(curve) => curve.get_borrow_rate(utilization),
+			Self::MultiKink(multi_kink) => multi_kink.get_borrow_rate(utilization),
+			Self::Adaptive(adaptive) => adaptive.get_borrow_rate(
+				utilization,
+				state
+					.rate_at_target
+					.unwrap_or(adaptive.initial_rate_at_target),
+			),
+			Self::VTokenApy(vtoken_apy) => {
+				vtoken_apy.get_borrow_rate(utilization, state.staking_apy)
+			}
+		}
+	}
+
+	/// The interest rate when utilization rate is 100%, if it doesn't change over time
+	pub fn full_rate(&self) -> Option<Rate> {
+		match self {
+			Self::Jump(jump) => Some(jump.full_rate),
+			Self::MultiKink(multi_kink) => Some(multi_kink.full_rate),
+			Self::VTokenApy(vtoken_apy) => Some(vtoken_apy.full_rate),
+			_ => None,
 		}
 	}
 
@@ -192,6 +236,275 @@ impl CurveModel {
 	}
 }
 
+/// The number of kinks of the multi-kink model
+pub const MAX_KINKS: usize = 3;
+
+/// A point of the multi-kink model
+#[cfg_attr(feature = "std", derive(serde::Deserialize, serde::Serialize))]
+#[derive(Encode, Decode, Eq, PartialEq, Copy, Clone, RuntimeDebug, Default, TypeInfo)]
+pub struct Kink {
+	/// The utilization rate of the kink
+	pub utilization: Ratio,
+	/// The interest rate on the kink
+	pub rate: Rate,
+}
+
+/// The multi-kink interest rate model
+///
+/// The borrow rate is piecewise linear from `base_rate` through the kinks to `full_rate`.
+/// A kink may repeat the previous one to use fewer segments.
+#[cfg_attr(feature = "std", derive(serde::Deserialize, serde::Serialize))]
+#[derive(Encode, Decode, Eq, PartialEq, Copy, Clone, RuntimeDebug, Default, TypeInfo)]
+pub struct MultiKinkModel {
+	/// The base interest rate when utilization rate is 0
+	pub base_rate: Rate,
+	/// The kinks, ordered by utilization rate
+	pub kinks: [Kink; MAX_KINKS],
+	/// The max interest rate when utilization rate is 100%
+	pub full_rate: Rate,
+}
+
+impl MultiKinkModel {
+	pub const MAX_BASE_RATE: Rate = Rate::from_inner(Rate::DIV / 100 * 10); // 10%
+	pub const MAX_FULL_RATE: Rate = Rate::from_inner(Rate::DIV); // 100%
+
+	/// Create a new multi-kink model
+	pub fn new_model(base_rate: Rate, kinks: [Kink; MAX_KINKS], full_rate: Rate) -> Self {
+		Self {
+			base_rate,
+			kinks,
+			full_rate,
+		}
+	}
+
+	/// Check the multi-kink model for sanity
+	pub fn check_model(&self) -> bool {
+		if self.base_rate > Self::MAX_BASE_RATE || self.full_rate > Self::MAX_FULL_RATE {
+			return false;
+		}
+		let mut previous = Kink {
+			utilization: Ratio::zero(),
+			rate: self.base_rate,
+		};
+		for kink in self.kinks.iter() {
+			if kink.utilization.is_zero()
+				|| kink.utilization == Ratio::one()
+				|| kink.utilization < previous.utilization
+				|| kink.rate < previous.rate
+			{
+				return false;
+			}
+			previous = *kink;
+		}
+
+		previous.rate <= self.full_rate
+	}
+
+	/// Calculates the borrow interest rate of multi-kink model
+	pub fn get_borrow_rate(&self, utilization: Ratio) -> Option<Rate> {
+		let full = Kink {
+			utilization: Ratio::one(),
+			rate: self.full_rate,
+		};
+		let mut start = Kink {
+			utilization: Ratio::zero(),
+			rate: self.base_rate,
+		};
+		for end in self.kinks.iter().chain(sp_std::iter::once(&full)) {
+			if utilization <= end.utilization {
+				// (utilization - start_utilization) * (end_rate - start_rate) /
+				// (end_utilization - start_utilization) + start_rate
+				let result = end
+					.rate
+					.checked_sub(&start.rate)?
+					.saturating_mul(utilization.saturating_sub(start.utilization).into())
+					.checked_div(&(end.utilization.saturating_sub(start.utilization).into()))?
+					.checked_add(&start.rate)?;
+
+				return Some(result);
+			}
+			start = *end;
+		}
+
+		None
+	}
+}
+
+/// The adaptive interest rate model
+///
+/// The borrow rate follows a curve that goes through the rate at target utilization. This
+/// rate drifts over time: it rises while the utilization is above `target_utilization` and
+/// falls while it is below, faster the further the utilization is from the target.
+#[cfg_attr(feature = "std", derive(serde::Deserialize, serde::Serialize))]
+#[derive(Encode, Decode, Eq, PartialEq, Copy, Clone, RuntimeDebug, Default, TypeInfo)]
+pub struct AdaptiveModel {
+	/// The utilization rate the model steers the market to
+	pub target_utilization: Ratio,
+	/// The interest rate at target utilization when the model is set
+	pub initial_rate_at_target: Rate,
+	/// The lower bound of the interest rate at target utilization
+	pub min_rate_at_target: Rate,
+	/// The upper bound of the interest rate at target utilization
+	pub max_rate_at_target: Rate,
+	/// The interest rate when utilization rate is 100% divided by the one at target
+	/// utilization, which is also the ratio of the latter to the base interest rate
+	pub curve_steepness: Rate,
+	/// The yearly relative change of the interest rate at target utilization when
+	/// utilization rate is 0 or 100%
+	pub adjustment_speed: Rate,
+}
+
+impl AdaptiveModel {
+	pub const MAX_RATE_AT_TARGET: Rate = Rate::from_inner(Rate::DIV / 100 * 25); // 25%
+	pub const MAX_CURVE_STEEPNESS: Rate = Rate::from_inner(Rate::DIV * 10);
+	pub const MAX_ADJUSTMENT_SPEED: Rate = Rate::from_inner(Rate::DIV * 100);
+
+	/// Check the adaptive model for sanity
+	pub fn check_model(&self) -> bool {
+		if self.target_utilization.is_zero() || self.target_utilization == Ratio::one() {
+			return false;
+		}
+		if self.min_rate_at_target.is_zero()
+			|| self.min_rate_at_target > self.initial_rate_at_target
+			|| self.initial_rate_at_target > self.max_rate_at_target
+			|| self.max_rate_at_target > Self::MAX_RATE_AT_TARGET
+		{
+			return false;
+		}
+
+		self.curve_steepness >= Rate::one()
+			&& self.curve_steepness <= Self::MAX_CURVE_STEEPNESS
+			&& self.adjustment_speed <= Self::MAX_ADJUSTMENT_SPEED
+	}
+
+	/// Calculates the borrow interest rate of adaptive model
+	pub fn get_borrow_rate(&self, utilization: Ratio, rate_at_target: Rate) -> Option<Rate> {
+		if utilization <= self.target_utilization {
+			// utilization * (rate_at_target - base_rate) / target_utilization + base_rate
+			let base_rate = rate_at_target.checked_div(&self.curve_steepness)?;
+			let result = rate_at_target
+				.checked_sub(&base_rate)?
+				.saturating_mul(utilization.into())
+				.checked_div(&self.target_utilization.into())?
+				.checked_add(&base_rate)?;
+
+			Some(result)
+		} else {
+			// (utilization - target_utilization) * (full_rate - rate_at_target) /
+			// (1 - target_utilization) + rate_at_target
+			let full_rate = rate_at_target.checked_mul(&self.curve_steepness)?;
+			let excess_util = utilization.saturating_sub(self.target_utilization);
+			let result = full_rate
+				.checked_sub(&rate_at_target)?
+				.saturating_mul(excess_util.into())
+				.checked_div(&(Ratio::one().saturating_sub(self.target_utilization).into()))?
+				.checked_add(&rate_at_target)?;
+
+			Some(result)
+		}
+	}
+
+	/// Calculates the interest rate at target utilization after `delta_time` seconds at
+	/// `utilization`
+	pub fn next_rate_at_target(
+		&self,
+		rate_at_target: Rate,
+		utilization: Ratio,
+		delta_time: Timestamp,
+	) -> Rate {
+		let elapsed = Rate::saturating_from_rational(delta_time, SECONDS_PER_YEAR);
+		// The distance from the target, normalized to [0, 1]
+		let (distance, rising) = if utilization > self.target_utilization {
+			(
+				Ratio::from_rational(
+					utilization
+						.saturating_sub(self.target_utilization)
+						.deconstruct(),
+					Ratio::one()
+						.saturating_sub(self.target_utilization)
+						.deconstruct(),
+				),
+				true,
+			)
+		} else {
+			(
+				Ratio::from_rational(
+					self.target_utilization
+						.saturating_sub(utilization)
+						.deconstruct(),
+					self.target_utilization.deconstruct(),
+				),
+				false,
+			)
+		};
+		let change = rate_at_target.saturating_mul(
+			self.adjustment_speed
+				.saturating_mul(distance.into())
+				.saturating_mul(elapsed),
+		);
+		let next = if rising {
+			rate_at_target.saturating_add(change)
+		} else {
+			rate_at_target.saturating_sub(change)
+		};
+
+		next.clamp(self.min_rate_at_target, self.max_rate_at_target)
+	}
+}
+
+/// The vToken APY tracking interest rate model
+///
+/// Below `jump_utilization` the borrow rate is the staking APY of `vtoken_id` plus a spread
+/// growing with utilization, so that borrowing the token to stake it stays profitable. Above
+/// it the borrow rate rises linearly to `full_rate`.
+#[cfg_attr(feature = "std", derive(serde::Deserialize, serde::Serialize))]
+#[derive(Encode, Decode, Eq, PartialEq, Copy, Clone, RuntimeDebug, TypeInfo)]
+pub struct VTokenApyModel {
+	/// The vToken whose staking APY is tracked
+	pub vtoken_id: CurrencyId,
+	/// The upper bound of the tracked staking APY
+	pub max_apy: Rate,
+	/// The interest rate added to the staking APY on jump utilization point
+	pub spread: Rate,
+	/// The max interest rate when utilization rate is 100%
+	pub full_rate: Rate,
+	/// The utilization point at which the whole spread is applied
+	pub jump_utilization: Ratio,
+}
+
+impl VTokenApyModel {
+	pub const MAX_APY: Rate = Rate::from_inner(Rate::DIV / 100 * 30); // 30%
+	pub const MAX_SPREAD: Rate = Rate::from_inner(Rate::DIV / 100 * 10); // 10%
+	pub const MAX_FULL_RATE: Rate = Rate::from_inner(Rate::DIV / 100 * 50); // 50%
+	/// The minimum seconds between two samples of the vToken exchange rate
+	pub const APY_SAMPLE_PERIOD: Timestamp = 24 * 60 * 60;
+
+	/// Check the vToken APY model for sanity
+	pub fn check_model(&self) -> bool {
+		if self.vtoken_id.to_token().is_err() {
+			return false;
+		}
+		if self.max_apy > Self::MAX_APY
+			|| self.spread > Self::MAX_SPREAD
+			|| self.full_rate > Self::MAX_FULL_RATE
+			|| self.max_apy.saturating_add(self.spread) > self.full_rate
+		{
+			return false;
+		}
+
+		!self.jump_utilization.is_zero() && self.jump_utilization < Ratio::one()
+	}
+
+	/// Calculates the borrow interest rate of vToken APY model
+	pub fn get_borrow_rate(&self, utilization: Ratio, staking_apy: Rate) -> Option<Rate> {
+		let base_rate = staking_apy.min(self.max_apy);
+		let jump_rate = base_rate.checked_add(&self.spread)?;
+
+		JumpModel::new_model(base_rate, jump_rate, self.full_rate, self.jump_utilization)
+			.get_borrow_rate(utilization)
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -267,6 +580,142 @@ mod tests {
 		);
 	}
 
+	#[test]
+	fn multi_kink_model_works() {
+		let kink = |utilization, rate| Kink {
+			utilization: Ratio::from_percent(utilization),
+			rate: Rate::saturating_from_rational(rate, 100),
+		};
+		let model = MultiKinkModel::new_model(
+			Rate::saturating_from_rational(2, 100),
+			[kink(50, 6), kink(80, 10), kink(90, 30)],
+			Rate::saturating_from_rational(80, 100),
+		);
+		assert!(model.check_model());
+
+		assert_eq!(model.get_borrow_rate(Ratio::zero()), Some(model.base_rate));
+		assert_eq!(
+			model.get_borrow_rate(Ratio::from_percent(25)),
+			Some(Rate::saturating_from_rational(4, 100))
+		);
+		assert_eq!(
+			model.get_borrow_rate(Ratio::from_percent(80)),
+			Some(Rate::saturating_from_rational(10, 100))
+		);
+		assert_eq!(
+			model.get_borrow_rate(Ratio::from_percent(85)),
+			Some(Rate::saturating_from_rational(20, 100))
+		);
+		assert_eq!(
+			model.get_borrow_rate(Ratio::from_percent(95)),
+			Some(Rate::saturating_from_rational(55, 100))
+		);
+		assert_eq!(model.get_borrow_rate(Ratio::one()), Some(model.full_rate));
+
+		// Repeating a kink removes a segment
+		let model = MultiKinkModel::new_model(
+			Rate::saturating_from_rational(2, 100),
+			[kink(50, 6), kink(50, 6), kink(90, 30)],
+			Rate::saturating_from_rational(80, 100),
+		);
+		assert!(model.check_model());
+		assert_eq!(
+			model.get_borrow_rate(Ratio::from_percent(70)),
+			Some(Rate::saturating_from_rational(18, 100))
+		);
+
+		// Kinks must be ordered
+		let model = MultiKinkModel::new_model(
+			Rate::saturating_from_rational(2, 100),
+			[kink(80, 10), kink(50, 6), kink(90, 30)],
+			Rate::saturating_from_rational(80, 100),
+		);
+		assert!(!model.check_model());
+	}
+
+	#[test]
+	fn adaptive_model_works() {
+		let model = AdaptiveModel {
+			target_utilization: Ratio::from_percent(90),
+			initial_rate_at_target: Rate::saturating_from_rational(4, 100),
+			min_rate_at_target: Rate::saturating_from_rational(1, 100),
+			max_rate_at_target: Rate::saturating_from_rational(20, 100),
+			curve_steepness: Rate::saturating_from_integer(4),
+			adjustment_speed: Rate::saturating_from_integer(50),
+		};
+		assert!(model.check_model());
+
+		let rate_at_target = model.initial_rate_at_target;
+		assert_eq!(
+			model.get_borrow_rate(Ratio::zero(), rate_at_target),
+			Some(Rate::saturating_from_rational(1, 100))
+		);
+		assert_eq!(
+			model.get_borrow_rate(Ratio::from_percent(90), rate_at_target),
+			Some(rate_at_target)
+		);
+		assert_eq!(
+			model.get_borrow_rate(Ratio::one(), rate_at_target),
+			Some(Rate::saturating_from_rational(16, 100))
+		);
+
+		// The rate at target doesn't move on target
+		assert_eq!(
+			model.next_rate_at_target(rate_at_target, Ratio::from_percent(90), 1000),
+			rate_at_target
+		);
+		// It rises above target and falls below target
+		let higher = model.next_rate_at_target(rate_at_target, Ratio::from_percent(95), 86400);
+		assert!(higher > rate_at_target);
+		let lower = model.next_rate_at_target(rate_at_target, Ratio::from_percent(45), 86400);
+		assert!(lower < rate_at_target);
+		// Within bounds
+		assert_eq!(
+			model.next_rate_at_target(rate_at_target, Ratio::one(), SECONDS_PER_YEAR),
+			model.max_rate_at_target
+		);
+		assert_eq!(
+			model.next_rate_at_target(rate_at_target, Ratio::zero(), SECONDS_PER_YEAR),
+			model.min_rate_at_target
+		);
+	}
+
+	#[test]
+	fn vtoken_apy_model_works() {
+		let model = VTokenApyModel {
+			vtoken_id: CurrencyId::VToken2(0),
+			max_apy: Rate::saturating_from_rational(20, 100),
+			spread: Rate::saturating_from_rational(2, 100),
+			full_rate: Rate::saturating_from_rational(50, 100),
+			jump_utilization: Ratio::from_percent(80),
+		};
+		assert!(model.check_model());
+		assert!(!VTokenApyModel {
+			vtoken_id: CurrencyId::Token2(0),
+			..model
+		}
+		.check_model());
+
+		let staking_apy = Rate::saturating_from_rational(15, 100);
+		assert_eq!(
+			model.get_borrow_rate(Ratio::zero(), staking_apy),
+			Some(staking_apy)
+		);
+		assert_eq!(
+			model.get_borrow_rate(Ratio::from_percent(80), staking_apy),
+			Some(Rate::saturating_from_rational(17, 100))
+		);
+		assert_eq!(
+			model.get_borrow_rate(Ratio::one(), staking_apy),
+			Some(model.full_rate)
+		);
+		// The staking APY is capped
+		assert_eq!(
+			model.get_borrow_rate(Ratio::zero(), Rate::saturating_from_rational(40, 100)),
+			Some(model.max_apy)
+		);
+	}
+
 	#[test]
 	fn curve_model_correctly_calculates_borrow_rate() {
 		let model = CurveModel::new_model(Rate::saturating_from_rational(2, 100));
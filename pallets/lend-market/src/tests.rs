@@ -16,7 +16,9 @@
 // You should have received a copy of the GNU General Public License
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
+mod auction;
 mod edge_cases;
+mod emode;
 mod interest_rate;
 mod lend_tokens;
 mod liquidate_borrow;
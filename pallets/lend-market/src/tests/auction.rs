@@ -0,0 +1,424 @@
+use crate::{
+	mock::*, tests::unit, AccountBorrows, AccountDeposits, AuctionConfig, Error,
+	LiquidationAuctionConfig, LiquidationAuctions, TotalReserves,
+};
+use frame_support::{assert_noop, assert_ok};
+use sp_runtime::{traits::Zero, FixedU128};
+
+const CONFIG: AuctionConfig = AuctionConfig {
+	initial_discount: Ratio::from_percent(5),
+	max_discount: Ratio::from_percent(25),
+	duration: 100,
+};
+
+fn initial_setup() {
+	assert_ok!(LendMarket::add_market_bond(
+		RuntimeOrigin::root(),
+		KSM,
+		vec![DOT_U]
+	));
+	assert_ok!(LendMarket::set_liquidation_auction_config(
+		RuntimeOrigin::root(),
+		Some(CONFIG)
+	));
+	// Bob deposits 200 KSM
+	assert_ok!(LendMarket::mint(RuntimeOrigin::signed(BOB), KSM, unit(200)));
+	// Alice deposits 200 DOT_U as collateral and borrows 100 KSM
+	assert_ok!(LendMarket::mint(
+		RuntimeOrigin::signed(ALICE),
+		DOT_U,
+		unit(200)
+	));
+	assert_ok!(LendMarket::collateral_asset(
+		RuntimeOrigin::signed(ALICE),
+		DOT_U,
+		true
+	));
+	assert_ok!(LendMarket::borrow(
+		RuntimeOrigin::signed(ALICE),
+		KSM,
+		unit(100)
+	));
+}
+
+fn seized_voucher_balance(bidder: AccountId) -> u128 {
+	AccountDeposits::<Test>::get(DOT_U, bidder).voucher_balance
+}
+
+#[test]
+fn set_liquidation_auction_config_works() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			LendMarket::set_liquidation_auction_config(RuntimeOrigin::signed(ALICE), Some(CONFIG)),
+			sp_runtime::DispatchError::BadOrigin
+		);
+		assert_noop!(
+			LendMarket::set_liquidation_auction_config(
+				RuntimeOrigin::root(),
+				Some(AuctionConfig {
+					max_discount: Ratio::from_percent(100),
+					..CONFIG
+				})
+			),
+			Error::<Test>::InvalidFactor
+		);
+		assert_noop!(
+			LendMarket::set_liquidation_auction_config(
+				RuntimeOrigin::root(),
+				Some(AuctionConfig {
+					initial_discount: Ratio::from_percent(30),
+					..CONFIG
+				})
+			),
+			Error::<Test>::InvalidFactor
+		);
+		assert_noop!(
+			LendMarket::set_liquidation_auction_config(
+				RuntimeOrigin::root(),
+				Some(AuctionConfig {
+					duration: 0,
+					..CONFIG
+				})
+			),
+			Error::<Test>::InvalidAmount
+		);
+		assert_ok!(LendMarket::set_liquidation_auction_config(
+			RuntimeOrigin::root(),
+			Some(CONFIG)
+		));
+		assert_eq!(LiquidationAuctionConfig::<Test>::get(), Some(CONFIG));
+	})
+}
+
+#[test]
+fn auction_discount_grows_linearly() {
+	new_test_ext().execute_with(|| {
+		// The mock starts at 6 seconds
+		assert_eq!(
+			LendMarket::auction_discount(&CONFIG, 6),
+			Ratio::from_percent(5)
+		);
+		TimestampPallet::set_timestamp(56_000);
+		assert_eq!(
+			LendMarket::auction_discount(&CONFIG, 6),
+			Ratio::from_percent(15)
+		);
+		TimestampPallet::set_timestamp(106_000);
+		assert_eq!(
+			LendMarket::auction_discount(&CONFIG, 6),
+			Ratio::from_percent(25)
+		);
+		TimestampPallet::set_timestamp(1_000_000);
+		assert_eq!(
+			LendMarket::auction_discount(&CONFIG, 6),
+			Ratio::from_percent(25)
+		);
+	})
+}
+
+#[test]
+fn start_liquidation_auction_works() {
+	new_test_ext().execute_with(|| {
+		initial_setup();
+		assert_noop!(
+			LendMarket::start_liquidation_auction(RuntimeOrigin::signed(BOB), ALICE),
+			Error::<Test>::InsufficientShortfall
+		);
+		assert!(LendMarket::get_liquidatable_accounts(None, 10).0.is_empty());
+
+		// Adjust KSM price to make shortfall
+		MockOraclePriceProvider::set_price(KSM, 2.into());
+		assert_eq!(
+			LendMarket::get_liquidatable_accounts(None, 10),
+			(vec![ALICE], None)
+		);
+		assert_ok!(LendMarket::start_liquidation_auction(
+			RuntimeOrigin::signed(BOB),
+			ALICE
+		));
+		assert_eq!(LiquidationAuctions::<Test>::get(ALICE), Some(6));
+		assert_noop!(
+			LendMarket::start_liquidation_auction(RuntimeOrigin::signed(BOB), ALICE),
+			Error::<Test>::AuctionAlreadyStarted
+		);
+	})
+}
+
+#[test]
+fn get_liquidatable_accounts_is_paged() {
+	new_test_ext().execute_with(|| {
+		initial_setup();
+		MockOraclePriceProvider::set_price(KSM, 2.into());
+
+		let (accounts, next_key) = LendMarket::get_liquidatable_accounts(None, 0);
+		assert!(accounts.is_empty());
+		let (accounts, next_key) = LendMarket::get_liquidatable_accounts(next_key, 1);
+		assert_eq!(accounts, vec![ALICE]);
+		assert!(next_key.is_some());
+		assert_eq!(
+			LendMarket::get_liquidatable_accounts(next_key, 1),
+			(vec![], None)
+		);
+	})
+}
+
+#[test]
+fn recovered_auction_is_closed_and_restarted() {
+	new_test_ext().execute_with(|| {
+		initial_setup();
+		MockOraclePriceProvider::set_price(KSM, 2.into());
+		assert_ok!(LendMarket::start_liquidation_auction(
+			RuntimeOrigin::signed(BOB),
+			ALICE
+		));
+		assert_eq!(LiquidationAuctions::<Test>::get(ALICE), Some(6));
+
+		// The price recovers, anyone can close the stale auction
+		MockOraclePriceProvider::set_price(KSM, 1.into());
+		assert_ok!(LendMarket::start_liquidation_auction(
+			RuntimeOrigin::signed(BOB),
+			ALICE
+		));
+		assert!(!LiquidationAuctions::<Test>::contains_key(ALICE));
+
+		// A later shortfall starts over at the initial discount
+		TimestampPallet::set_timestamp(1_000_000);
+		MockOraclePriceProvider::set_price(KSM, 2.into());
+		assert_ok!(LendMarket::start_liquidation_auction(
+			RuntimeOrigin::signed(BOB),
+			ALICE
+		));
+		assert_eq!(LiquidationAuctions::<Test>::get(ALICE), Some(1_000));
+		assert_eq!(
+			LendMarket::auction_discount(&CONFIG, 1_000),
+			CONFIG.initial_discount
+		);
+	})
+}
+
+#[test]
+fn repaying_closes_auction() {
+	new_test_ext().execute_with(|| {
+		initial_setup();
+		MockOraclePriceProvider::set_price(KSM, 2.into());
+		assert_ok!(LendMarket::start_liquidation_auction(
+			RuntimeOrigin::signed(BOB),
+			ALICE
+		));
+
+		// Still underwater after a small repayment
+		assert_ok!(LendMarket::repay_borrow(
+			RuntimeOrigin::signed(ALICE),
+			KSM,
+			unit(1)
+		));
+		assert!(LiquidationAuctions::<Test>::contains_key(ALICE));
+
+		assert_ok!(LendMarket::repay_borrow_all(
+			RuntimeOrigin::signed(ALICE),
+			KSM
+		));
+		assert!(!LiquidationAuctions::<Test>::contains_key(ALICE));
+	})
+}
+
+#[test]
+fn start_liquidation_auction_fails_without_config() {
+	new_test_ext().execute_with(|| {
+		initial_setup();
+		assert_ok!(LendMarket::set_liquidation_auction_config(
+			RuntimeOrigin::root(),
+			None
+		));
+		MockOraclePriceProvider::set_price(KSM, 2.into());
+		assert_noop!(
+			LendMarket::start_liquidation_auction(RuntimeOrigin::signed(BOB), ALICE),
+			Error::<Test>::AuctionNotConfigured
+		);
+	})
+}
+
+#[test]
+fn bid_liquidation_auction_discount_increases_over_time() {
+	new_test_ext().execute_with(|| {
+		initial_setup();
+		MockOraclePriceProvider::set_price(KSM, 2.into());
+		assert_noop!(
+			LendMarket::bid_liquidation_auction(
+				RuntimeOrigin::signed(BOB),
+				ALICE,
+				KSM,
+				unit(10),
+				DOT_U
+			),
+			Error::<Test>::AuctionNotStarted
+		);
+		assert_ok!(LendMarket::start_liquidation_auction(
+			RuntimeOrigin::signed(BOB),
+			ALICE
+		));
+		assert_noop!(
+			LendMarket::bid_liquidation_auction(
+				RuntimeOrigin::signed(BOB),
+				ALICE,
+				KSM,
+				unit(101),
+				DOT_U
+			),
+			Error::<Test>::TooMuchRepay
+		);
+
+		// Bob repays 10 KSM at a 5% discount
+		assert_ok!(LendMarket::bid_liquidation_auction(
+			RuntimeOrigin::signed(BOB),
+			ALICE,
+			KSM,
+			unit(10),
+			DOT_U
+		));
+		let first_seized = seized_voucher_balance(BOB);
+
+		// Bob repays 10 KSM again at a 15% discount, and gets more collateral
+		TimestampPallet::set_timestamp(56_000);
+		assert_ok!(LendMarket::bid_liquidation_auction(
+			RuntimeOrigin::signed(BOB),
+			ALICE,
+			KSM,
+			unit(10),
+			DOT_U
+		));
+		let second_seized = seized_voucher_balance(BOB) - first_seized;
+		assert!(second_seized > first_seized);
+
+		// Alice is still underwater, the auction goes on
+		assert!(LiquidationAuctions::<Test>::contains_key(ALICE));
+	})
+}
+
+#[test]
+fn bid_liquidation_auction_closes_when_healthy() {
+	new_test_ext().execute_with(|| {
+		initial_setup();
+		MockOraclePriceProvider::set_price(KSM, FixedU128::from_rational(3, 2));
+		assert_ok!(LendMarket::start_liquidation_auction(
+			RuntimeOrigin::signed(BOB),
+			ALICE
+		));
+		// Here the balance sheet of Alice is:
+		// Collateral   Borrows
+		// DOT_U $200   KSM $150
+		assert_ok!(LendMarket::bid_liquidation_auction(
+			RuntimeOrigin::signed(BOB),
+			ALICE,
+			KSM,
+			unit(70),
+			DOT_U
+		));
+		assert!(!LiquidationAuctions::<Test>::contains_key(ALICE));
+		assert!(LendMarket::get_liquidatable_accounts(None, 10).0.is_empty());
+	})
+}
+
+#[test]
+fn absorb_bad_debt_works() {
+	new_test_ext().execute_with(|| {
+		initial_setup();
+		assert_ok!(LendMarket::add_reserves(
+			RuntimeOrigin::root(),
+			BOB,
+			KSM,
+			unit(50)
+		));
+		MockOraclePriceProvider::set_price(KSM, 2.into());
+		assert_ok!(LendMarket::start_liquidation_auction(
+			RuntimeOrigin::signed(BOB),
+			ALICE
+		));
+		assert_noop!(
+			LendMarket::absorb_bad_debt(RuntimeOrigin::signed(BOB), ALICE),
+			Error::<Test>::AuctionNotFinished
+		);
+
+		// The collateral still covers the borrows
+		TimestampPallet::set_timestamp(106_000);
+		MockOraclePriceProvider::set_price(KSM, FixedU128::from_rational(3, 2));
+		assert_noop!(
+			LendMarket::absorb_bad_debt(RuntimeOrigin::signed(BOB), ALICE),
+			Error::<Test>::NoBadDebt
+		);
+
+		// Here the balance sheet of Alice is:
+		// Collateral   Borrows
+		// DOT_U $200   KSM $300
+		MockOraclePriceProvider::set_price(KSM, 3.into());
+		assert_ok!(LendMarket::absorb_bad_debt(
+			RuntimeOrigin::signed(BOB),
+			ALICE
+		));
+		assert!(AccountBorrows::<Test>::get(KSM, ALICE).principal.is_zero());
+		assert!(AccountDeposits::<Test>::get(DOT_U, ALICE)
+			.voucher_balance
+			.is_zero());
+		// The 200 DOT_U of collateral are kept in the reserves
+		assert!(TotalReserves::<Test>::get(DOT_U) >= unit(200));
+		assert!(AccountDeposits::<Test>::get(
+			DOT_U,
+			LendMarket::incentive_reward_account_id().unwrap()
+		)
+		.voucher_balance
+		.is_zero());
+		// The 100 KSM of bad debt exceed the reserves
+		assert_eq!(TotalReserves::<Test>::get(KSM), 0);
+		assert!(!LiquidationAuctions::<Test>::contains_key(ALICE));
+	})
+}
+
+#[test]
+fn absorb_bad_debt_covers_the_loss_with_the_collateral_first() {
+	new_test_ext().execute_with(|| {
+		initial_setup();
+		// Alice also supplies 20 KSM as collateral
+		assert_ok!(LendMarket::mint(
+			RuntimeOrigin::signed(ALICE),
+			KSM,
+			unit(20)
+		));
+		assert_ok!(LendMarket::collateral_asset(
+			RuntimeOrigin::signed(ALICE),
+			KSM,
+			true
+		));
+		assert_ok!(LendMarket::add_reserves(
+			RuntimeOrigin::root(),
+			BOB,
+			KSM,
+			unit(90)
+		));
+		MockOraclePriceProvider::set_price(KSM, 2.into());
+		assert_ok!(LendMarket::start_liquidation_auction(
+			RuntimeOrigin::signed(BOB),
+			ALICE
+		));
+
+		// Here the balance sheet of Alice is:
+		// Collateral   Borrows
+		// DOT_U $200   KSM $300
+		// KSM $60
+		TimestampPallet::set_timestamp(106_000);
+		MockOraclePriceProvider::set_price(KSM, 3.into());
+		let exchange_rate = LendMarket::exchange_rate_stored(KSM).unwrap();
+		assert_ok!(LendMarket::absorb_bad_debt(
+			RuntimeOrigin::signed(BOB),
+			ALICE
+		));
+
+		// The 20 KSM of collateral and the 90 KSM of reserves cover the 100 KSM of bad debt,
+		// so the other suppliers take no loss.
+		assert!(AccountDeposits::<Test>::get(KSM, ALICE)
+			.voucher_balance
+			.is_zero());
+		let reserves = TotalReserves::<Test>::get(KSM);
+		assert!(reserves > unit(9) && reserves < unit(11));
+		assert!(LendMarket::exchange_rate_stored(KSM).unwrap() >= exchange_rate);
+	})
+}
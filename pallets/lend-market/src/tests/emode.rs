@@ -0,0 +1,383 @@
+use crate::{
+	mock::*, tests::LendMarket, AccountEModeCategory, AccountIsolationDebt, EModeCategory, Error,
+	IsolationTotalDebt,
+};
+use frame_support::{assert_noop, assert_ok};
+use sp_runtime::FixedU128;
+
+const CATEGORY: EModeCategory = EModeCategory {
+	collateral_factor: Ratio::from_percent(90),
+	liquidation_threshold: Ratio::from_percent(93),
+};
+
+fn init_emode() {
+	assert_ok!(LendMarket::add_market_bond(
+		RuntimeOrigin::root(),
+		DOT,
+		vec![KSM]
+	));
+	assert_ok!(LendMarket::add_market_bond(
+		RuntimeOrigin::root(),
+		DOT_U,
+		vec![KSM]
+	));
+	assert_ok!(LendMarket::set_emode_category(
+		RuntimeOrigin::root(),
+		1,
+		Some(CATEGORY)
+	));
+	assert_ok!(LendMarket::set_asset_emode_category(
+		RuntimeOrigin::root(),
+		KSM,
+		Some(1)
+	));
+	assert_ok!(LendMarket::set_asset_emode_category(
+		RuntimeOrigin::root(),
+		DOT,
+		Some(1)
+	));
+	// Bob supplies the cash to borrow
+	assert_ok!(LendMarket::mint(RuntimeOrigin::signed(BOB), DOT, unit(500)));
+	assert_ok!(LendMarket::mint(
+		RuntimeOrigin::signed(DAVE),
+		DOT_U,
+		unit(500)
+	));
+	// Alice deposits 200 KSM as collateral
+	assert_ok!(LendMarket::mint(
+		RuntimeOrigin::signed(ALICE),
+		KSM,
+		unit(200)
+	));
+	assert_ok!(LendMarket::collateral_asset(
+		RuntimeOrigin::signed(ALICE),
+		KSM,
+		true
+	));
+}
+
+#[test]
+fn set_emode_category_works() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			LendMarket::set_emode_category(RuntimeOrigin::signed(ALICE), 1, Some(CATEGORY)),
+			sp_runtime::DispatchError::BadOrigin
+		);
+		assert_noop!(
+			LendMarket::set_emode_category(
+				RuntimeOrigin::root(),
+				1,
+				Some(EModeCategory {
+					collateral_factor: Ratio::from_percent(95),
+					liquidation_threshold: Ratio::from_percent(93),
+				})
+			),
+			Error::<Test>::InvalidFactor
+		);
+		assert_noop!(
+			LendMarket::set_asset_emode_category(RuntimeOrigin::root(), KSM, Some(1)),
+			Error::<Test>::EModeCategoryDoesNotExist
+		);
+		assert_noop!(
+			LendMarket::set_account_emode_category(RuntimeOrigin::signed(ALICE), Some(1)),
+			Error::<Test>::EModeCategoryDoesNotExist
+		);
+		assert_ok!(LendMarket::set_emode_category(
+			RuntimeOrigin::root(),
+			1,
+			Some(CATEGORY)
+		));
+		assert_ok!(LendMarket::set_asset_emode_category(
+			RuntimeOrigin::root(),
+			KSM,
+			Some(1)
+		));
+		assert_ok!(LendMarket::set_account_emode_category(
+			RuntimeOrigin::signed(ALICE),
+			Some(1)
+		));
+		assert_eq!(AccountEModeCategory::<Test>::get(ALICE), Some(1));
+	})
+}
+
+#[test]
+fn emode_increases_account_liquidity() {
+	new_test_ext().execute_with(|| {
+		init_emode();
+		let (liquidity, _, _, _) = LendMarket::get_account_liquidity(&ALICE).unwrap();
+		assert_eq!(liquidity, FixedU128::from_inner(unit(100)));
+		assert_noop!(
+			LendMarket::borrow(RuntimeOrigin::signed(ALICE), DOT, unit(150)),
+			Error::<Test>::InsufficientLiquidity
+		);
+
+		assert_ok!(LendMarket::set_account_emode_category(
+			RuntimeOrigin::signed(ALICE),
+			Some(1)
+		));
+		let (liquidity, _, _, _) = LendMarket::get_account_liquidity(&ALICE).unwrap();
+		assert_eq!(liquidity, FixedU128::from_inner(unit(180)));
+		let (threshold_liquidity, _, _, _) =
+			LendMarket::get_account_liquidation_threshold_liquidity(&ALICE).unwrap();
+		assert_eq!(threshold_liquidity, FixedU128::from_inner(unit(186)));
+
+		assert_ok!(LendMarket::borrow(
+			RuntimeOrigin::signed(ALICE),
+			DOT,
+			unit(150)
+		));
+		// Assets outside of the category cannot be borrowed in efficiency mode
+		assert_noop!(
+			LendMarket::borrow(RuntimeOrigin::signed(ALICE), DOT_U, unit(1)),
+			Error::<Test>::EModeCategoryMismatch
+		);
+		// Leaving efficiency mode would cause a shortfall
+		assert_noop!(
+			LendMarket::set_account_emode_category(RuntimeOrigin::signed(ALICE), None),
+			Error::<Test>::InsufficientLiquidity
+		);
+	})
+}
+
+#[test]
+fn entering_emode_requires_borrows_in_category() {
+	new_test_ext().execute_with(|| {
+		init_emode();
+		assert_ok!(LendMarket::borrow(
+			RuntimeOrigin::signed(ALICE),
+			DOT_U,
+			unit(10)
+		));
+		assert_noop!(
+			LendMarket::set_account_emode_category(RuntimeOrigin::signed(ALICE), Some(1)),
+			Error::<Test>::EModeCategoryMismatch
+		);
+	})
+}
+
+#[test]
+fn isolated_collateral_cannot_be_combined() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(LendMarket::set_isolation_debt_ceiling(
+			RuntimeOrigin::root(),
+			PHA,
+			Some(FixedU128::from_inner(unit(50)))
+		));
+		assert_ok!(LendMarket::mint(
+			RuntimeOrigin::signed(ALICE),
+			PHA,
+			unit(200)
+		));
+		assert_ok!(LendMarket::mint(
+			RuntimeOrigin::signed(ALICE),
+			KSM,
+			unit(200)
+		));
+		assert_ok!(LendMarket::collateral_asset(
+			RuntimeOrigin::signed(ALICE),
+			PHA,
+			true
+		));
+		assert_noop!(
+			LendMarket::collateral_asset(RuntimeOrigin::signed(ALICE), KSM, true),
+			Error::<Test>::IsolatedCollateralConflict
+		);
+	})
+}
+
+#[test]
+fn isolation_debt_ceiling_cannot_be_set_on_used_collateral() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(LendMarket::mint(RuntimeOrigin::signed(BOB), DOT, unit(500)));
+		for asset_id in [PHA, KSM] {
+			assert_ok!(LendMarket::mint(
+				RuntimeOrigin::signed(ALICE),
+				asset_id,
+				unit(200)
+			));
+			assert_ok!(LendMarket::collateral_asset(
+				RuntimeOrigin::signed(ALICE),
+				asset_id,
+				true
+			));
+		}
+		assert_ok!(LendMarket::borrow(
+			RuntimeOrigin::signed(ALICE),
+			DOT,
+			unit(150)
+		));
+
+		// Isolating PHA would drop the KSM collateral of the existing borrower.
+		assert_noop!(
+			LendMarket::set_isolation_debt_ceiling(
+				RuntimeOrigin::root(),
+				PHA,
+				Some(FixedU128::from_inner(unit(50)))
+			),
+			Error::<Test>::MarketUsedAsCollateral
+		);
+		let (liquidity, shortfall, _, _) = LendMarket::get_account_liquidity(&ALICE).unwrap();
+		assert_eq!(liquidity, FixedU128::from_inner(unit(50)));
+		assert_eq!(shortfall, FixedU128::from_inner(0));
+
+		// Once the market is no longer used as collateral, it can be isolated.
+		assert_ok!(LendMarket::repay_borrow_all(
+			RuntimeOrigin::signed(ALICE),
+			DOT
+		));
+		assert_ok!(LendMarket::collateral_asset(
+			RuntimeOrigin::signed(ALICE),
+			PHA,
+			false
+		));
+		assert_ok!(LendMarket::set_isolation_debt_ceiling(
+			RuntimeOrigin::root(),
+			PHA,
+			Some(FixedU128::from_inner(unit(50)))
+		));
+		assert_eq!(LendMarket::isolated_collateral(&ALICE), None);
+	})
+}
+
+#[test]
+fn isolation_debt_ceiling_works() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(LendMarket::add_market_bond(
+			RuntimeOrigin::root(),
+			DOT,
+			vec![PHA]
+		));
+		assert_ok!(LendMarket::set_isolation_debt_ceiling(
+			RuntimeOrigin::root(),
+			PHA,
+			Some(FixedU128::from_inner(unit(50)))
+		));
+		assert_ok!(LendMarket::mint(RuntimeOrigin::signed(BOB), DOT, unit(500)));
+		assert_ok!(LendMarket::mint(
+			RuntimeOrigin::signed(ALICE),
+			PHA,
+			unit(200)
+		));
+		assert_ok!(LendMarket::collateral_asset(
+			RuntimeOrigin::signed(ALICE),
+			PHA,
+			true
+		));
+
+		assert_ok!(LendMarket::borrow(
+			RuntimeOrigin::signed(ALICE),
+			DOT,
+			unit(50)
+		));
+		assert_eq!(IsolationTotalDebt::<Test>::get(PHA, DOT), unit(50));
+		assert_noop!(
+			LendMarket::borrow(RuntimeOrigin::signed(ALICE), DOT, unit(1)),
+			Error::<Test>::IsolationDebtCeilingExceeded
+		);
+
+		assert_ok!(LendMarket::repay_borrow(
+			RuntimeOrigin::signed(ALICE),
+			DOT,
+			unit(20)
+		));
+		assert_eq!(IsolationTotalDebt::<Test>::get(PHA, DOT), unit(30));
+		assert_eq!(
+			AccountIsolationDebt::<Test>::get(ALICE, DOT),
+			Some((PHA, unit(30)))
+		);
+		assert_ok!(LendMarket::borrow(
+			RuntimeOrigin::signed(ALICE),
+			DOT,
+			unit(20)
+		));
+	})
+}
+
+#[test]
+fn isolation_debt_is_valued_at_current_price() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(LendMarket::set_isolation_debt_ceiling(
+			RuntimeOrigin::root(),
+			PHA,
+			Some(FixedU128::from_inner(unit(50)))
+		));
+		assert_ok!(LendMarket::mint(RuntimeOrigin::signed(BOB), DOT, unit(500)));
+		assert_ok!(LendMarket::mint(
+			RuntimeOrigin::signed(ALICE),
+			PHA,
+			unit(200)
+		));
+		assert_ok!(LendMarket::collateral_asset(
+			RuntimeOrigin::signed(ALICE),
+			PHA,
+			true
+		));
+		assert_ok!(LendMarket::borrow(
+			RuntimeOrigin::signed(ALICE),
+			DOT,
+			unit(20)
+		));
+
+		// 20 DOT are worth 40 once the price doubles, leaving room for 5 DOT only.
+		MockOraclePriceProvider::set_price(DOT, 2.into());
+		assert_noop!(
+			LendMarket::borrow(RuntimeOrigin::signed(ALICE), DOT, unit(6)),
+			Error::<Test>::IsolationDebtCeilingExceeded
+		);
+		assert_ok!(LendMarket::borrow(
+			RuntimeOrigin::signed(ALICE),
+			DOT,
+			unit(5)
+		));
+		assert_eq!(IsolationTotalDebt::<Test>::get(PHA, DOT), unit(25));
+	})
+}
+
+#[test]
+fn isolation_debt_is_released_from_recorded_collateral() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(LendMarket::set_isolation_debt_ceiling(
+			RuntimeOrigin::root(),
+			PHA,
+			Some(FixedU128::from_inner(unit(50)))
+		));
+		assert_ok!(LendMarket::mint(RuntimeOrigin::signed(BOB), DOT, unit(500)));
+		assert_ok!(LendMarket::mint(
+			RuntimeOrigin::signed(ALICE),
+			PHA,
+			unit(200)
+		));
+		assert_ok!(LendMarket::collateral_asset(
+			RuntimeOrigin::signed(ALICE),
+			PHA,
+			true
+		));
+		assert_ok!(LendMarket::borrow(
+			RuntimeOrigin::signed(ALICE),
+			DOT,
+			unit(20)
+		));
+
+		// The isolated collateral is no longer in use, e.g. after it was seized.
+		assert_ok!(LendMarket::set_isolation_debt_ceiling(
+			RuntimeOrigin::root(),
+			PHA,
+			None
+		));
+		assert_eq!(LendMarket::isolated_collateral(&ALICE), None);
+		assert_ok!(LendMarket::repay_borrow(
+			RuntimeOrigin::signed(ALICE),
+			DOT,
+			unit(5)
+		));
+		assert_eq!(IsolationTotalDebt::<Test>::get(PHA, DOT), unit(15));
+
+		// Repaying in full releases the whole record.
+		assert_ok!(LendMarket::repay_borrow_all(
+			RuntimeOrigin::signed(ALICE),
+			DOT
+		));
+		assert_eq!(AccountIsolationDebt::<Test>::get(ALICE, DOT), None);
+		assert!(!IsolationTotalDebt::<Test>::contains_key(PHA, DOT));
+	})
+}
@@ -1,4 +1,7 @@
-use crate::{mock::*, tests::LendMarket, Markets};
+use crate::{
+	mock::*, tests::LendMarket, AdaptiveModel, AdaptiveRateAtTarget, InterestRateModel, Markets,
+	VTokenApyModel, VTokenStakingApy,
+};
 use bifrost_primitives::{Rate, Ratio, SECONDS_PER_YEAR};
 use frame_support::assert_ok;
 use sp_runtime::{
@@ -681,3 +684,122 @@ fn a_market_can_only_accrue_interest_once_in_a_block() {
 		);
 	})
 }
+
+#[test]
+fn adaptive_rate_model_moves_rate_at_target() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(LendMarket::add_market_bond(
+			RuntimeOrigin::root(),
+			DOT,
+			vec![DOT]
+		));
+		let adaptive = AdaptiveModel {
+			target_utilization: Ratio::from_percent(40),
+			initial_rate_at_target: Rate::saturating_from_rational(4, 100),
+			min_rate_at_target: Rate::saturating_from_rational(1, 100),
+			max_rate_at_target: Rate::saturating_from_rational(20, 100),
+			curve_steepness: Rate::saturating_from_integer(4),
+			adjustment_speed: Rate::saturating_from_integer(50),
+		};
+		assert_ok!(LendMarket::update_rate_model(
+			RuntimeOrigin::root(),
+			DOT,
+			InterestRateModel::Adaptive(adaptive),
+		));
+		// Deposit 200 DOT and borrow 100 DOT, the utilization is above the target
+		assert_ok!(LendMarket::mint(
+			RuntimeOrigin::signed(ALICE),
+			DOT,
+			unit(200)
+		));
+		assert_ok!(LendMarket::collateral_asset(
+			RuntimeOrigin::signed(ALICE),
+			DOT,
+			true
+		));
+		assert_ok!(LendMarket::borrow(
+			RuntimeOrigin::signed(ALICE),
+			DOT,
+			unit(100)
+		));
+		assert_eq!(AdaptiveRateAtTarget::<Test>::get(DOT), None);
+
+		TimestampPallet::set_timestamp(6000 + 86400 * 1000);
+		assert_ok!(LendMarket::accrue_interest(DOT));
+		let rate_at_target = AdaptiveRateAtTarget::<Test>::get(DOT).unwrap();
+		assert!(rate_at_target > adaptive.initial_rate_at_target);
+		assert_eq!(
+			rate_at_target,
+			adaptive.next_rate_at_target(
+				adaptive.initial_rate_at_target,
+				Ratio::from_percent(50),
+				86400
+			)
+		);
+
+		// The borrow rate follows the new rate at target
+		TimestampPallet::set_timestamp(6000 + 86401 * 1000);
+		assert_ok!(LendMarket::accrue_interest(DOT));
+		assert!(BorrowRate::<Test>::get(DOT) > rate_at_target);
+
+		// Setting the rate model again resets the rate at target
+		assert_ok!(LendMarket::update_rate_model(
+			RuntimeOrigin::root(),
+			DOT,
+			InterestRateModel::Adaptive(adaptive),
+		));
+		assert_eq!(AdaptiveRateAtTarget::<Test>::get(DOT), None);
+	})
+}
+
+#[test]
+fn vtoken_apy_rate_model_tracks_staking_apy() {
+	new_test_ext().execute_with(|| {
+		let vtoken_apy = VTokenApyModel {
+			vtoken_id: VDOT,
+			max_apy: Rate::saturating_from_rational(20, 100),
+			spread: Rate::saturating_from_rational(2, 100),
+			full_rate: Rate::saturating_from_rational(50, 100),
+			jump_utilization: Ratio::from_percent(80),
+		};
+		assert_ok!(LendMarket::update_rate_model(
+			RuntimeOrigin::root(),
+			DOT,
+			InterestRateModel::VTokenApy(vtoken_apy),
+		));
+		MockVTokenSupplyProvider::set_supplies(unit(100), unit(100));
+		assert_ok!(LendMarket::mint(
+			RuntimeOrigin::signed(ALICE),
+			DOT,
+			unit(200)
+		));
+
+		// The first accrual samples the exchange rate
+		TimestampPallet::set_timestamp(12000);
+		assert_ok!(LendMarket::accrue_interest(DOT));
+		assert_eq!(VTokenStakingApy::<Test>::get(VDOT), Rate::zero());
+
+		// Samples are taken once a day at most
+		MockVTokenSupplyProvider::set_supplies(unit(101), unit(100));
+		TimestampPallet::set_timestamp(18000);
+		assert_ok!(LendMarket::accrue_interest(DOT));
+		assert_eq!(VTokenStakingApy::<Test>::get(VDOT), Rate::zero());
+
+		// The exchange rate grows by 10% in a year
+		MockVTokenSupplyProvider::set_supplies(unit(110), unit(100));
+		TimestampPallet::set_timestamp(12000 + SECONDS_PER_YEAR * 1000);
+		assert_ok!(LendMarket::accrue_interest(DOT));
+		assert_eq!(
+			VTokenStakingApy::<Test>::get(VDOT),
+			Rate::saturating_from_rational(10, 100)
+		);
+
+		// Without borrows, the borrow rate is the staking APY
+		TimestampPallet::set_timestamp(18000 + SECONDS_PER_YEAR * 1000);
+		assert_ok!(LendMarket::accrue_interest(DOT));
+		assert_eq!(
+			BorrowRate::<Test>::get(DOT),
+			Rate::saturating_from_rational(10, 100)
+		);
+	})
+}
@@ -1,5 +1,5 @@
 use crate::InterestRateModel;
-use bifrost_primitives::{CurrencyId, Rate, Ratio};
+use bifrost_primitives::{CurrencyId, Rate, Ratio, Timestamp};
 use frame_support::pallet_prelude::*;
 use scale_info::TypeInfo;
 
@@ -89,6 +89,34 @@ pub struct Market<Balance> {
 	pub lend_token_id: CurrencyId,
 }
 
+/// Efficiency mode category.
+///
+/// Groups correlated assets, like vDOT and DOT, so that accounts opting into the category
+/// can use them as collateral with a higher loan-to-value.
+#[cfg_attr(feature = "std", derive(serde::Deserialize, serde::Serialize))]
+#[derive(Clone, Copy, PartialEq, Eq, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub struct EModeCategory {
+	/// The collateral utilization ratio inside the category
+	pub collateral_factor: Ratio,
+	/// The liquidation threshold ratio inside the category
+	pub liquidation_threshold: Ratio,
+}
+
+/// Liquidation auction parameters.
+///
+/// The discount on the collateral price grows linearly from `initial_discount` to
+/// `max_discount` over `duration` seconds after the auction starts.
+#[cfg_attr(feature = "std", derive(serde::Deserialize, serde::Serialize))]
+#[derive(Clone, Copy, PartialEq, Eq, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub struct AuctionConfig {
+	/// The discount when the auction starts
+	pub initial_discount: Ratio,
+	/// The discount once `duration` has elapsed
+	pub max_discount: Ratio,
+	/// Seconds taken to reach `max_discount`
+	pub duration: Timestamp,
+}
+
 #[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo, Default)]
 pub struct RewardMarketState<BlockNumber, Balance> {
 	pub index: Balance,
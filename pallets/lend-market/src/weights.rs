@@ -70,6 +70,14 @@ pub trait WeightInfo {
 	fn withdraw_missing_reward() -> Weight;
 	fn collateral_asset() -> Weight;
 	fn update_liquidation_free_collateral() -> Weight;
+	fn set_emode_category() -> Weight;
+	fn set_asset_emode_category() -> Weight;
+	fn set_isolation_debt_ceiling() -> Weight;
+	fn set_account_emode_category() -> Weight;
+	fn set_liquidation_auction_config() -> Weight;
+	fn start_liquidation_auction() -> Weight;
+	fn bid_liquidation_auction() -> Weight;
+	fn absorb_bad_debt() -> Weight;
 }
 
 /// Weights for loans using the Substrate node and recommended hardware.
@@ -691,6 +699,148 @@ impl<T: frame_system::Config> WeightInfo for BifrostWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(8_u64))
 			.saturating_add(T::DbWeight::get().writes(4_u64))
 	}
+	/// Storage: LendMarket EModeCategories (r:0 w:1)
+	/// Proof Skipped: LendMarket EModeCategories (max_values: None, max_size: None, mode: Measured)
+	fn set_emode_category() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 12_000_000 picoseconds.
+		Weight::from_parts(12_500_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: LendMarket Markets (r:1 w:0)
+	/// Proof Skipped: LendMarket Markets (max_values: None, max_size: None, mode: Measured)
+	/// Storage: LendMarket EModeCategories (r:1 w:0)
+	/// Proof Skipped: LendMarket EModeCategories (max_values: None, max_size: None, mode: Measured)
+	/// Storage: LendMarket AssetEModeCategory (r:0 w:1)
+	/// Proof Skipped: LendMarket AssetEModeCategory (max_values: None, max_size: None, mode: Measured)
+	fn set_asset_emode_category() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1207`
+		//  Estimated: `4672`
+		// Minimum execution time: 21_000_000 picoseconds.
+		Weight::from_parts(21_800_000, 4672)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: LendMarket Markets (r:1 w:0)
+	/// Proof Skipped: LendMarket Markets (max_values: None, max_size: None, mode: Measured)
+	/// Storage: LendMarket IsolationDebtCeilings (r:0 w:1)
+	/// Proof Skipped: LendMarket IsolationDebtCeilings (max_values: None, max_size: None, mode: Measured)
+	fn set_isolation_debt_ceiling() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1207`
+		//  Estimated: `4672`
+		// Minimum execution time: 18_700_000 picoseconds.
+		Weight::from_parts(19_300_000, 4672)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: LendMarket EModeCategories (r:1 w:0)
+	/// Proof Skipped: LendMarket EModeCategories (max_values: None, max_size: None, mode: Measured)
+	/// Storage: LendMarket Markets (r:6 w:0)
+	/// Proof Skipped: LendMarket Markets (max_values: None, max_size: None, mode: Measured)
+	/// Storage: LendMarket AccountBorrows (r:5 w:0)
+	/// Proof Skipped: LendMarket AccountBorrows (max_values: None, max_size: None, mode: Measured)
+	/// Storage: LendMarket AccountDeposits (r:5 w:0)
+	/// Proof Skipped: LendMarket AccountDeposits (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Prices EmergencyPrice (r:2 w:0)
+	/// Proof Skipped: Prices EmergencyPrice (max_values: None, max_size: None, mode: Measured)
+	/// Storage: LendMarket AccountEModeCategory (r:0 w:1)
+	/// Proof Skipped: LendMarket AccountEModeCategory (max_values: None, max_size: None, mode: Measured)
+	fn set_account_emode_category() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `2857`
+		//  Estimated: `26757`
+		// Minimum execution time: 98_000_000 picoseconds.
+		Weight::from_parts(99_200_000, 26757)
+			.saturating_add(T::DbWeight::get().reads(19_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: LendMarket LiquidationAuctionConfig (r:0 w:1)
+	/// Proof Skipped: LendMarket LiquidationAuctionConfig (max_values: Some(1), max_size: None, mode: Measured)
+	fn set_liquidation_auction_config() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 11_000_000 picoseconds.
+		Weight::from_parts(11_600_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: LendMarket LiquidationAuctionConfig (r:1 w:0)
+	/// Proof Skipped: LendMarket LiquidationAuctionConfig (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: LendMarket LiquidationAuctions (r:1 w:1)
+	/// Proof Skipped: LendMarket LiquidationAuctions (max_values: None, max_size: None, mode: Measured)
+	/// Storage: LendMarket Markets (r:6 w:0)
+	/// Proof Skipped: LendMarket Markets (max_values: None, max_size: None, mode: Measured)
+	/// Storage: LendMarket AccountBorrows (r:5 w:0)
+	/// Proof Skipped: LendMarket AccountBorrows (max_values: None, max_size: None, mode: Measured)
+	/// Storage: LendMarket AccountDeposits (r:5 w:0)
+	/// Proof Skipped: LendMarket AccountDeposits (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Prices EmergencyPrice (r:2 w:0)
+	/// Proof Skipped: Prices EmergencyPrice (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Timestamp Now (r:1 w:0)
+	/// Proof: Timestamp Now (max_values: Some(1), max_size: Some(8), added: 503, mode: MaxEncodedLen)
+	fn start_liquidation_auction() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `2857`
+		//  Estimated: `26757`
+		// Minimum execution time: 101_000_000 picoseconds.
+		Weight::from_parts(102_400_000, 26757)
+			.saturating_add(T::DbWeight::get().reads(21_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: LendMarket LiquidationAuctionConfig (r:1 w:0)
+	/// Proof Skipped: LendMarket LiquidationAuctionConfig (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: LendMarket LiquidationAuctions (r:1 w:1)
+	/// Proof Skipped: LendMarket LiquidationAuctions (max_values: None, max_size: None, mode: Measured)
+	/// Storage: LendMarket Markets (r:5 w:0)
+	/// Proof Skipped: LendMarket Markets (max_values: None, max_size: None, mode: Measured)
+	/// Storage: LendMarket AccountBorrows (r:3 w:1)
+	/// Proof Skipped: LendMarket AccountBorrows (max_values: None, max_size: None, mode: Measured)
+	/// Storage: LendMarket AccountDeposits (r:5 w:3)
+	/// Proof Skipped: LendMarket AccountDeposits (max_values: None, max_size: None, mode: Measured)
+	/// Storage: LendMarket TotalBorrows (r:2 w:1)
+	/// Proof Skipped: LendMarket TotalBorrows (max_values: None, max_size: None, mode: Measured)
+	/// Storage: LendMarket BorrowIndex (r:1 w:0)
+	/// Proof Skipped: LendMarket BorrowIndex (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Tokens Accounts (r:4 w:4)
+	/// Proof: Tokens Accounts (max_values: None, max_size: Some(118), added: 2593, mode: MaxEncodedLen)
+	/// Storage: System Account (r:1 w:1)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	fn bid_liquidation_auction() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `5226`
+		//  Estimated: `201519`
+		// Minimum execution time: 781_000_000 picoseconds.
+		Weight::from_parts(786_300_000, 201519)
+			.saturating_add(T::DbWeight::get().reads(46_u64))
+			.saturating_add(T::DbWeight::get().writes(22_u64))
+	}
+	/// Storage: LendMarket LiquidationAuctionConfig (r:1 w:0)
+	/// Proof Skipped: LendMarket LiquidationAuctionConfig (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: LendMarket LiquidationAuctions (r:1 w:1)
+	/// Proof Skipped: LendMarket LiquidationAuctions (max_values: None, max_size: None, mode: Measured)
+	/// Storage: LendMarket Markets (r:6 w:0)
+	/// Proof Skipped: LendMarket Markets (max_values: None, max_size: None, mode: Measured)
+	/// Storage: LendMarket AccountBorrows (r:5 w:5)
+	/// Proof Skipped: LendMarket AccountBorrows (max_values: None, max_size: None, mode: Measured)
+	/// Storage: LendMarket AccountDeposits (r:5 w:5)
+	/// Proof Skipped: LendMarket AccountDeposits (max_values: None, max_size: None, mode: Measured)
+	/// Storage: LendMarket TotalBorrows (r:5 w:5)
+	/// Proof Skipped: LendMarket TotalBorrows (max_values: None, max_size: None, mode: Measured)
+	/// Storage: LendMarket TotalReserves (r:5 w:5)
+	/// Proof Skipped: LendMarket TotalReserves (max_values: None, max_size: None, mode: Measured)
+	fn absorb_bad_debt() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `4520`
+		//  Estimated: `78430`
+		// Minimum execution time: 298_000_000 picoseconds.
+		Weight::from_parts(301_700_000, 78430)
+			.saturating_add(T::DbWeight::get().reads(33_u64))
+			.saturating_add(T::DbWeight::get().writes(26_u64))
+	}
 }
 
 // For backwards compatibility and tests
@@ -1311,4 +1461,146 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(8_u64))
 			.saturating_add(RocksDbWeight::get().writes(4_u64))
 	}
+	/// Storage: LendMarket EModeCategories (r:0 w:1)
+	/// Proof Skipped: LendMarket EModeCategories (max_values: None, max_size: None, mode: Measured)
+	fn set_emode_category() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 12_000_000 picoseconds.
+		Weight::from_parts(12_500_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: LendMarket Markets (r:1 w:0)
+	/// Proof Skipped: LendMarket Markets (max_values: None, max_size: None, mode: Measured)
+	/// Storage: LendMarket EModeCategories (r:1 w:0)
+	/// Proof Skipped: LendMarket EModeCategories (max_values: None, max_size: None, mode: Measured)
+	/// Storage: LendMarket AssetEModeCategory (r:0 w:1)
+	/// Proof Skipped: LendMarket AssetEModeCategory (max_values: None, max_size: None, mode: Measured)
+	fn set_asset_emode_category() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1207`
+		//  Estimated: `4672`
+		// Minimum execution time: 21_000_000 picoseconds.
+		Weight::from_parts(21_800_000, 4672)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: LendMarket Markets (r:1 w:0)
+	/// Proof Skipped: LendMarket Markets (max_values: None, max_size: None, mode: Measured)
+	/// Storage: LendMarket IsolationDebtCeilings (r:0 w:1)
+	/// Proof Skipped: LendMarket IsolationDebtCeilings (max_values: None, max_size: None, mode: Measured)
+	fn set_isolation_debt_ceiling() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1207`
+		//  Estimated: `4672`
+		// Minimum execution time: 18_700_000 picoseconds.
+		Weight::from_parts(19_300_000, 4672)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: LendMarket EModeCategories (r:1 w:0)
+	/// Proof Skipped: LendMarket EModeCategories (max_values: None, max_size: None, mode: Measured)
+	/// Storage: LendMarket Markets (r:6 w:0)
+	/// Proof Skipped: LendMarket Markets (max_values: None, max_size: None, mode: Measured)
+	/// Storage: LendMarket AccountBorrows (r:5 w:0)
+	/// Proof Skipped: LendMarket AccountBorrows (max_values: None, max_size: None, mode: Measured)
+	/// Storage: LendMarket AccountDeposits (r:5 w:0)
+	/// Proof Skipped: LendMarket AccountDeposits (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Prices EmergencyPrice (r:2 w:0)
+	/// Proof Skipped: Prices EmergencyPrice (max_values: None, max_size: None, mode: Measured)
+	/// Storage: LendMarket AccountEModeCategory (r:0 w:1)
+	/// Proof Skipped: LendMarket AccountEModeCategory (max_values: None, max_size: None, mode: Measured)
+	fn set_account_emode_category() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `2857`
+		//  Estimated: `26757`
+		// Minimum execution time: 98_000_000 picoseconds.
+		Weight::from_parts(99_200_000, 26757)
+			.saturating_add(RocksDbWeight::get().reads(19_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: LendMarket LiquidationAuctionConfig (r:0 w:1)
+	/// Proof Skipped: LendMarket LiquidationAuctionConfig (max_values: Some(1), max_size: None, mode: Measured)
+	fn set_liquidation_auction_config() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 11_000_000 picoseconds.
+		Weight::from_parts(11_600_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: LendMarket LiquidationAuctionConfig (r:1 w:0)
+	/// Proof Skipped: LendMarket LiquidationAuctionConfig (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: LendMarket LiquidationAuctions (r:1 w:1)
+	/// Proof Skipped: LendMarket LiquidationAuctions (max_values: None, max_size: None, mode: Measured)
+	/// Storage: LendMarket Markets (r:6 w:0)
+	/// Proof Skipped: LendMarket Markets (max_values: None, max_size: None, mode: Measured)
+	/// Storage: LendMarket AccountBorrows (r:5 w:0)
+	/// Proof Skipped: LendMarket AccountBorrows (max_values: None, max_size: None, mode: Measured)
+	/// Storage: LendMarket AccountDeposits (r:5 w:0)
+	/// Proof Skipped: LendMarket AccountDeposits (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Prices EmergencyPrice (r:2 w:0)
+	/// Proof Skipped: Prices EmergencyPrice (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Timestamp Now (r:1 w:0)
+	/// Proof: Timestamp Now (max_values: Some(1), max_size: Some(8), added: 503, mode: MaxEncodedLen)
+	fn start_liquidation_auction() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `2857`
+		//  Estimated: `26757`
+		// Minimum execution time: 101_000_000 picoseconds.
+		Weight::from_parts(102_400_000, 26757)
+			.saturating_add(RocksDbWeight::get().reads(21_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: LendMarket LiquidationAuctionConfig (r:1 w:0)
+	/// Proof Skipped: LendMarket LiquidationAuctionConfig (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: LendMarket LiquidationAuctions (r:1 w:1)
+	/// Proof Skipped: LendMarket LiquidationAuctions (max_values: None, max_size: None, mode: Measured)
+	/// Storage: LendMarket Markets (r:5 w:0)
+	/// Proof Skipped: LendMarket Markets (max_values: None, max_size: None, mode: Measured)
+	/// Storage: LendMarket AccountBorrows (r:3 w:1)
+	/// Proof Skipped: LendMarket AccountBorrows (max_values: None, max_size: None, mode: Measured)
+	/// Storage: LendMarket AccountDeposits (r:5 w:3)
+	/// Proof Skipped: LendMarket AccountDeposits (max_values: None, max_size: None, mode: Measured)
+	/// Storage: LendMarket TotalBorrows (r:2 w:1)
+	/// Proof Skipped: LendMarket TotalBorrows (max_values: None, max_size: None, mode: Measured)
+	/// Storage: LendMarket BorrowIndex (r:1 w:0)
+	/// Proof Skipped: LendMarket BorrowIndex (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Tokens Accounts (r:4 w:4)
+	/// Proof: Tokens Accounts (max_values: None, max_size: Some(118), added: 2593, mode: MaxEncodedLen)
+	/// Storage: System Account (r:1 w:1)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	fn bid_liquidation_auction() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `5226`
+		//  Estimated: `201519`
+		// Minimum execution time: 781_000_000 picoseconds.
+		Weight::from_parts(786_300_000, 201519)
+			.saturating_add(RocksDbWeight::get().reads(46_u64))
+			.saturating_add(RocksDbWeight::get().writes(22_u64))
+	}
+	/// Storage: LendMarket LiquidationAuctionConfig (r:1 w:0)
+	/// Proof Skipped: LendMarket LiquidationAuctionConfig (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: LendMarket LiquidationAuctions (r:1 w:1)
+	/// Proof Skipped: LendMarket LiquidationAuctions (max_values: None, max_size: None, mode: Measured)
+	/// Storage: LendMarket Markets (r:6 w:0)
+	/// Proof Skipped: LendMarket Markets (max_values: None, max_size: None, mode: Measured)
+	/// Storage: LendMarket AccountBorrows (r:5 w:5)
+	/// Proof Skipped: LendMarket AccountBorrows (max_values: None, max_size: None, mode: Measured)
+	/// Storage: LendMarket AccountDeposits (r:5 w:5)
+	/// Proof Skipped: LendMarket AccountDeposits (max_values: None, max_size: None, mode: Measured)
+	/// Storage: LendMarket TotalBorrows (r:5 w:5)
+	/// Proof Skipped: LendMarket TotalBorrows (max_values: None, max_size: None, mode: Measured)
+	/// Storage: LendMarket TotalReserves (r:5 w:5)
+	/// Proof Skipped: LendMarket TotalReserves (max_values: None, max_size: None, mode: Measured)
+	fn absorb_bad_debt() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `4520`
+		//  Estimated: `78430`
+		// Minimum execution time: 298_000_000 picoseconds.
+		Weight::from_parts(301_700_000, 78430)
+			.saturating_add(RocksDbWeight::get().reads(33_u64))
+			.saturating_add(RocksDbWeight::get().writes(26_u64))
+	}
 }
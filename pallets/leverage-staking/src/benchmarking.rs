@@ -24,7 +24,7 @@ use lend_market::{self, InterestRateModel, JumpModel, Market, MarketState};
 use orml_traits::MultiCurrency;
 use sp_runtime::{
 	traits::{StaticLookup, UniqueSaturatedFrom},
-	FixedPointNumber,
+	FixedPointNumber, Permill,
 };
 use sp_std::vec;
 
@@ -188,7 +188,28 @@ mod benchmarks {
 		let rate = FixedU128::from_inner(unit(990_000));
 
 		#[extrinsic_call]
-		Pallet::<T>::flash_loan_deposit(SystemOrigin::Signed(caller.clone()), coin0.into(), rate);
+		Pallet::<T>::flash_loan_deposit(
+			SystemOrigin::Signed(caller.clone()),
+			coin0.into(),
+			rate,
+			Permill::from_percent(5),
+		);
+
+		Ok(())
+	}
+
+	#[benchmark]
+	fn set_max_leverage_rate() -> Result<(), BenchmarkError> {
+		let origin = <T as Config>::ControlOrigin::try_successful_origin()
+			.map_err(|_| BenchmarkError::Weightless)?;
+		let rate = FixedU128::from_inner(unit(990_000));
+
+		#[extrinsic_call]
+		_(
+			origin as <T as frame_system::Config>::RuntimeOrigin,
+			KSM.into(),
+			Some(rate),
+		);
 
 		Ok(())
 	}
@@ -44,8 +44,8 @@ pub use pallet_traits::{
 };
 pub use parity_scale_codec::{Decode, Encode};
 use sp_runtime::{
-	traits::{CheckedSub, Zero},
-	ArithmeticError, FixedPointNumber, FixedU128, SaturatedConversion,
+	traits::{Bounded, CheckedAdd, CheckedDiv, CheckedSub, Saturating, Zero},
+	ArithmeticError, FixedPointNumber, FixedU128, Permill, SaturatedConversion,
 };
 use sp_std::{cmp::Ordering, marker::PhantomData};
 pub use weights::WeightInfo;
@@ -91,6 +91,12 @@ pub mod pallet {
 		ArgumentsError,
 		/// Not support token type
 		NotSupportTokenType,
+		/// The actual amount deviates from the exchange rate by more than the allowed slippage
+		SlippageExceeded,
+		/// The target leverage rate is greater than the maximum allowed by governance
+		ExceedMaxLeverageRate,
+		/// The position would fall below the liquidation threshold
+		InsufficientLiquidationThresholdLiquidity,
 	}
 
 	#[pallet::event]
@@ -107,8 +113,21 @@ pub mod pallet {
 			/// The new leverage rate.
 			new_rate: Rate,
 		},
+		/// The maximum leverage rate of an asset has been set.
+		MaxLeverageRateSet {
+			/// The asset id of the token.
+			asset_id: AssetIdOf<T>,
+			/// The maximum leverage rate, `None` means no limit.
+			max_rate: Option<Rate>,
+		},
 	}
 
+	/// The maximum leverage rate allowed for each asset.
+	///
+	/// Assets without an entry are not limited.
+	#[pallet::storage]
+	pub type MaxLeverageRates<T: Config> = StorageMap<_, Twox64Concat, AssetIdOf<T>, Rate>;
+
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
 		/// Deposit flash loan
@@ -117,16 +136,40 @@ pub mod pallet {
 		///
 		/// - `asset_id`: The asset id of the token
 		/// - `rate`: Leverage rate
+		/// - `max_slippage`: Maximum deviation from the vtoken exchange rate accepted when
+		///   minting or swapping
 		#[pallet::call_index(0)]
 		#[pallet::weight(<T as pallet::Config>::WeightInfo::flash_loan_deposit())]
 		pub fn flash_loan_deposit(
 			origin: OriginFor<T>,
 			asset_id: AssetIdOf<T>,
 			rate: Rate,
+			max_slippage: Permill,
 		) -> DispatchResult {
 			let who = ensure_signed(origin)?;
 
-			Pallet::<T>::flash_loan_deposit_inner(who, asset_id, rate)
+			Pallet::<T>::flash_loan_deposit_inner(who, asset_id, rate, max_slippage)
+		}
+
+		/// Set the maximum leverage rate of an asset
+		///
+		/// - `asset_id`: The asset id of the token
+		/// - `max_rate`: Maximum leverage rate, `None` removes the limit
+		#[pallet::call_index(1)]
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::set_max_leverage_rate())]
+		pub fn set_max_leverage_rate(
+			origin: OriginFor<T>,
+			asset_id: AssetIdOf<T>,
+			max_rate: Option<Rate>,
+		) -> DispatchResult {
+			T::ControlOrigin::ensure_origin(origin)?;
+
+			T::CurrencyIdConversion::convert_to_vtoken(asset_id)
+				.map_err(|_| Error::<T>::NotSupportTokenType)?;
+			MaxLeverageRates::<T>::set(asset_id, max_rate);
+
+			Self::deposit_event(Event::<T>::MaxLeverageRateSet { asset_id, max_rate });
+			Ok(())
 		}
 	}
 }
@@ -137,6 +180,7 @@ impl<T: Config> Pallet<T> {
 		who: AccountIdOf<T>,
 		asset_id: AssetIdOf<T>,
 		rate: Rate,
+		max_slippage: Permill,
 	) -> DispatchResult {
 		let vtoken_id = T::CurrencyIdConversion::convert_to_vtoken(asset_id)
 			.map_err(|_| Error::<T>::NotSupportTokenType)?;
@@ -145,21 +189,8 @@ impl<T: Config> Pallet<T> {
 		if !deposits.is_collateral {
 			T::LendMarket::do_collateral_asset(&who, vtoken_id, true)?;
 		}
-		let account_deposits = Self::current_collateral_amount(&who, vtoken_id)?;
-		let account_borrows = lend_market::Pallet::<T>::get_current_borrow_balance(&who, asset_id)?;
-
-		// Formula
-		// current_rate = account_borrows / (
-		// get_currency_amount_by_v_currency_amount(account_deposits) - account_borrows )
-		let deposits_token_value = T::VtokenMinting::get_currency_amount_by_v_currency_amount(
-			asset_id,
-			vtoken_id,
-			account_deposits,
-		)?;
-		let base_token_value = deposits_token_value
-			.checked_sub(account_borrows)
-			.ok_or(ArithmeticError::Overflow)?;
-		let current_rate = FixedU128::saturating_from_rational(account_borrows, base_token_value);
+		let (account_borrows, base_token_value, current_rate) =
+			Self::current_position(&who, asset_id, vtoken_id)?;
 
 		match rate.cmp(&current_rate) {
 			Ordering::Less => {
@@ -171,15 +202,23 @@ impl<T: Config> Pallet<T> {
 						.and_then(|r| r.checked_mul_int(base_token_value))
 						.ok_or(ArithmeticError::Overflow)?
 				};
-				Self::reduce_leverage(&who, asset_id, vtoken_id, reduce_amount)?;
+				Self::reduce_leverage(&who, asset_id, vtoken_id, reduce_amount, max_slippage)?;
 			}
 			Ordering::Equal => return Err(Error::<T>::ArgumentsError.into()),
 			Ordering::Greater => {
+				Self::ensure_under_max_leverage_rate(asset_id, rate)?;
 				let increase_amount = rate
 					.checked_sub(&current_rate)
 					.and_then(|r| r.checked_mul_int(base_token_value))
 					.ok_or(ArithmeticError::Overflow)?;
-				Self::increase_leverage(&who, asset_id, vtoken_id, increase_amount)?;
+				Self::increase_leverage(&who, asset_id, vtoken_id, increase_amount, max_slippage)?;
+
+				let (_, shortfall, _, _) =
+					lend_market::Pallet::<T>::get_account_liquidation_threshold_liquidity(&who)?;
+				ensure!(
+					shortfall.is_zero(),
+					Error::<T>::InsufficientLiquidationThresholdLiquidity
+				);
 			}
 		}
 		Self::deposit_event(Event::<T>::FlashLoanDeposited {
@@ -191,11 +230,114 @@ impl<T: Config> Pallet<T> {
 		Ok(())
 	}
 
+	/// Preview the position of `who` after changing the leverage rate of `asset_id` to `rate`.
+	///
+	/// Returns the borrow balance, the vtoken collateral and the account health factor, which
+	/// is the liquidation threshold value divided by the borrowed value. The result is
+	/// estimated from the current exchange rate, so it does not include swap slippage.
+	pub fn preview_flash_loan_deposit(
+		who: &AccountIdOf<T>,
+		asset_id: AssetIdOf<T>,
+		rate: Rate,
+	) -> Result<(BalanceOf<T>, BalanceOf<T>, FixedU128), DispatchError> {
+		let vtoken_id = T::CurrencyIdConversion::convert_to_vtoken(asset_id)
+			.map_err(|_| Error::<T>::NotSupportTokenType)?;
+		let (account_borrows, base_token_value, current_rate) =
+			Self::current_position(who, asset_id, vtoken_id)?;
+		if rate > current_rate {
+			Self::ensure_under_max_leverage_rate(asset_id, rate)?;
+		}
+
+		let borrow = rate
+			.checked_mul_int(base_token_value)
+			.ok_or(ArithmeticError::Overflow)?;
+		let collateral = T::VtokenMinting::get_v_currency_amount_by_currency_amount(
+			asset_id,
+			vtoken_id,
+			base_token_value
+				.checked_add(borrow)
+				.ok_or(ArithmeticError::Overflow)?,
+		)?;
+		let account_collateral = Self::current_collateral_amount(who, vtoken_id)?;
+
+		// Replace the current leveraged position with the target one in the account totals.
+		let liquidation_threshold =
+			lend_market::Pallet::<T>::market(vtoken_id)?.liquidation_threshold;
+		let old_threshold_value = if AccountDeposits::<T>::get(vtoken_id, who).is_collateral {
+			lend_market::Pallet::<T>::get_asset_value(
+				vtoken_id,
+				liquidation_threshold.mul_ceil(account_collateral),
+			)?
+		} else {
+			FixedU128::zero()
+		};
+		let new_threshold_value = lend_market::Pallet::<T>::get_asset_value(
+			vtoken_id,
+			liquidation_threshold.mul_ceil(collateral),
+		)?;
+		let threshold_value = lend_market::Pallet::<T>::total_liquidation_threshold_value(who)?
+			.saturating_sub(old_threshold_value)
+			.checked_add(&new_threshold_value)
+			.ok_or(ArithmeticError::Overflow)?;
+		let borrowed_value = lend_market::Pallet::<T>::total_borrowed_value(who)?
+			.saturating_sub(lend_market::Pallet::<T>::get_asset_value(
+				asset_id,
+				account_borrows,
+			)?)
+			.checked_add(&lend_market::Pallet::<T>::get_asset_value(
+				asset_id, borrow,
+			)?)
+			.ok_or(ArithmeticError::Overflow)?;
+
+		let health_factor = if borrowed_value.is_zero() {
+			FixedU128::max_value()
+		} else {
+			threshold_value
+				.checked_div(&borrowed_value)
+				.ok_or(ArithmeticError::Overflow)?
+		};
+
+		Ok((borrow, collateral, health_factor))
+	}
+
+	/// Returns the borrow balance, the base token value and the leverage rate of `who`.
+	fn current_position(
+		who: &AccountIdOf<T>,
+		asset_id: AssetIdOf<T>,
+		vtoken_id: AssetIdOf<T>,
+	) -> Result<(BalanceOf<T>, BalanceOf<T>, Rate), DispatchError> {
+		let account_deposits = Self::current_collateral_amount(who, vtoken_id)?;
+		let account_borrows = lend_market::Pallet::<T>::get_current_borrow_balance(who, asset_id)?;
+
+		// Formula
+		// current_rate = account_borrows / (
+		// get_currency_amount_by_v_currency_amount(account_deposits) - account_borrows )
+		let deposits_token_value = T::VtokenMinting::get_currency_amount_by_v_currency_amount(
+			asset_id,
+			vtoken_id,
+			account_deposits,
+		)?;
+		let base_token_value = deposits_token_value
+			.checked_sub(account_borrows)
+			.ok_or(ArithmeticError::Overflow)?;
+		let current_rate = FixedU128::saturating_from_rational(account_borrows, base_token_value);
+
+		Ok((account_borrows, base_token_value, current_rate))
+	}
+
+	fn ensure_under_max_leverage_rate(asset_id: AssetIdOf<T>, rate: Rate) -> DispatchResult {
+		if let Some(max_rate) = MaxLeverageRates::<T>::get(asset_id) {
+			ensure!(rate <= max_rate, Error::<T>::ExceedMaxLeverageRate);
+		}
+		Ok(())
+	}
+
 	fn reduce_leverage(
 		who: &T::AccountId,
 		asset_id: AssetIdOf<T>,
 		vtoken_id: AssetIdOf<T>,
 		reduce_amount: BalanceOf<T>,
+		max_slippage: Permill,
 	) -> DispatchResult {
 		let (pool_id, currency_id_in, currency_id_out) =
 			T::StablePoolHandler::get_pool_id(&vtoken_id, &asset_id)
@@ -210,6 +352,15 @@ impl<T: Config> Pallet<T> {
 			currency_id_out,
 			reduce_amount,
 		)?;
+		let expected_amount = T::VtokenMinting::get_v_currency_amount_by_currency_amount(
+			asset_id,
+			vtoken_id,
+			reduce_amount,
+		)?;
+		ensure!(
+			redeem_amount <= expected_amount.saturating_add(max_slippage.mul_ceil(expected_amount)),
+			Error::<T>::SlippageExceeded
+		);
 		// Do redeem
 		T::LendMarket::do_redeem(&who, vtoken_id, redeem_amount)?;
 
@@ -237,7 +388,13 @@ impl<T: Config> Pallet<T> {
 		asset_id: AssetIdOf<T>,
 		vtoken_id: AssetIdOf<T>,
 		increase_amount: BalanceOf<T>,
+		max_slippage: Permill,
 	) -> DispatchResult {
+		let expected_amount = T::VtokenMinting::get_v_currency_amount_by_currency_amount(
+			asset_id,
+			vtoken_id,
+			increase_amount,
+		)?;
 		<T as lend_market::Config>::Assets::mint_into(asset_id, &who, increase_amount)?;
 		let vtoken_value = T::VtokenMinting::mint(
 			who.clone(),
@@ -246,6 +403,10 @@ impl<T: Config> Pallet<T> {
 			BoundedVec::default(),
 			None,
 		)?;
+		ensure!(
+			vtoken_value >= expected_amount.saturating_sub(max_slippage.mul_floor(expected_amount)),
+			Error::<T>::SlippageExceeded
+		);
 		T::LendMarket::do_mint(&who, vtoken_id, vtoken_value)?;
 		T::LendMarket::do_borrow(&who, asset_id, increase_amount)?;
 		<T as lend_market::Config>::Assets::burn_from(
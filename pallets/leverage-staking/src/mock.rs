@@ -29,7 +29,7 @@ use bifrost_primitives::{
 use bifrost_runtime_common::milli;
 use frame_support::{
 	derive_impl, ord_parameter_types, parameter_types,
-	traits::{ConstU128, ConstU32, Everything, Nothing},
+	traits::{ConstU128, ConstU32, ConstU64, Everything, Nothing},
 };
 use frame_system::{EnsureRoot, EnsureSignedBy};
 use lend_market::{InterestRateModel, JumpModel, Market, MarketState};
@@ -253,6 +253,8 @@ impl bifrost_stable_asset::Config for Test {
 	type ListingOrigin = EnsureSignedBy<One, u128>;
 	type EnsurePoolAssetId = EnsurePoolAssetId;
 	type BlockNumberProvider = System;
+	type PriceObservationPeriod = ConstU64<10>;
+	type MaxPriceObservations = ConstU32<24>;
 }
 
 impl bifrost_stable_pool::Config for Test {
@@ -456,6 +458,7 @@ impl lend_market::Config for Test {
 	type LiquidationFreeAssetId = LiquidationFreeAssetId;
 	type MaxLengthLimit = MaxLengthLimit;
 	type BlockNumberProvider = System;
+	type VTokenSupplyProvider = VtokenMinting;
 }
 
 impl pallet_prices::Config for Test {
@@ -466,6 +469,11 @@ impl pallet_prices::Config for Test {
 	type RelayCurrency = RelayCurrencyId;
 	type Assets = Currencies;
 	type CurrencyIdConvert = AssetIdMaps<Test>;
+	type VtokenMinting = ();
+	type LendMarket = ();
+	type StableLpToken = ();
+	type UnixTime = TimestampPallet;
+	type StableTwap = ();
 	type WeightInfo = ();
 }
 
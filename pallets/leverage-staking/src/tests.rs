@@ -20,6 +20,7 @@
 use crate::mock::*;
 use frame_support::{assert_noop, assert_ok, traits::fungibles::Inspect, BoundedVec};
 use lend_market::{AccountBorrows, BorrowSnapshot, Deposits};
+use sp_runtime::{traits::Bounded, DispatchError::BadOrigin, Permill};
 
 fn init() {
 	env_logger::try_init().unwrap_or(());
@@ -105,6 +106,7 @@ fn increase_leverage_should_not_work() {
 					RuntimeOrigin::signed(1),
 					DOT,
 					FixedU128::from_inner(unit(1_000_100)),
+					Permill::from_percent(5),
 				),
 				lend_market::Error::<Test>::InsufficientLiquidity
 			);
@@ -122,6 +124,7 @@ fn increase_leverage_should_work() {
 				RuntimeOrigin::signed(1),
 				DOT,
 				FixedU128::from_inner(unit(100_000)),
+				Permill::from_percent(5),
 			));
 			assert_eq!(
 				AccountDeposits::<Test>::get(VDOT, 1),
@@ -141,6 +144,7 @@ fn increase_leverage_should_work() {
 				RuntimeOrigin::signed(1),
 				DOT,
 				FixedU128::from_inner(unit(800_000)),
+				Permill::from_percent(5),
 			));
 			assert_eq!(
 				AccountDeposits::<Test>::get(VDOT, 1),
@@ -160,6 +164,7 @@ fn increase_leverage_should_work() {
 				RuntimeOrigin::signed(1),
 				DOT,
 				FixedU128::from_inner(unit(900_000)),
+				Permill::from_percent(5),
 			));
 			assert_eq!(
 				AccountBorrows::<Test>::get(DOT, 1),
@@ -197,6 +202,7 @@ fn reduce_leverage_should_work() {
 				RuntimeOrigin::signed(1),
 				DOT,
 				FixedU128::from_inner(unit(900_000)),
+				Permill::from_percent(5),
 			));
 			assert_eq!(
 				AccountBorrows::<Test>::get(DOT, 1),
@@ -218,6 +224,7 @@ fn reduce_leverage_should_work() {
 				RuntimeOrigin::signed(1),
 				DOT,
 				FixedU128::from_inner(unit(800_000)),
+				Permill::from_percent(5),
 			));
 			assert_eq!(Tokens::balance(VDOT, &1), 9999999900000);
 			assert_eq!(Tokens::balance(DOT, &1), 990000000000098);
@@ -239,6 +246,7 @@ fn reduce_leverage_should_work() {
 				RuntimeOrigin::signed(1),
 				DOT,
 				FixedU128::from_inner(0),
+				Permill::from_percent(5),
 			));
 			assert_eq!(Tokens::balance(VDOT, &1), 9999999900000);
 			assert_eq!(Tokens::balance(DOT, &1), 990000000000196);
@@ -258,3 +266,110 @@ fn reduce_leverage_should_work() {
 			);
 		});
 }
+
+#[test]
+fn reduce_leverage_exceed_slippage_should_not_work() {
+	ExtBuilder::default()
+		.new_test_ext()
+		.build()
+		.execute_with(|| {
+			init();
+			assert_ok!(LeverageStaking::flash_loan_deposit(
+				RuntimeOrigin::signed(1),
+				DOT,
+				FixedU128::from_inner(unit(900_000)),
+				Permill::zero(),
+			));
+			assert_noop!(
+				LeverageStaking::flash_loan_deposit(
+					RuntimeOrigin::signed(1),
+					DOT,
+					FixedU128::from_inner(unit(800_000)),
+					Permill::zero(),
+				),
+				Error::<Test>::SlippageExceeded
+			);
+		});
+}
+
+#[test]
+fn max_leverage_rate_should_work() {
+	ExtBuilder::default()
+		.new_test_ext()
+		.build()
+		.execute_with(|| {
+			init();
+			let max_rate = FixedU128::from_inner(unit(500_000));
+			assert_noop!(
+				LeverageStaking::set_max_leverage_rate(
+					RuntimeOrigin::signed(1),
+					DOT,
+					Some(max_rate)
+				),
+				BadOrigin
+			);
+			assert_ok!(LeverageStaking::set_max_leverage_rate(
+				RuntimeOrigin::root(),
+				DOT,
+				Some(max_rate)
+			));
+			assert_eq!(MaxLeverageRates::<Test>::get(DOT), Some(max_rate));
+			assert_noop!(
+				LeverageStaking::flash_loan_deposit(
+					RuntimeOrigin::signed(1),
+					DOT,
+					FixedU128::from_inner(unit(800_000)),
+					Permill::from_percent(5),
+				),
+				Error::<Test>::ExceedMaxLeverageRate
+			);
+			assert_ok!(LeverageStaking::flash_loan_deposit(
+				RuntimeOrigin::signed(1),
+				DOT,
+				max_rate,
+				Permill::from_percent(5),
+			));
+
+			assert_ok!(LeverageStaking::set_max_leverage_rate(
+				RuntimeOrigin::root(),
+				DOT,
+				None
+			));
+			assert_ok!(LeverageStaking::flash_loan_deposit(
+				RuntimeOrigin::signed(1),
+				DOT,
+				FixedU128::from_inner(unit(800_000)),
+				Permill::from_percent(5),
+			));
+		});
+}
+
+#[test]
+fn preview_flash_loan_deposit_should_work() {
+	ExtBuilder::default()
+		.new_test_ext()
+		.build()
+		.execute_with(|| {
+			init();
+			let rate = FixedU128::from_inner(unit(100_000));
+			assert_eq!(
+				LeverageStaking::preview_flash_loan_deposit(&1, DOT, rate),
+				Ok((10_000, 110_000, FixedU128::from_rational(605, 100)))
+			);
+			assert_ok!(LeverageStaking::flash_loan_deposit(
+				RuntimeOrigin::signed(1),
+				DOT,
+				rate,
+				Permill::from_percent(5),
+			));
+			assert_eq!(AccountBorrows::<Test>::get(DOT, 1).principal, 10_000);
+			assert_eq!(
+				AccountDeposits::<Test>::get(VDOT, 1).voucher_balance,
+				5_500_000
+			);
+			assert_eq!(
+				LeverageStaking::preview_flash_loan_deposit(&1, DOT, FixedU128::from_inner(0)),
+				Ok((0, 100_000, FixedU128::max_value()))
+			);
+		});
+}
@@ -51,6 +51,7 @@ use frame_support::{
 
 pub trait WeightInfo {
 	fn flash_loan_deposit() -> Weight;
+	fn set_max_leverage_rate() -> Weight;
 }
 
 /// Weight functions for `leverage_staking`.
@@ -110,16 +111,30 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 	/// Proof Skipped: LendMarket RewardBorrowerIndex (max_values: None, max_size: None, mode: Measured)
 	/// Storage: LendMarket BorrowIndex (r:1 w:0)
 	/// Proof Skipped: LendMarket BorrowIndex (max_values: None, max_size: None, mode: Measured)
+	/// Storage: LeverageStaking MaxLeverageRates (r:1 w:0)
+	/// Proof Skipped: LeverageStaking MaxLeverageRates (max_values: None, max_size: None, mode: Measured)
 	fn flash_loan_deposit() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `3847`
 		//  Estimated: `13955`
 		// Minimum execution time: 265_916_000 picoseconds.
-		Weight::from_parts(267_741_000, 0)
+		// Estimated by hand for the max leverage rate and liquidation threshold checks, the
+		// benchmark has to be rerun.
+		Weight::from_parts(292_000_000, 0)
 			.saturating_add(Weight::from_parts(0, 13955))
-			.saturating_add(T::DbWeight::get().reads(41))
+			.saturating_add(T::DbWeight::get().reads(42))
 			.saturating_add(T::DbWeight::get().writes(21))
 	}
+	/// Storage: LeverageStaking MaxLeverageRates (r:0 w:1)
+	/// Proof Skipped: LeverageStaking MaxLeverageRates (max_values: None, max_size: None, mode: Measured)
+	fn set_max_leverage_rate() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 9_000_000 picoseconds.
+		Weight::from_parts(9_500_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
 }
 
 // For backwards compatibility and tests
@@ -178,14 +193,28 @@ impl WeightInfo for () {
 	/// Proof Skipped: LendMarket RewardBorrowerIndex (max_values: None, max_size: None, mode: Measured)
 	/// Storage: LendMarket BorrowIndex (r:1 w:0)
 	/// Proof Skipped: LendMarket BorrowIndex (max_values: None, max_size: None, mode: Measured)
+	/// Storage: LeverageStaking MaxLeverageRates (r:1 w:0)
+	/// Proof Skipped: LeverageStaking MaxLeverageRates (max_values: None, max_size: None, mode: Measured)
 	fn flash_loan_deposit() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `3847`
 		//  Estimated: `13955`
 		// Minimum execution time: 265_916_000 picoseconds.
-		Weight::from_parts(267_741_000, 0)
+		// Estimated by hand for the max leverage rate and liquidation threshold checks, the
+		// benchmark has to be rerun.
+		Weight::from_parts(292_000_000, 0)
 			.saturating_add(Weight::from_parts(0, 13955))
-			.saturating_add(RocksDbWeight::get().reads(41))
+			.saturating_add(RocksDbWeight::get().reads(42))
 			.saturating_add(RocksDbWeight::get().writes(21))
 	}
+	/// Storage: LeverageStaking MaxLeverageRates (r:0 w:1)
+	/// Proof Skipped: LeverageStaking MaxLeverageRates (max_values: None, max_size: None, mode: Measured)
+	fn set_max_leverage_rate() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 9_000_000 picoseconds.
+		Weight::from_parts(9_500_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1))
+	}
 }
\ No newline at end of file
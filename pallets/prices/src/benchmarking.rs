@@ -0,0 +1,89 @@
+// Copyright 2021 Parallel Finance Developer.
+// This file is part of Parallel Finance.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use crate::{Pallet as Prices, *};
+use bifrost_primitives::{DOT, VDOT};
+use frame_benchmarking::v2::*;
+use frame_support::traits::{EnsureOrigin, Hooks};
+
+fn guard() -> PriceGuard {
+	PriceGuard {
+		max_price_age: 6_000,
+		max_deviation: Permill::from_percent(10),
+	}
+}
+
+#[benchmarks]
+mod benchmarks {
+	use super::*;
+
+	#[benchmark]
+	fn set_price_guard() -> Result<(), BenchmarkError> {
+		let origin =
+			T::UpdateOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, DOT, Some(guard()));
+
+		assert_eq!(PriceGuards::<T>::get(DOT), Some(guard()));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn set_circuit_breaker() -> Result<(), BenchmarkError> {
+		let origin =
+			T::FeederOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, DOT, true);
+
+		assert_eq!(FeedStatuses::<T>::get(DOT), Some(FeedStatus::Tripped));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn set_twap_fallback() -> Result<(), BenchmarkError> {
+		let origin =
+			T::UpdateOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		let fallback = TwapFallback {
+			pool_id: 0,
+			anchor: DOT,
+			window: 10u32.into(),
+		};
+
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, VDOT, Some(fallback));
+
+		assert_eq!(TwapFallbacks::<T>::get(VDOT), Some(fallback));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn on_initialize(n: Linear<0, MAX_PRICE_GUARDS>) -> Result<(), BenchmarkError> {
+		for i in 0..n {
+			PriceGuards::<T>::insert(CurrencyId::ForeignAsset(i), guard());
+		}
+
+		#[block]
+		{
+			Prices::<T>::on_initialize(1u32.into());
+		}
+
+		Ok(())
+	}
+
+	impl_benchmark_test_suite!(Prices, crate::mock::new_test_ext(), crate::mock::Test);
+}
@@ -19,23 +19,44 @@
 //! This pallet provides the price from Oracle Module by implementing the
 //! `OraclePriceProvider` trait. In case of emergency, the price can be set directly
 //! by Oracle Collective.
+//!
+//! The prices of vTokens, stable pool lp tokens and lend tokens are derived from the
+//! price of their underlying assets, so they don't need their own feeder prices.
+//!
+//! Feeder prices can be guarded per asset by a maximum age and a maximum deviation per
+//! update. A stale feed, or a feed whose circuit breaker is tripped, returns no price. A
+//! tripped asset returns no derived or fallback price either.
+//!
+//! An asset without a fresh feeder price can fall back to its time-weighted average price in
+//! a stable pool, against an anchor asset that still has a price.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
 use bifrost_asset_registry::AssetMetadata;
 use bifrost_primitives::{
-	Balance, CurrencyId, CurrencyIdMapping, OraclePriceProvider, Price, PriceDetail,
-	TimeStampedPrice, TokenInfo,
+	Balance, CurrencyId, CurrencyIdExt, CurrencyIdMapping, Moment, OraclePriceProvider, Price,
+	PriceDetail, StableLpTokenProvider, StableTwapProvider, TimeStampedPrice, TokenInfo,
+	VtokenMintingInterface,
+};
+use frame_support::{
+	dispatch::DispatchClass,
+	pallet_prelude::*,
+	traits::{fungibles::Inspect, UnixTime},
+	transactional,
 };
-use frame_support::{dispatch::DispatchClass, pallet_prelude::*, transactional};
 use frame_system::pallet_prelude::*;
 use log;
 use orml_oracle::{DataFeeder, DataProvider, DataProviderExtended};
 pub use pallet::*;
 use pallet_traits::*;
-use sp_runtime::{traits::CheckedDiv, FixedU128};
+use sp_runtime::{
+	traits::{CheckedAdd, CheckedDiv, CheckedMul, Saturating, Zero},
+	FixedPointNumber, FixedU128, Permill, SaturatedConversion,
+};
 use sp_std::vec::Vec;
 
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
 #[cfg(test)]
 mod mock;
 #[cfg(test)]
@@ -43,6 +64,38 @@ mod tests;
 
 pub mod weights;
 
+/// The maximum number of assets with a price guard, as all guards are checked every block.
+pub const MAX_PRICE_GUARDS: u32 = 100;
+
+/// The guard applied to the feeder price of an asset.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct PriceGuard {
+	/// The maximum age of a price in milliseconds, zero means no limit.
+	pub max_price_age: Moment,
+	/// The maximum deviation of a price update from the last accepted price.
+	pub max_deviation: Permill,
+}
+
+/// The abnormal status of a price feed. A feed without status is healthy.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub enum FeedStatus {
+	/// The feeder price is older than `max_price_age`.
+	Stale,
+	/// The circuit breaker is tripped by governance.
+	Tripped,
+}
+
+/// The stable pool time-weighted average price an asset falls back to.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct TwapFallback<BlockNumber> {
+	/// The stable pool holding the asset and the anchor.
+	pub pool_id: u32,
+	/// The asset the average price is taken against, which must have its own price.
+	pub anchor: CurrencyId,
+	/// The number of blocks the price is averaged over.
+	pub window: BlockNumber,
+}
+
 #[frame_support::pallet]
 pub mod pallet {
 	use super::*;
@@ -80,6 +133,21 @@ pub mod pallet {
 		/// Convert Location to `T::CurrencyId`.
 		type CurrencyIdConvert: CurrencyIdMapping<CurrencyId, AssetMetadata<BalanceOf<Self>>>;
 
+		/// The vtoken minting, used to derive the vtoken price from its exchange rate.
+		type VtokenMinting: VtokenMintingInterface<Self::AccountId, CurrencyId, Balance>;
+
+		/// The lend market, used to derive the lend token price from its exchange rate.
+		type LendMarket: LendMarketMarketDataProvider<CurrencyId, Balance>;
+
+		/// The stable pools, used to derive the lp token price from the pool reserves.
+		type StableLpToken: StableLpTokenProvider<CurrencyId, Balance>;
+
+		/// Unix time, used to check the age of prices.
+		type UnixTime: UnixTime;
+
+		/// The stable pools, used as a fallback price source.
+		type StableTwap: StableTwapProvider<CurrencyId, BlockNumberFor<Self>>;
+
 		/// Weight information
 		type WeightInfo: WeightInfo;
 	}
@@ -91,6 +159,43 @@ pub mod pallet {
 		SetPrice(CurrencyId, Price),
 		/// Reset emergency price. \[asset_id\]
 		ResetPrice(CurrencyId),
+		/// Price guard updated, `None` means removed.
+		PriceGuardSet {
+			asset_id: CurrencyId,
+			guard: Option<PriceGuard>,
+		},
+		/// The feeder price is older than the maximum price age.
+		PriceStale {
+			asset_id: CurrencyId,
+			timestamp: Option<Moment>,
+		},
+		/// The feeder price is fresh again.
+		PriceRecovered { asset_id: CurrencyId },
+		/// The feeder price deviates too much from the last accepted price and is clamped.
+		PriceClamped {
+			asset_id: CurrencyId,
+			fed_price: Price,
+			accepted_price: Price,
+		},
+		/// The circuit breaker is tripped.
+		CircuitBreakerTripped { asset_id: CurrencyId },
+		/// The circuit breaker is reset.
+		CircuitBreakerReset { asset_id: CurrencyId },
+		/// Twap fallback updated, `None` means removed.
+		TwapFallbackSet {
+			asset_id: CurrencyId,
+			fallback: Option<TwapFallback<BlockNumberFor<T>>>,
+		},
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The circuit breaker is already in the requested state.
+		CircuitBreakerUnchanged,
+		/// The anchor of a twap fallback is the asset itself.
+		InvalidTwapAnchor,
+		/// The number of price guards reached `MAX_PRICE_GUARDS`.
+		TooManyPriceGuards,
 	}
 
 	/// Mapping from currency id to it's emergency price
@@ -103,6 +208,26 @@ pub mod pallet {
 	pub type ForeignToNativeAsset<T: Config> =
 		StorageMap<_, Twox64Concat, CurrencyId, CurrencyId, OptionQuery>;
 
+	/// Mapping from currency id to the guard of its feeder price
+	#[pallet::storage]
+	pub type PriceGuards<T: Config> =
+		CountedStorageMap<_, Twox64Concat, CurrencyId, PriceGuard, OptionQuery>;
+
+	/// Mapping from currency id to the last accepted feeder price of guarded assets
+	#[pallet::storage]
+	pub type AcceptedPrice<T: Config> =
+		StorageMap<_, Twox64Concat, CurrencyId, TimeStampedPrice, OptionQuery>;
+
+	/// Mapping from currency id to the abnormal status of its feed
+	#[pallet::storage]
+	pub type FeedStatuses<T: Config> =
+		StorageMap<_, Twox64Concat, CurrencyId, FeedStatus, OptionQuery>;
+
+	/// Mapping from currency id to the stable pool twap used when its feeder price is missing
+	#[pallet::storage]
+	pub type TwapFallbacks<T: Config> =
+		StorageMap<_, Twox64Concat, CurrencyId, TwapFallback<BlockNumberFor<T>>, OptionQuery>;
+
 	#[pallet::genesis_config]
 	#[derive(frame_support::DefaultNoBound)]
 	pub struct GenesisConfig<T: Config> {
@@ -127,6 +252,19 @@ pub mod pallet {
 	#[pallet::without_storage_info]
 	pub struct Pallet<T>(PhantomData<T>);
 
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_initialize(_n: BlockNumberFor<T>) -> Weight {
+			// The number of guards is capped by `MAX_PRICE_GUARDS` in `set_price_guard`.
+			let count = PriceGuards::<T>::count();
+			for (asset_id, guard) in PriceGuards::<T>::iter() {
+				Self::check_price_guard(asset_id, guard);
+			}
+
+			<T as Config>::WeightInfo::on_initialize(count)
+		}
+	}
+
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
 		/// Set emergency price
@@ -173,6 +311,78 @@ pub mod pallet {
 			ForeignToNativeAsset::<T>::insert(foreign_asset_id, asset_id);
 			Ok(().into())
 		}
+
+		/// Set the guard of an asset feeder price, `None` to remove it
+		#[pallet::call_index(3)]
+		#[pallet::weight(<T as Config>::WeightInfo::set_price_guard())]
+		#[transactional]
+		pub fn set_price_guard(
+			origin: OriginFor<T>,
+			asset_id: CurrencyId,
+			guard: Option<PriceGuard>,
+		) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			ensure!(
+				guard.is_none()
+					|| PriceGuards::<T>::contains_key(asset_id)
+					|| PriceGuards::<T>::count() < MAX_PRICE_GUARDS,
+				Error::<T>::TooManyPriceGuards
+			);
+			if let Some(guard) = guard {
+				PriceGuards::<T>::insert(asset_id, guard);
+			} else {
+				PriceGuards::<T>::remove(asset_id);
+				AcceptedPrice::<T>::remove(asset_id);
+				FeedStatuses::<T>::mutate_exists(asset_id, |status| {
+					if *status == Some(FeedStatus::Stale) {
+						*status = None;
+					}
+				});
+			}
+			Self::deposit_event(Event::<T>::PriceGuardSet { asset_id, guard });
+			Ok(().into())
+		}
+
+		/// Trip or reset the circuit breaker of an asset price
+		#[pallet::call_index(4)]
+		#[pallet::weight((<T as Config>::WeightInfo::set_circuit_breaker(), DispatchClass::Operational))]
+		#[transactional]
+		pub fn set_circuit_breaker(
+			origin: OriginFor<T>,
+			asset_id: CurrencyId,
+			tripped: bool,
+		) -> DispatchResultWithPostInfo {
+			T::FeederOrigin::ensure_origin(origin)?;
+			let is_tripped = FeedStatuses::<T>::get(asset_id) == Some(FeedStatus::Tripped);
+			ensure!(is_tripped != tripped, Error::<T>::CircuitBreakerUnchanged);
+			if tripped {
+				FeedStatuses::<T>::insert(asset_id, FeedStatus::Tripped);
+				Self::deposit_event(Event::<T>::CircuitBreakerTripped { asset_id });
+			} else {
+				// The stale status is re-evaluated in the next block.
+				FeedStatuses::<T>::remove(asset_id);
+				Self::deposit_event(Event::<T>::CircuitBreakerReset { asset_id });
+			}
+			Ok(().into())
+		}
+
+		/// Set the stable pool twap an asset price falls back to, `None` to remove it
+		#[pallet::call_index(5)]
+		#[pallet::weight(<T as Config>::WeightInfo::set_twap_fallback())]
+		#[transactional]
+		pub fn set_twap_fallback(
+			origin: OriginFor<T>,
+			asset_id: CurrencyId,
+			fallback: Option<TwapFallback<BlockNumberFor<T>>>,
+		) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			if let Some(fallback) = fallback {
+				ensure!(fallback.anchor != asset_id, Error::<T>::InvalidTwapAnchor);
+			}
+			TwapFallbacks::<T>::set(asset_id, fallback);
+			Self::deposit_event(Event::<T>::TwapFallbackSet { asset_id, fallback });
+			Ok(().into())
+		}
 	}
 }
 
@@ -194,7 +404,181 @@ impl<T: Config> Pallet<T> {
 
 	fn get_storage_price(asset_id: &CurrencyId) -> Option<Price> {
 		EmergencyPrice::<T>::get(asset_id)
-			.or_else(|| T::Source::get(asset_id).and_then(|price| Some(price.value)))
+			.or_else(|| Self::get_guarded_price(asset_id).map(|price| price.value))
+	}
+
+	fn now() -> Moment {
+		T::UnixTime::now().as_millis().saturated_into()
+	}
+
+	/// Get the derived price of a derived asset. Get the feeder price of any other asset, or the
+	/// twap fallback price if the feeder price is missing or stale. Returns `None` if the circuit
+	/// breaker of the asset is tripped, or if the derived price is unavailable or older than the
+	/// maximum price age of the asset.
+	fn get_guarded_price(asset_id: &CurrencyId) -> Option<TimeStampedPrice> {
+		if FeedStatuses::<T>::get(asset_id) == Some(FeedStatus::Tripped) {
+			return None;
+		}
+		if Self::is_derived_asset(asset_id) {
+			// Never fall back to the own feeder price of a derived asset, it would bypass the
+			// circuit breaker and the guard of the underlying assets.
+			let price = Self::get_special_asset_price(*asset_id)?;
+			// A zero timestamp means the price is derived from emergency prices only.
+			return match PriceGuards::<T>::get(asset_id) {
+				Some(guard) if price.timestamp != 0 && Self::is_stale(&guard, price.timestamp) => {
+					None
+				}
+				_ => Some(price),
+			};
+		}
+		Self::get_feeder_price(asset_id).or_else(|| Self::get_twap_price(asset_id))
+	}
+
+	/// Whether the price of the asset is derived from the prices of its underlying assets.
+	fn is_derived_asset(asset_id: &CurrencyId) -> bool {
+		matches!(
+			asset_id,
+			CurrencyId::VToken(_)
+				| CurrencyId::VToken2(_)
+				| CurrencyId::Lend(_)
+				| CurrencyId::StableLpToken(_)
+		)
+	}
+
+	/// Get the feeder price with the guard of the asset applied.
+	/// Returns `None` if the price is stale.
+	fn get_feeder_price(asset_id: &CurrencyId) -> Option<TimeStampedPrice> {
+		let Some(guard) = PriceGuards::<T>::get(asset_id) else {
+			return T::Source::get(asset_id);
+		};
+		let (price, _) = Self::apply_price_guard(
+			&guard,
+			AcceptedPrice::<T>::get(asset_id),
+			T::Source::get(asset_id),
+		)?;
+		if Self::is_stale(&guard, price.timestamp) {
+			return None;
+		}
+		Some(price)
+	}
+
+	/// Derive the price of an asset from its twap in a stable pool and the price of the anchor.
+	/// The anchor price is its emergency or feeder price, never a twap fallback itself.
+	fn get_twap_price(asset_id: &CurrencyId) -> Option<TimeStampedPrice> {
+		let fallback = TwapFallbacks::<T>::get(asset_id)?;
+		let anchor_price = match EmergencyPrice::<T>::get(fallback.anchor) {
+			Some(value) => TimeStampedPrice {
+				value,
+				timestamp: 0,
+			},
+			None if FeedStatuses::<T>::get(fallback.anchor) == Some(FeedStatus::Tripped) => {
+				return None
+			}
+			None => Self::get_feeder_price(&fallback.anchor)?,
+		};
+		let twap = T::StableTwap::get_twap(
+			fallback.pool_id,
+			*asset_id,
+			fallback.anchor,
+			fallback.window,
+		)?;
+		// The twap is between the smallest units, the prices are per whole token.
+		let decimals_ratio = FixedU128::checked_from_rational(
+			Self::get_asset_mantissa(asset_id)?,
+			Self::get_asset_mantissa(&fallback.anchor)?,
+		)?;
+		log::trace!(
+			target: "prices::get_twap_price",
+			"asset_id: {:?}, twap: {:?}, anchor_price: {:?}",
+			asset_id,
+			twap,
+			anchor_price
+		);
+
+		anchor_price
+			.value
+			.checked_mul(&twap)?
+			.checked_mul(&decimals_ratio)
+			.map(|value| TimeStampedPrice {
+				value,
+				timestamp: anchor_price.timestamp,
+			})
+	}
+
+	fn is_stale(guard: &PriceGuard, timestamp: Moment) -> bool {
+		!guard.max_price_age.is_zero()
+			&& Self::now().saturating_sub(timestamp) > guard.max_price_age
+	}
+
+	/// Clamp the fed price into `max_deviation` of the last accepted price.
+	/// Returns the accepted price and whether it was clamped.
+	fn apply_price_guard(
+		guard: &PriceGuard,
+		accepted: Option<TimeStampedPrice>,
+		fed: Option<TimeStampedPrice>,
+	) -> Option<(TimeStampedPrice, bool)> {
+		let (accepted, fed) = match (accepted, fed) {
+			(Some(accepted), Some(fed)) if fed.timestamp > accepted.timestamp => (accepted, fed),
+			(Some(accepted), _) => return Some((accepted, false)),
+			(None, fed) => return fed.map(|fed| (fed, false)),
+		};
+		let max_change = FixedU128::from(guard.max_deviation).saturating_mul(accepted.value);
+		let upper = accepted.value.saturating_add(max_change);
+		let lower = accepted.value.saturating_sub(max_change);
+		if fed.value > upper {
+			Some((
+				TimeStampedPrice {
+					value: upper,
+					timestamp: fed.timestamp,
+				},
+				true,
+			))
+		} else if fed.value < lower {
+			Some((
+				TimeStampedPrice {
+					value: lower,
+					timestamp: fed.timestamp,
+				},
+				true,
+			))
+		} else {
+			Some((fed, false))
+		}
+	}
+
+	/// Accept the latest feeder price of a guarded asset and update its feed status.
+	fn check_price_guard(asset_id: CurrencyId, guard: PriceGuard) {
+		let fed = T::Source::get(&asset_id);
+		let fed_price = fed.as_ref().map(|fed| fed.value).unwrap_or_default();
+		if let Some((price, clamped)) =
+			Self::apply_price_guard(&guard, AcceptedPrice::<T>::get(asset_id), fed)
+		{
+			if clamped {
+				Self::deposit_event(Event::<T>::PriceClamped {
+					asset_id,
+					fed_price,
+					accepted_price: price.value,
+				});
+			}
+			AcceptedPrice::<T>::insert(asset_id, price);
+		}
+
+		let timestamp = AcceptedPrice::<T>::get(asset_id).map(|price| price.timestamp);
+		let is_stale = timestamp.map_or(true, |timestamp| Self::is_stale(&guard, timestamp));
+		match FeedStatuses::<T>::get(asset_id) {
+			None if is_stale => {
+				FeedStatuses::<T>::insert(asset_id, FeedStatus::Stale);
+				Self::deposit_event(Event::<T>::PriceStale {
+					asset_id,
+					timestamp,
+				});
+			}
+			Some(FeedStatus::Stale) if !is_stale => {
+				FeedStatuses::<T>::remove(asset_id);
+				Self::deposit_event(Event::<T>::PriceRecovered { asset_id });
+			}
+			_ => {}
+		}
 	}
 
 	fn get_asset_mantissa(asset_id: &CurrencyId) -> Option<u128> {
@@ -209,11 +593,64 @@ impl<T: Config> Pallet<T> {
 		)
 	}
 
-	fn get_special_asset_price(
-		_asset_id: CurrencyId,
-		_base_price: TimeStampedPrice,
-	) -> Option<TimeStampedPrice> {
-		None
+	/// Derive the price of vtokens, lend tokens and stable pool lp tokens from their
+	/// underlying assets. The timestamp is the oldest one among the underlying prices, so it is
+	/// zero only if the price is derived from an emergency price.
+	fn get_special_asset_price(asset_id: CurrencyId) -> Option<TimeStampedPrice> {
+		let (underlying_price, timestamp) = match asset_id {
+			CurrencyId::VToken(_) | CurrencyId::VToken2(_) => {
+				let token_id = asset_id.to_token().ok()?;
+				let token_pool = T::VtokenMinting::get_token_pool(token_id);
+				let vtoken_issuance = T::Assets::total_issuance(asset_id);
+				if token_pool.is_zero() {
+					return None;
+				}
+				let (price, timestamp) = Self::get_price(&token_id)?;
+				(
+					price.checked_mul(&FixedU128::checked_from_rational(
+						token_pool,
+						vtoken_issuance,
+					)?)?,
+					timestamp,
+				)
+			}
+			CurrencyId::Lend(_) => {
+				let underlying_id = T::LendMarket::get_underlying_asset_id(asset_id)?;
+				let exchange_rate = T::LendMarket::get_market_status(underlying_id)
+					.ok()?
+					.exchange_rate;
+				let (price, timestamp) = Self::get_price(&underlying_id)?;
+				(price.checked_mul(&exchange_rate)?, timestamp)
+			}
+			CurrencyId::StableLpToken(_) => {
+				let (reserves, lp_issuance) = T::StableLpToken::get_pool_reserves(asset_id)?;
+				let mut total_value = FixedU128::zero();
+				let mut timestamp = Moment::MAX;
+				for (reserve_id, reserve_amount) in reserves {
+					let (price, reserve_timestamp) = Self::get_price(&reserve_id)?;
+					total_value = total_value
+						.checked_add(&price.checked_mul(&FixedU128::from_inner(reserve_amount))?)?;
+					timestamp = timestamp.min(reserve_timestamp);
+				}
+				(
+					total_value.checked_div(&FixedU128::from_inner(lp_issuance))?,
+					timestamp,
+				)
+			}
+			_ => return None,
+		};
+		let mantissa = Self::get_asset_mantissa(&asset_id)?;
+		log::trace!(
+			target: "prices::get_special_asset_price",
+			"asset_id: {:?}, underlying_price: {:?}, timestamp: {:?}",
+			asset_id,
+			underlying_price,
+			timestamp
+		);
+
+		underlying_price
+			.checked_mul(&FixedU128::from_inner(mantissa))
+			.map(|value| TimeStampedPrice { value, timestamp })
 	}
 
 	fn normalize_detail_price(price: TimeStampedPrice, mantissa: u128) -> Option<PriceDetail> {
@@ -236,9 +673,7 @@ impl<T: Config> OraclePriceProvider for Pallet<T> {
 		// if emergency price exists, return it
 		Self::get_emergency_price(asset_id).or_else(|| {
 			let mantissa = Self::get_asset_mantissa(asset_id)?;
-			T::Source::get(&T::RelayCurrency::get())
-				.and_then(|base_price| Self::get_special_asset_price(*asset_id, base_price))
-				.or_else(|| T::Source::get(asset_id))
+			Self::get_guarded_price(asset_id)
 				.and_then(|price| Self::normalize_detail_price(price, mantissa))
 		})
 	}
@@ -313,9 +748,7 @@ impl<T: Config> EmergencyOraclePriceProvider<CurrencyId, Price> for Pallet<T> {
 impl<T: Config> DataProviderExtended<CurrencyId, TimeStampedPrice> for Pallet<T> {
 	fn get_no_op(asset_id: &CurrencyId) -> Option<TimeStampedPrice> {
 		let _mantissa = Self::get_asset_mantissa(asset_id)?;
-		T::Source::get_no_op(&T::RelayCurrency::get())
-			.and_then(|base_price| Self::get_special_asset_price(*asset_id, base_price))
-			.or_else(|| T::Source::get_no_op(asset_id))
+		Self::get_guarded_price(asset_id)
 	}
 
 	fn get_all_values() -> Vec<(CurrencyId, Option<TimeStampedPrice>)> {
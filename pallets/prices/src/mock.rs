@@ -20,14 +20,14 @@ use frame_support::{
 	traits::{AsEnsureOriginWithArg, Nothing},
 };
 use frame_system::{EnsureRoot, EnsureSigned, EnsureSignedBy};
-use sp_runtime::{traits::IdentityLookup, FixedPointNumber};
+use sp_runtime::{traits::IdentityLookup, DispatchError, DispatchResult, FixedPointNumber};
 
 use bifrost_asset_registry::AssetIdMaps;
 pub use bifrost_primitives::{
 	currency::{FIL, VFIL},
-	DOT, KSM, MANTA, VDOT,
+	DOT, DOT_U, KSM, MANTA, VDOT,
 };
-use bifrost_primitives::{Moment, ASTR, BNC, DOT_U, GLMR};
+use bifrost_primitives::{Moment, RedeemType, TimeUnit, ASTR, BNC, GLMR};
 use sp_runtime::BuildStorage;
 
 pub type AccountId = u128;
@@ -53,6 +53,11 @@ pub type TimeStampedPrice = orml_oracle::TimestampedValue<Price, Moment>;
 pub struct MockDataProvider;
 impl DataProvider<CurrencyId, TimeStampedPrice> for MockDataProvider {
 	fn get(asset_id: &CurrencyId) -> Option<TimeStampedPrice> {
+		if let Some((fed_asset_id, price)) = MockFeed::get() {
+			if fed_asset_id == *asset_id {
+				return Some(price);
+			}
+		}
 		match *asset_id {
 			BNC => Some(TimeStampedPrice {
 				value: Price::from_inner(200_000_000_000_000_000),
@@ -177,6 +182,102 @@ impl pallet_assets::Config for Test {
 parameter_types! {
 	pub const RelayCurrency: CurrencyId = DOT;
 	pub const NativeCurrencyId: CurrencyId = BNC;
+	pub static MockTokenPool: Balance = 0;
+	pub static MockNow: Moment = 0;
+	pub static MockFeed: Option<(CurrencyId, TimeStampedPrice)> = None;
+	pub static MockTwap: Option<Price> = None;
+}
+
+pub struct MockUnixTime;
+impl UnixTime for MockUnixTime {
+	fn now() -> core::time::Duration {
+		core::time::Duration::from_millis(MockNow::get())
+	}
+}
+
+pub struct MockStableTwap;
+impl StableTwapProvider<CurrencyId, BlockNumber> for MockStableTwap {
+	fn get_twap(
+		_pool_id: u32,
+		_currency_in: CurrencyId,
+		_currency_out: CurrencyId,
+		_window: BlockNumber,
+	) -> Option<Price> {
+		MockTwap::get()
+	}
+}
+
+pub struct MockVtokenMinting;
+impl VtokenMintingInterface<AccountId, CurrencyId, Balance> for MockVtokenMinting {
+	fn mint(
+		_exchanger: AccountId,
+		_token_id: CurrencyId,
+		_token_amount: Balance,
+		_remark: BoundedVec<u8, ConstU32<32>>,
+		_channel_id: Option<u32>,
+	) -> Result<Balance, DispatchError> {
+		Ok(0)
+	}
+
+	fn redeem(
+		_exchanger: AccountId,
+		_vtoken_id: CurrencyId,
+		_vtoken_amount: Balance,
+	) -> DispatchResultWithPostInfo {
+		Ok(().into())
+	}
+
+	fn slpx_redeem(
+		_exchanger: AccountId,
+		_vtoken_id: CurrencyId,
+		_vtoken_amount: Balance,
+		_redeem_type: RedeemType<AccountId>,
+	) -> DispatchResultWithPostInfo {
+		Ok(().into())
+	}
+
+	fn rebond(
+		_exchanger: AccountId,
+		_token_id: CurrencyId,
+		_token_amount: Balance,
+	) -> DispatchResult {
+		Ok(())
+	}
+
+	fn get_v_currency_amount_by_currency_amount(
+		_token_id: CurrencyId,
+		_vtoken_id: CurrencyId,
+		_token_amount: Balance,
+	) -> Result<Balance, DispatchError> {
+		Ok(0)
+	}
+
+	fn get_currency_amount_by_v_currency_amount(
+		_token_id: CurrencyId,
+		_vtoken_id: CurrencyId,
+		_vtoken_amount: Balance,
+	) -> Result<Balance, DispatchError> {
+		Ok(0)
+	}
+
+	fn get_token_pool(_currency_id: CurrencyId) -> Balance {
+		MockTokenPool::get()
+	}
+
+	fn get_minimums_redeem(_vtoken_id: CurrencyId) -> Balance {
+		0
+	}
+
+	fn get_moonbeam_parachain_id() -> u32 {
+		0
+	}
+
+	fn get_user_unlock_queue(
+		_exchanger: AccountId,
+		_token_id: CurrencyId,
+	) -> Vec<(u32, Balance, TimeUnit)> {
+		Vec::new()
+	}
 }
 
 impl crate::Config for Test {
@@ -187,6 +288,11 @@ impl crate::Config for Test {
 	type RelayCurrency = RelayCurrency;
 	type CurrencyIdConvert = AssetIdMaps<Test>;
 	type Assets = Currencies;
+	type VtokenMinting = MockVtokenMinting;
+	type LendMarket = ();
+	type StableLpToken = ();
+	type UnixTime = MockUnixTime;
+	type StableTwap = MockStableTwap;
 	type WeightInfo = ();
 }
 
@@ -254,7 +360,7 @@ pub fn new_test_ext() -> sp_io::TestExternalities {
 			(DOT, 1, Some(("_".to_string(), "_".to_string(), 10))),
 			(ASTR, 1, None),
 			(GLMR, 1, None),
-			(DOT_U, 1, None),
+			(DOT_U, 1, Some(("_".to_string(), "_".to_string(), 6))),
 			(FIL, 1, Some(("_".to_string(), "_".to_string(), 10))),
 			(VFIL, 1, Some(("_".to_string(), "_".to_string(), 10))),
 		],
@@ -17,8 +17,8 @@
 use super::*;
 use bifrost_asset_registry::AssetMetadata;
 use bifrost_primitives::{BNC, MANTA, VKSM};
-use frame_support::{assert_noop, assert_ok};
-use mock::{RuntimeEvent, *};
+use frame_support::{assert_noop, assert_ok, traits::Hooks};
+use mock::{RuntimeEvent, TimeStampedPrice, *};
 use sp_runtime::{traits::BadOrigin, FixedPointNumber};
 
 #[test]
@@ -308,3 +308,459 @@ fn get_oracle_amount_by_currency_and_amount_in() {
 		);
 	});
 }
+
+#[test]
+fn get_vtoken_price_by_exchange_rate_work() {
+	new_test_ext().execute_with(|| {
+		// token pool is empty, a derived asset has no price
+		assert_eq!(Prices::get_price(&VDOT), None);
+
+		// 1 VDOT = 1.2 DOT
+		MockTokenPool::set(1200 * PRICE_ONE);
+		assert_eq!(
+			Prices::get_price(&VDOT),
+			Some((Price::from_inner(12_000_000_000 * PRICE_ONE), 0))
+		);
+		assert_eq!(
+			Some((
+				12 * 10u128.pow(10),
+				Price::saturating_from_integer(120),
+				Price::saturating_from_integer(100)
+			)),
+			Prices::get_oracle_amount_by_currency_and_amount_in(&VDOT, 10u128.pow(11), &DOT)
+		);
+
+		// emergency price has priority
+		assert_ok!(Prices::set_price(
+			RuntimeOrigin::signed(ALICE),
+			VDOT,
+			Price::saturating_from_integer(110)
+		));
+		assert_eq!(
+			Prices::get_price(&VDOT),
+			Some((Price::from_inner(11_000_000_000 * PRICE_ONE), 0))
+		);
+	});
+}
+
+#[test]
+fn stale_price_work() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let guard = PriceGuard {
+			max_price_age: 6_000,
+			max_deviation: Permill::from_percent(10),
+		};
+		assert_noop!(
+			Prices::set_price_guard(RuntimeOrigin::signed(CHARLIE), DOT, Some(guard)),
+			BadOrigin
+		);
+		assert_ok!(Prices::set_price_guard(
+			RuntimeOrigin::signed(ALICE),
+			DOT,
+			Some(guard)
+		));
+		System::assert_last_event(RuntimeEvent::Prices(crate::Event::PriceGuardSet {
+			asset_id: DOT,
+			guard: Some(guard),
+		}));
+
+		MockNow::set(6_000);
+		Prices::on_initialize(1);
+		assert_eq!(FeedStatuses::<Test>::get(DOT), None);
+		assert_eq!(
+			Prices::get_price(&DOT),
+			Some((Price::from_inner(10_000_000_000 * PRICE_ONE), 0))
+		);
+
+		MockNow::set(6_001);
+		assert_eq!(Prices::get_price(&DOT), None);
+		Prices::on_initialize(2);
+		assert_eq!(FeedStatuses::<Test>::get(DOT), Some(FeedStatus::Stale));
+		System::assert_last_event(RuntimeEvent::Prices(crate::Event::PriceStale {
+			asset_id: DOT,
+			timestamp: Some(0),
+		}));
+
+		// emergency price is not guarded
+		assert_ok!(Prices::set_price(
+			RuntimeOrigin::signed(ALICE),
+			DOT,
+			Price::saturating_from_integer(99)
+		));
+		assert_eq!(
+			Prices::get_price(&DOT),
+			Some((Price::from_inner(9_900_000_000 * PRICE_ONE), 0))
+		);
+		assert_ok!(Prices::reset_price(RuntimeOrigin::signed(ALICE), DOT));
+
+		MockFeed::set(Some((
+			DOT,
+			TimeStampedPrice {
+				value: Price::saturating_from_integer(100),
+				timestamp: 6_001,
+			},
+		)));
+		Prices::on_initialize(3);
+		assert_eq!(FeedStatuses::<Test>::get(DOT), None);
+		System::assert_last_event(RuntimeEvent::Prices(crate::Event::PriceRecovered {
+			asset_id: DOT,
+		}));
+		assert_eq!(
+			Prices::get_price(&DOT),
+			Some((Price::from_inner(10_000_000_000 * PRICE_ONE), 6_001))
+		);
+	});
+}
+
+#[test]
+fn clamp_price_work() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let guard = PriceGuard {
+			max_price_age: 0,
+			max_deviation: Permill::from_percent(10),
+		};
+		assert_ok!(Prices::set_price_guard(
+			RuntimeOrigin::signed(ALICE),
+			DOT,
+			Some(guard)
+		));
+		Prices::on_initialize(1);
+		assert_eq!(
+			AcceptedPrice::<Test>::get(DOT),
+			Some(TimeStampedPrice {
+				value: Price::saturating_from_integer(100),
+				timestamp: 0
+			})
+		);
+
+		// 100 -> 150 is clamped to 110
+		MockFeed::set(Some((
+			DOT,
+			TimeStampedPrice {
+				value: Price::saturating_from_integer(150),
+				timestamp: 1,
+			},
+		)));
+		assert_eq!(
+			Prices::get_price(&DOT),
+			Some((Price::from_inner(11_000_000_000 * PRICE_ONE), 1))
+		);
+		Prices::on_initialize(2);
+		System::assert_last_event(RuntimeEvent::Prices(crate::Event::PriceClamped {
+			asset_id: DOT,
+			fed_price: Price::saturating_from_integer(150),
+			accepted_price: Price::saturating_from_integer(110),
+		}));
+
+		// 110 -> 90 is clamped to 99
+		MockFeed::set(Some((
+			DOT,
+			TimeStampedPrice {
+				value: Price::saturating_from_integer(90),
+				timestamp: 2,
+			},
+		)));
+		Prices::on_initialize(3);
+		assert_eq!(
+			AcceptedPrice::<Test>::get(DOT),
+			Some(TimeStampedPrice {
+				value: Price::saturating_from_integer(99),
+				timestamp: 2
+			})
+		);
+	});
+}
+
+#[test]
+fn circuit_breaker_work() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(
+			Prices::set_circuit_breaker(RuntimeOrigin::signed(ALICE), DOT, false),
+			Error::<Test>::CircuitBreakerUnchanged
+		);
+		assert_ok!(Prices::set_circuit_breaker(
+			RuntimeOrigin::signed(ALICE),
+			DOT,
+			true
+		));
+		System::assert_last_event(RuntimeEvent::Prices(crate::Event::CircuitBreakerTripped {
+			asset_id: DOT,
+		}));
+		assert_eq!(Prices::get_price(&DOT), None);
+		assert_eq!(
+			Prices::get_oracle_amount_by_currency_and_amount_in(&BNC, 100, &DOT),
+			None
+		);
+
+		assert_ok!(Prices::set_circuit_breaker(
+			RuntimeOrigin::signed(ALICE),
+			DOT,
+			false
+		));
+		System::assert_last_event(RuntimeEvent::Prices(crate::Event::CircuitBreakerReset {
+			asset_id: DOT,
+		}));
+		assert_eq!(
+			Prices::get_price(&DOT),
+			Some((Price::from_inner(10_000_000_000 * PRICE_ONE), 0))
+		);
+	});
+}
+
+#[test]
+fn twap_fallback_work() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let fallback = TwapFallback {
+			pool_id: 0,
+			anchor: DOT,
+			window: 10,
+		};
+		assert_noop!(
+			Prices::set_twap_fallback(RuntimeOrigin::signed(CHARLIE), DOT_U, Some(fallback)),
+			BadOrigin
+		);
+		assert_noop!(
+			Prices::set_twap_fallback(RuntimeOrigin::signed(ALICE), DOT, Some(fallback)),
+			Error::<Test>::InvalidTwapAnchor
+		);
+		assert_ok!(Prices::set_twap_fallback(
+			RuntimeOrigin::signed(ALICE),
+			DOT_U,
+			Some(fallback)
+		));
+		System::assert_last_event(RuntimeEvent::Prices(crate::Event::TwapFallbackSet {
+			asset_id: DOT_U,
+			fallback: Some(fallback),
+		}));
+		assert_eq!(TwapFallbacks::<Test>::get(DOT_U), Some(fallback));
+
+		// no twap yet
+		assert_eq!(Prices::get_price(&DOT_U), None);
+
+		// 1 DOT_U (6 decimals) = 0.01 DOT (10 decimals), DOT = 100
+		MockTwap::set(Some(Price::saturating_from_integer(100)));
+		assert_eq!(
+			Prices::get_price(&DOT_U),
+			Some((Price::from_inner(1_000_000_000_000 * PRICE_ONE), 0))
+		);
+
+		// a tripped anchor gives no price
+		assert_ok!(Prices::set_circuit_breaker(
+			RuntimeOrigin::signed(ALICE),
+			DOT,
+			true
+		));
+		assert_eq!(Prices::get_price(&DOT_U), None);
+		assert_ok!(Prices::set_circuit_breaker(
+			RuntimeOrigin::signed(ALICE),
+			DOT,
+			false
+		));
+
+		// a tripped asset gives no price either
+		assert_ok!(Prices::set_circuit_breaker(
+			RuntimeOrigin::signed(ALICE),
+			DOT_U,
+			true
+		));
+		assert_eq!(Prices::get_price(&DOT_U), None);
+		assert_ok!(Prices::set_circuit_breaker(
+			RuntimeOrigin::signed(ALICE),
+			DOT_U,
+			false
+		));
+
+		assert_ok!(Prices::set_twap_fallback(
+			RuntimeOrigin::signed(ALICE),
+			DOT_U,
+			None
+		));
+		System::assert_last_event(RuntimeEvent::Prices(crate::Event::TwapFallbackSet {
+			asset_id: DOT_U,
+			fallback: None,
+		}));
+		assert_eq!(Prices::get_price(&DOT_U), None);
+	});
+}
+
+#[test]
+fn derived_price_is_guarded() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		// 1 VDOT = 1.2 DOT
+		MockTokenPool::set(1200 * PRICE_ONE);
+		MockFeed::set(Some((
+			DOT,
+			TimeStampedPrice {
+				value: Price::saturating_from_integer(100),
+				timestamp: 1_000,
+			},
+		)));
+		MockNow::set(1_000);
+		assert_eq!(
+			Prices::get_price(&VDOT),
+			Some((Price::from_inner(12_000_000_000 * PRICE_ONE), 1_000))
+		);
+
+		// a tripped asset gives no derived price on any read path
+		assert_ok!(Prices::set_circuit_breaker(
+			RuntimeOrigin::signed(ALICE),
+			VDOT,
+			true
+		));
+		assert_eq!(Prices::get_price(&VDOT), None);
+		assert_eq!(Prices::get_no_op(&VDOT), None);
+		assert_eq!(
+			Prices::get_oracle_amount_by_currency_and_amount_in(&VDOT, 100, &DOT),
+			None
+		);
+		assert_ok!(Prices::set_circuit_breaker(
+			RuntimeOrigin::signed(ALICE),
+			VDOT,
+			false
+		));
+		assert_eq!(
+			Prices::get_no_op(&VDOT).map(|price| price.timestamp),
+			Some(1_000)
+		);
+
+		// the derived price is as old as its underlying price
+		assert_ok!(Prices::set_price_guard(
+			RuntimeOrigin::signed(ALICE),
+			VDOT,
+			Some(PriceGuard {
+				max_price_age: 6_000,
+				max_deviation: Permill::from_percent(10),
+			})
+		));
+		MockNow::set(7_000);
+		assert!(Prices::get_price(&VDOT).is_some());
+		MockNow::set(7_001);
+		assert_eq!(Prices::get_price(&VDOT), None);
+		assert_eq!(Prices::get_no_op(&VDOT), None);
+	});
+}
+
+#[test]
+fn feeder_price_no_op_is_guarded() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert!(Prices::get_no_op(&DOT).is_some());
+		assert_ok!(Prices::set_circuit_breaker(
+			RuntimeOrigin::signed(ALICE),
+			DOT,
+			true
+		));
+		assert_eq!(Prices::get_no_op(&DOT), None);
+		assert_ok!(Prices::set_circuit_breaker(
+			RuntimeOrigin::signed(ALICE),
+			DOT,
+			false
+		));
+
+		assert_ok!(Prices::set_price_guard(
+			RuntimeOrigin::signed(ALICE),
+			DOT,
+			Some(PriceGuard {
+				max_price_age: 6_000,
+				max_deviation: Permill::from_percent(10),
+			})
+		));
+		MockNow::set(6_001);
+		assert_eq!(Prices::get_no_op(&DOT), None);
+	});
+}
+
+#[test]
+fn price_guards_are_capped() {
+	new_test_ext().execute_with(|| {
+		let guard = PriceGuard {
+			max_price_age: 6_000,
+			max_deviation: Permill::from_percent(10),
+		};
+		for i in 0..MAX_PRICE_GUARDS {
+			assert_ok!(Prices::set_price_guard(
+				RuntimeOrigin::signed(ALICE),
+				CurrencyId::ForeignAsset(i),
+				Some(guard)
+			));
+		}
+		assert_noop!(
+			Prices::set_price_guard(RuntimeOrigin::signed(ALICE), DOT, Some(guard)),
+			Error::<Test>::TooManyPriceGuards
+		);
+
+		// existing guards can still be updated or removed
+		assert_ok!(Prices::set_price_guard(
+			RuntimeOrigin::signed(ALICE),
+			CurrencyId::ForeignAsset(0),
+			Some(guard)
+		));
+		assert_ok!(Prices::set_price_guard(
+			RuntimeOrigin::signed(ALICE),
+			CurrencyId::ForeignAsset(0),
+			None
+		));
+		assert_ok!(Prices::set_price_guard(
+			RuntimeOrigin::signed(ALICE),
+			DOT,
+			Some(guard)
+		));
+		assert_eq!(PriceGuards::<Test>::count(), MAX_PRICE_GUARDS);
+	});
+}
+
+#[test]
+fn derived_price_does_not_fall_back_to_the_feeder_price() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		// 1 VDOT = 1.2 DOT, while the feeder price of VDOT is 150
+		MockTokenPool::set(1200 * PRICE_ONE);
+		MockFeed::set(Some((
+			DOT,
+			TimeStampedPrice {
+				value: Price::saturating_from_integer(100),
+				timestamp: 1_000,
+			},
+		)));
+		MockNow::set(1_000);
+		assert_eq!(
+			Prices::get_price(&VDOT),
+			Some((Price::from_inner(12_000_000_000 * PRICE_ONE), 1_000))
+		);
+
+		// the underlying asset is tripped
+		assert_ok!(Prices::set_circuit_breaker(
+			RuntimeOrigin::signed(ALICE),
+			DOT,
+			true
+		));
+		assert_eq!(Prices::get_price(&DOT), None);
+		assert_eq!(Prices::get_price(&VDOT), None);
+		assert_eq!(Prices::get_no_op(&VDOT), None);
+		assert_ok!(Prices::set_circuit_breaker(
+			RuntimeOrigin::signed(ALICE),
+			DOT,
+			false
+		));
+		assert!(Prices::get_price(&VDOT).is_some());
+
+		// the underlying price is stale
+		assert_ok!(Prices::set_price_guard(
+			RuntimeOrigin::signed(ALICE),
+			DOT,
+			Some(PriceGuard {
+				max_price_age: 6_000,
+				max_deviation: Permill::from_percent(10),
+			})
+		));
+		MockNow::set(7_001);
+		assert_eq!(Prices::get_price(&DOT), None);
+		assert_eq!(Prices::get_price(&VDOT), None);
+		assert_eq!(Prices::get_no_op(&VDOT), None);
+	});
+}
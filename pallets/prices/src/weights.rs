@@ -29,6 +29,10 @@ pub trait WeightInfo {
 	fn set_price() -> Weight;
 	fn reset_price() -> Weight;
 	fn set_foreign_asset() -> Weight;
+	fn set_price_guard() -> Weight;
+	fn set_circuit_breaker() -> Weight;
+	fn set_twap_fallback() -> Weight;
+	fn on_initialize(n: u32) -> Weight;
 }
 
 /// Weights for pallet_prices using the Substrate node and recommended hardware.
@@ -49,6 +53,63 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(1 as u64))
 			.saturating_add(T::DbWeight::get().writes(1 as u64))
 	}
+	/// Storage: Prices PriceGuards (r:1 w:1)
+	/// Proof Skipped: Prices PriceGuards (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Prices CounterForPriceGuards (r:1 w:1)
+	/// Proof: Prices CounterForPriceGuards (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	fn set_price_guard() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `76`
+		//  Estimated: `3541`
+		// Minimum execution time: 15_674_000 picoseconds.
+		Weight::from_parts(16_251_000 as u64, 3541)
+			.saturating_add(T::DbWeight::get().reads(2 as u64))
+			.saturating_add(T::DbWeight::get().writes(2 as u64))
+	}
+	/// Storage: Prices FeedStatuses (r:1 w:1)
+	/// Proof Skipped: Prices FeedStatuses (max_values: None, max_size: None, mode: Measured)
+	fn set_circuit_breaker() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `76`
+		//  Estimated: `3541`
+		// Minimum execution time: 13_962_000 picoseconds.
+		Weight::from_parts(14_430_000 as u64, 3541)
+			.saturating_add(T::DbWeight::get().reads(1 as u64))
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+	/// Storage: Prices TwapFallbacks (r:0 w:1)
+	/// Proof Skipped: Prices TwapFallbacks (max_values: None, max_size: None, mode: Measured)
+	fn set_twap_fallback() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 9_781_000 picoseconds.
+		Weight::from_parts(10_213_000 as u64, 0).saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+	/// Storage: Prices CounterForPriceGuards (r:1 w:0)
+	/// Proof: Prices CounterForPriceGuards (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	/// Storage: Prices PriceGuards (r:101 w:0)
+	/// Proof Skipped: Prices PriceGuards (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Oracle Values (r:100 w:0)
+	/// Proof Skipped: Oracle Values (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Prices AcceptedPrice (r:100 w:0)
+	/// Proof Skipped: Prices AcceptedPrice (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Prices FeedStatuses (r:100 w:100)
+	/// Proof Skipped: Prices FeedStatuses (max_values: None, max_size: None, mode: Measured)
+	/// The range of component `n` is `[0, 100]`.
+	fn on_initialize(n: u32) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `160 + n * (62 ±0)`
+		//  Estimated: `3625 + n * (2537 ±0)`
+		// Minimum execution time: 5_120_000 picoseconds.
+		Weight::from_parts(5_604_000 as u64, 3625)
+			// Standard Error: 9_417
+			.saturating_add(Weight::from_parts(21_306_000 as u64, 0).saturating_mul(n as u64))
+			.saturating_add(T::DbWeight::get().reads(2 as u64))
+			.saturating_add(T::DbWeight::get().reads((4 as u64).saturating_mul(n as u64)))
+			.saturating_add(T::DbWeight::get().writes((1 as u64).saturating_mul(n as u64)))
+			.saturating_add(Weight::from_parts(0, 2537).saturating_mul(n as u64))
+	}
 }
 
 // For backwards compatibility and tests
@@ -68,4 +129,62 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(1 as u64))
 			.saturating_add(RocksDbWeight::get().writes(1 as u64))
 	}
+	/// Storage: Prices PriceGuards (r:1 w:1)
+	/// Proof Skipped: Prices PriceGuards (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Prices CounterForPriceGuards (r:1 w:1)
+	/// Proof: Prices CounterForPriceGuards (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	fn set_price_guard() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `76`
+		//  Estimated: `3541`
+		// Minimum execution time: 15_674_000 picoseconds.
+		Weight::from_parts(16_251_000 as u64, 3541)
+			.saturating_add(RocksDbWeight::get().reads(2 as u64))
+			.saturating_add(RocksDbWeight::get().writes(2 as u64))
+	}
+	/// Storage: Prices FeedStatuses (r:1 w:1)
+	/// Proof Skipped: Prices FeedStatuses (max_values: None, max_size: None, mode: Measured)
+	fn set_circuit_breaker() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `76`
+		//  Estimated: `3541`
+		// Minimum execution time: 13_962_000 picoseconds.
+		Weight::from_parts(14_430_000 as u64, 3541)
+			.saturating_add(RocksDbWeight::get().reads(1 as u64))
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+	}
+	/// Storage: Prices TwapFallbacks (r:0 w:1)
+	/// Proof Skipped: Prices TwapFallbacks (max_values: None, max_size: None, mode: Measured)
+	fn set_twap_fallback() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 9_781_000 picoseconds.
+		Weight::from_parts(10_213_000 as u64, 0)
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+	}
+	/// Storage: Prices CounterForPriceGuards (r:1 w:0)
+	/// Proof: Prices CounterForPriceGuards (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	/// Storage: Prices PriceGuards (r:101 w:0)
+	/// Proof Skipped: Prices PriceGuards (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Oracle Values (r:100 w:0)
+	/// Proof Skipped: Oracle Values (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Prices AcceptedPrice (r:100 w:0)
+	/// Proof Skipped: Prices AcceptedPrice (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Prices FeedStatuses (r:100 w:100)
+	/// Proof Skipped: Prices FeedStatuses (max_values: None, max_size: None, mode: Measured)
+	/// The range of component `n` is `[0, 100]`.
+	fn on_initialize(n: u32) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `160 + n * (62 ±0)`
+		//  Estimated: `3625 + n * (2537 ±0)`
+		// Minimum execution time: 5_120_000 picoseconds.
+		Weight::from_parts(5_604_000 as u64, 3625)
+			// Standard Error: 9_417
+			.saturating_add(Weight::from_parts(21_306_000 as u64, 0).saturating_mul(n as u64))
+			.saturating_add(RocksDbWeight::get().reads(2 as u64))
+			.saturating_add(RocksDbWeight::get().reads((4 as u64).saturating_mul(n as u64)))
+			.saturating_add(RocksDbWeight::get().writes((1 as u64).saturating_mul(n as u64)))
+			.saturating_add(Weight::from_parts(0, 2537).saturating_mul(n as u64))
+	}
 }
@@ -346,6 +346,8 @@ impl bifrost_stable_asset::Config for Test {
 	type WeightInfo = ();
 	type ListingOrigin = EnsureSignedBy<CouncilAccount, AccountId>;
 	type EnsurePoolAssetId = EnsurePoolAssetId;
+	type PriceObservationPeriod = ConstU64<10>;
+	type MaxPriceObservations = ConstU32<24>;
 }
 
 impl bifrost_stable_pool::Config for Test {
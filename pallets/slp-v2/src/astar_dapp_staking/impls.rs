@@ -20,9 +20,7 @@ use crate::{
 	astar_dapp_staking::types::{
 		AstarCall, AstarDappStakingPendingStatus, AstarUnlockingRecord, AstarValidator, DappStaking,
 	},
-	common::types::{
-		Delegator, DelegatorIndex, Ledger, PendingStatus, StakingProtocol, Validator, XcmTask,
-	},
+	common::types::{Delegator, Ledger, PendingStatus, StakingProtocol, Validator, XcmTask},
 	Call, Config, ConfigurationByStakingProtocol, Error, Event,
 	LedgerByStakingProtocolAndDelegator, Pallet, PendingStatusByQueryId,
 	ValidatorsByStakingProtocolAndDelegator,
@@ -30,8 +28,8 @@ use crate::{
 use bifrost_primitives::VtokenMintingOperator;
 use frame_support::{dispatch::DispatchResultWithPostInfo, ensure};
 use parity_scale_codec::Encode;
-use sp_std::{cmp::Ordering, vec::Vec};
-use xcm::v4::{opaque::Xcm, Location, QueryId};
+use sp_std::cmp::Ordering;
+use xcm::v4::Location;
 
 pub const ASTAR_DAPP_STAKING: StakingProtocol = StakingProtocol::AstarDappStaking;
 
@@ -118,8 +116,18 @@ impl<T: Config> Pallet<T> {
 				None,
 			),
 		};
-		let (query_id, xcm_message) =
-			Self::get_query_id_and_xcm_message(call, delegator_index, &pending_status)?;
+		let notify_call = pending_status.as_ref().map(|_| {
+			<T as Config>::RuntimeCall::from(Call::<T>::notify_astar_dapp_staking {
+				query_id: 0,
+				response: Default::default(),
+			})
+		});
+		let (query_id, xcm_message) = Self::get_query_id_and_xcm_message(
+			&ASTAR_DAPP_STAKING,
+			call,
+			delegator_index,
+			notify_call,
+		)?;
 		if let Some(query_id) = query_id {
 			let pending_status = pending_status.clone().ok_or(Error::<T>::XcmFeeNotFound)?;
 			PendingStatusByQueryId::<T>::insert(query_id, pending_status.clone());
@@ -135,33 +143,6 @@ impl<T: Config> Pallet<T> {
 		Ok(().into())
 	}
 
-	pub fn get_query_id_and_xcm_message(
-		call: Vec<u8>,
-		delegator_index: DelegatorIndex,
-		pending_status: &Option<PendingStatus<T::AccountId>>,
-	) -> Result<(Option<QueryId>, Xcm), Error<T>> {
-		let call =
-			Self::wrap_utility_as_derivative_call_data(&ASTAR_DAPP_STAKING, delegator_index, call);
-		let mut query_id = None;
-		let xcm_message;
-		if pending_status.is_some() {
-			let notify_call =
-				<T as Config>::RuntimeCall::from(Call::<T>::notify_astar_dapp_staking {
-					query_id: 0,
-					response: Default::default(),
-				});
-			xcm_message = Self::wrap_xcm_message_with_notify(
-				&ASTAR_DAPP_STAKING,
-				call,
-				notify_call,
-				&mut query_id,
-			)?;
-		} else {
-			xcm_message = Self::wrap_xcm_message(&ASTAR_DAPP_STAKING, call)?;
-		};
-		Ok((query_id, xcm_message))
-	}
-
 	pub fn do_notify_astar_dapp_staking(
 		responder: Location,
 		pending_status: PendingStatus<T::AccountId>,
@@ -177,6 +158,7 @@ impl<T: Config> Pallet<T> {
 			PendingStatus::AstarDappStaking(AstarDappStakingPendingStatus::ClaimUnlocked(
 				delegator,
 			)) => delegator,
+			_ => return Err(Error::<T>::InvalidPendingStatus),
 		};
 		LedgerByStakingProtocolAndDelegator::<T>::mutate(
 			ASTAR_DAPP_STAKING,
@@ -224,6 +206,7 @@ impl<T: Config> Pallet<T> {
 								current_time_unit.cmp(&record.unlock_time) != Ordering::Greater
 							});
 						}
+						_ => return Err(Error::<T>::InvalidPendingStatus),
 					};
 					*ledger = Some(Ledger::AstarDappStaking(pending_ledger));
 				};
@@ -23,24 +23,26 @@ use crate::{
 		AstarDappStakingLedger, AstarDappStakingPendingStatus, AstarValidator, DappStaking,
 	},
 	common::types::{Ledger, PendingStatus, StakingProtocol, Validator, XcmFee},
+	nomination_pools::types::{NominationPools, NominationPoolsPendingStatus},
+	parachain_staking::types::{ParachainStaking, ParachainStakingPendingStatus},
 	Pallet as SlpV2,
 };
 use frame_benchmarking::v2::*;
 use frame_support::assert_ok;
 use frame_system::RawOrigin;
 use sp_core::{crypto::Ss58Codec, H160};
-use sp_runtime::{AccountId32 as AccountId, Permill};
+use sp_runtime::{AccountId32 as AccountId, Percent, Permill};
 use xcm::v4::MaybeErrorCode;
 
 pub const STAKING_PROTOCOL: StakingProtocol = StakingProtocol::AstarDappStaking;
 
-fn do_set_protocol_configuration<T: Config>()
+fn do_set_protocol_configuration<T: Config>(staking_protocol: StakingProtocol)
 where
 	<T as frame_system::Config>::AccountId: From<sp_runtime::AccountId32>,
 {
 	assert_ok!(SlpV2::<T>::set_protocol_configuration(
 		RawOrigin::Root.into(),
-		STAKING_PROTOCOL,
+		staking_protocol,
 		ProtocolConfiguration {
 			xcm_task_fee: XcmFee {
 				weight: Weight::zero(),
@@ -199,7 +201,7 @@ mod benchmarks {
 			STAKING_PROTOCOL,
 			None
 		));
-		do_set_protocol_configuration::<T>();
+		do_set_protocol_configuration::<T>(STAKING_PROTOCOL);
 		#[extrinsic_call]
 		_(RawOrigin::Root, STAKING_PROTOCOL, delegator, 1000);
 		Ok(())
@@ -241,7 +243,7 @@ mod benchmarks {
 			STAKING_PROTOCOL,
 			None
 		));
-		do_set_protocol_configuration::<T>();
+		do_set_protocol_configuration::<T>(STAKING_PROTOCOL);
 		let task = DappStaking::Lock(100);
 		#[extrinsic_call]
 		_(RawOrigin::Root, delegator, task);
@@ -260,7 +262,7 @@ mod benchmarks {
 			STAKING_PROTOCOL,
 			None
 		));
-		do_set_protocol_configuration::<T>();
+		do_set_protocol_configuration::<T>(STAKING_PROTOCOL);
 
 		PendingStatusByQueryId::<T>::insert(
 			0,
@@ -278,5 +280,105 @@ mod benchmarks {
 		Ok(())
 	}
 
+	#[benchmark]
+	fn parachain_staking() -> Result<(), BenchmarkError> {
+		let staking_protocol = StakingProtocol::MoonbeamParachainStaking;
+		assert_ok!(SlpV2::<T>::add_delegator(
+			RawOrigin::Root.into(),
+			staking_protocol,
+			None
+		));
+		let delegator = Delegator::Ethereum(SlpV2::<T>::derivative_ethereum_account_id(0).unwrap());
+		let candidate = H160::zero();
+		assert_ok!(SlpV2::<T>::add_validator(
+			RawOrigin::Root.into(),
+			staking_protocol,
+			delegator.clone(),
+			Validator::MoonbeamParachainStaking(candidate)
+		));
+		do_set_protocol_configuration::<T>(staking_protocol);
+		let task = ParachainStaking::DelegateWithAutoCompound(
+			candidate,
+			100,
+			Percent::from_percent(100),
+			100,
+			100,
+			100,
+		);
+		#[extrinsic_call]
+		_(RawOrigin::Root, delegator, task);
+		Ok(())
+	}
+
+	#[benchmark]
+	fn notify_parachain_staking() -> Result<(), BenchmarkError> {
+		let staking_protocol = StakingProtocol::MoonbeamParachainStaking;
+		assert_ok!(SlpV2::<T>::add_delegator(
+			RawOrigin::Root.into(),
+			staking_protocol,
+			None
+		));
+		let delegator = Delegator::Ethereum(SlpV2::<T>::derivative_ethereum_account_id(0).unwrap());
+		do_set_protocol_configuration::<T>(staking_protocol);
+
+		PendingStatusByQueryId::<T>::insert(
+			0,
+			PendingStatus::ParachainStaking(ParachainStakingPendingStatus::Delegate(
+				delegator.clone(),
+				H160::zero(),
+				100,
+			)),
+		);
+		#[extrinsic_call]
+		_(
+			RawOrigin::Root,
+			0,
+			xcm::v4::Response::DispatchResult(MaybeErrorCode::Success),
+		);
+		Ok(())
+	}
+
+	#[benchmark]
+	fn nomination_pools() -> Result<(), BenchmarkError> {
+		let staking_protocol = StakingProtocol::PolkadotStaking;
+		assert_ok!(SlpV2::<T>::add_delegator(
+			RawOrigin::Root.into(),
+			staking_protocol,
+			None
+		));
+		let delegator = DelegatorByStakingProtocolAndDelegatorIndex::<T>::get(staking_protocol, 0)
+			.ok_or(BenchmarkError::Weightless)?;
+		do_set_protocol_configuration::<T>(staking_protocol);
+		let task = NominationPools::Join(100, 1);
+		#[extrinsic_call]
+		_(RawOrigin::Root, delegator, task);
+		Ok(())
+	}
+
+	#[benchmark]
+	fn notify_nomination_pools() -> Result<(), BenchmarkError> {
+		let staking_protocol = StakingProtocol::PolkadotStaking;
+		assert_ok!(SlpV2::<T>::add_delegator(
+			RawOrigin::Root.into(),
+			staking_protocol,
+			None
+		));
+		let delegator = DelegatorByStakingProtocolAndDelegatorIndex::<T>::get(staking_protocol, 0)
+			.ok_or(BenchmarkError::Weightless)?;
+		do_set_protocol_configuration::<T>(staking_protocol);
+
+		PendingStatusByQueryId::<T>::insert(
+			0,
+			PendingStatus::NominationPools(NominationPoolsPendingStatus::Join(delegator, 100, 1)),
+		);
+		#[extrinsic_call]
+		_(
+			RawOrigin::Root,
+			0,
+			xcm::v4::Response::DispatchResult(MaybeErrorCode::Success),
+		);
+		Ok(())
+	}
+
 	impl_benchmark_test_suite!(SlpV2, crate::mock::new_test_ext(), crate::mock::Test);
 }
@@ -34,10 +34,12 @@ use frame_support::{
 use frame_system::pallet_prelude::OriginFor;
 use orml_traits::{MultiCurrency, XcmTransfer};
 use parity_scale_codec::{Decode, Encode};
-use sp_core::blake2_256;
+use polkadot_parachain_primitives::primitives::Sibling;
+use sp_core::{blake2_256, H160};
 use sp_runtime::{
-	helpers_128bit::multiply_by_rational_with_rounding, traits::TrailingZeroInput, DispatchError,
-	Rounding, Saturating,
+	helpers_128bit::multiply_by_rational_with_rounding,
+	traits::{AccountIdConversion, TrailingZeroInput},
+	DispatchError, Rounding, Saturating,
 };
 use sp_std::{vec, vec::Vec};
 use xcm::{
@@ -192,6 +194,18 @@ impl<T: Config> Pallet<T> {
 		Ok(account_id)
 	}
 
+	/// Implemented by Utility pallet to get derived account id of the sovereign account on an
+	/// ethereum compatible chain, e.g. Moonbeam.
+	pub fn derivative_ethereum_account_id(
+		delegator_index: DelegatorIndex,
+	) -> Result<H160, Error<T>> {
+		let sovereign_account: [u8; 20] =
+			Sibling::from(T::ParachainId::get()).into_account_truncating();
+		let entropy =
+			(b"modlpy/utilisuba", sovereign_account, delegator_index).using_encoded(blake2_256);
+		Ok(H160::from_slice(&entropy[0..20]))
+	}
+
 	/// Wrapping any runtime call with as_derivative.
 	pub fn wrap_utility_as_derivative_call_data(
 		staking_protocol: &StakingProtocol,
@@ -216,6 +230,7 @@ impl<T: Config> Pallet<T> {
 	) -> Result<Vec<u8>, Error<T>> {
 		let xcm_pallet_index = staking_protocol.info().xcm_pallet_index;
 		let bifrost_dest_location = staking_protocol.info().bifrost_dest_location;
+		let native_asset_location = staking_protocol.info().remote_fee_location;
 		let account_id = to
 			.encode()
 			.try_into()
@@ -239,7 +254,7 @@ impl<T: Config> Pallet<T> {
 		// native asset + amount
 		calldata.extend(
 			VersionedAssets::V4(Assets::from(vec![Asset {
-				id: AssetId(Location::here()),
+				id: AssetId(native_asset_location),
 				fun: Fungible(amount),
 			}]))
 			.encode(),
@@ -305,6 +320,28 @@ impl<T: Config> Pallet<T> {
 		Ok(xcm_message)
 	}
 
+	/// Wrapping the call with as_derivative, and with notify if `notify_call` is some.
+	pub fn get_query_id_and_xcm_message(
+		staking_protocol: &StakingProtocol,
+		call: Vec<u8>,
+		delegator_index: DelegatorIndex,
+		notify_call: Option<<T as Config>::RuntimeCall>,
+	) -> Result<(Option<QueryId>, Xcm), Error<T>> {
+		let call =
+			Self::wrap_utility_as_derivative_call_data(staking_protocol, delegator_index, call);
+		let mut query_id = None;
+		let xcm_message = match notify_call {
+			Some(notify_call) => Self::wrap_xcm_message_with_notify(
+				staking_protocol,
+				call,
+				notify_call,
+				&mut query_id,
+			)?,
+			None => Self::wrap_xcm_message(staking_protocol, call)?,
+		};
+		Ok((query_id, xcm_message))
+	}
+
 	pub fn send_xcm_message(
 		staking_protocol: StakingProtocol,
 		xcm_message: Xcm,
@@ -18,11 +18,15 @@
 
 use crate::{
 	common::types::{Delegator, DelegatorIndex, StakingProtocolInfo},
+	nomination_pools::types::{
+		NominationPools, NominationPoolsLedger, NominationPoolsPendingStatus,
+	},
+	parachain_staking::types::{
+		ParachainStaking, ParachainStakingLedger, ParachainStakingPendingStatus,
+	},
 	Config, Error,
 };
-use bifrost_primitives::{
-	Balance, BifrostKusamaChainId, MoonbeamChainId, MoonriverChainId, TimeUnit, KSM, MOVR,
-};
+use bifrost_primitives::{BifrostKusamaChainId, MoonriverChainId, TimeUnit, KSM, MOVR};
 use frame_support::traits::Get;
 use parity_scale_codec::{Decode, Encode, MaxEncodedLen};
 use polkadot_parachain_primitives::primitives::Sibling;
@@ -59,7 +63,7 @@ impl StakingProtocol {
 						key: Sibling::from(BifrostKusamaChainId::get()).into_account_truncating(),
 					}],
 				),
-				remote_dest_location: Location::new(1, [Parachain(MoonbeamChainId::get())]),
+				remote_dest_location: Location::new(1, [Parachain(MoonriverChainId::get())]),
 				bifrost_dest_location: Location::new(1, Parachain(BifrostKusamaChainId::get())),
 			},
 			StakingProtocol::KusamaStaking => StakingProtocolInfo {
@@ -112,16 +116,32 @@ impl StakingProtocol {
 
 	pub fn get_delegator<T: Config>(
 		&self,
-		_delegator_index: DelegatorIndex,
+		delegator_index: DelegatorIndex,
 	) -> Result<Delegator<T::AccountId>, Error<T>> {
 		match &self {
-			_ => unreachable!(),
+			StakingProtocol::MoonriverParachainStaking => {
+				let sub_sibling_account =
+					crate::Pallet::<T>::derivative_ethereum_account_id(delegator_index)?;
+				Ok(Delegator::Ethereum(sub_sibling_account))
+			}
+			StakingProtocol::KusamaStaking => {
+				let sub_account = crate::Pallet::<T>::derivative_account_id(
+					T::ParachainId::get().into_account_truncating(),
+					delegator_index,
+				)?;
+				Ok(Delegator::Substrate(sub_account))
+			}
 		}
 	}
 
 	pub fn get_default_ledger(&self) -> Ledger {
 		match self {
-			_ => unreachable!(),
+			StakingProtocol::MoonriverParachainStaking => {
+				Ledger::ParachainStaking(ParachainStakingLedger::default())
+			}
+			StakingProtocol::KusamaStaking => {
+				Ledger::NominationPools(NominationPoolsLedger::default())
+			}
 		}
 	}
 }
@@ -135,15 +155,21 @@ pub enum Validator<AccountId> {
 
 /// Ledger in slp protocol.
 #[derive(Encode, Decode, MaxEncodedLen, Clone, Debug, PartialEq, Eq, TypeInfo)]
-pub enum Ledger {}
+pub enum Ledger {
+	ParachainStaking(ParachainStakingLedger),
+	NominationPools(NominationPoolsLedger),
+}
 
 #[derive(Encode, Decode, MaxEncodedLen, Clone, Copy, Debug, PartialEq, Eq, TypeInfo)]
 pub enum XcmTask<AccountId> {
 	Todo(AccountId),
+	ParachainStaking(ParachainStaking),
+	NominationPools(NominationPools),
 }
 
 /// PendingStatus in slp protocol.
 #[derive(Encode, Decode, MaxEncodedLen, Clone, Copy, Debug, PartialEq, Eq, TypeInfo)]
 pub enum PendingStatus<AccountId> {
-	Todo(AccountId),
+	ParachainStaking(ParachainStakingPendingStatus<AccountId>),
+	NominationPools(NominationPoolsPendingStatus<AccountId>),
 }
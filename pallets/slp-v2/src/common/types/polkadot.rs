@@ -21,6 +21,12 @@ use crate::{
 		AstarDappStakingLedger, AstarDappStakingPendingStatus, AstarValidator, DappStaking,
 	},
 	common::types::{Delegator, DelegatorIndex, StakingProtocolInfo},
+	nomination_pools::types::{
+		NominationPools, NominationPoolsLedger, NominationPoolsPendingStatus,
+	},
+	parachain_staking::types::{
+		ParachainStaking, ParachainStakingLedger, ParachainStakingPendingStatus,
+	},
 	Config, Error,
 };
 use bifrost_primitives::{
@@ -157,6 +163,18 @@ impl StakingProtocol {
 				)?;
 				Ok(Delegator::Substrate(sub_sibling_account))
 			}
+			StakingProtocol::MoonbeamParachainStaking => {
+				let sub_sibling_account =
+					crate::Pallet::<T>::derivative_ethereum_account_id(delegator_index)?;
+				Ok(Delegator::Ethereum(sub_sibling_account))
+			}
+			StakingProtocol::PolkadotStaking => {
+				let sub_account = crate::Pallet::<T>::derivative_account_id(
+					T::ParachainId::get().into_account_truncating(),
+					delegator_index,
+				)?;
+				Ok(Delegator::Substrate(sub_account))
+			}
 			_ => Err(Error::<T>::UnsupportedStakingProtocol),
 		}
 	}
@@ -166,6 +184,12 @@ impl StakingProtocol {
 			StakingProtocol::AstarDappStaking => {
 				Ledger::AstarDappStaking(AstarDappStakingLedger::default())
 			}
+			StakingProtocol::MoonbeamParachainStaking => {
+				Ledger::ParachainStaking(ParachainStakingLedger::default())
+			}
+			StakingProtocol::PolkadotStaking => {
+				Ledger::NominationPools(NominationPoolsLedger::default())
+			}
 			_ => unreachable!(),
 		}
 	}
@@ -183,15 +207,21 @@ pub enum Validator<AccountId> {
 #[derive(Encode, Decode, MaxEncodedLen, Clone, Debug, PartialEq, Eq, TypeInfo)]
 pub enum Ledger {
 	AstarDappStaking(AstarDappStakingLedger),
+	ParachainStaking(ParachainStakingLedger),
+	NominationPools(NominationPoolsLedger),
 }
 
 #[derive(Encode, Decode, MaxEncodedLen, Clone, Copy, Debug, PartialEq, Eq, TypeInfo)]
 pub enum XcmTask<AccountId> {
 	AstarDappStaking(DappStaking<AccountId>),
+	ParachainStaking(ParachainStaking),
+	NominationPools(NominationPools),
 }
 
 /// PendingStatus in slp protocol.
 #[derive(Encode, Decode, MaxEncodedLen, Clone, Copy, Debug, PartialEq, Eq, TypeInfo)]
 pub enum PendingStatus<AccountId> {
 	AstarDappStaking(AstarDappStakingPendingStatus<AccountId>),
+	ParachainStaking(ParachainStakingPendingStatus<AccountId>),
+	NominationPools(NominationPoolsPendingStatus<AccountId>),
 }
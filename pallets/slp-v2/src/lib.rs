@@ -30,7 +30,9 @@ use frame_support::{
 	PalletId,
 };
 use frame_system::pallet_prelude::*;
+use nomination_pools::{impls::RELAYCHAIN_STAKING, types::NominationPools};
 use orml_traits::{MultiCurrency, XcmTransfer};
+use parachain_staking::{impls::PARACHAIN_STAKING, types::ParachainStaking};
 use polkadot_parachain_primitives::primitives::Id as ParaId;
 use sp_runtime::traits::AccountIdConversion;
 pub use weights::WeightInfo;
@@ -44,6 +46,9 @@ mod mock;
 #[cfg(feature = "polkadot")]
 mod astar_dapp_staking;
 mod common;
+pub mod migration;
+mod nomination_pools;
+mod parachain_staking;
 #[cfg(test)]
 mod tests;
 pub mod weights;
@@ -102,7 +107,10 @@ pub mod pallet {
 		type MaxValidators: Get<u32>;
 	}
 
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
 	#[pallet::pallet]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
 
 	/// Configuration for different staking protocols.
@@ -359,6 +367,26 @@ pub mod pallet {
 		InvalidParameter,
 		/// Not authorized.
 		NotAuthorized,
+		/// The maximum number of delegations has been reached.
+		DelegationsOverflow,
+		/// The delegation already exists.
+		DelegationAlreadyExists,
+		/// The delegation was not found.
+		DelegationNotFound,
+		/// The delegation already has a scheduled request.
+		DelegationRequestAlreadyExists,
+		/// The scheduled request of the delegation was not found.
+		DelegationRequestNotFound,
+		/// The scheduled request of the delegation is not executable yet.
+		DelegationRequestNotExecutable,
+		/// The pending status does not belong to the staking protocol.
+		InvalidPendingStatus,
+		/// The delegator has already joined a pool or bonded.
+		PoolAlreadyJoined,
+		/// The delegator has not joined any pool.
+		PoolNotJoined,
+		/// There is no unlocking chunk ready to be withdrawn.
+		NoUnlockedChunk,
 	}
 
 	#[pallet::hooks]
@@ -717,6 +745,14 @@ pub mod pallet {
 						astar_dapp_staking_ledger.add_lock_amount(amount);
 						Ok(())
 					}
+					Some(Ledger::ParachainStaking(parachain_staking_ledger)) => {
+						parachain_staking_ledger.add_rewards(amount);
+						Ok(())
+					}
+					Some(Ledger::NominationPools(nomination_pools_ledger)) => {
+						nomination_pools_ledger.add_active_amount(amount);
+						Ok(())
+					}
 					_ => Err(Error::<T>::LedgerNotFound),
 				},
 			)?;
@@ -777,5 +813,81 @@ pub mod pallet {
 			}
 			Ok(().into())
 		}
+
+		/// Manipulate a delegator to perform parachain staking related operations on
+		/// Moonbeam/Moonriver.
+		///
+		/// Can be called by governance or staking protocol operator.
+		///
+		/// Parameters
+		/// - `delegator`: Select the delegator which is existed.
+		/// - `task`: The parachain staking task.
+		#[pallet::call_index(12)]
+		#[pallet::weight(<T as Config>::WeightInfo::parachain_staking())]
+		pub fn parachain_staking(
+			origin: OriginFor<T>,
+			delegator: Delegator<T::AccountId>,
+			task: ParachainStaking,
+		) -> DispatchResultWithPostInfo {
+			Self::ensure_governance_or_operator(origin, PARACHAIN_STAKING)?;
+			Self::do_parachain_staking(delegator, task)
+		}
+
+		/// Processing Xcm message execution results of parachain staking.
+		///
+		/// Can be called by governance or xcm origin.
+		#[pallet::call_index(13)]
+		#[pallet::weight(<T as Config>::WeightInfo::notify_parachain_staking())]
+		pub fn notify_parachain_staking(
+			origin: OriginFor<T>,
+			query_id: QueryId,
+			response: Response,
+		) -> DispatchResultWithPostInfo {
+			let responder = Self::ensure_governance_or_xcm_response(origin)?;
+			let pending_status = PendingStatusByQueryId::<T>::take(query_id)
+				.ok_or(Error::<T>::PendingStatusNotFound)?;
+			if Response::DispatchResult(MaybeErrorCode::Success) == response {
+				Self::do_notify_parachain_staking(responder, pending_status)?;
+			}
+			Ok(().into())
+		}
+
+		/// Manipulate a delegator to perform nomination pools related operations on the relay
+		/// chain.
+		///
+		/// Can be called by governance or staking protocol operator.
+		///
+		/// Parameters
+		/// - `delegator`: Select the delegator which is existed.
+		/// - `task`: The nomination pools task.
+		#[pallet::call_index(14)]
+		#[pallet::weight(<T as Config>::WeightInfo::nomination_pools())]
+		pub fn nomination_pools(
+			origin: OriginFor<T>,
+			delegator: Delegator<T::AccountId>,
+			task: NominationPools,
+		) -> DispatchResultWithPostInfo {
+			Self::ensure_governance_or_operator(origin, RELAYCHAIN_STAKING)?;
+			Self::do_nomination_pools(delegator, task)
+		}
+
+		/// Processing Xcm message execution results of nomination pools.
+		///
+		/// Can be called by governance or xcm origin.
+		#[pallet::call_index(15)]
+		#[pallet::weight(<T as Config>::WeightInfo::notify_nomination_pools())]
+		pub fn notify_nomination_pools(
+			origin: OriginFor<T>,
+			query_id: QueryId,
+			response: Response,
+		) -> DispatchResultWithPostInfo {
+			let responder = Self::ensure_governance_or_xcm_response(origin)?;
+			let pending_status = PendingStatusByQueryId::<T>::take(query_id)
+				.ok_or(Error::<T>::PendingStatusNotFound)?;
+			if Response::DispatchResult(MaybeErrorCode::Success) == response {
+				Self::do_notify_nomination_pools(responder, pending_status)?;
+			}
+			Ok(().into())
+		}
 	}
 }
@@ -0,0 +1,268 @@
+// This file is part of Bifrost.
+
+// Copyright (C) Liebi Technologies PTE. LTD.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{
+	common::types::{Delegator, DelegatorIndex, Ledger},
+	nomination_pools::{impls::RELAYCHAIN_STAKING, types::NominationPoolsLedger},
+	Config, DelegatorByStakingProtocolAndDelegatorIndex,
+	DelegatorIndexByStakingProtocolAndDelegator, LedgerByStakingProtocolAndDelegator,
+	NextDelegatorIndexByStakingProtocol, Pallet,
+};
+use frame_support::{
+	pallet_prelude::*,
+	traits::{GetStorageVersion, OnRuntimeUpgrade},
+};
+use sp_runtime::traits::TrailingZeroInput;
+use sp_std::vec::Vec;
+
+/// Storage of slp-v1, which is the `Slp` pallet in the runtime.
+pub mod slp_v1 {
+	use super::*;
+	use bifrost_primitives::{Balance, CurrencyId, TimeUnit};
+	use xcm::v3::MultiLocation;
+
+	/// Unlocking chunk of the slp-v1 substrate ledger.
+	#[derive(Encode, Decode, Clone, Debug, PartialEq, Eq)]
+	pub struct UnlockChunk {
+		#[codec(compact)]
+		pub value: Balance,
+		pub unlock_time: TimeUnit,
+	}
+
+	/// Substrate ledger of slp-v1, which is used by the relay chain delegators.
+	#[derive(Encode, Decode, Clone, Debug, PartialEq, Eq)]
+	pub struct SubstrateLedger {
+		pub account: MultiLocation,
+		#[codec(compact)]
+		pub total: Balance,
+		#[codec(compact)]
+		pub active: Balance,
+		pub unlocking: Vec<UnlockChunk>,
+	}
+
+	/// Ledger of slp-v1, only the substrate variant is needed by the relay chain delegators.
+	#[derive(Encode, Decode, Clone, Debug, PartialEq, Eq)]
+	pub enum Ledger {
+		#[codec(index = 0)]
+		Substrate(SubstrateLedger),
+	}
+
+	#[frame_support::storage_alias]
+	pub type DelegatorLedgers = StorageDoubleMap<
+		Slp,
+		Blake2_128Concat,
+		CurrencyId,
+		Blake2_128Concat,
+		MultiLocation,
+		Ledger,
+		OptionQuery,
+	>;
+
+	#[frame_support::storage_alias]
+	pub type DelegatorsMultilocation2Index = StorageDoubleMap<
+		Slp,
+		Blake2_128Concat,
+		CurrencyId,
+		Blake2_128Concat,
+		MultiLocation,
+		u16,
+		OptionQuery,
+	>;
+
+	#[frame_support::storage_alias]
+	pub type DelegatorsIndex2Multilocation = StorageDoubleMap<
+		Slp,
+		Blake2_128Concat,
+		CurrencyId,
+		Blake2_128Concat,
+		u16,
+		MultiLocation,
+		OptionQuery,
+	>;
+
+	#[frame_support::storage_alias]
+	pub type ValidatorsByDelegator = StorageDoubleMap<
+		Slp,
+		Blake2_128Concat,
+		CurrencyId,
+		Blake2_128Concat,
+		MultiLocation,
+		Vec<MultiLocation>,
+		OptionQuery,
+	>;
+
+	#[frame_support::storage_alias]
+	pub type DelegatorLatestTuneRecord = StorageDoubleMap<
+		Slp,
+		Blake2_128Concat,
+		CurrencyId,
+		Blake2_128Concat,
+		MultiLocation,
+		TimeUnit,
+		OptionQuery,
+	>;
+}
+
+pub mod v1 {
+	use super::*;
+	use xcm::v3::{Junction, Junctions, MultiLocation};
+
+	const LOG_TARGET: &str = "runtime::slp-v2::migration";
+
+	/// Move the idle relay chain delegators of slp-v1 into slp-v2, keeping the delegator indexes,
+	/// so they can join a nomination pool. A delegator still bonded in the staking pallet can not
+	/// be driven by nomination pool calls, so it stays in slp-v1 until it is fully unbonded.
+	pub struct MigrateToV1<T>(sp_std::marker::PhantomData<T>);
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV1<T> {
+		fn on_runtime_upgrade() -> Weight {
+			let on_chain_storage_version = Pallet::<T>::on_chain_storage_version();
+			let in_code_storage_version = Pallet::<T>::in_code_storage_version();
+			if on_chain_storage_version == 0 && in_code_storage_version == 1 {
+				let weight_consumed = migrate_to_v1::<T>();
+				log::info!(target: LOG_TARGET, "Migrating slp-v2 storage to v1");
+				in_code_storage_version.put::<Pallet<T>>();
+				weight_consumed.saturating_add(T::DbWeight::get().writes(1))
+			} else {
+				log::warn!(target: LOG_TARGET, "slp-v2 migration should be removed.");
+				T::DbWeight::get().reads(1)
+			}
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<Vec<u8>, sp_runtime::DispatchError> {
+			let currency_id = RELAYCHAIN_STAKING.info().currency_id;
+			let ledger_count = slp_v1::DelegatorLedgers::iter_prefix(currency_id).count() as u64;
+			log::info!(target: LOG_TARGET, "slp-v1 ledgers before migration: {}", ledger_count);
+			let v2_ledger_count =
+				LedgerByStakingProtocolAndDelegator::<T>::iter_prefix(RELAYCHAIN_STAKING).count()
+					as u64;
+			Ok((ledger_count, v2_ledger_count).encode())
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(cnt: Vec<u8>) -> Result<(), sp_runtime::DispatchError> {
+			let (old_ledger_count, old_v2_ledger_count): (u64, u64) = Decode::decode(
+				&mut cnt.as_slice(),
+			)
+			.expect("the state parameter should be something that was generated by pre_upgrade");
+			let currency_id = RELAYCHAIN_STAKING.info().currency_id;
+			let ledger_count =
+				LedgerByStakingProtocolAndDelegator::<T>::iter_prefix(RELAYCHAIN_STAKING).count()
+					as u64;
+			log::info!(target: LOG_TARGET, "slp-v2 ledgers after migration: {}", ledger_count);
+
+			ensure!(
+				Pallet::<T>::on_chain_storage_version() == 1,
+				"on_chain_storage_version should be 1"
+			);
+			// The delegators still bonded are left in slp-v1.
+			let left_ledger_count =
+				slp_v1::DelegatorLedgers::iter_prefix(currency_id).count() as u64;
+			ensure!(
+				ledger_count.saturating_sub(old_v2_ledger_count)
+					== old_ledger_count.saturating_sub(left_ledger_count),
+				"LedgerByStakingProtocolAndDelegator post-migrate storage count not match"
+			);
+			Ok(())
+		}
+	}
+
+	/// The account of a relay chain delegator location in slp-v1.
+	pub fn account_of_location<T: Config>(location: &MultiLocation) -> Option<T::AccountId> {
+		match location {
+			MultiLocation {
+				parents: 1,
+				interior: Junctions::X1(Junction::AccountId32 { id, .. }),
+			} => T::AccountId::decode(&mut TrailingZeroInput::new(id.as_ref())).ok(),
+			_ => None,
+		}
+	}
+
+	/// Whether the slp-v1 ledger has nothing bonded or unlocking.
+	pub fn is_idle(ledger: &slp_v1::SubstrateLedger) -> bool {
+		ledger.total == 0 && ledger.active == 0 && ledger.unlocking.is_empty()
+	}
+
+	pub fn migrate_to_v1<T: Config>() -> Weight {
+		let mut weight = Weight::zero();
+		let currency_id = RELAYCHAIN_STAKING.info().currency_id;
+		let mut next_delegator_index: DelegatorIndex =
+			NextDelegatorIndexByStakingProtocol::<T>::get(RELAYCHAIN_STAKING);
+		weight.saturating_accrue(T::DbWeight::get().reads(1));
+
+		let v1_ledgers: Vec<(MultiLocation, slp_v1::Ledger)> =
+			slp_v1::DelegatorLedgers::iter_prefix(currency_id).collect();
+		for (location, v1_ledger) in v1_ledgers {
+			weight.saturating_accrue(T::DbWeight::get().reads(2));
+			let slp_v1::Ledger::Substrate(v1_ledger) = v1_ledger;
+			if !is_idle(&v1_ledger) {
+				log::warn!(
+					target: LOG_TARGET,
+					"Delegator still bonded, left in slp-v1: {:?}",
+					location
+				);
+				continue;
+			}
+			let (Some(account), Some(delegator_index)) = (
+				account_of_location::<T>(&location),
+				slp_v1::DelegatorsMultilocation2Index::get(currency_id, location),
+			) else {
+				log::error!(target: LOG_TARGET, "Invalid slp-v1 delegator: {:?}", location);
+				continue;
+			};
+			let delegator = Delegator::Substrate(account);
+			if DelegatorByStakingProtocolAndDelegatorIndex::<T>::contains_key(
+				RELAYCHAIN_STAKING,
+				delegator_index,
+			) || DelegatorIndexByStakingProtocolAndDelegator::<T>::contains_key(
+				RELAYCHAIN_STAKING,
+				&delegator,
+			) {
+				log::error!(target: LOG_TARGET, "Delegator already exists: {:?}", location);
+				continue;
+			}
+
+			slp_v1::DelegatorLedgers::remove(currency_id, location);
+			slp_v1::DelegatorsMultilocation2Index::remove(currency_id, location);
+			slp_v1::DelegatorsIndex2Multilocation::remove(currency_id, delegator_index);
+			slp_v1::ValidatorsByDelegator::remove(currency_id, location);
+			slp_v1::DelegatorLatestTuneRecord::remove(currency_id, location);
+
+			DelegatorByStakingProtocolAndDelegatorIndex::<T>::insert(
+				RELAYCHAIN_STAKING,
+				delegator_index,
+				delegator.clone(),
+			);
+			DelegatorIndexByStakingProtocolAndDelegator::<T>::insert(
+				RELAYCHAIN_STAKING,
+				delegator.clone(),
+				delegator_index,
+			);
+			LedgerByStakingProtocolAndDelegator::<T>::insert(
+				RELAYCHAIN_STAKING,
+				delegator,
+				Ledger::NominationPools(NominationPoolsLedger::default()),
+			);
+			next_delegator_index = next_delegator_index.max(delegator_index.saturating_add(1));
+			weight.saturating_accrue(T::DbWeight::get().reads_writes(2, 8));
+		}
+
+		NextDelegatorIndexByStakingProtocol::<T>::insert(RELAYCHAIN_STAKING, next_delegator_index);
+		weight.saturating_add(T::DbWeight::get().writes(1))
+	}
+}
@@ -0,0 +1,212 @@
+// This file is part of Bifrost.
+
+// Copyright (C) Liebi Technologies PTE. LTD.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{
+	common::types::{Delegator, Ledger, PendingStatus, StakingProtocol, XcmTask},
+	nomination_pools::types::{
+		BondExtra, NominationPools, NominationPoolsCall, NominationPoolsPendingStatus,
+		NominationPoolsUnlockChunk, RelaychainCall,
+	},
+	Call, Config, ConfigurationByStakingProtocol, Error, Event,
+	LedgerByStakingProtocolAndDelegator, Pallet, PendingStatusByQueryId,
+};
+use bifrost_primitives::VtokenMintingOperator;
+use frame_support::{dispatch::DispatchResultWithPostInfo, ensure};
+use parity_scale_codec::Encode;
+use sp_runtime::MultiAddress;
+use xcm::v4::Location;
+
+#[cfg(feature = "polkadot")]
+pub const RELAYCHAIN_STAKING: StakingProtocol = StakingProtocol::PolkadotStaking;
+#[cfg(feature = "kusama")]
+pub const RELAYCHAIN_STAKING: StakingProtocol = StakingProtocol::KusamaStaking;
+
+impl<T: Config> Pallet<T> {
+	pub fn do_nomination_pools(
+		delegator: Delegator<T::AccountId>,
+		task: NominationPools,
+	) -> DispatchResultWithPostInfo {
+		let delegator_index = Self::ensure_delegator_exist(&RELAYCHAIN_STAKING, &delegator)?;
+		let ledger =
+			match LedgerByStakingProtocolAndDelegator::<T>::get(RELAYCHAIN_STAKING, &delegator) {
+				Some(Ledger::NominationPools(ledger)) => ledger,
+				_ => return Err(Error::<T>::LedgerNotFound.into()),
+			};
+		let Delegator::Substrate(member) = delegator.clone() else {
+			return Err(Error::<T>::InvalidParameter.into());
+		};
+		let (call, pending_status) = match task {
+			NominationPools::Join(amount, pool_id) => {
+				// A bonded account can not join a pool.
+				ensure!(
+					ledger.pool_id.is_none() && ledger.active == 0,
+					Error::<T>::PoolAlreadyJoined
+				);
+				(
+					NominationPoolsCall::<T::AccountId>::Join(amount, pool_id),
+					Some(NominationPoolsPendingStatus::Join(
+						delegator.clone(),
+						amount,
+						pool_id,
+					)),
+				)
+			}
+			NominationPools::BondExtra(extra) => {
+				ensure!(ledger.pool_id.is_some(), Error::<T>::PoolNotJoined);
+				let pending_status = match extra {
+					BondExtra::FreeBalance(amount) => Some(
+						NominationPoolsPendingStatus::BondExtra(delegator.clone(), amount),
+					),
+					BondExtra::Rewards => None,
+				};
+				(
+					NominationPoolsCall::<T::AccountId>::BondExtra(extra),
+					pending_status,
+				)
+			}
+			NominationPools::ClaimPayout => {
+				ensure!(ledger.pool_id.is_some(), Error::<T>::PoolNotJoined);
+				(NominationPoolsCall::<T::AccountId>::ClaimPayout, None)
+			}
+			NominationPools::Unbond(amount) => {
+				ensure!(ledger.pool_id.is_some(), Error::<T>::PoolNotJoined);
+				ensure!(amount <= ledger.active, Error::<T>::InvalidParameter);
+				(
+					NominationPoolsCall::<T::AccountId>::Unbond(MultiAddress::Id(member), amount),
+					Some(NominationPoolsPendingStatus::Unbond(
+						delegator.clone(),
+						amount,
+					)),
+				)
+			}
+			NominationPools::WithdrawUnbonded(num_slashing_spans) => {
+				let current_time_unit =
+					T::VtokenMinting::get_ongoing_time_unit(RELAYCHAIN_STAKING.info().currency_id)
+						.ok_or(Error::<T>::TimeUnitNotFound)?;
+				ensure!(
+					ledger
+						.unlocking
+						.iter()
+						.any(|chunk| chunk.unlock_time <= current_time_unit),
+					Error::<T>::NoUnlockedChunk
+				);
+				(
+					NominationPoolsCall::<T::AccountId>::WithdrawUnbonded(
+						MultiAddress::Id(member),
+						num_slashing_spans,
+					),
+					Some(NominationPoolsPendingStatus::WithdrawUnbonded(
+						delegator.clone(),
+					)),
+				)
+			}
+		};
+		let pending_status = pending_status.map(PendingStatus::NominationPools);
+
+		let call = RelaychainCall::NominationPools(call).encode();
+		let notify_call = pending_status.as_ref().map(|_| {
+			<T as Config>::RuntimeCall::from(Call::<T>::notify_nomination_pools {
+				query_id: 0,
+				response: Default::default(),
+			})
+		});
+		let (query_id, xcm_message) = Self::get_query_id_and_xcm_message(
+			&RELAYCHAIN_STAKING,
+			call,
+			delegator_index,
+			notify_call,
+		)?;
+		if let Some(query_id) = query_id {
+			let pending_status = pending_status
+				.clone()
+				.ok_or(Error::<T>::PendingStatusNotFound)?;
+			PendingStatusByQueryId::<T>::insert(query_id, pending_status);
+		}
+		Self::send_xcm_message(RELAYCHAIN_STAKING, xcm_message)?;
+		Self::deposit_event(Event::<T>::SendXcmTask {
+			query_id,
+			delegator,
+			task: XcmTask::NominationPools(task),
+			pending_status,
+			dest_location: RELAYCHAIN_STAKING.info().remote_dest_location,
+		});
+		Ok(().into())
+	}
+
+	pub fn do_notify_nomination_pools(
+		responder: Location,
+		pending_status: PendingStatus<T::AccountId>,
+	) -> Result<(), Error<T>> {
+		let nomination_pools_pending_status = match pending_status.clone() {
+			PendingStatus::NominationPools(pending_status) => pending_status,
+			_ => return Err(Error::<T>::InvalidPendingStatus),
+		};
+		LedgerByStakingProtocolAndDelegator::<T>::mutate(
+			RELAYCHAIN_STAKING,
+			nomination_pools_pending_status.delegator(),
+			|ledger| -> Result<(), Error<T>> {
+				let Some(Ledger::NominationPools(pending_ledger)) = ledger else {
+					return Err(Error::<T>::LedgerNotFound);
+				};
+				let currency_id = RELAYCHAIN_STAKING.info().currency_id;
+				match nomination_pools_pending_status {
+					NominationPoolsPendingStatus::Join(_, amount, pool_id) => {
+						pending_ledger.pool_id = Some(pool_id);
+						pending_ledger.add_active_amount(amount);
+					}
+					NominationPoolsPendingStatus::BondExtra(_, amount) => {
+						pending_ledger.add_active_amount(amount);
+					}
+					NominationPoolsPendingStatus::Unbond(_, amount) => {
+						let current_time_unit =
+							T::VtokenMinting::get_ongoing_time_unit(currency_id)
+								.ok_or(Error::<T>::TimeUnitNotFound)?;
+						let configuration =
+							ConfigurationByStakingProtocol::<T>::get(RELAYCHAIN_STAKING)
+								.ok_or(Error::<T>::ConfigurationNotFound)?;
+						let unlock_time = current_time_unit
+							.add(configuration.unlock_period)
+							.ok_or(Error::<T>::TimeUnitNotFound)?;
+						pending_ledger.subtract_active_amount(amount);
+						pending_ledger
+							.unlocking
+							.try_push(NominationPoolsUnlockChunk {
+								amount,
+								unlock_time,
+							})
+							.map_err(|_| Error::<T>::UnlockRecordOverflow)?;
+					}
+					NominationPoolsPendingStatus::WithdrawUnbonded(_) => {
+						let current_time_unit =
+							T::VtokenMinting::get_ongoing_time_unit(currency_id)
+								.ok_or(Error::<T>::TimeUnitNotFound)?;
+						pending_ledger
+							.unlocking
+							.retain(|chunk| chunk.unlock_time > current_time_unit);
+					}
+				};
+				Ok(())
+			},
+		)?;
+		Self::deposit_event(Event::<T>::NotifyResponseReceived {
+			responder,
+			pending_status,
+		});
+		Ok(())
+	}
+}
@@ -0,0 +1,131 @@
+// This file is part of Bifrost.
+
+// Copyright (C) Liebi Technologies PTE. LTD.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::common::types::Delegator;
+use bifrost_primitives::{Balance, TimeUnit};
+use frame_support::{
+	pallet_prelude::{Decode, Encode, MaxEncodedLen, TypeInfo},
+	BoundedVec,
+};
+use sp_core::ConstU32;
+use sp_runtime::{MultiAddress, Saturating};
+
+/// Identifier of a nomination pool on the relay chain.
+pub type PoolId = u32;
+
+/// Maximum unlocking chunks of a pool member, same as `MaxUnbonding` on the relay chain.
+pub type MaxUnlockingChunks = ConstU32<32>;
+
+/// Extra bond of a pool member.
+#[derive(Encode, Decode, MaxEncodedLen, Clone, Copy, Debug, PartialEq, Eq, TypeInfo)]
+pub enum BondExtra {
+	/// Bond from the free balance of the member.
+	#[codec(index = 0)]
+	FreeBalance(Balance),
+	/// Bond the pending rewards of the member.
+	#[codec(index = 1)]
+	Rewards,
+}
+
+/// Nomination pools task of a pool member.
+#[derive(Encode, Decode, MaxEncodedLen, Clone, Copy, Debug, PartialEq, Eq, TypeInfo)]
+pub enum NominationPools {
+	/// Join the pool (amount, pool_id).
+	Join(Balance, PoolId),
+	BondExtra(BondExtra),
+	ClaimPayout,
+	/// Unbond the points, which are equal to the unbonded amount as long as the pool is not
+	/// slashed.
+	Unbond(Balance),
+	/// Withdraw the unlocked funds (num_slashing_spans).
+	WithdrawUnbonded(u32),
+}
+
+/// Nomination pools extrinsic call on the relay chain.
+#[derive(Encode, Decode, Debug, Clone)]
+pub enum NominationPoolsCall<AccountId> {
+	#[codec(index = 0)]
+	Join(#[codec(compact)] Balance, PoolId),
+	#[codec(index = 1)]
+	BondExtra(BondExtra),
+	#[codec(index = 2)]
+	ClaimPayout,
+	#[codec(index = 3)]
+	Unbond(MultiAddress<AccountId, ()>, #[codec(compact)] Balance),
+	#[codec(index = 5)]
+	WithdrawUnbonded(MultiAddress<AccountId, ()>, u32),
+}
+
+/// Relay chain extrinsic call.
+#[derive(Encode, Decode, Debug, Clone)]
+pub enum RelaychainCall<AccountId> {
+	#[cfg_attr(feature = "polkadot", codec(index = 39))]
+	#[cfg_attr(feature = "kusama", codec(index = 41))]
+	NominationPools(NominationPoolsCall<AccountId>),
+}
+
+/// Unlocking chunk of a pool member.
+#[derive(Encode, Decode, MaxEncodedLen, Clone, Debug, PartialEq, Eq, TypeInfo)]
+pub struct NominationPoolsUnlockChunk {
+	pub amount: Balance,
+	pub unlock_time: TimeUnit,
+}
+
+/// Nomination pools ledger of a pool member.
+#[derive(Encode, Decode, MaxEncodedLen, Clone, Debug, Default, PartialEq, Eq, TypeInfo)]
+pub struct NominationPoolsLedger {
+	/// The pool joined by the member, `None` if the member has not joined any pool.
+	pub pool_id: Option<PoolId>,
+	/// The amount bonded in the pool.
+	#[codec(compact)]
+	pub active: Balance,
+	/// Vector of all the unlocking chunks.
+	pub unlocking: BoundedVec<NominationPoolsUnlockChunk, MaxUnlockingChunks>,
+}
+
+impl NominationPoolsLedger {
+	/// Adds the specified amount to the active bonded amount.
+	pub fn add_active_amount(&mut self, amount: Balance) {
+		self.active.saturating_accrue(amount);
+	}
+
+	/// Subtracts the specified amount of the active bonded amount.
+	pub fn subtract_active_amount(&mut self, amount: Balance) {
+		self.active.saturating_reduce(amount);
+	}
+}
+
+/// PendingStatus in slp protocol.
+#[derive(Encode, Decode, MaxEncodedLen, Clone, Copy, Debug, PartialEq, Eq, TypeInfo)]
+pub enum NominationPoolsPendingStatus<AccountId> {
+	Join(Delegator<AccountId>, Balance, PoolId),
+	BondExtra(Delegator<AccountId>, Balance),
+	Unbond(Delegator<AccountId>, Balance),
+	WithdrawUnbonded(Delegator<AccountId>),
+}
+
+impl<AccountId: Clone> NominationPoolsPendingStatus<AccountId> {
+	pub fn delegator(&self) -> Delegator<AccountId> {
+		match self {
+			NominationPoolsPendingStatus::Join(delegator, ..)
+			| NominationPoolsPendingStatus::BondExtra(delegator, _)
+			| NominationPoolsPendingStatus::Unbond(delegator, _)
+			| NominationPoolsPendingStatus::WithdrawUnbonded(delegator) => delegator.clone(),
+		}
+	}
+}
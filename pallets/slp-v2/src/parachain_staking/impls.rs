@@ -0,0 +1,276 @@
+// This file is part of Bifrost.
+
+// Copyright (C) Liebi Technologies PTE. LTD.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{
+	common::types::{Delegator, Ledger, PendingStatus, StakingProtocol, Validator, XcmTask},
+	parachain_staking::types::{
+		MoonbeamCall, ParachainStaking, ParachainStakingDelegationAction,
+		ParachainStakingPendingStatus, ParachainStakingScheduledRequest,
+	},
+	Call, Config, ConfigurationByStakingProtocol, Error, Event,
+	LedgerByStakingProtocolAndDelegator, Pallet, PendingStatusByQueryId,
+	ValidatorsByStakingProtocolAndDelegator,
+};
+use bifrost_primitives::{TimeUnit, VtokenMintingOperator};
+use frame_support::{dispatch::DispatchResultWithPostInfo, ensure};
+use parity_scale_codec::Encode;
+use sp_core::H160;
+use sp_runtime::Saturating;
+use xcm::v4::Location;
+
+#[cfg(feature = "polkadot")]
+pub const PARACHAIN_STAKING: StakingProtocol = StakingProtocol::MoonbeamParachainStaking;
+#[cfg(feature = "kusama")]
+pub const PARACHAIN_STAKING: StakingProtocol = StakingProtocol::MoonriverParachainStaking;
+
+impl<T: Config> Pallet<T> {
+	pub fn ensure_parachain_staking_validator_exist(
+		delegator: Delegator<T::AccountId>,
+		validator: H160,
+	) -> DispatchResultWithPostInfo {
+		let validators =
+			ValidatorsByStakingProtocolAndDelegator::<T>::get(PARACHAIN_STAKING, delegator);
+		let is_exist = validators
+			.iter()
+			.any(|storage_validator| match storage_validator {
+				#[cfg(feature = "polkadot")]
+				Validator::MoonbeamParachainStaking(candidate) => *candidate == validator,
+				#[cfg(feature = "kusama")]
+				Validator::MoonriverParachainStaking(candidate) => *candidate == validator,
+				_ => false,
+			});
+		ensure!(is_exist, Error::<T>::ValidatorNotFound);
+		Ok(().into())
+	}
+
+	pub fn do_parachain_staking(
+		delegator: Delegator<T::AccountId>,
+		task: ParachainStaking,
+	) -> DispatchResultWithPostInfo {
+		let delegator_index = Self::ensure_delegator_exist(&PARACHAIN_STAKING, &delegator)?;
+		let ledger =
+			match LedgerByStakingProtocolAndDelegator::<T>::get(PARACHAIN_STAKING, &delegator) {
+				Some(Ledger::ParachainStaking(ledger)) => ledger,
+				_ => return Err(Error::<T>::LedgerNotFound.into()),
+			};
+		let pending_status = match task {
+			ParachainStaking::DelegateWithAutoCompound(validator, amount, ..) => {
+				Self::ensure_parachain_staking_validator_exist(delegator.clone(), validator)?;
+				ensure!(
+					ledger.get_delegation(&validator).is_none(),
+					Error::<T>::DelegationAlreadyExists
+				);
+				ParachainStakingPendingStatus::Delegate(delegator.clone(), validator, amount)
+			}
+			ParachainStaking::DelegatorBondMore(validator, amount) => {
+				ensure!(
+					ledger.get_delegation(&validator).is_some(),
+					Error::<T>::DelegationNotFound
+				);
+				ensure!(
+					ledger.get_request(&validator).is_none(),
+					Error::<T>::DelegationRequestAlreadyExists
+				);
+				ParachainStakingPendingStatus::DelegatorBondMore(
+					delegator.clone(),
+					validator,
+					amount,
+				)
+			}
+			ParachainStaking::ScheduleDelegatorBondLess(validator, amount) => {
+				let delegation = ledger
+					.get_delegation(&validator)
+					.ok_or(Error::<T>::DelegationNotFound)?;
+				ensure!(amount < delegation, Error::<T>::InvalidParameter);
+				ensure!(
+					ledger.get_request(&validator).is_none(),
+					Error::<T>::DelegationRequestAlreadyExists
+				);
+				ParachainStakingPendingStatus::ScheduleDelegatorBondLess(
+					delegator.clone(),
+					validator,
+					amount,
+				)
+			}
+			ParachainStaking::ScheduleRevokeDelegation(validator) => {
+				ensure!(
+					ledger.get_delegation(&validator).is_some(),
+					Error::<T>::DelegationNotFound
+				);
+				ensure!(
+					ledger.get_request(&validator).is_none(),
+					Error::<T>::DelegationRequestAlreadyExists
+				);
+				ParachainStakingPendingStatus::ScheduleRevokeDelegation(
+					delegator.clone(),
+					validator,
+				)
+			}
+			ParachainStaking::ExecuteDelegationRequest(delegator_account, validator) => {
+				ensure!(
+					delegator == Delegator::Ethereum(delegator_account),
+					Error::<T>::InvalidParameter
+				);
+				let request = ledger
+					.get_request(&validator)
+					.ok_or(Error::<T>::DelegationRequestNotFound)?;
+				let current_time_unit =
+					T::VtokenMinting::get_ongoing_time_unit(PARACHAIN_STAKING.info().currency_id)
+						.ok_or(Error::<T>::TimeUnitNotFound)?;
+				ensure!(
+					current_time_unit >= request.when_executable,
+					Error::<T>::DelegationRequestNotExecutable
+				);
+				ParachainStakingPendingStatus::ExecuteDelegationRequest(
+					delegator.clone(),
+					validator,
+				)
+			}
+			ParachainStaking::CancelDelegationRequest(validator) => {
+				ensure!(
+					ledger.get_request(&validator).is_some(),
+					Error::<T>::DelegationRequestNotFound
+				);
+				ParachainStakingPendingStatus::CancelDelegationRequest(delegator.clone(), validator)
+			}
+		};
+		let pending_status = PendingStatus::ParachainStaking(pending_status);
+
+		let call = MoonbeamCall::ParachainStaking(task).encode();
+		let notify_call = <T as Config>::RuntimeCall::from(Call::<T>::notify_parachain_staking {
+			query_id: 0,
+			response: Default::default(),
+		});
+		let (query_id, xcm_message) = Self::get_query_id_and_xcm_message(
+			&PARACHAIN_STAKING,
+			call,
+			delegator_index,
+			Some(notify_call),
+		)?;
+		if let Some(query_id) = query_id {
+			PendingStatusByQueryId::<T>::insert(query_id, pending_status.clone());
+		}
+		Self::send_xcm_message(PARACHAIN_STAKING, xcm_message)?;
+		Self::deposit_event(Event::<T>::SendXcmTask {
+			query_id,
+			delegator,
+			task: XcmTask::ParachainStaking(task),
+			pending_status: Some(pending_status),
+			dest_location: PARACHAIN_STAKING.info().remote_dest_location,
+		});
+		Ok(().into())
+	}
+
+	pub fn do_notify_parachain_staking(
+		responder: Location,
+		pending_status: PendingStatus<T::AccountId>,
+	) -> Result<(), Error<T>> {
+		let parachain_staking_pending_status = match pending_status.clone() {
+			PendingStatus::ParachainStaking(pending_status) => pending_status,
+			_ => return Err(Error::<T>::InvalidPendingStatus),
+		};
+		LedgerByStakingProtocolAndDelegator::<T>::mutate(
+			PARACHAIN_STAKING,
+			parachain_staking_pending_status.delegator(),
+			|ledger| -> Result<(), Error<T>> {
+				let Some(Ledger::ParachainStaking(pending_ledger)) = ledger else {
+					return Err(Error::<T>::LedgerNotFound);
+				};
+				match parachain_staking_pending_status {
+					ParachainStakingPendingStatus::Delegate(_, validator, amount)
+					| ParachainStakingPendingStatus::DelegatorBondMore(_, validator, amount) => {
+						pending_ledger.add_delegation_amount::<T>(validator, amount)?;
+					}
+					ParachainStakingPendingStatus::ScheduleDelegatorBondLess(
+						_,
+						validator,
+						amount,
+					) => {
+						pending_ledger
+							.requests
+							.try_push(ParachainStakingScheduledRequest {
+								validator,
+								when_executable: Self::get_parachain_staking_unlock_time()?,
+								action: ParachainStakingDelegationAction::Decrease(amount),
+							})
+							.map_err(|_| Error::<T>::DelegationsOverflow)?;
+						pending_ledger.less_total.saturating_accrue(amount);
+					}
+					ParachainStakingPendingStatus::ScheduleRevokeDelegation(_, validator) => {
+						let amount = pending_ledger
+							.get_delegation(&validator)
+							.ok_or(Error::<T>::DelegationNotFound)?;
+						pending_ledger
+							.requests
+							.try_push(ParachainStakingScheduledRequest {
+								validator,
+								when_executable: Self::get_parachain_staking_unlock_time()?,
+								action: ParachainStakingDelegationAction::Revoke(amount),
+							})
+							.map_err(|_| Error::<T>::DelegationsOverflow)?;
+						pending_ledger.less_total.saturating_accrue(amount);
+					}
+					ParachainStakingPendingStatus::ExecuteDelegationRequest(_, validator) => {
+						let request = pending_ledger
+							.get_request(&validator)
+							.ok_or(Error::<T>::DelegationRequestNotFound)?;
+						pending_ledger.requests.retain(|r| r.validator != validator);
+						pending_ledger
+							.less_total
+							.saturating_reduce(request.action.amount());
+						// A revoke removes the whole delegation, including the rewards added to it
+						// since the request was scheduled.
+						let amount = match request.action {
+							ParachainStakingDelegationAction::Revoke(_) => pending_ledger
+								.get_delegation(&validator)
+								.unwrap_or_default(),
+							ParachainStakingDelegationAction::Decrease(amount) => amount,
+						};
+						pending_ledger.subtract_delegation_amount(validator, amount);
+					}
+					ParachainStakingPendingStatus::CancelDelegationRequest(_, validator) => {
+						let request = pending_ledger
+							.get_request(&validator)
+							.ok_or(Error::<T>::DelegationRequestNotFound)?;
+						pending_ledger.requests.retain(|r| r.validator != validator);
+						pending_ledger
+							.less_total
+							.saturating_reduce(request.action.amount());
+					}
+				};
+				Ok(())
+			},
+		)?;
+		Self::deposit_event(Event::<T>::NotifyResponseReceived {
+			responder,
+			pending_status,
+		});
+		Ok(())
+	}
+
+	fn get_parachain_staking_unlock_time() -> Result<TimeUnit, Error<T>> {
+		let currency_id = PARACHAIN_STAKING.info().currency_id;
+		let current_time_unit = T::VtokenMinting::get_ongoing_time_unit(currency_id)
+			.ok_or(Error::<T>::TimeUnitNotFound)?;
+		let configuration = ConfigurationByStakingProtocol::<T>::get(PARACHAIN_STAKING)
+			.ok_or(Error::<T>::ConfigurationNotFound)?;
+		current_time_unit
+			.add(configuration.unlock_period)
+			.ok_or(Error::<T>::TimeUnitNotFound)
+	}
+}
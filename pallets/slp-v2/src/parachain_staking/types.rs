@@ -0,0 +1,185 @@
+// This file is part of Bifrost.
+
+// Copyright (C) Liebi Technologies PTE. LTD.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{common::types::Delegator, Config, Error};
+use bifrost_primitives::{Balance, TimeUnit};
+use frame_support::{
+	pallet_prelude::{Decode, Encode, MaxEncodedLen, TypeInfo},
+	BoundedVec,
+};
+use sp_core::{ConstU32, H160};
+use sp_runtime::{
+	helpers_128bit::multiply_by_rational_with_rounding, Percent, Rounding, Saturating,
+};
+
+/// Maximum delegations of a delegator, same as `MaxDelegationsPerDelegator` on Moonbeam.
+pub type MaxDelegations = ConstU32<100>;
+
+/// Parachain staking extrinsic call.
+#[derive(Encode, Decode, MaxEncodedLen, Clone, Copy, Debug, PartialEq, Eq, TypeInfo)]
+pub enum ParachainStaking {
+	/// Delegate (candidate, amount, auto_compound, candidate_delegation_count,
+	/// candidate_auto_compounding_delegation_count, delegation_count).
+	#[codec(index = 18)]
+	DelegateWithAutoCompound(H160, Balance, Percent, u32, u32, u32),
+	#[codec(index = 22)]
+	ScheduleRevokeDelegation(H160),
+	#[codec(index = 23)]
+	DelegatorBondMore(H160, Balance),
+	#[codec(index = 24)]
+	ScheduleDelegatorBondLess(H160, Balance),
+	/// Execute (delegator, candidate).
+	#[codec(index = 25)]
+	ExecuteDelegationRequest(H160, H160),
+	#[codec(index = 26)]
+	CancelDelegationRequest(H160),
+}
+
+/// Moonbeam/Moonriver extrinsic call.
+#[derive(Encode, Decode, Debug, Clone)]
+pub enum MoonbeamCall {
+	#[codec(index = 20)]
+	ParachainStaking(ParachainStaking),
+}
+
+/// Scheduled action of a delegation.
+#[derive(Encode, Decode, MaxEncodedLen, Clone, Copy, Debug, PartialEq, Eq, TypeInfo)]
+pub enum ParachainStakingDelegationAction {
+	Revoke(Balance),
+	Decrease(Balance),
+}
+
+impl ParachainStakingDelegationAction {
+	pub fn amount(&self) -> Balance {
+		match self {
+			ParachainStakingDelegationAction::Revoke(amount) => *amount,
+			ParachainStakingDelegationAction::Decrease(amount) => *amount,
+		}
+	}
+}
+
+/// Scheduled request of a delegation.
+#[derive(Encode, Decode, MaxEncodedLen, Clone, Copy, Debug, PartialEq, Eq, TypeInfo)]
+pub struct ParachainStakingScheduledRequest {
+	pub validator: H160,
+	pub when_executable: TimeUnit,
+	pub action: ParachainStakingDelegationAction,
+}
+
+/// Parachain staking ledger.
+#[derive(Encode, Decode, MaxEncodedLen, Clone, Debug, Default, PartialEq, Eq, TypeInfo)]
+pub struct ParachainStakingLedger {
+	/// Total amount delegated to all the validators.
+	pub total: Balance,
+	/// Total amount scheduled to leave the delegations.
+	pub less_total: Balance,
+	/// The delegations of the delegator.
+	pub delegations: BoundedVec<(H160, Balance), MaxDelegations>,
+	/// The scheduled requests, at most one for each validator.
+	pub requests: BoundedVec<ParachainStakingScheduledRequest, MaxDelegations>,
+}
+
+impl ParachainStakingLedger {
+	/// The delegated amount to `validator`.
+	pub fn get_delegation(&self, validator: &H160) -> Option<Balance> {
+		self.delegations
+			.iter()
+			.find(|(v, _)| v == validator)
+			.map(|(_, amount)| *amount)
+	}
+
+	/// The scheduled request of `validator`.
+	pub fn get_request(&self, validator: &H160) -> Option<ParachainStakingScheduledRequest> {
+		self.requests
+			.iter()
+			.find(|request| request.validator == *validator)
+			.copied()
+	}
+
+	/// Adds the specified amount to the delegation of `validator`.
+	pub fn add_delegation_amount<T: Config>(
+		&mut self,
+		validator: H160,
+		amount: Balance,
+	) -> Result<(), Error<T>> {
+		match self.delegations.iter_mut().find(|(v, _)| *v == validator) {
+			Some((_, delegation)) => delegation.saturating_accrue(amount),
+			None => self
+				.delegations
+				.try_push((validator, amount))
+				.map_err(|_| Error::<T>::DelegationsOverflow)?,
+		}
+		self.total.saturating_accrue(amount);
+		Ok(())
+	}
+
+	/// Subtracts the specified amount of the delegation of `validator`, and removes the
+	/// delegation if nothing is left.
+	pub fn subtract_delegation_amount(&mut self, validator: H160, amount: Balance) {
+		if let Some((_, delegation)) = self.delegations.iter_mut().find(|(v, _)| *v == validator) {
+			delegation.saturating_reduce(amount);
+		}
+		self.delegations.retain(|(_, delegation)| *delegation != 0);
+		self.total.saturating_reduce(amount);
+	}
+
+	/// Adds the compounded rewards to the delegations, pro rata to their amounts.
+	pub fn add_rewards(&mut self, amount: Balance) {
+		let total = self.total;
+		let mut remaining = amount;
+		if total != 0 {
+			for (_, delegation) in self.delegations.iter_mut() {
+				let reward =
+					multiply_by_rational_with_rounding(amount, *delegation, total, Rounding::Down)
+						.unwrap_or_default()
+						.min(remaining);
+				delegation.saturating_accrue(reward);
+				remaining.saturating_reduce(reward);
+			}
+			// The rounding dust goes to the first delegation.
+			if let Some((_, delegation)) = self.delegations.first_mut() {
+				delegation.saturating_accrue(remaining);
+			}
+		}
+		self.total.saturating_accrue(amount);
+	}
+}
+
+/// PendingStatus in slp protocol.
+#[derive(Encode, Decode, MaxEncodedLen, Clone, Copy, Debug, PartialEq, Eq, TypeInfo)]
+pub enum ParachainStakingPendingStatus<AccountId> {
+	Delegate(Delegator<AccountId>, H160, Balance),
+	DelegatorBondMore(Delegator<AccountId>, H160, Balance),
+	ScheduleDelegatorBondLess(Delegator<AccountId>, H160, Balance),
+	ScheduleRevokeDelegation(Delegator<AccountId>, H160),
+	ExecuteDelegationRequest(Delegator<AccountId>, H160),
+	CancelDelegationRequest(Delegator<AccountId>, H160),
+}
+
+impl<AccountId: Clone> ParachainStakingPendingStatus<AccountId> {
+	pub fn delegator(&self) -> Delegator<AccountId> {
+		match self {
+			ParachainStakingPendingStatus::Delegate(delegator, _, _)
+			| ParachainStakingPendingStatus::DelegatorBondMore(delegator, _, _)
+			| ParachainStakingPendingStatus::ScheduleDelegatorBondLess(delegator, _, _)
+			| ParachainStakingPendingStatus::ScheduleRevokeDelegation(delegator, _)
+			| ParachainStakingPendingStatus::ExecuteDelegationRequest(delegator, _)
+			| ParachainStakingPendingStatus::CancelDelegationRequest(delegator, _) => delegator.clone(),
+		}
+	}
+}
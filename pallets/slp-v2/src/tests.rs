@@ -25,22 +25,31 @@ use crate::{
 		Delegator, Ledger, PendingStatus, ProtocolConfiguration, StakingProtocol, Validator,
 		XcmFee, XcmTask,
 	},
+	migration::{slp_v1, v1::migrate_to_v1},
 	mock::*,
+	nomination_pools::types::{
+		BondExtra, NominationPools, NominationPoolsLedger, NominationPoolsPendingStatus,
+		NominationPoolsUnlockChunk,
+	},
+	parachain_staking::types::{
+		ParachainStaking, ParachainStakingDelegationAction, ParachainStakingLedger,
+		ParachainStakingScheduledRequest,
+	},
 	DelegatorByStakingProtocolAndDelegatorIndex, DelegatorIndexByStakingProtocolAndDelegator,
 	Error as SlpV2Error, Event as SlpV2Event, LastUpdateOngoingTimeUnitBlockNumber,
 	LedgerByStakingProtocolAndDelegator, NextDelegatorIndexByStakingProtocol,
 	ValidatorsByStakingProtocolAndDelegator,
 };
-use bifrost_primitives::{CommissionPalletId, TimeUnit, VtokenMintingOperator, VASTR};
+use bifrost_primitives::{CommissionPalletId, TimeUnit, VtokenMintingOperator, DOT, VASTR};
 use cumulus_primitives_core::Weight;
 use frame_support::{assert_noop, assert_ok, traits::fungibles::Mutate};
 use orml_traits::MultiCurrency;
 use pallet_xcm::Origin as XcmOrigin;
-use polkadot_parachain_primitives::primitives::Sibling;
+use polkadot_parachain_primitives::primitives::{Id as ParaId, Sibling};
 use sp_core::{bytes::to_hex, crypto::Ss58Codec, H160};
 use sp_runtime::{
 	helpers_128bit::multiply_by_rational_with_rounding, traits::AccountIdConversion, BoundedVec,
-	Permill, Rounding,
+	Percent, Permill, Rounding,
 };
 use xcm::{
 	latest::{MaybeErrorCode, Parent, Response},
@@ -1052,3 +1061,624 @@ fn update_token_exchange_rate_limt_error() {
 		);
 	})
 }
+
+fn set_parachain_staking_configuration() {
+	assert_ok!(SlpV2::set_protocol_configuration(
+		RuntimeOrigin::root(),
+		StakingProtocol::MoonbeamParachainStaking,
+		ProtocolConfiguration {
+			unlock_period: TimeUnit::Round(28),
+			..CONFIGURATION
+		}
+	));
+}
+
+#[test]
+fn parachain_staking_add_delegator() {
+	new_test_ext().execute_with(|| {
+		let staking_protocol = StakingProtocol::MoonbeamParachainStaking;
+		let delegator = Delegator::Ethereum(SlpV2::derivative_ethereum_account_id(0).unwrap());
+		assert_ok!(SlpV2::add_delegator(
+			RuntimeOrigin::root(),
+			staking_protocol,
+			None
+		));
+		assert_eq!(
+			DelegatorByStakingProtocolAndDelegatorIndex::<Test>::get(staking_protocol, 0),
+			Some(delegator.clone())
+		);
+		assert_eq!(
+			LedgerByStakingProtocolAndDelegator::<Test>::get(staking_protocol, delegator),
+			Some(Ledger::ParachainStaking(ParachainStakingLedger::default()))
+		);
+	})
+}
+
+#[test]
+fn parachain_staking_delegate_validator_not_found() {
+	new_test_ext().execute_with(|| {
+		let staking_protocol = StakingProtocol::MoonbeamParachainStaking;
+		let delegator = Delegator::Ethereum(SlpV2::derivative_ethereum_account_id(0).unwrap());
+		assert_ok!(SlpV2::add_delegator(
+			RuntimeOrigin::root(),
+			staking_protocol,
+			None
+		));
+		set_parachain_staking_configuration();
+
+		let task = ParachainStaking::DelegateWithAutoCompound(
+			H160::default(),
+			100,
+			Percent::from_percent(100),
+			100,
+			100,
+			100,
+		);
+		assert_noop!(
+			SlpV2::parachain_staking(RuntimeOrigin::root(), delegator, task),
+			SlpV2Error::<Test>::ValidatorNotFound
+		);
+	})
+}
+
+#[test]
+fn parachain_staking_delegate_and_bond_less() {
+	new_test_ext().execute_with(|| {
+		let staking_protocol = StakingProtocol::MoonbeamParachainStaking;
+		let delegator_account = SlpV2::derivative_ethereum_account_id(0).unwrap();
+		let delegator = Delegator::Ethereum(delegator_account);
+		let candidate = H160::default();
+		assert_ok!(SlpV2::add_delegator(
+			RuntimeOrigin::root(),
+			staking_protocol,
+			None
+		));
+		assert_ok!(SlpV2::add_validator(
+			RuntimeOrigin::root(),
+			staking_protocol,
+			delegator.clone(),
+			Validator::MoonbeamParachainStaking(candidate)
+		));
+		set_parachain_staking_configuration();
+
+		let task = ParachainStaking::DelegateWithAutoCompound(
+			candidate,
+			100,
+			Percent::from_percent(100),
+			100,
+			100,
+			100,
+		);
+		assert_ok!(SlpV2::parachain_staking(
+			RuntimeOrigin::root(),
+			delegator.clone(),
+			task
+		));
+		assert_ok!(SlpV2::notify_parachain_staking(
+			XcmOrigin::Response(Parent.into()).into(),
+			0,
+			Response::DispatchResult(MaybeErrorCode::Success)
+		));
+		assert_noop!(
+			SlpV2::parachain_staking(RuntimeOrigin::root(), delegator.clone(), task),
+			SlpV2Error::<Test>::DelegationAlreadyExists
+		);
+
+		RelaychainDataProvider::set_block_number(100);
+		assert_ok!(SlpV2::update_ongoing_time_unit(
+			RuntimeOrigin::root(),
+			staking_protocol,
+			Some(TimeUnit::Round(1))
+		));
+		assert_ok!(SlpV2::parachain_staking(
+			RuntimeOrigin::root(),
+			delegator.clone(),
+			ParachainStaking::ScheduleDelegatorBondLess(candidate, 40)
+		));
+		assert_ok!(SlpV2::notify_parachain_staking(
+			XcmOrigin::Response(Parent.into()).into(),
+			1,
+			Response::DispatchResult(MaybeErrorCode::Success)
+		));
+		assert_eq!(
+			LedgerByStakingProtocolAndDelegator::<Test>::get(staking_protocol, delegator.clone()),
+			Some(Ledger::ParachainStaking(ParachainStakingLedger {
+				total: 100,
+				less_total: 40,
+				delegations: BoundedVec::try_from(vec![(candidate, 100)]).unwrap(),
+				requests: BoundedVec::try_from(vec![ParachainStakingScheduledRequest {
+					validator: candidate,
+					when_executable: TimeUnit::Round(29),
+					action: ParachainStakingDelegationAction::Decrease(40),
+				}])
+				.unwrap(),
+			}))
+		);
+
+		let execute_task = ParachainStaking::ExecuteDelegationRequest(delegator_account, candidate);
+		assert_noop!(
+			SlpV2::parachain_staking(RuntimeOrigin::root(), delegator.clone(), execute_task),
+			SlpV2Error::<Test>::DelegationRequestNotExecutable
+		);
+
+		RelaychainDataProvider::set_block_number(200);
+		assert_ok!(SlpV2::update_ongoing_time_unit(
+			RuntimeOrigin::root(),
+			staking_protocol,
+			Some(TimeUnit::Round(29))
+		));
+		assert_ok!(SlpV2::parachain_staking(
+			RuntimeOrigin::root(),
+			delegator.clone(),
+			execute_task
+		));
+		assert_ok!(SlpV2::notify_parachain_staking(
+			XcmOrigin::Response(Parent.into()).into(),
+			2,
+			Response::DispatchResult(MaybeErrorCode::Success)
+		));
+		assert_eq!(
+			LedgerByStakingProtocolAndDelegator::<Test>::get(staking_protocol, delegator),
+			Some(Ledger::ParachainStaking(ParachainStakingLedger {
+				total: 60,
+				less_total: 0,
+				delegations: BoundedVec::try_from(vec![(candidate, 60)]).unwrap(),
+				requests: BoundedVec::default(),
+			}))
+		);
+	})
+}
+
+#[test]
+fn parachain_staking_revoke_should_remove_the_rewards() {
+	new_test_ext().execute_with(|| {
+		let staking_protocol = StakingProtocol::MoonbeamParachainStaking;
+		let delegator_account = SlpV2::derivative_ethereum_account_id(0).unwrap();
+		let delegator = Delegator::Ethereum(delegator_account);
+		let candidate = H160::default();
+		assert_ok!(SlpV2::add_delegator(
+			RuntimeOrigin::root(),
+			staking_protocol,
+			None
+		));
+		assert_ok!(SlpV2::add_validator(
+			RuntimeOrigin::root(),
+			staking_protocol,
+			delegator.clone(),
+			Validator::MoonbeamParachainStaking(candidate)
+		));
+		set_parachain_staking_configuration();
+
+		assert_ok!(SlpV2::parachain_staking(
+			RuntimeOrigin::root(),
+			delegator.clone(),
+			ParachainStaking::DelegateWithAutoCompound(
+				candidate,
+				100,
+				Percent::from_percent(100),
+				100,
+				100,
+				100,
+			)
+		));
+		assert_ok!(SlpV2::notify_parachain_staking(
+			XcmOrigin::Response(Parent.into()).into(),
+			0,
+			Response::DispatchResult(MaybeErrorCode::Success)
+		));
+
+		RelaychainDataProvider::set_block_number(100);
+		assert_ok!(SlpV2::update_ongoing_time_unit(
+			RuntimeOrigin::root(),
+			staking_protocol,
+			Some(TimeUnit::Round(1))
+		));
+		assert_ok!(SlpV2::parachain_staking(
+			RuntimeOrigin::root(),
+			delegator.clone(),
+			ParachainStaking::ScheduleRevokeDelegation(candidate)
+		));
+		assert_ok!(SlpV2::notify_parachain_staking(
+			XcmOrigin::Response(Parent.into()).into(),
+			1,
+			Response::DispatchResult(MaybeErrorCode::Success)
+		));
+
+		// The rewards compounded after the revoke was scheduled are revoked too.
+		LedgerByStakingProtocolAndDelegator::<Test>::mutate(
+			staking_protocol,
+			delegator.clone(),
+			|ledger| {
+				if let Some(Ledger::ParachainStaking(ledger)) = ledger {
+					ledger.add_rewards(20);
+				}
+			},
+		);
+		assert_eq!(
+			LedgerByStakingProtocolAndDelegator::<Test>::get(staking_protocol, delegator.clone()),
+			Some(Ledger::ParachainStaking(ParachainStakingLedger {
+				total: 120,
+				less_total: 100,
+				delegations: BoundedVec::try_from(vec![(candidate, 120)]).unwrap(),
+				requests: BoundedVec::try_from(vec![ParachainStakingScheduledRequest {
+					validator: candidate,
+					when_executable: TimeUnit::Round(29),
+					action: ParachainStakingDelegationAction::Revoke(100),
+				}])
+				.unwrap(),
+			}))
+		);
+
+		RelaychainDataProvider::set_block_number(200);
+		assert_ok!(SlpV2::update_ongoing_time_unit(
+			RuntimeOrigin::root(),
+			staking_protocol,
+			Some(TimeUnit::Round(29))
+		));
+		assert_ok!(SlpV2::parachain_staking(
+			RuntimeOrigin::root(),
+			delegator.clone(),
+			ParachainStaking::ExecuteDelegationRequest(delegator_account, candidate)
+		));
+		assert_ok!(SlpV2::notify_parachain_staking(
+			XcmOrigin::Response(Parent.into()).into(),
+			2,
+			Response::DispatchResult(MaybeErrorCode::Success)
+		));
+		assert_eq!(
+			LedgerByStakingProtocolAndDelegator::<Test>::get(staking_protocol, delegator),
+			Some(Ledger::ParachainStaking(ParachainStakingLedger::default()))
+		);
+	})
+}
+
+#[test]
+fn parachain_staking_ledger_add_rewards() {
+	let mut ledger = ParachainStakingLedger {
+		total: 300,
+		less_total: 0,
+		delegations: BoundedVec::try_from(vec![
+			(H160::from_low_u64_be(1), 100),
+			(H160::from_low_u64_be(2), 200),
+		])
+		.unwrap(),
+		requests: BoundedVec::default(),
+	};
+	ledger.add_rewards(100);
+	assert_eq!(ledger.total, 400);
+	assert_eq!(
+		ledger.delegations.into_inner(),
+		vec![
+			(H160::from_low_u64_be(1), 134),
+			(H160::from_low_u64_be(2), 266)
+		]
+	);
+}
+
+fn nomination_pools_delegator() -> Delegator<AccountId> {
+	Delegator::Substrate(
+		SlpV2::derivative_account_id(ParaId::from(2030).into_account_truncating(), 0).unwrap(),
+	)
+}
+
+fn set_nomination_pools_configuration() {
+	assert_ok!(SlpV2::set_protocol_configuration(
+		RuntimeOrigin::root(),
+		StakingProtocol::PolkadotStaking,
+		ProtocolConfiguration {
+			unlock_period: TimeUnit::Era(28),
+			..CONFIGURATION
+		}
+	));
+}
+
+#[test]
+fn nomination_pools_add_delegator() {
+	new_test_ext().execute_with(|| {
+		let staking_protocol = StakingProtocol::PolkadotStaking;
+		let delegator = nomination_pools_delegator();
+		assert_ok!(SlpV2::add_delegator(
+			RuntimeOrigin::root(),
+			staking_protocol,
+			None
+		));
+		assert_eq!(
+			DelegatorByStakingProtocolAndDelegatorIndex::<Test>::get(staking_protocol, 0),
+			Some(delegator.clone())
+		);
+		assert_eq!(
+			LedgerByStakingProtocolAndDelegator::<Test>::get(staking_protocol, delegator),
+			Some(Ledger::NominationPools(NominationPoolsLedger::default()))
+		);
+	})
+}
+
+#[test]
+fn nomination_pools_not_joined() {
+	new_test_ext().execute_with(|| {
+		let delegator = nomination_pools_delegator();
+		assert_ok!(SlpV2::add_delegator(
+			RuntimeOrigin::root(),
+			StakingProtocol::PolkadotStaking,
+			None
+		));
+		set_nomination_pools_configuration();
+
+		assert_noop!(
+			SlpV2::nomination_pools(
+				RuntimeOrigin::root(),
+				delegator.clone(),
+				NominationPools::BondExtra(BondExtra::FreeBalance(100))
+			),
+			SlpV2Error::<Test>::PoolNotJoined
+		);
+		assert_noop!(
+			SlpV2::nomination_pools(
+				RuntimeOrigin::root(),
+				delegator,
+				NominationPools::Unbond(100)
+			),
+			SlpV2Error::<Test>::PoolNotJoined
+		);
+	})
+}
+
+#[test]
+fn nomination_pools_join_unbond_withdraw() {
+	new_test_ext().execute_with(|| {
+		let staking_protocol = StakingProtocol::PolkadotStaking;
+		let delegator = nomination_pools_delegator();
+		assert_ok!(SlpV2::add_delegator(
+			RuntimeOrigin::root(),
+			staking_protocol,
+			None
+		));
+		set_nomination_pools_configuration();
+
+		assert_ok!(SlpV2::nomination_pools(
+			RuntimeOrigin::root(),
+			delegator.clone(),
+			NominationPools::Join(100, 1)
+		));
+		expect_event(SlpV2Event::SendXcmTask {
+			query_id: Some(0),
+			delegator: delegator.clone(),
+			task: XcmTask::NominationPools(NominationPools::Join(100, 1)),
+			pending_status: Some(PendingStatus::NominationPools(
+				NominationPoolsPendingStatus::Join(delegator.clone(), 100, 1),
+			)),
+			dest_location: Location::parent(),
+		});
+		assert_ok!(SlpV2::notify_nomination_pools(
+			XcmOrigin::Response(Parent.into()).into(),
+			0,
+			Response::DispatchResult(MaybeErrorCode::Success)
+		));
+		assert_noop!(
+			SlpV2::nomination_pools(
+				RuntimeOrigin::root(),
+				delegator.clone(),
+				NominationPools::Join(100, 2)
+			),
+			SlpV2Error::<Test>::PoolAlreadyJoined
+		);
+
+		RelaychainDataProvider::set_block_number(100);
+		assert_ok!(SlpV2::update_ongoing_time_unit(
+			RuntimeOrigin::root(),
+			staking_protocol,
+			Some(TimeUnit::Era(1))
+		));
+		assert_ok!(SlpV2::nomination_pools(
+			RuntimeOrigin::root(),
+			delegator.clone(),
+			NominationPools::Unbond(40)
+		));
+		assert_ok!(SlpV2::notify_nomination_pools(
+			XcmOrigin::Response(Parent.into()).into(),
+			1,
+			Response::DispatchResult(MaybeErrorCode::Success)
+		));
+		assert_eq!(
+			LedgerByStakingProtocolAndDelegator::<Test>::get(staking_protocol, delegator.clone()),
+			Some(Ledger::NominationPools(NominationPoolsLedger {
+				pool_id: Some(1),
+				active: 60,
+				unlocking: BoundedVec::try_from(vec![NominationPoolsUnlockChunk {
+					amount: 40,
+					unlock_time: TimeUnit::Era(29),
+				}])
+				.unwrap(),
+			}))
+		);
+		assert_noop!(
+			SlpV2::nomination_pools(
+				RuntimeOrigin::root(),
+				delegator.clone(),
+				NominationPools::WithdrawUnbonded(0)
+			),
+			SlpV2Error::<Test>::NoUnlockedChunk
+		);
+
+		RelaychainDataProvider::set_block_number(200);
+		assert_ok!(SlpV2::update_ongoing_time_unit(
+			RuntimeOrigin::root(),
+			staking_protocol,
+			Some(TimeUnit::Era(29))
+		));
+		assert_ok!(SlpV2::nomination_pools(
+			RuntimeOrigin::root(),
+			delegator.clone(),
+			NominationPools::WithdrawUnbonded(0)
+		));
+		assert_ok!(SlpV2::notify_nomination_pools(
+			XcmOrigin::Response(Parent.into()).into(),
+			2,
+			Response::DispatchResult(MaybeErrorCode::Success)
+		));
+		assert_eq!(
+			LedgerByStakingProtocolAndDelegator::<Test>::get(staking_protocol, delegator.clone()),
+			Some(Ledger::NominationPools(NominationPoolsLedger {
+				pool_id: Some(1),
+				active: 60,
+				unlocking: BoundedVec::default(),
+			}))
+		);
+
+		assert_ok!(SlpV2::nomination_pools(
+			RuntimeOrigin::root(),
+			delegator.clone(),
+			NominationPools::ClaimPayout
+		));
+		expect_event(SlpV2Event::SendXcmTask {
+			query_id: None,
+			delegator,
+			task: XcmTask::NominationPools(NominationPools::ClaimPayout),
+			pending_status: None,
+			dest_location: Location::parent(),
+		});
+	})
+}
+
+fn slp_v1_delegator(index: u16) -> (AccountId, xcm::v3::MultiLocation) {
+	let account_id: AccountId =
+		SlpV2::derivative_account_id(ParaId::from(2030).into_account_truncating(), index).unwrap();
+	let location = xcm::v3::MultiLocation {
+		parents: 1,
+		interior: xcm::v3::Junctions::X1(xcm::v3::Junction::AccountId32 {
+			network: None,
+			id: account_id.clone().into(),
+		}),
+	};
+	slp_v1::DelegatorsMultilocation2Index::insert(DOT, location, index);
+	slp_v1::DelegatorsIndex2Multilocation::insert(DOT, index, location);
+	(account_id, location)
+}
+
+#[test]
+fn migrate_slp_v1_delegator_ledgers() {
+	new_test_ext().execute_with(|| {
+		let staking_protocol = StakingProtocol::PolkadotStaking;
+		let (_, bonded_location) = slp_v1_delegator(3);
+		let bonded_ledger = slp_v1::Ledger::Substrate(slp_v1::SubstrateLedger {
+			account: bonded_location,
+			total: 150,
+			active: 100,
+			unlocking: vec![slp_v1::UnlockChunk {
+				value: 50,
+				unlock_time: TimeUnit::Era(10),
+			}],
+		});
+		slp_v1::DelegatorLedgers::insert(DOT, bonded_location, bonded_ledger.clone());
+		let (account_id, location) = slp_v1_delegator(4);
+		slp_v1::DelegatorLedgers::insert(
+			DOT,
+			location,
+			slp_v1::Ledger::Substrate(slp_v1::SubstrateLedger {
+				account: location,
+				total: 0,
+				active: 0,
+				unlocking: vec![],
+			}),
+		);
+		slp_v1::ValidatorsByDelegator::insert(DOT, location, vec![bonded_location]);
+
+		migrate_to_v1::<Test>();
+
+		// The bonded delegator is left in slp-v1.
+		assert_eq!(
+			slp_v1::DelegatorLedgers::get(DOT, bonded_location),
+			Some(bonded_ledger)
+		);
+		assert_eq!(
+			slp_v1::DelegatorsMultilocation2Index::get(DOT, bonded_location),
+			Some(3)
+		);
+		assert_eq!(
+			DelegatorByStakingProtocolAndDelegatorIndex::<Test>::get(staking_protocol, 3),
+			None
+		);
+
+		// The idle delegator is moved with its index.
+		let delegator = Delegator::Substrate(account_id);
+		assert_eq!(slp_v1::DelegatorLedgers::get(DOT, location), None);
+		assert_eq!(
+			slp_v1::DelegatorsMultilocation2Index::get(DOT, location),
+			None
+		);
+		assert_eq!(slp_v1::DelegatorsIndex2Multilocation::get(DOT, 4), None);
+		assert_eq!(slp_v1::ValidatorsByDelegator::get(DOT, location), None);
+		assert_eq!(
+			DelegatorByStakingProtocolAndDelegatorIndex::<Test>::get(staking_protocol, 4),
+			Some(delegator.clone())
+		);
+		assert_eq!(
+			DelegatorIndexByStakingProtocolAndDelegator::<Test>::get(
+				staking_protocol,
+				delegator.clone()
+			),
+			Some(4)
+		);
+		assert_eq!(
+			LedgerByStakingProtocolAndDelegator::<Test>::get(staking_protocol, delegator.clone()),
+			Some(Ledger::NominationPools(NominationPoolsLedger::default()))
+		);
+		assert_eq!(
+			NextDelegatorIndexByStakingProtocol::<Test>::get(staking_protocol),
+			5
+		);
+
+		// The migrated delegator can be bonded, unbonded and claimed.
+		set_nomination_pools_configuration();
+		assert_ok!(SlpV2::nomination_pools(
+			RuntimeOrigin::root(),
+			delegator.clone(),
+			NominationPools::Join(100, 1)
+		));
+		assert_ok!(SlpV2::notify_nomination_pools(
+			XcmOrigin::Response(Parent.into()).into(),
+			0,
+			Response::DispatchResult(MaybeErrorCode::Success)
+		));
+		RelaychainDataProvider::set_block_number(100);
+		assert_ok!(SlpV2::update_ongoing_time_unit(
+			RuntimeOrigin::root(),
+			staking_protocol,
+			Some(TimeUnit::Era(1))
+		));
+		assert_ok!(SlpV2::nomination_pools(
+			RuntimeOrigin::root(),
+			delegator.clone(),
+			NominationPools::Unbond(40)
+		));
+		assert_ok!(SlpV2::notify_nomination_pools(
+			XcmOrigin::Response(Parent.into()).into(),
+			1,
+			Response::DispatchResult(MaybeErrorCode::Success)
+		));
+		assert_eq!(
+			LedgerByStakingProtocolAndDelegator::<Test>::get(staking_protocol, delegator.clone()),
+			Some(Ledger::NominationPools(NominationPoolsLedger {
+				pool_id: Some(1),
+				active: 60,
+				unlocking: BoundedVec::try_from(vec![NominationPoolsUnlockChunk {
+					amount: 40,
+					unlock_time: TimeUnit::Era(29),
+				}])
+				.unwrap(),
+			}))
+		);
+		assert_ok!(SlpV2::nomination_pools(
+			RuntimeOrigin::root(),
+			delegator.clone(),
+			NominationPools::ClaimPayout
+		));
+		expect_event(SlpV2Event::SendXcmTask {
+			query_id: None,
+			delegator,
+			task: XcmTask::NominationPools(NominationPools::ClaimPayout),
+			pending_status: None,
+			dest_location: Location::parent(),
+		});
+	})
+}
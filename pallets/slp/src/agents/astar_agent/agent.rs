@@ -26,7 +26,7 @@ use crate::{
 	traits::{QueryResponseManager, StakingAgent},
 	AccountIdOf, BalanceOf, Config, DelegatorLedgerXcmUpdateQueue, DelegatorLedgers,
 	DelegatorsMultilocation2Index, LedgerUpdateEntry, MinimumsAndMaximums, Pallet, TimeUnit,
-	Validators,
+	Validators, XcmQueryCreatedAt,
 };
 use bifrost_primitives::{CurrencyId, VtokenMintingOperator, XcmOperationType, ASTR_TOKEN_ID};
 use core::marker::PhantomData;
@@ -34,7 +34,7 @@ use frame_support::{ensure, traits::Get};
 use frame_system::pallet_prelude::BlockNumberFor;
 use parity_scale_codec::Encode;
 use sp_runtime::{
-	traits::{CheckedAdd, CheckedSub, Convert, UniqueSaturatedInto, Zero},
+	traits::{BlockNumberProvider, CheckedAdd, CheckedSub, Convert, UniqueSaturatedInto, Zero},
 	DispatchResult,
 };
 use sp_std::prelude::*;
@@ -586,6 +586,16 @@ impl<T: Config>
 		Ok(())
 	}
 
+	fn slash_delegator_ledger(
+		&self,
+		_who: &MultiLocation,
+		_validator: &MultiLocation,
+		_amount: BalanceOf<T>,
+		_currency_id: CurrencyId,
+	) -> Result<(), Error<T>> {
+		Err(Error::<T>::Unsupported)
+	}
+
 	/// Remove an existing serving delegator for a particular currency.
 	fn remove_delegator(&self, who: &MultiLocation, currency_id: CurrencyId) -> DispatchResult {
 		// Get the delegator ledger
@@ -664,6 +674,7 @@ impl<T: Config>
 
 		// delete update entry
 		DelegatorLedgerXcmUpdateQueue::<T>::remove(query_id);
+		XcmQueryCreatedAt::<T>::remove(query_id);
 
 		// Deposit event.
 		Pallet::<T>::deposit_event(Event::DelegatorLedgerQueryResponseFailed { query_id });
@@ -791,6 +802,7 @@ impl<T: Config> AstarAgent<T> {
 
 		// Delete the DelegatorLedgerXcmUpdateQueue<T> query
 		DelegatorLedgerXcmUpdateQueue::<T>::remove(query_id);
+		XcmQueryCreatedAt::<T>::remove(query_id);
 
 		// Delete the query in pallet_xcm.
 		ensure!(
@@ -826,6 +838,7 @@ impl<T: Config> AstarAgent<T> {
 			unlock_time,
 		});
 		DelegatorLedgerXcmUpdateQueue::<T>::insert(query_id, (entry, timeout));
+		XcmQueryCreatedAt::<T>::insert(query_id, T::BlockNumberProvider::current_block_number());
 
 		Ok(())
 	}
@@ -17,16 +17,14 @@
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 use crate::{
 	pallet::Error,
-	primitives::{
-		ParachainStakingLedgerUpdateEntry, ParachainStakingLedgerUpdateOperation, TIMEOUT_BLOCKS,
-	},
+	primitives::{ParachainStakingLedgerUpdateEntry, ParachainStakingLedgerUpdateOperation},
 	traits::QueryResponseManager,
 	vec, AccountIdOf, BalanceOf, BlockNumberFor, BoundedVec, Config, CurrencyDelays,
 	DelegationsOccupied, DelegatorLatestTuneRecord, DelegatorLedgerXcmUpdateQueue,
 	DelegatorLedgers, DelegatorNextIndex, DelegatorsIndex2Multilocation,
 	DelegatorsMultilocation2Index, Encode, Event, FeeSources, Ledger, LedgerUpdateEntry,
-	MinimumsAndMaximums, Pallet, TimeUnit, Validators, Vec, Weight, XcmOperationType, Zero, ASTR,
-	BNC, DOT, GLMR, KSM, MANTA, MOVR, PHA,
+	MinimumsAndMaximums, Pallet, TimeUnit, Validators, ValidatorsMetrics, Vec, Weight,
+	XcmOperationType, XcmQueryCreatedAt, Zero, ASTR, BNC, DOT, GLMR, KSM, MANTA, MOVR, PHA,
 };
 use bifrost_primitives::{CurrencyId, VtokenMintingOperator, XcmDestWeightAndFeeHandler};
 use frame_support::{dispatch::GetDispatchInfo, ensure, traits::Len};
@@ -170,6 +168,7 @@ impl<T: Config> Pallet<T> {
 				}
 			}
 		});
+		ValidatorsMetrics::<T>::remove(currency_id, who);
 
 		Ok(())
 	}
@@ -367,7 +366,7 @@ impl<T: Config> Pallet<T> {
 	) -> Result<(QueryId, BlockNumberFor<T>, BalanceOf<T>, xcm::v4::Xcm<()>), Error<T>> {
 		// prepare the query_id for reporting back transact status
 		let now = T::BlockNumberProvider::current_block_number();
-		let timeout = BlockNumberFor::<T>::from(TIMEOUT_BLOCKS).saturating_add(now);
+		let timeout = T::XcmQueryTimeout::get().saturating_add(now);
 		let (query_id, notify_call_weight) =
 			Self::get_query_id_and_notify_call_weight(currency_id, &operation)?;
 
@@ -396,7 +395,7 @@ impl<T: Config> Pallet<T> {
 		operation: &XcmOperationType,
 	) -> Result<(QueryId, Weight), Error<T>> {
 		let now = T::BlockNumberProvider::current_block_number();
-		let timeout = BlockNumberFor::<T>::from(TIMEOUT_BLOCKS).saturating_add(now);
+		let timeout = T::XcmQueryTimeout::get().saturating_add(now);
 		let responder = Self::convert_currency_to_dest_location(currency_id)?;
 
 		let (notify_call_weight, callback_option) = match (currency_id, operation) {
@@ -509,6 +508,7 @@ impl<T: Config> Pallet<T> {
 			unlock_time,
 		});
 		DelegatorLedgerXcmUpdateQueue::<T>::insert(query_id, (entry, timeout));
+		XcmQueryCreatedAt::<T>::insert(query_id, T::BlockNumberProvider::current_block_number());
 
 		Ok(())
 	}
@@ -497,6 +497,16 @@ impl<T: Config>
 		Ok(())
 	}
 
+	fn slash_delegator_ledger(
+		&self,
+		_who: &MultiLocation,
+		_validator: &MultiLocation,
+		_amount: BalanceOf<T>,
+		_currency_id: CurrencyId,
+	) -> Result<(), Error<T>> {
+		Err(Error::<T>::Unsupported)
+	}
+
 	/// Remove an existing serving delegator for a particular currency.
 	fn remove_delegator(&self, who: &MultiLocation, currency_id: CurrencyId) -> DispatchResult {
 		// Get the delegator ledger
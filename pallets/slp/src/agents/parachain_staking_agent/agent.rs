@@ -28,7 +28,7 @@ use crate::{
 	traits::{QueryResponseManager, StakingAgent},
 	AccountIdOf, BalanceOf, Config, DelegatorLedgerXcmUpdateQueue, DelegatorLedgers,
 	DelegatorsMultilocation2Index, LedgerUpdateEntry, MinimumsAndMaximums, Pallet, TimeUnit,
-	Validators, ValidatorsByDelegatorUpdateEntry, BNC,
+	Validators, ValidatorsByDelegatorUpdateEntry, XcmQueryCreatedAt, BNC,
 };
 use bifrost_parachain_staking::ParachainStakingInterface;
 use bifrost_primitives::{
@@ -1603,6 +1603,86 @@ impl<T: Config>
 		Ok(())
 	}
 
+	/// Deduct the slashed amount from the delegation to the slashed validator. A scheduled
+	/// request for the validator is capped at what is left of the delegation, and a delegation
+	/// slashed to zero is removed together with its request.
+	fn slash_delegator_ledger(
+		&self,
+		who: &MultiLocation,
+		validator: &MultiLocation,
+		amount: BalanceOf<T>,
+		currency_id: CurrencyId,
+	) -> Result<(), Error<T>> {
+		let delegation_removed = DelegatorLedgers::<T>::mutate(
+			currency_id,
+			who,
+			|old_ledger| -> Result<bool, Error<T>> {
+				if let Some(Ledger::ParachainStaking(ref mut ledger)) = old_ledger {
+					let delegation = ledger
+						.delegations
+						.get(validator)
+						.ok_or(Error::<T>::ValidatorNotBonded)?;
+					let new_delegation = delegation
+						.checked_sub(&amount)
+						.ok_or(Error::<T>::SlashExceedsBonded)?;
+
+					ledger.total = ledger
+						.total
+						.checked_sub(&amount)
+						.ok_or(Error::<T>::SlashExceedsBonded)?;
+
+					let request_index = ledger
+						.requests
+						.iter()
+						.position(|rqst| rqst.validator == *validator);
+					if let Some((_, request_amount)) = ledger.request_briefs.get_mut(validator) {
+						let new_request_amount = (*request_amount).min(new_delegation);
+						let reduced_amount = request_amount
+							.checked_sub(&new_request_amount)
+							.ok_or(Error::<T>::UnderFlow)?;
+						ledger.less_total = ledger
+							.less_total
+							.checked_sub(&reduced_amount)
+							.ok_or(Error::<T>::UnderFlow)?;
+						*request_amount = new_request_amount;
+
+						let request = request_index
+							.and_then(|index| ledger.requests.get_mut(index))
+							.ok_or(Error::<T>::RequestNotExist)?;
+						request.action = match request.action {
+							OneToManyDelegationAction::Revoke(_) => {
+								OneToManyDelegationAction::Revoke(new_request_amount)
+							}
+							OneToManyDelegationAction::Decrease(_) => {
+								OneToManyDelegationAction::Decrease(new_request_amount)
+							}
+						};
+					}
+
+					if new_delegation.is_zero() {
+						ledger.delegations.remove(validator);
+						ledger.request_briefs.remove(validator);
+						if let Some(index) = request_index {
+							ledger.requests.remove(index);
+						}
+						Ok(true)
+					} else {
+						ledger.delegations.insert(*validator, new_delegation);
+						Ok(false)
+					}
+				} else {
+					Err(Error::<T>::DelegatorNotBonded)?
+				}
+			},
+		)?;
+
+		if delegation_removed {
+			Pallet::<T>::update_all_occupied_status_storage(currency_id)?;
+		}
+
+		Ok(())
+	}
+
 	/// Remove an existing serving delegator for a particular currency.
 	fn remove_delegator(&self, who: &MultiLocation, currency_id: CurrencyId) -> DispatchResult {
 		// Get the delegator ledger
@@ -1686,6 +1766,7 @@ impl<T: Config>
 
 		// delete update entry
 		DelegatorLedgerXcmUpdateQueue::<T>::remove(query_id);
+		XcmQueryCreatedAt::<T>::remove(query_id);
 
 		// Deposit event.
 		Pallet::<T>::deposit_event(Event::DelegatorLedgerQueryResponseFailed { query_id });
@@ -2034,6 +2115,7 @@ impl<T: Config> ParachainStakingAgent<T> {
 
 			// Delete the DelegatorLedgerXcmUpdateQueue<T> query
 			DelegatorLedgerXcmUpdateQueue::<T>::remove(query_id);
+			XcmQueryCreatedAt::<T>::remove(query_id);
 
 			// Delete the query in pallet_xcm.
 			T::SubstrateResponseManager::remove_query_record(query_id);
@@ -26,7 +26,7 @@ use crate::{
 	traits::{QueryResponseManager, StakingAgent},
 	AccountIdOf, BalanceOf, Config, CurrencyId, DelegatorLedgerXcmUpdateQueue, DelegatorLedgers,
 	DelegatorsMultilocation2Index, Hash, LedgerUpdateEntry, MinimumsAndMaximums, Pallet, TimeUnit,
-	Validators, ValidatorsByDelegatorUpdateEntry,
+	Validators, ValidatorsByDelegatorUpdateEntry, XcmQueryCreatedAt,
 };
 use bifrost_primitives::{TokenSymbol, VtokenMintingOperator, XcmOperationType};
 use core::marker::PhantomData;
@@ -35,7 +35,10 @@ use frame_system::pallet_prelude::BlockNumberFor;
 use parity_scale_codec::Encode;
 use sp_core::U256;
 use sp_runtime::{
-	traits::{CheckedAdd, CheckedSub, Convert, UniqueSaturatedFrom, UniqueSaturatedInto, Zero},
+	traits::{
+		BlockNumberProvider, CheckedAdd, CheckedSub, Convert, UniqueSaturatedFrom,
+		UniqueSaturatedInto, Zero,
+	},
 	DispatchResult, SaturatedConversion,
 };
 use sp_std::prelude::*;
@@ -773,6 +776,16 @@ impl<T: Config>
 		Ok(())
 	}
 
+	fn slash_delegator_ledger(
+		&self,
+		_who: &MultiLocation,
+		_validator: &MultiLocation,
+		_amount: BalanceOf<T>,
+		_currency_id: CurrencyId,
+	) -> Result<(), Error<T>> {
+		Err(Error::<T>::Unsupported)
+	}
+
 	/// Remove an existing serving delegator for a particular currency.
 	fn remove_delegator(&self, who: &MultiLocation, currency_id: CurrencyId) -> DispatchResult {
 		// Get the delegator ledger
@@ -855,6 +868,7 @@ impl<T: Config>
 
 		// delete update entry
 		DelegatorLedgerXcmUpdateQueue::<T>::remove(query_id);
+		XcmQueryCreatedAt::<T>::remove(query_id);
 
 		// Deposit event.
 		Pallet::<T>::deposit_event(Event::DelegatorLedgerQueryResponseFailed { query_id });
@@ -895,6 +909,7 @@ impl<T: Config> PhalaAgent<T> {
 			unlock_time,
 		});
 		DelegatorLedgerXcmUpdateQueue::<T>::insert(query_id, (entry, timeout));
+		XcmQueryCreatedAt::<T>::insert(query_id, T::BlockNumberProvider::current_block_number());
 
 		Ok(())
 	}
@@ -961,6 +976,7 @@ impl<T: Config> PhalaAgent<T> {
 
 		// Delete the DelegatorLedgerXcmUpdateQueue<T> query
 		DelegatorLedgerXcmUpdateQueue::<T>::remove(query_id);
+		XcmQueryCreatedAt::<T>::remove(query_id);
 
 		// Delete the query in pallet_xcm.
 		T::SubstrateResponseManager::remove_query_record(query_id);
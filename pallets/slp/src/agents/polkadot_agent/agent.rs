@@ -27,7 +27,7 @@ use crate::{
 	traits::{QueryResponseManager, StakingAgent},
 	AccountIdOf, BalanceOf, BoundedVec, Config, DelegatorLedgerXcmUpdateQueue, DelegatorLedgers,
 	DelegatorsMultilocation2Index, LedgerUpdateEntry, MinimumsAndMaximums, Pallet, TimeUnit,
-	ValidatorsByDelegator, ValidatorsByDelegatorXcmUpdateQueue,
+	ValidatorsByDelegator, ValidatorsByDelegatorXcmUpdateQueue, XcmQueryCreatedAt,
 };
 use bifrost_primitives::{
 	currency::KSM, CurrencyId, VtokenMintingOperator, XcmDestWeightAndFeeHandler, XcmOperationType,
@@ -38,8 +38,11 @@ use frame_support::{ensure, traits::Get};
 use frame_system::pallet_prelude::BlockNumberFor;
 use parity_scale_codec::Encode;
 use sp_runtime::{
-	traits::{CheckedAdd, CheckedSub, Convert, StaticLookup, UniqueSaturatedInto, Zero},
-	DispatchResult,
+	traits::{
+		BlockNumberProvider, CheckedAdd, CheckedSub, Convert, Saturating, StaticLookup,
+		UniqueSaturatedInto, Zero,
+	},
+	DispatchResult, Perquintill,
 };
 use sp_std::prelude::*;
 use xcm::{opaque::v3::MultiLocation, v3::prelude::*, VersionedAssets, VersionedLocation};
@@ -998,6 +1001,61 @@ impl<T: Config>
 		Ok(())
 	}
 
+	/// Spread the slashed amount over the active bonded amount and the unlocking chunks of the
+	/// delegator in proportion to their size, as the relay chain does.
+	fn slash_delegator_ledger(
+		&self,
+		who: &MultiLocation,
+		validator: &MultiLocation,
+		amount: BalanceOf<T>,
+		currency_id: CurrencyId,
+	) -> Result<(), Error<T>> {
+		// Ensure the validator is nominated by the delegator.
+		let validators = ValidatorsByDelegator::<T>::get(currency_id, who)
+			.ok_or(Error::<T>::ValidatorSetNotExist)?;
+		ensure!(
+			validators.contains(validator),
+			Error::<T>::NotDelegateValidator
+		);
+
+		DelegatorLedgers::<T>::mutate(currency_id, who, |old_ledger| -> Result<(), Error<T>> {
+			if let Some(Ledger::Substrate(ref mut old_sub_ledger)) = old_ledger {
+				ensure!(
+					amount <= old_sub_ledger.total,
+					Error::<T>::SlashExceedsBonded
+				);
+				let ratio = Perquintill::from_rational(amount, old_sub_ledger.total);
+
+				let mut remaining = amount;
+				for chunk in old_sub_ledger.unlocking.iter_mut() {
+					let chunk_slash = ratio.mul_floor(chunk.value).min(remaining);
+					chunk.value = chunk.value.saturating_sub(chunk_slash);
+					remaining = remaining.saturating_sub(chunk_slash);
+				}
+
+				// The active amount takes the rest, including what the rounding left over.
+				// Whatever exceeds it comes off the unlocking chunks in order.
+				let active_slash = remaining.min(old_sub_ledger.active);
+				old_sub_ledger.active = old_sub_ledger.active.saturating_sub(active_slash);
+				remaining = remaining.saturating_sub(active_slash);
+				for chunk in old_sub_ledger.unlocking.iter_mut() {
+					let chunk_slash = remaining.min(chunk.value);
+					chunk.value = chunk.value.saturating_sub(chunk_slash);
+					remaining = remaining.saturating_sub(chunk_slash);
+				}
+				ensure!(remaining.is_zero(), Error::<T>::SlashExceedsBonded);
+				old_sub_ledger
+					.unlocking
+					.retain(|chunk| !chunk.value.is_zero());
+
+				old_sub_ledger.total = old_sub_ledger.total.saturating_sub(amount);
+				Ok(())
+			} else {
+				Err(Error::<T>::DelegatorNotBonded)?
+			}
+		})
+	}
+
 	/// Remove an existing serving delegator for a particular currency.
 	fn remove_delegator(&self, who: &MultiLocation, currency_id: CurrencyId) -> DispatchResult {
 		// Get the delegator ledger
@@ -1095,6 +1153,7 @@ impl<T: Config>
 
 		// delete update entry
 		DelegatorLedgerXcmUpdateQueue::<T>::remove(query_id);
+		XcmQueryCreatedAt::<T>::remove(query_id);
 
 		// Deposit event.
 		Pallet::<T>::deposit_event(Event::DelegatorLedgerQueryResponseFailed { query_id });
@@ -1111,6 +1170,7 @@ impl<T: Config>
 
 		// delete update entry
 		ValidatorsByDelegatorXcmUpdateQueue::<T>::remove(query_id);
+		XcmQueryCreatedAt::<T>::remove(query_id);
 
 		// Deposit event.
 		Pallet::<T>::deposit_event(Event::ValidatorsByDelegatorQueryResponseFailed { query_id });
@@ -1257,6 +1317,7 @@ impl<T: Config> PolkadotAgent<T> {
 
 		// Delete the DelegatorLedgerXcmUpdateQueue<T> query
 		DelegatorLedgerXcmUpdateQueue::<T>::remove(query_id);
+		XcmQueryCreatedAt::<T>::remove(query_id);
 
 		// Delete the query in pallet_xcm.
 		T::SubstrateResponseManager::remove_query_record(query_id);
@@ -1290,6 +1351,7 @@ impl<T: Config> PolkadotAgent<T> {
 
 		// update ValidatorsByDelegatorXcmUpdateQueue<T> storage
 		ValidatorsByDelegatorXcmUpdateQueue::<T>::remove(query_id);
+		XcmQueryCreatedAt::<T>::remove(query_id);
 
 		Ok(())
 	}
@@ -1319,6 +1381,7 @@ impl<T: Config> PolkadotAgent<T> {
 			unlock_time,
 		});
 		DelegatorLedgerXcmUpdateQueue::<T>::insert(query_id, (entry, timeout));
+		XcmQueryCreatedAt::<T>::insert(query_id, T::BlockNumberProvider::current_block_number());
 
 		Ok(())
 	}
@@ -1339,6 +1402,7 @@ impl<T: Config> PolkadotAgent<T> {
 			},
 		);
 		ValidatorsByDelegatorXcmUpdateQueue::<T>::insert(query_id, (entry, timeout));
+		XcmQueryCreatedAt::<T>::insert(query_id, T::BlockNumberProvider::current_block_number());
 
 		Ok(())
 	}
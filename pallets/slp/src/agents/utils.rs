@@ -16,18 +16,25 @@
 // You should have received a copy of the GNU General Public License
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 use crate::{
-	blake2_256, pallet::Error, AccountIdOf, Config, Decode, DelegatorLedgerXcmUpdateQueue,
-	LedgerUpdateEntry, MinimumsAndMaximums, Pallet, TrailingZeroInput, Validators,
-	ValidatorsByDelegatorUpdateEntry, ValidatorsByDelegatorXcmUpdateQueue, ASTR, DOT, GLMR, H160,
-	KSM, MANTA, MOVR, PHA,
+	blake2_256,
+	pallet::{Error, Event},
+	primitives::{
+		ParachainStakingLedgerUpdateEntry, ParachainStakingLedgerUpdateOperation,
+		SubstrateLedgerUpdateEntry, SubstrateLedgerUpdateOperation,
+	},
+	AccountIdOf, BalanceOf, Config, Decode, DelegatorLedgerXcmUpdateQueue,
+	DelegatorLedgerXcmUpdateQueueCursor, DelegatorLedgers, Ledger, LedgerUpdateEntry,
+	MinimumsAndMaximums, Pallet, TrailingZeroInput, Validators, ValidatorsByDelegatorUpdateEntry,
+	ValidatorsByDelegatorXcmUpdateQueue, ValidatorsByDelegatorXcmUpdateQueueCursor, ASTR, DOT,
+	GLMR, H160, KSM, MANTA, MOVR, PHA,
 };
 use bifrost_primitives::{
 	AstarChainId, CurrencyId, MantaChainId, MoonbeamChainId, MoonriverChainId, PhalaChainId,
 };
-use frame_support::ensure;
+use frame_support::{ensure, weights::WeightMeter};
 use parity_scale_codec::Encode;
 use sp_core::Get;
-use sp_runtime::traits::BlockNumberProvider;
+use sp_runtime::traits::{BlockNumberProvider, Zero};
 use sp_std::prelude::*;
 use xcm::v3::{prelude::*, MultiLocation};
 
@@ -377,6 +384,173 @@ impl<T: Config> Pallet<T> {
 		Ok(())
 	}
 
+	/// Expire the ledger and validators update entries whose query response has not arrived
+	/// before the timeout, as far as the weight meter allows. Each queue scan resumes where the
+	/// previous one stopped.
+	pub(crate) fn expire_xcm_update_queues(meter: &mut WeightMeter) {
+		let db_weight = T::DbWeight::get();
+		// read the current block and both cursors, write both cursors back.
+		if meter.try_consume(db_weight.reads_writes(3, 2)).is_err() {
+			return;
+		}
+		let now = T::BlockNumberProvider::current_block_number();
+
+		let mut cursor = DelegatorLedgerXcmUpdateQueueCursor::<T>::get();
+		let iter = match cursor {
+			Some(query_id) => DelegatorLedgerXcmUpdateQueue::<T>::iter_from(
+				DelegatorLedgerXcmUpdateQueue::<T>::hashed_key_for(query_id),
+			),
+			None => DelegatorLedgerXcmUpdateQueue::<T>::iter(),
+		};
+		let mut out_of_weight = false;
+		for (query_id, (entry, timeout)) in iter {
+			if meter.try_consume(db_weight.reads(1)).is_err()
+				|| (now > timeout
+					&& Self::do_expire_delegator_ledger_query_response(query_id, entry, meter)
+						.is_err())
+			{
+				out_of_weight = true;
+				break;
+			}
+			cursor = Some(query_id);
+		}
+		// Start over from the beginning once the end of the queue is reached.
+		DelegatorLedgerXcmUpdateQueueCursor::<T>::set(cursor.filter(|_| out_of_weight));
+		if out_of_weight {
+			return;
+		}
+
+		let mut cursor = ValidatorsByDelegatorXcmUpdateQueueCursor::<T>::get();
+		let iter = match cursor {
+			Some(query_id) => ValidatorsByDelegatorXcmUpdateQueue::<T>::iter_from(
+				ValidatorsByDelegatorXcmUpdateQueue::<T>::hashed_key_for(query_id),
+			),
+			None => ValidatorsByDelegatorXcmUpdateQueue::<T>::iter(),
+		};
+		let mut out_of_weight = false;
+		for (query_id, (entry, timeout)) in iter {
+			if meter.try_consume(db_weight.reads(1)).is_err()
+				|| (now > timeout
+					&& Self::do_expire_validators_by_delegator_query_response(
+						query_id, entry, meter,
+					)
+					.is_err())
+			{
+				out_of_weight = true;
+				break;
+			}
+			cursor = Some(query_id);
+		}
+		ValidatorsByDelegatorXcmUpdateQueueCursor::<T>::set(cursor.filter(|_| out_of_weight));
+	}
+
+	/// Fail an expired ledger query through the staking agent of its currency. The first bond
+	/// of a delegator inserts an empty ledger before the remote chain confirms it, so that ledger
+	/// is removed again if nothing else is in flight for the delegator. Returns an error if the
+	/// weight meter runs out before the entry is handled.
+	pub(crate) fn do_expire_delegator_ledger_query_response(
+		query_id: QueryId,
+		entry: LedgerUpdateEntry<BalanceOf<T>>,
+		meter: &mut WeightMeter,
+	) -> Result<(), ()> {
+		let db_weight = T::DbWeight::get();
+		let first_bond = match &entry {
+			LedgerUpdateEntry::Substrate(SubstrateLedgerUpdateEntry {
+				currency_id,
+				delegator_id,
+				update_operation: SubstrateLedgerUpdateOperation::Bond,
+				..
+			})
+			| LedgerUpdateEntry::ParachainStaking(ParachainStakingLedgerUpdateEntry {
+				currency_id,
+				delegator_id,
+				update_operation: ParachainStakingLedgerUpdateOperation::Bond,
+				..
+			}) => Some((*currency_id, *delegator_id)),
+			_ => None,
+		};
+
+		let mut empty_ledger = first_bond;
+		if first_bond.is_some() {
+			for (other_query_id, (other_entry, _)) in DelegatorLedgerXcmUpdateQueue::<T>::iter() {
+				meter.try_consume(db_weight.reads(1))?;
+				if other_query_id != query_id
+					&& Self::ledger_update_entry_delegator(&other_entry) == first_bond
+				{
+					empty_ledger = None;
+					break;
+				}
+			}
+		}
+
+		// read and remove the update entry, remove the query record and creation block, read
+		// and rollback the ledger.
+		meter.try_consume(db_weight.reads_writes(2, 4))?;
+		if Self::do_fail_delegator_ledger_query_response(query_id).is_err() {
+			return Ok(());
+		}
+
+		if let Some((currency_id, delegator_id)) = empty_ledger {
+			DelegatorLedgers::<T>::mutate_exists(currency_id, delegator_id, |ledger| {
+				let is_empty = match ledger {
+					Some(Ledger::Substrate(substrate_ledger)) => {
+						substrate_ledger.total.is_zero()
+							&& substrate_ledger.active.is_zero()
+							&& substrate_ledger.unlocking.is_empty()
+					}
+					Some(Ledger::ParachainStaking(parachain_staking_ledger)) => {
+						parachain_staking_ledger.total.is_zero()
+							&& parachain_staking_ledger.delegations.is_empty()
+							&& parachain_staking_ledger.requests.is_empty()
+					}
+					_ => false,
+				};
+				if is_empty {
+					*ledger = None;
+				}
+			});
+		}
+
+		// Deposit event.
+		Self::deposit_event(Event::DelegatorLedgerQueryResponseExpired { query_id, entry });
+
+		Ok(())
+	}
+
+	/// Fail an expired validators by delegator query through the staking agent of its currency.
+	/// Returns an error if the weight meter runs out before the entry is handled.
+	pub(crate) fn do_expire_validators_by_delegator_query_response(
+		query_id: QueryId,
+		entry: ValidatorsByDelegatorUpdateEntry,
+		meter: &mut WeightMeter,
+	) -> Result<(), ()> {
+		// read and remove the update entry, remove the query record and creation block.
+		meter.try_consume(T::DbWeight::get().reads_writes(1, 3))?;
+		if Self::do_fail_validators_by_delegator_query_response(query_id).is_err() {
+			return Ok(());
+		}
+
+		// Deposit event.
+		Self::deposit_event(Event::ValidatorsByDelegatorQueryResponseExpired { query_id, entry });
+
+		Ok(())
+	}
+
+	fn ledger_update_entry_delegator(
+		entry: &LedgerUpdateEntry<BalanceOf<T>>,
+	) -> Option<(CurrencyId, MultiLocation)> {
+		match entry {
+			LedgerUpdateEntry::Substrate(substrate_entry) => {
+				Some((substrate_entry.currency_id, substrate_entry.delegator_id))
+			}
+			LedgerUpdateEntry::ParachainStaking(parachain_staking_entry) => Some((
+				parachain_staking_entry.currency_id,
+				parachain_staking_entry.delegator_id,
+			)),
+			_ => None,
+		}
+	}
+
 	pub fn derivative_account_id_20(who: [u8; 20], index: u16) -> H160 {
 		let entropy = (b"modlpy/utilisuba", who, index).using_encoded(blake2_256);
 		let sub_id: [u8; 20] = Decode::decode(&mut TrailingZeroInput::new(entropy.as_ref()))
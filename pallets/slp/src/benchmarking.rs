@@ -1350,6 +1350,167 @@ mod benchmarks {
 		Ok(())
 	}
 
+	#[benchmark]
+	fn report_slash() -> Result<(), BenchmarkError> {
+		let origin = <T as Config>::ControlOrigin::try_successful_origin()
+			.map_err(|_| BenchmarkError::Weightless)?;
+		set_mins_and_maxs::<T>(origin.clone());
+		init_ongoing_time::<T>(origin.clone());
+
+		assert_ok!(Pallet::<T>::increase_token_pool(
+			origin.clone() as <T as frame_system::Config>::RuntimeOrigin,
+			KSM,
+			1000u32.into()
+		));
+		CurrencyTuneExchangeRateLimit::<T>::insert(
+			KSM,
+			(1000u32, Permill::from_parts(100_0000u32)),
+		);
+
+		DelegatorLedgers::<T>::insert(
+			KSM,
+			DELEGATOR1,
+			Ledger::Substrate(SubstrateLedger {
+				account: DELEGATOR1,
+				total: 1000u32.into(),
+				active: 1000u32.into(),
+				unlocking: vec![],
+			}),
+		);
+		ValidatorsByDelegator::<T>::insert(
+			KSM,
+			DELEGATOR1,
+			BoundedVec::try_from(vec![DELEGATOR2]).unwrap(),
+		);
+		assert_ok!(Pallet::<T>::add_to_validator_boost_list(
+			origin.clone() as <T as frame_system::Config>::RuntimeOrigin,
+			KSM,
+			Box::new(DELEGATOR2)
+		));
+
+		#[extrinsic_call]
+		_(
+			origin as <T as frame_system::Config>::RuntimeOrigin,
+			KSM,
+			Box::new(DELEGATOR1),
+			Box::new(DELEGATOR2),
+			10u32.into(),
+		);
+
+		Ok(())
+	}
+
+	#[benchmark]
+	fn set_validator_metrics() -> Result<(), BenchmarkError> {
+		let origin = <T as Config>::ControlOrigin::try_successful_origin()
+			.map_err(|_| BenchmarkError::Weightless)?;
+		Validators::<T>::insert(KSM, BoundedVec::try_from(vec![DELEGATOR1]).unwrap());
+
+		#[extrinsic_call]
+		_(
+			origin as <T as frame_system::Config>::RuntimeOrigin,
+			KSM,
+			Box::new(DELEGATOR1),
+			Some(ValidatorMetrics {
+				commission: Permill::from_percent(5),
+				uptime: Permill::from_percent(99),
+			}),
+		);
+
+		Ok(())
+	}
+
+	#[benchmark]
+	fn set_validator_concentration_cap() -> Result<(), BenchmarkError> {
+		let origin = <T as Config>::ControlOrigin::try_successful_origin()
+			.map_err(|_| BenchmarkError::Weightless)?;
+
+		#[extrinsic_call]
+		_(
+			origin as <T as frame_system::Config>::RuntimeOrigin,
+			KSM,
+			Some(Permill::from_percent(50)),
+		);
+
+		Ok(())
+	}
+
+	#[benchmark]
+	fn rebalance_validators(n: Linear<1, 100>) -> Result<(), BenchmarkError> {
+		let origin = <T as Config>::ControlOrigin::try_successful_origin()
+			.map_err(|_| BenchmarkError::Weightless)?;
+		init_bond::<T>(origin.clone());
+		MinimumsAndMaximums::<T>::mutate(KSM, |mins_maxs| {
+			if let Some(mins_maxs) = mins_maxs {
+				mins_maxs.delegators_maximum = n as u16;
+			}
+		});
+
+		// Other delegators nominating the validator, all of them scanned for the delegated
+		// amounts.
+		for i in 1..n {
+			let delegator = MultiLocation {
+				parents: 1,
+				interior: X1(AccountId32 {
+					network: None,
+					id: [(i + 2) as u8; 32],
+				}),
+			};
+			DelegatorLedgers::<T>::insert(
+				KSM,
+				delegator,
+				Ledger::Substrate(SubstrateLedger {
+					account: delegator,
+					total: 1000u32.into(),
+					active: 1000u32.into(),
+					unlocking: vec![],
+				}),
+			);
+			ValidatorsByDelegator::<T>::insert(
+				KSM,
+				delegator,
+				BoundedVec::try_from(vec![DELEGATOR2]).unwrap(),
+			);
+		}
+
+		DelegatorLedgers::<T>::insert(
+			KSM,
+			DELEGATOR1,
+			Ledger::Substrate(SubstrateLedger {
+				account: DELEGATOR1,
+				total: 1000u32.into(),
+				active: 1000u32.into(),
+				unlocking: vec![],
+			}),
+		);
+		Validators::<T>::insert(KSM, BoundedVec::try_from(vec![DELEGATOR2]).unwrap());
+		ValidatorsMetrics::<T>::insert(
+			KSM,
+			DELEGATOR2,
+			ValidatorMetrics {
+				commission: Permill::from_percent(5),
+				uptime: Permill::from_percent(99),
+			},
+		);
+		ValidatorConcentrationCap::<T>::insert(KSM, Permill::from_percent(100));
+
+		T::XcmWeightAndFeeHandler::set_xcm_dest_weight_and_fee(
+			KSM,
+			XcmOperationType::Delegate,
+			Some((Weight::from_parts(4000000000, 100000), 0u32.into())),
+		)?;
+
+		#[extrinsic_call]
+		_(
+			origin as <T as frame_system::Config>::RuntimeOrigin,
+			KSM,
+			Box::new(DELEGATOR1),
+			Some((Weight::from_parts(4000000000, 100000), 100u32.into())),
+		);
+
+		Ok(())
+	}
+
 	//   `cargo test -p bifrost-slp --all-features`
 	impl_benchmark_test_suite!(
 		Pallet,
@@ -24,8 +24,8 @@ extern crate core;
 use crate::{agents::PolkadotAgent, Junction::GeneralIndex, Junctions::X2};
 pub use crate::{
 	primitives::{
-		Delays, LedgerUpdateEntry, MinimumsMaximums, QueryId, SubstrateLedger,
-		ValidatorsByDelegatorUpdateEntry,
+		Delays, LedgerUpdateEntry, MinimumsMaximums, QueryId, SlashRecord, SubstrateLedger,
+		ValidatorMetrics, ValidatorsByDelegatorUpdateEntry,
 	},
 	traits::{OnRefund, QueryResponseManager, StakingAgent},
 	Junction::AccountId32,
@@ -42,7 +42,11 @@ use bifrost_primitives::{
 };
 use bifrost_stable_pool::traits::StablePoolHandler;
 use cumulus_primitives_core::{relay_chain::HashT, ParaId};
-use frame_support::{pallet_prelude::*, traits::Contains, weights::Weight};
+use frame_support::{
+	pallet_prelude::*,
+	traits::Contains,
+	weights::{Weight, WeightMeter},
+};
 use frame_system::{
 	ensure_signed,
 	pallet_prelude::{BlockNumberFor, OriginFor},
@@ -64,6 +68,7 @@ mod mocks;
 pub mod primitives;
 mod tests;
 pub mod traits;
+mod validator_scoring;
 pub mod weights;
 
 #[cfg(feature = "runtime-benchmarks")]
@@ -97,7 +102,7 @@ pub mod pallet {
 	use frame_support::dispatch::GetDispatchInfo;
 	use orml_traits::XcmTransfer;
 	use pallet_xcm::ensure_response;
-	use sp_runtime::traits::BlockNumberProvider;
+	use sp_runtime::traits::{BlockNumberProvider, Saturating};
 	use xcm::v3::{MaybeErrorCode, Response};
 
 	#[pallet::config]
@@ -175,6 +180,10 @@ pub mod pallet {
 
 		#[pallet::constant]
 		type TreasuryAccount: Get<Self::AccountId>;
+
+		/// Number of blocks an XCM query may stay unanswered before its update entry expires.
+		#[pallet::constant]
+		type XcmQueryTimeout: Get<BlockNumberFor<Self>>;
 	}
 
 	#[pallet::error]
@@ -262,6 +271,13 @@ pub mod pallet {
 		ExceedLimit,
 		InvalidPageNumber,
 		NoMoreValidatorBoostListForCurrency,
+		DecreaseTokenPoolError,
+		SlashExceedsBonded,
+		ValidatorSlashed,
+		ValidatorConcentrationCapNotSet,
+		NoEligibleValidator,
+		NoRebalanceNeeded,
+		ExceedDelegatorsMaximum,
 	}
 
 	#[pallet::event]
@@ -528,6 +544,42 @@ pub mod pallet {
 			currency_id: CurrencyId,
 			amount: BalanceOf<T>,
 		},
+		DelegatorLedgerQueryResponseExpired {
+			#[codec(compact)]
+			query_id: QueryId,
+			entry: LedgerUpdateEntry<BalanceOf<T>>,
+		},
+		ValidatorsByDelegatorQueryResponseExpired {
+			#[codec(compact)]
+			query_id: QueryId,
+			entry: ValidatorsByDelegatorUpdateEntry,
+		},
+		/// A delegator has been slashed through a validator, and the slashed amount has been
+		/// deducted from the token pool, lowering the vtoken exchange rate.
+		DelegatorSlashed {
+			currency_id: CurrencyId,
+			delegator: MultiLocation,
+			validator: MultiLocation,
+			#[codec(compact)]
+			amount: BalanceOf<T>,
+			time_unit: TimeUnit,
+		},
+		ValidatorMetricsSet {
+			currency_id: CurrencyId,
+			validator: MultiLocation,
+			metrics: Option<ValidatorMetrics>,
+		},
+		ValidatorConcentrationCapSet {
+			currency_id: CurrencyId,
+			cap: Option<Permill>,
+		},
+		/// Delegation moves (from validator, to validator, amount) proposed for a delegator to
+		/// bring its validators back within the concentration cap.
+		RebalanceProposed {
+			currency_id: CurrencyId,
+			delegator_id: MultiLocation,
+			moves: Vec<(MultiLocation, MultiLocation, BalanceOf<T>)>,
+		},
 	}
 
 	/// The current storage version, we set to 3 our new version(after migrate stroage from vec t
@@ -632,6 +684,19 @@ pub mod pallet {
 		(LedgerUpdateEntry<BalanceOf<T>>, BlockNumberFor<T>),
 	>;
 
+	/// The block each outstanding ledger or validators by delegator update query was sent at.
+	#[pallet::storage]
+	pub type XcmQueryCreatedAt<T> = StorageMap<_, Blake2_128Concat, QueryId, BlockNumberFor<T>>;
+
+	/// The last ledger update query checked for expiry. The next scan resumes after it.
+	#[pallet::storage]
+	pub type DelegatorLedgerXcmUpdateQueueCursor<T> = StorageValue<_, QueryId>;
+
+	/// The last validators by delegator update query checked for expiry. The next scan resumes
+	/// after it.
+	#[pallet::storage]
+	pub type ValidatorsByDelegatorXcmUpdateQueueCursor<T> = StorageValue<_, QueryId>;
+
 	/// Minimum and Maximum constraints for different chains.
 	#[pallet::storage]
 	pub type MinimumsAndMaximums<T> =
@@ -684,13 +749,51 @@ pub mod pallet {
 	pub type SupplementFeeAccountWhitelist<T> =
 		StorageMap<_, Blake2_128Concat, CurrencyId, Vec<(MultiLocation, Hash<T>)>>;
 
+	/// Slash history of validators, oldest first.
+	/// Currency Id + Validator => slash records
+	#[pallet::storage]
+	pub type ValidatorSlashHistory<T> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		CurrencyId,
+		Blake2_128Concat,
+		MultiLocation,
+		BoundedVec<SlashRecord<BalanceOf<T>, BlockNumberFor<T>>, T::MaxLengthLimit>,
+		ValueQuery,
+	>;
+
+	/// Metrics of validators used to score them.
+	/// Currency Id + Validator => metrics
+	#[pallet::storage]
+	pub type ValidatorsMetrics<T> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		CurrencyId,
+		Blake2_128Concat,
+		MultiLocation,
+		ValidatorMetrics,
+		OptionQuery,
+	>;
+
+	/// The maximum share of the total delegated amount of a currency a single validator may
+	/// receive when rebalancing.
+	#[pallet::storage]
+	pub type ValidatorConcentrationCap<T> =
+		StorageMap<_, Blake2_128Concat, CurrencyId, Permill, OptionQuery>;
+
 	#[pallet::pallet]
 	#[pallet::without_storage_info]
 	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(PhantomData<T>);
 
 	#[pallet::hooks]
-	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {}
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_idle(_n: BlockNumberFor<T>, limit: Weight) -> Weight {
+			let mut meter = WeightMeter::with_limit(limit);
+			Self::expire_xcm_update_queues(&mut meter);
+			meter.consumed()
+		}
+	}
 
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
@@ -1263,38 +1366,9 @@ pub mod pallet {
 			// Ensure the value is valid.
 			ensure!(value > Zero::zero(), Error::<T>::AmountZero);
 
-			// Ensure the value is valid.
-			let (limit_num, max_permill) = CurrencyTuneExchangeRateLimit::<T>::get(currency_id)
-				.ok_or(Error::<T>::TuneExchangeRateLimitNotSet)?;
-			// Get pool token value
-			let pool_token = T::VtokenMinting::get_token_pool(currency_id);
-			// Calculate max increase allowed.
-			let max_to_increase = max_permill.mul_floor(pool_token);
-			ensure!(value <= max_to_increase, Error::<T>::GreaterThanMaximum);
-
 			// Ensure this tune is within limit.
-			// Get current TimeUnit.
-			let current_time_unit = T::VtokenMinting::get_ongoing_time_unit(currency_id)
-				.ok_or(Error::<T>::TimeUnitNotExist)?;
-			// If this is the first time.
-			if !CurrencyLatestTuneRecord::<T>::contains_key(currency_id) {
-				// Insert an empty record into CurrencyLatestTuneRecord storage.
-				CurrencyLatestTuneRecord::<T>::insert(currency_id, (current_time_unit.clone(), 0));
-			}
-
-			// Get CurrencyLatestTuneRecord for the currencyId.
-			let (latest_time_unit, tune_num) = CurrencyLatestTuneRecord::<T>::get(currency_id)
-				.ok_or(Error::<T>::CurrencyLatestTuneRecordNotExist)?;
-
-			// See if exceeds tuning limit.
-			// If it has been tuned in the current time unit, ensure this tuning is within limit.
-			let mut new_tune_num = Zero::zero();
-			if latest_time_unit == current_time_unit {
-				ensure!(tune_num < limit_num, Error::<T>::GreaterThanMaximum);
-				new_tune_num = tune_num;
-			}
-
-			new_tune_num = new_tune_num.checked_add(1).ok_or(Error::<T>::OverFlow)?;
+			let (current_time_unit, new_tune_num) =
+				Self::check_tune_exchange_rate_limit(currency_id, value)?;
 
 			// Get charged fee value
 			let (fee_permill, beneficiary) =
@@ -1917,6 +1991,14 @@ pub mod pallet {
 			let validator_set =
 				Pallet::<T>::check_length_and_deduplicate(currency_id, validator_list)?;
 
+			// offenders can not be boosted until a whole boost period has passed since the slash
+			for validator in validator_set.iter() {
+				ensure!(
+					!Self::is_recently_slashed(currency_id, validator),
+					Error::<T>::ValidatorSlashed
+				);
+			}
+
 			// get current block number
 			let current_block_number = T::BlockNumberProvider::current_block_number();
 			// get the due block number
@@ -1989,6 +2071,12 @@ pub mod pallet {
 			// Check the validity of origin
 			T::ControlOrigin::ensure_origin(origin)?;
 
+			// offenders can not be boosted until a whole boost period has passed since the slash
+			ensure!(
+				!Self::is_recently_slashed(currency_id, &who),
+				Error::<T>::ValidatorSlashed
+			);
+
 			// get current block number
 			let current_block_number = T::BlockNumberProvider::current_block_number();
 
@@ -2090,29 +2178,7 @@ pub mod pallet {
 			// Check the validity of origin
 			T::ControlOrigin::ensure_origin(origin)?;
 
-			// check if the validator is in the validator boost list
-			ValidatorBoostList::<T>::mutate(currency_id, |validator_boost_list_op| {
-				if let Some(ref mut validator_boost_list) = validator_boost_list_op {
-					// if the validator is in the validator boost list, remove it
-					if let Some(index) = validator_boost_list
-						.iter()
-						.position(|(validator, _)| validator == who.as_ref())
-					{
-						validator_boost_list.remove(index);
-
-						// if the validator boost list is empty, remove it
-						if validator_boost_list.is_empty() {
-							*validator_boost_list_op = None;
-						}
-
-						// Deposit event.
-						Pallet::<T>::deposit_event(Event::RemovedFromBoostList {
-							currency_id,
-							who: *who,
-						});
-					}
-				}
-			});
+			Self::inner_remove_from_validator_boost_list(currency_id, &who);
 
 			Ok(())
 		}
@@ -2227,6 +2293,191 @@ pub mod pallet {
 
 			Ok(())
 		}
+
+		/// Report a slash suffered by a delegator through one of its validators. The slashed
+		/// amount is deducted from the delegator ledger and the token pool, the slash is recorded
+		/// in the validator slash history and the validator is dropped from the boost list.
+		#[pallet::call_index(49)]
+		#[pallet::weight(<T as Config>::WeightInfo::report_slash())]
+		pub fn report_slash(
+			origin: OriginFor<T>,
+			currency_id: CurrencyId,
+			who: Box<MultiLocation>,
+			validator: Box<MultiLocation>,
+			#[pallet::compact] amount: BalanceOf<T>,
+		) -> DispatchResult {
+			// Ensure origin
+			Self::ensure_authorized(origin, currency_id)?;
+
+			// Ensure the amount is valid.
+			ensure!(amount > Zero::zero(), Error::<T>::AmountZero);
+
+			// Ensure this tune is within limit.
+			let (current_time_unit, new_tune_num) =
+				Self::check_tune_exchange_rate_limit(currency_id, amount)?;
+
+			// Check the slash against the delegator ledger and deduct it.
+			let staking_agent = Self::get_currency_staking_agent(currency_id)?;
+			staking_agent.slash_delegator_ledger(&who, &validator, amount, currency_id)?;
+
+			// Lower the exchange rate.
+			T::VtokenMinting::decrease_token_pool(currency_id, amount)
+				.map_err(|_| Error::<T>::DecreaseTokenPoolError)?;
+
+			// Update the CurrencyLatestTuneRecord<T> storage.
+			CurrencyLatestTuneRecord::<T>::insert(
+				currency_id,
+				(current_time_unit.clone(), new_tune_num),
+			);
+
+			// Record the slash. Drop the oldest record if the history is full.
+			let record = SlashRecord {
+				delegator: *who,
+				amount,
+				time_unit: current_time_unit.clone(),
+				block_number: T::BlockNumberProvider::current_block_number(),
+			};
+			ValidatorSlashHistory::<T>::mutate(currency_id, *validator, |history| {
+				if history.is_full() {
+					history.remove(0);
+				}
+				let _ = history.try_push(record);
+			});
+
+			Self::inner_remove_from_validator_boost_list(currency_id, &validator);
+
+			// Deposit event.
+			Pallet::<T>::deposit_event(Event::DelegatorSlashed {
+				currency_id,
+				delegator: *who,
+				validator: *validator,
+				amount,
+				time_unit: current_time_unit,
+			});
+			Pallet::<T>::deposit_event(Event::PoolTokenDecreased {
+				currency_id,
+				amount,
+			});
+			Ok(())
+		}
+
+		/// Set or remove the metrics used to score a validator.
+		#[pallet::call_index(50)]
+		#[pallet::weight(<T as Config>::WeightInfo::set_validator_metrics())]
+		pub fn set_validator_metrics(
+			origin: OriginFor<T>,
+			currency_id: CurrencyId,
+			validator: Box<MultiLocation>,
+			metrics: Option<ValidatorMetrics>,
+		) -> DispatchResult {
+			// Ensure origin
+			Self::ensure_authorized(origin, currency_id)?;
+
+			// Ensure the validator is in service.
+			let validators =
+				Validators::<T>::get(currency_id).ok_or(Error::<T>::ValidatorSetNotExist)?;
+			ensure!(
+				validators.contains(&validator),
+				Error::<T>::ValidatorNotExist
+			);
+
+			ValidatorsMetrics::<T>::set(currency_id, *validator, metrics);
+
+			// Deposit event.
+			Pallet::<T>::deposit_event(Event::ValidatorMetricsSet {
+				currency_id,
+				validator: *validator,
+				metrics,
+			});
+
+			Ok(())
+		}
+
+		/// Set ValidatorConcentrationCap storage.
+		#[pallet::call_index(51)]
+		#[pallet::weight(<T as Config>::WeightInfo::set_validator_concentration_cap())]
+		pub fn set_validator_concentration_cap(
+			origin: OriginFor<T>,
+			currency_id: CurrencyId,
+			cap: Option<Permill>,
+		) -> DispatchResult {
+			// Check the validity of origin
+			T::ControlOrigin::ensure_origin(origin)?;
+
+			ValidatorConcentrationCap::<T>::set(currency_id, cap);
+
+			// Deposit event.
+			Pallet::<T>::deposit_event(Event::ValidatorConcentrationCapSet { currency_id, cap });
+
+			Ok(())
+		}
+
+		/// Rebalance a delegator towards the best scored validators within the concentration
+		/// cap. Nominations of a substrate delegator are replaced right away, while moves between
+		/// the delegations of a parachain staking delegator are proposed for operators to carry
+		/// out.
+		#[pallet::call_index(52)]
+		#[pallet::weight(<T as Config>::WeightInfo::rebalance_validators(
+			MinimumsAndMaximums::<T>::get(currency_id)
+				.map_or(0, |mins_maxs| mins_maxs.delegators_maximum as u32)
+		))]
+		pub fn rebalance_validators(
+			origin: OriginFor<T>,
+			currency_id: CurrencyId,
+			who: Box<MultiLocation>,
+			weight_and_fee: Option<(Weight, BalanceOf<T>)>,
+		) -> DispatchResult {
+			// Ensure origin
+			Self::ensure_authorized(origin, currency_id)?;
+
+			let ledger = DelegatorLedgers::<T>::get(currency_id, *who)
+				.ok_or(Error::<T>::DelegatorNotBonded)?;
+			match ledger {
+				Ledger::Substrate(substrate_ledger) => {
+					let targets = Self::select_nomination_targets(
+						currency_id,
+						&who,
+						substrate_ledger.active,
+					)?;
+
+					let staking_agent = Self::get_currency_staking_agent(currency_id)?;
+					let query_id = staking_agent.redelegate(
+						&who,
+						&Some(targets.clone()),
+						currency_id,
+						weight_and_fee,
+					)?;
+					let query_id_hash =
+						<T as frame_system::Config>::Hashing::hash(&query_id.encode());
+
+					// Deposit event.
+					Pallet::<T>::deposit_event(Event::Delegated {
+						currency_id,
+						delegator_id: *who,
+						targets: Some(targets),
+						query_id,
+						query_id_hash,
+					});
+				}
+				Ledger::ParachainStaking(parachain_staking_ledger) => {
+					let moves = Self::propose_delegation_moves(
+						currency_id,
+						&parachain_staking_ledger.delegations,
+					)?;
+					ensure!(!moves.is_empty(), Error::<T>::NoRebalanceNeeded);
+
+					// Deposit event.
+					Pallet::<T>::deposit_event(Event::RebalanceProposed {
+						currency_id,
+						delegator_id: *who,
+						moves,
+					});
+				}
+				_ => Err(Error::<T>::Unsupported)?,
+			}
+
+			Ok(())
+		}
 	}
 
 	impl<T: Config> Pallet<T> {
@@ -2249,6 +2500,88 @@ pub mod pallet {
 			}
 		}
 
+		/// Ensure tuning the token pool by `value` is within the CurrencyTuneExchangeRateLimit.
+		/// Return the current time unit and the tune number to be recorded for it.
+		fn check_tune_exchange_rate_limit(
+			currency_id: CurrencyId,
+			value: BalanceOf<T>,
+		) -> Result<(TimeUnit, u32), Error<T>> {
+			let (limit_num, max_permill) = CurrencyTuneExchangeRateLimit::<T>::get(currency_id)
+				.ok_or(Error::<T>::TuneExchangeRateLimitNotSet)?;
+			// Get pool token value
+			let pool_token = T::VtokenMinting::get_token_pool(currency_id);
+			// Calculate max tune allowed.
+			let max_to_tune = max_permill.mul_floor(pool_token);
+			ensure!(value <= max_to_tune, Error::<T>::GreaterThanMaximum);
+
+			// Get current TimeUnit.
+			let current_time_unit = T::VtokenMinting::get_ongoing_time_unit(currency_id)
+				.ok_or(Error::<T>::TimeUnitNotExist)?;
+			// If this is the first time.
+			if !CurrencyLatestTuneRecord::<T>::contains_key(currency_id) {
+				// Insert an empty record into CurrencyLatestTuneRecord storage.
+				CurrencyLatestTuneRecord::<T>::insert(currency_id, (current_time_unit.clone(), 0));
+			}
+
+			// Get CurrencyLatestTuneRecord for the currencyId.
+			let (latest_time_unit, tune_num) = CurrencyLatestTuneRecord::<T>::get(currency_id)
+				.ok_or(Error::<T>::CurrencyLatestTuneRecordNotExist)?;
+
+			// See if exceeds tuning limit.
+			// If it has been tuned in the current time unit, ensure this tuning is within limit.
+			let mut new_tune_num = Zero::zero();
+			if latest_time_unit == current_time_unit {
+				ensure!(tune_num < limit_num, Error::<T>::GreaterThanMaximum);
+				new_tune_num = tune_num;
+			}
+
+			new_tune_num = new_tune_num.checked_add(1).ok_or(Error::<T>::OverFlow)?;
+
+			Ok((current_time_unit, new_tune_num))
+		}
+
+		fn inner_remove_from_validator_boost_list(currency_id: CurrencyId, who: &MultiLocation) {
+			// check if the validator is in the validator boost list
+			ValidatorBoostList::<T>::mutate(currency_id, |validator_boost_list_op| {
+				if let Some(ref mut validator_boost_list) = validator_boost_list_op {
+					// if the validator is in the validator boost list, remove it
+					if let Some(index) = validator_boost_list
+						.iter()
+						.position(|(validator, _)| validator == who)
+					{
+						validator_boost_list.remove(index);
+
+						// if the validator boost list is empty, remove it
+						if validator_boost_list.is_empty() {
+							*validator_boost_list_op = None;
+						}
+
+						// Deposit event.
+						Pallet::<T>::deposit_event(Event::RemovedFromBoostList {
+							currency_id,
+							who: *who,
+						});
+					}
+				}
+			});
+		}
+
+		/// Whether the validator has been slashed within the last boost period.
+		pub(crate) fn is_recently_slashed(
+			currency_id: CurrencyId,
+			validator: &MultiLocation,
+		) -> bool {
+			let current_block_number = T::BlockNumberProvider::current_block_number();
+			ValidatorSlashHistory::<T>::get(currency_id, validator)
+				.last()
+				.map_or(false, |record| {
+					record
+						.block_number
+						.saturating_add(BlockNumberFor::<T>::from(SIX_MONTHS))
+						> current_block_number
+				})
+		}
+
 		pub(crate) fn get_currency_staking_agent(
 			currency_id: CurrencyId,
 		) -> Result<StakingAgentBoxType<T>, Error<T>> {
@@ -2277,6 +2610,38 @@ pub mod pallet {
 			};
 			<T as Config>::RuntimeCall::from(call)
 		}
+
+		/// Get the outstanding delegator ledger queries together with their age in blocks.
+		pub fn get_pending_ledger_queries(
+		) -> Vec<(QueryId, LedgerUpdateEntry<BalanceOf<T>>, BlockNumberFor<T>)> {
+			DelegatorLedgerXcmUpdateQueue::<T>::iter()
+				.map(|(query_id, (entry, timeout))| {
+					(query_id, entry, Self::query_age(query_id, timeout))
+				})
+				.collect()
+		}
+
+		/// Get the outstanding validators by delegator queries together with their age in blocks.
+		pub fn get_pending_validators_queries(
+		) -> Vec<(QueryId, ValidatorsByDelegatorUpdateEntry, BlockNumberFor<T>)> {
+			ValidatorsByDelegatorXcmUpdateQueue::<T>::iter()
+				.map(|(query_id, (entry, timeout))| {
+					(query_id, entry, Self::query_age(query_id, timeout))
+				})
+				.collect()
+		}
+
+		/// Queries sent before their creation block was recorded derive it from the timeout,
+		/// assuming `XcmQueryTimeout` has not changed since.
+		fn query_age(query_id: QueryId, timeout: BlockNumberFor<T>) -> BlockNumberFor<T> {
+			let now = T::BlockNumberProvider::current_block_number();
+			match XcmQueryCreatedAt::<T>::get(query_id) {
+				Some(created_at) => now.saturating_sub(created_at),
+				None => now
+					.saturating_add(T::XcmQueryTimeout::get())
+					.saturating_sub(timeout),
+			}
+		}
 	}
 
 	// Functions to be called by other pallets.
@@ -40,7 +40,7 @@ use frame_support::{
 use frame_system::{EnsureRoot, EnsureSignedBy};
 use orml_traits::{location::RelativeReserveProvider, parameter_type_with_key};
 use parity_scale_codec::{Decode, Encode};
-use sp_core::{bounded::BoundedVec, hashing::blake2_256, ConstU32};
+use sp_core::{bounded::BoundedVec, hashing::blake2_256, ConstU32, ConstU64};
 use sp_runtime::{
 	traits::{AccountIdConversion, Convert, IdentityLookup, TrailingZeroInput},
 	AccountId32, BuildStorage,
@@ -460,6 +460,7 @@ impl Config for Runtime {
 	type AssetIdMaps = AssetIdMaps<Runtime>;
 	type TreasuryAccount = BifrostTreasuryAccount;
 	type BlockNumberProvider = System;
+	type XcmQueryTimeout = ConstU64<1000>;
 }
 
 pub struct XcmDestWeightAndFee;
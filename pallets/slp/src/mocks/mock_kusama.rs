@@ -35,7 +35,8 @@ use frame_support::{
 	construct_runtime, derive_impl, ord_parameter_types,
 	pallet_prelude::Get,
 	parameter_types,
-	traits::{ConstU128, ConstU32, Everything, Nothing, ProcessMessageError},
+	traits::{ConstU128, ConstU32, ConstU64, Everything, Nothing, ProcessMessageError},
+	weights::constants::RocksDbWeight,
 	PalletId,
 };
 use frame_system::{EnsureRoot, EnsureSignedBy};
@@ -110,6 +111,8 @@ impl bifrost_stable_asset::Config for Runtime {
 	type ListingOrigin = EnsureSignedBy<One, AccountId>;
 	type EnsurePoolAssetId = EnsurePoolAssetId;
 	type BlockNumberProvider = System;
+	type PriceObservationPeriod = ConstU64<10>;
+	type MaxPriceObservations = ConstU32<24>;
 }
 
 parameter_types! {
@@ -134,6 +137,7 @@ impl frame_system::Config for Runtime {
 	type AccountId = AccountId;
 	type Lookup = Indices;
 	type AccountData = pallet_balances::AccountData<Balance>;
+	type DbWeight = RocksDbWeight;
 }
 
 parameter_types! {
@@ -542,6 +546,7 @@ impl Config for Runtime {
 	type AssetIdMaps = AssetIdMaps<Runtime>;
 	type TreasuryAccount = BifrostTreasuryAccount;
 	type BlockNumberProvider = System;
+	type XcmQueryTimeout = ConstU64<1000>;
 }
 
 pub struct XcmDestWeightAndFee;
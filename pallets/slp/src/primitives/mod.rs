@@ -29,10 +29,10 @@ pub use polkadot_primitives::*;
 use bifrost_primitives::TimeUnit;
 use parity_scale_codec::{Decode, Encode};
 use scale_info::TypeInfo;
-use sp_runtime::RuntimeDebug;
+use sp_runtime::{Permill, RuntimeDebug};
+use xcm::v3::MultiLocation;
 
 pub type QueryId = u64;
-pub const TIMEOUT_BLOCKS: u32 = 1000;
 pub const BASE_WEIGHT: u64 = 1000;
 
 #[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
@@ -107,3 +107,26 @@ pub struct Delays {
 	/// Leave from delegator set delay.
 	pub leave_delegators_delay: TimeUnit,
 }
+
+/// A slash suffered by a delegator through one of its validators.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub struct SlashRecord<Balance, BlockNumber> {
+	/// The delegator whose bonded amount was slashed.
+	pub delegator: MultiLocation,
+	/// The slashed amount.
+	#[codec(compact)]
+	pub amount: Balance,
+	/// The time unit in which the slash was reported.
+	pub time_unit: TimeUnit,
+	/// The block number at which the slash was reported.
+	pub block_number: BlockNumber,
+}
+
+/// Performance metrics of a validator, fed by operators, used to score the validator.
+#[derive(PartialEq, Eq, Clone, Copy, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub struct ValidatorMetrics {
+	/// The commission the validator charges on rewards.
+	pub commission: Permill,
+	/// The share of the recent time units in which the validator was active and online.
+	pub uptime: Permill,
+}
@@ -18,10 +18,17 @@
 
 #![cfg(test)]
 
-use crate::{mocks::mock_kusama::*, *};
+use crate::{
+	mocks::mock_kusama::*,
+	primitives::{
+		SubstrateLedgerUpdateEntry, SubstrateLedgerUpdateOperation,
+		SubstrateValidatorsByDelegatorUpdateEntry, UnlockChunk,
+	},
+	*,
+};
 use bifrost_primitives::currency::{KSM, VKSM};
 use bifrost_vtoken_minting::{OngoingTimeUnit, TokenPool};
-use frame_support::{assert_ok, PalletId};
+use frame_support::{assert_noop, assert_ok, traits::Hooks, PalletId};
 use orml_traits::MultiCurrency;
 use sp_runtime::traits::AccountIdConversion;
 use xcm::v3::prelude::*;
@@ -35,6 +42,13 @@ const SUBACCOUNT_0_LOCATION: MultiLocation = MultiLocation {
 		id: SUBACCOUNT_0_32,
 	}),
 };
+const VALIDATOR_0_LOCATION: MultiLocation = MultiLocation {
+	parents: 1,
+	interior: X1(AccountId32 {
+		network: None,
+		id: [3u8; 32],
+	}),
+};
 
 #[test]
 fn construct_xcm_and_send_as_subaccount_should_work() {
@@ -538,3 +552,683 @@ fn test_construct_xcm() {
 		assert_eq!(fee, BalanceOf::<Runtime>::from(100u32));
 	});
 }
+
+fn empty_substrate_ledger() -> Ledger<BalanceOf<Runtime>> {
+	Ledger::Substrate(SubstrateLedger {
+		account: SUBACCOUNT_0_LOCATION,
+		total: 0,
+		active: 0,
+		unlocking: vec![],
+	})
+}
+
+fn bond_update_entry() -> LedgerUpdateEntry<BalanceOf<Runtime>> {
+	LedgerUpdateEntry::Substrate(SubstrateLedgerUpdateEntry {
+		currency_id: DOT,
+		delegator_id: SUBACCOUNT_0_LOCATION,
+		update_operation: SubstrateLedgerUpdateOperation::Bond,
+		amount: 1_000_000_000_000,
+		unlock_time: None,
+	})
+}
+
+#[test]
+fn on_idle_expires_timed_out_ledger_queries() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+
+		// The first bond leaves an empty ledger until the query response arrives.
+		DelegatorLedgers::<Runtime>::insert(DOT, SUBACCOUNT_0_LOCATION, empty_substrate_ledger());
+		DelegatorLedgerXcmUpdateQueue::<Runtime>::insert(0, (bond_update_entry(), 1001));
+		XcmQueryCreatedAt::<Runtime>::insert(0, 1);
+
+		System::set_block_number(1001);
+		Slp::on_idle(1001, Weight::MAX);
+		assert!(DelegatorLedgerXcmUpdateQueue::<Runtime>::contains_key(0));
+		assert_eq!(
+			Slp::get_pending_ledger_queries(),
+			vec![(0, bond_update_entry(), 1000)]
+		);
+
+		System::set_block_number(1002);
+		Slp::on_idle(1002, Weight::MAX);
+		assert!(!DelegatorLedgerXcmUpdateQueue::<Runtime>::contains_key(0));
+		assert!(!XcmQueryCreatedAt::<Runtime>::contains_key(0));
+		assert_eq!(
+			DelegatorLedgers::<Runtime>::get(DOT, SUBACCOUNT_0_LOCATION),
+			None
+		);
+		assert!(Slp::get_pending_ledger_queries().is_empty());
+		System::assert_last_event(RuntimeEvent::Slp(
+			Event::DelegatorLedgerQueryResponseExpired {
+				query_id: 0,
+				entry: bond_update_entry(),
+			},
+		));
+	});
+}
+
+#[test]
+fn on_idle_keeps_ledgers_with_pending_queries() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(100);
+
+		DelegatorLedgers::<Runtime>::insert(DOT, SUBACCOUNT_0_LOCATION, empty_substrate_ledger());
+		DelegatorLedgerXcmUpdateQueue::<Runtime>::insert(0, (bond_update_entry(), 10));
+		DelegatorLedgerXcmUpdateQueue::<Runtime>::insert(1, (bond_update_entry(), 1050));
+
+		let validators_entry = ValidatorsByDelegatorUpdateEntry::Substrate(
+			SubstrateValidatorsByDelegatorUpdateEntry {
+				currency_id: DOT,
+				delegator_id: SUBACCOUNT_0_LOCATION,
+				validators: vec![],
+			},
+		);
+		ValidatorsByDelegatorXcmUpdateQueue::<Runtime>::insert(2, (validators_entry.clone(), 10));
+		assert_eq!(
+			Slp::get_pending_validators_queries(),
+			vec![(2, validators_entry.clone(), 1090)]
+		);
+
+		// Nothing is expired without enough weight.
+		Slp::on_idle(100, Weight::zero());
+		assert!(DelegatorLedgerXcmUpdateQueue::<Runtime>::contains_key(0));
+		assert!(ValidatorsByDelegatorXcmUpdateQueue::<Runtime>::contains_key(2));
+
+		Slp::on_idle(100, Weight::MAX);
+		assert!(!DelegatorLedgerXcmUpdateQueue::<Runtime>::contains_key(0));
+		assert!(DelegatorLedgerXcmUpdateQueue::<Runtime>::contains_key(1));
+		assert!(!ValidatorsByDelegatorXcmUpdateQueue::<Runtime>::contains_key(2));
+		// The bond of query 1 is still in flight, so the ledger is kept.
+		assert_eq!(
+			DelegatorLedgers::<Runtime>::get(DOT, SUBACCOUNT_0_LOCATION),
+			Some(empty_substrate_ledger())
+		);
+		System::assert_has_event(RuntimeEvent::Slp(
+			Event::DelegatorLedgerQueryResponseExpired {
+				query_id: 0,
+				entry: bond_update_entry(),
+			},
+		));
+		System::assert_last_event(RuntimeEvent::Slp(
+			Event::ValidatorsByDelegatorQueryResponseExpired {
+				query_id: 2,
+				entry: validators_entry,
+			},
+		));
+	});
+}
+
+#[test]
+fn on_idle_resumes_expiring_from_the_cursor() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(100);
+
+		let unlock_entry = LedgerUpdateEntry::Substrate(SubstrateLedgerUpdateEntry {
+			currency_id: DOT,
+			delegator_id: SUBACCOUNT_0_LOCATION,
+			update_operation: SubstrateLedgerUpdateOperation::Unlock,
+			amount: 1_000_000_000_000,
+			unlock_time: None,
+		});
+		for query_id in 0..3 {
+			DelegatorLedgerXcmUpdateQueue::<Runtime>::insert(query_id, (unlock_entry.clone(), 10));
+		}
+
+		// Only enough weight to expire a single entry per block.
+		let db_weight = <Runtime as frame_system::Config>::DbWeight::get();
+		let limit = db_weight
+			.reads_writes(3, 2)
+			.saturating_add(db_weight.reads(1))
+			.saturating_add(db_weight.reads_writes(2, 4));
+
+		Slp::on_idle(100, limit);
+		assert_eq!(DelegatorLedgerXcmUpdateQueue::<Runtime>::iter().count(), 2);
+		let cursor = DelegatorLedgerXcmUpdateQueueCursor::<Runtime>::get();
+		assert!(cursor.is_some());
+
+		Slp::on_idle(100, limit);
+		assert_eq!(DelegatorLedgerXcmUpdateQueue::<Runtime>::iter().count(), 1);
+		assert!(DelegatorLedgerXcmUpdateQueueCursor::<Runtime>::get().is_some());
+		assert_ne!(
+			DelegatorLedgerXcmUpdateQueueCursor::<Runtime>::get(),
+			cursor
+		);
+
+		// The scan reaches the end of the queue and starts over in the next block.
+		Slp::on_idle(100, limit);
+		assert_eq!(DelegatorLedgerXcmUpdateQueue::<Runtime>::iter().count(), 0);
+		assert_eq!(DelegatorLedgerXcmUpdateQueueCursor::<Runtime>::get(), None);
+		for query_id in 0..3 {
+			System::assert_has_event(RuntimeEvent::Slp(
+				Event::DelegatorLedgerQueryResponseExpired {
+					query_id,
+					entry: unlock_entry.clone(),
+				},
+			));
+		}
+	});
+}
+
+#[test]
+fn pending_query_age_counts_from_the_creation_block() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(100);
+
+		DelegatorLedgerXcmUpdateQueue::<Runtime>::insert(0, (bond_update_entry(), 1050));
+		XcmQueryCreatedAt::<Runtime>::insert(0, 50);
+		assert_eq!(
+			Slp::get_pending_ledger_queries(),
+			vec![(0, bond_update_entry(), 50)]
+		);
+	});
+}
+
+fn init_report_slash() {
+	bifrost_vtoken_minting::OngoingTimeUnit::<Runtime>::insert(KSM, TimeUnit::Era(1));
+
+	DelegatorLedgers::<Runtime>::insert(
+		KSM,
+		SUBACCOUNT_0_LOCATION,
+		Ledger::Substrate(SubstrateLedger {
+			account: SUBACCOUNT_0_LOCATION,
+			total: 1000,
+			active: 1000,
+			unlocking: vec![],
+		}),
+	);
+	ValidatorsByDelegator::<Runtime>::insert(
+		KSM,
+		SUBACCOUNT_0_LOCATION,
+		BoundedVec::try_from(vec![VALIDATOR_0_LOCATION]).unwrap(),
+	);
+
+	// At most 10% of the pool can be slashed once per time unit.
+	assert_ok!(Slp::set_currency_tune_exchange_rate_limit(
+		RuntimeOrigin::signed(ALICE),
+		KSM,
+		Some((1, Permill::from_percent(10)))
+	));
+	assert_ok!(Slp::increase_token_pool(
+		RuntimeOrigin::signed(ALICE),
+		KSM,
+		1000
+	));
+}
+
+#[test]
+fn report_slash_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		init_report_slash();
+
+		assert_ok!(Slp::add_to_validator_boost_list(
+			RuntimeOrigin::signed(ALICE),
+			KSM,
+			Box::new(VALIDATOR_0_LOCATION)
+		));
+
+		assert_ok!(Slp::report_slash(
+			RuntimeOrigin::signed(ALICE),
+			KSM,
+			Box::new(SUBACCOUNT_0_LOCATION),
+			Box::new(VALIDATOR_0_LOCATION),
+			50
+		));
+
+		assert_eq!(<Runtime as Config>::VtokenMinting::get_token_pool(KSM), 950);
+		assert_eq!(
+			DelegatorLedgers::<Runtime>::get(KSM, SUBACCOUNT_0_LOCATION),
+			Some(Ledger::Substrate(SubstrateLedger {
+				account: SUBACCOUNT_0_LOCATION,
+				total: 950,
+				active: 950,
+				unlocking: vec![],
+			}))
+		);
+		assert_eq!(
+			ValidatorSlashHistory::<Runtime>::get(KSM, VALIDATOR_0_LOCATION).to_vec(),
+			vec![SlashRecord {
+				delegator: SUBACCOUNT_0_LOCATION,
+				amount: 50,
+				time_unit: TimeUnit::Era(1),
+				block_number: 1,
+			}]
+		);
+		assert_eq!(ValidatorBoostList::<Runtime>::get(KSM), None);
+		System::assert_has_event(RuntimeEvent::Slp(Event::DelegatorSlashed {
+			currency_id: KSM,
+			delegator: SUBACCOUNT_0_LOCATION,
+			validator: VALIDATOR_0_LOCATION,
+			amount: 50,
+			time_unit: TimeUnit::Era(1),
+		}));
+
+		// The offender can not be boosted again until a whole boost period has passed.
+		assert_noop!(
+			Slp::add_to_validator_boost_list(
+				RuntimeOrigin::signed(ALICE),
+				KSM,
+				Box::new(VALIDATOR_0_LOCATION)
+			),
+			Error::<Runtime>::ValidatorSlashed
+		);
+		assert_noop!(
+			Slp::set_validator_boost_list(
+				RuntimeOrigin::signed(ALICE),
+				KSM,
+				vec![VALIDATOR_0_LOCATION]
+			),
+			Error::<Runtime>::ValidatorSlashed
+		);
+
+		System::set_block_number(1 + SIX_MONTHS as u64);
+		assert_ok!(Slp::add_to_validator_boost_list(
+			RuntimeOrigin::signed(ALICE),
+			KSM,
+			Box::new(VALIDATOR_0_LOCATION)
+		));
+	});
+}
+
+#[test]
+fn report_slash_checks_ledger_and_limits() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		init_report_slash();
+
+		// Exceeds the tune exchange rate limit of 10% of the pool.
+		assert_noop!(
+			Slp::report_slash(
+				RuntimeOrigin::signed(ALICE),
+				KSM,
+				Box::new(SUBACCOUNT_0_LOCATION),
+				Box::new(VALIDATOR_0_LOCATION),
+				101
+			),
+			Error::<Runtime>::GreaterThanMaximum
+		);
+
+		// The validator is not nominated by the delegator.
+		assert_noop!(
+			Slp::report_slash(
+				RuntimeOrigin::signed(ALICE),
+				KSM,
+				Box::new(SUBACCOUNT_0_LOCATION),
+				Box::new(SUBACCOUNT_0_LOCATION),
+				50
+			),
+			Error::<Runtime>::NotDelegateValidator
+		);
+
+		// Exceeds the bonded amount of the delegator.
+		DelegatorLedgers::<Runtime>::insert(
+			KSM,
+			SUBACCOUNT_0_LOCATION,
+			Ledger::Substrate(SubstrateLedger {
+				account: SUBACCOUNT_0_LOCATION,
+				total: 30,
+				active: 30,
+				unlocking: vec![],
+			}),
+		);
+		assert_noop!(
+			Slp::report_slash(
+				RuntimeOrigin::signed(ALICE),
+				KSM,
+				Box::new(SUBACCOUNT_0_LOCATION),
+				Box::new(VALIDATOR_0_LOCATION),
+				50
+			),
+			Error::<Runtime>::SlashExceedsBonded
+		);
+
+		// Only one tune is allowed per time unit.
+		assert_ok!(Slp::report_slash(
+			RuntimeOrigin::signed(ALICE),
+			KSM,
+			Box::new(SUBACCOUNT_0_LOCATION),
+			Box::new(VALIDATOR_0_LOCATION),
+			10
+		));
+		assert_noop!(
+			Slp::report_slash(
+				RuntimeOrigin::signed(ALICE),
+				KSM,
+				Box::new(SUBACCOUNT_0_LOCATION),
+				Box::new(VALIDATOR_0_LOCATION),
+				10
+			),
+			Error::<Runtime>::GreaterThanMaximum
+		);
+	});
+}
+
+#[test]
+fn report_slash_spreads_over_unlocking_chunks() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		init_report_slash();
+
+		DelegatorLedgers::<Runtime>::insert(
+			KSM,
+			SUBACCOUNT_0_LOCATION,
+			Ledger::Substrate(SubstrateLedger {
+				account: SUBACCOUNT_0_LOCATION,
+				total: 1000,
+				active: 600,
+				unlocking: vec![
+					UnlockChunk {
+						value: 300,
+						unlock_time: TimeUnit::Era(5),
+					},
+					UnlockChunk {
+						value: 100,
+						unlock_time: TimeUnit::Era(6),
+					},
+				],
+			}),
+		);
+
+		assert_ok!(Slp::report_slash(
+			RuntimeOrigin::signed(ALICE),
+			KSM,
+			Box::new(SUBACCOUNT_0_LOCATION),
+			Box::new(VALIDATOR_0_LOCATION),
+			100
+		));
+
+		assert_eq!(
+			DelegatorLedgers::<Runtime>::get(KSM, SUBACCOUNT_0_LOCATION),
+			Some(Ledger::Substrate(SubstrateLedger {
+				account: SUBACCOUNT_0_LOCATION,
+				total: 900,
+				active: 540,
+				unlocking: vec![
+					UnlockChunk {
+						value: 270,
+						unlock_time: TimeUnit::Era(5)
+					},
+					UnlockChunk {
+						value: 90,
+						unlock_time: TimeUnit::Era(6)
+					},
+				],
+			}))
+		);
+	});
+}
+
+fn validator_location(id: u8) -> MultiLocation {
+	MultiLocation {
+		parents: 1,
+		interior: X1(AccountId32 {
+			network: None,
+			id: [id; 32],
+		}),
+	}
+}
+
+fn metrics(commission: u32, uptime: u32) -> ValidatorMetrics {
+	ValidatorMetrics {
+		commission: Permill::from_percent(commission),
+		uptime: Permill::from_percent(uptime),
+	}
+}
+
+#[test]
+fn validator_scoring_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		let (validator_0, validator_1, validator_2) = (
+			validator_location(3),
+			validator_location(4),
+			validator_location(5),
+		);
+
+		// Only validators in service can be scored.
+		assert_noop!(
+			Slp::set_validator_metrics(
+				RuntimeOrigin::signed(ALICE),
+				KSM,
+				Box::new(validator_0),
+				Some(metrics(10, 100))
+			),
+			Error::<Runtime>::ValidatorSetNotExist
+		);
+
+		Validators::<Runtime>::insert(
+			KSM,
+			BoundedVec::try_from(vec![validator_0, validator_1, validator_2]).unwrap(),
+		);
+		assert_ok!(Slp::set_validator_metrics(
+			RuntimeOrigin::signed(ALICE),
+			KSM,
+			Box::new(validator_0),
+			Some(metrics(10, 100))
+		));
+		System::assert_last_event(RuntimeEvent::Slp(Event::ValidatorMetricsSet {
+			currency_id: KSM,
+			validator: validator_0,
+			metrics: Some(metrics(10, 100)),
+		}));
+		assert_ok!(Slp::set_validator_metrics(
+			RuntimeOrigin::signed(ALICE),
+			KSM,
+			Box::new(validator_1),
+			Some(metrics(5, 100))
+		));
+
+		// Validators without metrics are left out of the ranking.
+		assert_eq!(
+			Slp::validator_score(KSM, &validator_0),
+			Permill::from_percent(90)
+		);
+		assert_eq!(Slp::validator_score(KSM, &validator_2), Permill::zero());
+		assert_eq!(
+			Slp::ranked_validators(KSM),
+			vec![
+				(validator_1, Permill::from_percent(95)),
+				(validator_0, Permill::from_percent(90))
+			]
+		);
+
+		// Recently slashed validators score zero.
+		ValidatorSlashHistory::<Runtime>::mutate(KSM, validator_1, |history| {
+			history
+				.try_push(SlashRecord {
+					delegator: SUBACCOUNT_0_LOCATION,
+					amount: 50,
+					time_unit: TimeUnit::Era(1),
+					block_number: 1,
+				})
+				.unwrap();
+		});
+		assert_eq!(Slp::validator_score(KSM, &validator_1), Permill::zero());
+		assert_eq!(
+			Slp::ranked_validators(KSM),
+			vec![(validator_0, Permill::from_percent(90))]
+		);
+
+		// Metrics are dropped along with the validator.
+		assert_ok!(Slp::remove_validator(
+			RuntimeOrigin::signed(ALICE),
+			KSM,
+			Box::new(validator_0)
+		));
+		assert_eq!(ValidatorsMetrics::<Runtime>::get(KSM, validator_0), None);
+	});
+}
+
+#[test]
+fn select_nomination_targets_respects_concentration_cap() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		let (validator_0, validator_1, validator_2) = (
+			validator_location(3),
+			validator_location(4),
+			validator_location(5),
+		);
+		let other_delegator = validator_location(9);
+
+		Validators::<Runtime>::insert(
+			KSM,
+			BoundedVec::try_from(vec![validator_0, validator_1, validator_2]).unwrap(),
+		);
+		ValidatorsMetrics::<Runtime>::insert(KSM, validator_0, metrics(5, 100));
+		ValidatorsMetrics::<Runtime>::insert(KSM, validator_1, metrics(10, 100));
+		ValidatorsMetrics::<Runtime>::insert(KSM, validator_2, metrics(20, 100));
+		MinimumsAndMaximums::<Runtime>::insert(
+			KSM,
+			MinimumsMaximums {
+				delegator_bonded_minimum: 0,
+				bond_extra_minimum: 0,
+				unbond_minimum: 0,
+				rebond_minimum: 0,
+				unbond_record_maximum: 32,
+				validators_back_maximum: 2,
+				delegator_active_staking_maximum: 1_000_000,
+				validators_reward_maximum: 256,
+				delegation_amount_minimum: 0,
+				delegators_maximum: 100,
+				validators_maximum: 300,
+			},
+		);
+
+		// Another delegator already backs the best scored validator with 600 of 1000.
+		for (delegator, active) in [(other_delegator, 600), (SUBACCOUNT_0_LOCATION, 400)] {
+			DelegatorLedgers::<Runtime>::insert(
+				KSM,
+				delegator,
+				Ledger::Substrate(SubstrateLedger {
+					account: delegator,
+					total: active,
+					active,
+					unlocking: vec![],
+				}),
+			);
+		}
+		ValidatorsByDelegator::<Runtime>::insert(
+			KSM,
+			other_delegator,
+			BoundedVec::try_from(vec![validator_0]).unwrap(),
+		);
+
+		assert_noop!(
+			Slp::rebalance_validators(
+				RuntimeOrigin::signed(ALICE),
+				KSM,
+				Box::new(SUBACCOUNT_0_LOCATION),
+				None
+			),
+			Error::<Runtime>::ValidatorConcentrationCapNotSet
+		);
+
+		assert_ok!(Slp::set_validator_concentration_cap(
+			RuntimeOrigin::signed(ALICE),
+			KSM,
+			Some(Permill::from_percent(50))
+		));
+		assert_eq!(
+			Slp::select_nomination_targets(KSM, &SUBACCOUNT_0_LOCATION, 400).unwrap(),
+			vec![validator_1, validator_2]
+		);
+
+		// With a tighter cap, no validator can take a 200 share.
+		ValidatorConcentrationCap::<Runtime>::insert(KSM, Permill::from_percent(15));
+		assert_noop!(
+			Slp::rebalance_validators(
+				RuntimeOrigin::signed(ALICE),
+				KSM,
+				Box::new(SUBACCOUNT_0_LOCATION),
+				None
+			),
+			Error::<Runtime>::NoEligibleValidator
+		);
+	});
+}
+
+#[test]
+fn select_nomination_targets_recomputes_the_share_of_fewer_targets() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		let (validator_0, validator_1, validator_2) = (
+			validator_location(3),
+			validator_location(4),
+			validator_location(5),
+		);
+
+		Validators::<Runtime>::insert(
+			KSM,
+			BoundedVec::try_from(vec![validator_0, validator_1, validator_2]).unwrap(),
+		);
+		ValidatorsMetrics::<Runtime>::insert(KSM, validator_0, metrics(5, 100));
+		ValidatorsMetrics::<Runtime>::insert(KSM, validator_1, metrics(10, 100));
+		ValidatorsMetrics::<Runtime>::insert(KSM, validator_2, metrics(20, 100));
+		MinimumsAndMaximums::<Runtime>::insert(
+			KSM,
+			MinimumsMaximums {
+				delegator_bonded_minimum: 0,
+				bond_extra_minimum: 0,
+				unbond_minimum: 0,
+				rebond_minimum: 0,
+				unbond_record_maximum: 32,
+				validators_back_maximum: 3,
+				delegator_active_staking_maximum: 1_000_000,
+				validators_reward_maximum: 256,
+				delegation_amount_minimum: 0,
+				delegators_maximum: 3,
+				validators_maximum: 300,
+			},
+		);
+
+		// Other delegators back validator 0 with 600 and validator 1 with 350 of 1350.
+		for (delegator, active, validator) in [
+			(validator_location(9), 600, Some(validator_0)),
+			(validator_location(10), 350, Some(validator_1)),
+			(SUBACCOUNT_0_LOCATION, 400, None),
+		] {
+			DelegatorLedgers::<Runtime>::insert(
+				KSM,
+				delegator,
+				Ledger::Substrate(SubstrateLedger {
+					account: delegator,
+					total: active,
+					active,
+					unlocking: vec![],
+				}),
+			);
+			if let Some(validator) = validator {
+				ValidatorsByDelegator::<Runtime>::insert(
+					KSM,
+					delegator,
+					BoundedVec::try_from(vec![validator]).unwrap(),
+				);
+			}
+		}
+		ValidatorConcentrationCap::<Runtime>::insert(KSM, Permill::from_percent(40));
+
+		// Only validators 1 and 2 can take a third of 400 under the cap of 540, but validator 1
+		// can not take the half the two of them would get.
+		assert_eq!(
+			Slp::select_nomination_targets(KSM, &SUBACCOUNT_0_LOCATION, 400).unwrap(),
+			vec![validator_2]
+		);
+
+		// The delegators scanned are bounded by the maximum.
+		MinimumsAndMaximums::<Runtime>::mutate(KSM, |mins_maxs| {
+			if let Some(mins_maxs) = mins_maxs {
+				mins_maxs.delegators_maximum = 2;
+			}
+		});
+		assert_noop!(
+			Slp::rebalance_validators(
+				RuntimeOrigin::signed(ALICE),
+				KSM,
+				Box::new(SUBACCOUNT_0_LOCATION),
+				None
+			),
+			Error::<Runtime>::ExceedDelegatorsMaximum
+		);
+	});
+}
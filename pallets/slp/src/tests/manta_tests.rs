@@ -2157,3 +2157,103 @@ fn clean_outdated_validator_boost_list_work() {
 		);
 	});
 }
+
+#[test]
+fn slash_delegator_ledger_reconciles_requests() {
+	ExtBuilder::default().build().execute_with(|| {
+		let subaccount_0_location = MultiLocation {
+			parents: 1,
+			interior: X2(
+				Parachain(2104),
+				Junction::AccountId32 {
+					network: None,
+					id: [1u8; 32],
+				},
+			),
+		};
+
+		let mut delegation_set: BTreeMap<MultiLocation, BalanceOf<Runtime>> = BTreeMap::new();
+		delegation_set.insert(VALIDATOR_0_LOCATION, 10_000);
+		delegation_set.insert(VALIDATOR_1_LOCATION, 5_000);
+
+		let mut request_briefs_set: BTreeMap<MultiLocation, (TimeUnit, BalanceOf<Runtime>)> =
+			BTreeMap::new();
+		request_briefs_set.insert(VALIDATOR_0_LOCATION, (TimeUnit::Round(24), 4_000));
+		request_briefs_set.insert(VALIDATOR_1_LOCATION, (TimeUnit::Round(24), 5_000));
+
+		let request_list = vec![
+			OneToManyScheduledRequest {
+				validator: VALIDATOR_0_LOCATION,
+				when_executable: TimeUnit::Round(24),
+				action: OneToManyDelegationAction::Decrease(4_000),
+			},
+			OneToManyScheduledRequest {
+				validator: VALIDATOR_1_LOCATION,
+				when_executable: TimeUnit::Round(24),
+				action: OneToManyDelegationAction::Revoke(5_000),
+			},
+		];
+
+		DelegatorLedgers::<Runtime>::insert(
+			MANTA,
+			subaccount_0_location,
+			Ledger::ParachainStaking(OneToManyLedger {
+				account: subaccount_0_location,
+				total: 15_000,
+				less_total: 9_000,
+				delegations: delegation_set,
+				requests: request_list,
+				request_briefs: request_briefs_set,
+				status: OneToManyDelegatorStatus::Active,
+			}),
+		);
+
+		let staking_agent = Slp::get_currency_staking_agent(MANTA).unwrap();
+
+		// The decrease request is capped at what is left of the delegation.
+		assert_ok!(staking_agent.slash_delegator_ledger(
+			&subaccount_0_location,
+			&VALIDATOR_0_LOCATION,
+			7_000,
+			MANTA
+		));
+		// A delegation slashed to zero is removed together with its revoke request.
+		assert_ok!(staking_agent.slash_delegator_ledger(
+			&subaccount_0_location,
+			&VALIDATOR_1_LOCATION,
+			5_000,
+			MANTA
+		));
+
+		let mut delegation_set: BTreeMap<MultiLocation, BalanceOf<Runtime>> = BTreeMap::new();
+		delegation_set.insert(VALIDATOR_0_LOCATION, 3_000);
+		let mut request_briefs_set: BTreeMap<MultiLocation, (TimeUnit, BalanceOf<Runtime>)> =
+			BTreeMap::new();
+		request_briefs_set.insert(VALIDATOR_0_LOCATION, (TimeUnit::Round(24), 3_000));
+		assert_eq!(
+			DelegatorLedgers::<Runtime>::get(MANTA, subaccount_0_location),
+			Some(Ledger::ParachainStaking(OneToManyLedger {
+				account: subaccount_0_location,
+				total: 3_000,
+				less_total: 3_000,
+				delegations: delegation_set,
+				requests: vec![OneToManyScheduledRequest {
+					validator: VALIDATOR_0_LOCATION,
+					when_executable: TimeUnit::Round(24),
+					action: OneToManyDelegationAction::Decrease(3_000),
+				}],
+				request_briefs: request_briefs_set,
+				status: OneToManyDelegatorStatus::Active,
+			}))
+		);
+		assert_noop!(
+			staking_agent.slash_delegator_ledger(
+				&subaccount_0_location,
+				&VALIDATOR_1_LOCATION,
+				1,
+				MANTA
+			),
+			Error::<Runtime>::ValidatorNotBonded
+		);
+	});
+}
@@ -1111,3 +1111,131 @@ fn charge_host_fee_and_tune_vtoken_exchange_rate_works() {
 		assert_eq!(Currencies::total_issuance(VBNC), 1200);
 	});
 }
+
+#[test]
+fn rebalance_validators_proposes_moves_within_cap() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		let validator_location = |id: u8| MultiLocation {
+			parents: 0,
+			interior: X1(AccountId32 {
+				network: None,
+				id: [id; 32],
+			}),
+		};
+		let (validator_0, validator_1, validator_2) = (
+			validator_location(1),
+			validator_location(2),
+			validator_location(3),
+		);
+		let subaccount_0_location = validator_location(9);
+
+		Validators::<Runtime>::insert(
+			BNC,
+			BoundedVec::try_from(vec![validator_0, validator_1, validator_2]).unwrap(),
+		);
+		// validator_2 has no metrics and scores zero.
+		ValidatorsMetrics::<Runtime>::insert(
+			BNC,
+			validator_0,
+			ValidatorMetrics {
+				commission: Permill::from_percent(10),
+				uptime: Permill::from_percent(100),
+			},
+		);
+		ValidatorsMetrics::<Runtime>::insert(
+			BNC,
+			validator_1,
+			ValidatorMetrics {
+				commission: Permill::from_percent(5),
+				uptime: Permill::from_percent(100),
+			},
+		);
+
+		let mut delegation_set: BTreeMap<MultiLocation, BalanceOf<Runtime>> = BTreeMap::new();
+		delegation_set.insert(validator_0, 700);
+		delegation_set.insert(validator_1, 200);
+		delegation_set.insert(validator_2, 100);
+		DelegatorLedgers::<Runtime>::insert(
+			BNC,
+			subaccount_0_location,
+			Ledger::ParachainStaking(OneToManyLedger {
+				account: subaccount_0_location,
+				total: 1000,
+				less_total: 0,
+				delegations: delegation_set,
+				requests: vec![],
+				request_briefs: BTreeMap::new(),
+				status: OneToManyDelegatorStatus::Active,
+			}),
+		);
+		MinimumsAndMaximums::<Runtime>::insert(
+			BNC,
+			MinimumsMaximums {
+				delegator_bonded_minimum: 0,
+				bond_extra_minimum: 0,
+				unbond_minimum: 0,
+				rebond_minimum: 0,
+				unbond_record_maximum: 1,
+				validators_back_maximum: 100,
+				delegator_active_staking_maximum: 200_000_000_000_000,
+				validators_reward_maximum: 300,
+				delegation_amount_minimum: 0,
+				delegators_maximum: 100,
+				validators_maximum: 300,
+			},
+		);
+
+		assert_ok!(Slp::set_validator_concentration_cap(
+			RuntimeOrigin::signed(ALICE),
+			BNC,
+			Some(Permill::from_percent(50))
+		));
+		assert_ok!(Slp::rebalance_validators(
+			RuntimeOrigin::signed(ALICE),
+			BNC,
+			Box::new(subaccount_0_location),
+			None
+		));
+		System::assert_last_event(RuntimeEvent::Slp(Event::RebalanceProposed {
+			currency_id: BNC,
+			delegator_id: subaccount_0_location,
+			moves: vec![
+				(validator_0, validator_1, 200),
+				(validator_2, validator_1, 100),
+			],
+		}));
+
+		// Once the cap is loosened, the remaining moves only drain validator_2.
+		ValidatorConcentrationCap::<Runtime>::insert(BNC, Permill::from_percent(100));
+		assert_ok!(Slp::rebalance_validators(
+			RuntimeOrigin::signed(ALICE),
+			BNC,
+			Box::new(subaccount_0_location),
+			None
+		));
+		System::assert_last_event(RuntimeEvent::Slp(Event::RebalanceProposed {
+			currency_id: BNC,
+			delegator_id: subaccount_0_location,
+			moves: vec![(validator_2, validator_1, 100)],
+		}));
+
+		ValidatorsMetrics::<Runtime>::insert(
+			BNC,
+			validator_2,
+			ValidatorMetrics {
+				commission: Permill::from_percent(20),
+				uptime: Permill::from_percent(100),
+			},
+		);
+		assert_noop!(
+			Slp::rebalance_validators(
+				RuntimeOrigin::signed(ALICE),
+				BNC,
+				Box::new(subaccount_0_location),
+				None
+			),
+			Error::<Runtime>::NoRebalanceNeeded
+		);
+	});
+}
@@ -180,6 +180,15 @@ pub trait StakingAgent<
 		currency_id: CurrencyId,
 	) -> Result<(), Error>;
 
+	/// Deduct a slash suffered through a validator from the delegator ledger.
+	fn slash_delegator_ledger(
+		&self,
+		who: &MultiLocation,
+		validator: &MultiLocation,
+		amount: Balance,
+		currency_id: CurrencyId,
+	) -> Result<(), Error>;
+
 	/// ************************************
 	/// Abstraction over a fee manager for charging fee from the origin chain(Bifrost)
 	/// or deposit fee reserves for the destination chain nominator accounts.
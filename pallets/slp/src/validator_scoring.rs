@@ -0,0 +1,213 @@
+// This file is part of Bifrost.
+
+// Copyright (C) Liebi Technologies PTE. LTD.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{
+	pallet::Error, BalanceOf, Config, DelegatorLedgers, Ledger, MinimumsAndMaximums, Pallet,
+	ValidatorConcentrationCap, Validators, ValidatorsByDelegator, ValidatorsMetrics,
+};
+use bifrost_primitives::CurrencyId;
+use frame_support::ensure;
+use sp_arithmetic::{
+	per_things::{PerThing, Permill},
+	traits::{Saturating, Zero},
+};
+use sp_std::{collections::btree_map::BTreeMap, prelude::*};
+use xcm::v3::MultiLocation;
+
+// Validator scoring and rebalancing.
+impl<T: Config> Pallet<T> {
+	/// Score a validator as `(1 - commission) * uptime`. Validators without metrics or
+	/// recently slashed score zero.
+	pub fn validator_score(currency_id: CurrencyId, validator: &MultiLocation) -> Permill {
+		if Self::is_recently_slashed(currency_id, validator) {
+			return Permill::zero();
+		}
+
+		ValidatorsMetrics::<T>::get(currency_id, validator).map_or(Permill::zero(), |metrics| {
+			Permill::one()
+				.saturating_sub(metrics.commission)
+				.saturating_mul(metrics.uptime)
+		})
+	}
+
+	/// Validators in service ranked by score, best first. Validators scoring zero are left out.
+	pub fn ranked_validators(currency_id: CurrencyId) -> Vec<(MultiLocation, Permill)> {
+		let mut ranked: Vec<(MultiLocation, Permill)> = Validators::<T>::get(currency_id)
+			.unwrap_or_default()
+			.into_iter()
+			.map(|validator| (validator, Self::validator_score(currency_id, &validator)))
+			.filter(|(_, score)| !score.is_zero())
+			.collect();
+		// The sort is stable, so equal scores keep the order of the validator set.
+		ranked.sort_by(|(_, a), (_, b)| b.cmp(a));
+
+		ranked
+	}
+
+	/// Amount delegated to each validator by all delegators of a currency but `excluded`, and
+	/// the total amount delegated by all of them. The active amount of a substrate delegator is
+	/// split evenly among the validators it nominates. At most `delegators_maximum` delegators
+	/// are scanned, which bounds the weight of the callers.
+	pub(crate) fn delegated_amounts(
+		currency_id: CurrencyId,
+		excluded: Option<&MultiLocation>,
+	) -> Result<(BTreeMap<MultiLocation, BalanceOf<T>>, BalanceOf<T>), Error<T>> {
+		let delegators_maximum = MinimumsAndMaximums::<T>::get(currency_id)
+			.ok_or(Error::<T>::MinimumsAndMaximumsNotExist)?
+			.delegators_maximum as usize;
+		let mut delegated: BTreeMap<MultiLocation, BalanceOf<T>> = BTreeMap::new();
+		let mut total: BalanceOf<T> = Zero::zero();
+
+		for (index, (delegator, ledger)) in
+			DelegatorLedgers::<T>::iter_prefix(currency_id).enumerate()
+		{
+			ensure!(
+				index < delegators_maximum,
+				Error::<T>::ExceedDelegatorsMaximum
+			);
+			let amounts: Vec<(MultiLocation, BalanceOf<T>)> = match ledger {
+				Ledger::Substrate(substrate_ledger) => {
+					total = total.saturating_add(substrate_ledger.active);
+					let validators =
+						ValidatorsByDelegator::<T>::get(currency_id, delegator).unwrap_or_default();
+					if validators.is_empty() {
+						continue;
+					}
+					let share =
+						substrate_ledger.active / BalanceOf::<T>::from(validators.len() as u32);
+					validators
+						.into_iter()
+						.map(|validator| (validator, share))
+						.collect()
+				}
+				Ledger::ParachainStaking(parachain_staking_ledger) => {
+					let amounts: Vec<(MultiLocation, BalanceOf<T>)> =
+						parachain_staking_ledger.delegations.into_iter().collect();
+					total = amounts
+						.iter()
+						.fold(total, |total, (_, amount)| total.saturating_add(*amount));
+					amounts
+				}
+				_ => continue,
+			};
+
+			if excluded == Some(&delegator) {
+				continue;
+			}
+			for (validator, amount) in amounts {
+				let entry = delegated.entry(validator).or_insert_with(Zero::zero);
+				*entry = entry.saturating_add(amount);
+			}
+		}
+
+		Ok((delegated, total))
+	}
+
+	/// Select the best ranked validators a substrate delegator can nominate with its `active`
+	/// amount without pushing any of them over the concentration cap. The `active` amount is
+	/// split evenly among the targets, so fewer targets are tried until all of them can take
+	/// their share.
+	pub(crate) fn select_nomination_targets(
+		currency_id: CurrencyId,
+		who: &MultiLocation,
+		active: BalanceOf<T>,
+	) -> Result<Vec<MultiLocation>, Error<T>> {
+		let cap = ValidatorConcentrationCap::<T>::get(currency_id)
+			.ok_or(Error::<T>::ValidatorConcentrationCapNotSet)?;
+		let max_targets = MinimumsAndMaximums::<T>::get(currency_id)
+			.ok_or(Error::<T>::MinimumsAndMaximumsNotExist)?
+			.validators_back_maximum;
+		ensure!(max_targets > 0, Error::<T>::NoEligibleValidator);
+
+		let (delegated, total) = Self::delegated_amounts(currency_id, Some(who))?;
+		let cap_amount = cap.mul_floor(total);
+		let ranked = Self::ranked_validators(currency_id);
+
+		for targets_count in (1..=max_targets.min(ranked.len() as u32)).rev() {
+			let share = active / BalanceOf::<T>::from(targets_count);
+			let targets: Vec<MultiLocation> = ranked
+				.iter()
+				.filter(|(validator, _)| {
+					delegated
+						.get(validator)
+						.copied()
+						.unwrap_or_else(Zero::zero)
+						.saturating_add(share)
+						<= cap_amount
+				})
+				.map(|(validator, _)| *validator)
+				.take(targets_count as usize)
+				.collect();
+			if targets.len() == targets_count as usize {
+				return Ok(targets);
+			}
+		}
+
+		Err(Error::<T>::NoEligibleValidator)
+	}
+
+	/// Propose moves (from validator, to validator, amount) of a delegator's delegations away
+	/// from validators scoring zero or over the concentration cap, towards the best ranked
+	/// validators with room left under the cap.
+	pub(crate) fn propose_delegation_moves(
+		currency_id: CurrencyId,
+		delegations: &BTreeMap<MultiLocation, BalanceOf<T>>,
+	) -> Result<Vec<(MultiLocation, MultiLocation, BalanceOf<T>)>, Error<T>> {
+		let cap = ValidatorConcentrationCap::<T>::get(currency_id)
+			.ok_or(Error::<T>::ValidatorConcentrationCapNotSet)?;
+
+		let (mut delegated, total) = Self::delegated_amounts(currency_id, None)?;
+		let cap_amount = cap.mul_floor(total);
+		let ranked = Self::ranked_validators(currency_id);
+
+		let mut moves = Vec::new();
+		for (source, amount) in delegations.iter() {
+			let mut excess = if Self::validator_score(currency_id, source).is_zero() {
+				*amount
+			} else {
+				delegated
+					.get(source)
+					.copied()
+					.unwrap_or_else(Zero::zero)
+					.saturating_sub(cap_amount)
+					.min(*amount)
+			};
+
+			for (destination, _) in ranked.iter() {
+				if excess.is_zero() {
+					break;
+				}
+				if destination == source {
+					continue;
+				}
+
+				let destination_amount = delegated.entry(*destination).or_insert_with(Zero::zero);
+				let moved = cap_amount.saturating_sub(*destination_amount).min(excess);
+				if moved.is_zero() {
+					continue;
+				}
+
+				*destination_amount = destination_amount.saturating_add(moved);
+				excess = excess.saturating_sub(moved);
+				moves.push((*source, *destination, moved));
+			}
+		}
+
+		Ok(moves)
+	}
+}
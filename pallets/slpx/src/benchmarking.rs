@@ -162,6 +162,80 @@ mod benchmarks {
 		);
 	}
 
+	#[benchmark]
+	fn cancel_order() {
+		let (caller, receiver) = init_whitelist::<T>();
+		assert_ok!(Pallet::<T>::mint(
+			RawOrigin::Signed(caller.clone()).into(),
+			receiver,
+			KSM,
+			TargetChain::Astar(receiver),
+			BoundedVec::default(),
+		));
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller), 0u64);
+
+		assert!(OrderQueue::<T>::get().is_empty());
+	}
+
+	#[benchmark]
+	fn set_order_expiry() {
+		DelayBlock::<T>::put(BlockNumberFor::<T>::from(2u32));
+
+		#[extrinsic_call]
+		_(RawOrigin::Root, BlockNumberFor::<T>::from(10u32));
+
+		assert_eq!(OrderExpiry::<T>::get(), BlockNumberFor::<T>::from(10u32));
+	}
+
+	#[benchmark]
+	fn register_chain() {
+		let chain_info = ChainInfo {
+			evm_chain_id: Some(1287),
+			account_format: AccountFormat::AccountKey20,
+			callback_style: CallbackStyle::EthereumXcm { pallet_index: 38 },
+			fee_asset: KSM,
+		};
+
+		#[extrinsic_call]
+		_(RawOrigin::Root, 2004, chain_info);
+
+		assert_eq!(ChainRegistry::<T>::get(2004), Some(chain_info));
+	}
+
+	#[benchmark]
+	fn deregister_chain() {
+		let chain_info = ChainInfo {
+			evm_chain_id: Some(1287),
+			account_format: AccountFormat::AccountKey20,
+			callback_style: CallbackStyle::EthereumXcm { pallet_index: 38 },
+			fee_asset: KSM,
+		};
+		ChainRegistry::<T>::insert(2004, chain_info);
+
+		#[extrinsic_call]
+		_(RawOrigin::Root, 2004);
+
+		assert!(!ChainRegistry::<T>::contains_key(2004));
+	}
+
+	#[benchmark]
+	fn set_xcm_oracle_chain() {
+		let chain_info = ChainInfo {
+			evm_chain_id: Some(1287),
+			account_format: AccountFormat::AccountKey20,
+			callback_style: CallbackStyle::EthereumXcm { pallet_index: 38 },
+			fee_asset: KSM,
+		};
+		ChainRegistry::<T>::insert(2004, chain_info);
+
+		#[extrinsic_call]
+		_(RawOrigin::Root, Some(2004));
+
+		assert_eq!(XcmOracleChain::<T>::get(), Some(2004));
+	}
+
 	//   `cargo test -p pallet-example-basic --all-features`, you will see one line per case:
 	impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::Test);
 }
@@ -18,9 +18,10 @@
 
 #![cfg_attr(not(feature = "std"), no_std)]
 use crate::types::{
-	AccountIdOf, BalanceOf, CurrencyIdOf, EthereumCallConfiguration, EthereumXcmCall,
-	EthereumXcmTransaction, EthereumXcmTransactionV2, MoonbeamCall, Order, OrderCaller, OrderType,
-	SupportChain, TargetChain, EVM_FUNCTION_SELECTOR, MAX_GAS_LIMIT,
+	AccountFormat, AccountIdOf, BalanceOf, CallbackStyle, ChainAccount, ChainInfo, CurrencyIdOf,
+	EthereumCallConfiguration, EthereumXcmCall, EthereumXcmTransaction, EthereumXcmTransactionV2,
+	Order, OrderCaller, OrderType, SupportChain, TargetChain, EVM_FUNCTION_SELECTOR, MAX_GAS_LIMIT,
+	MOONBEAM_ETHEREUM_XCM_PALLET_INDEX,
 };
 use bifrost_asset_registry::AssetMetadata;
 use bifrost_primitives::{
@@ -51,6 +52,7 @@ use sp_core::{Hasher, H160, U256};
 use sp_runtime::{
 	traits::{
 		AccountIdConversion, BlakeTwo256, BlockNumberProvider, CheckedSub, UniqueSaturatedFrom,
+		Zero,
 	},
 	BoundedVec, DispatchError,
 };
@@ -85,7 +87,7 @@ pub mod pallet {
 	use frame_system::ensure_root;
 	use sp_runtime::traits::BlockNumberProvider;
 
-	const STORAGE_VERSION: StorageVersion = StorageVersion::new(2);
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(3);
 
 	#[pallet::pallet]
 	#[pallet::storage_version(STORAGE_VERSION)]
@@ -214,6 +216,52 @@ pub mod pallet {
 		OrderFailed {
 			order: Order<AccountIdOf<T>, CurrencyIdOf<T>, BalanceOf<T>, BlockNumberFor<T>>,
 		},
+		/// Order cancelled and refunded
+		OrderCancelled {
+			order: Order<AccountIdOf<T>, CurrencyIdOf<T>, BalanceOf<T>, BlockNumberFor<T>>,
+		},
+		/// Order expired before being handled
+		OrderExpired {
+			order: Order<AccountIdOf<T>, CurrencyIdOf<T>, BalanceOf<T>, BlockNumberFor<T>>,
+		},
+		/// The assets of the order have been refunded to the target chain
+		OrderRefunded {
+			/// The id of the order
+			order_id: u64,
+			/// The currency id of the refunded token
+			currency_id: CurrencyId,
+			/// The refunded amount
+			currency_amount: BalanceOf<T>,
+		},
+		/// Failed to refund the assets of the order
+		OrderRefundFailed {
+			/// The id of the order
+			order_id: u64,
+			/// The error of the refund
+			error: DispatchError,
+		},
+		/// Set the order expiry
+		SetOrderExpiry {
+			/// The number of blocks after which a pending order expires
+			order_expiry: BlockNumberFor<T>,
+		},
+		/// Register a chain or update its information
+		ChainRegistered {
+			/// The parachain id of the chain
+			para_id: u32,
+			/// The information of the chain
+			chain_info: ChainInfo<CurrencyId>,
+		},
+		/// Remove a chain from the chain registry
+		ChainDeregistered {
+			/// The parachain id of the chain
+			para_id: u32,
+		},
+		/// Set the chain called back by the xcm oracle
+		SetXcmOracleChain {
+			/// The parachain id of the chain, Moonbeam if none
+			para_id: Option<u32>,
+		},
 		/// Xcm oracle failed
 		XcmOracleFailed { error: DispatchError },
 		/// Withdraw xcm fee
@@ -252,6 +300,20 @@ pub mod pallet {
 		ErrorChargeFee,
 		ErrorArguments,
 		Unsupported,
+		/// The order is not in the order queue
+		OrderNotFound,
+		/// Only the derivative account or the owner of the order can cancel it
+		NotOrderOwner,
+		/// The chain is not in the chain registry
+		ChainNotRegistered,
+		/// The account format of the receiver does not match the registered chain
+		InvalidAccountFormat,
+		/// The EVM chain id is already used by another registered chain
+		EvmChainIdAlreadyExists,
+		/// The order expiry must be longer than the delay block
+		InvalidOrderExpiry,
+		/// The chain is not called back via ethereum-xcm
+		InvalidCallbackStyle,
 	}
 
 	/// Contract whitelist
@@ -304,6 +366,24 @@ pub mod pallet {
 	#[pallet::storage]
 	pub type DelayBlock<T: Config> = StorageValue<_, BlockNumberFor<T>, ValueQuery>;
 
+	/// The id of the next order
+	#[pallet::storage]
+	pub type NextOrderId<T: Config> = StorageValue<_, u64, ValueQuery>;
+
+	/// Number of blocks after which a pending order expires and is refunded, zero means never
+	#[pallet::storage]
+	pub type OrderExpiry<T: Config> = StorageValue<_, BlockNumberFor<T>, ValueQuery>;
+
+	/// Chains registered at runtime
+	/// ParaId -> ChainInfo
+	#[pallet::storage]
+	pub type ChainRegistry<T: Config> =
+		StorageMap<_, Blake2_128Concat, u32, ChainInfo<CurrencyId>, OptionQuery>;
+
+	/// The registered chain called back by the xcm oracle, Moonbeam if not set
+	#[pallet::storage]
+	pub type XcmOracleChain<T: Config> = StorageValue<_, u32, OptionQuery>;
+
 	#[pallet::hooks]
 	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
 		fn on_idle(_: BlockNumberFor<T>, limit: Weight) -> Weight {
@@ -637,6 +717,11 @@ pub mod pallet {
 		) -> DispatchResultWithPostInfo {
 			// Check the validity of origin
 			T::ControlOrigin::ensure_origin(origin)?;
+			let order_expiry = OrderExpiry::<T>::get();
+			ensure!(
+				order_expiry.is_zero() || order_expiry > delay_block,
+				Error::<T>::InvalidOrderExpiry
+			);
 			DelayBlock::<T>::put(delay_block);
 			Self::deposit_event(Event::SetDelayBlock { delay_block });
 			Ok(().into())
@@ -756,6 +841,128 @@ pub mod pallet {
 			)
 		}
 
+		/// Set the order expiry, pending orders older than the expiry are refunded to the target
+		/// chain instead of being executed. The expiry must be longer than the delay block, zero
+		/// disables it.
+		/// Parameters:
+		/// - `order_expiry`: The number of blocks after which a pending order expires
+		#[pallet::call_index(15)]
+		#[pallet::weight(T::WeightInfo::set_order_expiry())]
+		pub fn set_order_expiry(
+			origin: OriginFor<T>,
+			order_expiry: BlockNumberFor<T>,
+		) -> DispatchResultWithPostInfo {
+			// Check the validity of origin
+			T::ControlOrigin::ensure_origin(origin)?;
+			ensure!(
+				order_expiry.is_zero() || order_expiry > DelayBlock::<T>::get(),
+				Error::<T>::InvalidOrderExpiry
+			);
+			OrderExpiry::<T>::put(order_expiry);
+			Self::deposit_event(Event::SetOrderExpiry { order_expiry });
+			Ok(().into())
+		}
+
+		/// Cancel a pending order and refund its assets to the target chain
+		/// Parameters:
+		/// - `order_id`: The id of the order
+		#[pallet::call_index(16)]
+		#[pallet::weight(<T as Config>::WeightInfo::cancel_order())]
+		pub fn cancel_order(origin: OriginFor<T>, order_id: u64) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+
+			let mut order = OrderQueue::<T>::try_mutate(
+				|order_queue| -> Result<Order<_, _, _, _>, DispatchError> {
+					let index = order_queue
+						.iter()
+						.position(|order| order.order_id == order_id)
+						.ok_or(Error::<T>::OrderNotFound)?;
+					ensure!(
+						Self::is_order_owner(&order_queue[index], &who),
+						Error::<T>::NotOrderOwner
+					);
+					Ok(order_queue.remove(index))
+				},
+			)?;
+
+			Self::update_order_amount(&mut order);
+			Self::refund_order(&order)?;
+			Self::deposit_event(Event::<T>::OrderCancelled { order });
+			Ok(().into())
+		}
+
+		/// Register a chain or update its information. Redeem orders to the chain require its
+		/// parachain to be allowlisted as a redeem destination of vtoken-minting.
+		/// Parameters:
+		/// - `para_id`: The parachain id of the chain
+		/// - `chain_info`: The information of the chain
+		#[pallet::call_index(17)]
+		#[pallet::weight(T::WeightInfo::register_chain())]
+		pub fn register_chain(
+			origin: OriginFor<T>,
+			para_id: u32,
+			chain_info: ChainInfo<CurrencyId>,
+		) -> DispatchResultWithPostInfo {
+			// Check the validity of origin
+			T::ControlOrigin::ensure_origin(origin)?;
+			if let Some(evm_chain_id) = chain_info.evm_chain_id {
+				ensure!(
+					Self::match_source_chain_id(evm_chain_id)
+						.map_or(true, |chain| chain == SupportChain::Registered(para_id)),
+					Error::<T>::EvmChainIdAlreadyExists
+				);
+			}
+			ChainRegistry::<T>::insert(para_id, chain_info);
+			Self::deposit_event(Event::ChainRegistered {
+				para_id,
+				chain_info,
+			});
+			Ok(().into())
+		}
+
+		/// Remove a chain from the chain registry
+		/// Parameters:
+		/// - `para_id`: The parachain id of the chain
+		#[pallet::call_index(18)]
+		#[pallet::weight(T::WeightInfo::deregister_chain())]
+		pub fn deregister_chain(origin: OriginFor<T>, para_id: u32) -> DispatchResultWithPostInfo {
+			// Check the validity of origin
+			T::ControlOrigin::ensure_origin(origin)?;
+			ensure!(
+				ChainRegistry::<T>::contains_key(para_id),
+				Error::<T>::ChainNotRegistered
+			);
+			ChainRegistry::<T>::remove(para_id);
+			Self::deposit_event(Event::ChainDeregistered { para_id });
+			Ok(().into())
+		}
+
+		/// Set the chain called back by the xcm oracle, it must be registered with the
+		/// ethereum-xcm callback style.
+		/// Parameters:
+		/// - `para_id`: The parachain id of the chain, Moonbeam if none
+		#[pallet::call_index(19)]
+		#[pallet::weight(T::WeightInfo::set_xcm_oracle_chain())]
+		pub fn set_xcm_oracle_chain(
+			origin: OriginFor<T>,
+			para_id: Option<u32>,
+		) -> DispatchResultWithPostInfo {
+			// Check the validity of origin
+			T::ControlOrigin::ensure_origin(origin)?;
+			if let Some(para_id) = para_id {
+				ensure!(
+					matches!(
+						Self::chain_info(para_id)?.callback_style,
+						CallbackStyle::EthereumXcm { .. }
+					),
+					Error::<T>::InvalidCallbackStyle
+				);
+			}
+			XcmOracleChain::<T>::set(para_id);
+			Self::deposit_event(Event::SetXcmOracleChain { para_id });
+			Ok(().into())
+		}
+
 		// TODO: Substrate user create order
 		// #[pallet::call_index(14)]
 		// #[pallet::weight(<T as Config>::WeightInfo::mint())]
@@ -793,8 +1000,84 @@ impl<T: Config> Pallet<T> {
 		{
 			Some(SupportChain::Moonbeam)
 		} else {
-			None
+			ChainRegistry::<T>::iter()
+				.find(|(_, chain_info)| chain_info.evm_chain_id == Some(source_chain_id))
+				.map(|(para_id, _)| SupportChain::Registered(para_id))
+		}
+	}
+
+	/// Get the information of a registered chain
+	fn chain_info(para_id: u32) -> Result<ChainInfo<CurrencyId>, Error<T>> {
+		ChainRegistry::<T>::get(para_id).ok_or(Error::<T>::ChainNotRegistered)
+	}
+
+	/// According to the chain registry, return the location of the account on the chain
+	fn registered_chain_location(
+		para_id: u32,
+		account: &ChainAccount<T::AccountId>,
+	) -> Result<Location, Error<T>> {
+		let chain_info = Self::chain_info(para_id)?;
+		ensure!(
+			account.account_format() == chain_info.account_format,
+			Error::<T>::InvalidAccountFormat
+		);
+		let junction = match account {
+			ChainAccount::AccountId32(account_id) => AccountId32 {
+				network: None,
+				id: account_id
+					.encode()
+					.try_into()
+					.map_err(|_| Error::<T>::ErrorEncode)?,
+			},
+			ChainAccount::AccountKey20(key) => AccountKey20 {
+				network: None,
+				key: key.to_fixed_bytes(),
+			},
+		};
+		Ok(Location::new(1, [Parachain(para_id), junction]))
+	}
+
+	/// The chain called back by the xcm oracle, according to its callback style in the chain
+	/// registry. Returns the parachain id, the pallet index of ethereum-xcm and the fee asset
+	/// of the chain. An unregistered Moonbeam uses its well-known pallet index.
+	fn xcm_oracle_callback() -> Result<(u32, u8, CurrencyId), Error<T>> {
+		let moonbeam_para_id = T::VtokenMintingInterface::get_moonbeam_parachain_id();
+		let para_id = XcmOracleChain::<T>::get().unwrap_or(moonbeam_para_id);
+		match ChainRegistry::<T>::get(para_id) {
+			Some(ChainInfo {
+				callback_style: CallbackStyle::EthereumXcm { pallet_index },
+				fee_asset,
+				..
+			}) => Ok((para_id, pallet_index, fee_asset)),
+			Some(_) => Err(Error::<T>::InvalidCallbackStyle),
+			None if para_id == moonbeam_para_id => {
+				let fee_asset = if T::ParachainId::get() == Id::from(BifrostKusamaChainId::get()) {
+					MOVR
+				} else {
+					GLMR
+				};
+				Ok((para_id, MOONBEAM_ETHEREUM_XCM_PALLET_INDEX, fee_asset))
+			}
+			None => Err(Error::<T>::ChainNotRegistered),
+		}
+	}
+
+	/// The location of `fee_asset` as seen from the chain `para_id`
+	fn fee_asset_location(para_id: u32, fee_asset: CurrencyId) -> Result<Location, Error<T>> {
+		if para_id == T::VtokenMintingInterface::get_moonbeam_parachain_id()
+			&& (fee_asset == GLMR || fee_asset == MOVR)
+		{
+			// Moonbeam Native Token
+			return Ok(Location::new(0, [PalletInstance(10)]));
 		}
+		let location =
+			T::CurrencyIdConvert::get_location(&fee_asset).ok_or(Error::<T>::Unsupported)?;
+		let (interior, first) = location.split_first_interior();
+		ensure!(
+			interior.parent_count() == 1 && first == Some(Parachain(para_id)),
+			Error::<T>::Unsupported
+		);
+		Ok(Location::new(0, interior.interior().clone()))
 	}
 
 	/// According to currency_id, return the order type
@@ -846,6 +1129,15 @@ impl<T: Config> Pallet<T> {
 					},
 				],
 			),
+			SupportChain::Registered(para_id) => {
+				let account = match Self::chain_info(para_id)?.account_format {
+					AccountFormat::AccountId32 => {
+						ChainAccount::AccountId32(Self::h160_to_account_id(&contract_address))
+					}
+					AccountFormat::AccountKey20 => ChainAccount::AccountKey20(contract_address),
+				};
+				Self::registered_chain_location(para_id, &account)?
+			}
 			_ => {
 				ensure!(false, Error::<T>::Unsupported);
 				Location::default()
@@ -873,8 +1165,17 @@ impl<T: Config> Pallet<T> {
 		target_chain: TargetChain<T::AccountId>,
 	) -> DispatchResultWithPostInfo {
 		let order_type = Self::order_type(currency_id)?;
+		if let TargetChain::Registered(para_id, receiver) = &target_chain {
+			Self::registered_chain_location(*para_id, receiver)?;
+		}
 		let derivative_account = Self::frontier_derivative_account(&source_chain_caller);
+		let order_id = NextOrderId::<T>::mutate(|id| {
+			let order_id = *id;
+			*id = id.saturating_add(1);
+			order_id
+		});
 		let order = Order {
+			order_id,
 			create_block_number: T::BlockNumberProvider::current_block_number(),
 			order_type,
 			currency_id,
@@ -899,20 +1200,16 @@ impl<T: Config> Pallet<T> {
 	}
 
 	fn send_xcm_to_set_token_amount(
+		para_id: u32,
+		fee_asset: CurrencyId,
 		call: Vec<u8>,
 		xcm_weight: Weight,
 		xcm_fee: u128,
 	) -> DispatchResult {
-		let dest = Location::new(
-			1,
-			[Parachain(
-				T::VtokenMintingInterface::get_moonbeam_parachain_id(),
-			)],
-		);
+		let dest = Location::new(1, [Parachain(para_id)]);
 
-		// Moonbeam Native Token
 		let asset = Asset {
-			id: AssetId::from(Location::new(0, [PalletInstance(10)])),
+			id: AssetId::from(Self::fee_asset_location(para_id, fee_asset)?),
 			fun: Fungible(xcm_fee),
 		};
 
@@ -968,7 +1265,10 @@ impl<T: Config> Pallet<T> {
 		call
 	}
 
+	/// Encode the `EthereumXcm::transact` call of a chain whose ethereum-xcm pallet is at
+	/// `pallet_index`
 	pub fn encode_transact_call(
+		pallet_index: u8,
 		contract: H160,
 		currency_id: CurrencyId,
 		token_amount: BalanceOf<T>,
@@ -982,7 +1282,9 @@ impl<T: Config> Pallet<T> {
 			input: BoundedVec::try_from(ethereum_call).map_err(|_| Error::<T>::ErrorEncode)?,
 			access_list: None,
 		});
-		Ok(MoonbeamCall::EthereumXcm(EthereumXcmCall::Transact(transaction)).encode())
+		let mut call = vec![pallet_index];
+		EthereumXcmCall::Transact(transaction).encode_to(&mut call);
+		Ok(call)
 	}
 
 	/// Check if the signer is in the whitelist
@@ -993,25 +1295,31 @@ impl<T: Config> Pallet<T> {
 	) -> Result<(OrderCaller<AccountIdOf<T>>, AccountIdOf<T>, AccountIdOf<T>), DispatchError> {
 		let bifrost_chain_caller = ensure_signed(origin)?;
 
-		match target_chain {
-			TargetChain::Hydradx(_) | TargetChain::Manta(_) | TargetChain::Interlay(_) => Ok((
+		let is_substrate_caller = match target_chain {
+			TargetChain::Hydradx(_) | TargetChain::Manta(_) | TargetChain::Interlay(_) => true,
+			TargetChain::Registered(para_id, _) => {
+				Self::chain_info(*para_id)?.callback_style == CallbackStyle::SubstrateRemark
+			}
+			_ => false,
+		};
+
+		if is_substrate_caller {
+			Ok((
 				OrderCaller::Substrate(bifrost_chain_caller.clone()),
 				bifrost_chain_caller.clone(),
 				bifrost_chain_caller,
-			)),
-			_ => {
-				let whitelist_account_ids =
-					WhitelistAccountId::<T>::get(target_chain.support_chain());
-				ensure!(
-					whitelist_account_ids.contains(&bifrost_chain_caller),
-					Error::<T>::AccountNotFound
-				);
-				Ok((
-					OrderCaller::Evm(evm_caller),
-					Self::h160_to_account_id(&evm_caller),
-					bifrost_chain_caller,
-				))
-			}
+			))
+		} else {
+			let whitelist_account_ids = WhitelistAccountId::<T>::get(target_chain.support_chain());
+			ensure!(
+				whitelist_account_ids.contains(&bifrost_chain_caller),
+				Error::<T>::AccountNotFound
+			);
+			Ok((
+				OrderCaller::Evm(evm_caller),
+				Self::h160_to_account_id(&evm_caller),
+				bifrost_chain_caller,
+			))
 		}
 	}
 
@@ -1107,19 +1415,40 @@ impl<T: Config> Pallet<T> {
 					},
 				],
 			),
+			TargetChain::Registered(para_id, receiver) => {
+				Self::registered_chain_location(*para_id, receiver)?
+			}
 		};
 
-		if let TargetChain::Moonbeam(_) = target_chain {
-			if SupportXcmFeeList::<T>::get().contains(&currency_id) {
-				T::XcmTransfer::transfer(caller, currency_id, amount, dest, Unlimited)?;
-			} else {
-				let fee_amount = Self::get_moonbeam_transfer_to_fee();
-				T::MultiCurrency::transfer(BNC, evm_contract_account_id, &caller, fee_amount)?;
-				let assets = vec![(currency_id, amount), (BNC, fee_amount)];
+		let fee = match target_chain {
+			TargetChain::Moonbeam(_) => Some((BNC, Self::get_moonbeam_transfer_to_fee())),
+			TargetChain::Registered(para_id, _) => {
+				let fee_asset = Self::chain_info(*para_id)?.fee_asset;
+				(fee_asset != currency_id).then(|| {
+					let fee_amount = TransferToFee::<T>::get(SupportChain::Registered(*para_id))
+						.unwrap_or_else(|| Self::get_default_fee(fee_asset));
+					(fee_asset, fee_amount)
+				})
+			}
+			_ => None,
+		};
+
+		match fee {
+			Some((fee_asset, fee_amount))
+				if !SupportXcmFeeList::<T>::get().contains(&currency_id) =>
+			{
+				T::MultiCurrency::transfer(
+					fee_asset,
+					evm_contract_account_id,
+					&caller,
+					fee_amount,
+				)?;
+				let assets = vec![(currency_id, amount), (fee_asset, fee_amount)];
 				T::XcmTransfer::transfer_multicurrencies(caller, assets, 1, dest, Unlimited)?;
 			}
-		} else {
-			T::XcmTransfer::transfer(caller, currency_id, amount, dest, Unlimited)?;
+			_ => {
+				T::XcmTransfer::transfer(caller, currency_id, amount, dest, Unlimited)?;
+			}
 		}
 		Ok(())
 	}
@@ -1199,6 +1528,9 @@ impl<T: Config> Pallet<T> {
 					TargetChain::Hydradx(receiver) => RedeemType::Hydradx(receiver),
 					TargetChain::Interlay(receiver) => RedeemType::Interlay(receiver),
 					TargetChain::Manta(receiver) => RedeemType::Manta(receiver),
+					TargetChain::Registered(para_id, receiver) => RedeemType::Location(
+						Self::registered_chain_location(para_id, &receiver)?.into(),
+					),
 				};
 				T::VtokenMintingInterface::slpx_redeem(
 					order.derivative_account.clone(),
@@ -1225,37 +1557,106 @@ impl<T: Config> Pallet<T> {
 
 			if current_block_number - order_queue[0].create_block_number >= DelayBlock::<T>::get() {
 				let mut order = order_queue.remove(0);
-				// For compatibility with older versions
-				if order.currency_amount == Default::default() {
-					order.currency_amount = T::MultiCurrency::free_balance(
-						order.currency_id,
-						&order.derivative_account,
-					);
+				Self::update_order_amount(&mut order);
+
+				let order_expiry = OrderExpiry::<T>::get();
+				if !order_expiry.is_zero()
+					&& current_block_number - order.create_block_number >= order_expiry
+				{
+					Self::deposit_event(Event::<T>::OrderExpired {
+						order: order.clone(),
+					});
+					Self::try_refund_order(&order);
 				} else {
-					// Ensure that the currency amount is not greater than the free balance
-					let free_balance = T::MultiCurrency::free_balance(
-						order.currency_id,
-						&order.derivative_account,
-					);
-					order.currency_amount = order.currency_amount.min(free_balance);
+					match Self::handle_order(&order) {
+						Ok(_) => {
+							Self::deposit_event(Event::<T>::OrderHandled {
+								order: order.clone(),
+							});
+						}
+						Err(_) => {
+							Self::deposit_event(Event::<T>::OrderFailed {
+								order: order.clone(),
+							});
+							Self::try_refund_order(&order);
+						}
+					};
 				}
-				match Self::handle_order(&order) {
-					Ok(_) => {
-						Self::deposit_event(Event::<T>::OrderHandled {
-							order: order.clone(),
-						});
-					}
-					Err(_) => {
-						Self::deposit_event(Event::<T>::OrderFailed { order });
-					}
-				};
-				*weight = weight.saturating_add(T::DbWeight::get().reads_writes(12, 8));
+				*weight = weight.saturating_add(T::DbWeight::get().reads_writes(13, 8));
 			};
 			*weight = weight.saturating_add(T::DbWeight::get().reads_writes(2, 0));
 			Ok(())
 		})
 	}
 
+	/// Update the currency amount of the order according to the free balance of the derivative
+	/// account.
+	fn update_order_amount(
+		order: &mut Order<AccountIdOf<T>, CurrencyIdOf<T>, BalanceOf<T>, BlockNumberFor<T>>,
+	) {
+		let free_balance =
+			T::MultiCurrency::free_balance(order.currency_id, &order.derivative_account);
+		// For compatibility with older versions
+		if order.currency_amount == Default::default() {
+			order.currency_amount = free_balance;
+		} else {
+			// Ensure that the currency amount is not greater than the free balance
+			order.currency_amount = order.currency_amount.min(free_balance);
+		}
+	}
+
+	/// Whether the account is the derivative account or the owner of the order
+	fn is_order_owner(
+		order: &Order<AccountIdOf<T>, CurrencyIdOf<T>, BalanceOf<T>, BlockNumberFor<T>>,
+		who: &AccountIdOf<T>,
+	) -> bool {
+		order.derivative_account == *who || order.bifrost_chain_caller == *who
+	}
+
+	/// Refund the assets of the order to the target chain
+	#[transactional]
+	pub fn refund_order(
+		order: &Order<AccountIdOf<T>, CurrencyIdOf<T>, BalanceOf<T>, BlockNumberFor<T>>,
+	) -> DispatchResult {
+		if !order.currency_amount.is_zero() {
+			Self::transfer_to(
+				order.derivative_account.clone(),
+				&order.bifrost_chain_caller,
+				order.currency_id,
+				order.currency_amount,
+				&order.target_chain,
+			)?;
+		}
+		Self::deposit_event(Event::<T>::OrderRefunded {
+			order_id: order.order_id,
+			currency_id: order.currency_id,
+			currency_amount: order.currency_amount,
+		});
+		Ok(())
+	}
+
+	/// Refund the assets of the order, and report the failure by event
+	fn try_refund_order(
+		order: &Order<AccountIdOf<T>, CurrencyIdOf<T>, BalanceOf<T>, BlockNumberFor<T>>,
+	) {
+		if let Err(error) = Self::refund_order(order) {
+			Self::deposit_event(Event::<T>::OrderRefundFailed {
+				order_id: order.order_id,
+				error,
+			});
+		}
+	}
+
+	/// Get the pending orders of the account
+	pub fn get_pending_orders(
+		account: AccountIdOf<T>,
+	) -> Vec<Order<AccountIdOf<T>, CurrencyIdOf<T>, BalanceOf<T>, BlockNumberFor<T>>> {
+		OrderQueue::<T>::get()
+			.into_iter()
+			.filter(|order| Self::is_order_owner(order, &account))
+			.collect()
+	}
+
 	#[transactional]
 	pub fn handle_xcm_oracle(
 		current_block_number: BlockNumberFor<T>,
@@ -1278,7 +1679,9 @@ impl<T: Config> Pallet<T> {
 			let v_currency_total_supply = T::MultiCurrency::total_issuance(v_currency_id);
 
 			if config.last_block + config.period < current_block_number {
+				let (para_id, pallet_index, fee_asset) = Self::xcm_oracle_callback()?;
 				let encoded_call = Self::encode_transact_call(
+					pallet_index,
 					config.contract,
 					currency_id,
 					staking_currency_amount,
@@ -1286,8 +1689,14 @@ impl<T: Config> Pallet<T> {
 				)
 				.map_err(|_| Error::<T>::ErrorEncode)?;
 
-				Self::send_xcm_to_set_token_amount(encoded_call, config.xcm_weight, config.xcm_fee)
-					.map_err(|_| Error::<T>::ErrorDelivering)?;
+				Self::send_xcm_to_set_token_amount(
+					para_id,
+					fee_asset,
+					encoded_call,
+					config.xcm_weight,
+					config.xcm_fee,
+				)
+				.map_err(|_| Error::<T>::ErrorDelivering)?;
 
 				Self::deposit_event(Event::XcmOracle {
 					currency_id,
@@ -1296,15 +1705,10 @@ impl<T: Config> Pallet<T> {
 					v_currency_total_supply,
 				});
 
-				let mut target_fee_currency_id = GLMR;
-				if T::ParachainId::get() == Id::from(BifrostKusamaChainId::get()) {
-					target_fee_currency_id = MOVR;
-				}
-
 				// Will not check results and will be sent regardless of the success of
 				// the burning
 				if T::MultiCurrency::withdraw(
-					target_fee_currency_id,
+					fee_asset,
 					&T::TreasuryAccount::get(),
 					BalanceOf::<T>::unique_saturated_from(config.xcm_fee),
 				)
@@ -1320,7 +1724,7 @@ impl<T: Config> Pallet<T> {
 					BoundedVec::try_from(currency_list).map_err(|_| Error::<T>::ErrorEncode)?,
 				);
 
-				*weight = weight.saturating_add(T::DbWeight::get().reads_writes(4, 2));
+				*weight = weight.saturating_add(T::DbWeight::get().reads_writes(6, 2));
 
 				*is_handle_xcm_oracle = true;
 			}
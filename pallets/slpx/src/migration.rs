@@ -192,17 +192,45 @@ pub mod v1 {
 
 pub mod v2 {
 	use super::*;
-	use frame_support::traits::GetStorageVersion;
+	use frame_support::{
+		pallet_prelude::{StorageVersion, ValueQuery},
+		traits::GetStorageVersion,
+	};
+
+	#[derive(Encode, Decode, Clone)]
+	pub struct Order<AccountId, CurrencyId, Balance, BlockNumber> {
+		pub source_chain_caller: OrderCaller<AccountId>,
+		pub source_chain_id: u64,
+		pub source_chain_block_number: Option<u128>,
+		pub bifrost_chain_caller: AccountId,
+		pub derivative_account: AccountId,
+		pub create_block_number: BlockNumber,
+		pub currency_id: CurrencyId,
+		pub currency_amount: Balance,
+		pub order_type: OrderType,
+		pub remark: BoundedVec<u8, ConstU32<32>>,
+		pub target_chain: TargetChain<AccountId>,
+		pub channel_id: u32,
+	}
+
+	#[storage_alias]
+	pub(super) type OrderQueue<T: Config> = StorageValue<
+		Pallet<T>,
+		BoundedVec<
+			Order<AccountIdOf<T>, CurrencyIdOf<T>, BalanceOf<T>, BlockNumberFor<T>>,
+			ConstU32<1000>,
+		>,
+		ValueQuery,
+	>;
 
 	pub struct MigrateToV2<T>(sp_std::marker::PhantomData<T>);
 	impl<T: Config> OnRuntimeUpgrade for MigrateToV2<T> {
 		fn on_runtime_upgrade() -> Weight {
 			let on_chain_storage_version = Pallet::<T>::on_chain_storage_version();
-			let in_code_storage_version = Pallet::<T>::in_code_storage_version();
-			if on_chain_storage_version == 1 && in_code_storage_version == 2 {
+			if on_chain_storage_version == 1 {
 				let weight_consumed = migrate_to_v2::<T>();
 				log::info!("Migrating slpx storage to v2");
-				in_code_storage_version.put::<Pallet<T>>();
+				StorageVersion::new(2).put::<Pallet<T>>();
 				weight_consumed.saturating_add(T::DbWeight::get().writes(1))
 			} else {
 				log::warn!("slpx migration should be removed.");
@@ -216,9 +244,58 @@ pub mod v2 {
 				Pallet::<T>::on_chain_storage_version() == 2,
 				"on_chain_storage_version should be 2"
 			);
+			Ok(())
+		}
+	}
+}
+
+pub mod v3 {
+	use super::*;
+	use frame_support::traits::GetStorageVersion;
+
+	pub struct MigrateToV3<T>(sp_std::marker::PhantomData<T>);
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV3<T> {
+		fn on_runtime_upgrade() -> Weight {
+			let on_chain_storage_version = Pallet::<T>::on_chain_storage_version();
+			let in_code_storage_version = Pallet::<T>::in_code_storage_version();
+			if on_chain_storage_version == 2 && in_code_storage_version == 3 {
+				let weight_consumed = migrate_to_v3::<T>();
+				log::info!("Migrating slpx storage to v3");
+				in_code_storage_version.put::<Pallet<T>>();
+				weight_consumed.saturating_add(T::DbWeight::get().writes(1))
+			} else {
+				log::warn!("slpx migration should be removed.");
+				T::DbWeight::get().reads(1)
+			}
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<Vec<u8>, sp_runtime::DispatchError> {
+			let order_count = v2::OrderQueue::<T>::get().len() as u64;
+			log::info!("slpx before migration: v2 count: {}", order_count);
+
+			Ok(order_count.encode())
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(cnt: Vec<u8>) -> Result<(), sp_runtime::DispatchError> {
+			let old_order_count: u64 = Decode::decode(&mut cnt.as_slice()).expect(
+				"the state parameter should be something that was generated by pre_upgrade",
+			);
+			let order_count = OrderQueue::<T>::get().len() as u64;
+			log::info!("slpx after migration: v3 count: {}", order_count);
+
+			ensure!(
+				Pallet::<T>::on_chain_storage_version() == 3,
+				"on_chain_storage_version should be 3"
+			);
 			ensure!(
-				Pallet::<T>::in_code_storage_version() == 2,
-				"in_code_storage_version should be 2"
+				order_count == old_order_count,
+				"OrderQueue post-migrate storage count not match"
+			);
+			ensure!(
+				NextOrderId::<T>::get() == order_count,
+				"NextOrderId post-migrate not match"
 			);
 			Ok(())
 		}
@@ -263,7 +340,7 @@ pub fn migrate_to_v2<T: Config>() -> Weight {
 
 	let old_order_queue = v1::OrderQueue::<T>::take();
 	for old_order in old_order_queue.into_iter() {
-		let order = Order {
+		let order = v2::Order {
 			source_chain_caller: old_order.source_chain_caller,
 			source_chain_id: 0,
 			source_chain_block_number: None,
@@ -278,7 +355,7 @@ pub fn migrate_to_v2<T: Config>() -> Weight {
 			channel_id: old_order.channel_id,
 		};
 
-		OrderQueue::<T>::mutate(|order_queue| -> DispatchResultWithPostInfo {
+		v2::OrderQueue::<T>::mutate(|order_queue| -> DispatchResultWithPostInfo {
 			order_queue
 				.try_push(order.clone())
 				.map_err(|_| Error::<T>::ErrorArguments)?;
@@ -291,3 +368,41 @@ pub fn migrate_to_v2<T: Config>() -> Weight {
 
 	weight
 }
+
+pub fn migrate_to_v3<T: Config>() -> Weight {
+	let mut weight: Weight = Weight::zero();
+
+	let old_order_queue = v2::OrderQueue::<T>::take();
+	let mut order_id = NextOrderId::<T>::get();
+	for old_order in old_order_queue.into_iter() {
+		let order = Order {
+			order_id,
+			source_chain_caller: old_order.source_chain_caller,
+			source_chain_id: old_order.source_chain_id,
+			source_chain_block_number: old_order.source_chain_block_number,
+			bifrost_chain_caller: old_order.bifrost_chain_caller,
+			derivative_account: old_order.derivative_account,
+			create_block_number: old_order.create_block_number,
+			currency_id: old_order.currency_id,
+			currency_amount: old_order.currency_amount,
+			order_type: old_order.order_type,
+			remark: old_order.remark,
+			target_chain: old_order.target_chain,
+			channel_id: old_order.channel_id,
+		};
+		order_id = order_id.saturating_add(1);
+
+		OrderQueue::<T>::mutate(|order_queue| -> DispatchResultWithPostInfo {
+			order_queue
+				.try_push(order.clone())
+				.map_err(|_| Error::<T>::ErrorArguments)?;
+			Ok(().into())
+		})
+		.expect("BoundedVec should not overflow");
+
+		weight = weight.saturating_add(T::DbWeight::get().writes(1));
+	}
+	NextOrderId::<T>::put(order_id);
+
+	weight.saturating_add(T::DbWeight::get().reads_writes(1, 1))
+}
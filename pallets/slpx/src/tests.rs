@@ -19,7 +19,10 @@
 
 use crate::{
 	mock::*,
-	types::{EthereumXcmCall, EthereumXcmTransaction, EthereumXcmTransactionV2, MoonbeamCall},
+	types::{
+		EthereumXcmCall, EthereumXcmTransaction, EthereumXcmTransactionV2, MoonbeamCall,
+		MOONBEAM_ETHEREUM_XCM_PALLET_INDEX,
+	},
 	*,
 };
 use bifrost_primitives::{TokenSymbol, DOT, VDOT};
@@ -306,7 +309,7 @@ fn test_ethereum_call() {
 		});
 		let call = MoonbeamCall::EthereumXcm(EthereumXcmCall::Transact(r));
 		println!("{}", hex::encode(call.encode()));
-		assert_eq!("6d000180fc0a000000000000000000000000000000000000000000000000000000000000ae0daa9bfc50f03ce23d30c796709a58470b5f42000000000000000000000000000000000000000000000000000000000000000091019a41b9240001000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000007b00000000000000000000000000000000000000000000000000000000000001c800", hex::encode(Slpx::encode_transact_call(H160::from(addr), BNC, 123u128, 456u128).unwrap()));
+		assert_eq!("6d000180fc0a000000000000000000000000000000000000000000000000000000000000ae0daa9bfc50f03ce23d30c796709a58470b5f42000000000000000000000000000000000000000000000000000000000000000091019a41b9240001000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000007b00000000000000000000000000000000000000000000000000000000000001c800", hex::encode(Slpx::encode_transact_call(MOONBEAM_ETHEREUM_XCM_PALLET_INDEX, H160::from(addr), BNC, 123u128, 456u128).unwrap()));
 	})
 }
 
@@ -493,3 +496,419 @@ fn test_hook() {
 		println!("{}", Currencies::free_balance(VDOT, &BOB));
 	})
 }
+
+fn create_astar_order() -> u64 {
+	WhitelistAccountId::<Test>::insert(
+		SupportChain::Astar,
+		BoundedVec::try_from(vec![ALICE]).unwrap(),
+	);
+	let source_chain_caller = H160::default();
+	let order_id = NextOrderId::<Test>::get();
+	assert_ok!(Slpx::mint(
+		RuntimeOrigin::signed(ALICE),
+		source_chain_caller,
+		DOT,
+		TargetChain::Astar(source_chain_caller),
+		BoundedVec::default()
+	));
+	order_id
+}
+
+#[test]
+fn test_order_id() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(create_astar_order(), 0);
+		assert_eq!(create_astar_order(), 1);
+		assert_eq!(NextOrderId::<Test>::get(), 2);
+
+		let order_ids: Vec<u64> = OrderQueue::<Test>::get()
+			.iter()
+			.map(|order| order.order_id)
+			.collect();
+		assert_eq!(order_ids, vec![0, 1]);
+	})
+}
+
+#[test]
+fn test_cancel_order() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let order_id = create_astar_order();
+		let order = OrderQueue::<Test>::get()[0].clone();
+		assert_ok!(Currencies::deposit(
+			DOT,
+			&order.derivative_account,
+			10_000_000_000_000
+		));
+
+		assert_noop!(
+			Slpx::cancel_order(RuntimeOrigin::signed(ALICE), order_id + 1),
+			Error::<Test>::OrderNotFound
+		);
+		assert_noop!(
+			Slpx::cancel_order(RuntimeOrigin::signed(BOB), order_id),
+			Error::<Test>::NotOrderOwner
+		);
+
+		assert_ok!(Slpx::cancel_order(RuntimeOrigin::signed(ALICE), order_id));
+		assert!(OrderQueue::<Test>::get().is_empty());
+		System::assert_has_event(RuntimeEvent::Slpx(Event::OrderRefunded {
+			order_id,
+			currency_id: DOT,
+			currency_amount: 10_000_000_000_000,
+		}));
+		System::assert_last_event(RuntimeEvent::Slpx(Event::OrderCancelled {
+			order: Order {
+				currency_amount: 10_000_000_000_000,
+				..order
+			},
+		}));
+
+		// The derivative account can cancel the order as well
+		let order_id = create_astar_order();
+		let derivative_account = OrderQueue::<Test>::get()[0].derivative_account.clone();
+		assert_ok!(Slpx::cancel_order(
+			RuntimeOrigin::signed(derivative_account),
+			order_id
+		));
+		assert!(OrderQueue::<Test>::get().is_empty());
+	})
+}
+
+#[test]
+fn order_expiry_must_exceed_delay_block() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Slpx::set_delay_block(RuntimeOrigin::root(), 5u32.into()));
+		assert_noop!(
+			Slpx::set_order_expiry(RuntimeOrigin::root(), 5u32.into()),
+			Error::<Test>::InvalidOrderExpiry
+		);
+		assert_ok!(Slpx::set_order_expiry(RuntimeOrigin::root(), 6u32.into()));
+		assert_noop!(
+			Slpx::set_delay_block(RuntimeOrigin::root(), 6u32.into()),
+			Error::<Test>::InvalidOrderExpiry
+		);
+		// Zero disables the expiry
+		assert_ok!(Slpx::set_order_expiry(RuntimeOrigin::root(), 0u32.into()));
+		assert_ok!(Slpx::set_delay_block(RuntimeOrigin::root(), 6u32.into()));
+	})
+}
+
+#[test]
+fn test_order_expiry() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(Slpx::set_order_expiry(RuntimeOrigin::root(), 5u32.into()));
+		assert_eq!(OrderExpiry::<Test>::get(), 5);
+
+		let order_id = create_astar_order();
+		let derivative_account = OrderQueue::<Test>::get()[0].derivative_account.clone();
+		assert_ok!(Currencies::deposit(
+			DOT,
+			&derivative_account,
+			10_000_000_000_000
+		));
+
+		System::set_block_number(6);
+		Slpx::on_idle(6, Weight::default());
+		assert!(OrderQueue::<Test>::get().is_empty());
+		assert!(System::events().iter().any(|record| matches!(
+			&record.event,
+			RuntimeEvent::Slpx(Event::OrderExpired { order }) if order.order_id == order_id
+		)));
+		System::assert_last_event(RuntimeEvent::Slpx(Event::OrderRefunded {
+			order_id,
+			currency_id: DOT,
+			currency_amount: 10_000_000_000_000,
+		}));
+	})
+}
+
+#[test]
+fn test_get_pending_orders() {
+	new_test_ext().execute_with(|| {
+		create_astar_order();
+		assert_ok!(Slpx::force_add_order(
+			RuntimeOrigin::root(),
+			OrderCaller::Substrate(BOB),
+			BOB,
+			DOT,
+			TargetChain::Hydradx(BOB),
+			BoundedVec::default(),
+			0
+		));
+
+		let alice_orders = Slpx::get_pending_orders(ALICE);
+		assert_eq!(alice_orders.len(), 1);
+		assert_eq!(alice_orders[0].order_id, 0);
+
+		let bob_orders = Slpx::get_pending_orders(BOB);
+		assert_eq!(bob_orders.len(), 1);
+		assert_eq!(bob_orders[0].order_id, 1);
+	})
+}
+
+const REGISTERED_PARA_ID: u32 = 2004;
+const REGISTERED_EVM_CHAIN_ID: u64 = 1287;
+
+fn registered_evm_chain() -> ChainInfo<CurrencyId> {
+	ChainInfo {
+		evm_chain_id: Some(REGISTERED_EVM_CHAIN_ID),
+		account_format: AccountFormat::AccountKey20,
+		callback_style: CallbackStyle::EthereumXcm { pallet_index: 38 },
+		fee_asset: BNC,
+	}
+}
+
+#[test]
+fn test_register_chain() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			Slpx::register_chain(
+				RuntimeOrigin::signed(ALICE),
+				REGISTERED_PARA_ID,
+				registered_evm_chain()
+			),
+			DispatchError::BadOrigin
+		);
+		assert_ok!(Slpx::register_chain(
+			RuntimeOrigin::root(),
+			REGISTERED_PARA_ID,
+			registered_evm_chain()
+		));
+		assert_eq!(
+			ChainRegistry::<Test>::get(REGISTERED_PARA_ID),
+			Some(registered_evm_chain())
+		);
+		assert_eq!(
+			Slpx::match_source_chain_id(REGISTERED_EVM_CHAIN_ID),
+			Some(SupportChain::Registered(REGISTERED_PARA_ID))
+		);
+
+		// The EVM chain id can not be shared by two chains
+		assert_noop!(
+			Slpx::register_chain(RuntimeOrigin::root(), 2005, registered_evm_chain()),
+			Error::<Test>::EvmChainIdAlreadyExists
+		);
+		assert_noop!(
+			Slpx::register_chain(
+				RuntimeOrigin::root(),
+				2005,
+				ChainInfo {
+					evm_chain_id: Some(AstarEvmChainId::get()),
+					..registered_evm_chain()
+				}
+			),
+			Error::<Test>::EvmChainIdAlreadyExists
+		);
+
+		assert_ok!(Slpx::deregister_chain(
+			RuntimeOrigin::root(),
+			REGISTERED_PARA_ID
+		));
+		assert_eq!(ChainRegistry::<Test>::get(REGISTERED_PARA_ID), None);
+		assert_eq!(Slpx::match_source_chain_id(REGISTERED_EVM_CHAIN_ID), None);
+		assert_noop!(
+			Slpx::deregister_chain(RuntimeOrigin::root(), REGISTERED_PARA_ID),
+			Error::<Test>::ChainNotRegistered
+		);
+	})
+}
+
+#[test]
+fn test_registered_evm_chain_order() {
+	new_test_ext().execute_with(|| {
+		let contract = H160::from_slice(&MOONBEAM_SLPX_ADDR);
+		let receiver = H160::from_slice(&EVM_ADDR);
+		let target_chain =
+			TargetChain::Registered(REGISTERED_PARA_ID, ChainAccount::AccountKey20(receiver));
+
+		assert_noop!(
+			Slpx::add_whitelist(
+				RuntimeOrigin::root(),
+				SupportChain::Registered(REGISTERED_PARA_ID),
+				contract
+			),
+			Error::<Test>::ChainNotRegistered
+		);
+		assert_ok!(Slpx::register_chain(
+			RuntimeOrigin::root(),
+			REGISTERED_PARA_ID,
+			registered_evm_chain()
+		));
+		assert_ok!(Slpx::add_whitelist(
+			RuntimeOrigin::root(),
+			SupportChain::Registered(REGISTERED_PARA_ID),
+			contract
+		));
+		let contract_account_id =
+			Slpx::xcm_derivative_account(SupportChain::Registered(REGISTERED_PARA_ID), contract)
+				.unwrap();
+
+		// The receiver must match the account format of the chain
+		assert_noop!(
+			Slpx::mint(
+				RuntimeOrigin::signed(contract_account_id.clone()),
+				receiver,
+				DOT,
+				TargetChain::Registered(REGISTERED_PARA_ID, ChainAccount::AccountId32(BOB)),
+				BoundedVec::default()
+			),
+			Error::<Test>::InvalidAccountFormat
+		);
+		// Only whitelisted contracts can create orders for EVM chains
+		assert_noop!(
+			Slpx::mint(
+				RuntimeOrigin::signed(ALICE),
+				receiver,
+				DOT,
+				target_chain,
+				BoundedVec::default()
+			),
+			Error::<Test>::AccountNotFound
+		);
+		assert_ok!(Slpx::mint(
+			RuntimeOrigin::signed(contract_account_id.clone()),
+			receiver,
+			DOT,
+			target_chain,
+			BoundedVec::default()
+		));
+		assert_ok!(Slpx::evm_create_order(
+			RuntimeOrigin::signed(contract_account_id),
+			receiver,
+			REGISTERED_EVM_CHAIN_ID,
+			0,
+			DOT,
+			10_000_000_000_000,
+			target_chain,
+			BoundedVec::default(),
+			0
+		));
+
+		let order_queue = OrderQueue::<Test>::get();
+		assert_eq!(order_queue.len(), 2);
+		assert!(order_queue.iter().all(|order| order.source_chain_caller
+			== OrderCaller::Evm(receiver)
+			&& order.derivative_account == Slpx::h160_to_account_id(&receiver)));
+		assert_eq!(order_queue[1].source_chain_id, REGISTERED_EVM_CHAIN_ID);
+	})
+}
+
+#[test]
+fn test_registered_substrate_chain_order() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Slpx::register_chain(
+			RuntimeOrigin::root(),
+			REGISTERED_PARA_ID,
+			ChainInfo {
+				evm_chain_id: None,
+				account_format: AccountFormat::AccountId32,
+				callback_style: CallbackStyle::SubstrateRemark,
+				fee_asset: DOT,
+			}
+		));
+		assert_eq!(
+			Slpx::registered_chain_location(REGISTERED_PARA_ID, &ChainAccount::AccountId32(BOB)),
+			Ok(Location::new(
+				1,
+				[
+					Parachain(REGISTERED_PARA_ID),
+					AccountId32 {
+						network: None,
+						id: BOB.into()
+					}
+				]
+			))
+		);
+
+		// Substrate accounts create orders without being whitelisted
+		assert_ok!(Slpx::mint(
+			RuntimeOrigin::signed(BOB),
+			H160::default(),
+			DOT,
+			TargetChain::Registered(REGISTERED_PARA_ID, ChainAccount::AccountId32(BOB)),
+			BoundedVec::default()
+		));
+		let order = OrderQueue::<Test>::get()[0].clone();
+		assert_eq!(order.source_chain_caller, OrderCaller::Substrate(BOB));
+		assert_eq!(order.derivative_account, BOB);
+	})
+}
+
+#[test]
+fn test_xcm_oracle_callback() {
+	new_test_ext().execute_with(|| {
+		let contract = H160::from(hex!["ae0daa9bfc50f03ce23d30c796709a58470b5f42"]);
+		let moonbeam_para_id = VtokenMinting::get_moonbeam_parachain_id();
+		assert_eq!(
+			Slpx::xcm_oracle_callback().unwrap().1,
+			MOONBEAM_ETHEREUM_XCM_PALLET_INDEX
+		);
+		let call = Slpx::encode_transact_call(38, contract, BNC, 123u128, 456u128).unwrap();
+		assert_eq!(call[0], 38);
+		assert_eq!(
+			call[1..],
+			Slpx::encode_transact_call(
+				MOONBEAM_ETHEREUM_XCM_PALLET_INDEX,
+				contract,
+				BNC,
+				123u128,
+				456u128
+			)
+			.unwrap()[1..]
+		);
+
+		assert_ok!(Slpx::register_chain(
+			RuntimeOrigin::root(),
+			moonbeam_para_id,
+			ChainInfo {
+				evm_chain_id: None,
+				..registered_evm_chain()
+			}
+		));
+		assert_eq!(
+			Slpx::xcm_oracle_callback().ok(),
+			Some((moonbeam_para_id, 38, BNC))
+		);
+
+		// The oracle follows the callback style of the chain it is set to
+		assert_noop!(
+			Slpx::set_xcm_oracle_chain(RuntimeOrigin::root(), Some(REGISTERED_PARA_ID)),
+			Error::<Test>::ChainNotRegistered
+		);
+		assert_ok!(Slpx::register_chain(
+			RuntimeOrigin::root(),
+			REGISTERED_PARA_ID,
+			ChainInfo {
+				callback_style: CallbackStyle::EthereumXcm { pallet_index: 42 },
+				..registered_evm_chain()
+			}
+		));
+		assert_ok!(Slpx::set_xcm_oracle_chain(
+			RuntimeOrigin::root(),
+			Some(REGISTERED_PARA_ID)
+		));
+		assert_eq!(
+			Slpx::xcm_oracle_callback().ok(),
+			Some((REGISTERED_PARA_ID, 42, BNC))
+		);
+
+		assert_ok!(Slpx::register_chain(
+			RuntimeOrigin::root(),
+			REGISTERED_PARA_ID,
+			ChainInfo {
+				callback_style: CallbackStyle::SubstrateRemark,
+				..registered_evm_chain()
+			}
+		));
+		assert!(matches!(
+			Slpx::xcm_oracle_callback(),
+			Err(Error::<Test>::InvalidCallbackStyle)
+		));
+		assert_noop!(
+			Slpx::set_xcm_oracle_chain(RuntimeOrigin::root(), Some(REGISTERED_PARA_ID)),
+			Error::<Test>::InvalidCallbackStyle
+		);
+	})
+}
@@ -42,6 +42,9 @@ pub type CurrencyIdOf<T> = <<T as pallet::Config>::MultiCurrency as MultiCurrenc
 pub type BalanceOf<T> =
 	<<T as pallet::Config>::MultiCurrency as MultiCurrency<AccountIdOf<T>>>::Balance;
 
+/// Moonbeam's pallet index of `EthereumXcm`
+pub const MOONBEAM_ETHEREUM_XCM_PALLET_INDEX: u8 = 109;
+
 #[derive(Encode, Decode, Copy, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
 pub enum SupportChain {
 	Astar,
@@ -49,6 +52,8 @@ pub enum SupportChain {
 	Hydradx,
 	Interlay,
 	Manta,
+	/// A chain in the chain registry, identified by its parachain id
+	Registered(u32),
 }
 
 #[derive(Encode, Decode, Copy, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
@@ -58,6 +63,8 @@ pub enum TargetChain<AccountId> {
 	Hydradx(AccountId),
 	Interlay(AccountId),
 	Manta(AccountId),
+	/// A chain in the chain registry, identified by its parachain id
+	Registered(u32, ChainAccount<AccountId>),
 }
 
 impl<AccountId> TargetChain<AccountId> {
@@ -68,10 +75,59 @@ impl<AccountId> TargetChain<AccountId> {
 			TargetChain::Hydradx(_) => SupportChain::Hydradx,
 			TargetChain::Interlay(_) => SupportChain::Interlay,
 			TargetChain::Manta(_) => SupportChain::Manta,
+			TargetChain::Registered(para_id, _) => SupportChain::Registered(*para_id),
+		}
+	}
+}
+
+/// The account on a registered chain
+#[derive(Encode, Decode, Copy, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum ChainAccount<AccountId> {
+	AccountId32(AccountId),
+	AccountKey20(H160),
+}
+
+impl<AccountId> ChainAccount<AccountId> {
+	pub fn account_format(&self) -> AccountFormat {
+		match self {
+			ChainAccount::AccountId32(_) => AccountFormat::AccountId32,
+			ChainAccount::AccountKey20(_) => AccountFormat::AccountKey20,
 		}
 	}
 }
 
+/// The account format of a registered chain
+#[derive(Encode, Decode, Copy, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum AccountFormat {
+	AccountId32,
+	AccountKey20,
+}
+
+/// How the orders of a registered chain are created and called back
+#[derive(Encode, Decode, Copy, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum CallbackStyle {
+	/// Orders are created by Substrate accounts and carry a remark
+	SubstrateRemark,
+	/// Orders are created by whitelisted EVM contracts, which are called back via the
+	/// ethereum-xcm pallet
+	EthereumXcm {
+		/// The pallet index of ethereum-xcm on the chain
+		pallet_index: u8,
+	},
+}
+
+#[derive(Encode, Decode, Copy, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct ChainInfo<CurrencyId> {
+	/// The EVM chain id, used to match the source chain of `evm_create_order`
+	pub evm_chain_id: Option<u64>,
+	/// The account format of the chain
+	pub account_format: AccountFormat,
+	/// How the orders of the chain are created and called back
+	pub callback_style: CallbackStyle,
+	/// The asset paying the XCM fee on the chain
+	pub fee_asset: CurrencyId,
+}
+
 #[derive(Clone, Debug, Eq, PartialEq, Encode, Decode, TypeInfo, MaxEncodedLen)]
 pub struct EthereumCallConfiguration<BlockNumber> {
 	/// XCM message execution costs to be consumed
@@ -133,6 +189,7 @@ pub enum OrderType {
 
 #[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
 pub struct Order<AccountId, CurrencyId, Balance, BlockNumber> {
+	pub order_id: u64,
 	pub source_chain_caller: OrderCaller<AccountId>,
 	pub source_chain_id: u64,
 	pub source_chain_block_number: Option<u128>,
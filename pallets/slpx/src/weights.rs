@@ -63,6 +63,11 @@ pub trait WeightInfo {
 	fn mint_with_channel_id() -> Weight;
 	fn redeem() -> Weight;
 	fn evm_create_order() -> Weight;
+	fn cancel_order() -> Weight;
+	fn set_order_expiry() -> Weight;
+	fn register_chain() -> Weight;
+	fn deregister_chain() -> Weight;
+	fn set_xcm_oracle_chain() -> Weight;
 }
 
 // For backwards compatibility and tests
@@ -161,4 +166,69 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(2_u64))
 			.saturating_add(RocksDbWeight::get().writes(1_u64))
 	}
-}
\ No newline at end of file
+	/// Storage: `Slpx::OrderQueue` (r:1 w:1)
+	/// Proof: `Slpx::OrderQueue` (`max_values`: Some(1), `max_size`: Some(117502), added: 117997, mode: `MaxEncodedLen`)
+	/// Storage: `Tokens::Accounts` (r:2 w:2)
+	/// Proof: `Tokens::Accounts` (`max_values`: None, `max_size`: Some(118), added: 2593, mode: `MaxEncodedLen`)
+	/// Storage: `AssetRegistry::CurrencyIdToLocations` (r:1 w:0)
+	/// Proof: `AssetRegistry::CurrencyIdToLocations` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Tokens::TotalIssuance` (r:1 w:1)
+	/// Proof: `Tokens::TotalIssuance` (`max_values`: None, `max_size`: Some(38), added: 2513, mode: `MaxEncodedLen`)
+	fn cancel_order() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1450`
+		//  Estimated: `118987`
+		// Minimum execution time: 98_412_000 picoseconds.
+		Weight::from_parts(101_207_000, 118987)
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+	}
+	/// Storage: `Slpx::DelayBlock` (r:1 w:0)
+	/// Proof: `Slpx::DelayBlock` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+	/// Storage: `Slpx::OrderExpiry` (r:0 w:1)
+	/// Proof: `Slpx::OrderExpiry` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+	fn set_order_expiry() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `1489`
+		// Minimum execution time: 9_870_000 picoseconds.
+		Weight::from_parts(10_284_000, 1489)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `Slpx::ChainRegistry` (r:2 w:1)
+	/// Proof: `Slpx::ChainRegistry` (`max_values`: None, `max_size`: Some(34), added: 2509, mode: `MaxEncodedLen`)
+	fn register_chain() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `113`
+		//  Estimated: `6018`
+		// Minimum execution time: 18_542_000 picoseconds.
+		Weight::from_parts(19_127_000, 6018)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `Slpx::ChainRegistry` (r:1 w:1)
+	/// Proof: `Slpx::ChainRegistry` (`max_values`: None, `max_size`: Some(34), added: 2509, mode: `MaxEncodedLen`)
+	fn deregister_chain() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `113`
+		//  Estimated: `3499`
+		// Minimum execution time: 15_913_000 picoseconds.
+		Weight::from_parts(16_468_000, 3499)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `Slpx::ChainRegistry` (r:1 w:0)
+	/// Proof: `Slpx::ChainRegistry` (`max_values`: None, `max_size`: Some(34), added: 2509, mode: `MaxEncodedLen`)
+	/// Storage: `Slpx::XcmOracleChain` (r:0 w:1)
+	/// Proof: `Slpx::XcmOracleChain` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+	fn set_xcm_oracle_chain() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `113`
+		//  Estimated: `3499`
+		// Minimum execution time: 12_760_000 picoseconds.
+		Weight::from_parts(13_245_000, 3499)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+}
@@ -33,6 +33,7 @@ pub mod weights;
 pub mod migration;
 
 pub use crate::traits::StableAsset;
+use bifrost_primitives::{StableLpTokenProvider, StableTwapProvider};
 use frame_support::{dispatch::DispatchResult, ensure, traits::Get, weights::Weight};
 use frame_system::pallet_prelude::BlockNumberFor;
 use orml_traits::MultiCurrency;
@@ -42,9 +43,9 @@ use sp_core::{U256, U512};
 use sp_runtime::{
 	traits::{
 		AccountIdConversion, BlockNumberProvider, CheckedAdd, CheckedDiv, CheckedMul, CheckedSub,
-		One, Zero,
+		One, Saturating, Zero,
 	},
-	ArithmeticError, DispatchError, SaturatedConversion,
+	ArithmeticError, DispatchError, FixedPointNumber, FixedU128, Permill, SaturatedConversion,
 };
 use sp_std::prelude::*;
 
@@ -75,6 +76,47 @@ pub struct StableAssetPoolInfo<AssetId, AtLeast64BitUnsigned, Balance, AccountId
 	pub precision: AtLeast64BitUnsigned,
 }
 
+/// The cumulative prices of a pool, the base of its time-weighted average prices.
+#[derive(Encode, Decode, Clone, Default, PartialEq, Eq, Debug, TypeInfo)]
+pub struct PriceAccumulator<BlockNumber> {
+	/// The block of the last update.
+	pub block_number: BlockNumber,
+	/// The price of each asset in the first asset of the pool, summed over every block, in
+	/// 18 decimals and wrapping on overflow.
+	pub cumulative_prices: Vec<u128>,
+}
+
+/// The operating status of a pool, or of an asset in every pool holding it.
+#[derive(Encode, Decode, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Debug, TypeInfo)]
+pub enum PoolStatus {
+	/// Every operation is allowed.
+	#[default]
+	Normal,
+	/// Only proportional redemption is allowed.
+	Emergency,
+	/// No operation is allowed.
+	Paused,
+}
+
+/// The thresholds guarding a pool against depegs and token rate bugs.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, Debug, TypeInfo)]
+pub struct CircuitBreaker {
+	/// The largest spread between the pool balances, as a share of the largest balance. Trades
+	/// increasing the spread beyond it are rejected, and a pool left beyond it trips.
+	pub max_imbalance: Permill,
+	/// Whether a token rate change beyond its `TokenRateHardcap` trips the pool.
+	pub trip_on_rate_jump: bool,
+}
+
+/// The reason a circuit breaker tripped.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Debug, TypeInfo)]
+pub enum TripReason<AssetId> {
+	/// The spread between the pool balances exceeded the threshold.
+	Imbalance(Permill),
+	/// The token rate of the asset changed beyond its hardcap.
+	TokenRateJump(AssetId),
+}
+
 pub trait WeightInfo {
 	fn create_pool() -> Weight;
 	fn modify_a() -> Weight;
@@ -85,6 +127,9 @@ pub trait WeightInfo {
 	fn redeem_proportion(u: u32) -> Weight;
 	fn redeem_single(u: u32) -> Weight;
 	fn redeem_multi(u: u32) -> Weight;
+	fn set_pool_status() -> Weight;
+	fn set_asset_status() -> Weight;
+	fn set_circuit_breaker() -> Weight;
 }
 
 pub mod traits {
@@ -343,7 +388,10 @@ pub mod traits {
 
 #[frame_support::pallet]
 pub mod pallet {
-	use super::{PoolTokenIndex, StableAssetPoolId, StableAssetPoolInfo};
+	use super::{
+		CircuitBreaker, PoolStatus, PoolTokenIndex, PriceAccumulator, StableAssetPoolId,
+		StableAssetPoolInfo, TripReason,
+	};
 	use crate::{
 		traits::{StableAsset, ValidateAssetId},
 		WeightInfo,
@@ -405,6 +453,15 @@ pub mod pallet {
 
 		/// The current block number provider.
 		type BlockNumberProvider: BlockNumberProvider<BlockNumber = BlockNumberFor<Self>>;
+
+		/// The minimum number of blocks between two price observations of a pool.
+		#[pallet::constant]
+		type PriceObservationPeriod: Get<BlockNumberFor<Self>>;
+
+		/// The maximum number of price observations kept per pool, which bounds the window of
+		/// the time-weighted average prices.
+		#[pallet::constant]
+		type MaxPriceObservations: Get<u32>;
 	}
 
 	#[pallet::pallet]
@@ -445,6 +502,37 @@ pub mod pallet {
 	#[pallet::storage]
 	pub type TokenRateHardcap<T: Config> = StorageMap<_, Twox64Concat, T::AssetId, Permill>;
 
+	/// The cumulative prices of each pool, updated on every mint, swap and redeem.
+	#[pallet::storage]
+	pub type PriceAccumulators<T: Config> =
+		StorageMap<_, Twox64Concat, StableAssetPoolId, PriceAccumulator<BlockNumberFor<T>>>;
+
+	/// The past cumulative prices of each pool, at least `PriceObservationPeriod` blocks apart
+	/// and oldest first.
+	#[pallet::storage]
+	pub type PriceObservations<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		StableAssetPoolId,
+		Vec<PriceAccumulator<BlockNumberFor<T>>>,
+		ValueQuery,
+	>;
+
+	/// The status set on each pool, pools not listed are normal.
+	#[pallet::storage]
+	pub type PoolStatuses<T: Config> =
+		StorageMap<_, Twox64Concat, StableAssetPoolId, PoolStatus, ValueQuery>;
+
+	/// The status set on each asset, applied to every pool holding the asset.
+	#[pallet::storage]
+	pub type AssetStatuses<T: Config> =
+		StorageMap<_, Twox64Concat, T::AssetId, PoolStatus, ValueQuery>;
+
+	/// The circuit breaker of each pool.
+	#[pallet::storage]
+	pub type CircuitBreakers<T: Config> =
+		StorageMap<_, Twox64Concat, StableAssetPoolId, CircuitBreaker>;
+
 	#[pallet::event]
 	#[pallet::generate_deposit(pub fn deposit_event)]
 	pub enum Event<T: Config> {
@@ -667,6 +755,34 @@ pub mod pallet {
 			/// The pool id.
 			pool_id: StableAssetPoolId,
 		},
+		/// The status of a pool is set.
+		PoolStatusSet {
+			/// The pool id.
+			pool_id: StableAssetPoolId,
+			/// The new status.
+			status: PoolStatus,
+		},
+		/// The status of an asset is set.
+		AssetStatusSet {
+			/// The asset id.
+			asset_id: T::AssetId,
+			/// The new status.
+			status: PoolStatus,
+		},
+		/// The circuit breaker of a pool is set, `None` means removed.
+		CircuitBreakerSet {
+			/// The pool id.
+			pool_id: StableAssetPoolId,
+			/// The new circuit breaker.
+			circuit_breaker: Option<CircuitBreaker>,
+		},
+		/// The circuit breaker of a pool tripped and put it into emergency mode.
+		CircuitBreakerTripped {
+			/// The pool id.
+			pool_id: StableAssetPoolId,
+			/// The reason of the trip.
+			reason: TripReason<T::AssetId>,
+		},
 	}
 
 	#[pallet::error]
@@ -697,6 +813,12 @@ pub mod pallet {
 		RedeemOverMax,
 		/// The old token rate is not cleared.
 		TokenRateNotCleared,
+		/// The pool or one of its assets is paused.
+		PoolPaused,
+		/// The pool is in emergency mode, only proportional redemption is allowed.
+		PoolInEmergency,
+		/// The trade would leave the pool balances beyond the imbalance threshold.
+		PoolImbalanced,
 	}
 
 	/// The add liquidity result.
@@ -971,6 +1093,42 @@ pub mod pallet {
 				Ok(())
 			})
 		}
+
+		#[pallet::call_index(9)]
+		#[pallet::weight(T::WeightInfo::set_pool_status())]
+		#[transactional]
+		pub fn set_pool_status(
+			origin: OriginFor<T>,
+			pool_id: StableAssetPoolId,
+			status: PoolStatus,
+		) -> DispatchResult {
+			T::ListingOrigin::ensure_origin(origin)?;
+			Self::do_set_pool_status(pool_id, status)
+		}
+
+		#[pallet::call_index(10)]
+		#[pallet::weight(T::WeightInfo::set_asset_status())]
+		#[transactional]
+		pub fn set_asset_status(
+			origin: OriginFor<T>,
+			asset_id: T::AssetId,
+			status: PoolStatus,
+		) -> DispatchResult {
+			T::ListingOrigin::ensure_origin(origin)?;
+			Self::do_set_asset_status(asset_id, status)
+		}
+
+		#[pallet::call_index(11)]
+		#[pallet::weight(T::WeightInfo::set_circuit_breaker())]
+		#[transactional]
+		pub fn set_circuit_breaker(
+			origin: OriginFor<T>,
+			pool_id: StableAssetPoolId,
+			circuit_breaker: Option<CircuitBreaker>,
+		) -> DispatchResult {
+			T::ListingOrigin::ensure_origin(origin)?;
+			Self::do_set_circuit_breaker(pool_id, circuit_breaker)
+		}
 	}
 }
 impl<T: Config> Pallet<T> {
@@ -1737,6 +1895,294 @@ impl<T: Config> Pallet<T> {
 		cloned_stable_asset_info.balances = updated_balances;
 		Ok(cloned_stable_asset_info)
 	}
+
+	/// The marginal price of each asset in the first asset of the pool, in their smallest units
+	/// and 18 decimals. `None` if the pool is empty.
+	pub fn get_spot_prices(
+		pool_info: &StableAssetPoolInfo<
+			T::AssetId,
+			T::AtLeast64BitUnsigned,
+			T::Balance,
+			T::AccountId,
+			BlockNumberFor<T>,
+		>,
+	) -> Option<Vec<u128>> {
+		let balances = Self::convert_vec_balance_to_number(pool_info.balances.clone());
+		if balances.is_empty() || balances.iter().any(|balance| balance.is_zero()) {
+			return None;
+		}
+		let a = Self::get_a(
+			pool_info.a,
+			pool_info.a_block,
+			pool_info.future_a,
+			pool_info.future_a_block,
+		)?;
+		let d = U512::from(Self::get_d(&balances, a)?.saturated_into::<u128>());
+		let balance_size = U512::from(balances.len());
+		let a_precision = U512::from(T::APrecision::get().saturated_into::<u128>());
+		let mut ann = U512::from(a.saturated_into::<u128>());
+		let mut p_d = d;
+		for balance in balances.iter() {
+			let balance = U512::from((*balance).saturated_into::<u128>());
+			ann = ann.checked_mul(balance_size)?;
+			p_d = p_d
+				.checked_mul(d)?
+				.checked_div(balance.checked_mul(balance_size)?)?;
+		}
+
+		// Along the invariant, one unit of asset k is worth
+		// `x_0 * (ann * x_k + p_d) / (x_k * (ann * x_0 + p_d))` units of asset 0, scaled by the
+		// precisions and token rates to get the price of the smallest units.
+		let mut scaled = Vec::with_capacity(balances.len());
+		for (k, balance) in balances.iter().enumerate() {
+			let balance = U512::from((*balance).saturated_into::<u128>());
+			let (denominator, numerator) =
+				Self::get_token_rate(pool_info.pool_id, pool_info.assets[k])
+					.unwrap_or((One::one(), One::one()));
+			let precision = U512::from(pool_info.precisions[k].saturated_into::<u128>());
+			let weight = ann
+				.checked_mul(balance)?
+				.checked_add(p_d.checked_mul(a_precision)?)?
+				.checked_mul(precision)?
+				.checked_mul(U512::from(numerator.saturated_into::<u128>()))?;
+			let unit = balance.checked_mul(U512::from(denominator.saturated_into::<u128>()))?;
+			scaled.push((weight, unit));
+		}
+		let (weight_0, unit_0) = scaled[0];
+		scaled
+			.iter()
+			.map(|(weight, unit)| {
+				u128::try_from(
+					U512::from(10u128.pow(18))
+						.checked_mul(*weight)?
+						.checked_mul(unit_0)?
+						.checked_div(unit.checked_mul(weight_0)?)?,
+				)
+				.ok()
+			})
+			.collect()
+	}
+
+	/// `accumulator` brought forward to `now` with the current prices of the pool.
+	fn accumulate_prices(
+		accumulator: PriceAccumulator<BlockNumberFor<T>>,
+		pool_info: &StableAssetPoolInfo<
+			T::AssetId,
+			T::AtLeast64BitUnsigned,
+			T::Balance,
+			T::AccountId,
+			BlockNumberFor<T>,
+		>,
+		now: BlockNumberFor<T>,
+	) -> PriceAccumulator<BlockNumberFor<T>> {
+		let elapsed: u128 = now
+			.saturating_sub(accumulator.block_number)
+			.saturated_into();
+		let mut cumulative_prices = accumulator.cumulative_prices;
+		// An empty pool has no price and accumulates nothing.
+		if let Some(prices) = Self::get_spot_prices(pool_info) {
+			for (cumulative_price, price) in cumulative_prices.iter_mut().zip(prices) {
+				*cumulative_price = cumulative_price.wrapping_add(price.wrapping_mul(elapsed));
+			}
+		}
+		PriceAccumulator {
+			block_number: now,
+			cumulative_prices,
+		}
+	}
+
+	/// Accumulate the prices of the pool up to the current block, before the pool changes.
+	fn update_price_accumulator(
+		pool_id: StableAssetPoolId,
+		pool_info: &StableAssetPoolInfo<
+			T::AssetId,
+			T::AtLeast64BitUnsigned,
+			T::Balance,
+			T::AccountId,
+			BlockNumberFor<T>,
+		>,
+	) {
+		let now = T::BlockNumberProvider::current_block_number();
+		let accumulator = match PriceAccumulators::<T>::get(pool_id) {
+			Some(accumulator) if accumulator.block_number >= now => return,
+			Some(accumulator) => Self::accumulate_prices(accumulator, pool_info, now),
+			None => PriceAccumulator {
+				block_number: now,
+				cumulative_prices: sp_std::vec![0; pool_info.assets.len()],
+			},
+		};
+		PriceAccumulators::<T>::insert(pool_id, &accumulator);
+
+		PriceObservations::<T>::mutate(pool_id, |observations| {
+			let is_due = observations.last().map_or(true, |last| {
+				now >= last
+					.block_number
+					.saturating_add(T::PriceObservationPeriod::get())
+			});
+			if is_due {
+				observations.push(accumulator);
+				let max_observations = T::MaxPriceObservations::get() as usize;
+				if observations.len() > max_observations {
+					observations.drain(..observations.len() - max_observations);
+				}
+			}
+		});
+	}
+
+	/// The average price of `asset_in` in `asset_out` in the pool over the last `window`
+	/// blocks, both in their smallest units. `None` if the observations of the pool don't go
+	/// back that far.
+	pub fn get_twap(
+		pool_id: StableAssetPoolId,
+		asset_in: T::AssetId,
+		asset_out: T::AssetId,
+		window: BlockNumberFor<T>,
+	) -> Option<FixedU128> {
+		if window.is_zero() {
+			return None;
+		}
+		let pool_info = Pools::<T>::get(pool_id)?;
+		let i = pool_info
+			.assets
+			.iter()
+			.position(|asset| *asset == asset_in)?;
+		let j = pool_info
+			.assets
+			.iter()
+			.position(|asset| *asset == asset_out)?;
+
+		let now = T::BlockNumberProvider::current_block_number();
+		let start = now.checked_sub(&window)?;
+		let current =
+			Self::accumulate_prices(PriceAccumulators::<T>::get(pool_id)?, &pool_info, now);
+		let observation = PriceObservations::<T>::get(pool_id)
+			.into_iter()
+			.rev()
+			.find(|observation| observation.block_number <= start)?;
+
+		let elapsed: u128 = now
+			.saturating_sub(observation.block_number)
+			.saturated_into();
+		let average_price = |k: usize| -> Option<u128> {
+			current
+				.cumulative_prices
+				.get(k)?
+				.wrapping_sub(*observation.cumulative_prices.get(k)?)
+				.checked_div(elapsed)
+		};
+		FixedU128::checked_from_rational(average_price(i)?, average_price(j)?)
+	}
+	pub fn do_set_pool_status(pool_id: StableAssetPoolId, status: PoolStatus) -> DispatchResult {
+		ensure!(Pools::<T>::contains_key(pool_id), Error::<T>::PoolNotFound);
+		PoolStatuses::<T>::set(pool_id, status);
+		Self::deposit_event(Event::PoolStatusSet { pool_id, status });
+		Ok(())
+	}
+
+	pub fn do_set_asset_status(asset_id: T::AssetId, status: PoolStatus) -> DispatchResult {
+		AssetStatuses::<T>::set(asset_id, status);
+		Self::deposit_event(Event::AssetStatusSet { asset_id, status });
+		Ok(())
+	}
+
+	pub fn do_set_circuit_breaker(
+		pool_id: StableAssetPoolId,
+		circuit_breaker: Option<CircuitBreaker>,
+	) -> DispatchResult {
+		ensure!(Pools::<T>::contains_key(pool_id), Error::<T>::PoolNotFound);
+		CircuitBreakers::<T>::set(pool_id, circuit_breaker);
+		Self::deposit_event(Event::CircuitBreakerSet {
+			pool_id,
+			circuit_breaker,
+		});
+		Ok(())
+	}
+
+	/// The status of the pool, the most restrictive of the pool status and of its assets.
+	pub fn get_pool_status(pool_id: StableAssetPoolId, assets: &[T::AssetId]) -> PoolStatus {
+		assets
+			.iter()
+			.map(AssetStatuses::<T>::get)
+			.fold(PoolStatuses::<T>::get(pool_id), sp_std::cmp::max)
+	}
+
+	/// Ensure the pool status allows the operation. Only proportional redemption is allowed in
+	/// emergency mode.
+	pub fn ensure_pool_status(
+		pool_id: StableAssetPoolId,
+		assets: &[T::AssetId],
+		is_redeem_proportion: bool,
+	) -> DispatchResult {
+		match Self::get_pool_status(pool_id, assets) {
+			PoolStatus::Normal => Ok(()),
+			PoolStatus::Emergency if is_redeem_proportion => Ok(()),
+			PoolStatus::Emergency => Err(Error::<T>::PoolInEmergency.into()),
+			PoolStatus::Paused => Err(Error::<T>::PoolPaused.into()),
+		}
+	}
+
+	/// The spread between the largest and the smallest balance, as a share of the largest.
+	pub fn get_imbalance(balances: &[T::Balance]) -> Permill {
+		let balances: Vec<u128> = Self::convert_vec_balance_to_number(balances.to_vec())
+			.into_iter()
+			.map(|x| x.saturated_into::<u128>())
+			.collect();
+		match (balances.iter().max(), balances.iter().min()) {
+			(Some(&max), Some(&min)) if max > 0 => {
+				Permill::from_rational(max.saturating_sub(min), max)
+			}
+			_ => Permill::zero(),
+		}
+	}
+
+	/// Ensure a trade moving the pool from `old_balances` to `new_balances` leaves them within the
+	/// imbalance threshold of its circuit breaker, or at least doesn't increase the imbalance, so
+	/// that trades restoring the balances of a pool beyond the threshold still go through. Trades
+	/// are checked before any transfer.
+	pub fn ensure_balanced(
+		pool_id: StableAssetPoolId,
+		old_balances: &[T::Balance],
+		new_balances: &[T::Balance],
+	) -> DispatchResult {
+		if let Some(circuit_breaker) = CircuitBreakers::<T>::get(pool_id) {
+			let new_imbalance = Self::get_imbalance(new_balances);
+			ensure!(
+				new_imbalance <= circuit_breaker.max_imbalance
+					|| new_imbalance <= Self::get_imbalance(old_balances),
+				Error::<T>::PoolImbalanced
+			);
+		}
+		Ok(())
+	}
+
+	/// Put a normal pool into emergency mode if its balances are too imbalanced, e.g. after a
+	/// token rate change.
+	pub fn check_imbalance(pool_id: StableAssetPoolId, balances: &[T::Balance]) {
+		let Some(circuit_breaker) = CircuitBreakers::<T>::get(pool_id) else {
+			return;
+		};
+		let imbalance = Self::get_imbalance(balances);
+		if imbalance > circuit_breaker.max_imbalance {
+			Self::trip_circuit_breaker(pool_id, TripReason::Imbalance(imbalance));
+		}
+	}
+
+	/// Put a normal pool into emergency mode if its circuit breaker trips on token rate jumps.
+	pub fn check_token_rate_jump(pool_id: StableAssetPoolId, asset_id: T::AssetId) {
+		if CircuitBreakers::<T>::get(pool_id)
+			.is_some_and(|circuit_breaker| circuit_breaker.trip_on_rate_jump)
+		{
+			Self::trip_circuit_breaker(pool_id, TripReason::TokenRateJump(asset_id));
+		}
+	}
+
+	fn trip_circuit_breaker(pool_id: StableAssetPoolId, reason: TripReason<T::AssetId>) {
+		if PoolStatuses::<T>::get(pool_id) != PoolStatus::Normal {
+			return;
+		}
+		PoolStatuses::<T>::insert(pool_id, PoolStatus::Emergency);
+		Self::deposit_event(Event::CircuitBreakerTripped { pool_id, reason });
+	}
 }
 
 impl<T: Config> StableAsset for Pallet<T> {
@@ -1793,6 +2239,10 @@ impl<T: Config> StableAsset for Pallet<T> {
 			Self::BlockNumber,
 		>,
 	) {
+		// The prices up to this block are those of the pool before the update.
+		if let Some(old_pool_info) = Pools::<T>::get(pool_id) {
+			Self::update_price_accumulator(pool_id, &old_pool_info);
+		}
 		Pools::<T>::insert(pool_id, pool_info)
 	}
 
@@ -2039,6 +2489,8 @@ impl<T: Config> StableAsset for Pallet<T> {
 	) -> DispatchResult {
 		Pools::<T>::try_mutate_exists(pool_id, |maybe_pool_info| -> DispatchResult {
 			let pool_info = maybe_pool_info.as_mut().ok_or(Error::<T>::PoolNotFound)?;
+			Self::ensure_pool_status(pool_id, &pool_info.assets, false)?;
+			Self::update_price_accumulator(pool_id, pool_info);
 			Self::collect_yield(pool_id, pool_info)?;
 			let MintResult {
 				mint_amount,
@@ -2054,6 +2506,7 @@ impl<T: Config> StableAsset for Pallet<T> {
 			)
 			.ok_or(Error::<T>::Math)?;
 			ensure!(mint_amount >= min_mint_amount, Error::<T>::MintUnderMin);
+			Self::ensure_balanced(pool_id, &pool_info.balances, &balances)?;
 			for (i, amount) in amounts.iter().enumerate() {
 				if *amount == Zero::zero() {
 					continue;
@@ -2068,6 +2521,7 @@ impl<T: Config> StableAsset for Pallet<T> {
 			pool_info.total_supply = total_supply;
 			pool_info.balances = balances;
 			Self::collect_fee(pool_id, pool_info)?;
+			Self::check_imbalance(pool_id, &pool_info.balances);
 			Self::deposit_event(Event::LiquidityAdded {
 				minter: who.clone(),
 				pool_id,
@@ -2107,6 +2561,8 @@ impl<T: Config> StableAsset for Pallet<T> {
 			pool_id,
 			|maybe_pool_info| -> sp_std::result::Result<(Self::Balance, Self::Balance), DispatchError> {
 				let pool_info = maybe_pool_info.as_mut().ok_or(Error::<T>::PoolNotFound)?;
+				Self::ensure_pool_status(pool_id, &pool_info.assets, false)?;
+				Self::update_price_accumulator(pool_id, pool_info);
 				let asset_length_usize = asset_length as usize;
 				ensure!(asset_length_usize == pool_info.assets.len(), Error::<T>::ArgumentsError);
 				Self::collect_yield(pool_id, pool_info)?;
@@ -2122,6 +2578,7 @@ impl<T: Config> StableAsset for Pallet<T> {
 				let j_usize = j as usize;
 				balances[i_usize] = balance_i;
 				balances[j_usize] = y;
+				Self::ensure_balanced(pool_id, &pool_info.balances, &balances)?;
 				T::Assets::transfer(pool_info.assets[i_usize], who, &pool_info.account_id, dx)?;
 				T::Assets::transfer(pool_info.assets[j_usize], &pool_info.account_id, who, dy)?;
 				let asset_i = pool_info.assets[i_usize];
@@ -2130,6 +2587,7 @@ impl<T: Config> StableAsset for Pallet<T> {
 				// Since the actual output amount is round down, collect fee should update the pool balances and
 				// total supply
 				Self::collect_fee(pool_id, pool_info)?;
+				Self::check_imbalance(pool_id, &pool_info.balances);
 				let a: T::AtLeast64BitUnsigned = Self::get_a(
 					pool_info.a,
 					pool_info.a_block,
@@ -2170,6 +2628,8 @@ impl<T: Config> StableAsset for Pallet<T> {
 	) -> DispatchResult {
 		Pools::<T>::try_mutate_exists(pool_id, |maybe_pool_info| -> DispatchResult {
 			let pool_info = maybe_pool_info.as_mut().ok_or(Error::<T>::PoolNotFound)?;
+			Self::ensure_pool_status(pool_id, &pool_info.assets, true)?;
+			Self::update_price_accumulator(pool_id, pool_info);
 			Self::collect_yield(pool_id, pool_info)?;
 			ensure!(
 				min_redeem_amounts.len() == pool_info.assets.len(),
@@ -2205,6 +2665,7 @@ impl<T: Config> StableAsset for Pallet<T> {
 			// Since the output amounts are round down, collect fee updates pool balances and total
 			// supply.
 			Self::collect_fee(pool_id, pool_info)?;
+			Self::check_imbalance(pool_id, &pool_info.balances);
 			let a: T::AtLeast64BitUnsigned = Self::get_a(
 				pool_info.a,
 				pool_info.a_block,
@@ -2249,6 +2710,8 @@ impl<T: Config> StableAsset for Pallet<T> {
 			pool_id,
 			|maybe_pool_info| -> sp_std::result::Result<(Self::Balance, Self::Balance), DispatchError> {
 				let pool_info = maybe_pool_info.as_mut().ok_or(Error::<T>::PoolNotFound)?;
+				Self::ensure_pool_status(pool_id, &pool_info.assets, false)?;
+				Self::update_price_accumulator(pool_id, pool_info);
 				Self::collect_yield(pool_id, pool_info)?;
 				let RedeemSingleResult {
 					dy,
@@ -2262,6 +2725,7 @@ impl<T: Config> StableAsset for Pallet<T> {
 				let asset_length_usize = asset_length as usize;
 				ensure!(asset_length_usize == pool_size, Error::<T>::ArgumentsError);
 				ensure!(dy >= min_redeem_amount, Error::<T>::RedeemUnderMin);
+				Self::ensure_balanced(pool_id, &pool_info.balances, &balances)?;
 				if fee_amount > Zero::zero() {
 					T::Assets::transfer(pool_info.pool_asset, who, &pool_info.fee_recipient, fee_amount)?;
 				}
@@ -2280,6 +2744,7 @@ impl<T: Config> StableAsset for Pallet<T> {
 				pool_info.balances = balances;
 				// Since the output amounts are round down, collect fee updates pool balances and total supply.
 				Self::collect_fee(pool_id, pool_info)?;
+				Self::check_imbalance(pool_id, &pool_info.balances);
 				let a: T::AtLeast64BitUnsigned = Self::get_a(
 					pool_info.a,
 					pool_info.a_block,
@@ -2320,6 +2785,8 @@ impl<T: Config> StableAsset for Pallet<T> {
 	) -> DispatchResult {
 		Pools::<T>::try_mutate_exists(pool_id, |maybe_pool_info| -> DispatchResult {
 			let pool_info = maybe_pool_info.as_mut().ok_or(Error::<T>::PoolNotFound)?;
+			Self::ensure_pool_status(pool_id, &pool_info.assets, false)?;
+			Self::update_price_accumulator(pool_id, pool_info);
 			Self::collect_yield(pool_id, pool_info)?;
 			let RedeemMultiResult {
 				redeem_amount,
@@ -2333,6 +2800,7 @@ impl<T: Config> StableAsset for Pallet<T> {
 				redeem_amount <= max_redeem_amount,
 				Error::<T>::RedeemOverMax
 			);
+			Self::ensure_balanced(pool_id, &pool_info.balances, &balances)?;
 			if fee_amount > zero {
 				T::Assets::transfer(
 					pool_info.pool_asset,
@@ -2356,6 +2824,7 @@ impl<T: Config> StableAsset for Pallet<T> {
 			pool_info.total_supply = total_supply;
 			pool_info.balances = balances;
 			Self::collect_fee(pool_id, pool_info)?;
+			Self::check_imbalance(pool_id, &pool_info.balances);
 			let a: T::AtLeast64BitUnsigned = Self::get_a(
 				pool_info.a,
 				pool_info.a_block,
@@ -2589,3 +3058,34 @@ impl<T: Config> StableAsset for Pallet<T> {
 		Self::get_a(a0, t0, a1, t1)
 	}
 }
+
+impl<T: Config> StableTwapProvider<T::AssetId, BlockNumberFor<T>> for Pallet<T> {
+	fn get_twap(
+		pool_id: StableAssetPoolId,
+		currency_in: T::AssetId,
+		currency_out: T::AssetId,
+		window: BlockNumberFor<T>,
+	) -> Option<FixedU128> {
+		Self::get_twap(pool_id, currency_in, currency_out, window)
+	}
+}
+
+impl<T: Config> StableLpTokenProvider<T::AssetId, T::Balance> for Pallet<T> {
+	fn get_pool_reserves(
+		lp_token: T::AssetId,
+	) -> Option<(Vec<(T::AssetId, T::Balance)>, T::Balance)> {
+		let pool_info =
+			Pools::<T>::iter_values().find(|pool_info| pool_info.pool_asset == lp_token)?;
+		let reserves = pool_info
+			.assets
+			.iter()
+			.map(|asset| {
+				(
+					*asset,
+					T::Assets::free_balance(*asset, &pool_info.account_id),
+				)
+			})
+			.collect();
+		Some((reserves, T::Assets::total_issuance(lp_token)))
+	}
+}
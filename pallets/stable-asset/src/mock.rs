@@ -22,7 +22,7 @@ use frame_support::{
 	derive_impl,
 	dispatch::DispatchResult,
 	parameter_types,
-	traits::{ConstU128, ConstU32, Currency, EnsureOrigin, Nothing, OnUnbalanced},
+	traits::{ConstU128, ConstU32, ConstU64, Currency, EnsureOrigin, Nothing, OnUnbalanced},
 };
 use frame_system::RawOrigin;
 use orml_traits::MultiCurrency;
@@ -302,6 +302,8 @@ impl stable_asset::Config for Test {
 	type ListingOrigin = EnsureStableAsset;
 	type EnsurePoolAssetId = EnsurePoolAssetId;
 	type BlockNumberProvider = System;
+	type PriceObservationPeriod = ConstU64<10>;
+	type MaxPriceObservations = ConstU32<24>;
 }
 
 // Build genesis storage according to the mock runtime.
@@ -17,12 +17,13 @@
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
 use crate::{
-	mock::*, traits::StableAsset as StableAssetInterface, Error, MintResult, PoolCount, Pools,
-	RedeemMultiResult, RedeemProportionResult, RedeemSingleResult, StableAssetPoolInfo, SwapResult,
+	mock::*, traits::StableAsset as StableAssetInterface, CircuitBreaker, Error, MintResult,
+	PoolCount, PoolStatus, PoolStatuses, Pools, PriceObservations, RedeemMultiResult,
+	RedeemProportionResult, RedeemSingleResult, StableAssetPoolInfo, SwapResult, TripReason,
 };
 use frame_support::{assert_noop, assert_ok};
 use orml_traits::MultiCurrency;
-use sp_runtime::DispatchError;
+use sp_runtime::{DispatchError, FixedPointNumber, FixedU128, Permill};
 
 pub const BALANCE_OFF: u128 = 1;
 
@@ -1740,3 +1741,283 @@ fn swap_should_work_with_unbalance_coin1() {
 		));
 	});
 }
+
+#[test]
+fn twap_work() {
+	new_test_ext().execute_with(|| {
+		let (coin0, coin1, _, _) = create_pool();
+		System::set_block_number(2);
+		assert_ok!(StableAsset::mint(
+			RuntimeOrigin::signed(1),
+			0,
+			vec![10000000u128, 10000000u128],
+			0
+		));
+		let balanced_prices =
+			StableAsset::get_spot_prices(&Pools::<Test>::get(0).unwrap()).unwrap();
+		assert_eq!(
+			balanced_prices,
+			vec![1000000000000000000u128, 1000000000000000000u128]
+		);
+
+		System::set_block_number(12);
+		assert_ok!(StableAsset::swap(
+			RuntimeOrigin::signed(1),
+			0,
+			0,
+			1,
+			5000000u128,
+			0,
+			2
+		));
+		assert_eq!(PriceObservations::<Test>::get(0).len(), 2);
+		let prices = StableAsset::get_spot_prices(&Pools::<Test>::get(0).unwrap()).unwrap();
+		// coin1 is scarcer after the swap
+		assert!(prices[1] > balanced_prices[1]);
+
+		System::set_block_number(22);
+		assert_eq!(
+			StableAsset::get_twap(0, coin1, coin0, 10),
+			FixedU128::checked_from_rational(prices[1], prices[0])
+		);
+		assert_eq!(
+			StableAsset::get_twap(0, coin1, coin0, 20),
+			FixedU128::checked_from_rational((balanced_prices[1] + prices[1]) / 2, prices[0])
+		);
+		assert_eq!(
+			StableAsset::get_twap(0, coin0, coin1, 10),
+			FixedU128::checked_from_rational(prices[0], prices[1])
+		);
+		assert_eq!(StableAsset::get_twap(0, coin1, coin0, 0), None);
+		assert_eq!(StableAsset::get_twap(0, coin1, coin0, 30), None);
+		assert_eq!(StableAsset::get_twap(1, coin1, coin0, 10), None);
+	});
+}
+
+#[test]
+fn pool_status_work() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let (coin0, _, _, _) = create_pool();
+		assert_ok!(StableAsset::mint(
+			RuntimeOrigin::signed(1),
+			0,
+			vec![10000000u128, 10000000u128],
+			0
+		));
+		assert_noop!(
+			StableAsset::set_pool_status(RuntimeOrigin::signed(1), 1, PoolStatus::Paused),
+			Error::<Test>::PoolNotFound
+		);
+
+		assert_ok!(StableAsset::set_pool_status(
+			RuntimeOrigin::signed(1),
+			0,
+			PoolStatus::Emergency
+		));
+		System::assert_last_event(RuntimeEvent::StableAsset(crate::Event::PoolStatusSet {
+			pool_id: 0,
+			status: PoolStatus::Emergency,
+		}));
+		assert_noop!(
+			StableAsset::swap(RuntimeOrigin::signed(1), 0, 0, 1, 5000000u128, 0, 2),
+			Error::<Test>::PoolInEmergency
+		);
+		assert_noop!(
+			StableAsset::mint(RuntimeOrigin::signed(1), 0, vec![100u128, 100u128], 0),
+			Error::<Test>::PoolInEmergency
+		);
+		assert_noop!(
+			StableAsset::redeem_single(RuntimeOrigin::signed(1), 0, 1000u128, 0, 0u128, 2),
+			Error::<Test>::PoolInEmergency
+		);
+		assert_ok!(StableAsset::redeem_proportion(
+			RuntimeOrigin::signed(1),
+			0,
+			1000u128,
+			vec![0u128, 0u128]
+		));
+
+		assert_ok!(StableAsset::set_pool_status(
+			RuntimeOrigin::signed(1),
+			0,
+			PoolStatus::Normal
+		));
+		assert_ok!(StableAsset::set_asset_status(
+			RuntimeOrigin::signed(1),
+			coin0,
+			PoolStatus::Paused
+		));
+		System::assert_last_event(RuntimeEvent::StableAsset(crate::Event::AssetStatusSet {
+			asset_id: coin0,
+			status: PoolStatus::Paused,
+		}));
+		assert_eq!(
+			StableAsset::get_pool_status(0, &[coin0]),
+			PoolStatus::Paused
+		);
+		assert_noop!(
+			StableAsset::redeem_proportion(
+				RuntimeOrigin::signed(1),
+				0,
+				1000u128,
+				vec![0u128, 0u128]
+			),
+			Error::<Test>::PoolPaused
+		);
+
+		assert_ok!(StableAsset::set_asset_status(
+			RuntimeOrigin::signed(1),
+			coin0,
+			PoolStatus::Normal
+		));
+		assert_ok!(StableAsset::swap(
+			RuntimeOrigin::signed(1),
+			0,
+			0,
+			1,
+			5000u128,
+			0,
+			2
+		));
+	});
+}
+
+#[test]
+fn circuit_breaker_rejects_imbalanced_trades() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		create_pool();
+		let circuit_breaker = CircuitBreaker {
+			max_imbalance: Permill::from_percent(50),
+			trip_on_rate_jump: false,
+		};
+		assert_noop!(
+			StableAsset::set_circuit_breaker(RuntimeOrigin::signed(1), 1, Some(circuit_breaker)),
+			Error::<Test>::PoolNotFound
+		);
+		assert_ok!(StableAsset::set_circuit_breaker(
+			RuntimeOrigin::signed(1),
+			0,
+			Some(circuit_breaker)
+		));
+		System::assert_last_event(RuntimeEvent::StableAsset(crate::Event::CircuitBreakerSet {
+			pool_id: 0,
+			circuit_breaker: Some(circuit_breaker),
+		}));
+		assert_ok!(StableAsset::mint(
+			RuntimeOrigin::signed(1),
+			0,
+			vec![10000000u128, 10000000u128],
+			0
+		));
+		assert_noop!(
+			StableAsset::mint(RuntimeOrigin::signed(1), 0, vec![30000000u128, 0], 0),
+			Error::<Test>::PoolImbalanced
+		);
+
+		// The trade is rejected before any balance moves.
+		let balances = Pools::<Test>::get(0).unwrap().balances;
+		assert_noop!(
+			StableAsset::swap(RuntimeOrigin::signed(1), 0, 0, 1, 8000000u128, 0, 2),
+			Error::<Test>::PoolImbalanced
+		);
+		assert_eq!(Pools::<Test>::get(0).unwrap().balances, balances);
+		assert_eq!(PoolStatuses::<Test>::get(0), PoolStatus::Normal);
+
+		assert_ok!(StableAsset::swap(
+			RuntimeOrigin::signed(1),
+			0,
+			0,
+			1,
+			1000000u128,
+			0,
+			2
+		));
+		assert!(
+			StableAsset::get_imbalance(&Pools::<Test>::get(0).unwrap().balances)
+				<= Permill::from_percent(50)
+		);
+
+		assert_ok!(StableAsset::set_circuit_breaker(
+			RuntimeOrigin::signed(1),
+			0,
+			None
+		));
+		assert_ok!(StableAsset::swap(
+			RuntimeOrigin::signed(1),
+			0,
+			0,
+			1,
+			8000000u128,
+			0,
+			2
+		));
+	});
+}
+
+#[test]
+fn circuit_breaker_trips_on_imbalance() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		create_pool();
+		assert_ok!(StableAsset::mint(
+			RuntimeOrigin::signed(1),
+			0,
+			vec![10000000u128, 10000000u128],
+			0
+		));
+		assert_ok!(StableAsset::swap(
+			RuntimeOrigin::signed(1),
+			0,
+			0,
+			1,
+			8000000u128,
+			0,
+			2
+		));
+		assert!(
+			StableAsset::get_imbalance(&Pools::<Test>::get(0).unwrap().balances)
+				> Permill::from_percent(50)
+		);
+		assert_ok!(StableAsset::set_circuit_breaker(
+			RuntimeOrigin::signed(1),
+			0,
+			Some(CircuitBreaker {
+				max_imbalance: Permill::from_percent(50),
+				trip_on_rate_jump: false,
+			})
+		));
+
+		// Trades increasing the imbalance are still rejected.
+		assert_noop!(
+			StableAsset::swap(RuntimeOrigin::signed(1), 0, 0, 1, 1000u128, 0, 2),
+			Error::<Test>::PoolImbalanced
+		);
+
+		// A trade reducing the imbalance goes through, and the pool trips as it is still beyond
+		// the threshold.
+		assert_ok!(StableAsset::swap(
+			RuntimeOrigin::signed(1),
+			0,
+			1,
+			0,
+			1000u128,
+			0,
+			2
+		));
+		assert_eq!(PoolStatuses::<Test>::get(0), PoolStatus::Emergency);
+		let imbalance = StableAsset::get_imbalance(&Pools::<Test>::get(0).unwrap().balances);
+		assert!(imbalance > Permill::from_percent(50));
+		System::assert_has_event(RuntimeEvent::StableAsset(
+			crate::Event::CircuitBreakerTripped {
+				pool_id: 0,
+				reason: TripReason::Imbalance(imbalance),
+			},
+		));
+		assert_noop!(
+			StableAsset::swap(RuntimeOrigin::signed(1), 0, 1, 0, 1000u128, 0, 2),
+			Error::<Test>::PoolInEmergency
+		);
+	});
+}
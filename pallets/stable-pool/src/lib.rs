@@ -461,16 +461,25 @@ impl<T: Config> Pallet<T> {
 			let old_price = U256::from(fee_denominator)
 				.checked_mul(numerator_u256)?
 				.checked_div(demoninator_u256)?;
-			// Skip if the new price is less than old price.
-			if new_price <= delta.checked_add(old_price)? && new_price > old_price {
+			// The price may move by at most the hardcap, whether it goes up or down. A drop within
+			// the hardcap, e.g. after a slash, is applied as well, otherwise the pool keeps pricing
+			// the vToken above its value. A drop beyond it is a rate bug just like a jump.
+			let change = if new_price > old_price {
+				new_price.saturating_sub(old_price)
+			} else {
+				old_price.saturating_sub(new_price)
+			};
+			if change.is_zero() {
+				// Do not update token rate or emit failed event if the price is the same.
+				return Some(());
+			} else if change <= delta {
 				return bifrost_stable_asset::Pallet::<T>::set_token_rate(
 					pool_id,
 					sp_std::vec![(vtoken, (vtoken_issuance, token_pool_amount))],
 				)
 				.ok();
-			} else if new_price == old_price {
-				// Do not update token rate or emit failed event if the price is the same.
-				return Some(());
+			} else {
+				bifrost_stable_asset::Pallet::<T>::check_token_rate_jump(pool_id, vtoken);
 			}
 		}
 		None
@@ -489,6 +498,7 @@ impl<T: Config> Pallet<T> {
 	) -> DispatchResult {
 		let mut pool_info =
 			T::StableAsset::pool(pool_id).ok_or(bifrost_stable_asset::Error::<T>::PoolNotFound)?;
+		bifrost_stable_asset::Pallet::<T>::ensure_pool_status(pool_id, &pool_info.assets, false)?;
 		let amounts_old = amounts.clone();
 		for (i, amount) in amounts.iter_mut().enumerate() {
 			*amount = Self::upscale(
@@ -515,6 +525,11 @@ impl<T: Config> Pallet<T> {
 		)
 		.ok_or(bifrost_stable_asset::Error::<T>::Math)?;
 		ensure!(mint_amount >= min_mint_amount, Error::<T>::MintUnderMin);
+		bifrost_stable_asset::Pallet::<T>::ensure_balanced(
+			pool_id,
+			&pool_info.balances,
+			&balances,
+		)?;
 		for (i, amount) in amounts.iter().enumerate() {
 			if *amount == Zero::zero() {
 				continue;
@@ -553,6 +568,7 @@ impl<T: Config> Pallet<T> {
 		pool_info.total_supply = total_supply;
 		pool_info.balances = balances;
 		T::StableAsset::collect_fee(pool_id, &mut pool_info)?;
+		bifrost_stable_asset::Pallet::<T>::check_imbalance(pool_id, &pool_info.balances);
 		T::StableAsset::insert_pool(pool_id, &pool_info);
 		bifrost_stable_asset::Pallet::<T>::deposit_event(
 			bifrost_stable_asset::Event::<T>::LiquidityAdded {
@@ -579,6 +595,7 @@ impl<T: Config> Pallet<T> {
 	) -> DispatchResult {
 		let mut pool_info =
 			T::StableAsset::pool(pool_id).ok_or(bifrost_stable_asset::Error::<T>::PoolNotFound)?;
+		bifrost_stable_asset::Pallet::<T>::ensure_pool_status(pool_id, &pool_info.assets, true)?;
 		T::StableAsset::collect_yield(pool_id, &mut pool_info)?;
 		ensure!(
 			min_redeem_amounts.len() == pool_info.assets.len(),
@@ -638,6 +655,7 @@ impl<T: Config> Pallet<T> {
 		// Since the output amounts are round down, collect fee updates pool balances and total
 		// supply.
 		T::StableAsset::collect_fee(pool_id, &mut pool_info)?;
+		bifrost_stable_asset::Pallet::<T>::check_imbalance(pool_id, &pool_info.balances);
 		T::StableAsset::insert_pool(pool_id, &pool_info);
 		let a = T::StableAsset::get_a(
 			pool_info.a,
@@ -671,6 +689,7 @@ impl<T: Config> Pallet<T> {
 	) -> DispatchResult {
 		let mut pool_info =
 			T::StableAsset::pool(pool_id).ok_or(bifrost_stable_asset::Error::<T>::PoolNotFound)?;
+		bifrost_stable_asset::Pallet::<T>::ensure_pool_status(pool_id, &pool_info.assets, false)?;
 		T::StableAsset::collect_yield(pool_id, &mut pool_info)?;
 		let mut new_amounts = amounts.clone();
 		for (i, amount) in new_amounts.iter_mut().enumerate() {
@@ -695,6 +714,11 @@ impl<T: Config> Pallet<T> {
 			redeem_amount <= max_redeem_amount,
 			Error::<T>::RedeemOverMax
 		);
+		bifrost_stable_asset::Pallet::<T>::ensure_balanced(
+			pool_id,
+			&pool_info.balances,
+			&balances,
+		)?;
 		if fee_amount > zero {
 			<T as bifrost_stable_asset::Config>::Assets::transfer(
 				pool_info.pool_asset,
@@ -722,6 +746,7 @@ impl<T: Config> Pallet<T> {
 		pool_info.total_supply = total_supply;
 		pool_info.balances = balances;
 		T::StableAsset::collect_fee(pool_id, &mut pool_info)?;
+		bifrost_stable_asset::Pallet::<T>::check_imbalance(pool_id, &pool_info.balances);
 		T::StableAsset::insert_pool(pool_id, &pool_info);
 		let a = T::StableAsset::get_a(
 			pool_info.a,
@@ -757,6 +782,7 @@ impl<T: Config> Pallet<T> {
 	) -> Result<(T::Balance, T::Balance), DispatchError> {
 		let mut pool_info =
 			T::StableAsset::pool(pool_id).ok_or(bifrost_stable_asset::Error::<T>::PoolNotFound)?;
+		bifrost_stable_asset::Pallet::<T>::ensure_pool_status(pool_id, &pool_info.assets, false)?;
 
 		T::StableAsset::collect_yield(pool_id, &mut pool_info)?;
 		let RedeemSingleResult {
@@ -785,6 +811,11 @@ impl<T: Config> Pallet<T> {
 			dy >= min_redeem_amount,
 			bifrost_stable_asset::Error::<T>::RedeemUnderMin
 		);
+		bifrost_stable_asset::Pallet::<T>::ensure_balanced(
+			pool_id,
+			&pool_info.balances,
+			&balances,
+		)?;
 		if fee_amount > Zero::zero() {
 			T::MultiCurrency::transfer(
 				pool_info.pool_asset,
@@ -801,6 +832,7 @@ impl<T: Config> Pallet<T> {
 		// Since the output amounts are round down, collect fee updates pool balances and total
 		// supply.
 		T::StableAsset::collect_fee(pool_id, &mut pool_info)?;
+		bifrost_stable_asset::Pallet::<T>::check_imbalance(pool_id, &pool_info.balances);
 		T::StableAsset::insert_pool(pool_id, &pool_info);
 		let a: T::AtLeast64BitUnsigned = T::StableAsset::get_a(
 			pool_info.a,
@@ -837,6 +869,7 @@ impl<T: Config> Pallet<T> {
 	) -> DispatchResult {
 		let mut pool_info =
 			T::StableAsset::pool(pool_id).ok_or(bifrost_stable_asset::Error::<T>::PoolNotFound)?;
+		bifrost_stable_asset::Pallet::<T>::ensure_pool_status(pool_id, &pool_info.assets, false)?;
 
 		let token_in = *pool_info
 			.assets
@@ -868,6 +901,11 @@ impl<T: Config> Pallet<T> {
 		let j_usize = currency_id_out as usize;
 		balances[i_usize] = balance_i;
 		balances[j_usize] = y;
+		bifrost_stable_asset::Pallet::<T>::ensure_balanced(
+			pool_id,
+			&pool_info.balances,
+			&balances,
+		)?;
 		<T as bifrost_stable_asset::Config>::Assets::transfer(
 			pool_info.assets[i_usize],
 			who,
@@ -883,6 +921,7 @@ impl<T: Config> Pallet<T> {
 		let asset_i = pool_info.assets[i_usize];
 		let asset_j = pool_info.assets[j_usize];
 		T::StableAsset::collect_fee(pool_id, &mut pool_info)?;
+		bifrost_stable_asset::Pallet::<T>::check_imbalance(pool_id, &pool_info.balances);
 		T::StableAsset::insert_pool(pool_id, &pool_info);
 		let a = T::StableAsset::get_a(
 			pool_info.a,
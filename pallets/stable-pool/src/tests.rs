@@ -1266,3 +1266,221 @@ fn remove_vtoken_auto_refresh_should_work() {
 			);
 		});
 }
+
+#[test]
+fn swap_updates_price_accumulator() {
+	ExtBuilder::default()
+		.new_test_ext()
+		.build()
+		.execute_with(|| {
+			assert_ok!(VtokenMinting::set_minimum_mint(
+				RuntimeOrigin::signed(1),
+				DOT,
+				0
+			));
+			assert_ok!(VtokenMinting::mint(
+				Some(3).into(),
+				DOT,
+				100_000_000,
+				BoundedVec::default(),
+				None
+			));
+			let (coin0, coin1, _, _) = create_pool();
+			System::set_block_number(2);
+			assert_ok!(StablePool::add_liquidity(
+				RuntimeOrigin::signed(3),
+				0,
+				vec![10000000u128, 20000000u128],
+				0
+			));
+			assert_eq!(
+				bifrost_stable_asset::PriceAccumulators::<Test>::get(0).map(|acc| acc.block_number),
+				Some(2)
+			);
+
+			System::set_block_number(12);
+			assert_ok!(StablePool::swap(
+				RuntimeOrigin::signed(3),
+				0,
+				0,
+				1,
+				5000000u128,
+				0
+			));
+			assert_eq!(
+				bifrost_stable_asset::PriceObservations::<Test>::get(0).len(),
+				2
+			);
+
+			System::set_block_number(22);
+			assert!(StableAsset::get_twap(0, coin1, coin0, 10).is_some());
+			assert!(StableAsset::get_twap(0, coin1, coin0, 20).is_some());
+		});
+}
+
+#[test]
+fn token_rate_jump_trips_circuit_breaker() {
+	ExtBuilder::default()
+		.new_test_ext()
+		.build()
+		.execute_with(|| {
+			let (coin0, coin1, _pool_asset, _swap_id) = init();
+
+			assert_ok!(StablePool::config_vtoken_auto_refresh(
+				RuntimeOrigin::root(),
+				VDOT,
+				Permill::from_percent(10)
+			));
+			assert_ok!(StablePool::edit_token_rate(
+				RuntimeOrigin::root(),
+				0,
+				vec![(coin0, (1, 1)), (coin1, (1, 1))]
+			));
+			assert_ok!(StableAsset::set_circuit_breaker(
+				RuntimeOrigin::signed(1),
+				0,
+				Some(bifrost_stable_asset::CircuitBreaker {
+					max_imbalance: Permill::one(),
+					trip_on_rate_jump: true,
+				})
+			));
+
+			assert_ok!(<Test as crate::Config>::VtokenMinting::increase_token_pool(
+				DOT, 20_000_000
+			));
+			assert_ok!(StablePool::on_swap(&3u128, 0, 0, 1, 5000000u128, 0));
+			assert_eq!(
+				bifrost_stable_asset::PoolStatuses::<Test>::get(0),
+				bifrost_stable_asset::PoolStatus::Emergency
+			);
+			System::assert_has_event(RuntimeEvent::StableAsset(
+				bifrost_stable_asset::Event::CircuitBreakerTripped {
+					pool_id: 0,
+					reason: bifrost_stable_asset::TripReason::TokenRateJump(VDOT),
+				},
+			));
+			assert_noop!(
+				StablePool::swap(RuntimeOrigin::signed(3), 0, 0, 1, 5000000u128, 0),
+				bifrost_stable_asset::Error::<Test>::PoolInEmergency
+			);
+			assert_ok!(StablePool::redeem_proportion(
+				RuntimeOrigin::signed(3),
+				0,
+				1000u128,
+				vec![0u128, 0u128]
+			));
+		});
+}
+
+#[test]
+fn token_rate_drop_within_the_hardcap_should_refresh() {
+	ExtBuilder::default()
+		.new_test_ext()
+		.build()
+		.execute_with(|| {
+			let (coin0, coin1, _pool_asset, _swap_id) = init();
+
+			assert_ok!(StablePool::config_vtoken_auto_refresh(
+				RuntimeOrigin::root(),
+				VDOT,
+				Permill::from_percent(10)
+			));
+			assert_ok!(StablePool::edit_token_rate(
+				RuntimeOrigin::root(),
+				0,
+				vec![(coin0, (1, 1)), (coin1, (1, 1))]
+			));
+
+			assert_ok!(<Test as crate::Config>::VtokenMinting::decrease_token_pool(
+				DOT, 5_000_000
+			));
+			assert_ok!(StablePool::on_swap(&3u128, 0, 0, 1, 5000000u128, 0));
+			assert_eq!(
+				bifrost_stable_asset::TokenRateCaches::<Test>::iter_prefix(0).collect::<Vec<(
+					AssetIdOf<Test>,
+					(AtLeast64BitUnsignedOf<Test>, AtLeast64BitUnsignedOf<Test>),
+				)>>(),
+				vec![(coin0, (1, 1)), (coin1, (100000000, 95000000))]
+			);
+		});
+}
+
+#[test]
+fn token_rate_drop_beyond_the_hardcap_trips_circuit_breaker() {
+	ExtBuilder::default()
+		.new_test_ext()
+		.build()
+		.execute_with(|| {
+			let (coin0, coin1, _pool_asset, _swap_id) = init();
+
+			assert_ok!(StablePool::config_vtoken_auto_refresh(
+				RuntimeOrigin::root(),
+				VDOT,
+				Permill::from_percent(10)
+			));
+			assert_ok!(StablePool::edit_token_rate(
+				RuntimeOrigin::root(),
+				0,
+				vec![(coin0, (1, 1)), (coin1, (1, 1))]
+			));
+			assert_ok!(StableAsset::set_circuit_breaker(
+				RuntimeOrigin::signed(1),
+				0,
+				Some(bifrost_stable_asset::CircuitBreaker {
+					max_imbalance: Permill::one(),
+					trip_on_rate_jump: true,
+				})
+			));
+
+			assert_ok!(<Test as crate::Config>::VtokenMinting::decrease_token_pool(
+				DOT, 20_000_000
+			));
+			assert_ok!(StablePool::on_swap(&3u128, 0, 0, 1, 5000000u128, 0));
+			assert_eq!(
+				bifrost_stable_asset::TokenRateCaches::<Test>::iter_prefix(0).collect::<Vec<(
+					AssetIdOf<Test>,
+					(AtLeast64BitUnsignedOf<Test>, AtLeast64BitUnsignedOf<Test>),
+				)>>(),
+				vec![(coin0, (1, 1)), (coin1, (1, 1))]
+			);
+			assert_eq!(
+				bifrost_stable_asset::PoolStatuses::<Test>::get(0),
+				bifrost_stable_asset::PoolStatus::Emergency
+			);
+			System::assert_has_event(RuntimeEvent::StableAsset(
+				bifrost_stable_asset::Event::CircuitBreakerTripped {
+					pool_id: 0,
+					reason: bifrost_stable_asset::TripReason::TokenRateJump(VDOT),
+				},
+			));
+		});
+}
+
+#[test]
+fn imbalanced_swap_should_be_rejected_before_any_transfer() {
+	ExtBuilder::default()
+		.new_test_ext()
+		.build()
+		.execute_with(|| {
+			let (coin0, _coin1, _pool_asset, swap_id) = init();
+			assert_ok!(StableAsset::set_circuit_breaker(
+				RuntimeOrigin::signed(1),
+				0,
+				Some(bifrost_stable_asset::CircuitBreaker {
+					max_imbalance: Permill::from_percent(50),
+					trip_on_rate_jump: false,
+				})
+			));
+
+			let pool_balance = Tokens::free_balance(coin0, &swap_id);
+			assert_noop!(
+				StablePool::swap(RuntimeOrigin::signed(3), 0, 0, 1, 30000000u128, 0),
+				bifrost_stable_asset::Error::<Test>::PoolImbalanced
+			);
+			assert_eq!(Tokens::free_balance(coin0, &swap_id), pool_balance);
+			assert_eq!(
+				bifrost_stable_asset::PoolStatuses::<Test>::get(0),
+				bifrost_stable_asset::PoolStatus::Normal
+			);
+		});
+}
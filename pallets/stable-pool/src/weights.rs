@@ -131,14 +131,18 @@ impl WeightInfo for () {
 	/// Proof: `AssetRegistry::CurrencyMetadatas` (`max_values`: None, `max_size`: None, mode: `Measured`)
 	/// Storage: `Tokens::TotalIssuance` (r:1 w:1)
 	/// Proof: `Tokens::TotalIssuance` (`max_values`: None, `max_size`: Some(38), added: 2513, mode: `MaxEncodedLen`)
+	/// Storage: `StableAsset::PriceAccumulators` (r:1 w:1)
+	/// Proof: `StableAsset::PriceAccumulators` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `StableAsset::PriceObservations` (r:1 w:1)
+	/// Proof: `StableAsset::PriceObservations` (`max_values`: None, `max_size`: None, mode: `Measured`)
 	fn add_liquidity() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `1488`
 		//  Estimated: `8769`
 		// Minimum execution time: 122_609_000 picoseconds.
 		Weight::from_parts(127_984_000, 8769)
-			.saturating_add(RocksDbWeight::get().reads(11_u64))
-			.saturating_add(RocksDbWeight::get().writes(7_u64))
+			.saturating_add(RocksDbWeight::get().reads(13_u64))
+			.saturating_add(RocksDbWeight::get().writes(9_u64))
 	}
 	/// Storage: `StableAsset::Pools` (r:1 w:1)
 	/// Proof: `StableAsset::Pools` (`max_values`: None, `max_size`: None, mode: `Measured`)
@@ -154,14 +158,18 @@ impl WeightInfo for () {
 	/// Proof: `Tokens::TotalIssuance` (`max_values`: None, `max_size`: Some(38), added: 2513, mode: `MaxEncodedLen`)
 	/// Storage: `StableAsset::TokenRateHardcap` (r:1 w:0)
 	/// Proof: `StableAsset::TokenRateHardcap` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `StableAsset::PriceAccumulators` (r:1 w:1)
+	/// Proof: `StableAsset::PriceAccumulators` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `StableAsset::PriceObservations` (r:1 w:1)
+	/// Proof: `StableAsset::PriceObservations` (`max_values`: None, `max_size`: None, mode: `Measured`)
 	fn swap() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `1399`
 		//  Estimated: `6196`
 		// Minimum execution time: 111_540_000 picoseconds.
 		Weight::from_parts(115_438_000, 6196)
-			.saturating_add(RocksDbWeight::get().reads(8_u64))
-			.saturating_add(RocksDbWeight::get().writes(5_u64))
+			.saturating_add(RocksDbWeight::get().reads(10_u64))
+			.saturating_add(RocksDbWeight::get().writes(7_u64))
 	}
 	/// Storage: `StableAsset::Pools` (r:1 w:1)
 	/// Proof: `StableAsset::Pools` (`max_values`: None, `max_size`: None, mode: `Measured`)
@@ -175,14 +183,18 @@ impl WeightInfo for () {
 	/// Proof: `AssetRegistry::CurrencyMetadatas` (`max_values`: None, `max_size`: None, mode: `Measured`)
 	/// Storage: `Tokens::TotalIssuance` (r:1 w:1)
 	/// Proof: `Tokens::TotalIssuance` (`max_values`: None, `max_size`: Some(38), added: 2513, mode: `MaxEncodedLen`)
+	/// Storage: `StableAsset::PriceAccumulators` (r:1 w:1)
+	/// Proof: `StableAsset::PriceAccumulators` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `StableAsset::PriceObservations` (r:1 w:1)
+	/// Proof: `StableAsset::PriceObservations` (`max_values`: None, `max_size`: None, mode: `Measured`)
 	fn redeem_proportion() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `1399`
 		//  Estimated: `6196`
 		// Minimum execution time: 105_276_000 picoseconds.
 		Weight::from_parts(109_130_000, 6196)
-			.saturating_add(RocksDbWeight::get().reads(7_u64))
-			.saturating_add(RocksDbWeight::get().writes(5_u64))
+			.saturating_add(RocksDbWeight::get().reads(9_u64))
+			.saturating_add(RocksDbWeight::get().writes(7_u64))
 	}
 	/// Storage: `StableAsset::Pools` (r:1 w:1)
 	/// Proof: `StableAsset::Pools` (`max_values`: None, `max_size`: None, mode: `Measured`)
@@ -196,14 +208,18 @@ impl WeightInfo for () {
 	/// Proof: `AssetRegistry::CurrencyMetadatas` (`max_values`: None, `max_size`: None, mode: `Measured`)
 	/// Storage: `Tokens::TotalIssuance` (r:1 w:1)
 	/// Proof: `Tokens::TotalIssuance` (`max_values`: None, `max_size`: Some(38), added: 2513, mode: `MaxEncodedLen`)
+	/// Storage: `StableAsset::PriceAccumulators` (r:1 w:1)
+	/// Proof: `StableAsset::PriceAccumulators` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `StableAsset::PriceObservations` (r:1 w:1)
+	/// Proof: `StableAsset::PriceObservations` (`max_values`: None, `max_size`: None, mode: `Measured`)
 	fn redeem_single() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `1399`
 		//  Estimated: `6196`
 		// Minimum execution time: 87_950_000 picoseconds.
 		Weight::from_parts(90_586_000, 6196)
-			.saturating_add(RocksDbWeight::get().reads(7_u64))
-			.saturating_add(RocksDbWeight::get().writes(5_u64))
+			.saturating_add(RocksDbWeight::get().reads(9_u64))
+			.saturating_add(RocksDbWeight::get().writes(7_u64))
 	}
 	/// Storage: `StableAsset::Pools` (r:1 w:1)
 	/// Proof: `StableAsset::Pools` (`max_values`: None, `max_size`: None, mode: `Measured`)
@@ -217,14 +233,18 @@ impl WeightInfo for () {
 	/// Proof: `AssetRegistry::CurrencyMetadatas` (`max_values`: None, `max_size`: None, mode: `Measured`)
 	/// Storage: `Tokens::TotalIssuance` (r:1 w:1)
 	/// Proof: `Tokens::TotalIssuance` (`max_values`: None, `max_size`: Some(38), added: 2513, mode: `MaxEncodedLen`)
+	/// Storage: `StableAsset::PriceAccumulators` (r:1 w:1)
+	/// Proof: `StableAsset::PriceAccumulators` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `StableAsset::PriceObservations` (r:1 w:1)
+	/// Proof: `StableAsset::PriceObservations` (`max_values`: None, `max_size`: None, mode: `Measured`)
 	fn redeem_multi() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `1399`
 		//  Estimated: `6196`
 		// Minimum execution time: 107_490_000 picoseconds.
 		Weight::from_parts(110_310_000, 6196)
-			.saturating_add(RocksDbWeight::get().reads(7_u64))
-			.saturating_add(RocksDbWeight::get().writes(5_u64))
+			.saturating_add(RocksDbWeight::get().reads(9_u64))
+			.saturating_add(RocksDbWeight::get().writes(7_u64))
 	}
 	/// Storage: `StableAsset::Pools` (r:1 w:1)
 	/// Proof: `StableAsset::Pools` (`max_values`: None, `max_size`: None, mode: `Measured`)
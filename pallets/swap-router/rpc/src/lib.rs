@@ -0,0 +1,118 @@
+// This file is part of Bifrost.
+
+// Copyright (C) Liebi Technologies PTE. LTD.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use std::{marker::PhantomData, sync::Arc};
+
+use bifrost_primitives::{Balance, CurrencyId, SwapRoute};
+pub use bifrost_swap_router_rpc_runtime_api::{self as runtime_api, SwapRouterRuntimeApi};
+use jsonrpsee::{
+	core::{async_trait, RpcResult},
+	proc_macros::rpc,
+	types::error::{ErrorCode, ErrorObject},
+};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::traits::Block as BlockT;
+
+#[rpc(client, server)]
+pub trait SwapRouterRpcApi<BlockHash> {
+	/// rpc method for quoting the best route for an exact amount in
+	#[method(name = "swap_router_quoteExactIn")]
+	fn quote_exact_in(
+		&self,
+		currency_in: CurrencyId,
+		currency_out: CurrencyId,
+		amount_in: Balance,
+		at: Option<BlockHash>,
+	) -> RpcResult<Option<SwapRoute<CurrencyId, Balance>>>;
+
+	/// rpc method for quoting the cheapest route for an exact amount out
+	#[method(name = "swap_router_quoteExactOut")]
+	fn quote_exact_out(
+		&self,
+		currency_in: CurrencyId,
+		currency_out: CurrencyId,
+		amount_out: Balance,
+		at: Option<BlockHash>,
+	) -> RpcResult<Option<SwapRoute<CurrencyId, Balance>>>;
+}
+
+#[derive(Clone, Debug)]
+pub struct SwapRouterRpc<C, Block> {
+	client: Arc<C>,
+	_marker: PhantomData<Block>,
+}
+
+impl<C, Block> SwapRouterRpc<C, Block> {
+	pub fn new(client: Arc<C>) -> Self {
+		Self {
+			client,
+			_marker: PhantomData,
+		}
+	}
+}
+
+#[async_trait]
+impl<C, Block> SwapRouterRpcApiServer<<Block as BlockT>::Hash> for SwapRouterRpc<C, Block>
+where
+	Block: BlockT,
+	C: Send + Sync + 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+	C::Api: SwapRouterRuntimeApi<Block>,
+{
+	fn quote_exact_in(
+		&self,
+		currency_in: CurrencyId,
+		currency_out: CurrencyId,
+		amount_in: Balance,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Option<SwapRoute<CurrencyId, Balance>>> {
+		let swap_router_rpc_api = self.client.runtime_api();
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		swap_router_rpc_api
+			.quote_exact_in(at, currency_in, currency_out, amount_in)
+			.map_err(|e| {
+				ErrorObject::owned(
+					ErrorCode::InternalError.code(),
+					"Failed to quote swap_router exact in.",
+					Some(format!("{:?}", e)),
+				)
+			})
+	}
+
+	fn quote_exact_out(
+		&self,
+		currency_in: CurrencyId,
+		currency_out: CurrencyId,
+		amount_out: Balance,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Option<SwapRoute<CurrencyId, Balance>>> {
+		let swap_router_rpc_api = self.client.runtime_api();
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		swap_router_rpc_api
+			.quote_exact_out(at, currency_in, currency_out, amount_out)
+			.map_err(|e| {
+				ErrorObject::owned(
+					ErrorCode::InternalError.code(),
+					"Failed to quote swap_router exact out.",
+					Some(format!("{:?}", e)),
+				)
+			})
+	}
+}
@@ -0,0 +1,56 @@
+// This file is part of Bifrost.
+
+// Copyright (C) Liebi Technologies PTE. LTD.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use crate::{Call, Config, Pallet as SwapRouter, *};
+use bifrost_primitives::{BNC, DOT, KSM, VDOT, VKSM};
+use frame_benchmarking::v2::*;
+use frame_support::traits::EnsureOrigin;
+
+#[benchmarks]
+mod benchmarks {
+	use super::*;
+
+	#[benchmark]
+	fn set_route_assets() -> Result<(), BenchmarkError> {
+		let origin =
+			T::ControlOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		let assets = vec![BNC, DOT, VDOT, KSM, VKSM];
+
+		#[extrinsic_call]
+		_(origin as <T as frame_system::Config>::RuntimeOrigin, assets);
+
+		assert_eq!(RouteAssets::<T>::get().len(), 5);
+		Ok(())
+	}
+
+	// This line generates test cases for benchmarking, and could be run by:
+	//   `cargo test -p bifrost-swap-router --all-features`, you will see one line per case:
+	//   `test benchmarking::bench_set_route_assets ... ok`
+	//   `test benchmarking::benchmark_tests::test_benchmarks ... ok`
+	//   in the result.
+	//
+	// The line generates three steps per benchmark, with repeat=1 and the three steps are
+	//   [low, mid, high] of the range.
+	impl_benchmark_test_suite!(
+		SwapRouter,
+		crate::mock::ExtBuilder::default().build(),
+		crate::mock::Runtime
+	);
+}
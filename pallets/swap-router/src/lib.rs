@@ -0,0 +1,552 @@
+// This file is part of Bifrost.
+
+// Copyright (C) Liebi Technologies PTE. LTD.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! # Swap Router
+//!
+//! Routes swaps across the Zenlink pairs and the stable pools. A route is made of up to
+//! `MaxHops` hops going through the intermediate assets set by governance, and every hop picks
+//! whichever venue quotes best for it.
+
+// Ensure we're `no_std` when compiling for Wasm.
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+
+pub mod weights;
+
+use bifrost_primitives::{CurrencyId, SwapHop, SwapRoute, SwapRouter, TryConvertFrom};
+use bifrost_stable_pool::traits::StablePoolHandler;
+use cumulus_primitives_core::ParaId;
+use frame_support::{pallet_prelude::*, transactional};
+use frame_system::pallet_prelude::*;
+use orml_traits::MultiCurrency;
+pub use pallet::*;
+use sp_runtime::traits::{Saturating, Zero};
+use sp_std::{vec, vec::Vec};
+pub use weights::WeightInfo;
+use zenlink_protocol::{AssetId, ExportZenlink};
+
+pub type AccountIdOf<T> = <T as frame_system::Config>::AccountId;
+
+pub type BalanceOf<T> = <<T as Config>::MultiCurrency as MultiCurrency<AccountIdOf<T>>>::Balance;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+		type MultiCurrency: MultiCurrency<
+			AccountIdOf<Self>,
+			CurrencyId = CurrencyId,
+			Balance = u128,
+		>;
+
+		type ControlOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+		type WeightInfo: WeightInfo;
+
+		/// Zenlink, for the constant product pairs.
+		type DexOperator: ExportZenlink<Self::AccountId, AssetId>;
+
+		/// The stable pools.
+		type StablePoolHandler: StablePoolHandler<
+			Balance = BalanceOf<Self>,
+			AccountId = AccountIdOf<Self>,
+			CurrencyId = CurrencyId,
+		>;
+
+		type ParachainId: Get<ParaId>;
+
+		/// The maximum number of hops of a route.
+		#[pallet::constant]
+		type MaxHops: Get<u32>;
+
+		/// The maximum number of intermediate assets routes may go through.
+		#[pallet::constant]
+		type MaxRouteAssets: Get<u32>;
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// The intermediate assets of routes have been set.
+		RouteAssetsSet { assets: Vec<CurrencyId> },
+		/// A swap has been executed along a route.
+		Swapped {
+			who: AccountIdOf<T>,
+			currency_in: CurrencyId,
+			currency_out: CurrencyId,
+			amount_in: BalanceOf<T>,
+			amount_out: BalanceOf<T>,
+			hops: Vec<SwapHop<CurrencyId>>,
+		},
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// No route between the two currencies.
+		NoRoute,
+		/// The route gives less than the minimum output or needs more than the maximum input.
+		SlippageExceeded,
+		/// More intermediate assets than `MaxRouteAssets`.
+		TooManyRouteAssets,
+		/// The currency in and the currency out are the same.
+		SameCurrency,
+		/// The amount is zero.
+		ZeroAmount,
+		/// The currency cannot be converted to a Zenlink asset id.
+		ConversionError,
+	}
+
+	/// The intermediate assets routes may go through, e.g. BNC or the vTokens.
+	#[pallet::storage]
+	pub type RouteAssets<T: Config> =
+		StorageValue<_, BoundedVec<CurrencyId, T::MaxRouteAssets>, ValueQuery>;
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Set the intermediate assets routes may go through.
+		///
+		/// - `assets`: the assets, replacing the current ones.
+		#[pallet::call_index(0)]
+		#[pallet::weight(T::WeightInfo::set_route_assets())]
+		pub fn set_route_assets(origin: OriginFor<T>, assets: Vec<CurrencyId>) -> DispatchResult {
+			T::ControlOrigin::ensure_origin(origin)?;
+
+			let mut assets = assets;
+			assets.sort();
+			assets.dedup();
+			let bounded_assets: BoundedVec<CurrencyId, T::MaxRouteAssets> = assets
+				.clone()
+				.try_into()
+				.map_err(|_| Error::<T>::TooManyRouteAssets)?;
+			RouteAssets::<T>::put(bounded_assets);
+
+			Self::deposit_event(Event::RouteAssetsSet { assets });
+			Ok(())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	fn zenlink_path(
+		currency_in: CurrencyId,
+		currency_out: CurrencyId,
+	) -> Result<Vec<AssetId>, DispatchError> {
+		let para_id: u32 = T::ParachainId::get().into();
+		let asset_in = AssetId::try_convert_from(currency_in, para_id)
+			.map_err(|_| Error::<T>::ConversionError)?;
+		let asset_out = AssetId::try_convert_from(currency_out, para_id)
+			.map_err(|_| Error::<T>::ConversionError)?;
+		Ok(vec![asset_in, asset_out])
+	}
+
+	/// The hops able to swap `currency_in` for `currency_out` directly.
+	fn venues(currency_in: CurrencyId, currency_out: CurrencyId) -> Vec<SwapHop<CurrencyId>> {
+		let mut venues = vec![SwapHop::Zenlink {
+			currency_in,
+			currency_out,
+		}];
+		if let Some((pool_id, _, _)) =
+			T::StablePoolHandler::get_pool_id(&currency_in, &currency_out)
+		{
+			venues.push(SwapHop::StablePool {
+				pool_id,
+				currency_in,
+				currency_out,
+			});
+		}
+		venues
+	}
+
+	fn pool_token_indexes(
+		pool_id: u32,
+		currency_in: CurrencyId,
+		currency_out: CurrencyId,
+	) -> Option<(u32, u32)> {
+		Some((
+			T::StablePoolHandler::get_pool_token_index(pool_id, currency_in)?,
+			T::StablePoolHandler::get_pool_token_index(pool_id, currency_out)?,
+		))
+	}
+
+	/// The amount out of `hop` for `amount_in`, `None` if the venue cannot take the swap.
+	fn hop_amount_out(hop: &SwapHop<CurrencyId>, amount_in: BalanceOf<T>) -> Option<BalanceOf<T>> {
+		let amount_out = match *hop {
+			SwapHop::Zenlink {
+				currency_in,
+				currency_out,
+			} => {
+				let path = Self::zenlink_path(currency_in, currency_out).ok()?;
+				T::DexOperator::get_amount_out_by_path(amount_in, &path)
+					.ok()?
+					.last()
+					.copied()?
+			}
+			SwapHop::StablePool {
+				pool_id,
+				currency_in,
+				currency_out,
+			} => {
+				let (i, j) = Self::pool_token_indexes(pool_id, currency_in, currency_out)?;
+				T::StablePoolHandler::get_swap_output(pool_id, i, j, amount_in).ok()?
+			}
+		};
+		(!amount_out.is_zero()).then_some(amount_out)
+	}
+
+	/// The amount into `hop` for `amount_out`, `None` if the venue cannot take the swap.
+	fn hop_amount_in(hop: &SwapHop<CurrencyId>, amount_out: BalanceOf<T>) -> Option<BalanceOf<T>> {
+		let amount_in = match *hop {
+			SwapHop::Zenlink {
+				currency_in,
+				currency_out,
+			} => {
+				let path = Self::zenlink_path(currency_in, currency_out).ok()?;
+				T::DexOperator::get_amount_in_by_path(amount_out, &path)
+					.ok()?
+					.first()
+					.copied()?
+			}
+			SwapHop::StablePool {
+				pool_id,
+				currency_in,
+				currency_out,
+			} => {
+				let (i, j) = Self::pool_token_indexes(pool_id, currency_in, currency_out)?;
+				T::StablePoolHandler::get_swap_input(pool_id, i, j, amount_out).ok()?
+			}
+		};
+		(!amount_in.is_zero()).then_some(amount_in)
+	}
+
+	/// Explores the routes from the last currency of `route` to `currency_out`, keeping the one
+	/// giving the most out in `best`.
+	fn search_exact_in(
+		currency_out: CurrencyId,
+		route_assets: &[CurrencyId],
+		route: &mut SwapRoute<CurrencyId, BalanceOf<T>>,
+		current: CurrencyId,
+		best: &mut Option<SwapRoute<CurrencyId, BalanceOf<T>>>,
+	) {
+		if route.hops.len() as u32 >= T::MaxHops::get() {
+			return;
+		}
+		let amount_in = route.amount_out();
+
+		// The direct hop goes first, so that a longer route only wins when strictly better.
+		for next in sp_std::iter::once(&currency_out).chain(route_assets.iter()) {
+			let next = *next;
+			let visited = next == current
+				|| route.hops.iter().any(|hop| hop.currency_in() == next)
+				|| (next != currency_out && route.hops.len() as u32 + 1 >= T::MaxHops::get());
+			if visited {
+				continue;
+			}
+
+			let Some((hop, amount_out)) = Self::venues(current, next)
+				.into_iter()
+				.filter_map(|hop| {
+					Self::hop_amount_out(&hop, amount_in).map(|amount_out| (hop, amount_out))
+				})
+				.max_by_key(|(_, amount_out)| *amount_out)
+			else {
+				continue;
+			};
+
+			route.hops.push(hop);
+			route.amounts.push(amount_out);
+			if next == currency_out {
+				if best
+					.as_ref()
+					.map_or(true, |best| amount_out > best.amount_out())
+				{
+					*best = Some(route.clone());
+				}
+			} else {
+				Self::search_exact_in(currency_out, route_assets, route, next, best);
+			}
+			route.hops.pop();
+			route.amounts.pop();
+		}
+	}
+
+	/// Explores the routes from `currency_in` to the first currency of `route`, keeping the one
+	/// needing the least in in `best`. `route` is kept in reverse order while exploring.
+	fn search_exact_out(
+		currency_in: CurrencyId,
+		route_assets: &[CurrencyId],
+		route: &mut SwapRoute<CurrencyId, BalanceOf<T>>,
+		current: CurrencyId,
+		best: &mut Option<SwapRoute<CurrencyId, BalanceOf<T>>>,
+	) {
+		if route.hops.len() as u32 >= T::MaxHops::get() {
+			return;
+		}
+		let amount_out = route.amount_out();
+
+		for prev in sp_std::iter::once(&currency_in).chain(route_assets.iter()) {
+			let prev = *prev;
+			let visited = prev == current
+				|| route.hops.iter().any(|hop| hop.currency_out() == prev)
+				|| (prev != currency_in && route.hops.len() as u32 + 1 >= T::MaxHops::get());
+			if visited {
+				continue;
+			}
+
+			let Some((hop, amount_in)) = Self::venues(prev, current)
+				.into_iter()
+				.filter_map(|hop| {
+					Self::hop_amount_in(&hop, amount_out).map(|amount_in| (hop, amount_in))
+				})
+				.min_by_key(|(_, amount_in)| *amount_in)
+			else {
+				continue;
+			};
+
+			route.hops.push(hop);
+			route.amounts.push(amount_in);
+			if prev == currency_in {
+				if best
+					.as_ref()
+					.map_or(true, |best| amount_in < best.amount_in())
+				{
+					let mut found = route.clone();
+					found.hops.reverse();
+					found.amounts.reverse();
+					*best = Some(found);
+				}
+			} else {
+				Self::search_exact_out(currency_in, route_assets, route, prev, best);
+			}
+			route.hops.pop();
+			route.amounts.pop();
+		}
+	}
+
+	/// Executes `route` with `amount_in` and returns the amount received. Every hop takes the
+	/// full output of the previous one, failing if the route gives less than `min_amount_out`.
+	#[transactional]
+	fn execute_route(
+		who: &AccountIdOf<T>,
+		route: &SwapRoute<CurrencyId, BalanceOf<T>>,
+		amount_in: BalanceOf<T>,
+		min_amount_out: BalanceOf<T>,
+	) -> Result<BalanceOf<T>, DispatchError> {
+		let mut amount = amount_in;
+		for hop in route.hops.iter() {
+			let balance_before = T::MultiCurrency::free_balance(hop.currency_out(), who);
+			match *hop {
+				SwapHop::Zenlink {
+					currency_in,
+					currency_out,
+				} => {
+					let path = Self::zenlink_path(currency_in, currency_out)?;
+					T::DexOperator::inner_swap_exact_assets_for_assets(
+						who,
+						amount,
+						Zero::zero(),
+						&path,
+						who,
+					)?;
+				}
+				SwapHop::StablePool {
+					pool_id,
+					currency_in,
+					currency_out,
+				} => {
+					let (i, j) = Self::pool_token_indexes(pool_id, currency_in, currency_out)
+						.ok_or(Error::<T>::NoRoute)?;
+					T::StablePoolHandler::swap(who, pool_id, i, j, amount, Zero::zero())?;
+				}
+			}
+			amount = T::MultiCurrency::free_balance(hop.currency_out(), who)
+				.saturating_sub(balance_before);
+		}
+		ensure!(amount >= min_amount_out, Error::<T>::SlippageExceeded);
+		Ok(amount)
+	}
+
+	/// Executes `route` as quoted by `quote_exact_out` and returns the amount received. Every
+	/// hop pays its quoted input for at least its quoted output, Zenlink hops through their exact
+	/// out swap, so the route fails rather than delivering less than its last amount.
+	#[transactional]
+	fn execute_route_exact_out(
+		who: &AccountIdOf<T>,
+		route: &SwapRoute<CurrencyId, BalanceOf<T>>,
+	) -> Result<BalanceOf<T>, DispatchError> {
+		let mut received = Zero::zero();
+		for (hop, amounts) in route.hops.iter().zip(route.amounts.windows(2)) {
+			let (amount_in, amount_out) = (amounts[0], amounts[1]);
+			let balance_before = T::MultiCurrency::free_balance(hop.currency_out(), who);
+			match *hop {
+				SwapHop::Zenlink {
+					currency_in,
+					currency_out,
+				} => {
+					let path = Self::zenlink_path(currency_in, currency_out)?;
+					T::DexOperator::inner_swap_assets_for_exact_assets(
+						who, amount_out, amount_in, &path, who,
+					)?;
+				}
+				SwapHop::StablePool {
+					pool_id,
+					currency_in,
+					currency_out,
+				} => {
+					let (i, j) = Self::pool_token_indexes(pool_id, currency_in, currency_out)
+						.ok_or(Error::<T>::NoRoute)?;
+					T::StablePoolHandler::swap(who, pool_id, i, j, amount_in, amount_out)?;
+				}
+			}
+			received = T::MultiCurrency::free_balance(hop.currency_out(), who)
+				.saturating_sub(balance_before);
+			ensure!(received >= amount_out, Error::<T>::SlippageExceeded);
+		}
+		Ok(received)
+	}
+
+	fn ensure_can_route(
+		currency_in: CurrencyId,
+		currency_out: CurrencyId,
+		amount: BalanceOf<T>,
+	) -> DispatchResult {
+		ensure!(currency_in != currency_out, Error::<T>::SameCurrency);
+		ensure!(!amount.is_zero(), Error::<T>::ZeroAmount);
+		Ok(())
+	}
+}
+
+impl<T: Config> SwapRouter<AccountIdOf<T>, CurrencyId, BalanceOf<T>> for Pallet<T> {
+	fn quote_exact_in(
+		currency_in: CurrencyId,
+		currency_out: CurrencyId,
+		amount_in: BalanceOf<T>,
+	) -> Result<SwapRoute<CurrencyId, BalanceOf<T>>, DispatchError> {
+		Self::ensure_can_route(currency_in, currency_out, amount_in)?;
+
+		let route_assets: Vec<CurrencyId> = RouteAssets::<T>::get()
+			.into_iter()
+			.filter(|asset| *asset != currency_in && *asset != currency_out)
+			.collect();
+		let mut route = SwapRoute {
+			hops: Vec::new(),
+			amounts: vec![amount_in],
+		};
+		let mut best = None;
+		Self::search_exact_in(
+			currency_out,
+			&route_assets,
+			&mut route,
+			currency_in,
+			&mut best,
+		);
+
+		best.ok_or(Error::<T>::NoRoute.into())
+	}
+
+	fn quote_exact_out(
+		currency_in: CurrencyId,
+		currency_out: CurrencyId,
+		amount_out: BalanceOf<T>,
+	) -> Result<SwapRoute<CurrencyId, BalanceOf<T>>, DispatchError> {
+		Self::ensure_can_route(currency_in, currency_out, amount_out)?;
+
+		let route_assets: Vec<CurrencyId> = RouteAssets::<T>::get()
+			.into_iter()
+			.filter(|asset| *asset != currency_in && *asset != currency_out)
+			.collect();
+		let mut route = SwapRoute {
+			hops: Vec::new(),
+			amounts: vec![amount_out],
+		};
+		let mut best = None;
+		Self::search_exact_out(
+			currency_in,
+			&route_assets,
+			&mut route,
+			currency_out,
+			&mut best,
+		);
+
+		best.ok_or(Error::<T>::NoRoute.into())
+	}
+
+	fn swap_exact_in(
+		who: &AccountIdOf<T>,
+		currency_in: CurrencyId,
+		currency_out: CurrencyId,
+		amount_in: BalanceOf<T>,
+		min_amount_out: BalanceOf<T>,
+	) -> Result<BalanceOf<T>, DispatchError> {
+		let route = Self::quote_exact_in(currency_in, currency_out, amount_in)?;
+		ensure!(
+			route.amount_out() >= min_amount_out,
+			Error::<T>::SlippageExceeded
+		);
+
+		let amount_out = Self::execute_route(who, &route, amount_in, min_amount_out)?;
+
+		Self::deposit_event(Event::Swapped {
+			who: who.clone(),
+			currency_in,
+			currency_out,
+			amount_in,
+			amount_out,
+			hops: route.hops,
+		});
+		Ok(amount_out)
+	}
+
+	fn swap_exact_out(
+		who: &AccountIdOf<T>,
+		currency_in: CurrencyId,
+		currency_out: CurrencyId,
+		amount_out: BalanceOf<T>,
+		max_amount_in: BalanceOf<T>,
+	) -> Result<BalanceOf<T>, DispatchError> {
+		let route = Self::quote_exact_out(currency_in, currency_out, amount_out)?;
+		let amount_in = route.amount_in();
+		ensure!(amount_in <= max_amount_in, Error::<T>::SlippageExceeded);
+
+		let received = Self::execute_route_exact_out(who, &route)?;
+
+		Self::deposit_event(Event::Swapped {
+			who: who.clone(),
+			currency_in,
+			currency_out,
+			amount_in,
+			amount_out: received,
+			hops: route.hops,
+		});
+		Ok(amount_in)
+	}
+}
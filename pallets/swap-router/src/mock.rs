@@ -0,0 +1,422 @@
+// This file is part of Bifrost.
+
+// Copyright (C) Liebi Technologies PTE. LTD.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+#![cfg(test)]
+
+use crate as bifrost_swap_router;
+use bifrost_primitives::ZenlinkPalletId;
+pub use bifrost_primitives::{currency::*, CurrencyId};
+use bifrost_stable_pool::{traits::StablePoolHandler, PoolTokenIndex, StableAssetPoolId};
+pub use cumulus_primitives_core::ParaId;
+use frame_support::{
+	derive_impl, ensure, parameter_types,
+	sp_runtime::{DispatchError, DispatchResult},
+	traits::Nothing,
+};
+use frame_system::EnsureRoot;
+use orml_traits::{parameter_type_with_key, MultiCurrency};
+use sp_core::ConstU32;
+use sp_runtime::{
+	traits::{IdentityLookup, UniqueSaturatedInto},
+	AccountId32, BuildStorage, SaturatedConversion,
+};
+use sp_std::marker::PhantomData;
+use zenlink_protocol::{
+	AssetBalance, AssetId as ZenlinkAssetId, LocalAssetHandler, PairLpGenerate, ZenlinkMultiAssets,
+};
+
+pub type BlockNumber = u64;
+pub type Amount = i128;
+pub type Balance = u128;
+
+pub type AccountId = AccountId32;
+pub const ALICE: AccountId = AccountId32::new([0u8; 32]);
+pub const BOB: AccountId = AccountId32::new([1u8; 32]);
+pub const STABLE_POOL_ACCOUNT: AccountId = AccountId32::new([7u8; 32]);
+
+/// The only stable pool of the mock, holding DOT and VDOT.
+pub const STABLE_POOL_ID: StableAssetPoolId = 0;
+
+frame_support::construct_runtime!(
+	pub enum Runtime {
+		System: frame_system,
+		Tokens: orml_tokens,
+		Balances: pallet_balances,
+		Currencies: bifrost_currencies,
+		ZenlinkProtocol: zenlink_protocol,
+		SwapRouter: bifrost_swap_router,
+	}
+);
+
+type Block = frame_system::mocking::MockBlock<Runtime>;
+
+#[derive_impl(frame_system::config_preludes::TestDefaultConfig as frame_system::DefaultConfig)]
+impl frame_system::Config for Runtime {
+	type AccountData = pallet_balances::AccountData<Balance>;
+	type AccountId = AccountId;
+	type Block = Block;
+	type Lookup = IdentityLookup<Self::AccountId>;
+}
+
+parameter_types! {
+	pub const GetNativeCurrencyId: CurrencyId = BNC;
+}
+
+pub type AdaptedBasicCurrency =
+	bifrost_currencies::BasicCurrencyAdapter<Runtime, Balances, Amount, BlockNumber>;
+
+impl bifrost_currencies::Config for Runtime {
+	type GetNativeCurrencyId = GetNativeCurrencyId;
+	type MultiCurrency = Tokens;
+	type NativeCurrency = AdaptedBasicCurrency;
+	type WeightInfo = ();
+}
+
+parameter_types! {
+	pub const ExistentialDeposit: Balance = 1;
+}
+
+impl pallet_balances::Config for Runtime {
+	type AccountStore = frame_system::Pallet<Runtime>;
+	type Balance = Balance;
+	type DustRemoval = ();
+	type RuntimeEvent = RuntimeEvent;
+	type ExistentialDeposit = ExistentialDeposit;
+	type MaxLocks = ();
+	type MaxReserves = ();
+	type ReserveIdentifier = [u8; 8];
+	type WeightInfo = ();
+	type RuntimeHoldReason = RuntimeHoldReason;
+	type RuntimeFreezeReason = RuntimeFreezeReason;
+	type FreezeIdentifier = ();
+	type MaxFreezes = ConstU32<0>;
+}
+
+parameter_type_with_key! {
+	pub ExistentialDeposits: |_currency_id: CurrencyId| -> Balance {
+		0
+	};
+}
+
+impl orml_tokens::Config for Runtime {
+	type Amount = i128;
+	type Balance = Balance;
+	type CurrencyId = CurrencyId;
+	type DustRemovalWhitelist = Nothing;
+	type RuntimeEvent = RuntimeEvent;
+	type ExistentialDeposits = ExistentialDeposits;
+	type MaxLocks = ();
+	type MaxReserves = ();
+	type ReserveIdentifier = [u8; 8];
+	type WeightInfo = ();
+	type CurrencyHooks = ();
+}
+
+parameter_types! {
+	pub const SelfParaId: u32 = 2001;
+}
+
+impl zenlink_protocol::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type MultiAssetsHandler = MultiAssets;
+	type PalletId = ZenlinkPalletId;
+	type SelfParaId = SelfParaId;
+	type TargetChains = ();
+	type WeightInfo = ();
+	type AssetId = ZenlinkAssetId;
+	type LpGenerate = PairLpGenerate<Self>;
+}
+
+type MultiAssets = ZenlinkMultiAssets<ZenlinkProtocol, Balances, LocalAssetAdaptor<Currencies>>;
+
+// Below is the implementation of tokens manipulation functions other than native token.
+pub struct LocalAssetAdaptor<Local>(PhantomData<Local>);
+
+impl<Local, AccountId> LocalAssetHandler<AccountId> for LocalAssetAdaptor<Local>
+where
+	Local: MultiCurrency<AccountId, CurrencyId = CurrencyId>,
+{
+	fn local_balance_of(asset_id: ZenlinkAssetId, who: &AccountId) -> AssetBalance {
+		let currency_id: CurrencyId = asset_id.try_into().unwrap();
+		Local::free_balance(currency_id, &who).saturated_into()
+	}
+
+	fn local_total_supply(asset_id: ZenlinkAssetId) -> AssetBalance {
+		let currency_id: CurrencyId = asset_id.try_into().unwrap();
+		Local::total_issuance(currency_id).saturated_into()
+	}
+
+	fn local_is_exists(asset_id: ZenlinkAssetId) -> bool {
+		let rs: Result<CurrencyId, _> = asset_id.try_into();
+		rs.is_ok()
+	}
+
+	fn local_transfer(
+		asset_id: ZenlinkAssetId,
+		origin: &AccountId,
+		target: &AccountId,
+		amount: AssetBalance,
+	) -> DispatchResult {
+		let currency_id: CurrencyId = asset_id.try_into().unwrap();
+		Local::transfer(
+			currency_id,
+			&origin,
+			&target,
+			amount.unique_saturated_into(),
+		)
+	}
+
+	fn local_deposit(
+		asset_id: ZenlinkAssetId,
+		origin: &AccountId,
+		amount: AssetBalance,
+	) -> Result<AssetBalance, DispatchError> {
+		let currency_id: CurrencyId = asset_id.try_into().unwrap();
+		Local::deposit(currency_id, &origin, amount.unique_saturated_into())?;
+		Ok(amount)
+	}
+
+	fn local_withdraw(
+		asset_id: ZenlinkAssetId,
+		origin: &AccountId,
+		amount: AssetBalance,
+	) -> Result<AssetBalance, DispatchError> {
+		let currency_id: CurrencyId = asset_id.try_into().unwrap();
+		Local::withdraw(currency_id, &origin, amount.unique_saturated_into())?;
+		Ok(amount)
+	}
+}
+
+/// A stable pool swapping DOT and VDOT one to one, minus a 1% fee.
+pub struct MockStablePool;
+
+impl MockStablePool {
+	fn pool_assets() -> [CurrencyId; 2] {
+		[DOT, VDOT]
+	}
+
+	fn amount_out(amount_in: Balance) -> Balance {
+		amount_in * 99 / 100
+	}
+
+	fn amount_in(amount_out: Balance) -> Balance {
+		(amount_out * 100).div_ceil(99)
+	}
+}
+
+impl StablePoolHandler for MockStablePool {
+	type Balance = Balance;
+	type AccountId = AccountId;
+	type CurrencyId = CurrencyId;
+
+	fn add_liquidity(
+		_who: Self::AccountId,
+		_pool_id: StableAssetPoolId,
+		_amounts: Vec<Self::Balance>,
+		_min_mint_amount: Self::Balance,
+	) -> DispatchResult {
+		Ok(())
+	}
+
+	fn swap(
+		who: &Self::AccountId,
+		pool_id: StableAssetPoolId,
+		currency_id_in: PoolTokenIndex,
+		currency_id_out: PoolTokenIndex,
+		amount: Self::Balance,
+		min_dy: Self::Balance,
+	) -> DispatchResult {
+		let dy = Self::get_swap_output(pool_id, currency_id_in, currency_id_out, amount)?;
+		ensure!(dy >= min_dy, DispatchError::Other("SwapUnderMin"));
+		let assets = Self::pool_assets();
+		Currencies::transfer(
+			assets[currency_id_in as usize],
+			who,
+			&STABLE_POOL_ACCOUNT,
+			amount,
+		)?;
+		Currencies::transfer(
+			assets[currency_id_out as usize],
+			&STABLE_POOL_ACCOUNT,
+			who,
+			dy,
+		)
+	}
+
+	fn redeem_single(
+		_who: Self::AccountId,
+		_pool_id: StableAssetPoolId,
+		_amount: Self::Balance,
+		_i: PoolTokenIndex,
+		_min_redeem_amount: Self::Balance,
+		_asset_length: u32,
+	) -> Result<(Self::Balance, Self::Balance), DispatchError> {
+		Ok((0, 0))
+	}
+
+	fn redeem_multi(
+		_who: Self::AccountId,
+		_pool_id: StableAssetPoolId,
+		_amounts: Vec<Self::Balance>,
+		_max_redeem_amount: Self::Balance,
+	) -> DispatchResult {
+		Ok(())
+	}
+
+	fn redeem_proportion(
+		_who: Self::AccountId,
+		_pool_id: StableAssetPoolId,
+		_amount: Self::Balance,
+		_min_redeem_amounts: Vec<Self::Balance>,
+	) -> DispatchResult {
+		Ok(())
+	}
+
+	fn get_pool_token_index(
+		pool_id: StableAssetPoolId,
+		currency_id: CurrencyId,
+	) -> Option<PoolTokenIndex> {
+		if pool_id != STABLE_POOL_ID {
+			return None;
+		}
+		Self::pool_assets()
+			.iter()
+			.position(|asset| *asset == currency_id)
+			.map(|index| index as PoolTokenIndex)
+	}
+
+	fn get_swap_output(
+		pool_id: StableAssetPoolId,
+		currency_id_in: PoolTokenIndex,
+		currency_id_out: PoolTokenIndex,
+		amount: Self::Balance,
+	) -> Result<Self::Balance, DispatchError> {
+		ensure!(
+			pool_id == STABLE_POOL_ID && currency_id_in != currency_id_out,
+			DispatchError::Other("ArgumentsError")
+		);
+		Ok(Self::amount_out(amount))
+	}
+
+	fn get_swap_input(
+		pool_id: StableAssetPoolId,
+		currency_id_in: PoolTokenIndex,
+		currency_id_out: PoolTokenIndex,
+		amount: Self::Balance,
+	) -> Result<Self::Balance, DispatchError> {
+		ensure!(
+			pool_id == STABLE_POOL_ID && currency_id_in != currency_id_out,
+			DispatchError::Other("ArgumentsError")
+		);
+		Ok(Self::amount_in(amount))
+	}
+
+	fn get_pool_id(
+		currency_id_in: &Self::CurrencyId,
+		currency_id_out: &Self::CurrencyId,
+	) -> Option<(StableAssetPoolId, PoolTokenIndex, PoolTokenIndex)> {
+		let i = Self::get_pool_token_index(STABLE_POOL_ID, *currency_id_in)?;
+		let j = Self::get_pool_token_index(STABLE_POOL_ID, *currency_id_out)?;
+		(i != j).then_some((STABLE_POOL_ID, i, j))
+	}
+}
+
+pub struct ParachainId;
+impl frame_support::pallet_prelude::Get<ParaId> for ParachainId {
+	fn get() -> ParaId {
+		SelfParaId::get().into()
+	}
+}
+
+impl bifrost_swap_router::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type MultiCurrency = Currencies;
+	type ControlOrigin = EnsureRoot<AccountId>;
+	type WeightInfo = ();
+	type DexOperator = ZenlinkProtocol;
+	type StablePoolHandler = MockStablePool;
+	type ParachainId = ParachainId;
+	type MaxHops = ConstU32<3>;
+	type MaxRouteAssets = ConstU32<5>;
+}
+
+pub struct ExtBuilder {
+	endowed_accounts: Vec<(AccountId, CurrencyId, Balance)>,
+}
+
+impl Default for ExtBuilder {
+	fn default() -> Self {
+		Self {
+			endowed_accounts: vec![],
+		}
+	}
+}
+
+impl ExtBuilder {
+	pub fn balances(mut self, endowed_accounts: Vec<(AccountId, CurrencyId, Balance)>) -> Self {
+		self.endowed_accounts = endowed_accounts;
+		self
+	}
+
+	pub fn one_million_for_alice_n_bob(self) -> Self {
+		self.balances(vec![
+			(ALICE, BNC, 1_000_000),
+			(ALICE, DOT, 1_000_000),
+			(ALICE, VDOT, 1_000_000),
+			(ALICE, KSM, 1_000_000),
+			(ALICE, VKSM, 1_000_000),
+			(BOB, BNC, 1_000_000),
+			(BOB, DOT, 1_000_000),
+			(BOB, KSM, 1_000_000),
+			(STABLE_POOL_ACCOUNT, DOT, 1_000_000),
+			(STABLE_POOL_ACCOUNT, VDOT, 1_000_000),
+		])
+	}
+
+	pub fn build(self) -> sp_io::TestExternalities {
+		let mut t = frame_system::GenesisConfig::<Runtime>::default()
+			.build_storage()
+			.unwrap();
+
+		pallet_balances::GenesisConfig::<Runtime> {
+			balances: self
+				.endowed_accounts
+				.clone()
+				.into_iter()
+				.filter(|(_, currency_id, _)| *currency_id == BNC)
+				.map(|(account_id, _, initial_balance)| (account_id, initial_balance))
+				.collect::<Vec<_>>(),
+		}
+		.assimilate_storage(&mut t)
+		.unwrap();
+
+		orml_tokens::GenesisConfig::<Runtime> {
+			balances: self
+				.endowed_accounts
+				.into_iter()
+				.filter(|(_, currency_id, _)| *currency_id != BNC)
+				.collect::<Vec<_>>(),
+		}
+		.assimilate_storage(&mut t)
+		.unwrap();
+
+		let mut ext = sp_io::TestExternalities::new(t);
+		ext.execute_with(|| System::set_block_number(1));
+		ext
+	}
+}
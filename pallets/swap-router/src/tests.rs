@@ -0,0 +1,273 @@
+// This file is part of Bifrost.
+
+// Copyright (C) Liebi Technologies PTE. LTD.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+#![cfg(test)]
+
+use crate::{
+	mock::{SwapRouter, *},
+	*,
+};
+use bifrost_primitives::SwapRouter as _;
+use frame_support::{assert_noop, assert_ok};
+use frame_system::pallet_prelude::BlockNumberFor;
+use sp_runtime::DispatchError::BadOrigin;
+
+fn zenlink_asset(currency_id: CurrencyId) -> AssetId {
+	AssetId::try_convert_from(currency_id, SelfParaId::get()).unwrap()
+}
+
+fn create_pair(currency_0: CurrencyId, currency_1: CurrencyId, amount_0: u128, amount_1: u128) {
+	let asset_0 = zenlink_asset(currency_0);
+	let asset_1 = zenlink_asset(currency_1);
+	assert_ok!(ZenlinkProtocol::create_pair(
+		RuntimeOrigin::root(),
+		asset_0,
+		asset_1,
+		ALICE
+	));
+	let deadline: BlockNumberFor<Runtime> = System::block_number() + 100;
+	assert_ok!(ZenlinkProtocol::add_liquidity(
+		RuntimeOrigin::signed(ALICE),
+		asset_0,
+		asset_1,
+		amount_0,
+		amount_1,
+		1,
+		1,
+		deadline
+	));
+}
+
+/// DOT/VDOT on both venues, and KSM to VKSM only through BNC.
+fn init_pairs() {
+	create_pair(DOT, VDOT, 10_000, 10_000);
+	create_pair(KSM, BNC, 100_000, 100_000);
+	create_pair(BNC, VKSM, 100_000, 100_000);
+}
+
+#[test]
+fn set_route_assets_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			SwapRouter::set_route_assets(RuntimeOrigin::signed(ALICE), vec![BNC]),
+			BadOrigin
+		);
+		assert_noop!(
+			SwapRouter::set_route_assets(
+				RuntimeOrigin::root(),
+				vec![BNC, DOT, VDOT, KSM, VKSM, MOVR]
+			),
+			Error::<Runtime>::TooManyRouteAssets
+		);
+
+		assert_ok!(SwapRouter::set_route_assets(
+			RuntimeOrigin::root(),
+			vec![VDOT, BNC, VDOT]
+		));
+		assert_eq!(RouteAssets::<Runtime>::get().into_inner(), vec![BNC, VDOT]);
+		System::assert_last_event(RuntimeEvent::SwapRouter(Event::RouteAssetsSet {
+			assets: vec![BNC, VDOT],
+		}));
+	});
+}
+
+#[test]
+fn quote_should_pick_the_best_venue() {
+	ExtBuilder::default()
+		.one_million_for_alice_n_bob()
+		.build()
+		.execute_with(|| {
+			init_pairs();
+
+			// The stable pool gives 990 against 906 on Zenlink.
+			let route = SwapRouter::quote_exact_in(DOT, VDOT, 1_000).unwrap();
+			assert_eq!(
+				route.hops,
+				vec![SwapHop::StablePool {
+					pool_id: STABLE_POOL_ID,
+					currency_in: DOT,
+					currency_out: VDOT
+				}]
+			);
+			assert_eq!(route.amounts, vec![1_000, 990]);
+
+			let route = SwapRouter::quote_exact_out(DOT, VDOT, 990).unwrap();
+			assert_eq!(route.amounts, vec![1_000, 990]);
+
+			assert_noop!(
+				SwapRouter::quote_exact_in(DOT, DOT, 1_000),
+				Error::<Runtime>::SameCurrency
+			);
+			assert_noop!(
+				SwapRouter::quote_exact_in(DOT, VDOT, 0),
+				Error::<Runtime>::ZeroAmount
+			);
+		});
+}
+
+#[test]
+fn quote_should_go_through_route_assets() {
+	ExtBuilder::default()
+		.one_million_for_alice_n_bob()
+		.build()
+		.execute_with(|| {
+			init_pairs();
+
+			assert_noop!(
+				SwapRouter::quote_exact_in(KSM, VKSM, 1_000),
+				Error::<Runtime>::NoRoute
+			);
+
+			assert_ok!(SwapRouter::set_route_assets(
+				RuntimeOrigin::root(),
+				vec![BNC, DOT]
+			));
+			let path = vec![zenlink_asset(KSM), zenlink_asset(BNC), zenlink_asset(VKSM)];
+			let hops = vec![
+				SwapHop::Zenlink {
+					currency_in: KSM,
+					currency_out: BNC,
+				},
+				SwapHop::Zenlink {
+					currency_in: BNC,
+					currency_out: VKSM,
+				},
+			];
+
+			let route = SwapRouter::quote_exact_in(KSM, VKSM, 1_000).unwrap();
+			assert_eq!(route.hops, hops);
+			assert_eq!(
+				route.amounts,
+				ZenlinkProtocol::get_amount_out_by_path(1_000, &path).unwrap()
+			);
+
+			let route = SwapRouter::quote_exact_out(KSM, VKSM, 500).unwrap();
+			assert_eq!(route.hops, hops);
+			assert_eq!(
+				route.amounts,
+				ZenlinkProtocol::get_amount_in_by_path(500, &path).unwrap()
+			);
+		});
+}
+
+#[test]
+fn swap_exact_in_should_work() {
+	ExtBuilder::default()
+		.one_million_for_alice_n_bob()
+		.build()
+		.execute_with(|| {
+			init_pairs();
+			assert_ok!(SwapRouter::set_route_assets(
+				RuntimeOrigin::root(),
+				vec![BNC]
+			));
+
+			let route = SwapRouter::quote_exact_in(KSM, VKSM, 1_000).unwrap();
+			let bnc_before = Currencies::free_balance(BNC, &BOB);
+
+			assert_noop!(
+				SwapRouter::swap_exact_in(&BOB, KSM, VKSM, 1_000, route.amount_out() + 1),
+				Error::<Runtime>::SlippageExceeded
+			);
+			assert_eq!(
+				SwapRouter::swap_exact_in(&BOB, KSM, VKSM, 1_000, route.amount_out()),
+				Ok(route.amount_out())
+			);
+
+			assert_eq!(Currencies::free_balance(KSM, &BOB), 1_000_000 - 1_000);
+			assert_eq!(Currencies::free_balance(VKSM, &BOB), route.amount_out());
+			assert_eq!(Currencies::free_balance(BNC, &BOB), bnc_before);
+			System::assert_last_event(RuntimeEvent::SwapRouter(Event::Swapped {
+				who: BOB,
+				currency_in: KSM,
+				currency_out: VKSM,
+				amount_in: 1_000,
+				amount_out: route.amount_out(),
+				hops: route.hops,
+			}));
+		});
+}
+
+#[test]
+fn swap_exact_out_should_work() {
+	ExtBuilder::default()
+		.one_million_for_alice_n_bob()
+		.build()
+		.execute_with(|| {
+			init_pairs();
+
+			assert_noop!(
+				SwapRouter::swap_exact_out(&BOB, DOT, VDOT, 990, 999),
+				Error::<Runtime>::SlippageExceeded
+			);
+			assert_eq!(
+				SwapRouter::swap_exact_out(&BOB, DOT, VDOT, 990, 1_000),
+				Ok(1_000)
+			);
+
+			assert_eq!(Currencies::free_balance(DOT, &BOB), 1_000_000 - 1_000);
+			assert_eq!(Currencies::free_balance(VDOT, &BOB), 990);
+			assert_eq!(
+				Currencies::free_balance(DOT, &STABLE_POOL_ACCOUNT),
+				1_000_000 + 1_000
+			);
+		});
+}
+
+#[test]
+fn swap_exact_out_should_deliver_the_requested_amount_through_route_assets() {
+	ExtBuilder::default()
+		.one_million_for_alice_n_bob()
+		.build()
+		.execute_with(|| {
+			init_pairs();
+			assert_ok!(SwapRouter::set_route_assets(
+				RuntimeOrigin::root(),
+				vec![BNC]
+			));
+
+			let route = SwapRouter::quote_exact_out(KSM, VKSM, 500).unwrap();
+			assert_eq!(route.hops.len(), 2);
+			let bnc_before = Currencies::free_balance(BNC, &BOB);
+
+			assert_noop!(
+				SwapRouter::swap_exact_out(&BOB, KSM, VKSM, 500, route.amount_in() - 1),
+				Error::<Runtime>::SlippageExceeded
+			);
+			assert_eq!(
+				SwapRouter::swap_exact_out(&BOB, KSM, VKSM, 500, route.amount_in()),
+				Ok(route.amount_in())
+			);
+
+			// Every Zenlink hop swaps for its exact quoted output, so nothing is left over.
+			assert_eq!(
+				Currencies::free_balance(KSM, &BOB),
+				1_000_000 - route.amount_in()
+			);
+			assert_eq!(Currencies::free_balance(BNC, &BOB), bnc_before);
+			assert_eq!(Currencies::free_balance(VKSM, &BOB), 500);
+			System::assert_last_event(RuntimeEvent::SwapRouter(Event::Swapped {
+				who: BOB,
+				currency_in: KSM,
+				currency_out: VKSM,
+				amount_in: route.amount_in(),
+				amount_out: 500,
+				hops: route.hops,
+			}));
+		});
+}
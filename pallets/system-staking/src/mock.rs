@@ -36,10 +36,10 @@ use frame_support::{
 };
 use frame_system::{EnsureRoot, EnsureSignedBy};
 use orml_traits::{location::RelativeReserveProvider, parameter_type_with_key};
-use sp_core::ConstU32;
+use sp_core::{ConstU32, ConstU64};
 use sp_runtime::{
 	traits::{AccountIdConversion, ConvertInto, IdentityLookup},
-	AccountId32, BuildStorage,
+	AccountId32, BuildStorage, Perbill,
 };
 use sp_std::vec;
 use xcm::v3::Weight;
@@ -274,10 +274,13 @@ impl bifrost_slp::Config for Runtime {
 	type AssetIdMaps = AssetIdMaps<Runtime>;
 	type TreasuryAccount = TreasuryAccount;
 	type BlockNumberProvider = System;
+	type XcmQueryTimeout = ConstU64<1000>;
 }
 
 parameter_types! {
 	pub const WhitelistMaximumLimit: u32 = 10;
+	pub const FarmingMaxPositionLock: BlockNumber = 1000;
+	pub const FarmingMaxPositionBoost: Perbill = Perbill::from_percent(100);
 }
 
 ord_parameter_types! {
@@ -299,6 +302,8 @@ impl bifrost_farming::Config for Runtime {
 	type WhitelistMaximumLimit = WhitelistMaximumLimit;
 	type GaugeRewardIssuer = FarmingGaugeRewardIssuerPalletId;
 	type BlockNumberProvider = System;
+	type MaxPositionLock = FarmingMaxPositionLock;
+	type MaxPositionBoost = FarmingMaxPositionBoost;
 }
 
 parameter_types! {
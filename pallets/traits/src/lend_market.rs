@@ -67,6 +67,26 @@ pub trait LendMarketMarketDataProvider<CurrencyId, Balance> {
 	fn get_market_status(asset_id: CurrencyId) -> Result<MarketStatus<Balance>, DispatchError>;
 	// for compatibility we keep this func
 	fn get_full_interest_rate(asset_id: CurrencyId) -> Option<Rate>;
+	/// Get the underlying asset id of a lend token.
+	fn get_underlying_asset_id(lend_token_id: CurrencyId) -> Option<CurrencyId>;
+}
+
+impl<CurrencyId, Balance> LendMarketMarketDataProvider<CurrencyId, Balance> for () {
+	fn get_market_info(_asset_id: CurrencyId) -> Result<MarketInfo, DispatchError> {
+		Err(DispatchError::Other("Not supported"))
+	}
+
+	fn get_market_status(_asset_id: CurrencyId) -> Result<MarketStatus<Balance>, DispatchError> {
+		Err(DispatchError::Other("Not supported"))
+	}
+
+	fn get_full_interest_rate(_asset_id: CurrencyId) -> Option<Rate> {
+		None
+	}
+
+	fn get_underlying_asset_id(_lend_token_id: CurrencyId) -> Option<CurrencyId> {
+		None
+	}
 }
 
 /// MarketInfo contains some static attrs as a subset of Market struct in LendMarket
@@ -235,6 +235,14 @@ benchmarks! {
 		let blocks = Some(BlockNumberFor::<T>::from(1000u32));
 	}: _<T::RuntimeOrigin>(origin, VKSM, blocks)
 
+	set_redeem_destination {
+		let origin = T::ControlOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		let destination = Some(RedeemDestination {
+			account_format: AccountFormat::AccountKey20,
+			fee: Some((VKSM, BalanceOf::<T>::unique_saturated_from(1000u128))),
+		});
+	}: _<T::RuntimeOrigin>(origin, 2004, destination)
+
 	impl_benchmark_test_suite!(
 	VtokenMinting,
 	crate::mock::ExtBuilder::default().one_hundred_for_alice_n_bob().build(),
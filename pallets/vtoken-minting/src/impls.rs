@@ -17,11 +17,12 @@
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
 use crate::{
-	AccountIdOf, BalanceOf, Config, CurrencyIdOf, Error, Event, Fees, HookIterationLimit,
-	MinTimeUnit, MinimumMint, MinimumRedeem, MintWithLockBlocks, OnRedeemSuccess, OngoingTimeUnit,
-	Pallet, RedeemTo, SupportedEth, TimeUnitUnlockLedger, TokenPool, TokenUnlockLedger,
-	TokenUnlockNextId, UnlockDuration, UnlockId, UnlockingTotal, UserUnlockLedger,
-	VtokenIncentiveCoef, VtokenLockLedger, WeightInfo,
+	AccountFormat, AccountIdOf, BalanceOf, Config, CurrencyIdOf, Error, Event, Fees,
+	HookIterationLimit, MinTimeUnit, MinimumMint, MinimumRedeem, MintWithLockBlocks,
+	OnRedeemSuccess, OngoingTimeUnit, Pallet, RedeemDestinations, RedeemTo, SupportedEth,
+	TimeUnitUnlockLedger, TokenPool, TokenToRebond, TokenUnlockLedger, TokenUnlockNextId,
+	UnlockDuration, UnlockId, UnlockingTotal, UserUnlockLedger, VtokenIncentiveCoef,
+	VtokenLockLedger, WeightInfo,
 };
 use bb_bnc::traits::BbBNCInterface;
 use bifrost_primitives::{
@@ -44,7 +45,7 @@ use sp_core::U256;
 use sp_runtime::traits::BlockNumberProvider;
 use sp_runtime::{helpers_128bit::multiply_by_rational_with_rounding, Rounding};
 use sp_std::{vec, vec::Vec};
-use xcm::{prelude::*, v4::Location};
+use xcm::{prelude::*, v4::Location, VersionedLocation};
 
 // incentive lock id for vtoken minted by user
 const INCENTIVE_LOCK_ID: LockIdentifier = *b"vmincntv";
@@ -445,7 +446,20 @@ impl<T: Config> Pallet<T> {
 				}
 				return Ok((redeem_currency_amount, RedeemTo::Native(redeemer)));
 			}
-			let (dest, redeem_to) = match redeem_type {
+			// Destinations that are no longer allowlisted fall back to the redeemer's account on
+			// Bifrost, so that a governance change can not block the unlock queue.
+			if let RedeemType::Location(ref location) = redeem_type {
+				if Self::ensure_redeem_destination(location).is_err() {
+					return Self::transfer_to_by_redeem_type(
+						redeemer,
+						redeem_currency_id,
+						redeem_currency_amount,
+						entrance_account_balance,
+						RedeemType::Native,
+					);
+				}
+			}
+			let (dest, redeem_to, fee) = match redeem_type {
 				RedeemType::Astar(receiver) => (
 					Location::new(
 						1,
@@ -458,6 +472,7 @@ impl<T: Config> Pallet<T> {
 						],
 					),
 					RedeemTo::Astar(receiver),
+					Self::legacy_redeem_fee(redeem_currency_id),
 				),
 				RedeemType::Hydradx(receiver) => (
 					Location::new(
@@ -471,6 +486,7 @@ impl<T: Config> Pallet<T> {
 						],
 					),
 					RedeemTo::Hydradx(receiver),
+					Self::legacy_redeem_fee(redeem_currency_id),
 				),
 				RedeemType::Interlay(receiver) => (
 					Location::new(
@@ -484,6 +500,7 @@ impl<T: Config> Pallet<T> {
 						],
 					),
 					RedeemTo::Interlay(receiver),
+					Self::legacy_redeem_fee(redeem_currency_id),
 				),
 				RedeemType::Manta(receiver) => (
 					Location::new(
@@ -497,6 +514,7 @@ impl<T: Config> Pallet<T> {
 						],
 					),
 					RedeemTo::Manta(receiver),
+					Self::legacy_redeem_fee(redeem_currency_id),
 				),
 				RedeemType::Moonbeam(receiver) => (
 					Location::new(
@@ -510,32 +528,40 @@ impl<T: Config> Pallet<T> {
 						],
 					),
 					RedeemTo::Moonbeam(receiver),
+					Self::legacy_redeem_fee(redeem_currency_id),
 				),
+				RedeemType::Location(location) => {
+					let (dest, fee) = Self::ensure_redeem_destination(&location)?;
+					(dest, RedeemTo::Location(location), fee)
+				}
 				RedeemType::Native => {
 					unreachable!()
 				}
 			};
-			if redeem_currency_id == FIL {
-				let assets = vec![
-					(redeem_currency_id, redeem_currency_amount),
-					(BNC, T::BifrostSlpx::get_moonbeam_transfer_to_fee()),
-				];
-
-				T::XcmTransfer::transfer_multicurrencies(
-					entrance_account.clone(),
-					assets,
-					1,
-					dest,
-					Unlimited,
-				)?;
-			} else {
-				T::XcmTransfer::transfer(
-					entrance_account.clone(),
-					redeem_currency_id,
-					redeem_currency_amount,
-					dest,
-					Unlimited,
-				)?;
+			match fee {
+				Some((fee_currency_id, fee_amount)) if fee_currency_id != redeem_currency_id => {
+					let assets = vec![
+						(redeem_currency_id, redeem_currency_amount),
+						(fee_currency_id, fee_amount),
+					];
+
+					T::XcmTransfer::transfer_multicurrencies(
+						entrance_account.clone(),
+						assets,
+						1,
+						dest,
+						Unlimited,
+					)?;
+				}
+				_ => {
+					T::XcmTransfer::transfer(
+						entrance_account.clone(),
+						redeem_currency_id,
+						redeem_currency_amount,
+						dest,
+						Unlimited,
+					)?;
+				}
 			};
 			Ok((redeem_currency_amount, redeem_to))
 		} else {
@@ -556,6 +582,44 @@ impl<T: Config> Pallet<T> {
 		}
 	}
 
+	/// The fee asset of the hard-coded redeem destinations.
+	/// FIL is not accepted as fee on the destination, so BNC is sent along with it.
+	fn legacy_redeem_fee(redeem_currency_id: CurrencyId) -> Option<(CurrencyId, BalanceOf<T>)> {
+		if redeem_currency_id == FIL {
+			Some((BNC, T::BifrostSlpx::get_moonbeam_transfer_to_fee()))
+		} else {
+			None
+		}
+	}
+
+	/// Check the redeem destination against the allowlist.
+	/// Parameters:
+	/// - `location`: The beneficiary location on the destination parachain.
+	/// Returns:
+	/// - `(Location, Option<(CurrencyId, BalanceOf<T>)>)`: The destination location and the fee
+	///   asset to send along with the redeemed tokens.
+	pub fn ensure_redeem_destination(
+		location: &VersionedLocation,
+	) -> Result<(Location, Option<(CurrencyId, BalanceOf<T>)>), DispatchError> {
+		let dest = Location::try_from(location.clone())
+			.map_err(|_| Error::<T>::InvalidRedeemDestination)?;
+		let (para_id, account_format) = match dest.unpack() {
+			(1, [Parachain(para_id), AccountId32 { .. }]) => (*para_id, AccountFormat::AccountId32),
+			(1, [Parachain(para_id), AccountKey20 { .. }]) => {
+				(*para_id, AccountFormat::AccountKey20)
+			}
+			_ => return Err(Error::<T>::InvalidRedeemDestination.into()),
+		};
+		let destination = RedeemDestinations::<T>::get(para_id)
+			.ok_or(Error::<T>::RedeemDestinationNotSupported)?;
+		ensure!(
+			destination.account_format == account_format,
+			Error::<T>::AccountFormatMismatch
+		);
+
+		Ok((dest, destination.fee))
+	}
+
 	#[transactional]
 	pub fn handle_ledger_by_currency(currency: CurrencyId) -> DispatchResult {
 		let time_unit = MinTimeUnit::<T>::get(currency);
@@ -653,6 +717,77 @@ impl<T: Config> Pallet<T> {
 		Ok(v_currency_amount.into())
 	}
 
+	/// Cancel the pending unlocks of `rebonder`, starting from the latest one, and mint
+	/// vtoken with the rebonded amount.
+	pub fn do_rebond(
+		rebonder: AccountIdOf<T>,
+		currency_id: CurrencyIdOf<T>,
+		currency_amount: BalanceOf<T>,
+	) -> DispatchResult {
+		let v_currency_id = currency_id
+			.to_vtoken()
+			.map_err(|_| Error::<T>::NotSupportTokenType)?;
+
+		let (user_unlock_amount, unlock_id_list) =
+			UserUnlockLedger::<T>::get(&rebonder, currency_id)
+				.ok_or(Error::<T>::UserUnlockLedgerNotFound)?;
+		ensure!(
+			user_unlock_amount >= currency_amount,
+			Error::<T>::NotEnoughBalanceToUnlock
+		);
+
+		let mut temp_currency_amount = currency_amount;
+		for unlock_id in unlock_id_list.into_iter().rev() {
+			let (_, mut unlock_amount, time_unit, _) =
+				TokenUnlockLedger::<T>::get(currency_id, unlock_id)
+					.ok_or(Error::<T>::TokenUnlockLedgerNotFound)?;
+
+			if temp_currency_amount <= unlock_amount {
+				unlock_amount = temp_currency_amount;
+			} else {
+				temp_currency_amount = temp_currency_amount.saturating_sub(unlock_amount);
+			}
+
+			let is_remove_record = Self::update_unlock_ledger(
+				&rebonder,
+				&currency_id,
+				&unlock_amount,
+				&unlock_id,
+				&time_unit,
+				None,
+				Operation::Sub,
+			)?;
+
+			if !is_remove_record {
+				break;
+			}
+		}
+
+		let (_, v_currency_amount, fee) =
+			Self::mint_without_transfer(&rebonder, v_currency_id, currency_id, currency_amount)?;
+
+		TokenToRebond::<T>::mutate(&currency_id, |maybe_value| -> Result<(), Error<T>> {
+			match maybe_value {
+				Some(rebonded_amount) => {
+					*rebonded_amount = rebonded_amount
+						.checked_add(&currency_amount)
+						.ok_or(Error::<T>::CalculationOverflow)?;
+					Ok(())
+				}
+				None => Err(Error::<T>::InvalidRebondToken),
+			}
+		})?;
+
+		Self::deposit_event(Event::Rebonded {
+			rebonder,
+			currency_id,
+			currency_amount,
+			v_currency_amount,
+			fee,
+		});
+		Ok(())
+	}
+
 	pub fn do_redeem(
 		redeemer: AccountIdOf<T>,
 		currency_id: Option<CurrencyIdOf<T>>,
@@ -677,6 +812,9 @@ impl<T: Config> Pallet<T> {
 			v_currency_amount >= MinimumRedeem::<T>::get(v_currency_id),
 			Error::<T>::BelowMinimumRedeem
 		);
+		if let RedeemType::Location(ref location) = redeem_type {
+			Self::ensure_redeem_destination(location)?;
+		}
 
 		// Charging fees
 		let (_, redeem_rate) = Fees::<T>::get();
@@ -1011,6 +1149,14 @@ impl<T: Config> VtokenMintingInterface<AccountIdOf<T>, CurrencyIdOf<T>, BalanceO
 		)
 	}
 
+	fn rebond(
+		exchanger: AccountIdOf<T>,
+		currency_id: CurrencyIdOf<T>,
+		currency_amount: BalanceOf<T>,
+	) -> DispatchResult {
+		Self::do_rebond(exchanger, currency_id, currency_amount)
+	}
+
 	fn get_v_currency_amount_by_currency_amount(
 		currency_id: CurrencyIdOf<T>,
 		v_currency_id: CurrencyIdOf<T>,
@@ -1089,6 +1235,23 @@ impl<T: Config> VtokenMintingInterface<AccountIdOf<T>, CurrencyIdOf<T>, BalanceO
 	fn get_moonbeam_parachain_id() -> u32 {
 		T::MoonbeamChainId::get()
 	}
+
+	fn get_user_unlock_queue(
+		exchanger: AccountIdOf<T>,
+		currency_id: CurrencyIdOf<T>,
+	) -> Vec<(UnlockId, BalanceOf<T>, TimeUnit)> {
+		UserUnlockLedger::<T>::get(&exchanger, currency_id)
+			.map(|(_, unlock_id_list)| {
+				unlock_id_list
+					.into_iter()
+					.filter_map(|unlock_id| {
+						TokenUnlockLedger::<T>::get(currency_id, unlock_id)
+							.map(|(_, amount, time_unit, _)| (unlock_id, amount, time_unit))
+					})
+					.collect()
+			})
+			.unwrap_or_default()
+	}
 }
 
 impl<T: Config> VTokenSupplyProvider<CurrencyIdOf<T>, BalanceOf<T>> for Pallet<T> {
@@ -299,6 +299,13 @@ pub mod pallet {
 		SupportedEthSet {
 			eths: BoundedVec<CurrencyId, ConstU32<10>>,
 		},
+		/// Redeem destination set.
+		RedeemDestinationSet {
+			/// The parachain id of the destination.
+			para_id: u32,
+			/// The destination config. `None` means removed from the allowlist.
+			destination: Option<RedeemDestination<BalanceOf<T>>>,
+		},
 	}
 
 	#[pallet::error]
@@ -351,6 +358,12 @@ pub mod pallet {
 		BalanceZero,
 		/// IncentiveLockBlocksNotSet
 		IncentiveLockBlocksNotSet,
+		/// The redeem destination location is malformed.
+		InvalidRedeemDestination,
+		/// The redeem destination parachain is not allowlisted.
+		RedeemDestinationNotSupported,
+		/// The beneficiary account does not match the destination account format.
+		AccountFormatMismatch,
 	}
 
 	/// The mint fee and redeem fee.
@@ -464,6 +477,11 @@ pub mod pallet {
 	pub type SupportedEth<T: Config> =
 		StorageValue<_, BoundedVec<CurrencyId, ConstU32<10>>, ValueQuery>;
 
+	/// Allowlisted parachains that redeemed tokens can be sent to by `RedeemType::Location`.
+	#[pallet::storage]
+	pub type RedeemDestinations<T: Config> =
+		StorageMap<_, Twox64Concat, u32, RedeemDestination<BalanceOf<T>>>;
+
 	//【vtoken -> Blocks】, the locked blocks for each vtoken when minted in an incentive mode
 	#[pallet::storage]
 	pub type MintWithLockBlocks<T: Config> =
@@ -573,72 +591,7 @@ pub mod pallet {
 			currency_amount: BalanceOf<T>,
 		) -> DispatchResult {
 			let rebonder = ensure_signed(origin)?;
-			let v_currency_id = currency_id
-				.to_vtoken()
-				.map_err(|_| Error::<T>::NotSupportTokenType)?;
-
-			let (user_unlock_amount, unlock_id_list) =
-				UserUnlockLedger::<T>::get(&rebonder, currency_id)
-					.ok_or(Error::<T>::UserUnlockLedgerNotFound)?;
-			ensure!(
-				user_unlock_amount >= currency_amount,
-				Error::<T>::NotEnoughBalanceToUnlock
-			);
-
-			let mut temp_currency_amount = currency_amount;
-			for unlock_id in unlock_id_list.into_iter().rev() {
-				let (_, mut unlock_amount, time_unit, _) =
-					TokenUnlockLedger::<T>::get(currency_id, unlock_id)
-						.ok_or(Error::<T>::TokenUnlockLedgerNotFound)?;
-
-				if temp_currency_amount <= unlock_amount {
-					unlock_amount = temp_currency_amount;
-				} else {
-					temp_currency_amount = temp_currency_amount.saturating_sub(unlock_amount);
-				}
-
-				let is_remove_record = Self::update_unlock_ledger(
-					&rebonder,
-					&currency_id,
-					&unlock_amount,
-					&unlock_id,
-					&time_unit,
-					None,
-					Operation::Sub,
-				)?;
-
-				if !is_remove_record {
-					break;
-				}
-			}
-
-			let (_, v_currency_amount, fee) = Self::mint_without_transfer(
-				&rebonder,
-				v_currency_id,
-				currency_id,
-				currency_amount,
-			)?;
-
-			TokenToRebond::<T>::mutate(&currency_id, |maybe_value| -> Result<(), Error<T>> {
-				match maybe_value {
-					Some(rebonded_amount) => {
-						*rebonded_amount = rebonded_amount
-							.checked_add(&currency_amount)
-							.ok_or(Error::<T>::CalculationOverflow)?;
-						Ok(())
-					}
-					None => Err(Error::<T>::InvalidRebondToken),
-				}
-			})?;
-
-			Self::deposit_event(Event::Rebonded {
-				rebonder,
-				currency_id,
-				currency_amount,
-				v_currency_amount,
-				fee,
-			});
-			Ok(())
+			Self::do_rebond(rebonder, currency_id, currency_amount)
 		}
 
 		/// Same function as Rebond. But need to provide unlock_id.
@@ -1176,5 +1129,27 @@ pub mod pallet {
 
 			Ok(())
 		}
+
+		/// Add, update or remove a parachain from the redeem destination allowlist.
+		/// Parameters:
+		/// - `para_id`: The parachain id of the destination.
+		/// - `destination`: The destination config, `None` to remove it.
+		#[pallet::call_index(19)]
+		#[pallet::weight(T::WeightInfo::set_redeem_destination())]
+		pub fn set_redeem_destination(
+			origin: OriginFor<T>,
+			para_id: u32,
+			destination: Option<RedeemDestination<BalanceOf<T>>>,
+		) -> DispatchResult {
+			T::ControlOrigin::ensure_origin(origin)?;
+
+			RedeemDestinations::<T>::set(para_id, destination.clone());
+			Self::deposit_event(Event::RedeemDestinationSet {
+				para_id,
+				destination,
+			});
+
+			Ok(())
+		}
 	}
 }
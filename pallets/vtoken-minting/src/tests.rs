@@ -23,10 +23,17 @@
 use crate::{mock::*, DispatchError::Module, *};
 use bifrost_primitives::{
 	currency::{BNC, FIL, KSM, MOVR, VBNC, VFIL, VKSM, VMOVR},
-	VtokenMintingOperator, V_WETH, WETH,
+	VtokenMintingInterface, VtokenMintingOperator, V_WETH, WETH,
 };
 use frame_support::{assert_noop, assert_ok, sp_runtime::Permill, BoundedVec};
 use sp_runtime::ModuleError;
+use xcm::{
+	v4::{
+		Junction::{AccountId32, Parachain},
+		Location,
+	},
+	VersionedLocation,
+};
 
 #[test]
 fn mint_bnc() {
@@ -1161,3 +1168,112 @@ fn set_vtoken_incentive_lock_blocks_should_work() {
 			assert_eq!(MintWithLockBlocks::<Runtime>::get(VKSM), None);
 		})
 }
+
+#[test]
+fn redeem_to_location_should_work() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob()
+		.build()
+		.execute_with(|| {
+			let location_of = |para_id: u32| {
+				VersionedLocation::V4(Location::new(
+					1,
+					[
+						Parachain(para_id),
+						AccountId32 {
+							network: None,
+							id: BOB.into(),
+						},
+					],
+				))
+			};
+			let destination = RedeemDestination {
+				account_format: AccountFormat::AccountId32,
+				fee: None,
+			};
+
+			assert_ok!(VtokenMinting::set_unlock_duration(
+				RuntimeOrigin::signed(ALICE),
+				KSM,
+				TimeUnit::Era(1)
+			));
+			assert_ok!(VtokenMinting::update_ongoing_time_unit(
+				KSM,
+				TimeUnit::Era(1)
+			));
+			assert_ok!(VtokenMinting::mint(
+				Some(BOB).into(),
+				KSM,
+				100,
+				BoundedVec::default(),
+				None
+			));
+
+			assert_noop!(
+				VtokenMinting::slpx_redeem(BOB, VKSM, 50, RedeemType::Location(location_of(2030))),
+				Error::<Runtime>::RedeemDestinationNotSupported
+			);
+			assert_ok!(VtokenMinting::set_redeem_destination(
+				RuntimeOrigin::signed(ALICE),
+				2030,
+				Some(RedeemDestination {
+					account_format: AccountFormat::AccountKey20,
+					fee: None
+				})
+			));
+			assert_noop!(
+				VtokenMinting::slpx_redeem(BOB, VKSM, 50, RedeemType::Location(location_of(2030))),
+				Error::<Runtime>::AccountFormatMismatch
+			);
+			assert_ok!(VtokenMinting::set_redeem_destination(
+				RuntimeOrigin::signed(ALICE),
+				2030,
+				Some(destination.clone())
+			));
+			assert_eq!(RedeemDestinations::<Runtime>::get(2030), Some(destination));
+
+			assert_ok!(VtokenMinting::slpx_redeem(
+				BOB,
+				VKSM,
+				50,
+				RedeemType::Location(location_of(2030))
+			));
+			assert_eq!(
+				TokenUnlockLedger::<Runtime>::get(KSM, 0),
+				Some((
+					BOB,
+					50,
+					TimeUnit::Era(2),
+					RedeemType::Location(location_of(2030))
+				))
+			);
+			assert_eq!(
+				VtokenMinting::transfer_to_by_redeem_type(
+					BOB,
+					KSM,
+					50,
+					100,
+					RedeemType::Location(location_of(2030))
+				),
+				Ok((50, RedeemTo::Location(location_of(2030))))
+			);
+
+			// Removed destinations fall back to the redeemer's account on Bifrost.
+			assert_ok!(VtokenMinting::set_redeem_destination(
+				RuntimeOrigin::signed(ALICE),
+				2030,
+				None
+			));
+			assert_eq!(
+				VtokenMinting::transfer_to_by_redeem_type(
+					BOB,
+					KSM,
+					50,
+					100,
+					RedeemType::Location(location_of(2030))
+				),
+				Ok((50, RedeemTo::Native(BOB)))
+			);
+			assert_eq!(Tokens::free_balance(KSM, &BOB), 50);
+		});
+}
@@ -18,10 +18,12 @@
 
 // Ensure we're `no_std` when compiling for Wasm.
 
+use bifrost_primitives::CurrencyId;
 use frame_support::pallet_prelude::Weight;
-use parity_scale_codec::{Decode, Encode};
+use parity_scale_codec::{Decode, Encode, MaxEncodedLen};
 use sp_core::H160;
 use sp_runtime::RuntimeDebug;
+use xcm::VersionedLocation;
 
 #[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, scale_info::TypeInfo)]
 pub enum RedeemTo<AccountId> {
@@ -37,6 +39,31 @@ pub enum RedeemTo<AccountId> {
 	Interlay(AccountId),
 	/// Manta chain.
 	Manta(AccountId),
+	/// Allowlisted parachain location.
+	Location(VersionedLocation),
+}
+
+/// The account key format used by the beneficiary on a redeem destination.
+#[derive(
+	PartialEq, Eq, Clone, Copy, Encode, Decode, MaxEncodedLen, RuntimeDebug, scale_info::TypeInfo,
+)]
+pub enum AccountFormat {
+	/// 32 bytes account, used by substrate based chains.
+	AccountId32,
+	/// 20 bytes account, used by evm based chains.
+	AccountKey20,
+}
+
+/// Governance managed configuration of a redeem destination parachain.
+#[derive(
+	PartialEq, Eq, Clone, Encode, Decode, MaxEncodedLen, RuntimeDebug, scale_info::TypeInfo,
+)]
+pub struct RedeemDestination<Balance> {
+	/// The account format accepted by the destination.
+	pub account_format: AccountFormat,
+	/// The asset and amount sent along with the redeemed tokens to pay for the xcm fee.
+	/// If `None`, the fee is paid with the redeemed tokens.
+	pub fee: Option<(CurrencyId, Balance)>,
 }
 
 pub trait OnRedeemSuccess<AccountId, CurrencyId, Balance> {
@@ -67,6 +67,30 @@ fn init_vote<T: Config>(vtoken: CurrencyIdOf<T>) -> Result<(), BenchmarkError> {
 	Ok(())
 }
 
+fn init_target_votes<T: Config>(
+	target: &AccountIdOf<T>,
+	vtoken: CurrencyIdOf<T>,
+	r: u32,
+) -> Result<(), BenchmarkError> {
+	let vote = account_vote::<T>(100u32.into());
+	let notify_origin =
+		T::ResponseOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+	let response = Response::DispatchResult(MaybeErrorCode::Success);
+	for index in 0..r {
+		Pallet::<T>::on_idle(Zero::zero(), Weight::MAX);
+		ReferendumClassFor::<T>::insert(vtoken, index, 0);
+		Pallet::<T>::vote(
+			RawOrigin::Signed(target.clone()).into(),
+			vtoken,
+			index,
+			vote,
+		)?;
+		Pallet::<T>::notify_vote(notify_origin.clone(), index as QueryId, response.clone())?;
+	}
+
+	Ok(())
+}
+
 #[benchmarks(where T::MaxVotes: core::fmt::Debug)]
 mod benchmarks {
 	use super::*;
@@ -423,6 +447,114 @@ mod benchmarks {
 		Ok(())
 	}
 
+	#[benchmark]
+	pub fn delegate(r: Linear<0, { T::MaxVotes::get() }>) -> Result<(), BenchmarkError> {
+		let caller = funded_account::<T>("caller", 0);
+		whitelist_account!(caller);
+		let target = funded_account::<T>("target", 1);
+		let vtoken = VKSM;
+
+		init_vote::<T>(vtoken)?;
+		init_target_votes::<T>(&target, vtoken, r)?;
+		Pallet::<T>::on_idle(Zero::zero(), Weight::MAX);
+
+		#[extrinsic_call]
+		_(
+			RawOrigin::Signed(caller.clone()),
+			vtoken,
+			0,
+			target,
+			Conviction::Locked1x,
+			100u32.into(),
+		);
+
+		assert!(DelegatingFor::<T>::contains_key((vtoken, &caller, 0)));
+
+		Ok(())
+	}
+
+	#[benchmark]
+	pub fn undelegate(r: Linear<0, { T::MaxVotes::get() }>) -> Result<(), BenchmarkError> {
+		let caller = funded_account::<T>("caller", 0);
+		whitelist_account!(caller);
+		let target = funded_account::<T>("target", 1);
+		let vtoken = VKSM;
+
+		init_vote::<T>(vtoken)?;
+		Pallet::<T>::set_vote_locking_period(RawOrigin::Root.into(), vtoken, 0u32.into())?;
+		init_target_votes::<T>(&target, vtoken, r)?;
+		Pallet::<T>::on_idle(Zero::zero(), Weight::MAX);
+		Pallet::<T>::delegate(
+			RawOrigin::Signed(caller.clone()).into(),
+			vtoken,
+			0,
+			target,
+			Conviction::Locked1x,
+			100u32.into(),
+		)?;
+
+		let notify_origin =
+			T::ResponseOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		let response = Response::DispatchResult(MaybeErrorCode::Success);
+		for query_id in r..2 * r {
+			Pallet::<T>::notify_vote(notify_origin.clone(), query_id as QueryId, response.clone())?;
+		}
+		Pallet::<T>::on_idle(Zero::zero(), Weight::MAX);
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller.clone()), vtoken, 0);
+
+		assert!(!DelegatingFor::<T>::contains_key((vtoken, &caller, 0)));
+
+		Ok(())
+	}
+
+	#[benchmark]
+	pub fn unlock_delegation() -> Result<(), BenchmarkError> {
+		let caller = funded_account::<T>("caller", 0);
+		whitelist_account!(caller);
+		let origin = RawOrigin::Signed(caller);
+		let target = funded_account::<T>("target", 1);
+		let vtoken = VKSM;
+
+		init_vote::<T>(vtoken)?;
+		Pallet::<T>::set_vote_locking_period(RawOrigin::Root.into(), vtoken, 0u32.into())?;
+		Pallet::<T>::delegate(
+			origin.clone().into(),
+			vtoken,
+			0,
+			target,
+			Conviction::Locked1x,
+			100u32.into(),
+		)?;
+		Pallet::<T>::undelegate(origin.clone().into(), vtoken, 0)?;
+
+		#[extrinsic_call]
+		_(origin, vtoken);
+
+		Ok(())
+	}
+
+	#[benchmark]
+	pub fn set_referendum_class() -> Result<(), BenchmarkError> {
+		let origin =
+			T::ControlOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		let vtoken = VKSM;
+		let poll_index = 0u32;
+
+		#[extrinsic_call]
+		_(
+			origin as <T as frame_system::Config>::RuntimeOrigin,
+			vtoken,
+			poll_index,
+			0,
+		);
+
+		assert_eq!(ReferendumClassFor::<T>::get(vtoken, poll_index), Some(0));
+
+		Ok(())
+	}
+
 	// This line generates test cases for benchmarking, and could be run by:
 	//   `cargo test -p pallet-example-basic --all-features`, you will see one line per case:
 	//   `test benchmarking::bench_sort_vector ... ok`
@@ -38,7 +38,7 @@ pub use crate::vote::{AccountVote, PollStatus, ReferendumInfo, ReferendumStatus,
 use crate::{
 	agents::{BifrostAgent, RelaychainAgent},
 	traits::VotingAgent,
-	vote::{Casting, Tally, Voting},
+	vote::{Casting, ClassDelegation, PriorLock, Tally, Voting},
 };
 use bifrost_primitives::{
 	currency::{BNC, DOT, KSM, VBNC, VDOT, VKSM},
@@ -58,7 +58,7 @@ use frame_system::{
 use orml_traits::{MultiCurrency, MultiLockableCurrency};
 pub use pallet::*;
 pub use pallet_conviction_voting::AccountVote as ConvictionVotingAccountVote;
-use pallet_conviction_voting::{Conviction, UnvoteScope, Vote};
+use pallet_conviction_voting::{Conviction, Delegations, UnvoteScope, Vote};
 use sp_runtime::{
 	traits::{
 		BlockNumberProvider, Bounded, CheckedDiv, CheckedMul, Dispatchable, Saturating,
@@ -71,6 +71,8 @@ pub use weights::WeightInfo;
 use xcm::v4::{prelude::*, Location, Weight as XcmWeight};
 
 const CONVICTION_VOTING_ID: LockIdentifier = *b"vtvoting";
+/// The maximum number of classes an account can delegate in for a vtoken.
+pub const MAX_DELEGATING_CLASSES: u32 = 32;
 type PollIndex = u32;
 type PollClass = u16;
 
@@ -86,6 +88,8 @@ pub type TallyOf<T> = Tally<BalanceOf<T>, ()>;
 type VotingOf<T> =
 	Voting<BalanceOf<T>, AccountIdOf<T>, BlockNumberFor<T>, PollIndex, <T as Config>::MaxVotes>;
 
+type ClassDelegationOf<T> = ClassDelegation<BalanceOf<T>, AccountIdOf<T>>;
+
 pub type ReferendumInfoOf<T> = ReferendumInfo<BlockNumberFor<T>, TallyOf<T>>;
 
 type VotingAgentBoxType<T> = Box<dyn VotingAgent<T>>;
@@ -302,6 +306,54 @@ pub mod pallet {
 			vtoken: CurrencyIdOf<T>,
 			vote_cap_ratio: Perbill,
 		},
+
+		/// An account has delegated its voting power in a class to another account.
+		///
+		/// - `who`: The account that delegated its voting power.
+		/// - `vtoken`: The token whose voting power is delegated.
+		/// - `class`: The class in which the voting power is delegated.
+		/// - `target`: The account to which the voting power is delegated.
+		/// - `conviction`: The conviction with which the voting power is delegated.
+		/// - `vtoken_balance`: The amount of vtoken locked for the delegation.
+		Delegated {
+			who: AccountIdOf<T>,
+			vtoken: CurrencyIdOf<T>,
+			class: PollClass,
+			target: AccountIdOf<T>,
+			conviction: Conviction,
+			vtoken_balance: BalanceOf<T>,
+		},
+
+		/// An account has undelegated its voting power in a class.
+		///
+		/// - `who`: The account that undelegated its voting power.
+		/// - `vtoken`: The token whose voting power was delegated.
+		/// - `class`: The class in which the voting power was delegated.
+		Undelegated {
+			who: AccountIdOf<T>,
+			vtoken: CurrencyIdOf<T>,
+			class: PollClass,
+		},
+
+		/// The expired delegation lock of an account has been released.
+		///
+		/// - `who`: The account whose tokens are unlocked.
+		/// - `vtoken`: The token that was locked during delegation.
+		DelegationUnlocked {
+			who: AccountIdOf<T>,
+			vtoken: CurrencyIdOf<T>,
+		},
+
+		/// The class of a referendum has been set.
+		///
+		/// - `vtoken`: The token associated with the referendum.
+		/// - `poll_index`: The index of the referendum.
+		/// - `class`: The class of the referendum.
+		ReferendumClassSet {
+			vtoken: CurrencyIdOf<T>,
+			poll_index: PollIndex,
+			class: PollClass,
+		},
 	}
 
 	#[pallet::error]
@@ -334,6 +386,15 @@ pub mod pallet {
 		NoPermissionYet,
 		/// The account is already delegating.
 		AlreadyDelegating,
+		/// The account currently has votes attached to it and the operation cannot succeed until
+		/// these are removed.
+		AlreadyVoting,
+		/// The account is not currently delegating.
+		NotDelegating,
+		/// Delegation to oneself makes no sense.
+		Nonsense,
+		/// The class of a referendum can not change once it has been voted on.
+		ReferendumAlreadyVoted,
 		/// Too high a balance was provided that the account cannot afford.
 		InsufficientFunds,
 		/// Maximum number of votes reached.
@@ -473,6 +534,36 @@ pub mod pallet {
 		DerivativeIndex,
 	>;
 
+	/// The class of a referendum. Delegations in this class are counted towards its tally.
+	#[pallet::storage]
+	pub type ReferendumClassFor<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, CurrencyIdOf<T>, Twox64Concat, PollIndex, PollClass>;
+
+	/// The voting power an account delegates in a class.
+	#[pallet::storage]
+	pub type DelegatingFor<T: Config> = StorageNMap<
+		_,
+		(
+			NMapKey<Twox64Concat, CurrencyIdOf<T>>,
+			NMapKey<Twox64Concat, AccountIdOf<T>>,
+			NMapKey<Twox64Concat, PollClass>,
+		),
+		ClassDelegationOf<T>,
+	>;
+
+	/// The voting power delegated to an account in a class, post-conviction-weighting.
+	#[pallet::storage]
+	pub type DelegationsFor<T: Config> = StorageNMap<
+		_,
+		(
+			NMapKey<Twox64Concat, CurrencyIdOf<T>>,
+			NMapKey<Twox64Concat, AccountIdOf<T>>,
+			NMapKey<Twox64Concat, PollClass>,
+		),
+		Delegations<BalanceOf<T>>,
+		ValueQuery,
+	>;
+
 	#[pallet::genesis_config]
 	#[derive(frame_support::DefaultNoBound)]
 	pub struct GenesisConfig<T: Config> {
@@ -597,28 +688,12 @@ pub mod pallet {
 			let (maybe_old_vote, maybe_total_vote) =
 				Self::try_vote(&who, vtoken, poll_index, token_vote, vtoken_vote.balance())?;
 
-			let delegator_total_vote = Self::compute_delegator_total_vote(
-				vtoken,
-				maybe_total_vote.ok_or(Error::<T>::NoData)?,
-			)?;
-			let new_delegator_votes =
-				Self::allocate_delegator_votes(vtoken, poll_index, delegator_total_vote)?;
-
-			PendingDelegatorVotes::<T>::try_mutate(vtoken, poll_index, |item| -> DispatchResult {
-				for (derivative_index, vote) in new_delegator_votes.iter() {
-					item.try_push((*derivative_index, *vote))
-						.map_err(|_| Error::<T>::TooMany)?;
-				}
-				Ok(())
-			})?;
-
-			let voting_agent = Self::get_voting_agent(&vtoken)?;
-			voting_agent.delegate_vote(
-				who.clone(),
+			let delegator_vote = Self::send_delegator_votes(
+				&who,
 				vtoken,
 				poll_index,
 				submitted,
-				new_delegator_votes.clone(),
+				maybe_total_vote.ok_or(Error::<T>::NoData)?,
 				maybe_old_vote,
 			)?;
 
@@ -627,7 +702,7 @@ pub mod pallet {
 				vtoken,
 				poll_index,
 				token_vote,
-				delegator_vote: new_delegator_votes[0].1,
+				delegator_vote,
 			});
 
 			Ok(())
@@ -906,6 +981,100 @@ pub mod pallet {
 
 			Ok(())
 		}
+
+		/// Delegate the voting power of `vtoken_balance` in `class` to `target` with `conviction`.
+		///
+		/// The delegated votes are added to the tally of every ongoing poll of `class` that
+		/// `target` has voted on, and the updated votes are sent to the relay chain.
+		#[pallet::call_index(12)]
+		#[pallet::weight(<T as Config>::WeightInfo::delegate(T::MaxVotes::get()))]
+		pub fn delegate(
+			origin: OriginFor<T>,
+			vtoken: CurrencyIdOf<T>,
+			#[pallet::compact] class: PollClass,
+			target: AccountIdOf<T>,
+			conviction: Conviction,
+			vtoken_balance: BalanceOf<T>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::ensure_vtoken(&vtoken)?;
+
+			Self::try_delegate(&who, vtoken, class, &target, conviction, vtoken_balance)?;
+
+			Self::deposit_event(Event::<T>::Delegated {
+				who,
+				vtoken,
+				class,
+				target,
+				conviction,
+				vtoken_balance,
+			});
+
+			Ok(())
+		}
+
+		/// Undelegate the voting power of `vtoken` in `class`.
+		///
+		/// The delegated balance stays locked for the lock period of the delegation's conviction,
+		/// and can be unlocked afterwards with `unlock_delegation`.
+		#[pallet::call_index(13)]
+		#[pallet::weight(<T as Config>::WeightInfo::undelegate(T::MaxVotes::get()))]
+		pub fn undelegate(
+			origin: OriginFor<T>,
+			vtoken: CurrencyIdOf<T>,
+			#[pallet::compact] class: PollClass,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::ensure_vtoken(&vtoken)?;
+
+			Self::try_undelegate(&who, vtoken, class)?;
+
+			Self::deposit_event(Event::<T>::Undelegated { who, vtoken, class });
+
+			Ok(())
+		}
+
+		/// Release the expired delegation lock of the caller.
+		#[pallet::call_index(14)]
+		#[pallet::weight(<T as Config>::WeightInfo::unlock_delegation())]
+		pub fn unlock_delegation(origin: OriginFor<T>, vtoken: CurrencyIdOf<T>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::ensure_vtoken(&vtoken)?;
+
+			Self::update_lock(&who, vtoken)?;
+
+			Self::deposit_event(Event::<T>::DelegationUnlocked { who, vtoken });
+
+			Ok(())
+		}
+
+		/// Set the class of a referendum, so that delegations in the class are counted towards
+		/// it. The class can only be set before the referendum is voted on.
+		#[pallet::call_index(15)]
+		#[pallet::weight(<T as Config>::WeightInfo::set_referendum_class())]
+		pub fn set_referendum_class(
+			origin: OriginFor<T>,
+			vtoken: CurrencyIdOf<T>,
+			#[pallet::compact] poll_index: PollIndex,
+			#[pallet::compact] class: PollClass,
+		) -> DispatchResult {
+			T::ControlOrigin::ensure_origin(origin)?;
+			Self::ensure_vtoken(&vtoken)?;
+			ensure!(
+				!ReferendumInfoFor::<T>::contains_key(vtoken, poll_index),
+				Error::<T>::ReferendumAlreadyVoted
+			);
+
+			ReferendumClassFor::<T>::insert(vtoken, poll_index, class);
+
+			Self::deposit_event(Event::<T>::ReferendumClassSet {
+				vtoken,
+				poll_index,
+				class,
+			});
+
+			Ok(())
+		}
 	}
 
 	impl<T: Config> Pallet<T> {
@@ -1167,24 +1336,27 @@ pub mod pallet {
 				vtoken_balance <= T::MultiCurrency::total_balance(vtoken, who),
 				Error::<T>::InsufficientFunds
 			);
+			if let Some(class) = ReferendumClassFor::<T>::get(vtoken, poll_index) {
+				ensure!(
+					!DelegatingFor::<T>::contains_key((vtoken, who, class)),
+					Error::<T>::AlreadyDelegating
+				);
+			}
+			let delegations = Self::poll_delegations(who, vtoken, poll_index);
+			let delegated_vtoken_balance = Self::delegated_vtoken_balance(who, vtoken);
 			let mut old_vote = None;
 			let mut total_vote = None;
 			Self::try_access_poll(vtoken, poll_index, |poll_status| {
 				let tally = poll_status.ensure_ongoing().ok_or(Error::<T>::NotOngoing)?;
 				VotingForV2::<T>::try_mutate(vtoken, who, |voting| {
-					if let Voting::Casting(Casting {
-						ref mut votes,
-						delegations,
-						..
-					}) = voting
-					{
+					if let Voting::Casting(Casting { ref mut votes, .. }) = voting {
 						match votes.binary_search_by_key(&poll_index, |i| i.0) {
 							Ok(i) => {
 								// Shouldn't be possible to fail, but we handle it gracefully.
 								tally.remove(votes[i].1).ok_or(ArithmeticError::Underflow)?;
 								old_vote = Some((votes[i].1, votes[i].3));
 								if let Some(approve) = votes[i].1.as_standard() {
-									tally.reduce(approve, *delegations);
+									tally.reduce(approve, delegations);
 								}
 								votes[i].1 = vote;
 								votes[i].2 = 0; // Deprecated: derivative_index
@@ -1203,7 +1375,7 @@ pub mod pallet {
 						// Shouldn't be possible to fail, but we handle it gracefully.
 						tally.add(vote).ok_or(ArithmeticError::Overflow)?;
 						if let Some(approve) = vote.as_standard() {
-							tally.increase(approve, *delegations);
+							tally.increase(approve, delegations);
 						}
 						total_vote = Some(tally.account_vote(Conviction::Locked1x));
 					} else {
@@ -1211,7 +1383,11 @@ pub mod pallet {
 					}
 					// Extend the lock to `balance` (rather than setting it) since we don't know
 					// what other votes are in place.
-					Self::set_lock(&who, vtoken, voting.locked_vtoken_balance())?;
+					Self::set_lock(
+						&who,
+						vtoken,
+						voting.locked_vtoken_balance().max(delegated_vtoken_balance),
+					)?;
 					Ok((old_vote, total_vote))
 				})
 			})
@@ -1229,11 +1405,12 @@ pub mod pallet {
 			poll_index: PollIndex,
 			scope: UnvoteScope,
 		) -> DispatchResult {
+			let delegations = Self::poll_delegations(who, vtoken, poll_index);
 			VotingForV2::<T>::try_mutate(vtoken, who, |voting| {
 				if let Voting::Casting(Casting {
 					ref mut votes,
-					delegations,
 					ref mut prior,
+					..
 				}) = voting
 				{
 					let i = votes
@@ -1247,7 +1424,7 @@ pub mod pallet {
 							// Shouldn't be possible to fail, but we handle it gracefully.
 							tally.remove(v.1).ok_or(ArithmeticError::Underflow)?;
 							if let Some(approve) = v.1.as_standard() {
-								tally.reduce(approve, *delegations);
+								tally.reduce(approve, delegations);
 							}
 							Ok(())
 						}
@@ -1280,6 +1457,226 @@ pub mod pallet {
 			})
 		}
 
+		/// Delegate the voting power of `who` in `class` to `target`. The lock of `who` is
+		/// extended to `vtoken_balance`.
+		pub(crate) fn try_delegate(
+			who: &AccountIdOf<T>,
+			vtoken: CurrencyIdOf<T>,
+			class: PollClass,
+			target: &AccountIdOf<T>,
+			conviction: Conviction,
+			vtoken_balance: BalanceOf<T>,
+		) -> DispatchResult {
+			ensure!(who != target, Error::<T>::Nonsense);
+			ensure!(
+				vtoken_balance <= T::MultiCurrency::total_balance(vtoken, who),
+				Error::<T>::InsufficientFunds
+			);
+			ensure!(
+				!DelegatingFor::<T>::contains_key((vtoken, who, class)),
+				Error::<T>::AlreadyDelegating
+			);
+			ensure!(
+				(DelegatingFor::<T>::iter_prefix_values((vtoken, who)).count() as u32)
+					< MAX_DELEGATING_CLASSES,
+				Error::<T>::TooMany
+			);
+			if let Voting::Casting(Casting { votes, .. }) = VotingForV2::<T>::get(vtoken, who) {
+				ensure!(
+					!votes.iter().any(|&(poll_index, ..)| {
+						ReferendumClassFor::<T>::get(vtoken, poll_index) == Some(class)
+					}),
+					Error::<T>::AlreadyVoting
+				);
+			}
+			let balance = Self::compute_token_vote(
+				vtoken,
+				AccountVote::new_standard(
+					Vote {
+						aye: true,
+						conviction,
+					},
+					vtoken_balance,
+				),
+			)?
+			.balance();
+
+			DelegatingFor::<T>::insert(
+				(vtoken, who, class),
+				ClassDelegation {
+					balance,
+					vtoken_balance,
+					target: target.clone(),
+					conviction,
+				},
+			);
+			Self::increase_upstream_delegation(target, vtoken, class, conviction.votes(balance))?;
+
+			Self::update_lock(who, vtoken)
+		}
+
+		/// Undelegate the voting power of `who` in `class`. The delegated balance stays locked
+		/// for the lock period of the delegation's conviction.
+		pub(crate) fn try_undelegate(
+			who: &AccountIdOf<T>,
+			vtoken: CurrencyIdOf<T>,
+			class: PollClass,
+		) -> DispatchResult {
+			let ClassDelegation {
+				balance,
+				vtoken_balance,
+				target,
+				conviction,
+			} = DelegatingFor::<T>::take((vtoken, who, class)).ok_or(Error::<T>::NotDelegating)?;
+			Self::reduce_upstream_delegation(&target, vtoken, class, conviction.votes(balance))?;
+
+			let now = Self::get_agent_block_number(&vtoken)?;
+			let unlock_at = now.saturating_add(
+				VoteLockingPeriod::<T>::get(vtoken)
+					.ok_or(Error::<T>::NoData)?
+					.saturating_mul(conviction.lock_periods().into()),
+			);
+			VotingForV2::<T>::mutate(vtoken, who, |voting| {
+				AsMut::<PriorLock<BlockNumberFor<T>, BalanceOf<T>>>::as_mut(voting)
+					.accumulate(unlock_at, vtoken_balance)
+			});
+
+			Self::update_lock(who, vtoken)
+		}
+
+		/// Add `amount` to the delegations of `who` in `class`, and to the tally of every ongoing
+		/// poll of `class` that `who` has voted on.
+		fn increase_upstream_delegation(
+			who: &AccountIdOf<T>,
+			vtoken: CurrencyIdOf<T>,
+			class: PollClass,
+			amount: Delegations<BalanceOf<T>>,
+		) -> DispatchResult {
+			DelegationsFor::<T>::mutate((vtoken, who, class), |delegations| {
+				*delegations = delegations.saturating_add(amount)
+			});
+			Self::update_delegated_votes(who, vtoken, class, |tally, approve| {
+				tally.increase(approve, amount)
+			})
+		}
+
+		/// Remove `amount` from the delegations of `who` in `class`, and from the tally of every
+		/// ongoing poll of `class` that `who` has voted on.
+		fn reduce_upstream_delegation(
+			who: &AccountIdOf<T>,
+			vtoken: CurrencyIdOf<T>,
+			class: PollClass,
+			amount: Delegations<BalanceOf<T>>,
+		) -> DispatchResult {
+			DelegationsFor::<T>::mutate_exists((vtoken, who, class), |maybe_delegations| {
+				let delegations = maybe_delegations.unwrap_or_default().saturating_sub(amount);
+				*maybe_delegations = (delegations != Default::default()).then_some(delegations);
+			});
+			Self::update_delegated_votes(who, vtoken, class, |tally, approve| {
+				tally.reduce(approve, amount)
+			})
+		}
+
+		/// Apply `f` to the tally of every ongoing poll of `class` that `who` has voted on, and
+		/// send the updated votes to the relay chain.
+		fn update_delegated_votes(
+			who: &AccountIdOf<T>,
+			vtoken: CurrencyIdOf<T>,
+			class: PollClass,
+			f: impl Fn(&mut TallyOf<T>, bool),
+		) -> DispatchResult {
+			let votes = match VotingForV2::<T>::get(vtoken, who) {
+				Voting::Casting(Casting { votes, .. }) => votes,
+				Voting::Delegating(_) => return Ok(()),
+			};
+			for (poll_index, account_vote, _, vtoken_balance) in votes {
+				if ReferendumClassFor::<T>::get(vtoken, poll_index) != Some(class) {
+					continue;
+				}
+				let approve = match account_vote.as_standard() {
+					Some(approve) => approve,
+					None => continue,
+				};
+				Self::ensure_no_pending_vote(vtoken, poll_index)?;
+				let mut total_vote = None;
+				Self::try_access_poll(vtoken, poll_index, |poll_status| {
+					if let PollStatus::Ongoing(tally) = poll_status {
+						f(tally, approve);
+						total_vote = Some(tally.account_vote(Conviction::Locked1x));
+					}
+					Ok(())
+				})?;
+				if let Some(total_vote) = total_vote {
+					Self::send_delegator_votes(
+						who,
+						vtoken,
+						poll_index,
+						true,
+						total_vote,
+						Some((account_vote, vtoken_balance)),
+					)?;
+				}
+			}
+			Ok(())
+		}
+
+		/// The delegations `who` has received in the class of `poll_index`.
+		fn poll_delegations(
+			who: &AccountIdOf<T>,
+			vtoken: CurrencyIdOf<T>,
+			poll_index: PollIndex,
+		) -> Delegations<BalanceOf<T>> {
+			ReferendumClassFor::<T>::get(vtoken, poll_index)
+				.map(|class| DelegationsFor::<T>::get((vtoken, who, class)))
+				.unwrap_or_default()
+		}
+
+		/// The largest amount of vtoken `who` locks for its delegations.
+		fn delegated_vtoken_balance(who: &AccountIdOf<T>, vtoken: CurrencyIdOf<T>) -> BalanceOf<T> {
+			DelegatingFor::<T>::iter_prefix_values((vtoken, who))
+				.fold(Zero::zero(), |a, delegation| {
+					a.max(delegation.vtoken_balance)
+				})
+		}
+
+		/// Split the total vote of the poll among the delegators, and send it to the relay chain.
+		/// Returns the vote of the first delegator.
+		fn send_delegator_votes(
+			who: &AccountIdOf<T>,
+			vtoken: CurrencyIdOf<T>,
+			poll_index: PollIndex,
+			submitted: bool,
+			total_vote: AccountVote<BalanceOf<T>>,
+			maybe_old_vote: Option<(AccountVote<BalanceOf<T>>, BalanceOf<T>)>,
+		) -> Result<AccountVote<BalanceOf<T>>, DispatchError> {
+			let delegator_total_vote = Self::compute_delegator_total_vote(vtoken, total_vote)?;
+			let new_delegator_votes =
+				Self::allocate_delegator_votes(vtoken, poll_index, delegator_total_vote)?;
+
+			PendingDelegatorVotes::<T>::try_mutate(vtoken, poll_index, |item| -> DispatchResult {
+				for (derivative_index, vote) in new_delegator_votes.iter() {
+					item.try_push((*derivative_index, *vote))
+						.map_err(|_| Error::<T>::TooMany)?;
+				}
+				Ok(())
+			})?;
+
+			let voting_agent = Self::get_voting_agent(&vtoken)?;
+			voting_agent.delegate_vote(
+				who.clone(),
+				vtoken,
+				poll_index,
+				submitted,
+				new_delegator_votes.clone(),
+				maybe_old_vote,
+			)?;
+
+			new_delegator_votes
+				.first()
+				.map(|(_, vote)| *vote)
+				.ok_or(Error::<T>::NoData.into())
+		}
+
 		/// Rejig the lock on an account. It will never get more stringent (since that would
 		/// indicate a security hole) but may be reduced from what they are currently.
 		pub(crate) fn update_lock(who: &AccountIdOf<T>, vtoken: CurrencyIdOf<T>) -> DispatchResult {
@@ -1287,7 +1684,8 @@ pub mod pallet {
 			let lock_needed = VotingForV2::<T>::mutate(vtoken, who, |voting| {
 				voting.rejig(current_block);
 				voting.locked_balance()
-			});
+			})
+			.max(Self::delegated_vtoken_balance(who, vtoken));
 
 			if lock_needed.is_zero() {
 				ClassLocksFor::<T>::mutate(who, |locks| {
@@ -1847,3 +1847,299 @@ fn set_lock_works() {
 		});
 	}
 }
+
+#[test]
+fn delegate_and_undelegate_works() {
+	for &vtoken in TOKENS {
+		new_test_ext().execute_with(|| {
+			let poll_index = 3;
+			let class = 0;
+
+			assert_ok!(VtokenVoting::set_referendum_class(
+				RuntimeOrigin::root(),
+				vtoken,
+				poll_index,
+				class
+			));
+			assert_ok!(VtokenVoting::vote(
+				RuntimeOrigin::signed(ALICE),
+				vtoken,
+				poll_index,
+				aye(2, 5)
+			));
+			assert_ok!(VtokenVoting::notify_vote(
+				origin_response(),
+				0,
+				response_success()
+			));
+			assert_eq!(tally(vtoken, poll_index), Tally::from_parts(20, 0, 4));
+
+			// A delegation in another class is not counted.
+			assert_ok!(VtokenVoting::delegate(
+				RuntimeOrigin::signed(BOB),
+				vtoken,
+				class + 1,
+				ALICE,
+				Conviction::Locked1x,
+				5
+			));
+			assert_eq!(tally(vtoken, poll_index), Tally::from_parts(20, 0, 4));
+			assert!(!PendingVotingInfo::<Runtime>::contains_key(1));
+
+			assert_ok!(VtokenVoting::delegate(
+				RuntimeOrigin::signed(BOB),
+				vtoken,
+				class,
+				ALICE,
+				Conviction::Locked1x,
+				5
+			));
+			System::assert_last_event(RuntimeEvent::VtokenVoting(Event::Delegated {
+				who: BOB,
+				vtoken,
+				class,
+				target: ALICE,
+				conviction: Conviction::Locked1x,
+				vtoken_balance: 5,
+			}));
+			assert_eq!(tally(vtoken, poll_index), Tally::from_parts(30, 0, 14));
+			// The updated vote is sent to the relay chain.
+			assert!(PendingVotingInfo::<Runtime>::contains_key(1));
+			assert_ok!(VtokenVoting::notify_vote(
+				origin_response(),
+				1,
+				response_success()
+			));
+			assert_eq!(
+				ClassLocksFor::<Runtime>::get(BOB).into_inner(),
+				vec![(vtoken, 5)]
+			);
+			assert_eq!(usable_balance(vtoken, &BOB), 15);
+
+			assert_ok!(VtokenVoting::set_vote_locking_period(
+				RuntimeOrigin::root(),
+				vtoken,
+				10
+			));
+			RelaychainDataProvider::set_block_number(1);
+			assert_ok!(VtokenVoting::undelegate(
+				RuntimeOrigin::signed(BOB),
+				vtoken,
+				class
+			));
+			System::assert_last_event(RuntimeEvent::VtokenVoting(Event::Undelegated {
+				who: BOB,
+				vtoken,
+				class,
+			}));
+			assert_eq!(tally(vtoken, poll_index), Tally::from_parts(20, 0, 4));
+			assert!(PendingVotingInfo::<Runtime>::contains_key(2));
+			assert_ok!(VtokenVoting::undelegate(
+				RuntimeOrigin::signed(BOB),
+				vtoken,
+				class + 1
+			));
+			assert_eq!(usable_balance(vtoken, &BOB), 15);
+
+			assert_ok!(VtokenVoting::unlock_delegation(
+				RuntimeOrigin::signed(BOB),
+				vtoken
+			));
+			assert_eq!(usable_balance(vtoken, &BOB), 15);
+
+			RelaychainDataProvider::set_block_number(11);
+			assert_ok!(VtokenVoting::unlock_delegation(
+				RuntimeOrigin::signed(BOB),
+				vtoken
+			));
+			System::assert_last_event(RuntimeEvent::VtokenVoting(Event::DelegationUnlocked {
+				who: BOB,
+				vtoken,
+			}));
+			assert!(ClassLocksFor::<Runtime>::get(BOB).is_empty());
+			assert_eq!(usable_balance(vtoken, &BOB), 20);
+		});
+	}
+}
+
+#[test]
+fn delegations_are_counted_in_new_votes() {
+	for &vtoken in TOKENS {
+		new_test_ext().execute_with(|| {
+			let poll_index = 3;
+			let class = 0;
+
+			assert_ok!(VtokenVoting::set_referendum_class(
+				RuntimeOrigin::root(),
+				vtoken,
+				poll_index,
+				class
+			));
+			assert_ok!(VtokenVoting::delegate(
+				RuntimeOrigin::signed(BOB),
+				vtoken,
+				class,
+				ALICE,
+				Conviction::Locked1x,
+				5
+			));
+			assert_ok!(VtokenVoting::vote(
+				RuntimeOrigin::signed(ALICE),
+				vtoken,
+				poll_index,
+				aye(2, 5)
+			));
+			assert_eq!(tally(vtoken, poll_index), Tally::from_parts(30, 0, 14));
+
+			assert_ok!(VtokenVoting::try_remove_vote(
+				&ALICE,
+				vtoken,
+				poll_index,
+				UnvoteScope::Any
+			));
+			assert_eq!(tally(vtoken, poll_index), Tally::from_parts(0, 0, 0));
+		});
+	}
+}
+
+#[test]
+fn delegations_are_not_counted_in_other_classes() {
+	for &vtoken in TOKENS {
+		new_test_ext().execute_with(|| {
+			let poll_index = 3;
+
+			assert_ok!(VtokenVoting::set_referendum_class(
+				RuntimeOrigin::root(),
+				vtoken,
+				poll_index,
+				1
+			));
+			assert_ok!(VtokenVoting::delegate(
+				RuntimeOrigin::signed(BOB),
+				vtoken,
+				0,
+				ALICE,
+				Conviction::Locked1x,
+				5
+			));
+			assert_ok!(VtokenVoting::vote(
+				RuntimeOrigin::signed(ALICE),
+				vtoken,
+				poll_index,
+				aye(2, 5)
+			));
+			assert_eq!(tally(vtoken, poll_index), Tally::from_parts(20, 0, 4));
+
+			// BOB only delegates in class 0, so it can still vote on a class 1 poll.
+			assert_ok!(VtokenVoting::notify_vote(
+				origin_response(),
+				0,
+				response_success()
+			));
+			assert_ok!(VtokenVoting::vote(
+				RuntimeOrigin::signed(BOB),
+				vtoken,
+				poll_index,
+				aye(1, 1)
+			));
+		});
+	}
+}
+
+#[test]
+fn delegate_fails_with_invalid_state() {
+	for &vtoken in TOKENS {
+		new_test_ext().execute_with(|| {
+			let class = 0;
+
+			assert_noop!(
+				VtokenVoting::delegate(
+					RuntimeOrigin::signed(ALICE),
+					vtoken,
+					class,
+					ALICE,
+					Conviction::Locked1x,
+					5
+				),
+				Error::<Runtime>::Nonsense
+			);
+			assert_noop!(
+				VtokenVoting::delegate(
+					RuntimeOrigin::signed(BOB),
+					vtoken,
+					class,
+					ALICE,
+					Conviction::Locked1x,
+					21
+				),
+				Error::<Runtime>::InsufficientFunds
+			);
+			assert_noop!(
+				VtokenVoting::undelegate(RuntimeOrigin::signed(BOB), vtoken, class),
+				Error::<Runtime>::NotDelegating
+			);
+
+			assert_ok!(VtokenVoting::set_referendum_class(
+				RuntimeOrigin::root(),
+				vtoken,
+				3,
+				class
+			));
+			assert_ok!(VtokenVoting::vote(
+				RuntimeOrigin::signed(ALICE),
+				vtoken,
+				3,
+				aye(2, 5)
+			));
+			assert_noop!(
+				VtokenVoting::set_referendum_class(RuntimeOrigin::root(), vtoken, 3, class + 1),
+				Error::<Runtime>::ReferendumAlreadyVoted
+			);
+			assert_noop!(
+				VtokenVoting::delegate(
+					RuntimeOrigin::signed(ALICE),
+					vtoken,
+					class,
+					BOB,
+					Conviction::Locked1x,
+					5
+				),
+				Error::<Runtime>::AlreadyVoting
+			);
+
+			assert_ok!(VtokenVoting::notify_vote(
+				origin_response(),
+				0,
+				response_success()
+			));
+			assert_ok!(VtokenVoting::delegate(
+				RuntimeOrigin::signed(BOB),
+				vtoken,
+				class,
+				ALICE,
+				Conviction::Locked1x,
+				5
+			));
+			assert_noop!(
+				VtokenVoting::delegate(
+					RuntimeOrigin::signed(BOB),
+					vtoken,
+					class,
+					ALICE,
+					Conviction::Locked1x,
+					5
+				),
+				Error::<Runtime>::AlreadyDelegating
+			);
+			assert_ok!(VtokenVoting::notify_vote(
+				origin_response(),
+				1,
+				response_success()
+			));
+			assert_noop!(
+				VtokenVoting::vote(RuntimeOrigin::signed(BOB), vtoken, 3, aye(2, 5)),
+				Error::<Runtime>::AlreadyDelegating
+			);
+		});
+	}
+}
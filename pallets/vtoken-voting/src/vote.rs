@@ -417,6 +417,20 @@ impl<BlockNumber: Ord + Copy + Zero, Balance: Ord + Copy + Zero> PriorLock<Block
 	}
 }
 
+/// The voting power an account delegates to a target in a single class.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct ClassDelegation<Balance, AccountId> {
+	/// The amount of balance delegated, denominated in the token.
+	pub balance: Balance,
+	/// The amount of vtoken locked for the delegation.
+	pub vtoken_balance: Balance,
+	/// The account to which the voting power is delegated.
+	pub target: AccountId,
+	/// The conviction with which the voting power is delegated. When this gets undelegated, the
+	/// relevant lock begins.
+	pub conviction: Conviction,
+}
+
 /// Information concerning the delegation of some voting power.
 #[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
 pub struct Delegating<Balance, AccountId, BlockNumber> {
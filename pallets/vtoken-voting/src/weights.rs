@@ -65,6 +65,10 @@ pub trait WeightInfo {
 	fn notify_vote() -> Weight;
 	fn notify_remove_delegator_vote() -> Weight;
 	fn set_vote_cap_ratio() -> Weight;
+	fn delegate(r: u32, ) -> Weight;
+	fn undelegate(r: u32, ) -> Weight;
+	fn unlock_delegation() -> Weight;
+	fn set_referendum_class() -> Weight;
 }
 
 // For backwards compatibility and tests
@@ -289,4 +293,130 @@ impl WeightInfo for () {
 		Weight::from_parts(39_364_000, 3501)
 			.saturating_add(RocksDbWeight::get().reads(1_u64))
 	}
+	/// Storage: Tokens Accounts (r:1 w:1)
+	/// Proof: Tokens Accounts (max_values: None, max_size: Some(118), added: 2593, mode: MaxEncodedLen)
+	/// Storage: Tokens TotalIssuance (r:1 w:0)
+	/// Proof: Tokens TotalIssuance (max_values: None, max_size: Some(38), added: 2513, mode: MaxEncodedLen)
+	/// Storage: VtokenMinting TokenPool (r:1 w:0)
+	/// Proof: VtokenMinting TokenPool (max_values: None, max_size: Some(38), added: 2513, mode: MaxEncodedLen)
+	/// Storage: VtokenVoting VotingFor (r:2 w:2)
+	/// Proof: VtokenVoting VotingFor (max_values: None, max_size: Some(13663), added: 16138, mode: MaxEncodedLen)
+	/// Storage: VtokenVoting ReferendumInfoFor (r:1 w:1)
+	/// Proof: VtokenVoting ReferendumInfoFor (max_values: None, max_size: Some(88), added: 2563, mode: MaxEncodedLen)
+	/// Storage: ParachainSystem ValidationData (r:1 w:0)
+	/// Proof Skipped: ParachainSystem ValidationData (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: VtokenVoting ClassLocksFor (r:1 w:1)
+	/// Proof: VtokenVoting ClassLocksFor (max_values: None, max_size: Some(5162), added: 7637, mode: MaxEncodedLen)
+	/// Storage: Tokens Locks (r:1 w:1)
+	/// Proof: Tokens Locks (max_values: None, max_size: Some(1271), added: 3746, mode: MaxEncodedLen)
+	/// Storage: VtokenVoting DelegatingFor (r:2 w:1)
+	/// Proof: VtokenVoting DelegatingFor (max_values: None, max_size: Some(96), added: 2571, mode: MaxEncodedLen)
+	/// Storage: VtokenVoting DelegationsFor (r:1 w:1)
+	/// Proof: VtokenVoting DelegationsFor (max_values: None, max_size: Some(76), added: 2551, mode: MaxEncodedLen)
+	/// Storage: VtokenVoting ReferendumClassFor (r:256 w:0)
+	/// Proof: VtokenVoting ReferendumClassFor (max_values: None, max_size: Some(33), added: 2508, mode: MaxEncodedLen)
+	/// Storage: VtokenVoting PendingVotingInfo (r:256 w:256)
+	/// Proof: VtokenVoting PendingVotingInfo (max_values: None, max_size: Some(117), added: 2592, mode: MaxEncodedLen)
+	/// Storage: VtokenVoting PendingDelegatorVotes (r:256 w:256)
+	/// Proof: VtokenVoting PendingDelegatorVotes (max_values: None, max_size: Some(2342), added: 4817, mode: MaxEncodedLen)
+	/// Storage: PolkadotXcm QueryCounter (r:256 w:256)
+	/// Proof Skipped: PolkadotXcm QueryCounter (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: PolkadotXcm Queries (r:0 w:256)
+	/// Proof Skipped: PolkadotXcm Queries (max_values: None, max_size: None, mode: Measured)
+	/// The range of component `r` is `[0, 256]`.
+	fn delegate(r: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `2412 + r * (103 ±0)`
+		//  Estimated: `17128 + r * (2563 ±0)`
+		// Minimum execution time: 98_316_000 picoseconds.
+		Weight::from_parts(101_540_000, 17128)
+			// Standard Error: 11_652
+			.saturating_add(Weight::from_parts(196_815_000, 0).saturating_mul(r.into()))
+			.saturating_add(RocksDbWeight::get().reads(12_u64))
+			.saturating_add(RocksDbWeight::get().reads((8_u64).saturating_mul(r.into())))
+			.saturating_add(RocksDbWeight::get().writes(7_u64))
+			.saturating_add(RocksDbWeight::get().writes((6_u64).saturating_mul(r.into())))
+			.saturating_add(Weight::from_parts(0, 2563).saturating_mul(r.into()))
+	}
+	/// Storage: VtokenVoting VotingFor (r:2 w:2)
+	/// Proof: VtokenVoting VotingFor (max_values: None, max_size: Some(13663), added: 16138, mode: MaxEncodedLen)
+	/// Storage: VtokenVoting VoteLockingPeriod (r:1 w:0)
+	/// Proof: VtokenVoting VoteLockingPeriod (max_values: None, max_size: Some(26), added: 2501, mode: MaxEncodedLen)
+	/// Storage: Tokens TotalIssuance (r:1 w:0)
+	/// Proof: Tokens TotalIssuance (max_values: None, max_size: Some(38), added: 2513, mode: MaxEncodedLen)
+	/// Storage: VtokenMinting TokenPool (r:1 w:0)
+	/// Proof: VtokenMinting TokenPool (max_values: None, max_size: Some(38), added: 2513, mode: MaxEncodedLen)
+	/// Storage: VtokenVoting ReferendumInfoFor (r:1 w:1)
+	/// Proof: VtokenVoting ReferendumInfoFor (max_values: None, max_size: Some(88), added: 2563, mode: MaxEncodedLen)
+	/// Storage: ParachainSystem ValidationData (r:1 w:0)
+	/// Proof Skipped: ParachainSystem ValidationData (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: VtokenVoting ClassLocksFor (r:1 w:1)
+	/// Proof: VtokenVoting ClassLocksFor (max_values: None, max_size: Some(5162), added: 7637, mode: MaxEncodedLen)
+	/// Storage: Tokens Locks (r:1 w:1)
+	/// Proof: Tokens Locks (max_values: None, max_size: Some(1271), added: 3746, mode: MaxEncodedLen)
+	/// Storage: Tokens Accounts (r:1 w:1)
+	/// Proof: Tokens Accounts (max_values: None, max_size: Some(118), added: 2593, mode: MaxEncodedLen)
+	/// Storage: VtokenVoting DelegatingFor (r:2 w:1)
+	/// Proof: VtokenVoting DelegatingFor (max_values: None, max_size: Some(96), added: 2571, mode: MaxEncodedLen)
+	/// Storage: VtokenVoting DelegationsFor (r:1 w:1)
+	/// Proof: VtokenVoting DelegationsFor (max_values: None, max_size: Some(76), added: 2551, mode: MaxEncodedLen)
+	/// Storage: VtokenVoting ReferendumClassFor (r:256 w:0)
+	/// Proof: VtokenVoting ReferendumClassFor (max_values: None, max_size: Some(33), added: 2508, mode: MaxEncodedLen)
+	/// Storage: VtokenVoting PendingVotingInfo (r:256 w:256)
+	/// Proof: VtokenVoting PendingVotingInfo (max_values: None, max_size: Some(117), added: 2592, mode: MaxEncodedLen)
+	/// Storage: VtokenVoting PendingDelegatorVotes (r:256 w:256)
+	/// Proof: VtokenVoting PendingDelegatorVotes (max_values: None, max_size: Some(2342), added: 4817, mode: MaxEncodedLen)
+	/// Storage: PolkadotXcm QueryCounter (r:256 w:256)
+	/// Proof Skipped: PolkadotXcm QueryCounter (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: PolkadotXcm Queries (r:0 w:256)
+	/// Proof Skipped: PolkadotXcm Queries (max_values: None, max_size: None, mode: Measured)
+	/// The range of component `r` is `[0, 256]`.
+	fn undelegate(r: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `2240 + r * (103 ±0)`
+		//  Estimated: `17128 + r * (2563 ±0)`
+		// Minimum execution time: 89_052_000 picoseconds.
+		Weight::from_parts(92_117_000, 17128)
+			// Standard Error: 10_971
+			.saturating_add(Weight::from_parts(196_652_000, 0).saturating_mul(r.into()))
+			.saturating_add(RocksDbWeight::get().reads(13_u64))
+			.saturating_add(RocksDbWeight::get().reads((8_u64).saturating_mul(r.into())))
+			.saturating_add(RocksDbWeight::get().writes(7_u64))
+			.saturating_add(RocksDbWeight::get().writes((6_u64).saturating_mul(r.into())))
+			.saturating_add(Weight::from_parts(0, 2563).saturating_mul(r.into()))
+	}
+	/// Storage: ParachainSystem ValidationData (r:1 w:0)
+	/// Proof Skipped: ParachainSystem ValidationData (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: VtokenVoting VotingFor (r:1 w:1)
+	/// Proof: VtokenVoting VotingFor (max_values: None, max_size: Some(13663), added: 16138, mode: MaxEncodedLen)
+	/// Storage: VtokenVoting ClassLocksFor (r:1 w:1)
+	/// Proof: VtokenVoting ClassLocksFor (max_values: None, max_size: Some(5162), added: 7637, mode: MaxEncodedLen)
+	/// Storage: Tokens Locks (r:1 w:1)
+	/// Proof: Tokens Locks (max_values: None, max_size: Some(1271), added: 3746, mode: MaxEncodedLen)
+	/// Storage: Tokens Accounts (r:1 w:1)
+	/// Proof: Tokens Accounts (max_values: None, max_size: Some(118), added: 2593, mode: MaxEncodedLen)
+	/// Storage: VtokenVoting DelegatingFor (r:1 w:0)
+	/// Proof: VtokenVoting DelegatingFor (max_values: None, max_size: Some(96), added: 2571, mode: MaxEncodedLen)
+	fn unlock_delegation() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1865`
+		//  Estimated: `17128`
+		// Minimum execution time: 61_834_000 picoseconds.
+		Weight::from_parts(63_205_000, 17128)
+			.saturating_add(RocksDbWeight::get().reads(6_u64))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+	}
+	/// Storage: VtokenVoting ReferendumInfoFor (r:1 w:0)
+	/// Proof: VtokenVoting ReferendumInfoFor (max_values: None, max_size: Some(88), added: 2563, mode: MaxEncodedLen)
+	/// Storage: VtokenVoting ReferendumClassFor (r:0 w:1)
+	/// Proof: VtokenVoting ReferendumClassFor (max_values: None, max_size: Some(33), added: 2508, mode: MaxEncodedLen)
+	fn set_referendum_class() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `329`
+		//  Estimated: `3553`
+		// Minimum execution time: 40_102_000 picoseconds.
+		Weight::from_parts(41_011_000, 3553)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
 }
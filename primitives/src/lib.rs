@@ -24,12 +24,14 @@ use frame_support::{parameter_types, PalletId};
 use hex_literal::hex;
 use parity_scale_codec::MaxEncodedLen;
 use scale_info::TypeInfo;
+use serde::{Deserialize, Serialize};
 use sp_core::{Decode, Encode, RuntimeDebug, H160};
 use sp_runtime::{
 	generic,
 	traits::{BlakeTwo256, IdentifyAccount, Verify},
 	FixedU128, MultiSignature, OpaqueExtrinsic, Permill,
 };
+use sp_std::vec::Vec;
 
 pub mod currency;
 pub use currency::*;
@@ -222,6 +224,8 @@ pub enum RedeemType<AccountId> {
 	Interlay(AccountId),
 	/// Manta chain.
 	Manta(AccountId),
+	/// Any allowlisted parachain, identified by the beneficiary location.
+	Location(::xcm::VersionedLocation),
 }
 
 impl<AccountId> Default for RedeemType<AccountId> {
@@ -230,6 +234,63 @@ impl<AccountId> Default for RedeemType<AccountId> {
 	}
 }
 
+// For swap-router
+/// A swap through a single venue.
+#[derive(
+	Encode, Decode, Eq, PartialEq, Copy, Clone, RuntimeDebug, TypeInfo, Serialize, Deserialize,
+)]
+pub enum SwapHop<CurrencyId> {
+	/// Through the Zenlink pair of the two currencies.
+	Zenlink {
+		currency_in: CurrencyId,
+		currency_out: CurrencyId,
+	},
+	/// Through the stable pool `pool_id`.
+	StablePool {
+		pool_id: u32,
+		currency_in: CurrencyId,
+		currency_out: CurrencyId,
+	},
+}
+
+impl<CurrencyId: Copy> SwapHop<CurrencyId> {
+	pub fn currency_in(&self) -> CurrencyId {
+		match self {
+			Self::Zenlink { currency_in, .. } | Self::StablePool { currency_in, .. } => {
+				*currency_in
+			}
+		}
+	}
+
+	pub fn currency_out(&self) -> CurrencyId {
+		match self {
+			Self::Zenlink { currency_out, .. } | Self::StablePool { currency_out, .. } => {
+				*currency_out
+			}
+		}
+	}
+}
+
+/// A route between two currencies together with its quote.
+#[derive(Encode, Decode, Eq, PartialEq, Clone, RuntimeDebug, TypeInfo, Serialize, Deserialize)]
+pub struct SwapRoute<CurrencyId, Balance> {
+	/// The hops, in execution order.
+	pub hops: Vec<SwapHop<CurrencyId>>,
+	/// The quoted amounts: `amounts[0]` goes into the first hop and `amounts[k + 1]` comes out
+	/// of hop `k`.
+	pub amounts: Vec<Balance>,
+}
+
+impl<CurrencyId, Balance: Copy + Default> SwapRoute<CurrencyId, Balance> {
+	pub fn amount_in(&self) -> Balance {
+		self.amounts.first().copied().unwrap_or_default()
+	}
+
+	pub fn amount_out(&self) -> Balance {
+		self.amounts.last().copied().unwrap_or_default()
+	}
+}
+
 #[derive(Encode, Decode, Eq, PartialEq, Copy, Clone, RuntimeDebug, TypeInfo)]
 pub enum XcmOperationType {
 	// SALP operations
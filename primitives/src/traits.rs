@@ -21,8 +21,8 @@
 #![allow(clippy::unnecessary_cast)]
 
 use crate::{
-	AssetIds, CurrencyId, DerivativeIndex, LeasePeriod, ParaId, PoolId, RedeemType, TokenId,
-	TokenSymbol, XcmOperationType,
+	AssetIds, CurrencyId, DerivativeIndex, LeasePeriod, ParaId, PoolId, Price, RedeemType,
+	SwapRoute, TimeUnit, TokenId, TokenSymbol, XcmOperationType,
 };
 use frame_support::pallet_prelude::{DispatchResultWithPostInfo, Weight};
 use parity_scale_codec::{Decode, Encode, FullCodec};
@@ -256,6 +256,7 @@ pub trait VtokenMintingInterface<AccountId, CurrencyId, Balance> {
 		vtoken_amount: Balance,
 		redeem: RedeemType<AccountId>,
 	) -> DispatchResultWithPostInfo;
+	fn rebond(exchanger: AccountId, token_id: CurrencyId, token_amount: Balance) -> DispatchResult;
 	fn get_v_currency_amount_by_currency_amount(
 		token_id: CurrencyId,
 		vtoken_id: CurrencyId,
@@ -269,6 +270,11 @@ pub trait VtokenMintingInterface<AccountId, CurrencyId, Balance> {
 	fn get_token_pool(currency_id: CurrencyId) -> Balance;
 	fn get_minimums_redeem(vtoken_id: CurrencyId) -> Balance;
 	fn get_moonbeam_parachain_id() -> u32;
+	/// The pending unlocks of `exchanger` for `token_id`, as (unlock id, amount, unlock time).
+	fn get_user_unlock_queue(
+		exchanger: AccountId,
+		token_id: CurrencyId,
+	) -> Vec<(u32, Balance, TimeUnit)>;
 }
 
 impl<AccountId, CurrencyId, Balance: Zero> VtokenMintingInterface<AccountId, CurrencyId, Balance>
@@ -301,6 +307,14 @@ impl<AccountId, CurrencyId, Balance: Zero> VtokenMintingInterface<AccountId, Cur
 		Ok(().into())
 	}
 
+	fn rebond(
+		_exchanger: AccountId,
+		_token_id: CurrencyId,
+		_token_amount: Balance,
+	) -> DispatchResult {
+		Ok(())
+	}
+
 	fn get_v_currency_amount_by_currency_amount(
 		_token_id: CurrencyId,
 		_vtoken_id: CurrencyId,
@@ -328,6 +342,13 @@ impl<AccountId, CurrencyId, Balance: Zero> VtokenMintingInterface<AccountId, Cur
 	fn get_moonbeam_parachain_id() -> u32 {
 		0
 	}
+
+	fn get_user_unlock_queue(
+		_exchanger: AccountId,
+		_token_id: CurrencyId,
+	) -> Vec<(u32, Balance, TimeUnit)> {
+		Vec::new()
+	}
 }
 
 pub trait TryConvertFrom<CurrencyId> {
@@ -477,6 +498,118 @@ pub trait PriceProvider {
 	fn get_price(asset_a: CurrencyId, asset_b: CurrencyId) -> Option<Self::Price>;
 }
 
+/// Provides the reserves backing a stable pool lp token.
+pub trait StableLpTokenProvider<CurrencyId, Balance> {
+	/// Returns the assets held by the pool of `lp_token` with their amounts,
+	/// and the total issuance of `lp_token`.
+	fn get_pool_reserves(lp_token: CurrencyId) -> Option<(Vec<(CurrencyId, Balance)>, Balance)>;
+}
+
+impl<CurrencyId, Balance> StableLpTokenProvider<CurrencyId, Balance> for () {
+	fn get_pool_reserves(_lp_token: CurrencyId) -> Option<(Vec<(CurrencyId, Balance)>, Balance)> {
+		None
+	}
+}
+
+/// Provides the time-weighted average prices of the stable pools.
+pub trait StableTwapProvider<CurrencyId, BlockNumber> {
+	/// Returns the average price of `currency_in` in `currency_out` in the pool `pool_id`
+	/// over the last `window` blocks, both in their smallest units.
+	fn get_twap(
+		pool_id: u32,
+		currency_in: CurrencyId,
+		currency_out: CurrencyId,
+		window: BlockNumber,
+	) -> Option<Price>;
+}
+
+impl<CurrencyId, BlockNumber> StableTwapProvider<CurrencyId, BlockNumber> for () {
+	fn get_twap(
+		_pool_id: u32,
+		_currency_in: CurrencyId,
+		_currency_out: CurrencyId,
+		_window: BlockNumber,
+	) -> Option<Price> {
+		None
+	}
+}
+
+/// Finds and executes the best swap route between two currencies, across the Zenlink pairs and
+/// the stable pools.
+pub trait SwapRouter<AccountId, CurrencyId, Balance> {
+	/// The route giving the most `currency_out` for `amount_in` of `currency_in`.
+	fn quote_exact_in(
+		currency_in: CurrencyId,
+		currency_out: CurrencyId,
+		amount_in: Balance,
+	) -> Result<SwapRoute<CurrencyId, Balance>, DispatchError>;
+
+	/// The route needing the least `currency_in` to get `amount_out` of `currency_out`.
+	fn quote_exact_out(
+		currency_in: CurrencyId,
+		currency_out: CurrencyId,
+		amount_out: Balance,
+	) -> Result<SwapRoute<CurrencyId, Balance>, DispatchError>;
+
+	/// Swaps `amount_in` along the best route and returns the amount received, failing if it is
+	/// below `min_amount_out`.
+	fn swap_exact_in(
+		who: &AccountId,
+		currency_in: CurrencyId,
+		currency_out: CurrencyId,
+		amount_in: Balance,
+		min_amount_out: Balance,
+	) -> Result<Balance, DispatchError>;
+
+	/// Swaps along the best route to receive `amount_out` and returns the amount paid, failing if
+	/// it is above `max_amount_in`.
+	fn swap_exact_out(
+		who: &AccountId,
+		currency_in: CurrencyId,
+		currency_out: CurrencyId,
+		amount_out: Balance,
+		max_amount_in: Balance,
+	) -> Result<Balance, DispatchError>;
+}
+
+impl<AccountId, CurrencyId, Balance> SwapRouter<AccountId, CurrencyId, Balance> for () {
+	fn quote_exact_in(
+		_currency_in: CurrencyId,
+		_currency_out: CurrencyId,
+		_amount_in: Balance,
+	) -> Result<SwapRoute<CurrencyId, Balance>, DispatchError> {
+		Err(DispatchError::Other("No swap route."))
+	}
+
+	fn quote_exact_out(
+		_currency_in: CurrencyId,
+		_currency_out: CurrencyId,
+		_amount_out: Balance,
+	) -> Result<SwapRoute<CurrencyId, Balance>, DispatchError> {
+		Err(DispatchError::Other("No swap route."))
+	}
+
+	fn swap_exact_in(
+		_who: &AccountId,
+		_currency_in: CurrencyId,
+		_currency_out: CurrencyId,
+		_amount_in: Balance,
+		_min_amount_out: Balance,
+	) -> Result<Balance, DispatchError> {
+		Err(DispatchError::Other("No swap route."))
+	}
+
+	fn swap_exact_out(
+		_who: &AccountId,
+		_currency_in: CurrencyId,
+		_currency_out: CurrencyId,
+		_amount_out: Balance,
+		_max_amount_in: Balance,
+	) -> Result<Balance, DispatchError> {
+		Err(DispatchError::Other("No swap route."))
+	}
+}
+
 /// A trait for comparing the balance of a specific currency for a given account.
 pub trait BalanceCmp<AccountId> {
 	type Error;
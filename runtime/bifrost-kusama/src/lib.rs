@@ -135,7 +135,7 @@ use governance::{
 
 // xcm config
 pub mod xcm_config;
-use bifrost_primitives::{MoonriverChainId, OraclePriceProvider};
+use bifrost_primitives::{MoonriverChainId, OraclePriceProvider, SwapRoute, SwapRouter as _};
 use bifrost_runtime_common::currency_converter::CurrencyIdConvert;
 use pallet_xcm::{EnsureResponse, QueryStatus};
 use sp_runtime::traits::{IdentityLookup, Verify};
@@ -1287,6 +1287,7 @@ parameter_types! {
 	pub const MaxTypeEntryPerBlock: u32 = 10;
 	pub const MaxRefundPerBlock: u32 = 10;
 	pub const MaxLengthLimit: u32 = 500;
+	pub const XcmQueryTimeout: BlockNumber = 1000;
 }
 
 pub struct SubstrateResponseManager;
@@ -1348,6 +1349,7 @@ impl bifrost_slp::Config for Runtime {
 	type AssetIdMaps = AssetIdMaps<Runtime>;
 	type TreasuryAccount = BifrostTreasuryAccount;
 	type BlockNumberProvider = System;
+	type XcmQueryTimeout = XcmQueryTimeout;
 }
 
 impl bifrost_vstoken_conversion::Config for Runtime {
@@ -1363,6 +1365,8 @@ impl bifrost_vstoken_conversion::Config for Runtime {
 
 parameter_types! {
 	pub const WhitelistMaximumLimit: u32 = 10;
+	pub const FarmingMaxPositionLock: BlockNumber = 365 * DAYS;
+	pub const FarmingMaxPositionBoost: Perbill = Perbill::from_percent(100);
 }
 
 impl bifrost_farming::Config for Runtime {
@@ -1380,6 +1384,8 @@ impl bifrost_farming::Config for Runtime {
 	type WhitelistMaximumLimit = WhitelistMaximumLimit;
 	type GaugeRewardIssuer = FarmingGaugeRewardIssuerPalletId;
 	type BlockNumberProvider = System;
+	type MaxPositionLock = FarmingMaxPositionLock;
+	type MaxPositionBoost = FarmingMaxPositionBoost;
 }
 
 parameter_types! {
@@ -1608,6 +1614,10 @@ impl bifrost_stable_asset::traits::ValidateAssetId<CurrencyId> for EnsurePoolAss
 	}
 }
 
+parameter_types! {
+	pub const StablePriceObservationPeriod: BlockNumber = HOURS;
+}
+
 /// Configure the pallet bifrost_stable_asset in pallets/bifrost_stable_asset.
 impl bifrost_stable_asset::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
@@ -1624,6 +1634,8 @@ impl bifrost_stable_asset::Config for Runtime {
 	type ListingOrigin = TechAdminOrCouncil;
 	type EnsurePoolAssetId = EnsurePoolAssetId;
 	type BlockNumberProvider = System;
+	type PriceObservationPeriod = StablePriceObservationPeriod;
+	type MaxPriceObservations = ConstU32<24>;
 }
 
 impl bifrost_stable_pool::Config for Runtime {
@@ -1637,6 +1649,23 @@ impl bifrost_stable_pool::Config for Runtime {
 	type CurrencyIdRegister = AssetIdMaps<Runtime>;
 }
 
+parameter_types! {
+	pub const SwapRouterMaxHops: u32 = 3;
+	pub const SwapRouterMaxRouteAssets: u32 = 8;
+}
+
+impl bifrost_swap_router::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type MultiCurrency = Currencies;
+	type ControlOrigin = TechAdminOrCouncil;
+	type WeightInfo = weights::bifrost_swap_router::BifrostWeight<Runtime>;
+	type DexOperator = ZenlinkProtocol;
+	type StablePoolHandler = StablePool;
+	type ParachainId = ParachainInfo;
+	type MaxHops = SwapRouterMaxHops;
+	type MaxRouteAssets = SwapRouterMaxRouteAssets;
+}
+
 parameter_types! {
 	pub const MinimumCount: u32 = 3;
 	pub const ExpiresIn: Moment = 1000 * 60 * 60; // 60 mins
@@ -1703,6 +1732,11 @@ impl pallet_prices::Config for Runtime {
 	type RelayCurrency = RelayCurrencyId;
 	type CurrencyIdConvert = AssetIdMaps<Runtime>;
 	type Assets = Currencies;
+	type VtokenMinting = VtokenMinting;
+	type LendMarket = LendMarket;
+	type StableLpToken = StableAsset;
+	type UnixTime = Timestamp;
+	type StableTwap = StableAsset;
 	type WeightInfo = pallet_prices::weights::SubstrateWeight<Runtime>;
 }
 
@@ -1719,6 +1753,7 @@ impl lend_market::Config for Runtime {
 	type LiquidationFreeAssetId = RelayCurrencyId;
 	type MaxLengthLimit = MaxLengthLimit;
 	type BlockNumberProvider = System;
+	type VTokenSupplyProvider = VtokenMinting;
 }
 
 parameter_types! {
@@ -1957,6 +1992,7 @@ construct_runtime! {
 		LeverageStaking: leverage_staking = 135,
 		ChannelCommission: bifrost_channel_commission = 136,
 		VBNCConvert: bifrost_vbnc_convert = 140,
+		SwapRouter: bifrost_swap_router = 141,
 	}
 }
 
@@ -2027,6 +2063,8 @@ pub mod migrations {
 		// permanent migration, do not remove
 		pallet_xcm::migration::MigrateToLatestXcmVersion<Runtime>,
 		bifrost_channel_commission::migrations::v1::MigrateToV1<Runtime>,
+		lend_market::migrations::v2::MigrateToV2<Runtime>,
+		bifrost_slpx::migration::v3::MigrateToV3<Runtime>,
 	);
 }
 
@@ -2054,6 +2092,7 @@ mod benches {
 		[bifrost_slp, Slp]
 		[bifrost_slpx, Slpx]
 		[bifrost_stable_pool, StablePool]
+		[bifrost_swap_router, SwapRouter]
 		[bifrost_system_staking, SystemStaking]
 		[bifrost_token_issuer, TokenIssuer]
 		[bifrost_vstoken_conversion, VstokenConversion]
@@ -2061,6 +2100,7 @@ mod benches {
 		[bifrost_vtoken_voting, VtokenVoting]
 		[lend_market, LendMarket]
 		[leverage_staking, LeverageStaking]
+		[pallet_prices, Prices]
 		[bifrost_vbnc_convert, VBNCConvert]
 		[bifrost_xcm_interface, XcmInterface]
 		// [bifrost_channel_commission, ChannelCommission]
@@ -2390,6 +2430,14 @@ impl_runtime_apis! {
 		fn get_gauge_rewards(who: AccountId, pid: PoolId) -> Vec<(CurrencyId, Balance)> {
 			Farming::get_gauge_rewards(&who, pid).unwrap_or(Vec::new())
 		}
+
+		fn get_user_positions(who: AccountId) -> Vec<(u64, PoolId, Balance, Vec<(CurrencyId, Balance)>)> {
+			Farming::get_user_positions(&who)
+		}
+
+		fn get_farming_boost(who: AccountId, pid: PoolId) -> u32 {
+			Farming::get_farming_boost(&who, pid).deconstruct()
+		}
 	}
 
 	impl bifrost_stable_pool_rpc_runtime_api::StablePoolRuntimeApi<Block> for Runtime {
@@ -2410,6 +2458,24 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl bifrost_swap_router_rpc_runtime_api::SwapRouterRuntimeApi<Block> for Runtime {
+		fn quote_exact_in(
+			currency_in: CurrencyId,
+			currency_out: CurrencyId,
+			amount_in: Balance,
+		) -> Option<SwapRoute<CurrencyId, Balance>> {
+			SwapRouter::quote_exact_in(currency_in, currency_out, amount_in).ok()
+		}
+
+		fn quote_exact_out(
+			currency_in: CurrencyId,
+			currency_out: CurrencyId,
+			amount_out: Balance,
+		) -> Option<SwapRoute<CurrencyId, Balance>> {
+			SwapRouter::quote_exact_out(currency_in, currency_out, amount_out).ok()
+		}
+	}
+
 	impl lend_market_rpc_runtime_api::LendMarketApi<Block, AccountId, Balance> for Runtime {
 		fn get_account_liquidity(account: AccountId) -> Result<(Liquidity, Shortfall, Liquidity, Shortfall), DispatchError> {
 			LendMarket::get_account_liquidity(&account)
@@ -2422,6 +2488,16 @@ impl_runtime_apis! {
 		fn get_liquidation_threshold_liquidity(account: AccountId) -> Result<(Liquidity, Shortfall, Liquidity, Shortfall), DispatchError> {
 			LendMarket::get_account_liquidation_threshold_liquidity(&account)
 		}
+
+		fn get_liquidatable_accounts(start_key: Option<Vec<u8>>, limit: u32) -> (Vec<AccountId>, Option<Vec<u8>>) {
+			LendMarket::get_liquidatable_accounts(start_key, limit)
+		}
+	}
+
+	impl leverage_staking_rpc_runtime_api::LeverageStakingApi<Block, AccountId, Balance> for Runtime {
+		fn preview_flash_loan_deposit(account: AccountId, asset_id: CurrencyId, rate: Rate) -> Result<(Balance, Balance, sp_runtime::FixedU128), DispatchError> {
+			LeverageStaking::preview_flash_loan_deposit(&account, asset_id, rate)
+		}
 	}
 
 	impl bifrost_vtoken_minting_rpc_runtime_api::VtokenMintingRuntimeApi<Block, CurrencyId, Balance> for Runtime {
@@ -2434,6 +2510,22 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl bifrost_slp_rpc_runtime_api::SlpRuntimeApi<Block, bifrost_slp::LedgerUpdateEntry<Balance>, bifrost_slp::ValidatorsByDelegatorUpdateEntry, BlockNumber> for Runtime {
+		fn get_pending_ledger_queries() -> Vec<(bifrost_slp::QueryId, bifrost_slp::LedgerUpdateEntry<Balance>, BlockNumber)> {
+			Slp::get_pending_ledger_queries()
+		}
+
+		fn get_pending_validators_queries() -> Vec<(bifrost_slp::QueryId, bifrost_slp::ValidatorsByDelegatorUpdateEntry, BlockNumber)> {
+			Slp::get_pending_validators_queries()
+		}
+	}
+
+	impl bifrost_slpx_rpc_runtime_api::SlpxRuntimeApi<Block, AccountId, bifrost_slpx::types::Order<AccountId, CurrencyId, Balance, BlockNumber>> for Runtime {
+		fn get_pending_orders(account: AccountId) -> Vec<bifrost_slpx::types::Order<AccountId, CurrencyId, Balance, BlockNumber>> {
+			Slpx::get_pending_orders(account)
+		}
+	}
+
 	#[cfg(feature = "runtime-benchmarks")]
 	impl frame_benchmarking::Benchmark<Block> for Runtime {
 		fn benchmark_metadata(extra: bool) -> (
@@ -0,0 +1,477 @@
+// This file is part of Bifrost.
+
+// Copyright (C) Liebi Technologies PTE. LTD.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::evm::{
+	precompiles::{
+		erc20_mapping::{BifrostErc20Mapping, Erc20Mapping},
+		handle::{EvmDataWriter, FunctionModifier, PrecompileHandleExt},
+		revert,
+		substrate::RuntimeHelper,
+		succeed, Address, EvmResult,
+	},
+	ExtendedAddressMapping,
+};
+use bifrost_primitives::{Balance, CurrencyId};
+use frame_support::{dispatch::PostDispatchInfo, traits::fungibles::Inspect};
+use lend_market::{AccountDeposits, ExchangeRate, Markets, WeightInfo};
+use module_evm_utility_macro::keccak256;
+use num_enum::{IntoPrimitive, TryFromPrimitive};
+use pallet_evm::{AddressMapping, Precompile, PrecompileHandle, PrecompileResult};
+use primitive_types::{H160, H256};
+use sp_runtime::{traits::Dispatchable, DispatchError, RuntimeDebug};
+use sp_std::{marker::PhantomData, prelude::*};
+
+/// Topic of the `Deposited` log.
+pub const SELECTOR_LOG_DEPOSITED: &[u8; 32] = keccak256!("Deposited(address,address,uint256)");
+
+/// Topic of the `Redeemed` log.
+pub const SELECTOR_LOG_REDEEMED: &[u8; 32] = keccak256!("Redeemed(address,address,uint256)");
+
+/// Topic of the `Borrowed` log.
+pub const SELECTOR_LOG_BORROWED: &[u8; 32] = keccak256!("Borrowed(address,address,uint256)");
+
+/// Topic of the `RepaidBorrow` log.
+pub const SELECTOR_LOG_REPAID_BORROW: &[u8; 32] =
+	keccak256!("RepaidBorrow(address,address,uint256)");
+
+/// Topic of the `CollateralAssetAdded` log.
+pub const SELECTOR_LOG_COLLATERAL_ASSET_ADDED: &[u8; 32] =
+	keccak256!("CollateralAssetAdded(address,address)");
+
+/// Topic of the `CollateralAssetRemoved` log.
+pub const SELECTOR_LOG_COLLATERAL_ASSET_REMOVED: &[u8; 32] =
+	keccak256!("CollateralAssetRemoved(address,address)");
+
+/// Topic of the `LiquidatedBorrow` log.
+pub const SELECTOR_LOG_LIQUIDATED_BORROW: &[u8; 32] =
+	keccak256!("LiquidatedBorrow(address,address,address,address,uint256,uint256)");
+
+#[module_evm_utility_macro::generate_function_selector]
+#[derive(RuntimeDebug, Eq, PartialEq, TryFromPrimitive, IntoPrimitive)]
+#[repr(u32)]
+pub enum Action {
+	Mint = "mint(address,uint256)",
+	Redeem = "redeem(address,uint256)",
+	Borrow = "borrow(address,uint256)",
+	RepayBorrow = "repayBorrow(address,uint256)",
+	CollateralAsset = "collateralAsset(address,bool)",
+	LiquidateBorrow = "liquidateBorrow(address,address,uint256,address)",
+	GetAccountLiquidity = "getAccountLiquidity(address)",
+	GetMarketStatus = "getMarketStatus(address)",
+}
+
+/// Precompile exposing the supply, borrow and liquidation calls of `lend_market` to the EVM.
+///
+/// Assets are identified by their ERC-20 mapped addresses. Every call is dispatched with a signed
+/// origin of the caller, so the same checks apply as for a substrate extrinsic, including the
+/// runtime call filter, and a log mirroring the pallet event is emitted on success.
+pub struct LendMarketPrecompile<Runtime>(PhantomData<Runtime>);
+
+impl<Runtime> Precompile for LendMarketPrecompile<Runtime>
+where
+	Runtime: frame_system::Config + pallet_evm::Config + lend_market::Config,
+	<Runtime as frame_system::Config>::AccountId: core::convert::From<sp_runtime::AccountId32>,
+	<Runtime as frame_system::Config>::RuntimeCall:
+		From<lend_market::Call<Runtime>> + Dispatchable<PostInfo = PostDispatchInfo>,
+{
+	fn execute(handle: &mut impl PrecompileHandle) -> PrecompileResult {
+		let selector = handle.read_selector()?;
+
+		handle.check_function_modifier(match selector {
+			Action::GetAccountLiquidity | Action::GetMarketStatus => FunctionModifier::View,
+			_ => FunctionModifier::NonPayable,
+		})?;
+
+		match selector {
+			Action::Mint => Self::mint(handle),
+			Action::Redeem => Self::redeem(handle),
+			Action::Borrow => Self::borrow(handle),
+			Action::RepayBorrow => Self::repay_borrow(handle),
+			Action::CollateralAsset => Self::collateral_asset(handle),
+			Action::LiquidateBorrow => Self::liquidate_borrow(handle),
+			Action::GetAccountLiquidity => Self::get_account_liquidity(handle),
+			Action::GetMarketStatus => Self::get_market_status(handle),
+		}
+	}
+}
+
+impl<Runtime> LendMarketPrecompile<Runtime>
+where
+	Runtime: frame_system::Config + pallet_evm::Config + lend_market::Config,
+	<Runtime as frame_system::Config>::AccountId: core::convert::From<sp_runtime::AccountId32>,
+	<Runtime as frame_system::Config>::RuntimeCall:
+		From<lend_market::Call<Runtime>> + Dispatchable<PostInfo = PostDispatchInfo>,
+{
+	/// Supplies `amount` of the underlying asset and returns the minted voucher amount.
+	fn mint(handle: &mut impl PrecompileHandle) -> PrecompileResult {
+		handle.record_cost(RuntimeHelper::<Runtime>::weight_to_gas(
+			<Runtime as lend_market::Config>::WeightInfo::mint(),
+		))?;
+		handle.record_log_costs_manual(3, 32)?;
+
+		// Parse input
+		let mut input = handle.read_input()?;
+		input.expect_arguments(2)?;
+
+		let asset = input.read::<Address>()?;
+		let asset_id = Self::read_currency_id(asset)?;
+		let amount = input.read::<Balance>()?;
+
+		let caller = handle.context().caller;
+		let who = Self::account_id(caller);
+
+		log::debug!(target: "evm", "lend-market: mint supplier: {:?}, asset: {:?}, amount: {:?}", who, asset_id, amount);
+
+		let vouchers_before = AccountDeposits::<Runtime>::get(asset_id, &who).voucher_balance;
+		RuntimeHelper::<Runtime>::try_dispatch(
+			who.clone(),
+			lend_market::Call::<Runtime>::mint {
+				asset_id,
+				mint_amount: amount,
+			},
+		)?;
+		let vouchers_after = AccountDeposits::<Runtime>::get(asset_id, &who).voucher_balance;
+
+		Self::log_position(handle, SELECTOR_LOG_DEPOSITED, caller, asset.into(), amount)?;
+
+		Ok(succeed(
+			EvmDataWriter::new()
+				.write(vouchers_after.saturating_sub(vouchers_before))
+				.build(),
+		))
+	}
+
+	fn redeem(handle: &mut impl PrecompileHandle) -> PrecompileResult {
+		handle.record_cost(RuntimeHelper::<Runtime>::weight_to_gas(
+			<Runtime as lend_market::Config>::WeightInfo::redeem(),
+		))?;
+		handle.record_log_costs_manual(3, 32)?;
+
+		// Parse input
+		let mut input = handle.read_input()?;
+		input.expect_arguments(2)?;
+
+		let asset = input.read::<Address>()?;
+		let asset_id = Self::read_currency_id(asset)?;
+		let amount = input.read::<Balance>()?;
+
+		let caller = handle.context().caller;
+		let who = Self::account_id(caller);
+
+		log::debug!(target: "evm", "lend-market: redeem supplier: {:?}, asset: {:?}, amount: {:?}", who, asset_id, amount);
+
+		// The pallet reports the underlying amount actually paid out.
+		let balance_before = <Runtime as lend_market::Config>::Assets::balance(asset_id, &who);
+		RuntimeHelper::<Runtime>::try_dispatch(
+			who.clone(),
+			lend_market::Call::<Runtime>::redeem {
+				asset_id,
+				redeem_amount: amount,
+			},
+		)?;
+		let balance_after = <Runtime as lend_market::Config>::Assets::balance(asset_id, &who);
+
+		Self::log_position(
+			handle,
+			SELECTOR_LOG_REDEEMED,
+			caller,
+			asset.into(),
+			balance_after.saturating_sub(balance_before),
+		)?;
+
+		Ok(succeed(EvmDataWriter::new().write(true).build()))
+	}
+
+	fn borrow(handle: &mut impl PrecompileHandle) -> PrecompileResult {
+		handle.record_cost(RuntimeHelper::<Runtime>::weight_to_gas(
+			<Runtime as lend_market::Config>::WeightInfo::borrow(),
+		))?;
+		handle.record_log_costs_manual(3, 32)?;
+
+		// Parse input
+		let mut input = handle.read_input()?;
+		input.expect_arguments(2)?;
+
+		let asset = input.read::<Address>()?;
+		let asset_id = Self::read_currency_id(asset)?;
+		let amount = input.read::<Balance>()?;
+
+		let caller = handle.context().caller;
+		let who = Self::account_id(caller);
+
+		log::debug!(target: "evm", "lend-market: borrow borrower: {:?}, asset: {:?}, amount: {:?}", who, asset_id, amount);
+
+		RuntimeHelper::<Runtime>::try_dispatch(
+			who,
+			lend_market::Call::<Runtime>::borrow {
+				asset_id,
+				borrow_amount: amount,
+			},
+		)?;
+
+		Self::log_position(handle, SELECTOR_LOG_BORROWED, caller, asset.into(), amount)?;
+
+		Ok(succeed(EvmDataWriter::new().write(true).build()))
+	}
+
+	fn repay_borrow(handle: &mut impl PrecompileHandle) -> PrecompileResult {
+		handle.record_cost(RuntimeHelper::<Runtime>::weight_to_gas(
+			<Runtime as lend_market::Config>::WeightInfo::repay_borrow(),
+		))?;
+		handle.record_log_costs_manual(3, 32)?;
+
+		// Parse input
+		let mut input = handle.read_input()?;
+		input.expect_arguments(2)?;
+
+		let asset = input.read::<Address>()?;
+		let asset_id = Self::read_currency_id(asset)?;
+		let amount = input.read::<Balance>()?;
+
+		let caller = handle.context().caller;
+		let who = Self::account_id(caller);
+
+		log::debug!(target: "evm", "lend-market: repay_borrow borrower: {:?}, asset: {:?}, amount: {:?}", who, asset_id, amount);
+
+		RuntimeHelper::<Runtime>::try_dispatch(
+			who,
+			lend_market::Call::<Runtime>::repay_borrow {
+				asset_id,
+				repay_amount: amount,
+			},
+		)?;
+
+		Self::log_position(
+			handle,
+			SELECTOR_LOG_REPAID_BORROW,
+			caller,
+			asset.into(),
+			amount,
+		)?;
+
+		Ok(succeed(EvmDataWriter::new().write(true).build()))
+	}
+
+	fn collateral_asset(handle: &mut impl PrecompileHandle) -> PrecompileResult {
+		handle.record_cost(RuntimeHelper::<Runtime>::weight_to_gas(
+			<Runtime as lend_market::Config>::WeightInfo::collateral_asset(),
+		))?;
+		handle.record_log_costs_manual(3, 0)?;
+
+		// Parse input
+		let mut input = handle.read_input()?;
+		input.expect_arguments(2)?;
+
+		let asset = input.read::<Address>()?;
+		let asset_id = Self::read_currency_id(asset)?;
+		let enable = input.read::<bool>()?;
+
+		let caller = handle.context().caller;
+		let who = Self::account_id(caller);
+
+		log::debug!(target: "evm", "lend-market: collateral_asset supplier: {:?}, asset: {:?}, enable: {:?}", who, asset_id, enable);
+
+		RuntimeHelper::<Runtime>::try_dispatch(
+			who,
+			lend_market::Call::<Runtime>::collateral_asset { asset_id, enable },
+		)?;
+
+		let selector = if enable {
+			SELECTOR_LOG_COLLATERAL_ASSET_ADDED
+		} else {
+			SELECTOR_LOG_COLLATERAL_ASSET_REMOVED
+		};
+		let address = handle.code_address();
+		handle.log(
+			address,
+			vec![
+				H256::from(*selector),
+				H256::from(caller),
+				H256::from(asset.0),
+			],
+			vec![],
+		)?;
+
+		Ok(succeed(EvmDataWriter::new().write(true).build()))
+	}
+
+	/// Repays `repayAmount` of the borrower's debt and returns the seized collateral, denominated
+	/// in the underlying collateral asset.
+	fn liquidate_borrow(handle: &mut impl PrecompileHandle) -> PrecompileResult {
+		handle.record_cost(RuntimeHelper::<Runtime>::weight_to_gas(
+			<Runtime as lend_market::Config>::WeightInfo::liquidate_borrow(),
+		))?;
+		handle.record_log_costs_manual(3, 32 * 4)?;
+
+		// Parse input
+		let mut input = handle.read_input()?;
+		input.expect_arguments(4)?;
+
+		let borrower: H160 = input.read::<Address>()?.into();
+		let liquidation_asset = input.read::<Address>()?;
+		let liquidation_asset_id = Self::read_currency_id(liquidation_asset)?;
+		let repay_amount = input.read::<Balance>()?;
+		let collateral_asset = input.read::<Address>()?;
+		let collateral_asset_id = Self::read_currency_id(collateral_asset)?;
+
+		let caller = handle.context().caller;
+		let who = Self::account_id(caller);
+		let borrower_account = Self::account_id(borrower);
+
+		log::debug!(target: "evm", "lend-market: liquidate_borrow liquidator: {:?}, borrower: {:?}, liquidation asset: {:?}, repay amount: {:?}, collateral asset: {:?}", who, borrower_account, liquidation_asset_id, repay_amount, collateral_asset_id);
+
+		let vouchers_before =
+			AccountDeposits::<Runtime>::get(collateral_asset_id, &borrower_account).voucher_balance;
+		RuntimeHelper::<Runtime>::try_dispatch(
+			who,
+			lend_market::Call::<Runtime>::liquidate_borrow {
+				borrower: borrower_account.clone(),
+				liquidation_asset_id,
+				repay_amount,
+				collateral_asset_id,
+			},
+		)?;
+		let vouchers_after =
+			AccountDeposits::<Runtime>::get(collateral_asset_id, &borrower_account).voucher_balance;
+
+		let collateral_amount = lend_market::Pallet::<Runtime>::calc_underlying_amount(
+			vouchers_before.saturating_sub(vouchers_after),
+			ExchangeRate::<Runtime>::get(collateral_asset_id),
+		)
+		.map_err(Self::dispatch_error)?;
+
+		let address = handle.code_address();
+		handle.log(
+			address,
+			vec![
+				H256::from(*SELECTOR_LOG_LIQUIDATED_BORROW),
+				H256::from(caller),
+				H256::from(borrower),
+			],
+			EvmDataWriter::new()
+				.write(liquidation_asset)
+				.write(collateral_asset)
+				.write(repay_amount)
+				.write(collateral_amount)
+				.build(),
+		)?;
+
+		Ok(succeed(
+			EvmDataWriter::new().write(collateral_amount).build(),
+		))
+	}
+
+	/// Returns `(liquidity, shortfall, lfLiquidity, lfShortfall)` of an account, as 18 decimals
+	/// fixed point values.
+	fn get_account_liquidity(handle: &mut impl PrecompileHandle) -> PrecompileResult {
+		// Parse input
+		let mut input = handle.read_input()?;
+		input.expect_arguments(1)?;
+
+		let who = Self::account_id(input.read::<Address>()?.into());
+
+		// Markets, AccountDeposits, AccountBorrows, BorrowIndex, ExchangeRate and the oracle price
+		// per market. The cost of a market is recorded before the next one is read, so the scan
+		// stops as soon as the gas runs out.
+		handle.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost())?;
+		for _ in Markets::<Runtime>::iter_keys() {
+			handle.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost().saturating_mul(6))?;
+		}
+
+		let (liquidity, shortfall, lf_liquidity, lf_shortfall) =
+			lend_market::Pallet::<Runtime>::get_account_liquidity(&who)
+				.map_err(Self::dispatch_error)?;
+
+		Ok(succeed(
+			EvmDataWriter::new()
+				.write(liquidity.into_inner())
+				.write(shortfall.into_inner())
+				.write(lf_liquidity.into_inner())
+				.write(lf_shortfall.into_inner())
+				.build(),
+		))
+	}
+
+	/// Returns `(borrowRate, supplyRate, exchangeRate, utilization, totalBorrows, totalReserves,
+	/// borrowIndex)` of a market. Rates and the borrow index are 18 decimals fixed point values and
+	/// the utilization is in parts per million.
+	fn get_market_status(handle: &mut impl PrecompileHandle) -> PrecompileResult {
+		// Markets, TotalSupply, TotalBorrows, TotalReserves, BorrowIndex,
+		// LastAccruedInterestTime and the pallet account balance
+		handle.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost().saturating_mul(7))?;
+
+		// Parse input
+		let mut input = handle.read_input()?;
+		input.expect_arguments(1)?;
+
+		let asset_id = Self::read_currency_id(input.read::<Address>()?)?;
+
+		let (
+			borrow_rate,
+			supply_rate,
+			exchange_rate,
+			utilization,
+			total_borrows,
+			total_reserves,
+			borrow_index,
+		) = lend_market::Pallet::<Runtime>::get_market_status(asset_id)
+			.map_err(Self::dispatch_error)?;
+
+		Ok(succeed(
+			EvmDataWriter::new()
+				.write(borrow_rate.into_inner())
+				.write(supply_rate.into_inner())
+				.write(exchange_rate.into_inner())
+				.write(utilization.deconstruct())
+				.write(total_borrows)
+				.write(total_reserves)
+				.write(borrow_index.into_inner())
+				.build(),
+		))
+	}
+
+	fn log_position(
+		handle: &mut impl PrecompileHandle,
+		selector: &[u8; 32],
+		who: H160,
+		asset: H160,
+		amount: Balance,
+	) -> EvmResult {
+		let address = handle.code_address();
+		handle.log(
+			address,
+			vec![H256::from(*selector), H256::from(who), H256::from(asset)],
+			EvmDataWriter::new().write(amount).build(),
+		)?;
+
+		Ok(())
+	}
+
+	fn read_currency_id(address: Address) -> EvmResult<CurrencyId> {
+		BifrostErc20Mapping::decode_evm_address(address.into())
+			.ok_or_else(|| revert("invalid currency id"))
+	}
+
+	fn account_id(address: H160) -> Runtime::AccountId {
+		ExtendedAddressMapping::into_account_id(address).into()
+	}
+
+	fn dispatch_error(e: DispatchError) -> pallet_evm::PrecompileFailure {
+		revert(Into::<&str>::into(e))
+	}
+}
@@ -19,7 +19,9 @@
 use core::marker::PhantomData;
 
 use crate::evm::precompiles::{
-	erc20_mapping::is_asset_address, multicurrency::MultiCurrencyPrecompile,
+	erc20_mapping::is_asset_address, lend_market::LendMarketPrecompile,
+	multicurrency::MultiCurrencyPrecompile, stable_pool::StablePoolPrecompile,
+	vtoken_minting::VtokenMintingPrecompile,
 };
 use ethabi::Token;
 use frame_support::dispatch::{GetDispatchInfo, PostDispatchInfo};
@@ -40,8 +42,11 @@ use sp_std::{borrow::ToOwned, vec::Vec};
 pub mod costs;
 pub mod erc20_mapping;
 pub mod handle;
+pub mod lend_market;
 pub mod multicurrency;
+pub mod stable_pool;
 pub mod substrate;
+pub mod vtoken_minting;
 
 pub type EvmResult<T = ()> = Result<T, PrecompileFailure>;
 
@@ -91,6 +96,9 @@ pub const BN_ADD: H160 = H160(hex!("0000000000000000000000000000000000000006"));
 pub const BN_MUL: H160 = H160(hex!("0000000000000000000000000000000000000007"));
 pub const BN_PAIRING: H160 = H160(hex!("0000000000000000000000000000000000000008"));
 pub const BLAKE2F: H160 = H160(hex!("0000000000000000000000000000000000000009"));
+pub const VTOKEN_MINTING: H160 = H160(hex!("0000000000000000000000000000000000000801"));
+pub const LEND_MARKET: H160 = H160(hex!("0000000000000000000000000000000000000802"));
+pub const STABLE_POOL: H160 = H160(hex!("0000000000000000000000000000000000000803"));
 pub const CALLPERMIT: H160 = H160(hex!("000000000000000000000000000000000000080a"));
 
 pub const ETH_PRECOMPILE_END: H160 = BLAKE2F;
@@ -105,6 +113,9 @@ where
 	R::RuntimeCall: Dispatchable<PostInfo = PostDispatchInfo> + GetDispatchInfo + Decode,
 	<R::RuntimeCall as Dispatchable>::RuntimeOrigin: From<Option<R::AccountId>>,
 	MultiCurrencyPrecompile<R>: Precompile,
+	VtokenMintingPrecompile<R>: Precompile,
+	LendMarketPrecompile<R>: Precompile,
+	StablePoolPrecompile<R>: Precompile,
 {
 	fn execute(&self, handle: &mut impl PrecompileHandle) -> Option<PrecompileResult> {
 		let context = handle.context();
@@ -136,6 +147,12 @@ where
 			Some(Bn128Pairing::execute(handle))
 		} else if address == BLAKE2F {
 			Some(Blake2F::execute(handle))
+		} else if address == VTOKEN_MINTING {
+			Some(VtokenMintingPrecompile::<R>::execute(handle))
+		} else if address == LEND_MARKET {
+			Some(LendMarketPrecompile::<R>::execute(handle))
+		} else if address == STABLE_POOL {
+			Some(StablePoolPrecompile::<R>::execute(handle))
 		} else if address == CALLPERMIT {
 			Some(pallet_evm_precompile_call_permit::CallPermitPrecompile::<R>::execute(handle))
 		} else if address == DISPATCH_ADDR {
@@ -151,6 +168,9 @@ where
 
 	fn is_precompile(&self, address: H160, _remaining_gas: u64) -> IsPrecompileResult {
 		let is_precompile = address == DISPATCH_ADDR
+			|| address == VTOKEN_MINTING
+			|| address == LEND_MARKET
+			|| address == STABLE_POOL
 			|| is_asset_address(address)
 			|| is_standard_precompile(address);
 		IsPrecompileResult::Answer {
@@ -21,8 +21,9 @@ use crate::{
 		precompiles::{
 			erc20_mapping::{BifrostErc20Mapping, Erc20Mapping},
 			handle::{EvmDataWriter, FunctionModifier, PrecompileHandleExt},
+			revert,
 			substrate::RuntimeHelper,
-			succeed, Address, Output,
+			succeed, Address, EvmResult, Output,
 		},
 		ExtendedAddressMapping,
 	},
@@ -30,16 +31,93 @@ use crate::{
 };
 use bifrost_asset_registry::AssetIdMaps;
 use bifrost_primitives::{Balance, CurrencyId, CurrencyIdMapping};
-use frame_support::traits::OriginTrait;
+use frame_support::{
+	storage::types::{Key as NMapKey, StorageMap, StorageNMap, ValueQuery},
+	traits::{Get, OriginTrait, StorageInstance, Time},
+	Blake2_128Concat,
+};
+use module_evm_utility_macro::keccak256;
 use num_enum::{IntoPrimitive, TryFromPrimitive};
 use orml_traits::{MultiCurrency as MultiCurrencyT, MultiCurrency};
 use pallet_evm::{
 	AddressMapping, ExitRevert, Precompile, PrecompileFailure, PrecompileHandle, PrecompileResult,
 };
-use primitive_types::H160;
-use sp_runtime::{traits::Dispatchable, RuntimeDebug};
+use primitive_types::{H160, H256, U256};
+use sp_io::hashing::keccak_256;
+use sp_runtime::{
+	traits::{Dispatchable, UniqueSaturatedInto},
+	RuntimeDebug,
+};
 use sp_std::{marker::PhantomData, prelude::*};
 
+/// Topic of the ERC-20 `Transfer` log.
+pub const SELECTOR_LOG_TRANSFER: &[u8; 32] = keccak256!("Transfer(address,address,uint256)");
+
+/// Topic of the ERC-20 `Approval` log.
+pub const SELECTOR_LOG_APPROVAL: &[u8; 32] = keccak256!("Approval(address,address,uint256)");
+
+/// EIP-2612 permit typehash.
+pub const PERMIT_TYPEHASH: &[u8; 32] = keccak256!(
+	"Permit(address owner,address spender,uint256 value,uint256 nonce,uint256 deadline)"
+);
+
+/// EIP-712 permit domain used to compute an individualized domain separator.
+const PERMIT_DOMAIN: &[u8; 32] = keccak256!(
+	"EIP712Domain(string name,string version,uint256 chainId,address verifyingContract)"
+);
+
+/// Half the order of the secp256k1 curve. Signatures with a higher `s` are malleable (EIP-2).
+const SECP256K1_HALF_ORDER: H256 = H256(hex_literal::hex!(
+	"7fffffffffffffffffffffffffffffff5d576e7357a4501ddfe92f46681b20a0"
+));
+
+/// Storage prefix for the ERC-20 allowances.
+pub struct Allowances;
+
+impl StorageInstance for Allowances {
+	const STORAGE_PREFIX: &'static str = "Allowances";
+
+	fn pallet_prefix() -> &'static str {
+		"PrecompileMultiCurrency"
+	}
+}
+
+/// Storage type used to store the ERC-20 allowances. An allowance of `Balance::MAX` is
+/// considered infinite and is never decreased.
+pub type AllowancesStorage = StorageNMap<
+	Allowances,
+	(
+		NMapKey<Blake2_128Concat, CurrencyId>,
+		// Owner
+		NMapKey<Blake2_128Concat, H160>,
+		// Spender
+		NMapKey<Blake2_128Concat, H160>,
+	),
+	Balance,
+	ValueQuery,
+>;
+
+/// Storage prefix for the EIP-2612 permit nonces.
+pub struct PermitNonces;
+
+impl StorageInstance for PermitNonces {
+	const STORAGE_PREFIX: &'static str = "PermitNonces";
+
+	fn pallet_prefix() -> &'static str {
+		"PrecompileMultiCurrency"
+	}
+}
+
+/// Storage type used to store the EIP-2612 permit nonces of each currency.
+pub type PermitNoncesStorage = StorageMap<
+	PermitNonces,
+	Blake2_128Concat,
+	// Currency and owner
+	(CurrencyId, H160),
+	U256,
+	ValueQuery,
+>;
+
 #[module_evm_utility_macro::generate_function_selector]
 #[derive(RuntimeDebug, Eq, PartialEq, TryFromPrimitive, IntoPrimitive)]
 #[repr(u32)]
@@ -53,6 +131,9 @@ pub enum Action {
 	Transfer = "transfer(address,uint256)",
 	Approve = "approve(address,uint256)",
 	TransferFrom = "transferFrom(address,address,uint256)",
+	Permit = "permit(address,address,uint256,uint256,uint8,bytes32,bytes32)",
+	Nonces = "nonces(address)",
+	DomainSeparator = "DOMAIN_SEPARATOR()",
 }
 pub struct MultiCurrencyPrecompile<Runtime>(PhantomData<Runtime>);
 
@@ -80,7 +161,9 @@ where
 			};
 
 			handle.check_function_modifier(match selector {
-				Action::Transfer => FunctionModifier::NonPayable,
+				Action::Transfer | Action::Approve | Action::TransferFrom | Action::Permit => {
+					FunctionModifier::NonPayable
+				}
 				_ => FunctionModifier::View,
 			})?;
 
@@ -91,9 +174,12 @@ where
 				Action::TotalSupply => Self::total_supply(asset_id, handle),
 				Action::BalanceOf => Self::balance_of(asset_id, handle),
 				Action::Transfer => Self::transfer(asset_id, handle),
-				Action::Allowance => Self::not_supported(asset_id, handle),
-				Action::Approve => Self::not_supported(asset_id, handle),
-				Action::TransferFrom => Self::not_supported(asset_id, handle),
+				Action::Allowance => Self::allowance(asset_id, handle),
+				Action::Approve => Self::approve(asset_id, handle),
+				Action::TransferFrom => Self::transfer_from(asset_id, handle),
+				Action::Permit => Self::permit(asset_id, handle),
+				Action::Nonces => Self::nonces(asset_id, handle),
+				Action::DomainSeparator => Self::domain_separator(asset_id, handle),
 			};
 		}
 		Err(PrecompileFailure::Revert {
@@ -209,6 +295,7 @@ where
 
 	fn transfer(currency_id: CurrencyId, handle: &mut impl PrecompileHandle) -> PrecompileResult {
 		handle.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost())?;
+		handle.record_log_costs_manual(3, 32)?;
 
 		// Parse input
 		let mut input = handle.read_input()?;
@@ -217,28 +304,331 @@ where
 		let to: H160 = input.read::<Address>()?.into();
 		let amount = input.read::<Balance>()?;
 
-		let origin = ExtendedAddressMapping::into_account_id(handle.context().caller);
+		let from = handle.context().caller;
+
+		Self::do_transfer(currency_id, from, to, amount)?;
+		Self::log_transfer(handle, from, to, amount.into())?;
+
+		Ok(succeed(EvmDataWriter::new().write(true).build()))
+	}
+
+	fn allowance(currency_id: CurrencyId, handle: &mut impl PrecompileHandle) -> PrecompileResult {
+		handle.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost())?;
+
+		// Parse input
+		let mut input = handle.read_input()?;
+		input.expect_arguments(2)?;
+
+		let owner: H160 = input.read::<Address>()?.into();
+		let spender: H160 = input.read::<Address>()?.into();
+
+		let allowance = AllowancesStorage::get((currency_id, owner, spender));
+
+		log::debug!(target: "evm", "multicurrency: allowance: {:?}", allowance);
+
+		Ok(succeed(
+			EvmDataWriter::new()
+				.write(Self::allowance_to_u256(allowance))
+				.build(),
+		))
+	}
+
+	fn approve(currency_id: CurrencyId, handle: &mut impl PrecompileHandle) -> PrecompileResult {
+		handle.record_cost(RuntimeHelper::<Runtime>::db_write_gas_cost())?;
+		handle.record_log_costs_manual(3, 32)?;
+
+		// Parse input
+		let mut input = handle.read_input()?;
+		input.expect_arguments(2)?;
+
+		let spender: H160 = input.read::<Address>()?.into();
+		let amount: U256 = input.read()?;
+
+		let owner = handle.context().caller;
+
+		log::debug!(target: "evm", "multicurrency: approve owner: {:?}, spender: {:?}, amount: {:?}", owner, spender, amount);
+
+		Self::do_approve(currency_id, owner, spender, amount);
+		Self::log_approval(handle, owner, spender, amount)?;
+
+		Ok(succeed(EvmDataWriter::new().write(true).build()))
+	}
+
+	fn transfer_from(
+		currency_id: CurrencyId,
+		handle: &mut impl PrecompileHandle,
+	) -> PrecompileResult {
+		handle.record_cost(
+			RuntimeHelper::<Runtime>::db_read_gas_cost()
+				.saturating_mul(2)
+				.saturating_add(RuntimeHelper::<Runtime>::db_write_gas_cost()),
+		)?;
+		handle.record_log_costs_manual(3, 32)?;
+
+		// Parse input
+		let mut input = handle.read_input()?;
+		input.expect_arguments(3)?;
+
+		let from: H160 = input.read::<Address>()?.into();
+		let to: H160 = input.read::<Address>()?.into();
+		let amount = input.read::<Balance>()?;
+
+		let spender = handle.context().caller;
+
+		log::debug!(target: "evm", "multicurrency: transferFrom spender: {:?}, from: {:?}, to: {:?}, amount: {:?}", spender, from, to, amount);
+
+		Self::spend_allowance(currency_id, from, spender, amount)?;
+		Self::do_transfer(currency_id, from, to, amount)?;
+		Self::log_transfer(handle, from, to, amount.into())?;
+
+		Ok(succeed(EvmDataWriter::new().write(true).build()))
+	}
+
+	fn permit(currency_id: CurrencyId, handle: &mut impl PrecompileHandle) -> PrecompileResult {
+		// Cost of the ECRecover precompile, reading the metadata and the nonce, and writing the
+		// nonce and the allowance.
+		handle.record_cost(
+			3_000u64
+				.saturating_add(RuntimeHelper::<Runtime>::db_read_gas_cost().saturating_mul(2))
+				.saturating_add(RuntimeHelper::<Runtime>::db_write_gas_cost().saturating_mul(2)),
+		)?;
+		handle.record_log_costs_manual(3, 32)?;
+
+		// Parse input
+		let mut input = handle.read_input()?;
+		input.expect_arguments(7)?;
+
+		let owner: H160 = input.read::<Address>()?.into();
+		let spender: H160 = input.read::<Address>()?.into();
+		let value: U256 = input.read()?;
+		let deadline: U256 = input.read()?;
+		let v: u8 = input.read()?;
+		let r: H256 = input.read()?;
+		let s: H256 = input.read()?;
+
+		// Blockchain time is in ms while Ethereum use second timestamps.
+		let timestamp: u128 =
+			<Runtime as pallet_evm::Config>::Timestamp::now().unique_saturated_into();
+		let timestamp: U256 = U256::from(timestamp / 1000);
+
+		if deadline < timestamp {
+			return Err(revert("Permit expired"));
+		}
+
+		if s > SECP256K1_HALF_ORDER {
+			return Err(revert("Invalid permit"));
+		}
+
+		let nonce = PermitNoncesStorage::get((currency_id, owner));
+		let permit = Self::generate_permit(
+			currency_id,
+			handle.code_address(),
+			owner,
+			spender,
+			value,
+			nonce,
+			deadline,
+		)?;
+
+		let mut sig = [0u8; 65];
+		sig[0..32].copy_from_slice(r.as_bytes());
+		sig[32..64].copy_from_slice(s.as_bytes());
+		sig[64] = v;
+		let signer = sp_io::crypto::secp256k1_ecdsa_recover(&sig, &permit)
+			.map_err(|_| revert("Invalid permit"))?;
+		let signer = H160::from(H256::from_slice(keccak_256(&signer).as_slice()));
+
+		if signer == H160::zero() || signer != owner {
+			return Err(revert("Invalid permit"));
+		}
+
+		PermitNoncesStorage::insert((currency_id, owner), nonce.saturating_add(U256::one()));
+		Self::do_approve(currency_id, owner, spender, value);
+		Self::log_approval(handle, owner, spender, value)?;
+
+		Ok(succeed(EvmDataWriter::new().build()))
+	}
+
+	fn nonces(currency_id: CurrencyId, handle: &mut impl PrecompileHandle) -> PrecompileResult {
+		handle.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost())?;
+
+		// Parse input
+		let mut input = handle.read_input()?;
+		input.expect_arguments(1)?;
+
+		let owner: H160 = input.read::<Address>()?.into();
+
+		let nonce = PermitNoncesStorage::get((currency_id, owner));
+
+		Ok(succeed(EvmDataWriter::new().write(nonce).build()))
+	}
+
+	fn domain_separator(
+		currency_id: CurrencyId,
+		handle: &mut impl PrecompileHandle,
+	) -> PrecompileResult {
+		handle.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost())?;
+
+		// Parse input
+		let input = handle.read_input()?;
+		input.expect_arguments(0)?;
+
+		let domain_separator = Self::compute_domain_separator(currency_id, handle.code_address())?;
+
+		Ok(succeed(
+			EvmDataWriter::new()
+				.write(H256::from(domain_separator))
+				.build(),
+		))
+	}
+
+	/// The EIP-712 domain separator of the ERC-20 contract of `currency_id`, deployed at
+	/// `address`. The name of the domain is the name of the asset.
+	pub fn compute_domain_separator(currency_id: CurrencyId, address: H160) -> EvmResult<[u8; 32]> {
+		let metadata = AssetIdMaps::<Runtime>::get_currency_metadata(currency_id).ok_or(
+			PrecompileFailure::Error {
+				exit_status: pallet_evm::ExitError::Other("Non-existing asset.".into()),
+			},
+		)?;
+		let name: H256 = keccak_256(metadata.name.as_slice()).into();
+		let version: H256 = keccak_256(b"1").into();
+		let chain_id: U256 = <Runtime as pallet_evm::Config>::ChainId::get().into();
+
+		let domain_separator_inner = EvmDataWriter::new()
+			.write(H256::from(*PERMIT_DOMAIN))
+			.write(name)
+			.write(version)
+			.write(chain_id)
+			.write(Address(address))
+			.build();
+
+		Ok(keccak_256(&domain_separator_inner))
+	}
+
+	/// The EIP-712 digest `owner` has to sign to approve `spender` to spend `value`.
+	pub fn generate_permit(
+		currency_id: CurrencyId,
+		address: H160,
+		owner: H160,
+		spender: H160,
+		value: U256,
+		nonce: U256,
+		deadline: U256,
+	) -> EvmResult<[u8; 32]> {
+		let domain_separator = Self::compute_domain_separator(currency_id, address)?;
+
+		let permit_content = EvmDataWriter::new()
+			.write(H256::from(*PERMIT_TYPEHASH))
+			.write(Address(owner))
+			.write(Address(spender))
+			.write(value)
+			.write(nonce)
+			.write(deadline)
+			.build();
+		let permit_content = keccak_256(&permit_content);
+		let mut pre_digest = Vec::with_capacity(2 + 32 + 32);
+		pre_digest.extend_from_slice(b"\x19\x01");
+		pre_digest.extend_from_slice(&domain_separator);
+		pre_digest.extend_from_slice(&permit_content);
+
+		Ok(keccak_256(&pre_digest))
+	}
+
+	/// Set the allowance of `spender` over the tokens of `owner`. Amounts that don't fit in a
+	/// `Balance` are stored as an infinite allowance.
+	pub fn do_approve(currency_id: CurrencyId, owner: H160, spender: H160, amount: U256) {
+		let amount: Balance = amount.try_into().unwrap_or(Balance::MAX);
+		if amount == 0 {
+			AllowancesStorage::remove((currency_id, owner, spender));
+		} else {
+			AllowancesStorage::insert((currency_id, owner, spender), amount);
+		}
+	}
+
+	/// Decrease the allowance of `spender` over the tokens of `owner` by `amount`, unless the
+	/// allowance is infinite.
+	pub fn spend_allowance(
+		currency_id: CurrencyId,
+		owner: H160,
+		spender: H160,
+		amount: Balance,
+	) -> EvmResult {
+		let allowance = AllowancesStorage::get((currency_id, owner, spender));
+		if allowance == Balance::MAX {
+			return Ok(());
+		}
+
+		let allowance = allowance
+			.checked_sub(amount)
+			.ok_or_else(|| revert("ERC20: insufficient allowance"))?;
+		Self::do_approve(currency_id, owner, spender, allowance.into());
+
+		Ok(())
+	}
+
+	fn allowance_to_u256(allowance: Balance) -> U256 {
+		if allowance == Balance::MAX {
+			U256::MAX
+		} else {
+			allowance.into()
+		}
+	}
+
+	fn do_transfer(currency_id: CurrencyId, from: H160, to: H160, amount: Balance) -> EvmResult {
+		let from = ExtendedAddressMapping::into_account_id(from);
 		let to = ExtendedAddressMapping::into_account_id(to);
 
-		log::debug!(target: "evm", "multicurrency: transfer from: {:?}, to: {:?}, amount: {:?}", origin, to, amount);
+		log::debug!(target: "evm", "multicurrency: transfer from: {:?}, to: {:?}, amount: {:?}", from, to, amount);
 
 		<bifrost_currencies::Pallet<Runtime> as MultiCurrency<Runtime::AccountId>>::transfer(
 			currency_id,
-			&(<sp_runtime::AccountId32 as Into<Runtime::AccountId>>::into(origin)),
+			&(<sp_runtime::AccountId32 as Into<Runtime::AccountId>>::into(from)),
 			&(<sp_runtime::AccountId32 as Into<Runtime::AccountId>>::into(to)),
 			amount,
 		)
 		.map_err(|e| PrecompileFailure::Revert {
 			exit_status: ExitRevert::Reverted,
 			output: Into::<&str>::into(e).as_bytes().to_vec(),
-		})?;
+		})
+	}
 
-		Ok(succeed(EvmDataWriter::new().write(true).build()))
+	fn log_transfer(
+		handle: &mut impl PrecompileHandle,
+		from: H160,
+		to: H160,
+		amount: U256,
+	) -> EvmResult {
+		let address = handle.code_address();
+		handle.log(
+			address,
+			vec![
+				H256::from(*SELECTOR_LOG_TRANSFER),
+				H256::from(from),
+				H256::from(to),
+			],
+			EvmDataWriter::new().write(amount).build(),
+		)?;
+
+		Ok(())
 	}
 
-	fn not_supported(_: CurrencyId, _: &mut impl PrecompileHandle) -> PrecompileResult {
-		Err(PrecompileFailure::Error {
-			exit_status: pallet_evm::ExitError::Other("not supported".into()),
-		})
+	fn log_approval(
+		handle: &mut impl PrecompileHandle,
+		owner: H160,
+		spender: H160,
+		amount: U256,
+	) -> EvmResult {
+		let address = handle.code_address();
+		handle.log(
+			address,
+			vec![
+				H256::from(*SELECTOR_LOG_APPROVAL),
+				H256::from(owner),
+				H256::from(spender),
+			],
+			EvmDataWriter::new().write(amount).build(),
+		)?;
+
+		Ok(())
 	}
 }
@@ -0,0 +1,398 @@
+// This file is part of Bifrost.
+
+// Copyright (C) Liebi Technologies PTE. LTD.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::evm::{
+	precompiles::{
+		erc20_mapping::{BifrostErc20Mapping, Erc20Mapping},
+		handle::{EvmDataWriter, FunctionModifier, PrecompileHandleExt},
+		revert,
+		substrate::RuntimeHelper,
+		succeed, Address, EvmResult,
+	},
+	ExtendedAddressMapping,
+};
+use bifrost_primitives::{Balance, CurrencyId};
+use bifrost_stable_pool::{
+	traits::StablePoolHandler, PoolTokenIndex, Pools, StableAssetPoolId, WeightInfo,
+};
+use frame_support::traits::Time;
+use num_enum::{IntoPrimitive, TryFromPrimitive};
+use orml_traits::MultiCurrency;
+use pallet_evm::{AddressMapping, Precompile, PrecompileHandle, PrecompileResult};
+use primitive_types::{H160, U256};
+use sp_runtime::{traits::UniqueSaturatedInto, DispatchError, RuntimeDebug};
+use sp_std::{marker::PhantomData, prelude::*};
+
+#[module_evm_utility_macro::generate_function_selector]
+#[derive(RuntimeDebug, Eq, PartialEq, TryFromPrimitive, IntoPrimitive)]
+#[repr(u32)]
+pub enum Action {
+	Swap = "swap(uint32,address,address,uint256,uint256,uint256)",
+	AddLiquidity = "addLiquidity(uint32,uint256[],uint256,uint256)",
+	RedeemProportion = "redeemProportion(uint32,uint256,uint256[],uint256)",
+	RedeemSingle = "redeemSingle(uint32,uint256,address,uint256,uint256)",
+	GetSwapOutput = "getSwapOutput(uint32,address,address,uint256)",
+	GetSwapInput = "getSwapInput(uint32,address,address,uint256)",
+	AddLiquidityAmount = "addLiquidityAmount(uint32,uint256[])",
+}
+
+/// Precompile exposing the swaps and liquidity operations of `bifrost_stable_pool` to the EVM.
+///
+/// Pool tokens are identified by their ERC-20 mapped addresses. Every state changing call takes a
+/// minimum output and a deadline in seconds, so aggregators can route through stable pools with the
+/// same guarantees as on any other AMM. A call paused by the runtime call filter reverts, as the
+/// matching extrinsic would fail.
+pub struct StablePoolPrecompile<Runtime>(PhantomData<Runtime>);
+
+impl<Runtime> Precompile for StablePoolPrecompile<Runtime>
+where
+	Runtime: frame_system::Config
+		+ pallet_evm::Config
+		+ bifrost_stable_pool::Config<CurrencyId = CurrencyId>
+		+ bifrost_stable_asset::Config<AssetId = CurrencyId, Balance = Balance>,
+	<Runtime as frame_system::Config>::AccountId: core::convert::From<sp_runtime::AccountId32>,
+	<Runtime as frame_system::Config>::RuntimeCall: From<bifrost_stable_pool::Call<Runtime>>,
+{
+	fn execute(handle: &mut impl PrecompileHandle) -> PrecompileResult {
+		let selector = handle.read_selector()?;
+
+		handle.check_function_modifier(match selector {
+			Action::Swap
+			| Action::AddLiquidity
+			| Action::RedeemProportion
+			| Action::RedeemSingle => FunctionModifier::NonPayable,
+			_ => FunctionModifier::View,
+		})?;
+
+		match selector {
+			Action::Swap => Self::swap(handle),
+			Action::AddLiquidity => Self::add_liquidity(handle),
+			Action::RedeemProportion => Self::redeem_proportion(handle),
+			Action::RedeemSingle => Self::redeem_single(handle),
+			Action::GetSwapOutput => Self::get_swap_output(handle),
+			Action::GetSwapInput => Self::get_swap_input(handle),
+			Action::AddLiquidityAmount => Self::add_liquidity_amount(handle),
+		}
+	}
+}
+
+impl<Runtime> StablePoolPrecompile<Runtime>
+where
+	Runtime: frame_system::Config
+		+ pallet_evm::Config
+		+ bifrost_stable_pool::Config<CurrencyId = CurrencyId>
+		+ bifrost_stable_asset::Config<AssetId = CurrencyId, Balance = Balance>,
+	<Runtime as frame_system::Config>::AccountId: core::convert::From<sp_runtime::AccountId32>,
+	<Runtime as frame_system::Config>::RuntimeCall: From<bifrost_stable_pool::Call<Runtime>>,
+{
+	/// Swaps `amountIn` of `tokenIn` for `tokenOut` and returns the amount received.
+	fn swap(handle: &mut impl PrecompileHandle) -> PrecompileResult {
+		handle.record_cost(RuntimeHelper::<Runtime>::weight_to_gas(
+			<Runtime as bifrost_stable_pool::Config>::WeightInfo::swap(),
+		))?;
+
+		// Parse input
+		let mut input = handle.read_input()?;
+		input.expect_arguments(6)?;
+
+		let pool_id = input.read::<StableAssetPoolId>()?;
+		let currency_in = Self::read_currency_id(input.read::<Address>()?)?;
+		let currency_out = Self::read_currency_id(input.read::<Address>()?)?;
+		let amount_in = input.read::<Balance>()?;
+		let min_amount_out = input.read::<Balance>()?;
+		Self::ensure_deadline(input.read::<U256>()?)?;
+
+		let i = Self::pool_token_index(pool_id, currency_in)?;
+		let j = Self::pool_token_index(pool_id, currency_out)?;
+		let who = Self::caller(handle);
+
+		RuntimeHelper::<Runtime>::ensure_call_allowed(
+			bifrost_stable_pool::Call::<Runtime>::swap {
+				pool_id,
+				i,
+				j,
+				dx: amount_in,
+				min_dy: min_amount_out,
+			},
+		)?;
+
+		log::debug!(target: "evm", "stable-pool: swap who: {:?}, pool: {:?}, in: {:?}, out: {:?}, amount: {:?}", who, pool_id, currency_in, currency_out, amount_in);
+
+		let balance_before = Self::free_balance(currency_out, &who);
+		<bifrost_stable_pool::Pallet<Runtime> as StablePoolHandler>::swap(
+			&who,
+			pool_id,
+			i,
+			j,
+			amount_in,
+			min_amount_out,
+		)
+		.map_err(Self::dispatch_error)?;
+		let amount_out = Self::free_balance(currency_out, &who).saturating_sub(balance_before);
+
+		Ok(succeed(EvmDataWriter::new().write(amount_out).build()))
+	}
+
+	/// Adds `amounts`, ordered as the pool assets, and returns the minted LP amount.
+	fn add_liquidity(handle: &mut impl PrecompileHandle) -> PrecompileResult {
+		handle.record_cost(RuntimeHelper::<Runtime>::weight_to_gas(
+			<Runtime as bifrost_stable_pool::Config>::WeightInfo::add_liquidity(),
+		))?;
+
+		// Parse input
+		let mut input = handle.read_input()?;
+		input.expect_arguments(4)?;
+
+		let pool_id = input.read::<StableAssetPoolId>()?;
+		let amounts = input.read::<Vec<Balance>>()?;
+		let min_mint_amount = input.read::<Balance>()?;
+		Self::ensure_deadline(input.read::<U256>()?)?;
+
+		let pool_asset = Pools::<Runtime>::get(pool_id)
+			.ok_or_else(|| revert("pool not found"))?
+			.pool_asset;
+		let who = Self::caller(handle);
+
+		RuntimeHelper::<Runtime>::ensure_call_allowed(
+			bifrost_stable_pool::Call::<Runtime>::add_liquidity {
+				pool_id,
+				amounts: amounts.clone(),
+				min_mint_amount,
+			},
+		)?;
+
+		log::debug!(target: "evm", "stable-pool: add_liquidity who: {:?}, pool: {:?}, amounts: {:?}", who, pool_id, amounts);
+
+		let balance_before = Self::free_balance(pool_asset, &who);
+		<bifrost_stable_pool::Pallet<Runtime> as StablePoolHandler>::add_liquidity(
+			who.clone(),
+			pool_id,
+			amounts,
+			min_mint_amount,
+		)
+		.map_err(Self::dispatch_error)?;
+		let minted = Self::free_balance(pool_asset, &who).saturating_sub(balance_before);
+
+		Ok(succeed(EvmDataWriter::new().write(minted).build()))
+	}
+
+	/// Burns `amount` LP for every pool asset in proportion and returns the received amounts,
+	/// ordered as the pool assets.
+	fn redeem_proportion(handle: &mut impl PrecompileHandle) -> PrecompileResult {
+		handle.record_cost(RuntimeHelper::<Runtime>::weight_to_gas(
+			<Runtime as bifrost_stable_pool::Config>::WeightInfo::redeem_proportion(),
+		))?;
+
+		// Parse input
+		let mut input = handle.read_input()?;
+		input.expect_arguments(4)?;
+
+		let pool_id = input.read::<StableAssetPoolId>()?;
+		let amount = input.read::<Balance>()?;
+		let min_redeem_amounts = input.read::<Vec<Balance>>()?;
+		Self::ensure_deadline(input.read::<U256>()?)?;
+
+		let assets = Pools::<Runtime>::get(pool_id)
+			.ok_or_else(|| revert("pool not found"))?
+			.assets;
+		let who = Self::caller(handle);
+
+		RuntimeHelper::<Runtime>::ensure_call_allowed(
+			bifrost_stable_pool::Call::<Runtime>::redeem_proportion {
+				pool_id,
+				amount,
+				min_redeem_amounts: min_redeem_amounts.clone(),
+			},
+		)?;
+
+		log::debug!(target: "evm", "stable-pool: redeem_proportion who: {:?}, pool: {:?}, amount: {:?}", who, pool_id, amount);
+
+		let balances_before: Vec<Balance> = assets
+			.iter()
+			.map(|asset| Self::free_balance(*asset, &who))
+			.collect();
+		<bifrost_stable_pool::Pallet<Runtime> as StablePoolHandler>::redeem_proportion(
+			who.clone(),
+			pool_id,
+			amount,
+			min_redeem_amounts,
+		)
+		.map_err(Self::dispatch_error)?;
+		let redeemed: Vec<Balance> = assets
+			.iter()
+			.zip(balances_before)
+			.map(|(asset, before)| Self::free_balance(*asset, &who).saturating_sub(before))
+			.collect();
+
+		Ok(succeed(EvmDataWriter::new().write(redeemed).build()))
+	}
+
+	/// Burns `amount` LP for `tokenOut` only and returns the amount received.
+	fn redeem_single(handle: &mut impl PrecompileHandle) -> PrecompileResult {
+		handle.record_cost(RuntimeHelper::<Runtime>::weight_to_gas(
+			<Runtime as bifrost_stable_pool::Config>::WeightInfo::redeem_single(),
+		))?;
+
+		// Parse input
+		let mut input = handle.read_input()?;
+		input.expect_arguments(5)?;
+
+		let pool_id = input.read::<StableAssetPoolId>()?;
+		let amount = input.read::<Balance>()?;
+		let currency_out = Self::read_currency_id(input.read::<Address>()?)?;
+		let min_amount_out = input.read::<Balance>()?;
+		Self::ensure_deadline(input.read::<U256>()?)?;
+
+		let asset_length = Pools::<Runtime>::get(pool_id)
+			.ok_or_else(|| revert("pool not found"))?
+			.assets
+			.len() as u32;
+		let i = Self::pool_token_index(pool_id, currency_out)?;
+		let who = Self::caller(handle);
+
+		RuntimeHelper::<Runtime>::ensure_call_allowed(
+			bifrost_stable_pool::Call::<Runtime>::redeem_single {
+				pool_id,
+				amount,
+				i,
+				min_redeem_amount: min_amount_out,
+				asset_length,
+			},
+		)?;
+
+		log::debug!(target: "evm", "stable-pool: redeem_single who: {:?}, pool: {:?}, amount: {:?}, out: {:?}", who, pool_id, amount, currency_out);
+
+		let (amount_out, _) =
+			<bifrost_stable_pool::Pallet<Runtime> as StablePoolHandler>::redeem_single(
+				who,
+				pool_id,
+				amount,
+				i,
+				min_amount_out,
+				asset_length,
+			)
+			.map_err(Self::dispatch_error)?;
+
+		Ok(succeed(EvmDataWriter::new().write(amount_out).build()))
+	}
+
+	fn get_swap_output(handle: &mut impl PrecompileHandle) -> PrecompileResult {
+		// Pools, TokenRateCaches of both assets
+		handle.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost().saturating_mul(3))?;
+
+		// Parse input
+		let mut input = handle.read_input()?;
+		input.expect_arguments(4)?;
+
+		let pool_id = input.read::<StableAssetPoolId>()?;
+		let i = Self::pool_token_index(pool_id, Self::read_currency_id(input.read::<Address>()?)?)?;
+		let j = Self::pool_token_index(pool_id, Self::read_currency_id(input.read::<Address>()?)?)?;
+		let amount_in = input.read::<Balance>()?;
+
+		let amount_out =
+			<bifrost_stable_pool::Pallet<Runtime> as StablePoolHandler>::get_swap_output(
+				pool_id, i, j, amount_in,
+			)
+			.map_err(Self::dispatch_error)?;
+
+		Ok(succeed(EvmDataWriter::new().write(amount_out).build()))
+	}
+
+	fn get_swap_input(handle: &mut impl PrecompileHandle) -> PrecompileResult {
+		// Pools, TokenRateCaches of both assets
+		handle.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost().saturating_mul(3))?;
+
+		// Parse input
+		let mut input = handle.read_input()?;
+		input.expect_arguments(4)?;
+
+		let pool_id = input.read::<StableAssetPoolId>()?;
+		let i = Self::pool_token_index(pool_id, Self::read_currency_id(input.read::<Address>()?)?)?;
+		let j = Self::pool_token_index(pool_id, Self::read_currency_id(input.read::<Address>()?)?)?;
+		let amount_out = input.read::<Balance>()?;
+
+		let amount_in =
+			<bifrost_stable_pool::Pallet<Runtime> as StablePoolHandler>::get_swap_input(
+				pool_id, i, j, amount_out,
+			)
+			.map_err(Self::dispatch_error)?;
+
+		Ok(succeed(EvmDataWriter::new().write(amount_in).build()))
+	}
+
+	fn add_liquidity_amount(handle: &mut impl PrecompileHandle) -> PrecompileResult {
+		// Parse input
+		let mut input = handle.read_input()?;
+		input.expect_arguments(2)?;
+
+		let pool_id = input.read::<StableAssetPoolId>()?;
+		let amounts = input.read::<Vec<Balance>>()?;
+
+		// Pools and a TokenRateCaches per asset
+		handle.record_cost(
+			RuntimeHelper::<Runtime>::db_read_gas_cost().saturating_mul(amounts.len() as u64 + 1),
+		)?;
+
+		let mint_amount =
+			bifrost_stable_pool::Pallet::<Runtime>::add_liquidity_amount(pool_id, amounts)
+				.map_err(Self::dispatch_error)?;
+
+		Ok(succeed(EvmDataWriter::new().write(mint_amount).build()))
+	}
+
+	/// Reverts once the block timestamp is past `deadline`, in seconds.
+	fn ensure_deadline(deadline: U256) -> EvmResult {
+		// Blockchain time is in ms while Ethereum use second timestamps.
+		let timestamp: u128 =
+			<Runtime as pallet_evm::Config>::Timestamp::now().unique_saturated_into();
+		if deadline < U256::from(timestamp / 1000) {
+			return Err(revert("transaction expired"));
+		}
+
+		Ok(())
+	}
+
+	fn pool_token_index(
+		pool_id: StableAssetPoolId,
+		currency_id: CurrencyId,
+	) -> EvmResult<PoolTokenIndex> {
+		<bifrost_stable_pool::Pallet<Runtime> as StablePoolHandler>::get_pool_token_index(
+			pool_id,
+			currency_id,
+		)
+		.ok_or_else(|| revert("currency not in pool"))
+	}
+
+	fn free_balance(currency_id: CurrencyId, who: &Runtime::AccountId) -> Balance {
+		<Runtime as bifrost_stable_pool::Config>::MultiCurrency::free_balance(currency_id, who)
+	}
+
+	fn read_currency_id(address: Address) -> EvmResult<CurrencyId> {
+		BifrostErc20Mapping::decode_evm_address(address.into())
+			.ok_or_else(|| revert("invalid currency id"))
+	}
+
+	fn caller(handle: &impl PrecompileHandle) -> Runtime::AccountId {
+		let caller: H160 = handle.context().caller;
+		ExtendedAddressMapping::into_account_id(caller).into()
+	}
+
+	fn dispatch_error(e: DispatchError) -> pallet_evm::PrecompileFailure {
+		revert(Into::<&str>::into(e))
+	}
+}
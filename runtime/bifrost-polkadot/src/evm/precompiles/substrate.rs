@@ -17,12 +17,18 @@
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
 //! Utils related to Substrate features:
-//! - Substrate call dispatch.
+//! - Substrate call dispatch and filtering.
 //! - Substrate DB read and write costs
 
+use super::{revert, EvmResult};
 use core::marker::PhantomData;
-use frame_support::{traits::Get, weights::Weight};
+use frame_support::{
+	dispatch::PostDispatchInfo,
+	traits::{Contains, Get},
+	weights::Weight,
+};
 use pallet_evm::GasWeightMapping;
+use sp_runtime::traits::Dispatchable;
 
 /// Helper functions requiring a Substrate runtime.
 /// This runtime must of course implement `pallet_evm::Config`.
@@ -40,4 +46,46 @@ where
 			0,
 		))
 	}
+
+	/// Cost of a Substrate weight in gas.
+	pub fn weight_to_gas(weight: Weight) -> u64 {
+		<Runtime as pallet_evm::Config>::GasWeightMapping::weight_to_gas(weight)
+	}
+
+	/// Cost of a Substrate DB write in gas.
+	pub fn db_write_gas_cost() -> u64 {
+		<Runtime as pallet_evm::Config>::GasWeightMapping::weight_to_gas(Weight::from_parts(
+			<Runtime as frame_system::Config>::DbWeight::get().write,
+			0,
+		))
+	}
+
+	/// Revert unless `call` passes the runtime `BaseCallFilter`, so a call paused for
+	/// extrinsics can't be reached through a precompile either.
+	pub fn ensure_call_allowed<Call>(call: Call) -> EvmResult
+	where
+		<Runtime as frame_system::Config>::RuntimeCall: From<Call>,
+	{
+		let call = <Runtime as frame_system::Config>::RuntimeCall::from(call);
+		if !<Runtime as frame_system::Config>::BaseCallFilter::contains(&call) {
+			return Err(revert("CallFiltered"));
+		}
+
+		Ok(())
+	}
+
+	/// Dispatch `call` with a signed origin of `who`. The origin applies the runtime
+	/// `BaseCallFilter`, as for an extrinsic.
+	pub fn try_dispatch<Call>(
+		who: <Runtime as frame_system::Config>::AccountId,
+		call: Call,
+	) -> EvmResult<PostDispatchInfo>
+	where
+		<Runtime as frame_system::Config>::RuntimeCall:
+			From<Call> + Dispatchable<PostInfo = PostDispatchInfo>,
+	{
+		<Runtime as frame_system::Config>::RuntimeCall::from(call)
+			.dispatch(frame_system::RawOrigin::Signed(who).into())
+			.map_err(|e| revert(Into::<&str>::into(e.error)))
+	}
 }
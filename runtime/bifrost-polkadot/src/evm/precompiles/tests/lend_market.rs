@@ -0,0 +1,280 @@
+// This file is part of Bifrost.
+
+// Copyright (C) Liebi Technologies PTE. LTD.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use super::mock::{account_of, execute, MockHandle};
+use crate::{
+	evm::precompiles::{
+		erc20_mapping::{BifrostErc20Mapping, Erc20Mapping},
+		handle::{EvmDataReader, EvmDataWriter},
+		lend_market::{Action, LendMarketPrecompile},
+		Address,
+	},
+	LendMarket, Runtime, Tokens, TxPause,
+};
+use bifrost_primitives::{Balance, CurrencyId, Rate, Ratio, DOT, LDOT};
+use frame_system::RawOrigin;
+use lend_market::{
+	AccountBorrows, AccountDeposits, InterestRateModel, JumpModel, Market, MarketBond, MarketState,
+};
+use orml_traits::MultiCurrency;
+use pallet_prices::EmergencyPrice;
+use primitive_types::H160;
+use sp_runtime::{BoundedVec, FixedPointNumber};
+
+type Precompile = LendMarketPrecompile<Runtime>;
+
+const ALICE: H160 = H160::repeat_byte(0xaa);
+const DOT_UNIT: Balance = 10_000_000_000;
+
+fn address(currency_id: CurrencyId) -> Address {
+	BifrostErc20Mapping::encode_evm_address(currency_id)
+		.unwrap()
+		.into()
+}
+
+fn new_test_ext() -> sp_io::TestExternalities {
+	let mut ext = sp_io::TestExternalities::default();
+	ext.execute_with(|| {
+		assert!(LendMarket::add_market(
+			RawOrigin::Root.into(),
+			DOT,
+			Market {
+				close_factor: Ratio::from_percent(50),
+				collateral_factor: Ratio::from_percent(50),
+				liquidation_threshold: Ratio::from_percent(55),
+				liquidate_incentive: Rate::from_inner(Rate::DIV / 100 * 110),
+				liquidate_incentive_reserved_factor: Ratio::from_percent(3),
+				state: MarketState::Pending,
+				rate_model: InterestRateModel::Jump(JumpModel {
+					base_rate: Rate::from_inner(Rate::DIV / 100 * 2),
+					jump_rate: Rate::from_inner(Rate::DIV / 100 * 10),
+					full_rate: Rate::from_inner(Rate::DIV / 100 * 32),
+					jump_utilization: Ratio::from_percent(80),
+				}),
+				reserve_factor: Ratio::from_percent(15),
+				supply_cap: 1_000_000 * DOT_UNIT,
+				borrow_cap: 1_000_000 * DOT_UNIT,
+				lend_token_id: LDOT,
+			},
+		)
+		.is_ok());
+		assert!(LendMarket::activate_market(RawOrigin::Root.into(), DOT).is_ok());
+		MarketBond::<Runtime>::insert(DOT, BoundedVec::truncate_from(vec![DOT]));
+		EmergencyPrice::<Runtime>::insert(DOT, Rate::saturating_from_integer(5));
+		assert!(Tokens::deposit(DOT, &account_of(ALICE), 100 * DOT_UNIT).is_ok());
+	});
+	ext
+}
+
+fn call(action: Action, asset: CurrencyId, amount: Balance) -> Result<Vec<u8>, Vec<u8>> {
+	execute::<Precompile>(
+		ALICE,
+		EvmDataWriter::new_with_selector(action)
+			.write(address(asset))
+			.write(amount)
+			.build(),
+	)
+}
+
+fn collateral_asset(asset: CurrencyId, enable: bool) -> Result<Vec<u8>, Vec<u8>> {
+	execute::<Precompile>(
+		ALICE,
+		EvmDataWriter::new_with_selector(Action::CollateralAsset)
+			.write(address(asset))
+			.write(enable)
+			.build(),
+	)
+}
+
+fn account_liquidity() -> (u128, u128, u128, u128) {
+	let output = execute::<Precompile>(
+		ALICE,
+		EvmDataWriter::new_with_selector(Action::GetAccountLiquidity)
+			.write(Address(ALICE))
+			.build(),
+	)
+	.unwrap();
+	let mut reader = EvmDataReader::new(&output);
+	(
+		reader.read().unwrap(),
+		reader.read().unwrap(),
+		reader.read().unwrap(),
+		reader.read().unwrap(),
+	)
+}
+
+fn balance(currency_id: CurrencyId) -> Balance {
+	Tokens::free_balance(currency_id, &account_of(ALICE))
+}
+
+fn borrowed() -> Balance {
+	AccountBorrows::<Runtime>::get(DOT, account_of(ALICE)).principal
+}
+
+#[test]
+fn mint_and_redeem_should_work() {
+	new_test_ext().execute_with(|| {
+		let output = call(Action::Mint, DOT, 10 * DOT_UNIT).unwrap();
+		let vouchers = AccountDeposits::<Runtime>::get(DOT, account_of(ALICE)).voucher_balance;
+		assert!(vouchers > 0);
+		assert_eq!(
+			EvmDataReader::new(&output).read::<Balance>().unwrap(),
+			vouchers
+		);
+		assert_eq!(balance(DOT), 90 * DOT_UNIT);
+
+		assert!(call(Action::Redeem, DOT, 4 * DOT_UNIT).is_ok());
+		assert_eq!(balance(DOT), 94 * DOT_UNIT);
+		assert!(AccountDeposits::<Runtime>::get(DOT, account_of(ALICE)).voucher_balance < vouchers);
+
+		assert_eq!(
+			call(Action::Redeem, DOT, 7 * DOT_UNIT),
+			Err(b"InsufficientDeposit".to_vec())
+		);
+		assert_eq!(balance(DOT), 94 * DOT_UNIT);
+	});
+}
+
+#[test]
+fn borrow_and_repay_should_work() {
+	new_test_ext().execute_with(|| {
+		assert!(call(Action::Mint, DOT, 10 * DOT_UNIT).is_ok());
+
+		// Deposits only count towards the liquidity once they are used as collateral.
+		assert_eq!(
+			call(Action::Borrow, DOT, DOT_UNIT),
+			Err(b"InsufficientLiquidity".to_vec())
+		);
+		assert!(collateral_asset(DOT, true).is_ok());
+		assert!(AccountDeposits::<Runtime>::get(DOT, account_of(ALICE)).is_collateral);
+
+		assert!(call(Action::Borrow, DOT, 2 * DOT_UNIT).is_ok());
+		assert_eq!(balance(DOT), 92 * DOT_UNIT);
+		assert_eq!(borrowed(), 2 * DOT_UNIT);
+
+		// The collateral factor is 50%.
+		assert_eq!(
+			call(Action::Borrow, DOT, 4 * DOT_UNIT),
+			Err(b"InsufficientLiquidity".to_vec())
+		);
+		assert_eq!(borrowed(), 2 * DOT_UNIT);
+
+		assert!(call(Action::RepayBorrow, DOT, DOT_UNIT).is_ok());
+		assert_eq!(balance(DOT), 91 * DOT_UNIT);
+		assert_eq!(borrowed(), DOT_UNIT);
+
+		assert_eq!(
+			call(Action::RepayBorrow, DOT, 2 * DOT_UNIT),
+			Err(b"TooMuchRepay".to_vec())
+		);
+		assert_eq!(borrowed(), DOT_UNIT);
+	});
+}
+
+#[test]
+fn get_account_liquidity_should_work() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(account_liquidity(), (0, 0, 0, 0));
+
+		assert!(call(Action::Mint, DOT, 10 * DOT_UNIT).is_ok());
+		assert!(collateral_asset(DOT, true).is_ok());
+		let (liquidity, shortfall, ..) = account_liquidity();
+		assert!(liquidity > 0);
+		assert_eq!(shortfall, 0);
+
+		assert!(call(Action::Borrow, DOT, 2 * DOT_UNIT).is_ok());
+		let (liquidity_after_borrow, shortfall, lf_liquidity, lf_shortfall) = account_liquidity();
+		assert!(liquidity_after_borrow < liquidity);
+		assert_eq!(shortfall, 0);
+
+		let expected = LendMarket::get_account_liquidity(&account_of(ALICE)).unwrap();
+		assert_eq!(
+			(
+				liquidity_after_borrow,
+				shortfall,
+				lf_liquidity,
+				lf_shortfall
+			),
+			(
+				expected.0.into_inner(),
+				expected.1.into_inner(),
+				expected.2.into_inner(),
+				expected.3.into_inner()
+			)
+		);
+	});
+}
+
+#[test]
+fn get_account_liquidity_should_charge_gas_per_market() {
+	new_test_ext().execute_with(|| {
+		let input = EvmDataWriter::new_with_selector(Action::GetAccountLiquidity)
+			.write(Address(ALICE))
+			.build();
+
+		let mut handle = MockHandle::new(ALICE, input.clone());
+		assert!(<Precompile as pallet_evm::Precompile>::execute(&mut handle).is_ok());
+		let gas_per_market = handle.gas_used;
+
+		// Without enough gas for every market, the call fails before reading the positions.
+		let mut handle = MockHandle::new(ALICE, input);
+		handle.gas_limit = gas_per_market - 1;
+		assert!(<Precompile as pallet_evm::Precompile>::execute(&mut handle).is_err());
+	});
+}
+
+#[test]
+fn calls_should_revert_on_an_inactive_market() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(
+			call(Action::Mint, CurrencyId::Token2(1), DOT_UNIT),
+			Err(b"MarketNotActivated".to_vec())
+		);
+		assert_eq!(
+			call(Action::Borrow, CurrencyId::Token2(1), DOT_UNIT),
+			Err(b"MarketNotActivated".to_vec())
+		);
+		assert_eq!(balance(DOT), 100 * DOT_UNIT);
+	});
+}
+
+#[test]
+fn paused_calls_should_revert() {
+	new_test_ext().execute_with(|| {
+		assert!(call(Action::Mint, DOT, 10 * DOT_UNIT).is_ok());
+		assert!(collateral_asset(DOT, true).is_ok());
+		assert!(TxPause::pause(
+			RawOrigin::Root.into(),
+			(
+				BoundedVec::truncate_from(b"LendMarket".to_vec()),
+				BoundedVec::truncate_from(b"borrow".to_vec())
+			)
+		)
+		.is_ok());
+
+		assert_eq!(
+			call(Action::Borrow, DOT, 2 * DOT_UNIT),
+			Err(b"CallFiltered".to_vec())
+		);
+		assert_eq!(borrowed(), 0);
+		assert_eq!(balance(DOT), 90 * DOT_UNIT);
+
+		// Other calls of the pallet are unaffected.
+		assert!(call(Action::Redeem, DOT, DOT_UNIT).is_ok());
+	});
+}
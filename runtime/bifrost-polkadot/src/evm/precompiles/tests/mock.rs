@@ -0,0 +1,166 @@
+// This file is part of Bifrost.
+
+// Copyright (C) Liebi Technologies PTE. LTD.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{evm::ExtendedAddressMapping, AccountId};
+use ::evm::{ExitError, ExitReason, ExitRevert, Transfer};
+use frame_support::storage::with_transaction;
+use pallet_evm::{AddressMapping, Context, Log, Precompile, PrecompileFailure, PrecompileHandle};
+use primitive_types::{H160, H256, U256};
+use sp_runtime::{DispatchError, TransactionOutcome};
+
+/// A precompile handle recording the gas used and the logs emitted by a single call.
+pub struct MockHandle {
+	pub input: Vec<u8>,
+	pub gas_limit: u64,
+	pub gas_used: u64,
+	pub logs: Vec<Log>,
+	pub context: Context,
+	pub is_static: bool,
+}
+
+impl MockHandle {
+	pub fn new(caller: H160, input: Vec<u8>) -> Self {
+		Self {
+			input,
+			gas_limit: u64::MAX,
+			gas_used: 0,
+			logs: vec![],
+			context: Context {
+				address: H160::zero(),
+				caller,
+				apparent_value: U256::zero(),
+			},
+			is_static: false,
+		}
+	}
+}
+
+impl PrecompileHandle for MockHandle {
+	fn call(
+		&mut self,
+		_: H160,
+		_: Option<Transfer>,
+		_: Vec<u8>,
+		_: Option<u64>,
+		_: bool,
+		_: &Context,
+	) -> (ExitReason, Vec<u8>) {
+		// The precompiles under test don't call other contracts.
+		(
+			ExitReason::Revert(ExitRevert::Reverted),
+			b"unsupported call".to_vec(),
+		)
+	}
+
+	fn record_cost(&mut self, cost: u64) -> Result<(), ExitError> {
+		self.gas_used = self.gas_used.saturating_add(cost);
+		if self.gas_used > self.gas_limit {
+			return Err(ExitError::OutOfGas);
+		}
+		Ok(())
+	}
+
+	fn record_external_cost(
+		&mut self,
+		_: Option<u64>,
+		_: Option<u64>,
+		_: Option<u64>,
+	) -> Result<(), ExitError> {
+		Ok(())
+	}
+
+	fn refund_external_cost(&mut self, _: Option<u64>, _: Option<u64>) {}
+
+	fn remaining_gas(&self) -> u64 {
+		self.gas_limit.saturating_sub(self.gas_used)
+	}
+
+	fn log(&mut self, address: H160, topics: Vec<H256>, data: Vec<u8>) -> Result<(), ExitError> {
+		self.logs.push(Log {
+			address,
+			topics,
+			data,
+		});
+		Ok(())
+	}
+
+	fn code_address(&self) -> H160 {
+		self.context.address
+	}
+
+	fn input(&self) -> &[u8] {
+		&self.input
+	}
+
+	fn context(&self) -> &Context {
+		&self.context
+	}
+
+	fn origin(&self) -> H160 {
+		self.context.caller
+	}
+
+	fn is_static(&self) -> bool {
+		self.is_static
+	}
+
+	fn gas_limit(&self) -> Option<u64> {
+		Some(self.gas_limit)
+	}
+
+	fn is_contract_being_constructed(&self, _: H160) -> bool {
+		false
+	}
+}
+
+/// Execute `P` with `input` on behalf of `caller`, returning the output or the revert reason.
+/// As in the EVM, the call runs in a storage transaction that is rolled back if it reverts.
+pub fn execute<P: Precompile>(caller: H160, input: Vec<u8>) -> Result<Vec<u8>, Vec<u8>> {
+	execute_at::<P>(H160::zero(), caller, input)
+}
+
+/// Execute `P` deployed at `address`, for the precompiles that depend on their own address.
+pub fn execute_at<P: Precompile>(
+	address: H160,
+	caller: H160,
+	input: Vec<u8>,
+) -> Result<Vec<u8>, Vec<u8>> {
+	let mut handle = MockHandle::new(caller, input);
+	handle.context.address = address;
+	let mut result = None;
+	let _ = with_transaction(|| -> TransactionOutcome<Result<(), DispatchError>> {
+		let output = P::execute(&mut handle);
+		let is_ok = output.is_ok();
+		result = Some(output);
+		if is_ok {
+			TransactionOutcome::Commit(Ok(()))
+		} else {
+			TransactionOutcome::Rollback(Ok(()))
+		}
+	});
+	match result.expect("the precompile is executed; qed") {
+		Ok(output) => Ok(output.output),
+		Err(PrecompileFailure::Revert { output, .. }) => Err(output),
+		Err(e) => panic!("unexpected precompile failure: {:?}", e),
+	}
+}
+
+/// The substrate account of an EVM address.
+pub fn account_of(address: H160) -> AccountId {
+	ExtendedAddressMapping::into_account_id(address).into()
+}
@@ -17,3 +17,8 @@
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
 mod erc20_mapping;
+mod lend_market;
+mod mock;
+mod multicurrency;
+mod stable_pool;
+mod vtoken_minting;
@@ -0,0 +1,345 @@
+// This file is part of Bifrost.
+
+// Copyright (C) Liebi Technologies PTE. LTD.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use super::mock::{account_of, execute_at};
+use crate::{
+	evm::precompiles::{
+		erc20_mapping::{BifrostErc20Mapping, Erc20Mapping},
+		handle::{EvmDataReader, EvmDataWriter},
+		multicurrency::{Action, AllowancesStorage, MultiCurrencyPrecompile},
+		Address,
+	},
+	Runtime, Tokens,
+};
+use bifrost_asset_registry::{AssetMetadata, CurrencyMetadatas};
+use bifrost_primitives::{Balance, CurrencyId, TokenSymbol::BNC, DOT};
+use orml_traits::MultiCurrency;
+use primitive_types::{H160, H256, U256};
+use sp_core::{ecdsa, Pair};
+use sp_io::hashing::keccak_256;
+
+type Precompile = MultiCurrencyPrecompile<Runtime>;
+
+const CURRENCY: CurrencyId = CurrencyId::Native(BNC);
+const OWNER: H160 = H160::repeat_byte(0x01);
+const SPENDER: H160 = H160::repeat_byte(0x02);
+const RECEIVER: H160 = H160::repeat_byte(0x03);
+const DOT_UNIT: Balance = 10_000_000_000;
+
+fn new_test_ext() -> sp_io::TestExternalities {
+	let mut ext = sp_io::TestExternalities::default();
+	ext.execute_with(|| {
+		CurrencyMetadatas::<Runtime>::insert(
+			DOT,
+			AssetMetadata {
+				name: b"Polkadot".to_vec(),
+				symbol: b"DOT".to_vec(),
+				decimals: 10,
+				minimal_balance: 1_000_000,
+			},
+		);
+	});
+	ext
+}
+
+fn dot_address() -> H160 {
+	BifrostErc20Mapping::encode_evm_address(DOT).unwrap()
+}
+
+fn call(caller: H160, input: Vec<u8>) -> Result<Vec<u8>, Vec<u8>> {
+	execute_at::<Precompile>(dot_address(), caller, input)
+}
+
+fn allowance(owner: H160, spender: H160) -> U256 {
+	let output = call(
+		owner,
+		EvmDataWriter::new_with_selector(Action::Allowance)
+			.write(Address(owner))
+			.write(Address(spender))
+			.build(),
+	)
+	.unwrap();
+	EvmDataReader::new(&output).read().unwrap()
+}
+
+fn nonce(owner: H160) -> U256 {
+	let output = call(
+		owner,
+		EvmDataWriter::new_with_selector(Action::Nonces)
+			.write(Address(owner))
+			.build(),
+	)
+	.unwrap();
+	EvmDataReader::new(&output).read().unwrap()
+}
+
+fn balance(who: H160) -> Balance {
+	Tokens::free_balance(DOT, &account_of(who))
+}
+
+/// The EVM address of the key of `pair`.
+fn address_of(pair: &ecdsa::Pair) -> H160 {
+	let message = [0u8; 32];
+	let signature = pair.sign_prehashed(&message);
+	let public = sp_io::crypto::secp256k1_ecdsa_recover(&signature.0, &message).unwrap();
+	H160::from(H256::from_slice(&keccak_256(&public)))
+}
+
+/// Sign a permit of `value` for `SPENDER` with `pair`, returning `v`, `r` and `s`.
+fn sign_permit(pair: &ecdsa::Pair, value: U256, deadline: U256) -> (u8, H256, H256) {
+	let owner = address_of(pair);
+	let digest = Precompile::generate_permit(
+		DOT,
+		dot_address(),
+		owner,
+		SPENDER,
+		value,
+		nonce(owner),
+		deadline,
+	)
+	.unwrap();
+	let signature = pair.sign_prehashed(&digest).0;
+	(
+		signature[64] + 27,
+		H256::from_slice(&signature[0..32]),
+		H256::from_slice(&signature[32..64]),
+	)
+}
+
+fn permit(
+	owner: H160,
+	value: U256,
+	deadline: U256,
+	(v, r, s): (u8, H256, H256),
+) -> Result<Vec<u8>, Vec<u8>> {
+	call(
+		SPENDER,
+		EvmDataWriter::new_with_selector(Action::Permit)
+			.write(Address(owner))
+			.write(Address(SPENDER))
+			.write(value)
+			.write(deadline)
+			.write(v)
+			.write(r)
+			.write(s)
+			.build(),
+	)
+}
+
+#[test]
+fn approve_and_spend_allowance_should_work() {
+	sp_io::TestExternalities::default().execute_with(|| {
+		Precompile::do_approve(CURRENCY, OWNER, SPENDER, U256::from(100));
+		assert_eq!(AllowancesStorage::get((CURRENCY, OWNER, SPENDER)), 100);
+
+		assert!(Precompile::spend_allowance(CURRENCY, OWNER, SPENDER, 101).is_err());
+		assert!(Precompile::spend_allowance(CURRENCY, OWNER, SPENDER, 40).is_ok());
+		assert_eq!(AllowancesStorage::get((CURRENCY, OWNER, SPENDER)), 60);
+
+		assert!(Precompile::spend_allowance(CURRENCY, OWNER, SPENDER, 60).is_ok());
+		assert!(!AllowancesStorage::contains_key((CURRENCY, OWNER, SPENDER)));
+
+		// Allowances are kept per currency and per spender.
+		assert_eq!(
+			AllowancesStorage::get((CurrencyId::Token2(0), OWNER, SPENDER)),
+			0
+		);
+		assert_eq!(AllowancesStorage::get((CURRENCY, SPENDER, OWNER)), 0);
+	});
+}
+
+#[test]
+fn infinite_allowance_should_not_decrease() {
+	sp_io::TestExternalities::default().execute_with(|| {
+		Precompile::do_approve(CURRENCY, OWNER, SPENDER, U256::MAX);
+		assert_eq!(
+			AllowancesStorage::get((CURRENCY, OWNER, SPENDER)),
+			Balance::MAX
+		);
+
+		assert!(Precompile::spend_allowance(CURRENCY, OWNER, SPENDER, 1_000).is_ok());
+		assert_eq!(
+			AllowancesStorage::get((CURRENCY, OWNER, SPENDER)),
+			Balance::MAX
+		);
+	});
+}
+
+#[test]
+fn permit_digest_should_depend_on_asset_and_nonce() {
+	sp_io::TestExternalities::default().execute_with(|| {
+		let address = BifrostErc20Mapping::encode_evm_address(CURRENCY).unwrap();
+		assert!(Precompile::compute_domain_separator(CURRENCY, address).is_err());
+
+		for currency_id in [CURRENCY, CurrencyId::Token2(0)] {
+			CurrencyMetadatas::<Runtime>::insert(
+				currency_id,
+				AssetMetadata {
+					name: b"Bifrost Native Coin".to_vec(),
+					symbol: b"BNC".to_vec(),
+					decimals: 12,
+					minimal_balance: 0,
+				},
+			);
+		}
+		let other_address = BifrostErc20Mapping::encode_evm_address(CurrencyId::Token2(0)).unwrap();
+
+		assert_ne!(
+			Precompile::compute_domain_separator(CURRENCY, address).unwrap(),
+			Precompile::compute_domain_separator(CurrencyId::Token2(0), other_address).unwrap()
+		);
+
+		let permit = |nonce: u64| {
+			Precompile::generate_permit(
+				CURRENCY,
+				address,
+				OWNER,
+				SPENDER,
+				U256::from(100),
+				U256::from(nonce),
+				U256::MAX,
+			)
+			.unwrap()
+		};
+		assert_eq!(permit(0), permit(0));
+		assert_ne!(permit(0), permit(1));
+	});
+}
+
+#[test]
+fn approve_and_transfer_from_should_work() {
+	new_test_ext().execute_with(|| {
+		assert!(Tokens::deposit(DOT, &account_of(OWNER), 100 * DOT_UNIT).is_ok());
+
+		assert_eq!(
+			call(
+				OWNER,
+				EvmDataWriter::new_with_selector(Action::Approve)
+					.write(Address(SPENDER))
+					.write(U256::from(10 * DOT_UNIT))
+					.build(),
+			),
+			Ok(EvmDataWriter::new().write(true).build())
+		);
+		assert_eq!(allowance(OWNER, SPENDER), U256::from(10 * DOT_UNIT));
+		assert_eq!(allowance(SPENDER, OWNER), U256::zero());
+
+		let transfer_from = |amount: Balance| {
+			call(
+				SPENDER,
+				EvmDataWriter::new_with_selector(Action::TransferFrom)
+					.write(Address(OWNER))
+					.write(Address(RECEIVER))
+					.write(amount)
+					.build(),
+			)
+		};
+		assert_eq!(
+			transfer_from(11 * DOT_UNIT),
+			Err(b"ERC20: insufficient allowance".to_vec())
+		);
+		assert_eq!(balance(OWNER), 100 * DOT_UNIT);
+		assert_eq!(allowance(OWNER, SPENDER), U256::from(10 * DOT_UNIT));
+
+		assert!(transfer_from(4 * DOT_UNIT).is_ok());
+		assert_eq!(balance(OWNER), 96 * DOT_UNIT);
+		assert_eq!(balance(RECEIVER), 4 * DOT_UNIT);
+		assert_eq!(allowance(OWNER, SPENDER), U256::from(6 * DOT_UNIT));
+
+		assert!(transfer_from(6 * DOT_UNIT).is_ok());
+		assert_eq!(allowance(OWNER, SPENDER), U256::zero());
+		assert!(transfer_from(1).is_err());
+		assert_eq!(balance(OWNER), 90 * DOT_UNIT);
+		assert_eq!(balance(RECEIVER), 10 * DOT_UNIT);
+	});
+}
+
+#[test]
+fn permit_should_work() {
+	new_test_ext().execute_with(|| {
+		let pair = ecdsa::Pair::from_seed(&[1u8; 32]);
+		let owner = address_of(&pair);
+		let value = U256::from(10 * DOT_UNIT);
+		let signature = sign_permit(&pair, value, U256::MAX);
+
+		// A permit signed by another key is rejected.
+		assert_eq!(
+			permit(RECEIVER, value, U256::MAX, signature),
+			Err(b"Invalid permit".to_vec())
+		);
+
+		assert!(permit(owner, value, U256::MAX, signature).is_ok());
+		assert_eq!(allowance(owner, SPENDER), value);
+		assert_eq!(nonce(owner), U256::one());
+
+		// The nonce is consumed, so the same permit can not be replayed.
+		assert_eq!(
+			permit(owner, U256::from(20 * DOT_UNIT), U256::MAX, signature),
+			Err(b"Invalid permit".to_vec())
+		);
+		assert_eq!(
+			permit(owner, value, U256::MAX, signature),
+			Err(b"Invalid permit".to_vec())
+		);
+		assert_eq!(nonce(owner), U256::one());
+
+		let signature = sign_permit(&pair, U256::zero(), U256::MAX);
+		assert!(permit(owner, U256::zero(), U256::MAX, signature).is_ok());
+		assert_eq!(allowance(owner, SPENDER), U256::zero());
+		assert_eq!(nonce(owner), U256::from(2));
+	});
+}
+
+#[test]
+fn permit_should_reject_expired_and_malleable_signatures() {
+	new_test_ext().execute_with(|| {
+		let pair = ecdsa::Pair::from_seed(&[1u8; 32]);
+		let owner = address_of(&pair);
+		let value = U256::from(10 * DOT_UNIT);
+
+		// The timestamp is in ms while the deadline is in seconds.
+		pallet_timestamp::Now::<Runtime>::put(1_000_000);
+		let signature = sign_permit(&pair, value, U256::from(999));
+		assert_eq!(
+			permit(owner, value, U256::from(999), signature),
+			Err(b"Permit expired".to_vec())
+		);
+
+		// The same signature with `s` replaced by `n - s` also recovers the owner.
+		let (v, r, s) = sign_permit(&pair, value, U256::from(1_000));
+		let order = U256::from_big_endian(&hex_literal::hex!(
+			"fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141"
+		));
+		let mut high_s = [0u8; 32];
+		(order - U256::from_big_endian(s.as_bytes())).to_big_endian(&mut high_s);
+		let flipped_v = if v == 27 { 28 } else { 27 };
+		assert_eq!(
+			permit(
+				owner,
+				value,
+				U256::from(1_000),
+				(flipped_v, r, H256(high_s))
+			),
+			Err(b"Invalid permit".to_vec())
+		);
+		assert_eq!(nonce(owner), U256::zero());
+
+		assert!(permit(owner, value, U256::from(1_000), (v, r, s)).is_ok());
+		assert_eq!(allowance(owner, SPENDER), value);
+	});
+}
@@ -0,0 +1,334 @@
+// This file is part of Bifrost.
+
+// Copyright (C) Liebi Technologies PTE. LTD.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use super::mock::{account_of, execute};
+use crate::{
+	evm::precompiles::{
+		erc20_mapping::{BifrostErc20Mapping, Erc20Mapping},
+		handle::{EvmDataReader, EvmDataWriter},
+		stable_pool::{Action, StablePoolPrecompile},
+		Address,
+	},
+	Runtime, StablePool, Timestamp, Tokens, TxPause,
+};
+use bifrost_asset_registry::{AssetMetadata, CurrencyMetadatas};
+use bifrost_primitives::{Balance, CurrencyId, BNC, DOT, VDOT};
+use frame_system::RawOrigin;
+use orml_traits::MultiCurrency;
+use primitive_types::{H160, U256};
+use sp_runtime::BoundedVec;
+
+type Precompile = StablePoolPrecompile<Runtime>;
+
+const ALICE: H160 = H160::repeat_byte(0xaa);
+const FEE_RECIPIENT: H160 = H160::repeat_byte(0xfe);
+const DOT_UNIT: Balance = 10_000_000_000;
+const POOL_ID: u32 = 0;
+const LP: CurrencyId = CurrencyId::BLP(0);
+
+fn address(currency_id: CurrencyId) -> Address {
+	BifrostErc20Mapping::encode_evm_address(currency_id)
+		.unwrap()
+		.into()
+}
+
+fn new_test_ext() -> sp_io::TestExternalities {
+	let mut ext = sp_io::TestExternalities::default();
+	ext.execute_with(|| {
+		CurrencyMetadatas::<Runtime>::insert(
+			VDOT,
+			AssetMetadata {
+				name: b"Voucher DOT".to_vec(),
+				symbol: b"vDOT".to_vec(),
+				decimals: 10,
+				minimal_balance: 0,
+			},
+		);
+		assert!(StablePool::create_pool(
+			RawOrigin::Root.into(),
+			vec![DOT, VDOT],
+			vec![100_000_000, 100_000_000],
+			10_000_000,
+			20_000_000,
+			50_000_000,
+			10_000,
+			account_of(FEE_RECIPIENT),
+			account_of(FEE_RECIPIENT),
+			1_000_000_000_000_000_000,
+		)
+		.is_ok());
+		assert!(StablePool::edit_token_rate(
+			RawOrigin::Root.into(),
+			POOL_ID,
+			vec![(DOT, (1, 1)), (VDOT, (1, 1))],
+		)
+		.is_ok());
+		assert!(Tokens::deposit(DOT, &account_of(ALICE), 100 * DOT_UNIT).is_ok());
+		assert!(Tokens::deposit(VDOT, &account_of(ALICE), 100 * DOT_UNIT).is_ok());
+	});
+	ext
+}
+
+fn deadline() -> U256 {
+	U256::MAX
+}
+
+fn add_liquidity(amounts: Vec<Balance>, min_mint_amount: Balance) -> Result<Vec<u8>, Vec<u8>> {
+	execute::<Precompile>(
+		ALICE,
+		EvmDataWriter::new_with_selector(Action::AddLiquidity)
+			.write(POOL_ID)
+			.write(amounts)
+			.write(min_mint_amount)
+			.write(deadline())
+			.build(),
+	)
+}
+
+fn swap(
+	currency_in: CurrencyId,
+	currency_out: CurrencyId,
+	amount_in: Balance,
+	min_amount_out: Balance,
+	deadline: U256,
+) -> Result<Vec<u8>, Vec<u8>> {
+	execute::<Precompile>(
+		ALICE,
+		EvmDataWriter::new_with_selector(Action::Swap)
+			.write(POOL_ID)
+			.write(address(currency_in))
+			.write(address(currency_out))
+			.write(amount_in)
+			.write(min_amount_out)
+			.write(deadline)
+			.build(),
+	)
+}
+
+fn quote(
+	action: Action,
+	currency_in: CurrencyId,
+	currency_out: CurrencyId,
+	amount: Balance,
+) -> Result<Vec<u8>, Vec<u8>> {
+	execute::<Precompile>(
+		ALICE,
+		EvmDataWriter::new_with_selector(action)
+			.write(POOL_ID)
+			.write(address(currency_in))
+			.write(address(currency_out))
+			.write(amount)
+			.build(),
+	)
+}
+
+fn add_liquidity_amount(amounts: Vec<Balance>) -> Result<Vec<u8>, Vec<u8>> {
+	execute::<Precompile>(
+		ALICE,
+		EvmDataWriter::new_with_selector(Action::AddLiquidityAmount)
+			.write(POOL_ID)
+			.write(amounts)
+			.build(),
+	)
+}
+
+fn redeem_proportion(
+	amount: Balance,
+	min_redeem_amounts: Vec<Balance>,
+) -> Result<Vec<u8>, Vec<u8>> {
+	execute::<Precompile>(
+		ALICE,
+		EvmDataWriter::new_with_selector(Action::RedeemProportion)
+			.write(POOL_ID)
+			.write(amount)
+			.write(min_redeem_amounts)
+			.write(deadline())
+			.build(),
+	)
+}
+
+fn redeem_single(
+	amount: Balance,
+	currency_out: CurrencyId,
+	min_amount_out: Balance,
+) -> Result<Vec<u8>, Vec<u8>> {
+	execute::<Precompile>(
+		ALICE,
+		EvmDataWriter::new_with_selector(Action::RedeemSingle)
+			.write(POOL_ID)
+			.write(amount)
+			.write(address(currency_out))
+			.write(min_amount_out)
+			.write(deadline())
+			.build(),
+	)
+}
+
+fn read_balance(output: Vec<u8>) -> Balance {
+	EvmDataReader::new(&output).read::<Balance>().unwrap()
+}
+
+fn balance(currency_id: CurrencyId) -> Balance {
+	Tokens::free_balance(currency_id, &account_of(ALICE))
+}
+
+#[test]
+fn add_liquidity_should_mint_the_quoted_amount() {
+	new_test_ext().execute_with(|| {
+		let amounts = vec![10 * DOT_UNIT, 10 * DOT_UNIT];
+		let quoted = read_balance(add_liquidity_amount(amounts.clone()).unwrap());
+		assert!(quoted > 0);
+
+		assert_eq!(
+			add_liquidity(amounts.clone(), quoted + 1),
+			Err(b"MintUnderMin".to_vec())
+		);
+		assert_eq!(balance(DOT), 100 * DOT_UNIT);
+
+		let minted = read_balance(add_liquidity(amounts, quoted).unwrap());
+		assert_eq!(minted, quoted);
+		assert_eq!(balance(LP), minted);
+		assert_eq!(balance(DOT), 90 * DOT_UNIT);
+		assert_eq!(balance(VDOT), 90 * DOT_UNIT);
+	});
+}
+
+#[test]
+fn swap_should_pay_the_quoted_output() {
+	new_test_ext().execute_with(|| {
+		assert!(add_liquidity(vec![50 * DOT_UNIT, 50 * DOT_UNIT], 0).is_ok());
+
+		let quoted = read_balance(quote(Action::GetSwapOutput, DOT, VDOT, DOT_UNIT).unwrap());
+		assert!(quoted > 0 && quoted < DOT_UNIT);
+
+		assert_eq!(
+			swap(DOT, VDOT, DOT_UNIT, quoted + 1, deadline()),
+			Err(b"SwapUnderMin".to_vec())
+		);
+		assert_eq!(balance(DOT), 50 * DOT_UNIT);
+
+		let amount_out = read_balance(swap(DOT, VDOT, DOT_UNIT, quoted, deadline()).unwrap());
+		assert_eq!(amount_out, quoted);
+		assert_eq!(balance(DOT), 49 * DOT_UNIT);
+		assert_eq!(balance(VDOT), 50 * DOT_UNIT + quoted);
+	});
+}
+
+#[test]
+fn get_swap_input_should_cover_the_requested_output() {
+	new_test_ext().execute_with(|| {
+		assert!(add_liquidity(vec![50 * DOT_UNIT, 50 * DOT_UNIT], 0).is_ok());
+
+		let amount_in = read_balance(quote(Action::GetSwapInput, DOT, VDOT, DOT_UNIT).unwrap());
+		assert!(amount_in > DOT_UNIT);
+
+		let amount_out = read_balance(swap(DOT, VDOT, amount_in, DOT_UNIT, deadline()).unwrap());
+		assert!(amount_out >= DOT_UNIT);
+	});
+}
+
+#[test]
+fn redeem_proportion_should_return_every_asset() {
+	new_test_ext().execute_with(|| {
+		let minted = read_balance(add_liquidity(vec![10 * DOT_UNIT, 10 * DOT_UNIT], 0).unwrap());
+
+		assert_eq!(
+			redeem_proportion(minted / 2, vec![10 * DOT_UNIT, 0]),
+			Err(b"RedeemUnderMin".to_vec())
+		);
+		assert_eq!(balance(LP), minted);
+
+		let output = redeem_proportion(minted / 2, vec![0, 0]).unwrap();
+		let redeemed = EvmDataReader::new(&output).read::<Vec<Balance>>().unwrap();
+		assert_eq!(redeemed.len(), 2);
+		assert!(redeemed[0] > 0 && redeemed[1] > 0);
+		assert_eq!(balance(DOT), 90 * DOT_UNIT + redeemed[0]);
+		assert_eq!(balance(VDOT), 90 * DOT_UNIT + redeemed[1]);
+		assert_eq!(balance(LP), minted - minted / 2);
+	});
+}
+
+#[test]
+fn redeem_single_should_return_one_asset() {
+	new_test_ext().execute_with(|| {
+		let minted = read_balance(add_liquidity(vec![10 * DOT_UNIT, 10 * DOT_UNIT], 0).unwrap());
+
+		let amount_out = read_balance(redeem_single(minted / 4, DOT, 0).unwrap());
+		assert!(amount_out > 0);
+		assert_eq!(balance(DOT), 90 * DOT_UNIT + amount_out);
+		assert_eq!(balance(VDOT), 90 * DOT_UNIT);
+
+		assert!(redeem_single(minted / 4, DOT, 10 * DOT_UNIT).is_err());
+		assert_eq!(balance(DOT), 90 * DOT_UNIT + amount_out);
+	});
+}
+
+#[test]
+fn invalid_calls_should_revert() {
+	new_test_ext().execute_with(|| {
+		assert!(add_liquidity(vec![50 * DOT_UNIT, 50 * DOT_UNIT], 0).is_ok());
+
+		assert_eq!(
+			swap(DOT, BNC, DOT_UNIT, 0, deadline()),
+			Err(b"currency not in pool".to_vec())
+		);
+		assert_eq!(
+			quote(Action::GetSwapOutput, BNC, VDOT, DOT_UNIT),
+			Err(b"currency not in pool".to_vec())
+		);
+
+		// Timestamps are in ms while the deadline is in seconds.
+		Timestamp::set_timestamp(10_000);
+		assert_eq!(
+			swap(DOT, VDOT, DOT_UNIT, 0, U256::from(9)),
+			Err(b"transaction expired".to_vec())
+		);
+		assert!(swap(DOT, VDOT, DOT_UNIT, 0, U256::from(10)).is_ok());
+	});
+}
+
+#[test]
+fn paused_calls_should_revert() {
+	new_test_ext().execute_with(|| {
+		let minted = read_balance(add_liquidity(vec![10 * DOT_UNIT, 10 * DOT_UNIT], 0).unwrap());
+		for call in [
+			b"swap".to_vec(),
+			b"add_liquidity".to_vec(),
+			b"redeem_proportion".to_vec(),
+			b"redeem_single".to_vec(),
+		] {
+			assert!(TxPause::pause(
+				RawOrigin::Root.into(),
+				(
+					BoundedVec::truncate_from(b"StablePool".to_vec()),
+					BoundedVec::truncate_from(call)
+				)
+			)
+			.is_ok());
+		}
+
+		let filtered = Err(b"CallFiltered".to_vec());
+		assert_eq!(swap(DOT, VDOT, DOT_UNIT, 0, deadline()), filtered);
+		assert_eq!(add_liquidity(vec![DOT_UNIT, DOT_UNIT], 0), filtered);
+		assert_eq!(redeem_proportion(minted / 2, vec![0, 0]), filtered);
+		assert_eq!(redeem_single(minted / 2, DOT, 0), filtered);
+		assert_eq!(balance(DOT), 90 * DOT_UNIT);
+		assert_eq!(balance(VDOT), 90 * DOT_UNIT);
+		assert_eq!(balance(LP), minted);
+	});
+}
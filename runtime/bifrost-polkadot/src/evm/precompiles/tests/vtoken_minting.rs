@@ -0,0 +1,263 @@
+// This file is part of Bifrost.
+
+// Copyright (C) Liebi Technologies PTE. LTD.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use super::mock::{account_of, execute, MockHandle};
+use crate::{
+	evm::precompiles::{
+		erc20_mapping::{BifrostErc20Mapping, Erc20Mapping},
+		handle::{EvmDataReader, EvmDataWriter},
+		vtoken_minting::{Action, VtokenMintingPrecompile},
+		Address, Bytes,
+	},
+	Runtime, Tokens, TxPause,
+};
+use bifrost_asset_registry::{AssetMetadata, CurrencyMetadatas};
+use bifrost_primitives::{Balance, CurrencyId, TimeUnit, DOT, VDOT};
+use bifrost_vtoken_minting::{
+	MinimumMint, OngoingTimeUnit, TokenPool, TokenToRebond, UnlockDuration,
+};
+use frame_system::RawOrigin;
+use orml_traits::MultiCurrency;
+use primitive_types::H160;
+use sp_runtime::BoundedVec;
+
+type Precompile = VtokenMintingPrecompile<Runtime>;
+
+const ALICE: H160 = H160::repeat_byte(0xaa);
+const DOT_UNIT: Balance = 10_000_000_000;
+
+fn address(currency_id: CurrencyId) -> Address {
+	BifrostErc20Mapping::encode_evm_address(currency_id)
+		.unwrap()
+		.into()
+}
+
+fn new_test_ext() -> sp_io::TestExternalities {
+	let mut ext = sp_io::TestExternalities::default();
+	ext.execute_with(|| {
+		CurrencyMetadatas::<Runtime>::insert(
+			VDOT,
+			AssetMetadata {
+				name: b"Voucher DOT".to_vec(),
+				symbol: b"vDOT".to_vec(),
+				decimals: 10,
+				minimal_balance: 0,
+			},
+		);
+		OngoingTimeUnit::<Runtime>::insert(DOT, TimeUnit::Era(1));
+		UnlockDuration::<Runtime>::insert(DOT, TimeUnit::Era(28));
+		TokenToRebond::<Runtime>::insert(DOT, 0);
+		assert!(Tokens::deposit(DOT, &account_of(ALICE), 100 * DOT_UNIT).is_ok());
+	});
+	ext
+}
+
+fn mint(amount: Balance) -> Result<Vec<u8>, Vec<u8>> {
+	execute::<Precompile>(
+		ALICE,
+		EvmDataWriter::new_with_selector(Action::Mint)
+			.write(address(DOT))
+			.write(amount)
+			.write(Bytes(b"evm".to_vec()))
+			.build(),
+	)
+}
+
+fn redeem(amount: Balance) -> Result<Vec<u8>, Vec<u8>> {
+	execute::<Precompile>(
+		ALICE,
+		EvmDataWriter::new_with_selector(Action::Redeem)
+			.write(address(VDOT))
+			.write(amount)
+			.build(),
+	)
+}
+
+fn rebond(amount: Balance) -> Result<Vec<u8>, Vec<u8>> {
+	execute::<Precompile>(
+		ALICE,
+		EvmDataWriter::new_with_selector(Action::Rebond)
+			.write(address(DOT))
+			.write(amount)
+			.build(),
+	)
+}
+
+fn unlock_queue() -> (Vec<u32>, Vec<Balance>, Vec<u32>) {
+	let output = execute::<Precompile>(
+		ALICE,
+		EvmDataWriter::new_with_selector(Action::UnlockQueue)
+			.write(Address(ALICE))
+			.write(address(DOT))
+			.build(),
+	)
+	.unwrap();
+	let mut reader = EvmDataReader::new(&output);
+	(
+		reader.read().unwrap(),
+		reader.read().unwrap(),
+		reader.read().unwrap(),
+	)
+}
+
+fn balance(currency_id: CurrencyId) -> Balance {
+	Tokens::free_balance(currency_id, &account_of(ALICE))
+}
+
+#[test]
+fn mint_should_work() {
+	new_test_ext().execute_with(|| {
+		let output = mint(10 * DOT_UNIT).unwrap();
+		assert_eq!(
+			EvmDataReader::new(&output).read::<Balance>().unwrap(),
+			10 * DOT_UNIT
+		);
+
+		assert_eq!(balance(DOT), 90 * DOT_UNIT);
+		assert_eq!(balance(VDOT), 10 * DOT_UNIT);
+		assert_eq!(TokenPool::<Runtime>::get(DOT), 10 * DOT_UNIT);
+	});
+}
+
+#[test]
+fn mint_should_revert_with_the_dispatch_error() {
+	new_test_ext().execute_with(|| {
+		MinimumMint::<Runtime>::insert(DOT, 5 * DOT_UNIT);
+		assert_eq!(mint(DOT_UNIT), Err(b"BelowMinimumMint".to_vec()));
+		assert!(mint(101 * DOT_UNIT).is_err());
+
+		assert_eq!(balance(DOT), 100 * DOT_UNIT);
+		assert_eq!(balance(VDOT), 0);
+
+		// A state-changing call is rejected in a static context.
+		let mut handle = MockHandle::new(
+			ALICE,
+			EvmDataWriter::new_with_selector(Action::Mint)
+				.write(address(DOT))
+				.write(10 * DOT_UNIT)
+				.write(Bytes(vec![]))
+				.build(),
+		);
+		handle.is_static = true;
+		assert!(<Precompile as pallet_evm::Precompile>::execute(&mut handle).is_err());
+		assert_eq!(balance(VDOT), 0);
+	});
+}
+
+#[test]
+fn redeem_should_queue_an_unlock() {
+	new_test_ext().execute_with(|| {
+		assert!(mint(10 * DOT_UNIT).is_ok());
+		assert!(redeem(4 * DOT_UNIT).is_ok());
+
+		assert_eq!(balance(VDOT), 6 * DOT_UNIT);
+		assert_eq!(TokenPool::<Runtime>::get(DOT), 6 * DOT_UNIT);
+		assert_eq!(unlock_queue(), (vec![0], vec![4 * DOT_UNIT], vec![29]));
+
+		assert!(redeem(7 * DOT_UNIT).is_err());
+		assert_eq!(unlock_queue(), (vec![0], vec![4 * DOT_UNIT], vec![29]));
+	});
+}
+
+#[test]
+fn rebond_should_cancel_the_unlock() {
+	new_test_ext().execute_with(|| {
+		assert!(mint(10 * DOT_UNIT).is_ok());
+		assert!(redeem(4 * DOT_UNIT).is_ok());
+
+		assert!(rebond(DOT_UNIT).is_ok());
+		assert_eq!(balance(VDOT), 7 * DOT_UNIT);
+		assert_eq!(TokenPool::<Runtime>::get(DOT), 7 * DOT_UNIT);
+		assert_eq!(TokenToRebond::<Runtime>::get(DOT), Some(DOT_UNIT));
+		assert_eq!(unlock_queue(), (vec![0], vec![3 * DOT_UNIT], vec![29]));
+
+		assert_eq!(
+			rebond(4 * DOT_UNIT),
+			Err(b"NotEnoughBalanceToUnlock".to_vec())
+		);
+
+		assert!(rebond(3 * DOT_UNIT).is_ok());
+		assert_eq!(balance(VDOT), 10 * DOT_UNIT);
+		assert_eq!(unlock_queue(), (vec![], vec![], vec![]));
+	});
+}
+
+#[test]
+fn invalid_currency_should_revert() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(
+			execute::<Precompile>(
+				ALICE,
+				EvmDataWriter::new_with_selector(Action::Mint)
+					.write(Address(H160::repeat_byte(0x01)))
+					.write(DOT_UNIT)
+					.write(Bytes(vec![]))
+					.build(),
+			),
+			Err(b"invalid currency id".to_vec())
+		);
+	});
+}
+
+#[test]
+fn unlock_queue_output_should_round_trip() {
+	let unlock_ids: Vec<u32> = vec![0, 3, 7];
+	let amounts: Vec<Balance> = vec![100, 2_000, 30_000];
+	let time_units: Vec<u32> = vec![10, 11, 12];
+
+	let output = EvmDataWriter::new()
+		.write(unlock_ids.clone())
+		.write(amounts.clone())
+		.write(time_units.clone())
+		.build();
+
+	let mut reader = EvmDataReader::new(&output);
+	assert_eq!(reader.read::<Vec<u32>>().unwrap(), unlock_ids);
+	assert_eq!(reader.read::<Vec<Balance>>().unwrap(), amounts);
+	assert_eq!(reader.read::<Vec<u32>>().unwrap(), time_units);
+
+	// An empty queue is encoded as three empty arrays.
+	let output = EvmDataWriter::new()
+		.write(Vec::<u32>::new())
+		.write(Vec::<Balance>::new())
+		.write(Vec::<u32>::new())
+		.build();
+	assert_eq!(output.len(), 32 * 6);
+}
+
+#[test]
+fn paused_calls_should_revert() {
+	new_test_ext().execute_with(|| {
+		for call in [b"mint".to_vec(), b"redeem".to_vec(), b"rebond".to_vec()] {
+			assert!(TxPause::pause(
+				RawOrigin::Root.into(),
+				(
+					BoundedVec::truncate_from(b"VtokenMinting".to_vec()),
+					BoundedVec::truncate_from(call)
+				)
+			)
+			.is_ok());
+		}
+
+		assert_eq!(mint(10 * DOT_UNIT), Err(b"CallFiltered".to_vec()));
+		assert_eq!(redeem(DOT_UNIT), Err(b"CallFiltered".to_vec()));
+		assert_eq!(rebond(DOT_UNIT), Err(b"CallFiltered".to_vec()));
+		assert_eq!(balance(DOT), 100 * DOT_UNIT);
+		assert_eq!(balance(VDOT), 0);
+	});
+}
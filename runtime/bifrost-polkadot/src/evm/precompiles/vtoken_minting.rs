@@ -0,0 +1,321 @@
+// This file is part of Bifrost.
+
+// Copyright (C) Liebi Technologies PTE. LTD.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::evm::{
+	precompiles::{
+		erc20_mapping::{BifrostErc20Mapping, Erc20Mapping},
+		handle::{EvmDataWriter, FunctionModifier, PrecompileHandleExt},
+		revert,
+		substrate::RuntimeHelper,
+		succeed, Address, Bytes, EvmResult,
+	},
+	ExtendedAddressMapping,
+};
+use bifrost_primitives::{Balance, CurrencyId, VtokenMintingInterface};
+use bifrost_vtoken_minting::WeightInfo;
+use num_enum::{IntoPrimitive, TryFromPrimitive};
+use pallet_evm::{AddressMapping, Precompile, PrecompileHandle, PrecompileResult};
+use primitive_types::H160;
+use sp_runtime::{BoundedVec, DispatchError, RuntimeDebug};
+use sp_std::{marker::PhantomData, prelude::*};
+
+#[module_evm_utility_macro::generate_function_selector]
+#[derive(RuntimeDebug, Eq, PartialEq, TryFromPrimitive, IntoPrimitive)]
+#[repr(u32)]
+pub enum Action {
+	Mint = "mint(address,uint256,bytes)",
+	Redeem = "redeem(address,uint256)",
+	Rebond = "rebond(address,uint256)",
+	TokenToVToken = "tokenToVToken(address,uint256)",
+	VTokenToToken = "vTokenToToken(address,uint256)",
+	TokenPool = "tokenPool(address)",
+	UnlockQueue = "unlockQueue(address,address)",
+}
+
+/// Precompile exposing the vToken minting, redeeming and rebonding of `bifrost_vtoken_minting`
+/// to the EVM. Tokens and vTokens are identified by their ERC-20 mapped addresses. A call paused
+/// by the runtime call filter reverts, as the matching extrinsic would fail.
+pub struct VtokenMintingPrecompile<Runtime>(PhantomData<Runtime>);
+
+impl<Runtime> Precompile for VtokenMintingPrecompile<Runtime>
+where
+	Runtime: frame_system::Config + pallet_evm::Config + bifrost_vtoken_minting::Config,
+	bifrost_vtoken_minting::Pallet<Runtime>:
+		VtokenMintingInterface<Runtime::AccountId, CurrencyId, Balance>,
+	<Runtime as frame_system::Config>::AccountId: core::convert::From<sp_runtime::AccountId32>,
+	<Runtime as frame_system::Config>::RuntimeCall: From<bifrost_vtoken_minting::Call<Runtime>>,
+	bifrost_vtoken_minting::BalanceOf<Runtime>: From<Balance>,
+{
+	fn execute(handle: &mut impl PrecompileHandle) -> PrecompileResult {
+		let selector = handle.read_selector()?;
+
+		handle.check_function_modifier(match selector {
+			Action::Mint | Action::Redeem | Action::Rebond => FunctionModifier::NonPayable,
+			_ => FunctionModifier::View,
+		})?;
+
+		match selector {
+			Action::Mint => Self::mint(handle),
+			Action::Redeem => Self::redeem(handle),
+			Action::Rebond => Self::rebond(handle),
+			Action::TokenToVToken => Self::token_to_vtoken(handle),
+			Action::VTokenToToken => Self::vtoken_to_token(handle),
+			Action::TokenPool => Self::token_pool(handle),
+			Action::UnlockQueue => Self::unlock_queue(handle),
+		}
+	}
+}
+
+impl<Runtime> VtokenMintingPrecompile<Runtime>
+where
+	Runtime: frame_system::Config + pallet_evm::Config + bifrost_vtoken_minting::Config,
+	bifrost_vtoken_minting::Pallet<Runtime>:
+		VtokenMintingInterface<Runtime::AccountId, CurrencyId, Balance>,
+	<Runtime as frame_system::Config>::AccountId: core::convert::From<sp_runtime::AccountId32>,
+	<Runtime as frame_system::Config>::RuntimeCall: From<bifrost_vtoken_minting::Call<Runtime>>,
+	bifrost_vtoken_minting::BalanceOf<Runtime>: From<Balance>,
+{
+	fn mint(handle: &mut impl PrecompileHandle) -> PrecompileResult {
+		handle.record_cost(RuntimeHelper::<Runtime>::weight_to_gas(
+			<Runtime as bifrost_vtoken_minting::Config>::WeightInfo::mint(),
+		))?;
+
+		// Parse input
+		let mut input = handle.read_input()?;
+		input.expect_arguments(3)?;
+
+		let token_id = Self::read_currency_id(input.read::<Address>()?)?;
+		let token_amount = input.read::<Balance>()?;
+		let remark: BoundedVec<u8, _> = input
+			.read::<Bytes>()?
+			.0
+			.try_into()
+			.map_err(|_| revert("remark is too long"))?;
+
+		let minter = Self::caller(handle);
+
+		RuntimeHelper::<Runtime>::ensure_call_allowed(
+			bifrost_vtoken_minting::Call::<Runtime>::mint {
+				currency_id: token_id,
+				currency_amount: token_amount.into(),
+				remark: remark.clone(),
+				channel_id: None,
+			},
+		)?;
+
+		log::debug!(target: "evm", "vtoken-minting: mint minter: {:?}, token: {:?}, amount: {:?}", minter, token_id, token_amount);
+
+		let vtoken_amount = <bifrost_vtoken_minting::Pallet<Runtime> as VtokenMintingInterface<
+			Runtime::AccountId,
+			CurrencyId,
+			Balance,
+		>>::mint(minter, token_id, token_amount, remark, None)
+		.map_err(Self::dispatch_error)?;
+
+		Ok(succeed(EvmDataWriter::new().write(vtoken_amount).build()))
+	}
+
+	fn redeem(handle: &mut impl PrecompileHandle) -> PrecompileResult {
+		handle.record_cost(RuntimeHelper::<Runtime>::weight_to_gas(
+			<Runtime as bifrost_vtoken_minting::Config>::WeightInfo::redeem(),
+		))?;
+
+		// Parse input
+		let mut input = handle.read_input()?;
+		input.expect_arguments(2)?;
+
+		let vtoken_id = Self::read_currency_id(input.read::<Address>()?)?;
+		let vtoken_amount = input.read::<Balance>()?;
+
+		let redeemer = Self::caller(handle);
+
+		RuntimeHelper::<Runtime>::ensure_call_allowed(
+			bifrost_vtoken_minting::Call::<Runtime>::redeem {
+				currency_id: None,
+				v_currency_id: vtoken_id,
+				v_currency_amount: vtoken_amount.into(),
+			},
+		)?;
+
+		log::debug!(target: "evm", "vtoken-minting: redeem redeemer: {:?}, vtoken: {:?}, amount: {:?}", redeemer, vtoken_id, vtoken_amount);
+
+		<bifrost_vtoken_minting::Pallet<Runtime> as VtokenMintingInterface<
+			Runtime::AccountId,
+			CurrencyId,
+			Balance,
+		>>::redeem(redeemer, vtoken_id, vtoken_amount)
+		.map_err(|e| Self::dispatch_error(e.error))?;
+
+		Ok(succeed(EvmDataWriter::new().write(true).build()))
+	}
+
+	fn rebond(handle: &mut impl PrecompileHandle) -> PrecompileResult {
+		handle.record_cost(RuntimeHelper::<Runtime>::weight_to_gas(
+			<Runtime as bifrost_vtoken_minting::Config>::WeightInfo::rebond(),
+		))?;
+
+		// Parse input
+		let mut input = handle.read_input()?;
+		input.expect_arguments(2)?;
+
+		let token_id = Self::read_currency_id(input.read::<Address>()?)?;
+		let token_amount = input.read::<Balance>()?;
+
+		let rebonder = Self::caller(handle);
+
+		RuntimeHelper::<Runtime>::ensure_call_allowed(
+			bifrost_vtoken_minting::Call::<Runtime>::rebond {
+				currency_id: token_id,
+				currency_amount: token_amount.into(),
+			},
+		)?;
+
+		log::debug!(target: "evm", "vtoken-minting: rebond rebonder: {:?}, token: {:?}, amount: {:?}", rebonder, token_id, token_amount);
+
+		<bifrost_vtoken_minting::Pallet<Runtime> as VtokenMintingInterface<
+			Runtime::AccountId,
+			CurrencyId,
+			Balance,
+		>>::rebond(rebonder, token_id, token_amount)
+		.map_err(Self::dispatch_error)?;
+
+		Ok(succeed(EvmDataWriter::new().write(true).build()))
+	}
+
+	fn token_to_vtoken(handle: &mut impl PrecompileHandle) -> PrecompileResult {
+		// TokenPool and the total issuance of the vToken
+		handle.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost().saturating_mul(2))?;
+
+		// Parse input
+		let mut input = handle.read_input()?;
+		input.expect_arguments(2)?;
+
+		let token_id = Self::read_currency_id(input.read::<Address>()?)?;
+		let token_amount = input.read::<Balance>()?;
+		let vtoken_id = token_id
+			.to_vtoken()
+			.map_err(|_| revert("not supported token"))?;
+
+		let vtoken_amount =
+			<bifrost_vtoken_minting::Pallet<Runtime> as VtokenMintingInterface<
+				Runtime::AccountId,
+				CurrencyId,
+				Balance,
+			>>::get_v_currency_amount_by_currency_amount(token_id, vtoken_id, token_amount)
+			.map_err(Self::dispatch_error)?;
+
+		Ok(succeed(EvmDataWriter::new().write(vtoken_amount).build()))
+	}
+
+	fn vtoken_to_token(handle: &mut impl PrecompileHandle) -> PrecompileResult {
+		// TokenPool and the total issuance of the vToken
+		handle.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost().saturating_mul(2))?;
+
+		// Parse input
+		let mut input = handle.read_input()?;
+		input.expect_arguments(2)?;
+
+		let vtoken_id = Self::read_currency_id(input.read::<Address>()?)?;
+		let vtoken_amount = input.read::<Balance>()?;
+		let token_id = vtoken_id
+			.to_token()
+			.map_err(|_| revert("not supported vtoken"))?;
+
+		let token_amount =
+			<bifrost_vtoken_minting::Pallet<Runtime> as VtokenMintingInterface<
+				Runtime::AccountId,
+				CurrencyId,
+				Balance,
+			>>::get_currency_amount_by_v_currency_amount(token_id, vtoken_id, vtoken_amount)
+			.map_err(Self::dispatch_error)?;
+
+		Ok(succeed(EvmDataWriter::new().write(token_amount).build()))
+	}
+
+	fn token_pool(handle: &mut impl PrecompileHandle) -> PrecompileResult {
+		handle.record_cost(RuntimeHelper::<Runtime>::db_read_gas_cost())?;
+
+		// Parse input
+		let mut input = handle.read_input()?;
+		input.expect_arguments(1)?;
+
+		let token_id = Self::read_currency_id(input.read::<Address>()?)?;
+
+		let token_pool = <bifrost_vtoken_minting::Pallet<Runtime> as VtokenMintingInterface<
+			Runtime::AccountId,
+			CurrencyId,
+			Balance,
+		>>::get_token_pool(token_id);
+
+		Ok(succeed(EvmDataWriter::new().write(token_pool).build()))
+	}
+
+	/// Returns the pending unlocks of an account as `(uint32[] unlockIds, uint256[] amounts,
+	/// uint32[] unlockTimeUnits)`.
+	fn unlock_queue(handle: &mut impl PrecompileHandle) -> PrecompileResult {
+		// Parse input
+		let mut input = handle.read_input()?;
+		input.expect_arguments(2)?;
+
+		let who: H160 = input.read::<Address>()?.into();
+		let token_id = Self::read_currency_id(input.read::<Address>()?)?;
+		let who: Runtime::AccountId = ExtendedAddressMapping::into_account_id(who).into();
+
+		let unlock_queue = <bifrost_vtoken_minting::Pallet<Runtime> as VtokenMintingInterface<
+			Runtime::AccountId,
+			CurrencyId,
+			Balance,
+		>>::get_user_unlock_queue(who, token_id);
+
+		// UserUnlockLedger and a TokenUnlockLedger per unlock
+		handle.record_cost(
+			RuntimeHelper::<Runtime>::db_read_gas_cost()
+				.saturating_mul(unlock_queue.len() as u64 + 1),
+		)?;
+
+		let mut unlock_ids = Vec::with_capacity(unlock_queue.len());
+		let mut amounts = Vec::with_capacity(unlock_queue.len());
+		let mut time_units = Vec::with_capacity(unlock_queue.len());
+		for (unlock_id, amount, time_unit) in unlock_queue {
+			unlock_ids.push(unlock_id);
+			amounts.push(amount);
+			time_units.push(time_unit.into_value());
+		}
+
+		Ok(succeed(
+			EvmDataWriter::new()
+				.write(unlock_ids)
+				.write(amounts)
+				.write(time_units)
+				.build(),
+		))
+	}
+
+	fn read_currency_id(address: Address) -> EvmResult<CurrencyId> {
+		BifrostErc20Mapping::decode_evm_address(address.into())
+			.ok_or_else(|| revert("invalid currency id"))
+	}
+
+	fn caller(handle: &impl PrecompileHandle) -> Runtime::AccountId {
+		ExtendedAddressMapping::into_account_id(handle.context().caller).into()
+	}
+
+	fn dispatch_error(e: DispatchError) -> pallet_evm::PrecompileFailure {
+		revert(Into::<&str>::into(e))
+	}
+}
@@ -139,7 +139,7 @@ use xcm_executor::{traits::QueryHandler, XcmExecutor};
 
 pub mod governance;
 use crate::xcm_config::XcmRouter;
-use bifrost_primitives::OraclePriceProvider;
+use bifrost_primitives::{OraclePriceProvider, SwapRoute, SwapRouter as _};
 use frame_support::weights::WeightToFee as _;
 use governance::{
 	custom_origins, CoreAdminOrCouncil, LiquidStaking, SALPAdmin, Spender, TechAdmin,
@@ -1128,6 +1128,7 @@ parameter_types! {
 	pub const MaxTypeEntryPerBlock: u32 = 10;
 	pub const MaxRefundPerBlock: u32 = 10;
 	pub const MaxLengthLimit: u32 = 500;
+	pub const XcmQueryTimeout: BlockNumber = 1000;
 }
 
 pub struct SubstrateResponseManager;
@@ -1187,6 +1188,7 @@ impl bifrost_slp::Config for Runtime {
 	type AssetIdMaps = AssetIdMaps<Runtime>;
 	type TreasuryAccount = BifrostTreasuryAccount;
 	type BlockNumberProvider = System;
+	type XcmQueryTimeout = XcmQueryTimeout;
 }
 
 parameter_types! {
@@ -1206,6 +1208,8 @@ impl bifrost_vstoken_conversion::Config for Runtime {
 
 parameter_types! {
 	pub const WhitelistMaximumLimit: u32 = 10;
+	pub const FarmingMaxPositionLock: BlockNumber = 365 * DAYS;
+	pub const FarmingMaxPositionBoost: Perbill = Perbill::from_percent(100);
 }
 
 impl bifrost_farming::Config for Runtime {
@@ -1223,6 +1227,8 @@ impl bifrost_farming::Config for Runtime {
 	type WhitelistMaximumLimit = WhitelistMaximumLimit;
 	type GaugeRewardIssuer = FarmingGaugeRewardIssuerPalletId;
 	type BlockNumberProvider = System;
+	type MaxPositionLock = FarmingMaxPositionLock;
+	type MaxPositionBoost = FarmingMaxPositionBoost;
 }
 
 parameter_types! {
@@ -1289,6 +1295,10 @@ impl bifrost_stable_asset::traits::ValidateAssetId<CurrencyId> for EnsurePoolAss
 	}
 }
 
+parameter_types! {
+	pub const StablePriceObservationPeriod: BlockNumber = HOURS;
+}
+
 /// Configure the pallet bifrost_stable_asset in pallets/bifrost_stable_asset.
 impl bifrost_stable_asset::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
@@ -1305,6 +1315,8 @@ impl bifrost_stable_asset::Config for Runtime {
 	type ListingOrigin = TechAdminOrCouncil;
 	type EnsurePoolAssetId = EnsurePoolAssetId;
 	type BlockNumberProvider = System;
+	type PriceObservationPeriod = StablePriceObservationPeriod;
+	type MaxPriceObservations = ConstU32<24>;
 }
 
 impl bifrost_stable_pool::Config for Runtime {
@@ -1318,6 +1330,23 @@ impl bifrost_stable_pool::Config for Runtime {
 	type CurrencyIdRegister = AssetIdMaps<Runtime>;
 }
 
+parameter_types! {
+	pub const SwapRouterMaxHops: u32 = 3;
+	pub const SwapRouterMaxRouteAssets: u32 = 8;
+}
+
+impl bifrost_swap_router::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type MultiCurrency = Currencies;
+	type ControlOrigin = TechAdminOrCouncil;
+	type WeightInfo = weights::bifrost_swap_router::BifrostWeight<Runtime>;
+	type DexOperator = ZenlinkProtocol;
+	type StablePoolHandler = StablePool;
+	type ParachainId = ParachainInfo;
+	type MaxHops = SwapRouterMaxHops;
+	type MaxRouteAssets = SwapRouterMaxRouteAssets;
+}
+
 parameter_types! {
 	pub const QueryTimeout: BlockNumber = 100;
 	pub const ReferendumCheckInterval: BlockNumber = 300;
@@ -1533,6 +1562,11 @@ impl pallet_prices::Config for Runtime {
 	type RelayCurrency = RelayCurrencyId;
 	type CurrencyIdConvert = AssetIdMaps<Runtime>;
 	type Assets = Currencies;
+	type VtokenMinting = VtokenMinting;
+	type LendMarket = LendMarket;
+	type StableLpToken = StableAsset;
+	type UnixTime = Timestamp;
+	type StableTwap = StableAsset;
 	type WeightInfo = pallet_prices::weights::SubstrateWeight<Runtime>;
 }
 
@@ -1549,6 +1583,7 @@ impl lend_market::Config for Runtime {
 	type LiquidationFreeAssetId = RelayCurrencyId;
 	type MaxLengthLimit = MaxLengthLimit;
 	type BlockNumberProvider = System;
+	type VTokenSupplyProvider = VtokenMinting;
 }
 
 parameter_types! {
@@ -1618,6 +1653,7 @@ impl bifrost_buy_back::Config for Runtime {
 	type CurrencyIdRegister = AssetIdMaps<Runtime>;
 	type BbBNC = BbBNC;
 	type BlockNumberProvider = System;
+	type OraclePriceProvider = Prices;
 }
 
 impl bifrost_slp_v2::Config for Runtime {
@@ -1836,6 +1872,7 @@ construct_runtime! {
 		CloudsConvert: bifrost_clouds_convert = 137,
 		BuyBack: bifrost_buy_back = 138,
 		SlpV2: bifrost_slp_v2 = 139,
+		SwapRouter: bifrost_swap_router = 141,
 	}
 }
 
@@ -1928,6 +1965,10 @@ pub mod migrations {
 		// permanent migration, do not remove
 		pallet_xcm::migration::MigrateToLatestXcmVersion<Runtime>,
 		bifrost_channel_commission::migrations::v1::MigrateToV1<Runtime>,
+		lend_market::migrations::v2::MigrateToV2<Runtime>,
+		bifrost_slpx::migration::v3::MigrateToV3<Runtime>,
+		bifrost_slp_v2::migration::v1::MigrateToV1<Runtime>,
+		bifrost_buy_back::migration::v1::MigrateToV1<Runtime>,
 	);
 }
 
@@ -2012,7 +2053,9 @@ mod benches {
 		[bifrost_xcm_interface, XcmInterface]
 		[bifrost_farming, Farming]
 		[bifrost_clouds_convert, CloudsConvert]
+		[bifrost_swap_router, SwapRouter]
 		[pallet_evm_accounts, EVMAccounts]
+		[pallet_prices, Prices]
 	);
 }
 
@@ -2554,6 +2597,14 @@ impl fp_rpc::EthereumRuntimeRPCApi<Block> for Runtime {
 		fn get_gauge_rewards(who: AccountId, pid: PoolId) -> Vec<(CurrencyId, Balance)> {
 			Farming::get_gauge_rewards(&who, pid).unwrap_or(Vec::new())
 		}
+
+		fn get_user_positions(who: AccountId) -> Vec<(u64, PoolId, Balance, Vec<(CurrencyId, Balance)>)> {
+			Farming::get_user_positions(&who)
+		}
+
+		fn get_farming_boost(who: AccountId, pid: PoolId) -> u32 {
+			Farming::get_farming_boost(&who, pid).deconstruct()
+		}
 	}
 
 	impl bb_bnc_rpc_runtime_api::BbBNCRuntimeApi<Block, AccountId> for Runtime {
@@ -2578,6 +2629,24 @@ impl fp_rpc::EthereumRuntimeRPCApi<Block> for Runtime {
 		}
 	}
 
+	impl bifrost_swap_router_rpc_runtime_api::SwapRouterRuntimeApi<Block> for Runtime {
+		fn quote_exact_in(
+			currency_in: CurrencyId,
+			currency_out: CurrencyId,
+			amount_in: Balance,
+		) -> Option<SwapRoute<CurrencyId, Balance>> {
+			SwapRouter::quote_exact_in(currency_in, currency_out, amount_in).ok()
+		}
+
+		fn quote_exact_out(
+			currency_in: CurrencyId,
+			currency_out: CurrencyId,
+			amount_out: Balance,
+		) -> Option<SwapRoute<CurrencyId, Balance>> {
+			SwapRouter::quote_exact_out(currency_in, currency_out, amount_out).ok()
+		}
+	}
+
 	impl lend_market_rpc_runtime_api::LendMarketApi<Block, AccountId, Balance> for Runtime {
 		fn get_account_liquidity(account: AccountId) -> Result<(Liquidity, Shortfall, Liquidity, Shortfall), DispatchError> {
 			LendMarket::get_account_liquidity(&account)
@@ -2590,6 +2659,16 @@ impl fp_rpc::EthereumRuntimeRPCApi<Block> for Runtime {
 		fn get_liquidation_threshold_liquidity(account: AccountId) -> Result<(Liquidity, Shortfall, Liquidity, Shortfall), DispatchError> {
 			LendMarket::get_account_liquidation_threshold_liquidity(&account)
 		}
+
+		fn get_liquidatable_accounts(start_key: Option<Vec<u8>>, limit: u32) -> (Vec<AccountId>, Option<Vec<u8>>) {
+			LendMarket::get_liquidatable_accounts(start_key, limit)
+		}
+	}
+
+	impl leverage_staking_rpc_runtime_api::LeverageStakingApi<Block, AccountId, Balance> for Runtime {
+		fn preview_flash_loan_deposit(account: AccountId, asset_id: CurrencyId, rate: Rate) -> Result<(Balance, Balance, sp_runtime::FixedU128), DispatchError> {
+			LeverageStaking::preview_flash_loan_deposit(&account, asset_id, rate)
+		}
 	}
 
 	impl bifrost_stable_pool_rpc_runtime_api::StablePoolRuntimeApi<Block> for Runtime {
@@ -2620,6 +2699,22 @@ impl fp_rpc::EthereumRuntimeRPCApi<Block> for Runtime {
 		}
 	}
 
+	impl bifrost_slp_rpc_runtime_api::SlpRuntimeApi<Block, bifrost_slp::LedgerUpdateEntry<Balance>, bifrost_slp::ValidatorsByDelegatorUpdateEntry, BlockNumber> for Runtime {
+		fn get_pending_ledger_queries() -> Vec<(bifrost_slp::QueryId, bifrost_slp::LedgerUpdateEntry<Balance>, BlockNumber)> {
+			Slp::get_pending_ledger_queries()
+		}
+
+		fn get_pending_validators_queries() -> Vec<(bifrost_slp::QueryId, bifrost_slp::ValidatorsByDelegatorUpdateEntry, BlockNumber)> {
+			Slp::get_pending_validators_queries()
+		}
+	}
+
+	impl bifrost_slpx_rpc_runtime_api::SlpxRuntimeApi<Block, AccountId, bifrost_slpx::types::Order<AccountId, CurrencyId, Balance, BlockNumber>> for Runtime {
+		fn get_pending_orders(account: AccountId) -> Vec<bifrost_slpx::types::Order<AccountId, CurrencyId, Balance, BlockNumber>> {
+			Slpx::get_pending_orders(account)
+		}
+	}
+
 	#[cfg(feature = "runtime-benchmarks")]
 	impl frame_benchmarking::Benchmark<Block> for Runtime {
 		fn benchmark_metadata(extra: bool) -> (
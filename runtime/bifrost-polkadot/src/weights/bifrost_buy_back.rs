@@ -127,4 +127,18 @@ impl<T: frame_system::Config> bifrost_buy_back::WeightInfo for BifrostWeight<T>
 			.saturating_add(T::DbWeight::get().reads(5))
 			.saturating_add(T::DbWeight::get().writes(1))
 	}
+	/// Storage: `AssetRegistry::CurrencyMetadatas` (r:1 w:0)
+	/// Proof: `AssetRegistry::CurrencyMetadatas` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `BuyBack::SwapChunkSize` (r:0 w:1)
+	/// Proof: `BuyBack::SwapChunkSize` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn set_swap_chunk_size() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `25`
+		//  Estimated: `1510`
+		// Minimum execution time: 11_021_000 picoseconds.
+		Weight::from_parts(11_392_000, 0)
+			.saturating_add(Weight::from_parts(0, 1510))
+			.saturating_add(T::DbWeight::get().reads(1))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
 }
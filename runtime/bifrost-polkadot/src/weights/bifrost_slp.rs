@@ -798,4 +798,68 @@ impl<T: frame_system::Config> bifrost_slp::WeightInfo for BifrostWeight<T> {
 			.saturating_add(RocksDbWeight::get().reads(1_u64))
 			.saturating_add(RocksDbWeight::get().writes(1_u64))
 	}
+	// Storage: Slp OperateOrigins (r:1 w:0)
+	// Proof Skipped: Slp OperateOrigins (max_values: None, max_size: None, mode: Measured)
+	// Storage: Slp CurrencyTuneExchangeRateLimit (r:1 w:0)
+	// Proof Skipped: Slp CurrencyTuneExchangeRateLimit (max_values: None, max_size: None, mode: Measured)
+	// Storage: VtokenMinting TokenPool (r:1 w:1)
+	// Proof: VtokenMinting TokenPool (max_values: None, max_size: Some(38), added: 2513, mode: MaxEncodedLen)
+	// Storage: VtokenMinting OngoingTimeUnit (r:1 w:0)
+	// Proof: VtokenMinting OngoingTimeUnit (max_values: None, max_size: Some(27), added: 2502, mode: MaxEncodedLen)
+	// Storage: Slp CurrencyLatestTuneRecord (r:1 w:1)
+	// Proof Skipped: Slp CurrencyLatestTuneRecord (max_values: None, max_size: None, mode: Measured)
+	// Storage: Slp ValidatorsByDelegator (r:1 w:0)
+	// Proof Skipped: Slp ValidatorsByDelegator (max_values: None, max_size: None, mode: Measured)
+	// Storage: Slp DelegatorLedgers (r:1 w:1)
+	// Proof Skipped: Slp DelegatorLedgers (max_values: None, max_size: None, mode: Measured)
+	// Storage: Slp ValidatorSlashHistory (r:1 w:1)
+	// Proof Skipped: Slp ValidatorSlashHistory (max_values: None, max_size: None, mode: Measured)
+	// Storage: Slp ValidatorBoostList (r:1 w:1)
+	// Proof Skipped: Slp ValidatorBoostList (max_values: None, max_size: None, mode: Measured)
+	fn report_slash() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1915`
+		//  Estimated: `5380`
+		// Minimum execution time: 86_412_000 picoseconds.
+		Weight::from_parts(89_105_000, 5380)
+			.saturating_add(T::DbWeight::get().reads(9))
+			.saturating_add(T::DbWeight::get().writes(5))
+	}
+	// Storage: Slp OperateOrigins (r:1 w:0)
+	// Proof Skipped: Slp OperateOrigins (max_values: None, max_size: None, mode: Measured)
+	// Storage: Slp Validators (r:1 w:0)
+	// Proof Skipped: Slp Validators (max_values: None, max_size: None, mode: Measured)
+	// Storage: Slp ValidatorsMetrics (r:0 w:1)
+	// Proof Skipped: Slp ValidatorsMetrics (max_values: None, max_size: None, mode: Measured)
+	fn set_validator_metrics() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `287`
+		//  Estimated: `3752`
+		// Minimum execution time: 24_108_000 picoseconds.
+		Weight::from_parts(25_021_000, 3752)
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	// Storage: Slp ValidatorConcentrationCap (r:0 w:1)
+	// Proof Skipped: Slp ValidatorConcentrationCap (max_values: None, max_size: None, mode: Measured)
+	fn set_validator_concentration_cap() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 13_240_000 picoseconds.
+		Weight::from_parts(13_862_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	/// The range of component `n` is `[1, 100]`.
+	fn rebalance_validators(n: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1650 + n * (160 ±0)`
+		//  Estimated: `7442 + n * (2635 ±0)`
+		Weight::from_parts(118_460_000, 7442)
+			.saturating_add(Weight::from_parts(10_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(14))
+			.saturating_add(T::DbWeight::get().reads((2_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes(4))
+			.saturating_add(Weight::from_parts(0, 2635).saturating_mul(n.into()))
+	}
 }
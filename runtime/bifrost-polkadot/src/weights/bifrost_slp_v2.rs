@@ -348,4 +348,116 @@ impl<T: frame_system::Config> bifrost_slp_v2::WeightInfo for BifrostWeight<T> {
             .saturating_add(T::DbWeight::get().reads(6))
             .saturating_add(T::DbWeight::get().writes(3))
     }
-}
\ No newline at end of file
+    // Storage: `SlpV2::DelegatorIndexByStakingProtocolAndDelegator` (r:1 w:0)
+    // Proof: `SlpV2::DelegatorIndexByStakingProtocolAndDelegator` (`max_values`: None, `max_size`: Some(68), added: 2543, mode: `MaxEncodedLen`)
+    // Storage: `SlpV2::LedgerByStakingProtocolAndDelegator` (r:1 w:0)
+    // Proof: `SlpV2::LedgerByStakingProtocolAndDelegator` (`max_values`: None, `max_size`: Some(7935), added: 10410, mode: `MaxEncodedLen`)
+    // Storage: `SlpV2::ValidatorsByStakingProtocolAndDelegator` (r:1 w:0)
+    // Proof: `SlpV2::ValidatorsByStakingProtocolAndDelegator` (`max_values`: None, `max_size`: Some(3350), added: 5825, mode: `MaxEncodedLen`)
+    // Storage: `System::Number` (r:1 w:0)
+    // Proof: `System::Number` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+    // Storage: `PolkadotXcm::QueryCounter` (r:1 w:1)
+    // Proof: `PolkadotXcm::QueryCounter` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+    // Storage: `SlpV2::ConfigurationByStakingProtocol` (r:1 w:0)
+    // Proof: `SlpV2::ConfigurationByStakingProtocol` (`max_values`: None, `max_size`: Some(102), added: 2577, mode: `MaxEncodedLen`)
+    // Storage: `System::ExecutionPhase` (r:1 w:0)
+    // Proof: `System::ExecutionPhase` (`max_values`: Some(1), `max_size`: Some(5), added: 500, mode: `MaxEncodedLen`)
+    // Storage: `System::EventCount` (r:1 w:1)
+    // Proof: `System::EventCount` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+    // Storage: `System::Events` (r:1 w:1)
+    // Proof: `System::Events` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+    // Storage: `SlpV2::PendingStatusByQueryId` (r:0 w:1)
+    // Proof: `SlpV2::PendingStatusByQueryId` (`max_values`: None, `max_size`: Some(75), added: 2550, mode: `MaxEncodedLen`)
+    // Storage: `PolkadotXcm::Queries` (r:0 w:1)
+    // Proof: `PolkadotXcm::Queries` (`max_values`: None, `max_size`: None, mode: `Measured`)
+    fn parachain_staking() -> Weight {
+        // Proof Size summary in bytes:
+        //  Measured:  `1021`
+        //  Estimated: `11400`
+        // Minimum execution time: 79_532 nanoseconds.
+        Weight::from_parts(80_814_000, 11400)
+            .saturating_add(T::DbWeight::get().reads(9))
+            .saturating_add(T::DbWeight::get().writes(5))
+    }
+    // Storage: `SlpV2::PendingStatusByQueryId` (r:1 w:1)
+    // Proof: `SlpV2::PendingStatusByQueryId` (`max_values`: None, `max_size`: Some(75), added: 2550, mode: `MaxEncodedLen`)
+    // Storage: `SlpV2::LedgerByStakingProtocolAndDelegator` (r:1 w:1)
+    // Proof: `SlpV2::LedgerByStakingProtocolAndDelegator` (`max_values`: None, `max_size`: Some(7935), added: 10410, mode: `MaxEncodedLen`)
+    // Storage: `VtokenMinting::OngoingTimeUnit` (r:1 w:0)
+    // Proof: `VtokenMinting::OngoingTimeUnit` (`max_values`: None, `max_size`: Some(27), added: 2502, mode: `MaxEncodedLen`)
+    // Storage: `SlpV2::ConfigurationByStakingProtocol` (r:1 w:0)
+    // Proof: `SlpV2::ConfigurationByStakingProtocol` (`max_values`: None, `max_size`: Some(102), added: 2577, mode: `MaxEncodedLen`)
+    // Storage: `System::Number` (r:1 w:0)
+    // Proof: `System::Number` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+    // Storage: `System::ExecutionPhase` (r:1 w:0)
+    // Proof: `System::ExecutionPhase` (`max_values`: Some(1), `max_size`: Some(5), added: 500, mode: `MaxEncodedLen`)
+    // Storage: `System::EventCount` (r:1 w:1)
+    // Proof: `System::EventCount` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+    // Storage: `System::Events` (r:1 w:1)
+    // Proof: `System::Events` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+    fn notify_parachain_staking() -> Weight {
+        // Proof Size summary in bytes:
+        //  Measured:  `752`
+        //  Estimated: `11400`
+        // Minimum execution time: 46_709 nanoseconds.
+        Weight::from_parts(47_535_000, 11400)
+            .saturating_add(T::DbWeight::get().reads(8))
+            .saturating_add(T::DbWeight::get().writes(4))
+    }
+    // Storage: `SlpV2::DelegatorIndexByStakingProtocolAndDelegator` (r:1 w:0)
+    // Proof: `SlpV2::DelegatorIndexByStakingProtocolAndDelegator` (`max_values`: None, `max_size`: Some(68), added: 2543, mode: `MaxEncodedLen`)
+    // Storage: `SlpV2::DelegatorByStakingProtocolAndDelegatorIndex` (r:1 w:0)
+    // Proof: `SlpV2::DelegatorByStakingProtocolAndDelegatorIndex` (`max_values`: None, `max_size`: Some(68), added: 2543, mode: `MaxEncodedLen`)
+    // Storage: `SlpV2::LedgerByStakingProtocolAndDelegator` (r:1 w:0)
+    // Proof: `SlpV2::LedgerByStakingProtocolAndDelegator` (`max_values`: None, `max_size`: Some(7935), added: 10410, mode: `MaxEncodedLen`)
+    // Storage: `System::Number` (r:1 w:0)
+    // Proof: `System::Number` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+    // Storage: `PolkadotXcm::QueryCounter` (r:1 w:1)
+    // Proof: `PolkadotXcm::QueryCounter` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+    // Storage: `SlpV2::ConfigurationByStakingProtocol` (r:1 w:0)
+    // Proof: `SlpV2::ConfigurationByStakingProtocol` (`max_values`: None, `max_size`: Some(102), added: 2577, mode: `MaxEncodedLen`)
+    // Storage: `System::ExecutionPhase` (r:1 w:0)
+    // Proof: `System::ExecutionPhase` (`max_values`: Some(1), `max_size`: Some(5), added: 500, mode: `MaxEncodedLen`)
+    // Storage: `System::EventCount` (r:1 w:1)
+    // Proof: `System::EventCount` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+    // Storage: `System::Events` (r:1 w:1)
+    // Proof: `System::Events` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+    // Storage: `SlpV2::PendingStatusByQueryId` (r:0 w:1)
+    // Proof: `SlpV2::PendingStatusByQueryId` (`max_values`: None, `max_size`: Some(75), added: 2550, mode: `MaxEncodedLen`)
+    // Storage: `PolkadotXcm::Queries` (r:0 w:1)
+    // Proof: `PolkadotXcm::Queries` (`max_values`: None, `max_size`: None, mode: `Measured`)
+    fn nomination_pools() -> Weight {
+        // Proof Size summary in bytes:
+        //  Measured:  `893`
+        //  Estimated: `11400`
+        // Minimum execution time: 73_418 nanoseconds.
+        Weight::from_parts(74_682_000, 11400)
+            .saturating_add(T::DbWeight::get().reads(9))
+            .saturating_add(T::DbWeight::get().writes(5))
+    }
+    // Storage: `SlpV2::PendingStatusByQueryId` (r:1 w:1)
+    // Proof: `SlpV2::PendingStatusByQueryId` (`max_values`: None, `max_size`: Some(75), added: 2550, mode: `MaxEncodedLen`)
+    // Storage: `SlpV2::LedgerByStakingProtocolAndDelegator` (r:1 w:1)
+    // Proof: `SlpV2::LedgerByStakingProtocolAndDelegator` (`max_values`: None, `max_size`: Some(7935), added: 10410, mode: `MaxEncodedLen`)
+    // Storage: `VtokenMinting::OngoingTimeUnit` (r:1 w:0)
+    // Proof: `VtokenMinting::OngoingTimeUnit` (`max_values`: None, `max_size`: Some(27), added: 2502, mode: `MaxEncodedLen`)
+    // Storage: `SlpV2::ConfigurationByStakingProtocol` (r:1 w:0)
+    // Proof: `SlpV2::ConfigurationByStakingProtocol` (`max_values`: None, `max_size`: Some(102), added: 2577, mode: `MaxEncodedLen`)
+    // Storage: `System::Number` (r:1 w:0)
+    // Proof: `System::Number` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+    // Storage: `System::ExecutionPhase` (r:1 w:0)
+    // Proof: `System::ExecutionPhase` (`max_values`: Some(1), `max_size`: Some(5), added: 500, mode: `MaxEncodedLen`)
+    // Storage: `System::EventCount` (r:1 w:1)
+    // Proof: `System::EventCount` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+    // Storage: `System::Events` (r:1 w:1)
+    // Proof: `System::Events` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+    fn notify_nomination_pools() -> Weight {
+        // Proof Size summary in bytes:
+        //  Measured:  `655`
+        //  Estimated: `11400`
+        // Minimum execution time: 44_927 nanoseconds.
+        Weight::from_parts(45_801_000, 11400)
+            .saturating_add(T::DbWeight::get().reads(8))
+            .saturating_add(T::DbWeight::get().writes(4))
+    }
+}
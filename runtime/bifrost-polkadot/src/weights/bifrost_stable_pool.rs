@@ -118,14 +118,18 @@ impl<T: frame_system::Config> bifrost_stable_pool::WeightInfo for BifrostWeight<
 	// Proof Skipped: AssetRegistry CurrencyMetadatas (max_values: None, max_size: None, mode: Measured)
 	// Storage: Tokens TotalIssuance (r:1 w:1)
 	// Proof: Tokens TotalIssuance (max_values: None, max_size: Some(38), added: 2513, mode: MaxEncodedLen)
+	// Storage: StableAsset PriceAccumulators (r:1 w:1)
+	// Proof Skipped: StableAsset PriceAccumulators (max_values: None, max_size: None, mode: Measured)
+	// Storage: StableAsset PriceObservations (r:1 w:1)
+	// Proof Skipped: StableAsset PriceObservations (max_values: None, max_size: None, mode: Measured)
 	fn add_liquidity() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `2557`
 		//  Estimated: `8769`
 		// Minimum execution time: 427_183 nanoseconds.
 		Weight::from_parts(438_909_000, 8769)
-			.saturating_add(T::DbWeight::get().reads(11))
-			.saturating_add(T::DbWeight::get().writes(7))
+			.saturating_add(T::DbWeight::get().reads(13))
+			.saturating_add(T::DbWeight::get().writes(9))
 	}
 	// Storage: StableAsset Pools (r:1 w:1)
 	// Proof Skipped: StableAsset Pools (max_values: None, max_size: None, mode: Measured)
@@ -139,14 +143,18 @@ impl<T: frame_system::Config> bifrost_stable_pool::WeightInfo for BifrostWeight<
 	// Proof Skipped: AssetRegistry CurrencyMetadatas (max_values: None, max_size: None, mode: Measured)
 	// Storage: Tokens TotalIssuance (r:1 w:1)
 	// Proof: Tokens TotalIssuance (max_values: None, max_size: Some(38), added: 2513, mode: MaxEncodedLen)
+	// Storage: StableAsset PriceAccumulators (r:1 w:1)
+	// Proof Skipped: StableAsset PriceAccumulators (max_values: None, max_size: None, mode: Measured)
+	// Storage: StableAsset PriceObservations (r:1 w:1)
+	// Proof Skipped: StableAsset PriceObservations (max_values: None, max_size: None, mode: Measured)
 	fn swap() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `2239`
 		//  Estimated: `6196`
 		// Minimum execution time: 375_244 nanoseconds.
 		Weight::from_parts(385_475_000, 6196)
-			.saturating_add(T::DbWeight::get().reads(7))
-			.saturating_add(T::DbWeight::get().writes(5))
+			.saturating_add(T::DbWeight::get().reads(9))
+			.saturating_add(T::DbWeight::get().writes(7))
 	}
 	// Storage: StableAsset Pools (r:1 w:1)
 	// Proof Skipped: StableAsset Pools (max_values: None, max_size: None, mode: Measured)
@@ -160,14 +168,18 @@ impl<T: frame_system::Config> bifrost_stable_pool::WeightInfo for BifrostWeight<
 	// Proof Skipped: AssetRegistry CurrencyMetadatas (max_values: None, max_size: None, mode: Measured)
 	// Storage: Tokens TotalIssuance (r:1 w:1)
 	// Proof: Tokens TotalIssuance (max_values: None, max_size: Some(38), added: 2513, mode: MaxEncodedLen)
+	// Storage: StableAsset PriceAccumulators (r:1 w:1)
+	// Proof Skipped: StableAsset PriceAccumulators (max_values: None, max_size: None, mode: Measured)
+	// Storage: StableAsset PriceObservations (r:1 w:1)
+	// Proof Skipped: StableAsset PriceObservations (max_values: None, max_size: None, mode: Measured)
 	fn redeem_proportion() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `2239`
 		//  Estimated: `6196`
 		// Minimum execution time: 359_499 nanoseconds.
 		Weight::from_parts(370_566_000, 6196)
-			.saturating_add(T::DbWeight::get().reads(7))
-			.saturating_add(T::DbWeight::get().writes(5))
+			.saturating_add(T::DbWeight::get().reads(9))
+			.saturating_add(T::DbWeight::get().writes(7))
 	}
 	// Storage: StableAsset Pools (r:1 w:1)
 	// Proof Skipped: StableAsset Pools (max_values: None, max_size: None, mode: Measured)
@@ -181,14 +193,18 @@ impl<T: frame_system::Config> bifrost_stable_pool::WeightInfo for BifrostWeight<
 	// Proof Skipped: AssetRegistry CurrencyMetadatas (max_values: None, max_size: None, mode: Measured)
 	// Storage: Tokens TotalIssuance (r:1 w:1)
 	// Proof: Tokens TotalIssuance (max_values: None, max_size: Some(38), added: 2513, mode: MaxEncodedLen)
+	// Storage: StableAsset PriceAccumulators (r:1 w:1)
+	// Proof Skipped: StableAsset PriceAccumulators (max_values: None, max_size: None, mode: Measured)
+	// Storage: StableAsset PriceObservations (r:1 w:1)
+	// Proof Skipped: StableAsset PriceObservations (max_values: None, max_size: None, mode: Measured)
 	fn redeem_single() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `2239`
 		//  Estimated: `6196`
 		// Minimum execution time: 304_691 nanoseconds.
 		Weight::from_parts(312_518_000, 6196)
-			.saturating_add(T::DbWeight::get().reads(7))
-			.saturating_add(T::DbWeight::get().writes(5))
+			.saturating_add(T::DbWeight::get().reads(9))
+			.saturating_add(T::DbWeight::get().writes(7))
 	}
 	// Storage: StableAsset Pools (r:1 w:1)
 	// Proof Skipped: StableAsset Pools (max_values: None, max_size: None, mode: Measured)
@@ -202,14 +218,18 @@ impl<T: frame_system::Config> bifrost_stable_pool::WeightInfo for BifrostWeight<
 	// Proof Skipped: AssetRegistry CurrencyMetadatas (max_values: None, max_size: None, mode: Measured)
 	// Storage: Tokens TotalIssuance (r:1 w:1)
 	// Proof: Tokens TotalIssuance (max_values: None, max_size: Some(38), added: 2513, mode: MaxEncodedLen)
+	// Storage: StableAsset PriceAccumulators (r:1 w:1)
+	// Proof Skipped: StableAsset PriceAccumulators (max_values: None, max_size: None, mode: Measured)
+	// Storage: StableAsset PriceObservations (r:1 w:1)
+	// Proof Skipped: StableAsset PriceObservations (max_values: None, max_size: None, mode: Measured)
 	fn redeem_multi() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `2239`
 		//  Estimated: `6196`
 		// Minimum execution time: 363_000 nanoseconds.
 		Weight::from_parts(367_057_000, 6196)
-			.saturating_add(T::DbWeight::get().reads(7))
-			.saturating_add(T::DbWeight::get().writes(5))
+			.saturating_add(T::DbWeight::get().reads(9))
+			.saturating_add(T::DbWeight::get().writes(7))
 	}
 	// Storage: StableAsset Pools (r:1 w:1)
 	// Proof Skipped: StableAsset Pools (max_values: None, max_size: None, mode: Measured)
@@ -323,4 +323,15 @@ impl<T: frame_system::Config> bifrost_vtoken_minting::WeightInfo for BifrostWeig
 			.saturating_add(RocksDbWeight::get().reads(1_u64))
 			.saturating_add(RocksDbWeight::get().writes(1_u64))
 	}
+
+	// Storage: VtokenMinting RedeemDestinations (r:0 w:1)
+	// Proof: VtokenMinting RedeemDestinations (max_values: None, max_size: Some(66), added: 2541, mode: MaxEncodedLen)
+	fn set_redeem_destination() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 9_318_000 picoseconds.
+		Weight::from_parts(9_710_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
 }
@@ -35,6 +35,7 @@ pub mod bifrost_slp;
 pub mod bifrost_slp_v2;
 pub mod bifrost_slpx;
 pub mod bifrost_stable_pool;
+pub mod bifrost_swap_router;
 pub mod bifrost_system_staking;
 pub mod bifrost_vesting;
 pub mod bifrost_vstoken_conversion;